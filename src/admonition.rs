@@ -0,0 +1,280 @@
+//! Admonition (callout) syntaxes and conversions between them.
+//!
+//! Markdown renderers disagree on how to write a callout. pave recognizes
+//! the three common forms:
+//!
+//! ```markdown
+//! > [!NOTE]
+//! > GitHub alerts.
+//!
+//! > **Note:** Bold labels in a blockquote.
+//!
+//! :::note
+//! Directive fences, as in Docusaurus and VitePress.
+//! :::
+//! ```
+//!
+//! Only the kinds all three agree on are recognized: note, tip, important,
+//! warning, and caution. `pave lint` uses this to flag callouts that don't
+//! use the repository's style and to rewrite them.
+
+use regex::Regex;
+
+use crate::config::AdmonitionStyle;
+use crate::parser::CodeBlockTracker;
+
+/// Callout kinds every supported syntax can express.
+const KINDS: &str = "note|tip|important|warning|caution";
+
+/// Opening-line patterns for each syntax.
+struct Patterns {
+    github: Regex,
+    blockquote: Regex,
+    directive: Regex,
+}
+
+impl Patterns {
+    fn new() -> Self {
+        Self {
+            github: Regex::new(&format!(r"(?i)^>\s*\[!({})\]\s*$", KINDS)).unwrap(),
+            blockquote: Regex::new(&format!(r"(?i)^>\s*\*\*({})(?::\*\*|\*\*:)\s*(.*)$", KINDS))
+                .unwrap(),
+            directive: Regex::new(&format!(r"(?i)^:::\s*({})\b\s*(.*)$", KINDS)).unwrap(),
+        }
+    }
+}
+
+/// A callout found in a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Admonition {
+    /// Syntax the callout is written in.
+    pub style: AdmonitionStyle,
+    /// Lowercase kind, such as `note` or `warning`.
+    pub kind: String,
+    /// First line of the callout (0-indexed).
+    pub start: usize,
+    /// Line after the callout (0-indexed, exclusive).
+    pub end: usize,
+    /// Body lines, without blockquote markers.
+    pub body: Vec<String>,
+    /// Custom title of a directive (`:::note Title`), which other syntaxes
+    /// can't express.
+    pub title: Option<String>,
+}
+
+impl Admonition {
+    /// How this callout's kind is opened in `style`, such as `> [!WARNING]`.
+    pub fn opening(&self, style: AdmonitionStyle) -> String {
+        match style {
+            AdmonitionStyle::Github => format!("> [!{}]", self.kind.to_uppercase()),
+            AdmonitionStyle::Blockquote => format!("> **{}:**", capitalize(&self.kind)),
+            AdmonitionStyle::Directive => format!(":::{}", self.kind),
+        }
+    }
+
+    /// Render this callout in another style.
+    ///
+    /// Returns `None` if the callout has a title the style can't express.
+    pub fn render(&self, style: AdmonitionStyle) -> Option<Vec<String>> {
+        if self.title.is_some() && style != AdmonitionStyle::Directive {
+            return None;
+        }
+        let quote = |line: &str| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        };
+
+        let mut lines = Vec::new();
+        match style {
+            AdmonitionStyle::Github => {
+                lines.push(format!("> [!{}]", self.kind.to_uppercase()));
+                lines.extend(self.body.iter().map(|line| quote(line)));
+            }
+            AdmonitionStyle::Blockquote => {
+                let label = format!("**{}:**", capitalize(&self.kind));
+                let mut body = self.body.iter();
+                match body.next() {
+                    Some(first) if !first.is_empty() => {
+                        lines.push(format!("> {} {}", label, first))
+                    }
+                    Some(_) | None => lines.push(format!("> {}", label)),
+                }
+                lines.extend(body.map(|line| quote(line)));
+            }
+            AdmonitionStyle::Directive => {
+                match &self.title {
+                    Some(title) => lines.push(format!(":::{} {}", self.kind, title)),
+                    None => lines.push(format!(":::{}", self.kind)),
+                }
+                lines.extend(self.body.iter().cloned());
+                lines.push(":::".to_string());
+            }
+        }
+        Some(lines)
+    }
+}
+
+/// Find the callouts in a document, skipping code blocks.
+pub fn find_admonitions(lines: &[&str]) -> Vec<Admonition> {
+    let patterns = Patterns::new();
+    let mut admonitions = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let was_in_code = tracker.in_code_block();
+        tracker.process_line(lines[idx]);
+        if was_in_code || tracker.in_code_block() {
+            idx += 1;
+            continue;
+        }
+        match parse_at(&patterns, lines, idx) {
+            Some(admonition) => {
+                idx = admonition.end;
+                admonitions.push(admonition);
+            }
+            None => idx += 1,
+        }
+    }
+    admonitions
+}
+
+/// Parse a callout starting at line `start`.
+fn parse_at(patterns: &Patterns, lines: &[&str], start: usize) -> Option<Admonition> {
+    let line = lines[start].trim_end();
+    let admonition = |style, kind: &str, end, body, title| Admonition {
+        style,
+        kind: kind.to_lowercase(),
+        start,
+        end,
+        body,
+        title,
+    };
+
+    if let Some(caps) = patterns.github.captures(line) {
+        let (end, body) = quoted_body(lines, start + 1);
+        return Some(admonition(
+            AdmonitionStyle::Github,
+            &caps[1],
+            end,
+            body,
+            None,
+        ));
+    }
+    if let Some(caps) = patterns.blockquote.captures(line) {
+        let (end, mut body) = quoted_body(lines, start + 1);
+        if !caps[2].is_empty() {
+            body.insert(0, caps[2].to_string());
+        }
+        return Some(admonition(
+            AdmonitionStyle::Blockquote,
+            &caps[1],
+            end,
+            body,
+            None,
+        ));
+    }
+    if let Some(caps) = patterns.directive.captures(line) {
+        // An unclosed directive isn't a callout
+        let close = (start + 1..lines.len()).find(|&idx| lines[idx].trim() == ":::")?;
+        let body = lines[start + 1..close]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let title = Some(caps[2].trim().to_string()).filter(|title| !title.is_empty());
+        return Some(admonition(
+            AdmonitionStyle::Directive,
+            &caps[1],
+            close + 1,
+            body,
+            title,
+        ));
+    }
+    None
+}
+
+/// Blockquote lines from `from` on, without their `>` markers.
+fn quoted_body(lines: &[&str], from: usize) -> (usize, Vec<String>) {
+    let mut body = Vec::new();
+    let mut end = from;
+    while let Some(rest) = lines.get(end).and_then(|line| line.strip_prefix('>')) {
+        body.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+        end += 1;
+    }
+    (end, body)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(content: &str) -> Vec<Admonition> {
+        let lines: Vec<&str> = content.lines().collect();
+        find_admonitions(&lines)
+    }
+
+    #[test]
+    fn finds_each_style_outside_code_blocks() {
+        let content = "> [!WARNING]\n> Careful.\n\n> **Tip:** Try this.\n> More.\n\n:::note\nBody\n:::\n\n```\n> [!NOTE]\n```\n\n> Just a quote\n";
+        let found = find(content);
+
+        let summary: Vec<(AdmonitionStyle, &str, usize, usize)> = found
+            .iter()
+            .map(|a| (a.style, a.kind.as_str(), a.start, a.end))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (AdmonitionStyle::Github, "warning", 0, 2),
+                (AdmonitionStyle::Blockquote, "tip", 3, 5),
+                (AdmonitionStyle::Directive, "note", 6, 9),
+            ]
+        );
+        assert_eq!(found[1].body, vec!["Try this.", "More."]);
+    }
+
+    #[test]
+    fn converts_between_styles() {
+        let github = &find("> [!NOTE]\n> First line.\n>\n> Second.\n")[0];
+        assert_eq!(
+            github.render(AdmonitionStyle::Blockquote).unwrap(),
+            vec!["> **Note:** First line.", ">", "> Second."]
+        );
+        assert_eq!(
+            github.render(AdmonitionStyle::Directive).unwrap(),
+            vec![":::note", "First line.", "", "Second.", ":::"]
+        );
+
+        let blockquote = &find("> **Warning**: Hot.\n")[0];
+        assert_eq!(
+            blockquote.render(AdmonitionStyle::Github).unwrap(),
+            vec!["> [!WARNING]", "> Hot."]
+        );
+
+        assert_eq!(blockquote.opening(AdmonitionStyle::Github), "> [!WARNING]");
+        assert_eq!(
+            blockquote.opening(AdmonitionStyle::Blockquote),
+            "> **Warning:**"
+        );
+        assert_eq!(blockquote.opening(AdmonitionStyle::Directive), ":::warning");
+
+        let titled = &find(":::tip Pro tip\nUse it.\n:::\n")[0];
+        assert_eq!(titled.title.as_deref(), Some("Pro tip"));
+        assert!(titled.render(AdmonitionStyle::Github).is_none());
+    }
+
+    #[test]
+    fn unclosed_directive_is_not_an_admonition() {
+        assert!(find(":::note\nNo end\n").is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Tamper-evident audit log of verification commands.
+//!
+//! With `[verify] audit_log = true`, every command `pave verify` runs is
+//! appended to `.pave/audit.log` as one JSON line: the document, command,
+//! working directory, names (never values) of the environment variables it
+//! set, exit code, status, and a SHA-256 hash of its output. Each entry also
+//! stores the hash of the entry before it and a hash of itself, so editing,
+//! reordering, or deleting an entry breaks the chain from that point on.
+//! `pave audit verify` walks the chain and reports the first break.
+//!
+//! Truncating the end of the log leaves a valid, shorter chain. Record the
+//! head hash that `pave audit verify` prints somewhere the log's writers
+//! can't change to detect that too.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Log file location, relative to the config directory.
+pub const AUDIT_LOG_PATH: &str = ".pave/audit.log";
+
+/// `prev` of the first entry.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One executed command.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0.
+    pub seq: u64,
+    /// When the entry was recorded (RFC 3339).
+    pub timestamp: String,
+    /// Document the command belongs to (relative to the config directory).
+    pub doc: String,
+    /// `setup`, `command`, or `teardown`.
+    pub phase: String,
+    /// The command that ran.
+    pub command: String,
+    /// Working directory the command ran in.
+    pub cwd: String,
+    /// Names of the environment variables set for the command.
+    pub env: Vec<String>,
+    /// Exit code, if the command completed.
+    pub exit_code: Option<i32>,
+    /// Verification status, as in `pave verify --format json`.
+    pub status: String,
+    /// SHA-256 of the command's stdout and stderr.
+    pub output_hash: String,
+    /// Hash of the previous entry.
+    pub prev: String,
+    /// Hash of this entry's other fields.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash of every field but `hash`.
+    fn compute_hash(&self) -> String {
+        let body = AuditEntry {
+            hash: String::new(),
+            ..self.clone()
+        };
+        // Serializing a struct of strings and numbers can't fail
+        sha256_hex(&serde_json::to_vec(&body).unwrap_or_default())
+    }
+}
+
+/// Append handle for an audit log.
+pub struct AuditLog {
+    path: PathBuf,
+    /// Hash of the last entry.
+    head: String,
+    next_seq: u64,
+}
+
+impl AuditLog {
+    /// Open the log at `path`, which is created on the first append.
+    ///
+    /// Only the last entry is read. Run [`verify_chain`] to check the rest.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut log = Self {
+            path: path.to_path_buf(),
+            head: GENESIS_HASH.to_string(),
+            next_seq: 0,
+        };
+        if !path.exists() {
+            return Ok(log);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read audit log: {}", path.display()))?;
+        if let Some(last) = content.lines().rev().find(|line| !line.trim().is_empty()) {
+            let entry: AuditEntry = serde_json::from_str(last).with_context(|| {
+                format!(
+                    "Audit log {} ends with a corrupt entry; run `pave audit verify`",
+                    path.display()
+                )
+            })?;
+            log.head = entry.hash;
+            log.next_seq = entry.seq + 1;
+        }
+        Ok(log)
+    }
+
+    /// Chain `entry` onto the log and write it.
+    ///
+    /// `seq`, `timestamp`, `prev`, and `hash` are filled in here.
+    pub fn append(&mut self, mut entry: AuditEntry) -> Result<()> {
+        entry.seq = self.next_seq;
+        entry.timestamp = chrono::Utc::now().to_rfc3339();
+        entry.prev = self.head.clone();
+        entry.hash = entry.compute_hash();
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log: {}", self.path.display()))?;
+        let line = serde_json::to_string(&entry).context("Failed to serialize audit entry")?;
+        writeln!(file, "{}", line)
+            .with_context(|| format!("Failed to write audit log: {}", self.path.display()))?;
+
+        self.head = entry.hash;
+        self.next_seq += 1;
+        Ok(())
+    }
+}
+
+/// Hash a command's output for [`AuditEntry::output_hash`].
+pub fn hash_output(stdout: &str, stderr: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(stdout.as_bytes());
+    hasher.update([0]);
+    hasher.update(stderr.as_bytes());
+    hex(&hasher.finalize())
+}
+
+/// An intact chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainSummary {
+    /// Number of entries.
+    pub entries: usize,
+    /// Hash of the last entry, or the genesis hash for an empty log.
+    pub head: String,
+}
+
+/// Where and why a chain is broken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainBreak {
+    /// Line of the first bad entry (1-indexed).
+    pub line: usize,
+    /// What's wrong with it.
+    pub reason: String,
+}
+
+impl fmt::Display for ChainBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Check every entry's hash and link to the entry before it.
+pub fn verify_chain(content: &str) -> std::result::Result<ChainSummary, ChainBreak> {
+    let mut head = GENESIS_HASH.to_string();
+    let mut entries = 0;
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let broken = |reason: String| ChainBreak {
+            line: idx + 1,
+            reason,
+        };
+
+        let entry: AuditEntry =
+            serde_json::from_str(line).map_err(|e| broken(format!("not a valid entry: {}", e)))?;
+        if entry.seq != entries as u64 {
+            return Err(broken(format!(
+                "expected entry {} but found entry {}",
+                entries, entry.seq
+            )));
+        }
+        if entry.prev != head {
+            return Err(broken(
+                "does not link to the previous entry (an entry was removed or reordered)"
+                    .to_string(),
+            ));
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(broken(
+                "contents do not match its hash (entry was edited)".to_string(),
+            ));
+        }
+        head = entry.hash;
+        entries += 1;
+    }
+
+    Ok(ChainSummary { entries, head })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(command: &str) -> AuditEntry {
+        AuditEntry {
+            doc: "docs/auth.md".to_string(),
+            phase: "command".to_string(),
+            command: command.to_string(),
+            cwd: ".".to_string(),
+            env: vec!["TOKEN".to_string()],
+            exit_code: Some(0),
+            status: "pass".to_string(),
+            output_hash: hash_output("ok\n", ""),
+            ..Default::default()
+        }
+    }
+
+    fn write_log(path: &Path, commands: &[&str]) {
+        let mut log = AuditLog::open(path).unwrap();
+        for command in commands {
+            log.append(entry(command)).unwrap();
+        }
+    }
+
+    #[test]
+    fn appends_chain_across_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(AUDIT_LOG_PATH);
+        write_log(&path, &["make test", "cargo build"]);
+        write_log(&path, &["./smoke.sh"]);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let summary = verify_chain(&content).unwrap();
+        assert_eq!(summary.entries, 3);
+
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries[0].prev, GENESIS_HASH);
+        assert_eq!(entries[2].seq, 2);
+        assert_eq!(entries[2].prev, entries[1].hash);
+        assert_eq!(summary.head, entries[2].hash);
+    }
+
+    #[test]
+    fn detects_edits_removals_and_reordering() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(AUDIT_LOG_PATH);
+        write_log(&path, &["a", "b", "c"]);
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+
+        let edited = content.replace("\"exit_code\":0", "\"exit_code\":1");
+        let err = verify_chain(&edited).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.reason.contains("edited"));
+
+        let removed = [lines[0], lines[2]].join("\n");
+        assert_eq!(verify_chain(&removed).unwrap_err().line, 2);
+
+        let reordered = [lines[0], lines[2], lines[1]].join("\n");
+        assert_eq!(verify_chain(&reordered).unwrap_err().line, 2);
+
+        assert_eq!(
+            verify_chain("").unwrap(),
+            ChainSummary {
+                entries: 0,
+                head: GENESIS_HASH.to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! `git blame` for issues, so they can be routed to whoever wrote the
+//! offending lines.
+//!
+//! `pave check --blame` and `pave lint --blame` annotate each issue with
+//! the author and commit that last changed its line, and `--group-by author`
+//! counts issues per author. Each file is blamed with one `git blame` run
+//! covering just the lines with issues. Lines git can't attribute (files
+//! outside a repository, uncommitted changes) get no blame.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+/// Who last changed a line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Blame {
+    /// Author of the commit.
+    pub author: String,
+    /// Author's email address.
+    pub email: String,
+    /// Abbreviated commit hash.
+    pub commit: String,
+    /// Date the commit was authored (YYYY-MM-DD, UTC).
+    pub date: String,
+}
+
+impl Blame {
+    /// Describe the blame for text output.
+    pub fn describe(&self) -> String {
+        format!(
+            "last changed by {} in {} ({})",
+            self.author, self.commit, self.date
+        )
+    }
+}
+
+/// Issues attributed to one author.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct AuthorSummary {
+    /// Author's name, or `unknown` for lines git can't attribute.
+    pub author: String,
+    /// Author's email address, empty when unknown.
+    pub email: String,
+    /// Number of issues on lines they last changed.
+    pub issues: usize,
+}
+
+/// Length of the abbreviated commit hashes shown.
+const SHORT_HASH_LEN: usize = 8;
+
+/// Blame each `(file, line)` location, returning the results in the same
+/// order. Line numbers are 1-indexed.
+pub fn blame_all(locations: &[(PathBuf, usize)]) -> Vec<Option<Blame>> {
+    let mut lines_by_file: BTreeMap<&Path, BTreeSet<usize>> = BTreeMap::new();
+    for (file, line) in locations {
+        lines_by_file.entry(file).or_default().insert(*line);
+    }
+    let blames: HashMap<&Path, HashMap<usize, Blame>> = lines_by_file
+        .into_iter()
+        .map(|(file, lines)| (file, blame_lines(file, &lines)))
+        .collect();
+    locations
+        .iter()
+        .map(|(file, line)| blames[file.as_path()].get(line).cloned())
+        .collect()
+}
+
+/// Blame `lines` of `file`, by line number. Lines past the end of the file
+/// are skipped, since `git blame -L` rejects them.
+fn blame_lines(file: &Path, lines: &BTreeSet<usize>) -> HashMap<usize, Blame> {
+    let line_count = std::fs::read_to_string(file).map_or(0, |content| content.lines().count());
+    let lines: Vec<usize> = lines
+        .iter()
+        .copied()
+        .filter(|line| (1..=line_count).contains(line))
+        .collect();
+    let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+        return HashMap::new();
+    };
+    if lines.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.arg("blame").arg("--line-porcelain");
+    for line in &lines {
+        cmd.arg("-L").arg(format!("{},{}", line, line));
+    }
+    // Run next to the file so it's blamed in its own repository
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    match cmd.arg("--").arg(name).current_dir(dir).output() {
+        Ok(output) if output.status.success() => {
+            parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            debug!(
+                "git blame failed for {}: {}",
+                file.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            HashMap::new()
+        }
+        Err(e) => {
+            debug!("Failed to run git blame: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Parse `git blame --line-porcelain` output into blame by final line
+/// number, leaving out uncommitted lines.
+fn parse_porcelain(output: &str) -> HashMap<usize, Blame> {
+    let mut blames = HashMap::new();
+    let mut current: Option<(usize, String)> = None;
+    let (mut author, mut email, mut time) = (String::new(), String::new(), 0);
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // The line's content ends its entry
+            if let Some((number, commit)) = current.take()
+                && commit.bytes().any(|b| b != b'0')
+            {
+                let date = chrono::DateTime::from_timestamp(time, 0)
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+                let blame = Blame {
+                    author: std::mem::take(&mut author),
+                    email: std::mem::take(&mut email),
+                    commit: commit.chars().take(SHORT_HASH_LEN).collect(),
+                    date,
+                };
+                blames.insert(number, blame);
+            }
+        } else if let Some(value) = line.strip_prefix("author ") {
+            author = value.to_string();
+        } else if let Some(value) = line.strip_prefix("author-mail ") {
+            email = value.trim_matches(['<', '>']).to_string();
+        } else if let Some(value) = line.strip_prefix("author-time ") {
+            time = value.parse().unwrap_or(0);
+        } else if current.is_none() {
+            // Header: <commit> <original line> <final line> [<group size>]
+            let mut fields = line.split(' ');
+            if let (Some(commit), Some(_), Some(number)) =
+                (fields.next(), fields.next(), fields.next())
+                && let Ok(number) = number.parse()
+            {
+                current = Some((number, commit.to_string()));
+            }
+        }
+    }
+    blames
+}
+
+/// Count issues per author, most issues first. `blames` holds one entry
+/// per issue.
+pub fn group_by_author<'a>(
+    blames: impl IntoIterator<Item = Option<&'a Blame>>,
+) -> Vec<AuthorSummary> {
+    let mut by_email: BTreeMap<String, AuthorSummary> = BTreeMap::new();
+    for blame in blames {
+        let (author, email) = match blame {
+            Some(blame) => (blame.author.as_str(), blame.email.as_str()),
+            None => ("unknown", ""),
+        };
+        by_email
+            .entry(email.to_string())
+            .or_insert_with(|| AuthorSummary {
+                author: author.to_string(),
+                email: email.to_string(),
+                issues: 0,
+            })
+            .issues += 1;
+    }
+    sort_by_issues(by_email.into_values().collect())
+}
+
+/// Combine per-author counts from separate runs, such as workspace members,
+/// ordered like [`group_by_author`].
+pub fn merge_authors(authors: impl IntoIterator<Item = AuthorSummary>) -> Vec<AuthorSummary> {
+    let mut by_email: BTreeMap<String, AuthorSummary> = BTreeMap::new();
+    for summary in authors {
+        by_email
+            .entry(summary.email.clone())
+            .and_modify(|merged| merged.issues += summary.issues)
+            .or_insert(summary);
+    }
+    sort_by_issues(by_email.into_values().collect())
+}
+
+/// Most issues first, then by name.
+fn sort_by_issues(mut summaries: Vec<AuthorSummary>) -> Vec<AuthorSummary> {
+    summaries.sort_by(|a, b| {
+        b.issues
+            .cmp(&a.issues)
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    summaries
+}
+
+/// Print the per-author counts as text.
+pub fn print_authors(authors: &[AuthorSummary]) {
+    println!("Issues by author:");
+    let width = authors
+        .iter()
+        .map(|a| a.issues.to_string().len())
+        .max()
+        .unwrap_or(1);
+    for summary in authors {
+        if summary.email.is_empty() {
+            println!("  {:>width$}  {}", summary.issues, summary.author);
+        } else {
+            println!(
+                "  {:>width$}  {} <{}>",
+                summary.issues, summary.author, summary.email
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_porcelain_and_groups_by_author() {
+        let output = "\
+4f1c2a9d0e8b7c6a5f4e3d2c1b0a9f8e7d6c5b4a 2 3 1
+author Ada Lovelace
+author-mail <ada@example.com>
+author-time 1767225600
+author-tz +0000
+summary Add overview
+filename docs/a.md
+\tSome prose.
+0000000000000000000000000000000000000000 7 7 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1767312000
+filename docs/a.md
+\tAn edit in progress.
+";
+        let blames = parse_porcelain(output);
+        assert_eq!(
+            blames[&3],
+            Blame {
+                author: "Ada Lovelace".to_string(),
+                email: "ada@example.com".to_string(),
+                commit: "4f1c2a9d".to_string(),
+                date: "2026-01-01".to_string(),
+            }
+        );
+        assert!(!blames.contains_key(&7));
+
+        let authors = group_by_author([blames.get(&3), None, blames.get(&3)]);
+        assert_eq!(authors[0].author, "Ada Lovelace");
+        assert_eq!(authors[0].issues, 2);
+        assert_eq!(authors[1].author, "unknown");
+        assert_eq!(authors[1].issues, 1);
+    }
+}
@@ -0,0 +1,168 @@
+//! Compiling `rust` examples against the project's own crate.
+//!
+//! A `pave:compile` example in a Cargo project is checked the way rustdoc
+//! checks a doctest: the library is built with `cargo build --lib`, and
+//! `rustc` gets it with `--extern`, so `use my_crate::Thing;` resolves and
+//! an example that no longer matches the API fails. The example is compiled
+//! with the package's edition. Outside a Cargo project, or in a package
+//! without a library, examples compile on their own.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A library built for examples to compile against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Library {
+    /// Crate name as written in `use` paths.
+    pub name: String,
+    /// Edition of the package.
+    pub edition: String,
+    /// The built `.rlib`.
+    pub rlib: PathBuf,
+    /// Directory holding the library's own dependencies.
+    pub deps: PathBuf,
+}
+
+impl Library {
+    /// Add the arguments that make `rustc` compile against the library,
+    /// replacing any `--edition` already given.
+    pub fn add_to(&self, argv: &mut Vec<String>) {
+        if let Some(pos) = argv.iter().position(|arg| arg == "--edition")
+            && pos + 1 < argv.len()
+        {
+            argv.drain(pos..pos + 2);
+        }
+        argv.extend([
+            "--edition".to_string(),
+            self.edition.clone(),
+            "--extern".to_string(),
+            format!("{}={}", self.name, self.rlib.display()),
+            "-L".to_string(),
+            format!("dependency={}", self.deps.display()),
+        ]);
+    }
+}
+
+/// Build the library of the Cargo package containing `dir`.
+///
+/// Returns `None` if `dir` isn't in a Cargo package or the package has no
+/// library, and an error message if the build fails.
+pub fn library(dir: &Path) -> Result<Option<Library>, String> {
+    let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let Some(manifest) = dir
+        .ancestors()
+        .map(|ancestor| ancestor.join("Cargo.toml"))
+        .find(|manifest| manifest.is_file())
+    else {
+        return Ok(None);
+    };
+
+    let output = Command::new("cargo")
+        .args(["build", "--lib", "--quiet", "--message-format=json"])
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .output()
+        .map_err(|e| format!("failed to run cargo: {}", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        // A package with only binaries has nothing to compile against
+        if stderr.contains("no library targets found") {
+            return Ok(None);
+        }
+        return Err(format!(
+            "cargo build failed for {}: {}",
+            manifest.display(),
+            stderr
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or_default()
+        ));
+    }
+    Ok(parse_library(
+        &String::from_utf8_lossy(&output.stdout),
+        &manifest,
+    ))
+}
+
+/// Find the library built from `manifest` in `cargo --message-format=json`
+/// output.
+fn parse_library(messages: &str, manifest: &Path) -> Option<Library> {
+    messages.lines().find_map(|line| {
+        let message: serde_json::Value = serde_json::from_str(line).ok()?;
+        if message["reason"] != "compiler-artifact"
+            || message["manifest_path"].as_str().map(Path::new) != Some(manifest)
+        {
+            return None;
+        }
+        let target = &message["target"];
+        let kinds = target["kind"].as_array()?;
+        if !kinds.iter().any(|kind| kind == "lib" || kind == "rlib") {
+            return None;
+        }
+        let rlib = message["filenames"]
+            .as_array()?
+            .iter()
+            .filter_map(|file| file.as_str())
+            .find(|file| file.ends_with(".rlib"))
+            .map(PathBuf::from)?;
+        let out_dir = rlib.parent()?;
+        let deps = if out_dir.ends_with("deps") {
+            out_dir.to_path_buf()
+        } else {
+            out_dir.join("deps")
+        };
+        Some(Library {
+            name: target["name"].as_str()?.replace('-', "_"),
+            edition: target["edition"].as_str().unwrap_or("2021").to_string(),
+            rlib,
+            deps,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_library_artifact() {
+        let messages = concat!(
+            r#"{"reason":"compiler-artifact","manifest_path":"/dep/Cargo.toml","target":{"kind":["lib"],"name":"dep","edition":"2018"},"filenames":["/p/target/debug/deps/libdep-1.rlib"]}"#,
+            "\n",
+            r#"{"reason":"compiler-artifact","manifest_path":"/p/Cargo.toml","target":{"kind":["lib"],"name":"my-crate","edition":"2024"},"filenames":["/p/target/debug/libmy_crate.rlib","/p/target/debug/libmy_crate.rmeta"]}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":true}"#,
+        );
+
+        let library = parse_library(messages, Path::new("/p/Cargo.toml")).unwrap();
+        assert_eq!(
+            library,
+            Library {
+                name: "my_crate".to_string(),
+                edition: "2024".to_string(),
+                rlib: PathBuf::from("/p/target/debug/libmy_crate.rlib"),
+                deps: PathBuf::from("/p/target/debug/deps"),
+            }
+        );
+
+        let mut argv: Vec<String> = ["rustc", "--edition", "2021", "--crate-type", "lib", "-"]
+            .map(String::from)
+            .to_vec();
+        library.add_to(&mut argv);
+        assert_eq!(
+            argv,
+            [
+                "rustc",
+                "--crate-type",
+                "lib",
+                "-",
+                "--edition",
+                "2024",
+                "--extern",
+                "my_crate=/p/target/debug/libmy_crate.rlib",
+                "-L",
+                "dependency=/p/target/debug/deps",
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 use crate::templates::TemplateType;
@@ -8,6 +9,37 @@ use crate::templates::TemplateType;
 #[command(name = "pave")]
 #[command(version, about, long_about = None)]
 pub struct Cli {
+    /// Use this config file instead of searching for .pave.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Run as if pave was started in this directory
+    #[arg(long, global = true, value_name = "DIR")]
+    pub root: Option<PathBuf>,
+
+    /// Only run in this workspace package (repeatable)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub package: Vec<String>,
+
+    /// Log diagnostics to stderr: -v for decisions such as skipped files,
+    /// -vv for timings, -vvv for everything
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only log errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Color text output: auto, always, never
+    #[arg(
+        long,
+        global = true,
+        value_name = "WHEN",
+        default_value = "auto",
+        value_enum
+    )]
+    pub color: ColorChoice,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -21,6 +53,10 @@ pub enum DocType {
     Runbook,
     /// Architecture Decision Record.
     Adr,
+    /// Incident postmortem.
+    Postmortem,
+    /// API reference.
+    Api,
 }
 
 /// Output format for prompt command.
@@ -43,6 +79,44 @@ pub enum AdoptOutputFormat {
     Json,
 }
 
+/// Doc systems `pave import` reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    /// An MkDocs site: its directory or mkdocs.yml
+    Mkdocs,
+    /// A Docusaurus site: its directory or its docs/ directory
+    Docusaurus,
+    /// A Confluence space exported as HTML: the export directory
+    Confluence,
+}
+
+/// Output format for the `pave import` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ImportOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Wikis `pave publish` mirrors the docs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PublishTarget {
+    /// The space in `[publish.confluence]`
+    Confluence,
+}
+
+/// Output format for the `pave publish` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum PublishOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
 /// Output format for migrate command.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 pub enum MigrateOutputFormat {
@@ -69,9 +143,41 @@ pub enum Command {
         #[arg(long)]
         suggest_config: bool,
 
-        /// Show what pave init would create (without creating)
+        /// Show what pave init would create (without creating), or with
+        /// --migrate, the diffs it would write
         #[arg(long)]
         dry_run: bool,
+
+        /// Add missing PAVED sections to every doc that can be migrated
+        /// safely, and report the ones that need manual work
+        #[arg(long)]
+        migrate: bool,
+    },
+
+    /// Import pages from MkDocs, Docusaurus, or a Confluence export
+    Import {
+        /// Doc system to import from
+        #[arg(long, value_enum)]
+        from: ImportSource,
+
+        /// Site directory, mkdocs.yml, or Confluence export directory
+        path: PathBuf,
+
+        /// Directory to write pages to [default: docs root from config]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Report what would be imported without writing files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Overwrite pages and the index if they already exist
+        #[arg(long)]
+        force: bool,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: ImportOutputFormat,
     },
 
     /// Initialize a project with PAVED documentation
@@ -87,7 +193,8 @@ pub enum Command {
         #[arg(long, default_value = "text", value_enum)]
         format: OutputFormat,
 
-        /// Treat warnings as errors (overrides gradual mode)
+        /// Treat warnings as errors (overrides gradual mode) and warn about
+        /// malformed markers, frontmatter, and code fences
         #[arg(long)]
         strict: bool,
 
@@ -95,6 +202,10 @@ pub enum Command {
         #[arg(long)]
         gradual: bool,
 
+        /// Exit 1 on: errors, warnings, or never [default: errors]
+        #[arg(long, default_value = "errors", value_enum)]
+        error_on: ErrorOn,
+
         /// Only check docs changed since base ref
         #[arg(long)]
         changed: bool,
@@ -102,13 +213,37 @@ pub enum Command {
         /// Base ref for --changed comparison [default: origin/main]
         #[arg(long)]
         base: Option<String>,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+
+        /// List files skipped by ignore rules and the pattern that matched
+        #[arg(long)]
+        list_ignored: bool,
+
+        /// Re-check every doc instead of reusing results for unchanged ones
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Docs to check at once (default: one per CPU)
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+
+        /// Show the author and commit that last changed each issue's line
+        #[arg(long)]
+        blame: bool,
+
+        /// Count issues per author (implies --blame)
+        #[arg(long, value_enum, value_name = "KEY")]
+        group_by: Option<IssueGrouping>,
     },
 
     /// Create a new document from template
     New {
-        /// Document type: component, runbook, adr
-        #[arg(value_enum)]
-        doc_type: DocType,
+        /// Document type: component, runbook, adr, postmortem, api, or a template in the
+        /// templates directory
+        doc_type: String,
 
         /// Name for the document (used in filename and title)
         name: String,
@@ -116,13 +251,23 @@ pub enum Command {
         /// Where to create the file [default: docs/{type}s/{name}.md]
         #[arg(long)]
         output: Option<PathBuf>,
+
+        /// Set a template variable: owner, tags, paths, verify, or any {{key}}
+        /// used in the template (can be specified multiple times)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+
+        /// Prompt for owner, covered paths, tags, and verification commands
+        #[arg(long, short = 'i')]
+        interactive: bool,
     },
 
     /// Generate prompts for AI agents
     Prompt {
-        /// Document type: component, runbook, adr
-        #[arg(value_enum)]
-        doc_type: DocType,
+        /// Document type: component, runbook, adr, postmortem, api, or a template in the
+        /// templates directory. `review` and `verify-fix` instead prompt to fix the --doc
+        /// document's check and lint issues, or its failing verification commands
+        doc_type: String,
 
         /// Name of the thing being documented
         #[arg(long = "for")]
@@ -136,6 +281,15 @@ pub enum Command {
         #[arg(long, value_name = "PATH")]
         context: Vec<PathBuf>,
 
+        /// Document to fix, for `review` and `verify-fix`
+        #[arg(long, value_name = "PATH")]
+        doc: Option<PathBuf>,
+
+        /// Read failures from this `pave verify --report` file instead of running the
+        /// document's commands, for `verify-fix`
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
+
         /// Output format: text, json
         #[arg(long, value_enum, default_value = "text")]
         output: PromptOutputFormat,
@@ -145,10 +299,26 @@ pub enum Command {
     #[command(subcommand)]
     Hooks(HooksCommand),
 
+    /// Generate CI configuration for documentation validation
+    #[command(subcommand)]
+    Ci(CiCommand),
+
     /// View or modify pave configuration
     #[command(subcommand)]
     Config(ConfigCommand),
 
+    /// Manage document templates
+    #[command(subcommand)]
+    Templates(TemplatesCommand),
+
+    /// Work with JSON reports from check and verify
+    #[command(subcommand)]
+    Report(ReportCommand),
+
+    /// Inspect the verification audit log
+    #[command(subcommand)]
+    Audit(AuditCommand),
+
     /// Generate an index document mapping all PAVED documentation
     Index {
         /// Where to write the index document
@@ -158,6 +328,62 @@ pub enum Command {
         /// Update existing index (preserve custom content)
         #[arg(short, long)]
         update: bool,
+
+        /// Output format; JSON is printed instead of written to the index document
+        #[arg(long, value_enum, default_value_t = IndexFormat::Markdown)]
+        format: IndexFormat,
+
+        /// Fail if the index document is out of date instead of writing it
+        #[arg(long, conflicts_with = "update")]
+        check: bool,
+    },
+
+    /// Search docs by title, tag, covered path, and content
+    Search {
+        /// Words to search for; a doc matches if it contains all of them
+        #[arg(default_value = "")]
+        query: String,
+
+        /// Only search docs of this type
+        #[arg(long = "type", value_enum)]
+        doc_type: Option<DocType>,
+
+        /// Only search content in this section (e.g., Verification)
+        #[arg(long)]
+        section: Option<String>,
+
+        /// Only search docs with this frontmatter tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = SearchOutputFormat::Text)]
+        format: SearchOutputFormat,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+    },
+
+    /// Show which docs cover a code file
+    Which {
+        /// Code file to look up
+        path: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = WhichOutputFormat::Text)]
+        format: WhichOutputFormat,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+    },
+
+    /// Print the JSON Schema of a command's JSON output
+    Schema {
+        /// Command whose output to describe
+        #[arg(value_enum)]
+        command: SchemaTarget,
     },
 
     /// Show docs impacted by code changes
@@ -173,6 +399,10 @@ pub enum Command {
         /// Fail if impacted docs weren't updated
         #[arg(long)]
         strict: bool,
+
+        /// Fail if changed code files aren't covered by any doc
+        #[arg(long)]
+        fail_on_uncovered: bool,
     },
 
     /// Run verification commands from PAVED documents
@@ -196,13 +426,89 @@ pub enum Command {
         /// Continue running after first failure
         #[arg(long)]
         keep_going: bool,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+
+        /// Re-run every command, ignoring cached results
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Don't start pave.services or compose services; use ones already running
+        #[arg(long)]
+        no_services: bool,
+
+        /// Only verify docs that changed or cover code changed since base ref
+        #[arg(long)]
+        changed: bool,
+
+        /// Base ref for --changed comparison [default: origin/main]
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Write the commands to a standalone shell script instead of running them
+        #[arg(long, value_name = "PATH")]
+        export_script: Option<PathBuf>,
+
+        /// Write the commands to a Makefile instead of running them
+        #[arg(long, value_name = "PATH")]
+        export_make: Option<PathBuf>,
+
+        /// Also verify docs with `pave.state: draft`
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Rewrite stale expected outputs in the docs with the actual output
+        #[arg(long)]
+        update_expected: bool,
+
+        /// Re-run only the commands that failed in a previous JSON report and
+        /// merge the outcomes into it
+        #[arg(
+            long,
+            value_name = "REPORT",
+            conflicts_with_all = ["changed", "update_expected", "export_script", "export_make"]
+        )]
+        rerun_failed: Option<PathBuf>,
+    },
+
+    /// Publish docs that pass check to a wiki, updating pages published before
+    Publish {
+        /// Wiki to publish to
+        #[arg(long, value_enum)]
+        target: PublishTarget,
+
+        /// Report what would be published without sending anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also publish docs with `pave.state: draft`
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Republish docs even if they haven't changed
+        #[arg(long)]
+        force: bool,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: PublishOutputFormat,
     },
 
     /// Build static documentation site
     Build {
-        /// Output directory for the built site
-        #[arg(short, long, default_value = "_site")]
-        output: PathBuf,
+        /// Output directory [default: _site, or book with --format mdbook]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also publish docs with `pave.state: draft`
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// What to build: site, mdbook
+        #[arg(long, default_value = "site", value_enum)]
+        format: BuildFormat,
     },
 
     /// Show code-to-documentation coverage
@@ -226,6 +532,30 @@ pub enum Command {
         /// Exclude these code patterns (can be specified multiple times)
         #[arg(long = "exclude", value_name = "PATTERN")]
         exclude: Vec<String>,
+
+        /// Break down coverage per owner from CODEOWNERS
+        #[arg(long)]
+        by_owner: bool,
+
+        /// CODEOWNERS file to use [default: .github/CODEOWNERS, CODEOWNERS, or docs/CODEOWNERS]
+        #[arg(long, value_name = "PATH")]
+        codeowners: Option<PathBuf>,
+
+        /// Write an SVG coverage badge to this path
+        #[arg(long, value_name = "PATH")]
+        badge: Option<PathBuf>,
+
+        /// Write a shields.io endpoint JSON badge to this path
+        #[arg(long, value_name = "PATH")]
+        badge_json: Option<PathBuf>,
+
+        /// Append a snapshot to .pave/coverage-history.jsonl
+        #[arg(long)]
+        record: bool,
+
+        /// Compare with the last recorded snapshot and fail if coverage dropped
+        #[arg(long)]
+        trend: bool,
     },
 
     /// Check if newly added code files are covered by documentation
@@ -261,6 +591,10 @@ pub enum Command {
         #[arg(long)]
         fix: bool,
 
+        /// Review each fix as a diff and choose which to apply (implies --fix)
+        #[arg(long, short = 'i')]
+        interactive: bool,
+
         /// Only run these rules (comma-separated)
         #[arg(long)]
         rules: Option<String>,
@@ -268,6 +602,30 @@ pub enum Command {
         /// Check external link validity (slow)
         #[arg(long)]
         external_links: bool,
+
+        /// Only fail when more than this many issues remain
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        max_warnings: usize,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+
+        /// Re-lint every doc instead of reusing results for unchanged ones
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Docs to lint at once (default: one per CPU)
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+
+        /// Show the author and commit that last changed each issue's line
+        #[arg(long)]
+        blame: bool,
+
+        /// Count issues per author (implies --blame)
+        #[arg(long, value_enum, value_name = "KEY")]
+        group_by: Option<IssueGrouping>,
     },
 
     /// Diagnose documentation setup and identify issues
@@ -279,6 +637,29 @@ pub enum Command {
         /// Output format: text, json, github
         #[arg(long, default_value = "text", value_enum)]
         format: OutputFormat,
+
+        /// How to display file paths: absolute, relative, workspace
+        #[arg(long, default_value = "workspace", value_enum)]
+        path_style: PathStyle,
+
+        /// Recompute every document instead of using cached summaries
+        #[arg(long)]
+        refresh: bool,
+
+        /// Apply safe fixes (missing config keys, docs root, pre-commit hook,
+        /// index) and list the commands that address the rest
+        #[arg(long)]
+        fix: bool,
+
+        /// Run each doc's verification commands N times [default: 3] and
+        /// report flaky, failing, and slow ones
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+        verify_runs: Option<usize>,
+
+        /// Only run the commands of the first N docs with verification
+        /// commands
+        #[arg(long, value_name = "N", requires = "verify_runs")]
+        verify_sample: Option<usize>,
     },
 
     /// Show documentation status and health overview
@@ -298,6 +679,194 @@ pub enum Command {
         /// Git ref for comparison with --changed [default: origin/main]
         #[arg(long)]
         base: Option<String>,
+
+        /// Recompute every document instead of using cached summaries
+        #[arg(long)]
+        refresh: bool,
+
+        /// Rank docs and undocumented hot code by risk and list the N to fix
+        /// first
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+        worklist: Option<usize>,
+
+        /// Verify report whose failures count toward risk [default:
+        /// .pave/verify-report.json if present]
+        #[arg(long, value_name = "PATH", requires = "worklist")]
+        verify_report: Option<PathBuf>,
+    },
+
+    /// Run check, coverage, and status in several repositories and report
+    /// them together
+    Fleet {
+        /// Repositories to report on
+        #[arg(required_unless_present = "manifest")]
+        repos: Vec<PathBuf>,
+
+        /// File listing repositories, one path per line, relative to the file
+        #[arg(long, value_name = "PATH")]
+        manifest: Option<PathBuf>,
+
+        /// Output format: text, json, html
+        #[arg(long, default_value = "text", value_enum)]
+        format: FleetOutputFormat,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
+    /// Time walking, parsing, check, and each lint rule over the docs
+    Bench {
+        /// Specific files or directories to benchmark [default: docs root from config]
+        #[arg()]
+        paths: Vec<PathBuf>,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: BenchOutputFormat,
+
+        /// Repeat each measurement N times and keep the fastest
+        #[arg(long, default_value = "1", value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+        runs: u16,
+
+        /// Number of slowest docs to list
+        #[arg(long, default_value = "10", value_name = "N")]
+        top: usize,
+    },
+
+    /// Find docs whose mapped code changed long after the doc was last updated
+    Stale {
+        /// Output format: text, json, github
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+
+        /// Days a doc may lag behind its code before it is reported as stale
+        #[arg(long, default_value = "30")]
+        threshold_days: u32,
+
+        /// Fail if any doc is stale
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Report translations that are missing or older than their source doc
+    TranslateStatus {
+        /// Output format: text, json, github
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+
+        /// Fail if a required translation is missing or any is outdated
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Find near-identical sections copied across docs
+    Dedupe {
+        /// Output format: text, json, github
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+
+        /// Similarity from 0 to 1 at which sections count as duplicates
+        #[arg(long, default_value = "0.8")]
+        threshold: f64,
+
+        /// Skip sections shorter than this many words
+        #[arg(long, default_value = "30")]
+        min_words: usize,
+
+        /// Fail if any duplicates are found
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Summarize doc changes, check results, coverage, and verify status for a PR comment
+    Summary {
+        /// Git ref to compare against (default: origin/main, origin/master, or HEAD~1)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Output format: markdown, json
+        #[arg(long, default_value = "markdown", value_enum)]
+        format: SummaryOutputFormat,
+
+        /// JSON report from `pave verify --report` to take verify status from
+        /// (default: .pave/verify-report.json if present)
+        #[arg(long, value_name = "PATH")]
+        verify_report: Option<PathBuf>,
+    },
+
+    /// List docs due for review, grouped by owner
+    Review {
+        /// Output format: text, markdown, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: ReviewOutputFormat,
+
+        /// Also list docs that come due within this many days
+        #[arg(long, default_value = "0")]
+        within_days: u32,
+
+        /// Only list docs owned by this owner
+        #[arg(long)]
+        owner: Option<String>,
+    },
+
+    /// Run verification, staleness, coverage, and link checks for scheduled jobs
+    Nightly {
+        /// Output format: markdown, json
+        #[arg(long, default_value = "markdown", value_enum)]
+        format: NightlyOutputFormat,
+
+        /// Write the report to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+
+        /// POST the report as JSON to this URL
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+
+        /// Days a doc may lag behind its code before it is reported as stale
+        #[arg(long, default_value = "30")]
+        stale_days: u32,
+
+        /// Skip checking external links
+        #[arg(long)]
+        skip_links: bool,
+
+        /// Timeout per verification command in seconds
+        #[arg(long, default_value = "300")]
+        timeout: u32,
+    },
+
+    /// Split an oversized document into smaller ones by section
+    Split {
+        /// Document to split
+        doc: PathBuf,
+
+        /// Show the split plan without modifying files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply the split without asking for confirmation
+        #[arg(long, short = 'y', visible_alias = "apply")]
+        yes: bool,
+
+        /// Move this section into a document of its own instead of packing
+        /// sections by size (can be specified multiple times)
+        #[arg(long = "section", value_name = "NAME")]
+        sections: Vec<String>,
+    },
+
+    /// Move or rename a doc and rewrite the links pointing at it
+    Mv {
+        /// Doc to move
+        from: PathBuf,
+
+        /// New path, or a directory to move the doc into
+        to: PathBuf,
+
+        /// Show the link changes without modifying files
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Bulk-insert missing PAVED sections into existing documentation
@@ -318,7 +887,8 @@ pub enum Command {
         #[arg(long)]
         sections: Option<String>,
 
-        /// Confirm each file before modifying
+        /// Review each heading rename and new section as a diff, accepting,
+        /// rejecting, or editing it before the file is written
         #[arg(long, short = 'i')]
         interactive: bool,
 
@@ -326,6 +896,10 @@ pub enum Command {
         #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
         backup: bool,
     },
+
+    /// Run the `pave-<name>` plugin on PATH
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
 }
 
 /// Output format for the `pave changed` command.
@@ -348,6 +922,28 @@ pub enum StatusOutputFormat {
     Json,
 }
 
+/// Output format for the `pave review` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ReviewOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// GitHub-flavored markdown checklist, one section per owner
+    Markdown,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Output format for the `pave nightly` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum NightlyOutputFormat {
+    /// Markdown report for humans, issues, and chat
+    #[default]
+    Markdown,
+    /// JSON output for programmatic use
+    Json,
+}
+
 /// Output format for the `pave coverage` command.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
 pub enum CoverageOutputFormat {
@@ -358,6 +954,140 @@ pub enum CoverageOutputFormat {
     Json,
 }
 
+/// Output format for the `pave search` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum SearchOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Output format for the `pave which` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum WhichOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Output format for the `pave summary` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum SummaryOutputFormat {
+    /// GitHub-flavored markdown, ready to post as a PR comment
+    #[default]
+    Markdown,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// How `pave check --group-by` and `pave lint --group-by` count issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IssueGrouping {
+    /// By the author who last changed each issue's line
+    Author,
+}
+
+/// Output format for the `pave bench` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum BenchOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Output format for the `pave fleet` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum FleetOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+    /// Standalone HTML page
+    Html,
+}
+
+/// Commands with a JSON output described by `pave schema`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaTarget {
+    /// `pave check --format json`
+    Check,
+    /// `pave verify --format json`
+    Verify,
+    /// `pave lint --format json`
+    Lint,
+    /// `pave coverage --format json`
+    Coverage,
+    /// `pave status --format json`
+    Status,
+    /// `pave stale --format json`
+    Stale,
+    /// `pave translate-status --format json`
+    TranslateStatus,
+    /// `pave import --format json`
+    Import,
+    /// `pave publish --format json`
+    Publish,
+    /// `pave dedupe --format json`
+    Dedupe,
+    /// `pave review --format json`
+    Review,
+    /// `pave index --format json`
+    Index,
+    /// `pave search --format json`
+    Search,
+    /// `pave which --format json`
+    Which,
+    /// `pave summary --format json`
+    Summary,
+    /// `pave fleet --format json`
+    Fleet,
+    /// `pave bench --format json`
+    Bench,
+    /// `pave doctor --format json`
+    Doctor,
+    /// `pave nightly --format json`
+    Nightly,
+    /// `pave adopt --format json`
+    Adopt,
+    /// `pave adopt --migrate --format json`
+    AdoptMigrate,
+    /// `pave changed --format json`
+    Changed,
+    /// `pave coverage-changed --format json`
+    CoverageChanged,
+    /// `pave migrate --format json`
+    Migrate,
+    /// `pave report diff --format json`
+    ReportDiff,
+}
+
+/// Output format for the `pave index` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum IndexFormat {
+    /// Markdown index document
+    #[default]
+    Markdown,
+    /// JSON printed to stdout
+    Json,
+}
+
+/// Output format for the `pave build` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum BuildFormat {
+    /// Static HTML site from the `site/` layouts
+    #[default]
+    Site,
+    /// mdBook project with `book.toml` and `SUMMARY.md`
+    Mdbook,
+}
+
 #[derive(Subcommand)]
 pub enum ConfigCommand {
     /// Get a config value
@@ -379,6 +1109,73 @@ pub enum ConfigCommand {
 
     /// Print path to config file
     Path,
+
+    /// Check the config file for unknown keys, bad values, and conflicts
+    Validate,
+}
+
+#[derive(Subcommand)]
+pub enum TemplatesCommand {
+    /// Validate templates against the configured rules
+    Check {
+        /// Output format: text, json, github
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+    },
+
+    /// List document types and the template each one uses
+    List {
+        /// Output format: text, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: TemplatesListOutputFormat,
+    },
+}
+
+/// Output format for the `pave templates list` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum TemplatesListOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommand {
+    /// Compare two reports and fail only on new failures
+    Diff {
+        /// Baseline report (e.g. from the main branch)
+        old: PathBuf,
+
+        /// Report to compare against the baseline
+        new: PathBuf,
+
+        /// Output format: text, json, github
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+    },
+
+    /// Write a static HTML site with check, verify, and coverage status
+    Html {
+        /// Directory to write the site to
+        #[arg(short, long, default_value = "pave-report")]
+        output: PathBuf,
+
+        /// Verify report to show results from [default: .pave/verify-report.json if present]
+        #[arg(long, value_name = "PATH")]
+        verify_report: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommand {
+    /// Check that no audit log entry was edited, removed, or reordered
+    Verify {
+        /// Audit log to check [default: .pave/audit.log next to .pave.toml]
+        #[arg(long, value_name = "PATH")]
+        log: Option<PathBuf>,
+    },
 }
 
 /// Output format for the `pave check` command.
@@ -393,6 +1190,42 @@ pub enum OutputFormat {
     Github,
 }
 
+/// When to color text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    /// Always color.
+    Always,
+    /// Never color.
+    Never,
+}
+
+/// Lowest severity that makes `pave check` exit with code 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ErrorOn {
+    /// Fail on errors; warnings are reported but pass.
+    #[default]
+    Errors,
+    /// Fail on errors or warnings.
+    Warnings,
+    /// Never fail for problems found; only tool errors exit non-zero.
+    Never,
+}
+
+/// How file paths are displayed in command output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum PathStyle {
+    /// Absolute paths.
+    Absolute,
+    /// Relative to the current directory.
+    Relative,
+    /// Relative to the directory containing .pave.toml.
+    #[default]
+    Workspace,
+}
+
 /// Type of git hook to install.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 pub enum HookType {
@@ -401,6 +1234,8 @@ pub enum HookType {
     PreCommit,
     /// Run validation before pushes.
     PrePush,
+    /// Run validation on commit messages.
+    CommitMsg,
 }
 
 impl HookType {
@@ -409,6 +1244,7 @@ impl HookType {
         match self {
             HookType::PreCommit => "pre-commit",
             HookType::PrePush => "pre-push",
+            HookType::CommitMsg => "commit-msg",
         }
     }
 }
@@ -417,7 +1253,7 @@ impl HookType {
 pub enum HooksCommand {
     /// Install git hooks for documentation validation
     Install {
-        /// Which hook to install: pre-commit, pre-push
+        /// Which hook to install: pre-commit, pre-push, commit-msg
         #[arg(long, value_enum, default_value = "pre-commit")]
         hook: HookType,
 
@@ -436,6 +1272,57 @@ pub enum HooksCommand {
         #[arg(long, value_enum, default_value = "pre-commit")]
         hook: HookType,
     },
+
+    /// Run a hook's steps from the [hooks] config (called by installed hooks)
+    Run {
+        /// Which hook to run
+        #[arg(value_enum)]
+        hook: HookType,
+
+        /// Also run pave verify, unless the hook's steps are configured
+        #[arg(long)]
+        verify: bool,
+
+        /// Arguments git passed to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
+/// CI system to generate configuration for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    /// GitHub Actions workflow.
+    Github,
+    /// GitLab CI job, included from .gitlab-ci.yml.
+    Gitlab,
+}
+
+#[derive(Subcommand)]
+pub enum CiCommand {
+    /// Write a CI configuration that runs pave check, lint, and optionally verify
+    Generate {
+        /// CI system: github, gitlab
+        #[arg(value_enum)]
+        provider: CiProvider,
+
+        /// Also run pave verify on changed docs
+        #[arg(long)]
+        verify: bool,
+
+        /// Where to write the configuration (default: .github/workflows/docs.yml
+        /// or .gitlab/ci/pave.yml)
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+
+        /// Print the configuration instead of writing it
+        #[arg(long, conflicts_with_all = ["output", "force"])]
+        stdout: bool,
+    },
 }
 
 impl From<DocType> for TemplateType {
@@ -444,6 +1331,8 @@ impl From<DocType> for TemplateType {
             DocType::Component => TemplateType::Component,
             DocType::Runbook => TemplateType::Runbook,
             DocType::Adr => TemplateType::Adr,
+            DocType::Postmortem => TemplateType::Postmortem,
+            DocType::Api => TemplateType::Api,
         }
     }
 }
@@ -462,4 +1351,8 @@ pub struct InitArgs {
     /// Overwrite existing files
     #[arg(long)]
     pub force: bool,
+
+    /// Also generate CI configuration: github, gitlab
+    #[arg(long, value_enum, value_name = "PROVIDER")]
+    pub ci: Option<CiProvider>,
 }
@@ -0,0 +1,197 @@
+//! CODEOWNERS file parsing.
+//!
+//! This module reads GitHub-style CODEOWNERS files and resolves the owners
+//! of a repository path. Later rules take precedence over earlier ones, as
+//! they do on GitHub.
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+
+/// Locations searched for a CODEOWNERS file, in order of precedence.
+pub const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// A single ownership rule from a CODEOWNERS file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnerRule {
+    /// The raw path pattern as written in the file.
+    pub pattern: String,
+    /// Owners assigned to matching paths (may be empty to unset ownership).
+    pub owners: Vec<String>,
+    /// Line number of the rule in the file (1-indexed).
+    pub line: usize,
+}
+
+/// Parsed CODEOWNERS rules.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeOwners {
+    /// Rules in file order.
+    pub rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Find the CODEOWNERS file for a repository root, if one exists.
+    pub fn find(root: &Path) -> Option<PathBuf> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|loc| root.join(loc))
+            .find(|p| p.is_file())
+    }
+
+    /// Load and parse a CODEOWNERS file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read CODEOWNERS file: {}", path.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Parse CODEOWNERS content.
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Vec::new();
+
+        for (idx, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = trimmed.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            let owners = parts
+                .take_while(|p| !p.starts_with('#'))
+                .map(|p| p.to_string())
+                .collect();
+
+            rules.push(OwnerRule {
+                pattern: pattern.to_string(),
+                owners,
+                line: idx + 1,
+            });
+        }
+
+        Self { rules }
+    }
+
+    /// Return the owners of a path relative to the repository root.
+    ///
+    /// The last matching rule wins. Returns an empty slice if no rule
+    /// matches or the matching rule has no owners.
+    pub fn owners_for(&self, path: &Path) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| pattern_matches(&rule.pattern, path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Check if a CODEOWNERS pattern matches a relative path.
+///
+/// Follows GitHub's semantics: a leading or interior `/` anchors the pattern
+/// to the repository root, otherwise it may match at any depth. A pattern
+/// naming a directory also matches everything beneath it, but a wildcard in
+/// the final segment (e.g. `docs/*`) only matches direct children.
+fn pattern_matches(pattern: &str, path: &Path) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.starts_with('/') || trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+    let last_segment = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    let files_only = !dir_only && last_segment.contains('*') && last_segment != "**";
+
+    if trimmed.is_empty() {
+        return false;
+    }
+
+    let Ok(glob) = Pattern::new(trimmed) else {
+        return false;
+    };
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    let components: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let count = components.len();
+
+    let starts: Vec<usize> = if anchored {
+        vec![0]
+    } else {
+        (0..count).collect()
+    };
+
+    for start in starts {
+        for end in start + 1..=count {
+            // A directory-only pattern cannot match the file itself, and a
+            // trailing wildcard cannot match an ancestor directory
+            if (dir_only && end == count) || (files_only && end < count) {
+                continue;
+            }
+            let candidate = components[start..end].join("/");
+            if glob.matches_with(&candidate, options) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let owners = CodeOwners::parse("# comment\n\n*.rs @rust-team\n/docs/ @docs # trailing\n");
+        assert_eq!(owners.rules.len(), 2);
+        assert_eq!(owners.rules[0].owners, vec!["@rust-team"]);
+        assert_eq!(owners.rules[1].owners, vec!["@docs"]);
+        assert_eq!(owners.rules[1].line, 4);
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let owners = CodeOwners::parse("* @everyone\nsrc/api/ @api-team\n");
+        assert_eq!(owners.owners_for(Path::new("README.md")), ["@everyone"]);
+        assert_eq!(
+            owners.owners_for(Path::new("src/api/handlers.rs")),
+            ["@api-team"]
+        );
+    }
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        let owners = CodeOwners::parse("*.py @py\nbuild/ @infra\n");
+        assert_eq!(owners.owners_for(Path::new("tools/gen/x.py")), ["@py"]);
+        assert_eq!(owners.owners_for(Path::new("app/build/out.js")), ["@infra"]);
+        assert!(owners.owners_for(Path::new("app/build.rs")).is_empty());
+    }
+
+    #[test]
+    fn anchored_patterns_only_match_from_root() {
+        let owners = CodeOwners::parse("/src/ @core\n");
+        assert_eq!(owners.owners_for(Path::new("src/lib.rs")), ["@core"]);
+        assert!(owners.owners_for(Path::new("vendor/src/lib.rs")).is_empty());
+    }
+
+    #[test]
+    fn single_star_does_not_cross_directories() {
+        let owners = CodeOwners::parse("docs/* @docs\n");
+        assert_eq!(owners.owners_for(Path::new("docs/a.md")), ["@docs"]);
+        assert!(owners.owners_for(Path::new("docs/nested/a.md")).is_empty());
+    }
+
+    #[test]
+    fn rule_without_owners_unsets_ownership() {
+        let owners = CodeOwners::parse("* @everyone\ngenerated/\n");
+        assert!(owners.owners_for(Path::new("generated/x.rs")).is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+//! Terminal colors for text output.
+//!
+//! `--color auto` (the default) colors stdout only when it is a terminal and
+//! `NO_COLOR` is unset; `always` and `never` override that, e.g. for CI logs
+//! that render ANSI codes. JSON and GitHub output are never colored.
+
+use std::env;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use crate::cli::ColorChoice;
+
+static COLOR_CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Set how text output is colored. Called once at startup from `--color`;
+/// later calls are ignored.
+pub fn set_color_choice(choice: ColorChoice) {
+    let _ = COLOR_CHOICE.set(choice);
+}
+
+/// Whether text written to stdout should be colored.
+pub fn enabled() -> bool {
+    let choice = COLOR_CHOICE.get().copied().unwrap_or_default();
+    resolve(
+        choice,
+        std::io::stdout().is_terminal(),
+        env::var_os("NO_COLOR").is_some(),
+    )
+}
+
+/// Whether `choice` colors output, given whether stdout is a terminal and
+/// whether `NO_COLOR` is set.
+fn resolve(choice: ColorChoice, terminal: bool, no_color: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => terminal && !no_color,
+    }
+}
+
+/// Wrap `text` in the ANSI SGR `code` if colors are enabled.
+pub fn paint(code: &str, text: impl std::fmt::Display) -> String {
+    paint_if(enabled(), code, text)
+}
+
+/// Wrap `text` in the ANSI SGR `code` if `color` is set.
+fn paint_if(color: bool, code: &str, text: impl std::fmt::Display) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Errors and failures.
+pub fn red(text: impl std::fmt::Display) -> String {
+    paint("31", text)
+}
+
+/// Warnings.
+pub fn yellow(text: impl std::fmt::Display) -> String {
+    paint("33", text)
+}
+
+/// Passes.
+pub fn green(text: impl std::fmt::Display) -> String {
+    paint("32", text)
+}
+
+/// Secondary detail such as rule names.
+pub fn dim(text: impl std::fmt::Display) -> String {
+    paint("2", text)
+}
+
+/// Headings such as file names.
+pub fn bold(text: impl std::fmt::Display) -> String {
+    paint("1", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_choice_controls_painting() {
+        assert!(resolve(ColorChoice::Always, false, true));
+        assert!(!resolve(ColorChoice::Never, true, false));
+        assert!(resolve(ColorChoice::Auto, true, false));
+        assert!(!resolve(ColorChoice::Auto, true, true));
+        assert!(!resolve(ColorChoice::Auto, false, false));
+
+        assert_eq!(paint_if(true, "31", "error"), "\x1b[31merror\x1b[0m");
+        assert_eq!(paint_if(false, "31", "error"), "error");
+    }
+}
@@ -2,15 +2,29 @@
 //!
 //! This module implements the `pave adopt` command which scans existing
 //! documentation to help users onboard pave into projects that already have docs.
+//! With `--migrate`, it also adds the missing PAVED sections to every doc that
+//! can take them without review, as `pave migrate` would, and reports the docs
+//! that need a person: ones pave can't parse cleanly or that would end up over
+//! the line limit.
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::parser::ParsedDoc;
+use crate::color;
+use crate::commands::check::skip_reason;
+use crate::commands::migrate::{analyze_file, migrate_content};
+use crate::config::PaveConfig;
+use crate::ignore::PaveIgnore;
+use crate::parser::{ParsedDoc, strict_warnings};
 use crate::rules::DocType;
 use crate::rules::detect_doc_type;
+use crate::schema::SCHEMA_VERSION;
+use crate::text_diff::{diff_lines, render_unified};
+use crate::walk::{NON_DOC_DIRS, collect_markdown_files, under_dir_named};
+use crate::workspace::Workdir;
 
 /// Arguments for the adopt command.
 pub struct AdoptArgs {
@@ -20,8 +34,11 @@ pub struct AdoptArgs {
     pub format: AdoptOutputFormat,
     /// Whether to print suggested config.
     pub suggest_config: bool,
-    /// Whether to show what pave init would create (without creating).
+    /// Whether to show what pave init would create (without creating), or
+    /// with `migrate`, the migration diffs without writing them.
     pub dry_run: bool,
+    /// Whether to add missing PAVED sections to every doc that allows it.
+    pub migrate: bool,
 }
 
 /// Output format for the adopt command.
@@ -33,7 +50,7 @@ pub enum AdoptOutputFormat {
 }
 
 /// Analysis result for a single document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DocAnalysis {
     /// Path to the document (relative).
     pub path: PathBuf,
@@ -58,7 +75,7 @@ pub struct DocAnalysis {
 }
 
 /// Summary of the adoption analysis.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AdoptionSummary {
     /// Total files found.
     pub total_files: usize,
@@ -87,8 +104,10 @@ pub struct AdoptionSummary {
 }
 
 /// Complete adoption report.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AdoptionReport {
+    /// Version of this output\'s JSON schema.
+    pub schema_version: u32,
     /// Summary statistics.
     pub summary: AdoptionSummary,
     /// Individual document analyses.
@@ -97,17 +116,68 @@ pub struct AdoptionReport {
     pub recommendations: Vec<String>,
 }
 
+/// What `pave adopt --migrate` does with a document.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigrationOutcome {
+    /// Missing sections were added (or would be, in a dry run).
+    Migrated,
+    /// The document has to be fixed by hand first.
+    NeedsManual,
+    /// The document needs no migration.
+    Skipped,
+}
+
+/// The migration of a single document.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DocMigration {
+    /// Path to the document (relative).
+    pub path: PathBuf,
+    /// What happens to the document.
+    pub outcome: MigrationOutcome,
+    /// The changes made, or why none were.
+    pub reason: String,
+    /// Content before migration.
+    #[serde(skip)]
+    pub before: String,
+    /// Content after migration, for migrated documents.
+    #[serde(skip)]
+    pub after: Option<String>,
+}
+
+/// Report of `pave adopt --migrate`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BulkMigrationReport {
+    /// Version of this output\'s JSON schema.
+    pub schema_version: u32,
+    /// Number of documents migrated.
+    pub migrated: usize,
+    /// Number of documents that need manual work.
+    pub needs_manual: usize,
+    /// Number of documents skipped.
+    pub skipped: usize,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+    /// Every document scanned, in path order.
+    pub documents: Vec<DocMigration>,
+}
+
 /// Execute the adopt command.
-pub fn execute(args: AdoptArgs) -> Result<()> {
+pub fn execute(args: AdoptArgs, workdir: &Workdir) -> Result<()> {
     // Determine the path to scan
-    let scan_path = args.path.clone().unwrap_or_else(|| PathBuf::from("."));
+    let explicit = args
+        .path
+        .as_deref()
+        .is_some_and(|path| path != Path::new("."));
+    let scan_path = workdir.resolve(args.path.as_deref().unwrap_or_else(|| Path::new(".")));
 
     // Detect docs location
-    let docs_root = detect_docs_root(&scan_path)?;
+    let docs_root = detect_docs_root(&scan_path, explicit)?;
 
     if docs_root.is_none() {
         if args.format == AdoptOutputFormat::Json {
             let report = AdoptionReport {
+                schema_version: SCHEMA_VERSION,
                 summary: AdoptionSummary {
                     total_files: 0,
                     files_with_purpose: 0,
@@ -146,11 +216,13 @@ pub fn execute(args: AdoptArgs) -> Result<()> {
     let docs_root = docs_root.unwrap();
 
     // Scan for markdown files
-    let documents = scan_docs(&docs_root)?;
+    let ignore = PaveIgnore::load(&scan_path)?;
+    let documents = scan_docs(&docs_root, &ignore)?;
 
     if documents.is_empty() {
         if args.format == AdoptOutputFormat::Json {
             let report = AdoptionReport {
+                schema_version: SCHEMA_VERSION,
                 summary: AdoptionSummary {
                     total_files: 0,
                     files_with_purpose: 0,
@@ -180,6 +252,19 @@ pub fn execute(args: AdoptArgs) -> Result<()> {
         return Ok(());
     }
 
+    if args.migrate {
+        let max_lines = match workdir.locate_config()? {
+            Some(config_path) => PaveConfig::load(&config_path)?.rules.max_lines,
+            None => PaveConfig::default().rules.max_lines,
+        };
+        let report = migrate_docs(&docs_root, &ignore, max_lines as usize, args.dry_run)?;
+        match args.format {
+            AdoptOutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            AdoptOutputFormat::Text => output_migration_report(&report),
+        }
+        return Ok(());
+    }
+
     // Generate report
     let report = generate_report(&docs_root, &documents)?;
 
@@ -206,8 +291,9 @@ pub fn execute(args: AdoptArgs) -> Result<()> {
     Ok(())
 }
 
-/// Detect the documentation root directory.
-fn detect_docs_root(scan_path: &Path) -> Result<Option<PathBuf>> {
+/// Detect the documentation root directory. `explicit` is whether
+/// `scan_path` was given rather than being the current directory.
+fn detect_docs_root(scan_path: &Path, explicit: bool) -> Result<Option<PathBuf>> {
     // If a specific file was provided, use its parent
     if scan_path.is_file() {
         return Ok(scan_path.parent().map(|p| p.to_path_buf()));
@@ -218,7 +304,7 @@ fn detect_docs_root(scan_path: &Path) -> Result<Option<PathBuf>> {
 
     // If an explicit directory was provided (not current dir),
     // check if it's a known docs directory name or if it should be scanned inside
-    if scan_path.is_dir() && scan_path != Path::new(".") {
+    if scan_path.is_dir() && explicit {
         let dir_name = scan_path
             .file_name()
             .and_then(|n| n.to_str())
@@ -265,52 +351,123 @@ fn detect_docs_root(scan_path: &Path) -> Result<Option<PathBuf>> {
     Ok(None)
 }
 
-/// Scan a directory for markdown files.
-fn scan_docs(docs_root: &Path) -> Result<Vec<DocAnalysis>> {
+/// Scan a directory for markdown files, skipping ignored paths and common
+/// non-doc directories.
+fn scan_docs(docs_root: &Path, ignore: &PaveIgnore) -> Result<Vec<DocAnalysis>> {
+    let mut files = Vec::new();
+    collect_markdown_files(docs_root, ignore, &mut files)?;
+
     let mut documents = Vec::new();
-    scan_docs_recursive(docs_root, docs_root, &mut documents)?;
+    for path in files {
+        if under_dir_named(&path, docs_root, &NON_DOC_DIRS) {
+            continue;
+        }
+        if let Some(analysis) = analyze_document(&path, docs_root)? {
+            documents.push(analysis);
+        }
+    }
     Ok(documents)
 }
 
-/// Recursively scan directory for markdown files.
-fn scan_docs_recursive(
+/// Plan the migration of every document under `docs_root`, then write the
+/// migrated ones unless `dry_run` is set.
+fn migrate_docs(
     docs_root: &Path,
-    current: &Path,
-    documents: &mut Vec<DocAnalysis>,
-) -> Result<()> {
-    let entries = fs::read_dir(current)
-        .with_context(|| format!("failed to read directory: {}", current.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip common non-doc directories
-            let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            if matches!(
-                dir_name,
-                "node_modules"
-                    | "target"
-                    | ".git"
-                    | ".github"
-                    | "templates"
-                    | "_site"
-                    | ".pave"
-                    | "vendor"
-                    | "build"
-            ) {
-                continue;
-            }
-            scan_docs_recursive(docs_root, &path, documents)?;
-        } else if path.extension().is_some_and(|ext| ext == "md")
-            && let Some(analysis) = analyze_document(&path, docs_root)?
-        {
-            documents.push(analysis);
+    ignore: &PaveIgnore,
+    max_lines: usize,
+    dry_run: bool,
+) -> Result<BulkMigrationReport> {
+    let mut files = Vec::new();
+    collect_markdown_files(docs_root, ignore, &mut files)?;
+    files.retain(|path| !under_dir_named(path, docs_root, &NON_DOC_DIRS));
+    files.sort();
+
+    let mut report = BulkMigrationReport {
+        schema_version: SCHEMA_VERSION,
+        migrated: 0,
+        needs_manual: 0,
+        skipped: 0,
+        dry_run,
+        documents: Vec::new(),
+    };
+    for path in &files {
+        let migration = plan_migration(path, docs_root, max_lines)?;
+        match migration.outcome {
+            MigrationOutcome::Migrated => report.migrated += 1,
+            MigrationOutcome::NeedsManual => report.needs_manual += 1,
+            MigrationOutcome::Skipped => report.skipped += 1,
         }
+        if !dry_run && let Some(after) = &migration.after {
+            fs::write(path, after)
+                .with_context(|| format!("failed to write file: {}", path.display()))?;
+        }
+        report.documents.push(migration);
     }
+    Ok(report)
+}
 
-    Ok(())
+/// Decide whether a document can be migrated without review.
+///
+/// Documents with unclosed code blocks or frontmatter, or unknown markers,
+/// could be mangled by section insertion, and ones that would end up over
+/// `max_lines` need splitting first; both are left for a person.
+fn plan_migration(path: &Path, docs_root: &Path, max_lines: usize) -> Result<DocMigration> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read file: {}", path.display()))?;
+    let mut migration = DocMigration {
+        path: path.strip_prefix(docs_root).unwrap_or(path).to_path_buf(),
+        outcome: MigrationOutcome::Skipped,
+        reason: String::new(),
+        before: content,
+        after: None,
+    };
+
+    if let Some(reason) = skip_reason(path) {
+        migration.reason = reason.to_string();
+        return Ok(migration);
+    }
+    if let Some(warning) = strict_warnings(&migration.before).first() {
+        migration.outcome = MigrationOutcome::NeedsManual;
+        migration.reason = format!("line {}: {}", warning.line, warning.message);
+        return Ok(migration);
+    }
+    let Some(analysis) = analyze_file(path, docs_root, &None)? else {
+        migration.reason = "already has the PAVED sections for its type".to_string();
+        return Ok(migration);
+    };
+
+    let after = migrate_content(
+        &migration.before,
+        &analysis.renamed_sections,
+        &analysis.missing_sections,
+    );
+    let line_count = after.lines().count();
+    if line_count > max_lines {
+        migration.outcome = MigrationOutcome::NeedsManual;
+        migration.reason = format!(
+            "would be {} lines, over the {} line limit; shorten it or run `pave split` first",
+            line_count, max_lines
+        );
+        return Ok(migration);
+    }
+
+    let mut changes: Vec<String> = analysis
+        .renamed_sections
+        .iter()
+        .map(|rename| format!("renamed {} to {}", rename.from, rename.to))
+        .collect();
+    if !analysis.missing_sections.is_empty() {
+        let added: Vec<&str> = analysis
+            .missing_sections
+            .iter()
+            .map(|section| section.name.as_str())
+            .collect();
+        changes.push(format!("added {}", added.join(", ")));
+    }
+    migration.outcome = MigrationOutcome::Migrated;
+    migration.reason = format!("{} ({})", changes.join("; "), analysis.doc_type);
+    migration.after = Some(after);
+    Ok(migration)
 }
 
 /// Analyze a single markdown document.
@@ -329,6 +486,8 @@ fn analyze_document(path: &Path, docs_root: &Path) -> Result<Option<DocAnalysis>
         DocType::Component => "component",
         DocType::Runbook => "runbook",
         DocType::Adr => "adr",
+        DocType::Postmortem => "postmortem",
+        DocType::Api => "api",
         DocType::Other => "other",
     }
     .to_string();
@@ -429,6 +588,7 @@ fn generate_report(docs_root: &Path, documents: &[DocAnalysis]) -> Result<Adopti
     }
 
     Ok(AdoptionReport {
+        schema_version: SCHEMA_VERSION,
         summary: AdoptionSummary {
             total_files,
             files_with_purpose,
@@ -555,6 +715,59 @@ fn output_suggested_config(report: &AdoptionReport) {
 }
 
 /// Output dry-run information.
+/// Output the `--migrate` report, with diffs of the migrated documents in a
+/// dry run.
+fn output_migration_report(report: &BulkMigrationReport) {
+    if report.dry_run {
+        for doc in &report.documents {
+            let Some(after) = &doc.after else {
+                continue;
+            };
+            println!("--- a/{}", doc.path.display());
+            println!("+++ b/{}", doc.path.display());
+            for line in render_unified(&diff_lines(&doc.before, after), 3, color::enabled()) {
+                println!("{}", line);
+            }
+            println!();
+        }
+    }
+
+    println!(
+        "{} {} doc(s), {} need manual work, {} skipped",
+        if report.dry_run {
+            "Would migrate"
+        } else {
+            "Migrated"
+        },
+        report.migrated,
+        report.needs_manual,
+        report.skipped
+    );
+    for (outcome, heading) in [
+        (MigrationOutcome::Migrated, "Migrated:"),
+        (MigrationOutcome::NeedsManual, "Needs manual work:"),
+        (MigrationOutcome::Skipped, "Skipped:"),
+    ] {
+        let docs: Vec<&DocMigration> = report
+            .documents
+            .iter()
+            .filter(|doc| doc.outcome == outcome)
+            .collect();
+        if docs.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}", heading);
+        for doc in docs {
+            println!("  {}: {}", doc.path.display(), doc.reason);
+        }
+    }
+    if report.dry_run && report.migrated > 0 {
+        println!();
+        println!("Run without --dry-run to apply these changes.");
+    }
+}
+
 fn output_dry_run(report: &AdoptionReport) {
     println!("Dry run: What 'pave init' would create:");
     println!();
@@ -607,7 +820,7 @@ mod tests {
         let docs_dir = dir.path().join("docs");
         fs::create_dir_all(&docs_dir).unwrap();
 
-        let result = detect_docs_root(dir.path()).unwrap();
+        let result = detect_docs_root(dir.path(), true).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap(), docs_dir);
     }
@@ -618,7 +831,7 @@ mod tests {
         let docs_dir = dir.path().join("documentation");
         fs::create_dir_all(&docs_dir).unwrap();
 
-        let result = detect_docs_root(dir.path()).unwrap();
+        let result = detect_docs_root(dir.path(), true).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap(), docs_dir);
     }
@@ -628,7 +841,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join("README.md"), "# README").unwrap();
 
-        let result = detect_docs_root(dir.path()).unwrap();
+        let result = detect_docs_root(dir.path(), true).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap(), dir.path());
     }
@@ -637,7 +850,7 @@ mod tests {
     fn detect_docs_root_returns_none_when_no_docs() {
         let dir = TempDir::new().unwrap();
 
-        let result = detect_docs_root(dir.path()).unwrap();
+        let result = detect_docs_root(dir.path(), true).unwrap();
         assert!(result.is_none());
     }
 
@@ -648,7 +861,7 @@ mod tests {
         let custom_docs = dir.path().join("docs");
         fs::create_dir_all(&custom_docs).unwrap();
 
-        let result = detect_docs_root(&custom_docs).unwrap();
+        let result = detect_docs_root(&custom_docs, true).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap(), custom_docs);
     }
@@ -661,7 +874,7 @@ mod tests {
         let docs_dir = project_dir.join("docs");
         fs::create_dir_all(&docs_dir).unwrap();
 
-        let result = detect_docs_root(&project_dir).unwrap();
+        let result = detect_docs_root(&project_dir, true).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap(), docs_dir);
     }
@@ -789,7 +1002,7 @@ Good performance.
             "# Deploy\n## Steps\n1. Deploy",
         );
 
-        let documents = scan_docs(dir.path()).unwrap();
+        let documents = scan_docs(dir.path(), &PaveIgnore::default()).unwrap();
         assert_eq!(documents.len(), 3);
     }
 
@@ -804,7 +1017,7 @@ Good performance.
             "# Should be skipped",
         );
 
-        let documents = scan_docs(dir.path()).unwrap();
+        let documents = scan_docs(dir.path(), &PaveIgnore::default()).unwrap();
         assert_eq!(documents.len(), 1);
     }
 
@@ -942,7 +1155,7 @@ During deployments.
 "#,
         );
 
-        let documents = scan_docs(&docs_dir).unwrap();
+        let documents = scan_docs(&docs_dir, &PaveIgnore::default()).unwrap();
         let report = generate_report(&docs_dir, &documents).unwrap();
 
         assert_eq!(report.summary.total_files, 2);
@@ -950,4 +1163,71 @@ During deployments.
         assert_eq!(report.summary.runbook_count, 1);
         assert_eq!(report.summary.files_with_purpose, 1); // Only auth has Purpose
     }
+
+    #[test]
+    fn migrate_docs_sorts_docs_into_migrated_manual_and_skipped() {
+        let dir = TempDir::new().unwrap();
+        let docs_dir = dir.path().join("docs");
+        create_test_doc(
+            &docs_dir,
+            "guide.md",
+            "# Guide\n\n## Overview\nWhy.\n\n## Examples\n```bash\nguide\n```\n",
+        );
+        create_test_doc(
+            &docs_dir,
+            "broken.md",
+            "# Broken\n\n```bash\nnever closed\n",
+        );
+        create_test_doc(
+            &docs_dir,
+            "long.md",
+            &format!("# Long\n\n{}", "text\n".repeat(40)),
+        );
+        create_test_doc(&docs_dir, "index.md", "# Index\n");
+
+        let report = migrate_docs(&docs_dir, &PaveIgnore::default(), 40, true).unwrap();
+        let outcomes: Vec<(&str, MigrationOutcome, &str)> = report
+            .documents
+            .iter()
+            .map(|doc| (doc.path.to_str().unwrap(), doc.outcome, doc.reason.as_str()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (
+                    "broken.md",
+                    MigrationOutcome::NeedsManual,
+                    "line 3: Code block is never closed"
+                ),
+                (
+                    "guide.md",
+                    MigrationOutcome::Migrated,
+                    "renamed Overview to Purpose; added Verification (other)"
+                ),
+                (
+                    "index.md",
+                    MigrationOutcome::Skipped,
+                    "index.md is a navigation page"
+                ),
+                (
+                    "long.md",
+                    MigrationOutcome::NeedsManual,
+                    "would be 62 lines, over the 40 line limit; shorten it or run `pave split` first"
+                ),
+            ]
+        );
+        assert_eq!(
+            (report.migrated, report.needs_manual, report.skipped),
+            (1, 2, 1)
+        );
+        let guide = docs_dir.join("guide.md");
+        assert!(fs::read_to_string(&guide).unwrap().contains("## Overview"));
+
+        migrate_docs(&docs_dir, &PaveIgnore::default(), 40, false).unwrap();
+        let migrated = fs::read_to_string(&guide).unwrap();
+        assert!(migrated.contains("## Purpose"));
+        assert!(migrated.contains("## Verification"));
+        let report = migrate_docs(&docs_dir, &PaveIgnore::default(), 40, true).unwrap();
+        assert_eq!(report.migrated, 0);
+    }
 }
@@ -0,0 +1,51 @@
+//! Implementation of the `pave audit` commands.
+//!
+//! `pave audit verify` checks the hash chain of the log that `pave verify`
+//! writes with `[verify] audit_log = true`. See [`crate::audit`].
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::audit::{AUDIT_LOG_PATH, verify_chain};
+use crate::exit::Violations;
+use crate::workspace::Workdir;
+
+/// Check the audit log at `log`, or the project's log by default.
+///
+/// Fails at the first entry that was edited, removed, or reordered.
+pub fn verify(log: Option<PathBuf>, workdir: &Workdir) -> Result<()> {
+    let path = match log {
+        Some(path) => workdir.resolve(&path),
+        None => {
+            let config_path = workdir.find_config()?;
+            let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+            config_dir.join(AUDIT_LOG_PATH)
+        }
+    };
+    if !path.is_file() {
+        anyhow::bail!(
+            "No audit log at {} (set `audit_log = true` in [verify] to record one)",
+            path.display()
+        );
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read audit log: {}", path.display()))?;
+    match verify_chain(&content) {
+        Ok(summary) => {
+            println!(
+                "{}: chain intact, {} entr{}",
+                path.display(),
+                summary.entries,
+                if summary.entries == 1 { "y" } else { "ies" }
+            );
+            println!("Head: {}", summary.head);
+            Ok(())
+        }
+        Err(broken) => anyhow::bail!(Violations(format!(
+            "{}: chain broken at {}",
+            path.display(),
+            broken
+        ))),
+    }
+}
@@ -0,0 +1,435 @@
+//! Implementation of the `pave bench` command for timing pave on the docs.
+//!
+//! Times each stage pave puts a document through (finding the docs,
+//! reading, parsing, `pave check`'s rules, and each `pave lint` rule) over
+//! the whole corpus, one document and one thread at a time. Each stage is
+//! summarized with percentiles across documents, and the slowest documents
+//! are listed so pathological ones stand out.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::cli::BenchOutputFormat;
+use crate::commands::check::{CheckResults, check_content, should_skip_file};
+use crate::commands::lint::time_rules;
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
+
+/// Arguments for the `pave bench` command.
+#[derive(Clone)]
+pub struct BenchArgs {
+    /// Files or directories to benchmark.
+    pub paths: Vec<PathBuf>,
+    /// Output format.
+    pub format: BenchOutputFormat,
+    /// Times to repeat each measurement, keeping the fastest.
+    pub runs: usize,
+    /// Number of slowest documents to list.
+    pub top: usize,
+}
+
+/// Timing of one stage or rule across documents, in milliseconds.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct Timing {
+    /// Stage or rule name.
+    pub name: String,
+    /// Time across all documents.
+    pub total_ms: f64,
+    /// Median time per document.
+    pub p50_ms: f64,
+    /// 90th percentile time per document.
+    pub p90_ms: f64,
+    /// 99th percentile time per document.
+    pub p99_ms: f64,
+    /// Slowest time for a document.
+    pub max_ms: f64,
+}
+
+/// Time spent on one document, in milliseconds.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct DocTiming {
+    /// Path relative to the project root.
+    pub file: PathBuf,
+    /// Number of lines.
+    pub lines: usize,
+    /// Time across all stages.
+    pub total_ms: f64,
+    /// Reading the file.
+    pub read_ms: f64,
+    /// Parsing the document.
+    pub parse_ms: f64,
+    /// Running `pave check`'s rules.
+    pub check_ms: f64,
+    /// Running every lint rule.
+    pub lint_ms: f64,
+    /// Lint rule that took longest on this document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slowest_rule: Option<String>,
+}
+
+/// Results of the bench command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BenchResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Number of documents benchmarked.
+    pub files: usize,
+    /// Times each measurement was repeated; the fastest run counts.
+    pub runs: usize,
+    /// Time to find the documents, in milliseconds.
+    pub walk_ms: f64,
+    /// Per-document stages: `read`, `parse`, `check`, and `lint`.
+    pub stages: Vec<Timing>,
+    /// Lint rules, slowest first.
+    pub lint_rules: Vec<Timing>,
+    /// Slowest documents, slowest first.
+    pub slowest: Vec<DocTiming>,
+    /// Per-document times behind `stages` and `lint_rules`.
+    #[serde(skip)]
+    durations: Durations,
+}
+
+/// Per-document times of each stage and lint rule, in document order.
+#[derive(Debug, Default)]
+struct Durations {
+    /// `read`, `parse`, `check`, and `lint`.
+    stages: [Vec<Duration>; 4],
+    /// Each lint rule.
+    rules: Vec<(String, Vec<Duration>)>,
+}
+
+/// Names of [`Durations::stages`].
+const STAGES: [&str; 4] = ["read", "parse", "check", "lint"];
+
+impl Durations {
+    /// Summarize the stages, and the lint rules slowest first.
+    fn timings(&self) -> (Vec<Timing>, Vec<Timing>) {
+        let stages = STAGES
+            .iter()
+            .zip(&self.stages)
+            .map(|(name, durations)| timing(name, durations))
+            .collect();
+        let mut rules: Vec<Timing> = self
+            .rules
+            .iter()
+            .map(|(name, durations)| timing(name, durations))
+            .collect();
+        rules.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        (stages, rules)
+    }
+}
+
+impl Merge for BenchResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        for doc in &mut self.slowest {
+            doc.file = prefixed(package, &doc.file);
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.files += other.files;
+        self.walk_ms += other.walk_ms;
+        for (merged, durations) in self.durations.stages.iter_mut().zip(other.durations.stages) {
+            merged.extend(durations);
+        }
+        for (name, durations) in other.durations.rules {
+            match self
+                .durations
+                .rules
+                .iter_mut()
+                .find(|(rule, _)| *rule == name)
+            {
+                Some((_, merged)) => merged.extend(durations),
+                None => self.durations.rules.push((name, durations)),
+            }
+        }
+        (self.stages, self.lint_rules) = self.durations.timings();
+        self.slowest.extend(other.slowest);
+        self.slowest
+            .sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+    }
+}
+
+impl PackageCommand for BenchArgs {
+    type Results = BenchResults;
+
+    fn is_json(&self) -> bool {
+        matches!(self.format, BenchOutputFormat::Json)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<BenchResults>>> {
+        Ok(run(self, workdir)?.map(|results| Outcome {
+            results,
+            status: Ok(()),
+        }))
+    }
+
+    fn output(&self, mut results: BenchResults) -> Result<()> {
+        results.slowest.truncate(self.top);
+        match self.format {
+            BenchOutputFormat::Text => print!("{}", render_text(&results)),
+            BenchOutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        }
+        Ok(())
+    }
+}
+
+/// Execute the `pave bench` command.
+pub fn execute(args: BenchArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Benchmark the documents selected by `args`, leaving output to the caller.
+fn run(args: &BenchArgs, workdir: &Workdir) -> Result<Option<BenchResults>> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let paths = if args.paths.is_empty() {
+        vec![config_dir.join(&config.docs.root)]
+    } else {
+        args.paths
+            .iter()
+            .map(|path| workdir.resolve(path))
+            .collect()
+    };
+    let ignore = load_ignore(config_dir, &config)?;
+    let mut files = Vec::new();
+    let walk = fastest(args.runs, || {
+        files = find_markdown_files(&paths, &ignore)?;
+        Ok(())
+    })?;
+    files.retain(|file| !should_skip_file(file));
+    if files.is_empty() {
+        eprintln!("No markdown files found to benchmark");
+        return Ok(None);
+    }
+
+    eprintln!("Benchmarking {} documents...", files.len());
+    benchmark(&files, walk, &config, config_dir, args.runs).map(Some)
+}
+
+/// Time every stage on `files`, which took `walk` to find. All documents
+/// are listed in [`BenchResults::slowest`]; output keeps the slowest few.
+fn benchmark(
+    files: &[PathBuf],
+    walk: Duration,
+    config: &PaveConfig,
+    config_dir: &Path,
+    runs: usize,
+) -> Result<BenchResults> {
+    let mut docs = Vec::new();
+    let (mut read, mut parse, mut check) = (Vec::new(), Vec::new(), Vec::new());
+    for file in files {
+        let mut content = String::new();
+        read.push(fastest(runs, || {
+            content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            Ok(())
+        })?);
+        let mut lines = 0;
+        let parsed = fastest(runs, || {
+            lines = ParsedDoc::parse_content(file.clone(), &content)?.line_count;
+            Ok(())
+        })?;
+        parse.push(parsed);
+        // Check parses the document itself; leave that out of its time
+        let checked = fastest(runs, || {
+            check_content(file, &content, config, &mut CheckResults::new())
+        })?;
+        check.push(checked.saturating_sub(parsed));
+        docs.push((file.strip_prefix(config_dir).unwrap_or(file), lines));
+    }
+
+    let rules = time_rules(files, config, config_dir, runs)?;
+    let lint: Vec<Duration> = (0..files.len())
+        .map(|i| rules.iter().map(|(_, durations)| durations[i]).sum())
+        .collect();
+
+    let mut slowest: Vec<DocTiming> = docs
+        .into_iter()
+        .enumerate()
+        .map(|(i, (file, lines))| DocTiming {
+            file: file.to_path_buf(),
+            lines,
+            total_ms: ms(read[i] + parse[i] + check[i] + lint[i]),
+            read_ms: ms(read[i]),
+            parse_ms: ms(parse[i]),
+            check_ms: ms(check[i]),
+            lint_ms: ms(lint[i]),
+            slowest_rule: rules
+                .iter()
+                .max_by_key(|(_, durations)| durations[i])
+                .map(|(name, _)| name.to_string()),
+        })
+        .collect();
+    slowest.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+
+    let durations = Durations {
+        stages: [read, parse, check, lint],
+        rules: rules
+            .into_iter()
+            .map(|(name, durations)| (name.to_string(), durations))
+            .collect(),
+    };
+    let (stages, lint_rules) = durations.timings();
+    Ok(BenchResults {
+        schema_version: SCHEMA_VERSION,
+        files: files.len(),
+        runs: runs.max(1),
+        walk_ms: ms(walk),
+        stages,
+        lint_rules,
+        slowest,
+        durations,
+    })
+}
+
+/// Run `f` `runs` times (at least once) and return the fastest run's time.
+fn fastest(runs: usize, mut f: impl FnMut() -> Result<()>) -> Result<Duration> {
+    let mut fastest = Duration::MAX;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        f()?;
+        fastest = fastest.min(start.elapsed());
+    }
+    Ok(fastest)
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Summarize per-document `durations` as percentiles.
+fn timing(name: &str, durations: &[Duration]) -> Timing {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    Timing {
+        name: name.to_string(),
+        total_ms: ms(sorted.iter().sum()),
+        p50_ms: ms(percentile(&sorted, 50)),
+        p90_ms: ms(percentile(&sorted, 90)),
+        p99_ms: ms(percentile(&sorted, 99)),
+        max_ms: ms(sorted.last().copied().unwrap_or_default()),
+    }
+}
+
+/// The `p`th percentile of `sorted` by the nearest-rank method.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Render the results as aligned tables.
+fn render_text(results: &BenchResults) -> String {
+    let mut out = format!(
+        "{} documents, fastest of {} run{}\nFinding documents: {:.2} ms\n",
+        results.files,
+        results.runs,
+        if results.runs == 1 { "" } else { "s" },
+        results.walk_ms
+    );
+    for (title, timings) in [
+        ("Stage", &results.stages),
+        ("Lint rule", &results.lint_rules),
+    ] {
+        let width = timings
+            .iter()
+            .map(|t| t.name.len())
+            .chain([title.len()])
+            .max()
+            .unwrap_or(0);
+        out.push_str(&format!(
+            "\n{:<width$}  {:>10}  {:>8}  {:>8}  {:>8}  {:>8}\n",
+            title, "Total ms", "p50", "p90", "p99", "Max"
+        ));
+        for t in timings {
+            out.push_str(&format!(
+                "{:<width$}  {:>10.2}  {:>8.3}  {:>8.3}  {:>8.3}  {:>8.3}\n",
+                t.name, t.total_ms, t.p50_ms, t.p90_ms, t.p99_ms, t.max_ms
+            ));
+        }
+    }
+
+    out.push_str("\nSlowest documents:\n");
+    for doc in &results.slowest {
+        out.push_str(&format!(
+            "{:>8.2} ms  {} ({} lines): parse {:.2}, check {:.2}, lint {:.2}",
+            doc.total_ms,
+            doc.file.display(),
+            doc.lines,
+            doc.parse_ms,
+            doc.check_ms,
+            doc.lint_ms
+        ));
+        if let Some(rule) = &doc.slowest_rule {
+            out.push_str(&format!(" (mostly {})", rule));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let durations: Vec<Duration> = (1..=10).rev().map(Duration::from_millis).collect();
+        let t = timing("parse", &durations);
+        assert_eq!(t.total_ms, 55.0);
+        assert_eq!(t.p50_ms, 5.0);
+        assert_eq!(t.p90_ms, 9.0);
+        assert_eq!(t.p99_ms, 10.0);
+        assert_eq!(t.max_ms, 10.0);
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+
+    #[test]
+    fn benchmarks_every_stage_and_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("short.md"), "# Short\n\nA line.\n").unwrap();
+        let long = format!("# Long\n\n{}", "A line of prose.\n\n".repeat(2000));
+        fs::write(docs.join("long.md"), long).unwrap();
+        let files = vec![docs.join("short.md"), docs.join("long.md")];
+        let config = PaveConfig::default();
+
+        let results = benchmark(&files, Duration::ZERO, &config, temp_dir.path(), 1).unwrap();
+        assert_eq!(results.files, 2);
+        let stages: Vec<&str> = results.stages.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(stages, ["read", "parse", "check", "lint"]);
+        assert!(!results.lint_rules.is_empty());
+        assert_eq!(results.slowest.len(), 2);
+        assert_eq!(results.slowest[0].file, Path::new("docs/long.md"));
+        assert_eq!(results.slowest[0].lines, 4002);
+
+        let text = render_text(&results);
+        assert!(text.starts_with("2 documents, fastest of 1 run\n"));
+        assert!(text.contains("docs/long.md (4002 lines)"));
+    }
+}
@@ -5,21 +5,34 @@
 
 use anyhow::{Context, Result};
 use pulldown_cmark::{Options, Parser, html};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::cli::BuildFormat;
+use crate::commands::build_mdbook;
+use crate::config::PaveConfig;
+use crate::ignore::PaveIgnore;
+use crate::include::{self, INCLUDES_DIR};
+use crate::parser::{DocState, ParsedDoc};
+use crate::vars;
+use crate::walk::load_ignore;
+use crate::workspace::Workdir;
 
 /// Arguments for the `pave build` command.
 pub struct BuildArgs {
-    /// Output directory for the built site.
-    pub output: PathBuf,
+    /// Output directory, or None for the format's default.
+    pub output: Option<PathBuf>,
+    /// Also publish documents in the draft state.
+    pub include_drafts: bool,
+    /// Whether to build the HTML site or an mdBook project.
+    pub format: BuildFormat,
 }
 
 /// Execute the `pave build` command.
-pub fn execute(args: BuildArgs) -> Result<()> {
-    let config = load_config()?;
-    let docs_root = &config.docs.root;
+pub fn execute(args: BuildArgs, workdir: &Workdir) -> Result<()> {
+    let (config, config_dir) = load_config(workdir)?;
+    let docs_root = &config_dir.join(&config.docs.root);
 
     // Check if docs directory exists
     if !docs_root.exists() {
@@ -29,11 +42,23 @@ pub fn execute(args: BuildArgs) -> Result<()> {
         );
     }
 
+    let ignore = load_ignore(&config_dir, &config)?;
+    if args.format == BuildFormat::Mdbook {
+        let output_dir = workdir.resolve(&args.output.unwrap_or_else(|| PathBuf::from("book")));
+        return build_book(
+            docs_root,
+            &output_dir,
+            &config_dir,
+            args.include_drafts,
+            &ignore,
+            &config.vars,
+        );
+    }
+
     // Find the site source directory
-    let cwd = std::env::current_dir().context("failed to get current directory")?;
-    let site_source = find_site_source(&cwd)?;
+    let site_source = find_site_source(&workdir.dir)?;
 
-    let output_dir = &args.output;
+    let output_dir = &workdir.resolve(&args.output.unwrap_or_else(|| PathBuf::from("_site")));
 
     // Clean output directory if it exists
     if output_dir.exists() {
@@ -56,44 +81,85 @@ pub fn execute(args: BuildArgs) -> Result<()> {
     // Step 2: Copy and process pave docs
     let paved_docs_dest = output_dir.join("paved-docs");
     fs::create_dir_all(&paved_docs_dest)?;
-    copy_and_process_docs(docs_root, &paved_docs_dest)?;
+    copy_and_process_docs(
+        docs_root,
+        &paved_docs_dest,
+        docs_root,
+        args.include_drafts,
+        &ignore,
+        &config.vars,
+    )?;
 
     // Step 3: Process user guide docs from site/docs
     let site_docs = site_source.join("docs");
     if site_docs.exists() {
         let docs_dest = output_dir.join("docs");
         fs::create_dir_all(&docs_dest)?;
-        copy_and_process_docs(&site_docs, &docs_dest)?;
+        copy_and_process_docs(
+            &site_docs,
+            &docs_dest,
+            &site_docs,
+            args.include_drafts,
+            &ignore,
+            &config.vars,
+        )?;
     }
 
     // Step 4: Build HTML from all markdown files
-    build_html_files(output_dir)?;
+    build_html_files(output_dir, &site_source)?;
 
     println!("Built site at: {}", output_dir.display());
 
     Ok(())
 }
 
-/// Load pave configuration from current directory or parents.
-fn load_config() -> Result<PaveConfig> {
-    let cwd = std::env::current_dir().context("failed to get current directory")?;
+/// Write the docs as an mdBook project, replacing its generated files.
+fn build_book(
+    docs_root: &Path,
+    output_dir: &Path,
+    project_dir: &Path,
+    include_drafts: bool,
+    ignore: &PaveIgnore,
+    vars: &BTreeMap<String, String>,
+) -> Result<()> {
+    // Only the generated sources are replaced, so mdBook's own build
+    // directory and any theme customizations survive a rebuild
+    let src = output_dir.join("src");
+    if src.exists() {
+        fs::remove_dir_all(&src)
+            .with_context(|| format!("failed to clean directory: {}", src.display()))?;
+    }
 
-    // Search for config file in current directory and parents
-    let mut search_path = cwd.as_path();
-    loop {
-        let config_path = search_path.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return PaveConfig::load(&config_path);
-        }
+    let title = project_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Documentation".to_string());
+    let count =
+        build_mdbook::write_book(docs_root, output_dir, &title, include_drafts, ignore, vars)?;
+
+    println!(
+        "Built mdBook with {} document{} at: {}",
+        count,
+        if count == 1 { "" } else { "s" },
+        output_dir.display()
+    );
+    println!("Run `mdbook build {}` to render it.", output_dir.display());
 
-        match search_path.parent() {
-            Some(parent) => search_path = parent,
-            None => break,
+    Ok(())
+}
+
+/// Load pave configuration from the working directory or its parents, with
+/// the directory paths in it are relative to.
+fn load_config(workdir: &Workdir) -> Result<(PaveConfig, PathBuf)> {
+    match workdir.locate_config()? {
+        Some(config_path) => {
+            let config = PaveConfig::load(&config_path)?;
+            let config_dir = config_path.parent().unwrap_or(&workdir.dir);
+            Ok((config, workdir.resolve(config_dir)))
         }
+        // No config found, use defaults
+        None => Ok((PaveConfig::default(), workdir.dir.clone())),
     }
-
-    // No config found, use defaults
-    Ok(PaveConfig::default())
 }
 
 /// Find the site source directory.
@@ -154,7 +220,21 @@ where
 }
 
 /// Copy and process documentation files.
-fn copy_and_process_docs(source: &Path, dest: &Path) -> Result<()> {
+///
+/// Draft documents are left out unless `include_drafts` is set, and paths
+/// ignored by the project are always left out. Includes are expanded and
+/// `vars` placeholders rendered.
+fn copy_and_process_docs(
+    source: &Path,
+    dest: &Path,
+    docs_root: &Path,
+    include_drafts: bool,
+    ignore: &PaveIgnore,
+    vars: &BTreeMap<String, String>,
+) -> Result<()> {
+    if ignore.is_ignored(source) {
+        return Ok(());
+    }
     if source.is_dir() {
         fs::create_dir_all(dest)?;
         for entry in fs::read_dir(source)? {
@@ -162,15 +242,33 @@ fn copy_and_process_docs(source: &Path, dest: &Path) -> Result<()> {
             let src_path = entry.path();
             let dest_path = dest.join(entry.file_name());
 
-            // Skip templates directory
-            if src_path.is_dir() && src_path.file_name().is_some_and(|n| n == "templates") {
+            // Skip templates and include snippets
+            if src_path.is_dir()
+                && src_path
+                    .file_name()
+                    .is_some_and(|n| n == "templates" || n == INCLUDES_DIR)
+            {
                 continue;
             }
 
-            copy_and_process_docs(&src_path, &dest_path)?;
+            copy_and_process_docs(
+                &src_path,
+                &dest_path,
+                docs_root,
+                include_drafts,
+                ignore,
+                vars,
+            )?;
         }
     } else if source.extension().is_some_and(|ext| ext == "md") {
         let content = fs::read_to_string(source)?;
+        let state = ParsedDoc::parse_content(source.to_path_buf(), &content)?.state();
+        if state == DocState::Draft && !include_drafts {
+            return Ok(());
+        }
+        let content = include::expand(&content, source, docs_root)
+            .with_context(|| format!("failed to expand includes in {}", source.display()))?;
+        let content = vars::render(&content, vars);
         let processed = process_markdown(&content, source)?;
         fs::write(dest, processed)?;
     } else {
@@ -222,11 +320,10 @@ fn extract_title(content: &str) -> Option<String> {
     None
 }
 
-/// Build HTML files from all markdown files in the output directory.
-fn build_html_files(output_dir: &Path) -> Result<()> {
+/// Build HTML files from all markdown files in the output directory, with
+/// the layouts in `site_source`.
+fn build_html_files(output_dir: &Path, site_source: &Path) -> Result<()> {
     // Load layouts
-    let cwd = std::env::current_dir()?;
-    let site_source = find_site_source(&cwd)?;
     let default_layout = fs::read_to_string(site_source.join("_layouts/default.html"))
         .context("failed to read default layout")?;
     let doc_layout = fs::read_to_string(site_source.join("_layouts/doc.html"))
@@ -632,10 +729,81 @@ mod tests {
         fs::create_dir_all(&source).unwrap();
         fs::write(source.join("test.md"), "# Test\n\nContent").unwrap();
 
-        copy_and_process_docs(&source, &dest).unwrap();
+        copy_and_process_docs(
+            &source,
+            &dest,
+            &source,
+            false,
+            &PaveIgnore::default(),
+            &BTreeMap::new(),
+        )
+        .unwrap();
 
         let output = fs::read_to_string(dest.join("test.md")).unwrap();
         assert!(output.contains("layout: doc"));
         assert!(output.contains("title: \"Test\""));
     }
+
+    #[test]
+    fn test_copy_and_process_docs_expands_includes() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        let dest = temp.path().join("dest");
+        fs::create_dir_all(source.join("_includes")).unwrap();
+        fs::write(source.join("_includes/setup.md"), "Run `make setup`.\n").unwrap();
+        fs::write(
+            source.join("deploy.md"),
+            "# Deploy\n\n<!-- pave:include _includes/setup.md -->\n",
+        )
+        .unwrap();
+
+        copy_and_process_docs(
+            &source,
+            &dest,
+            &source,
+            false,
+            &PaveIgnore::default(),
+            &BTreeMap::new(),
+        )
+        .unwrap();
+
+        let output = fs::read_to_string(dest.join("deploy.md")).unwrap();
+        assert!(output.contains("Run `make setup`."));
+        assert!(!output.contains("pave:include"));
+        assert!(!dest.join("_includes").exists());
+    }
+
+    #[test]
+    fn test_copy_and_process_docs_skips_drafts() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join("draft.md"),
+            "---\npave:\n  state: draft\n---\n# Draft\n",
+        )
+        .unwrap();
+
+        copy_and_process_docs(
+            &source,
+            &temp.path().join("dest"),
+            &source,
+            false,
+            &PaveIgnore::default(),
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(!temp.path().join("dest/draft.md").exists());
+
+        copy_and_process_docs(
+            &source,
+            &temp.path().join("all"),
+            &source,
+            true,
+            &PaveIgnore::default(),
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(temp.path().join("all/draft.md").exists());
+    }
 }
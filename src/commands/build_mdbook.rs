@@ -0,0 +1,200 @@
+//! Export PAVED docs as an mdBook project.
+//!
+//! This module implements `pave build --format mdbook`, which writes a
+//! `book.toml` and a `src/` directory with the docs and a `SUMMARY.md`
+//! grouping them into chapters by document type, the same way `pave index`
+//! does. Run `mdbook build` in the output directory to publish it.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::commands::index::{DocInfo, DocType, scan_docs};
+use crate::commands::report_html::strip_frontmatter;
+use crate::ignore::PaveIgnore;
+use crate::include;
+use crate::parser::DocState;
+use crate::vars;
+
+/// Order of the book's parts.
+const PART_ORDER: [DocType; 6] = [
+    DocType::Component,
+    DocType::Runbook,
+    DocType::Adr,
+    DocType::Postmortem,
+    DocType::Api,
+    DocType::Other,
+];
+
+/// Write an mdBook project for the docs under `docs_root` to `output`.
+///
+/// Draft documents are left out unless `include_drafts` is set. The docs'
+/// `index.md`, if there is one, becomes the book's introduction.
+pub fn write_book(
+    docs_root: &Path,
+    output: &Path,
+    title: &str,
+    include_drafts: bool,
+    ignore: &PaveIgnore,
+    vars: &BTreeMap<String, String>,
+) -> Result<usize> {
+    let docs: Vec<DocInfo> = scan_docs(docs_root, ignore)?
+        .into_iter()
+        .filter(|doc| include_drafts || doc.state != DocState::Draft)
+        .collect();
+
+    let src = output.join("src");
+    fs::create_dir_all(&src)
+        .with_context(|| format!("failed to create directory: {}", src.display()))?;
+
+    let index = docs_root.join("index.md");
+    let has_intro = index.is_file() && !ignore.is_ignored(&index);
+    if has_intro {
+        copy_doc(&index, &src.join("index.md"), docs_root, vars)?;
+    }
+    for doc in &docs {
+        copy_doc(
+            &docs_root.join(&doc.path),
+            &src.join(&doc.path),
+            docs_root,
+            vars,
+        )?;
+    }
+
+    fs::write(output.join("book.toml"), render_book_toml(title))
+        .context("failed to write book.toml")?;
+    fs::write(src.join("SUMMARY.md"), render_summary(&docs, has_intro))
+        .context("failed to write SUMMARY.md")?;
+
+    Ok(docs.len())
+}
+
+/// Copy a document into the book, expanding its includes, rendering its
+/// variables, and dropping its frontmatter.
+fn copy_doc(
+    source: &Path,
+    dest: &Path,
+    docs_root: &Path,
+    vars: &BTreeMap<String, String>,
+) -> Result<()> {
+    let content = fs::read_to_string(source)
+        .with_context(|| format!("failed to read file: {}", source.display()))?;
+    let content = include::expand(&content, source, docs_root)
+        .with_context(|| format!("failed to expand includes in {}", source.display()))?;
+    let content = vars::render(&content, vars);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(dest, strip_frontmatter(&content))
+        .with_context(|| format!("failed to write file: {}", dest.display()))
+}
+
+/// Render `book.toml` for a book with the given title.
+fn render_book_toml(title: &str) -> String {
+    format!(
+        "[book]\ntitle = {}\nsrc = \"src\"\n\n[output.html]\n",
+        toml::Value::String(title.to_string())
+    )
+}
+
+/// Render `SUMMARY.md` with one part per document type.
+fn render_summary(docs: &[DocInfo], has_intro: bool) -> String {
+    let mut grouped: HashMap<DocType, Vec<&DocInfo>> = HashMap::new();
+    for doc in docs {
+        grouped.entry(doc.doc_type).or_default().push(doc);
+    }
+
+    let mut output = String::from("# Summary\n\n");
+    if has_intro {
+        output.push_str("[Introduction](index.md)\n\n");
+    }
+    for doc_type in PART_ORDER {
+        let Some(docs_in_part) = grouped.get_mut(&doc_type) else {
+            continue;
+        };
+        // ADRs read in the order they were numbered, everything else by title
+        if doc_type == DocType::Adr {
+            docs_in_part.sort_by(|a, b| a.path.cmp(&b.path));
+        } else {
+            docs_in_part.sort_by_key(|doc| doc.title.to_lowercase());
+        }
+
+        output.push_str(&format!("# {}\n\n", doc_type.display_name()));
+        for doc in docs_in_part.iter() {
+            let link = doc.path.to_string_lossy().replace('\\', "/");
+            output.push_str(&format!(
+                "- [{}]({})\n",
+                doc.title.replace(['[', ']'], ""),
+                link.replace(' ', "%20")
+            ));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn writes_book_with_chapters_by_doc_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        write(&docs, "index.md", "# Index\n");
+        write(
+            &docs,
+            "components/auth.md",
+            "---\npave:\n  paths: [src/auth]\n---\n# Auth Service\n\n## Purpose\n",
+        );
+        write(&docs, "components/api.md", "# API\n");
+        write(&docs, "runbooks/deploy.md", "# Deploy\n\n## Steps\n");
+        write(
+            &docs,
+            "adrs/001-db.md",
+            "# ADR 001: Postgres\n\n## Status\n",
+        );
+        write(
+            &docs,
+            "notes.md",
+            "---\npave:\n  state: draft\n---\n# Notes\n",
+        );
+        let output = temp_dir.path().join("book");
+
+        let count = write_book(
+            &docs,
+            &output,
+            "My \"Docs\"",
+            false,
+            &PaveIgnore::default(),
+            &BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(count, 4);
+        let book_toml: toml::Value =
+            toml::from_str(&fs::read_to_string(output.join("book.toml")).unwrap()).unwrap();
+        assert_eq!(book_toml["book"]["title"].as_str(), Some("My \"Docs\""));
+
+        let summary = fs::read_to_string(output.join("src/SUMMARY.md")).unwrap();
+        assert_eq!(
+            summary,
+            "# Summary\n\n[Introduction](index.md)\n\n\
+             # Components\n\n- [API](components/api.md)\n- [Auth Service](components/auth.md)\n\n\
+             # Runbooks\n\n- [Deploy](runbooks/deploy.md)\n\n\
+             # Architecture Decisions\n\n- [ADR 001: Postgres](adrs/001-db.md)\n\n"
+        );
+
+        let auth = fs::read_to_string(output.join("src/components/auth.md")).unwrap();
+        assert!(auth.starts_with("# Auth Service"));
+        assert!(!output.join("src/notes.md").exists());
+    }
+}
@@ -1,18 +1,26 @@
 //! Implementation of the `pave changed` command for detecting impacted documentation.
 //!
 //! This module analyzes git diffs and reports which documentation should be reviewed
-//! or updated based on code-to-doc mappings defined in the docs.
+//! or updated based on code-to-doc mappings defined in the docs. It also reports
+//! changed code files that no doc covers, so CI can require docs to follow code.
 
 use anyhow::{Context, Result};
 use glob::Pattern;
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 use crate::cli::ChangedOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::commands::coverage::is_code_file;
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::git::{changed_files, determine_base_ref};
+use crate::ignore::PaveIgnore;
+use crate::parser::ParsedDoc;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
 
 /// Arguments for the `pave changed` command.
 pub struct ChangedArgs {
@@ -22,6 +30,8 @@ pub struct ChangedArgs {
     pub format: ChangedOutputFormat,
     /// Fail if impacted docs weren't updated.
     pub strict: bool,
+    /// Fail if changed code files aren't covered by any doc.
+    pub fail_on_uncovered: bool,
 }
 
 /// A documentation file with its path mappings.
@@ -36,7 +46,7 @@ pub struct DocMapping {
 }
 
 /// Information about an impacted document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ImpactedDoc {
     /// Path to the documentation file.
     pub doc_path: PathBuf,
@@ -49,9 +59,20 @@ pub struct ImpactedDoc {
     pub was_updated: bool,
 }
 
+/// A changed code file that no doc covers.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct UncoveredCode {
+    /// Path to the code file.
+    pub file: PathBuf,
+    /// Command that creates a doc for the file's component.
+    pub suggestion: String,
+}
+
 /// Results of the changed analysis.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ChangedResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Base ref that was compared against.
     pub base_ref: String,
     /// Number of changed files in the diff.
@@ -60,31 +81,35 @@ pub struct ChangedResults {
     pub impacted_docs: Vec<ImpactedDoc>,
     /// Docs that were impacted but not updated.
     pub missing_updates: Vec<PathBuf>,
+    /// Changed code files not covered by any doc's paths.
+    pub uncovered_files: Vec<UncoveredCode>,
 }
 
 /// Execute the `pave changed` command.
-pub fn execute(args: ChangedArgs) -> Result<()> {
+pub fn execute(args: ChangedArgs, workdir: &Workdir) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
     let docs_root = config_dir.join(&config.docs.root);
 
     // Determine base ref
-    let base_ref = determine_base_ref(args.base.as_deref())?;
+    let base_ref = determine_base_ref(config_dir, args.base.as_deref())?;
 
     // Get changed files from git
-    let changed_files = get_changed_files(&base_ref)?;
+    let changed_files = changed_files(config_dir, &base_ref)?;
 
     if changed_files.is_empty() {
         if args.format == ChangedOutputFormat::Text {
             println!("No changed files found compared to {}", base_ref);
         } else {
             let results = ChangedResults {
+                schema_version: SCHEMA_VERSION,
                 base_ref,
                 changed_files_count: 0,
                 impacted_docs: vec![],
                 missing_updates: vec![],
+                uncovered_files: vec![],
             };
             output_json(&results)?;
         }
@@ -92,10 +117,12 @@ pub fn execute(args: ChangedArgs) -> Result<()> {
     }
 
     // Load all docs with path mappings
-    let doc_mappings = load_doc_mappings(&docs_root, config_dir)?;
+    let ignore = load_ignore(config_dir, &config)?;
+    let doc_mappings = load_doc_mappings(&docs_root, config_dir, &ignore)?;
 
     // Find impacted docs
-    let impacted_docs = find_impacted_docs(&doc_mappings, &changed_files, config_dir);
+    let impacted_docs = find_impacted_docs(&doc_mappings, &changed_files, config_dir, &workdir.dir);
+    let uncovered_files = find_uncovered_code(&doc_mappings, &changed_files, config_dir, &ignore);
 
     // Collect missing updates
     let missing_updates: Vec<PathBuf> = impacted_docs
@@ -105,10 +132,12 @@ pub fn execute(args: ChangedArgs) -> Result<()> {
         .collect();
 
     let results = ChangedResults {
+        schema_version: SCHEMA_VERSION,
         base_ref: base_ref.clone(),
         changed_files_count: changed_files.len(),
         impacted_docs,
         missing_updates: missing_updates.clone(),
+        uncovered_files,
     };
 
     // Output results
@@ -119,143 +148,42 @@ pub fn execute(args: ChangedArgs) -> Result<()> {
 
     // Return error if strict mode and missing updates
     if args.strict && !missing_updates.is_empty() {
-        anyhow::bail!(
+        anyhow::bail!(Violations(format!(
             "Strict mode: {} impacted doc{} not updated",
             missing_updates.len(),
             if missing_updates.len() == 1 { "" } else { "s" }
-        );
+        )));
     }
 
-    Ok(())
-}
-
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
-/// Determine the base ref to compare against.
-fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
-    if let Some(base) = explicit_base {
-        return Ok(base.to_string());
-    }
-
-    // Try origin/main first
-    if ref_exists("origin/main") {
-        return Ok("origin/main".to_string());
-    }
-
-    // Try origin/master
-    if ref_exists("origin/master") {
-        return Ok("origin/master".to_string());
-    }
-
-    // Fall back to HEAD~1
-    Ok("HEAD~1".to_string())
-}
-
-/// Check if a git ref exists.
-fn ref_exists(ref_name: &str) -> bool {
-    Command::new("git")
-        .args(["rev-parse", "--verify", "--quiet", ref_name])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Get the list of changed files from git diff.
-fn get_changed_files(base_ref: &str) -> Result<HashSet<PathBuf>> {
-    let output = Command::new("git")
-        .args(["diff", "--name-only", &format!("{}..HEAD", base_ref)])
-        .output()
-        .context("Failed to run git diff")?;
-
-    if !output.status.success() {
-        // Try without ..HEAD for cases like HEAD~1
-        let output = Command::new("git")
-            .args(["diff", "--name-only", base_ref])
-            .output()
-            .context("Failed to run git diff")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("git diff failed: {}", stderr);
-        }
-
-        return parse_git_diff_output(&output.stdout);
+    // Return error if changed code has no covering doc
+    if args.fail_on_uncovered && !results.uncovered_files.is_empty() {
+        anyhow::bail!(Violations(format!(
+            "{} changed code file{} not covered by any doc",
+            results.uncovered_files.len(),
+            if results.uncovered_files.len() == 1 {
+                " is"
+            } else {
+                "s are"
+            }
+        )));
     }
 
-    parse_git_diff_output(&output.stdout)
-}
-
-/// Parse git diff --name-only output into a set of paths.
-fn parse_git_diff_output(output: &[u8]) -> Result<HashSet<PathBuf>> {
-    let stdout = String::from_utf8_lossy(output);
-    let files: HashSet<PathBuf> = stdout
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(PathBuf::from)
-        .collect();
-    Ok(files)
+    Ok(())
 }
 
 /// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path, config_dir: &Path) -> Result<Vec<DocMapping>> {
-    let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, config_dir, &mut mappings)?;
-    Ok(mappings)
-}
-
-/// Recursively load documentation files.
-fn load_doc_mappings_recursive(
-    current: &Path,
+fn load_doc_mappings(
+    docs_root: &Path,
     config_dir: &Path,
-    mappings: &mut Vec<DocMapping>,
-) -> Result<()> {
-    let entries = match std::fs::read_dir(current) {
-        Ok(entries) => entries,
-        Err(_) => return Ok(()), // Directory doesn't exist or can't be read
-    };
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip templates directory
-            if path.file_name().is_some_and(|n| n == "templates") {
-                continue;
-            }
-            load_doc_mappings_recursive(&path, config_dir, mappings)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            // Skip index.md
-            if path.file_name().is_some_and(|n| n == "index.md") {
-                continue;
-            }
-
-            if let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)? {
-                mappings.push(doc_mapping);
-            }
+    ignore: &PaveIgnore,
+) -> Result<Vec<DocMapping>> {
+    let mut mappings = Vec::new();
+    for path in find_docs(docs_root, ignore)? {
+        if let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)? {
+            mappings.push(doc_mapping);
         }
     }
-
-    Ok(())
+    Ok(mappings)
 }
 
 /// Parse a documentation file to extract path mappings.
@@ -266,7 +194,13 @@ fn parse_doc_mapping(path: &Path, config_dir: &Path) -> Result<Option<DocMapping
     let relative_path = path.strip_prefix(config_dir).unwrap_or(path).to_path_buf();
 
     let title = extract_title(&content);
-    let patterns = extract_paths_patterns(&content);
+    let mut patterns = extract_paths_patterns(&content);
+
+    // Frontmatter `pave.paths` entries are also coverage mappings
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    if let Some(frontmatter) = doc.frontmatter {
+        patterns.extend(frontmatter.paths);
+    }
 
     // Only include docs that have path mappings
     if patterns.is_empty() {
@@ -335,6 +269,7 @@ fn find_impacted_docs(
     doc_mappings: &[DocMapping],
     changed_files: &HashSet<PathBuf>,
     config_dir: &Path,
+    cwd: &Path,
 ) -> Vec<ImpactedDoc> {
     let mut impacted = Vec::new();
 
@@ -350,9 +285,7 @@ fn find_impacted_docs(
         if !matched_files.is_empty() {
             // Check if the doc itself was updated
             let doc_full_path = config_dir.join(&doc.doc_path);
-            let doc_relative = doc_full_path
-                .strip_prefix(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
-                .unwrap_or(&doc.doc_path);
+            let doc_relative = doc_full_path.strip_prefix(cwd).unwrap_or(&doc.doc_path);
 
             let was_updated = changed_files.contains(&doc.doc_path)
                 || changed_files.contains(doc_relative)
@@ -375,6 +308,49 @@ fn find_impacted_docs(
     impacted
 }
 
+/// Find changed code files that no doc's patterns cover.
+///
+/// Deleted files and files excluded by `.paveignore` are skipped.
+fn find_uncovered_code(
+    doc_mappings: &[DocMapping],
+    changed_files: &HashSet<PathBuf>,
+    config_dir: &Path,
+    ignore: &PaveIgnore,
+) -> Vec<UncoveredCode> {
+    let mut uncovered: Vec<UncoveredCode> = changed_files
+        .iter()
+        .filter(|file| is_code_file(file))
+        .filter(|file| {
+            let full_path = config_dir.join(file);
+            full_path.is_file() && !ignore.is_ignored(&full_path)
+        })
+        .filter(|file| {
+            !doc_mappings
+                .iter()
+                .any(|doc| matches_any_pattern(file, &doc.patterns))
+        })
+        .map(|file| UncoveredCode {
+            file: file.clone(),
+            suggestion: format!("pave new component {}", suggest_component_name(file)),
+        })
+        .collect();
+
+    uncovered.sort_by(|a, b| a.file.cmp(&b.file));
+    uncovered
+}
+
+/// Suggest a component name for a code file: its directory, or its file stem
+/// for files in a top-level directory like `src/` or at the project root.
+fn suggest_component_name(file: &Path) -> String {
+    let name = if file.components().count() > 2 {
+        file.parent().and_then(|parent| parent.file_name())
+    } else {
+        file.file_stem()
+    };
+    name.map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "component".to_string())
+}
+
 /// Check if a path matches any of the glob patterns.
 fn matches_any_pattern(path: &Path, patterns: &[String]) -> bool {
     let path_str = path.to_string_lossy();
@@ -416,6 +392,7 @@ fn output_text(results: &ChangedResults) {
     if results.impacted_docs.is_empty() {
         println!("No impacted documentation found.");
         println!("(No docs have ## Paths sections matching the changed files)");
+        output_uncovered(results);
         return;
     }
 
@@ -465,6 +442,32 @@ fn output_text(results: &ChangedResults) {
             println!("  - {}", path.display());
         }
     }
+
+    output_uncovered(results);
+}
+
+/// Output changed code files that no doc covers.
+fn output_uncovered(results: &ChangedResults) {
+    if results.uncovered_files.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "Changed code without docs ({} file{}):",
+        results.uncovered_files.len(),
+        if results.uncovered_files.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+    for uncovered in &results.uncovered_files {
+        println!("  ✗ {}", uncovered.file.display());
+        println!("      → {}", uncovered.suggestion);
+    }
+    println!();
+    println!("Add these files to an existing doc's ## Paths section, or create a doc.");
 }
 
 /// Output results in JSON format.
@@ -643,7 +646,12 @@ No paths section.
         changed_files.insert(PathBuf::from("src/commands/check.rs"));
         changed_files.insert(PathBuf::from("tests/test.rs"));
 
-        let impacted = find_impacted_docs(&doc_mappings, &changed_files, Path::new("."));
+        let impacted = find_impacted_docs(
+            &doc_mappings,
+            &changed_files,
+            Path::new("."),
+            Path::new("."),
+        );
 
         assert_eq!(impacted.len(), 2);
 
@@ -666,6 +674,62 @@ No paths section.
         );
     }
 
+    #[test]
+    fn test_find_uncovered_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for file in [
+            "src/cli.rs",
+            "src/auth/token.rs",
+            "src/schema.rs",
+            "build.rs",
+            "vendor/lib.rs",
+            "README.md",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let ignore = PaveIgnore::parse(root, "vendor/\n").unwrap();
+        let doc_mappings = vec![DocMapping {
+            doc_path: PathBuf::from("docs/cli.md"),
+            title: Some("CLI".to_string()),
+            patterns: vec!["src/cli.rs".to_string()],
+        }];
+
+        let changed_files: HashSet<PathBuf> = [
+            "src/cli.rs",
+            "src/auth/token.rs",
+            "src/auth/deleted.rs",
+            "src/schema.rs",
+            "build.rs",
+            "vendor/lib.rs",
+            "README.md",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let uncovered = find_uncovered_code(&doc_mappings, &changed_files, root, &ignore);
+        assert_eq!(
+            uncovered,
+            vec![
+                UncoveredCode {
+                    file: PathBuf::from("build.rs"),
+                    suggestion: "pave new component build".to_string(),
+                },
+                UncoveredCode {
+                    file: PathBuf::from("src/auth/token.rs"),
+                    suggestion: "pave new component auth".to_string(),
+                },
+                UncoveredCode {
+                    file: PathBuf::from("src/schema.rs"),
+                    suggestion: "pave new component schema".to_string(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_find_impacted_docs_with_update() {
         let doc_mappings = vec![DocMapping {
@@ -678,34 +742,17 @@ No paths section.
         changed_files.insert(PathBuf::from("src/cli.rs"));
         changed_files.insert(PathBuf::from("docs/cli.md")); // Doc was also updated
 
-        let impacted = find_impacted_docs(&doc_mappings, &changed_files, Path::new("."));
+        let impacted = find_impacted_docs(
+            &doc_mappings,
+            &changed_files,
+            Path::new("."),
+            Path::new("."),
+        );
 
         assert_eq!(impacted.len(), 1);
         assert!(impacted[0].was_updated);
     }
 
-    #[test]
-    fn test_parse_git_diff_output() {
-        let output = b"src/cli.rs\nsrc/main.rs\ndocs/readme.md\n";
-        let files = parse_git_diff_output(output).unwrap();
-
-        assert_eq!(files.len(), 3);
-        assert!(files.contains(&PathBuf::from("src/cli.rs")));
-        assert!(files.contains(&PathBuf::from("src/main.rs")));
-        assert!(files.contains(&PathBuf::from("docs/readme.md")));
-    }
-
-    #[test]
-    fn test_parse_git_diff_output_empty() {
-        let output = b"";
-        let files = parse_git_diff_output(output).unwrap();
-        assert!(files.is_empty());
-
-        let output = b"\n\n";
-        let files = parse_git_diff_output(output).unwrap();
-        assert!(files.is_empty());
-    }
-
     #[test]
     fn test_load_doc_mappings() {
         let temp_dir = TempDir::new().unwrap();
@@ -734,7 +781,8 @@ No paths section.
         // Create an index.md (should be skipped)
         fs::write(docs_dir.join("index.md"), "# Index").unwrap();
 
-        let mappings = load_doc_mappings(&docs_dir, temp_dir.path()).unwrap();
+        let mappings =
+            load_doc_mappings(&docs_dir, temp_dir.path(), &PaveIgnore::default()).unwrap();
 
         // Should only include the doc with paths, not the one without or index.md
         assert_eq!(mappings.len(), 1);
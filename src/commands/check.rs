@@ -1,18 +1,42 @@
 //! Implementation of the `pave check` command for validating PAVED documents.
 
 use anyhow::{Context, Result};
-use serde::Serialize;
-use std::collections::HashSet;
-use std::env;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-
-use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::ParsedDoc;
-use crate::rules::{RulesEngine, detect_doc_type, get_type_specific_rules};
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::blame::{self, AuthorSummary, Blame};
+use crate::cli::{ErrorOn, IssueGrouping, OutputFormat, PathStyle};
+use crate::color;
+use crate::config::{LocalesSection, PaveConfig, RuleSeverity};
+use crate::exit::Violations;
+use crate::git::{changed_files, determine_base_ref};
+use crate::ignore::PaveIgnore;
+use crate::include::{self, INCLUDES_DIR};
+use crate::locales;
+use crate::parallel;
+use crate::parser::{CodeBlockTracker, DocState, ParsedDoc, strict_warnings};
+use crate::paths::{absolutize, display_path};
+use crate::plugins::{self, PluginIssue, PluginSeverity, WasmRule};
+use crate::progress::Progress;
+use crate::result_cache::{self, CHECK_CACHE_PATH, ResultCache};
+use crate::rule_docs;
+use crate::rules::{
+    RulesEngine, ValidationResult, check_owner, check_review_date, detect_doc_type,
+    get_type_specific_rules, validate_frontmatter, validate_metadata, validate_state,
+};
+use crate::schema::SCHEMA_VERSION;
+use crate::vars;
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
 
 /// Arguments for the `pave check` command.
+#[derive(Clone)]
 pub struct CheckArgs {
     /// Specific files or directories to check.
     pub paths: Vec<PathBuf>,
@@ -22,14 +46,41 @@ pub struct CheckArgs {
     pub strict: bool,
     /// Force gradual mode (treat errors as warnings, exit 0).
     pub gradual: bool,
+    /// Lowest severity that fails the check; `--strict` raises it to warnings.
+    pub error_on: ErrorOn,
     /// Only check docs changed since base ref.
     pub changed: bool,
     /// Base ref for --changed comparison.
     pub base: Option<String>,
+    /// How to display file paths in output.
+    pub path_style: PathStyle,
+    /// List files skipped by ignore rules instead of checking.
+    pub list_ignored: bool,
+    /// Re-check every document instead of using cached results.
+    pub no_cache: bool,
+    /// Documents to check at once (default: one per CPU).
+    pub jobs: Option<usize>,
+    /// Annotate issues with who last changed their line.
+    pub blame: bool,
+    /// Count issues per author.
+    pub group_by: Option<IssueGrouping>,
+}
+
+/// A markdown file skipped by an ignore rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct IgnoredFile {
+    /// Path to the ignored file.
+    pub file: PathBuf,
+    /// The pattern that ignored it, as written.
+    pub pattern: String,
+    /// Where the pattern comes from: `.paveignore`, `.gitignore`, or `[docs] exclude`.
+    pub source: String,
+    /// Line number of the pattern in its source (1-indexed).
+    pub line: usize,
 }
 
 /// Severity of a validation issue.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
@@ -37,7 +88,7 @@ pub enum Severity {
 }
 
 /// A validation issue found in a document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Issue {
     /// Path to the file with the issue.
     pub file: PathBuf,
@@ -54,11 +105,21 @@ pub struct Issue {
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     #[serde(default)]
     pub converted_from_error: bool,
+    /// Name of the rule that raised the issue.
+    pub rule: String,
+    /// Link to the rule's documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// Who last changed the line, with `--blame`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<Blame>,
 }
 
 /// Results of checking documents.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CheckResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Number of files checked.
     pub files_checked: usize,
     /// List of errors found.
@@ -69,15 +130,24 @@ pub struct CheckResults {
     /// Only populated when gradual mode is active.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub would_fail_count: Option<usize>,
+    /// Issues per author, with `--group-by author`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<AuthorSummary>>,
+    /// Whether gradual mode turned errors into warnings.
+    #[serde(skip)]
+    gradual_mode: bool,
 }
 
 impl CheckResults {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             files_checked: 0,
             errors: Vec::new(),
             warnings: Vec::new(),
             would_fail_count: None,
+            authors: None,
+            gradual_mode: false,
         }
     }
 
@@ -88,16 +158,80 @@ impl CheckResults {
         }
     }
 
-    /// Returns true if there are no errors (and no warnings if strict mode).
-    fn is_success(&self, strict: bool) -> bool {
-        if strict {
-            self.errors.is_empty() && self.warnings.is_empty()
-        } else {
-            self.errors.is_empty()
+    /// Group all issues by the file they were found in.
+    fn issues_by_file(&self) -> HashMap<&Path, Vec<&Issue>> {
+        let mut map: HashMap<&Path, Vec<&Issue>> = HashMap::new();
+        for issue in self.errors.iter().chain(&self.warnings) {
+            map.entry(issue.file.as_path()).or_default().push(issue);
+        }
+        map
+    }
+
+    /// Returns true if nothing at or above the `error_on` severity was found.
+    fn is_success(&self, error_on: ErrorOn) -> bool {
+        match error_on {
+            ErrorOn::Errors => self.errors.is_empty(),
+            ErrorOn::Warnings => self.errors.is_empty() && self.warnings.is_empty(),
+            ErrorOn::Never => true,
         }
     }
 }
 
+impl Merge for CheckResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        for issue in self.errors.iter_mut().chain(self.warnings.iter_mut()) {
+            issue.file = prefixed(package, &issue.file);
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.files_checked += other.files_checked;
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+        self.would_fail_count = match (self.would_fail_count, other.would_fail_count) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+        self.authors = match (self.authors.take(), other.authors) {
+            (None, None) => None,
+            (a, b) => Some(blame::merge_authors(a.into_iter().chain(b).flatten())),
+        };
+        self.gradual_mode |= other.gradual_mode;
+    }
+}
+
+impl PackageCommand for CheckArgs {
+    type Results = CheckResults;
+
+    fn is_json(&self) -> bool {
+        matches!(self.format, OutputFormat::Json)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<CheckResults>>> {
+        run(self, workdir)
+    }
+
+    fn output(&self, results: CheckResults) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => output_text(&results, results.gradual_mode),
+            OutputFormat::Json => output_json(&results)?,
+            OutputFormat::Github => output_github(&results, results.gradual_mode),
+        }
+        Ok(())
+    }
+}
+
 /// Check if the gradual deadline has passed.
 /// Returns true if the deadline has passed (gradual mode should be disabled).
 fn is_gradual_deadline_passed(deadline: &str) -> bool {
@@ -211,31 +345,60 @@ fn is_gradual_mode_active(config: &PaveConfig, args: &CheckArgs) -> bool {
 }
 
 /// Execute the `pave check` command.
-pub fn execute(args: CheckArgs) -> Result<()> {
+pub fn execute(args: CheckArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Check the documents selected by `args`, leaving output to the caller.
+fn run(args: &CheckArgs, workdir: &Workdir) -> Result<Option<Outcome<CheckResults>>> {
     // Find and load config
-    let config_path = find_config()?;
-    let config = PaveConfig::load(&config_path)?;
+    let config_path = workdir.find_config()?;
+    let mut config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    if args.strict {
+        config.rules.strict_parse = true;
+    }
 
     // Determine paths to check
     let paths = if args.paths.is_empty() {
         // Use docs root from config, relative to config file location
         vec![config_dir.join(&config.docs.root)]
     } else {
-        args.paths.clone()
+        args.paths
+            .iter()
+            .map(|path| workdir.resolve(path))
+            .collect()
     };
 
+    let ignore = load_ignore(config_dir, &config)?;
+    if args.list_ignored {
+        let display_file =
+            |path: &Path| display_path(path, args.path_style, config_dir, &workdir.dir);
+        let ignored = find_ignored_files(&paths, &ignore, display_file)?;
+        output_ignored(&ignored, args.format)?;
+        return Ok(None);
+    }
+
     // Find all markdown files
-    let mut files = find_markdown_files(&paths)?;
+    let mut files = find_markdown_files(&paths, &ignore)?;
+
+    // Results also depend on the config, the other docs, and the day, for
+    // review dates
+    let today = chrono::Local::now().date_naive().to_string();
+    let fingerprint =
+        result_cache::fingerprint(&[&serde_json::to_vec(&config)?, today.as_bytes()], &files);
+    let cache: ResultCache<Issue> =
+        ResultCache::load(config_dir, CHECK_CACHE_PATH, fingerprint, !args.no_cache);
 
     // Filter to only changed files if --changed flag is set
-    if args.changed {
-        let base_ref = determine_base_ref(args.base.as_deref())?;
-        let changed_files = get_changed_md_files(&base_ref, config_dir)?;
+    let base_ref = if args.changed {
+        let base_ref = determine_base_ref(config_dir, args.base.as_deref())?;
+        let mut changed_files = changed_files(config_dir, &base_ref)?;
+        changed_files.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
 
         if changed_files.is_empty() {
             eprintln!("No changed markdown files found compared to {}", base_ref);
-            return Ok(());
+            return Ok(None);
         }
 
         // Filter files to only include those that changed
@@ -244,22 +407,69 @@ pub fn execute(args: CheckArgs) -> Result<()> {
             let relative = f.strip_prefix(config_dir).unwrap_or(f).to_path_buf();
             changed_files.contains(&relative) || changed_files.contains(f)
         });
-    }
+        Some(base_ref)
+    } else {
+        None
+    };
 
     if files.is_empty() {
         eprintln!("No markdown files found to check");
-        return Ok(());
+        return Ok(None);
     }
 
-    // Check each file
+    let wasm_rules = plugins::load_wasm_rules(&config.plugins.wasm, config_dir)?;
+
+    // Check the files in parallel, each into its own results, and merge
+    // them in file order
     let mut results = CheckResults::new();
-    for file in &files {
-        check_file(file, &config, &mut results)?;
+    let start = Instant::now();
+    let progress = Progress::new(files.len(), matches!(args.format, OutputFormat::Text));
+    let cache = Mutex::new(cache);
+    let per_file = parallel::map_files(&files, args.jobs, |file| {
+        progress.start(file.strip_prefix(config_dir).unwrap_or(file));
+        let file_start = Instant::now();
+        let mut file_results = CheckResults::new();
+        check_file_cached(file, &config, &cache, &mut file_results)?;
+        check_plugins(file, &config, config_dir, &wasm_rules, &mut file_results)?;
+        if let Some(base_ref) = &base_ref {
+            check_state_transition(file, base_ref, config_dir, &mut file_results)?;
+        }
+        debug!("Checked {} in {:?}", file.display(), file_start.elapsed());
+        progress.finish();
+        Ok(file_results)
+    })?;
+    drop(progress);
+    for file_results in per_file {
+        results.errors.extend(file_results.errors);
+        results.warnings.extend(file_results.warnings);
+    }
+    info!("Checked {} files in {:?}", files.len(), start.elapsed());
+    let mut cache = cache.into_inner().unwrap();
+    debug!("Reused cached results for {} files", cache.hits());
+    if let Err(e) = cache.save() {
+        warn!("Failed to write check cache: {:#}", e);
+    }
+    let display_file = |path: &Path| display_path(path, args.path_style, config_dir, &workdir.dir);
+    let docs_root = config_dir.join(&config.docs.root);
+    if config.rules.duplicate_titles != RuleSeverity::Off {
+        let index = TitleIndex::build(&config, config_dir, &files)?;
+        check_duplicate_titles(
+            &files,
+            &index,
+            config.rules.duplicate_titles,
+            display_file,
+            &mut results,
+        );
+    }
+    if let Some(locales) = &config.docs.locales {
+        check_translations(&files, &docs_root, locales, &mut results);
     }
+    check_includes(&files, &docs_root, &mut results)?;
+    check_vars(&files, &config.vars, &mut results)?;
     results.files_checked = files.len();
 
     // Determine if gradual mode is active
-    let gradual_mode = is_gradual_mode_active(&config, &args);
+    let gradual_mode = is_gradual_mode_active(&config, args);
 
     // In gradual mode, convert errors to warnings
     if gradual_mode && !results.errors.is_empty() {
@@ -274,183 +484,225 @@ pub fn execute(args: CheckArgs) -> Result<()> {
         }
     }
 
-    // Output results in the requested format
-    match args.format {
-        OutputFormat::Text => output_text(&results, gradual_mode),
-        OutputFormat::Json => output_json(&results)?,
-        OutputFormat::Github => output_github(&results, gradual_mode),
+    // Blame reads the files, so it runs before their paths are shortened
+    if args.blame || args.group_by.is_some() {
+        let locations: Vec<(PathBuf, usize)> = results
+            .errors
+            .iter()
+            .chain(&results.warnings)
+            .map(|issue| (issue.file.clone(), issue.line))
+            .collect();
+        let issues = results.errors.iter_mut().chain(results.warnings.iter_mut());
+        for (issue, blame) in issues.zip(blame::blame_all(&locations)) {
+            issue.blame = blame;
+        }
+        if args.group_by == Some(IssueGrouping::Author) {
+            let issues = results.errors.iter().chain(&results.warnings);
+            results.authors = Some(blame::group_by_author(
+                issues.map(|issue| issue.blame.as_ref()),
+            ));
+        }
+    }
+
+    let docs_base = config.rules.docs_url.as_deref();
+    for issue in results.errors.iter_mut().chain(results.warnings.iter_mut()) {
+        show_path(issue, display_file);
+        issue.docs_url = rule_docs::docs_url(docs_base, &issue.rule);
     }
 
-    // Return error if checks failed
+    results.gradual_mode = gradual_mode;
+
+    // Fail if checks failed
     // In gradual mode, always return success (exit 0)
-    if gradual_mode || results.is_success(args.strict) {
+    let error_on = if args.strict {
+        ErrorOn::Warnings
+    } else {
+        args.error_on
+    };
+    let status = if gradual_mode || results.is_success(error_on) {
         Ok(())
     } else {
         let error_count = results.errors.len();
         let warning_count = results.warnings.len();
-        if args.strict && error_count == 0 {
-            anyhow::bail!(
-                "Check failed: {} warning{} (strict mode)",
+        if error_count == 0 {
+            Err(Violations(format!(
+                "Check failed: {} warning{} ({})",
                 warning_count,
-                if warning_count == 1 { "" } else { "s" }
-            );
+                if warning_count == 1 { "" } else { "s" },
+                if args.strict {
+                    "strict mode"
+                } else {
+                    "--error-on warnings"
+                }
+            ))
+            .into())
         } else {
-            anyhow::bail!(
+            Err(Violations(format!(
                 "Check failed: {} error{}",
                 error_count,
                 if error_count == 1 { "" } else { "s" }
-            );
+            ))
+            .into())
         }
-    }
+    };
+    Ok(Some(Outcome { results, status }))
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
+/// Find the markdown files under `paths` that ignore rules skip, with the
+/// rule responsible for each.
+fn find_ignored_files(
+    paths: &[PathBuf],
+    ignore: &PaveIgnore,
+    display_file: impl Fn(&Path) -> PathBuf,
+) -> Result<Vec<IgnoredFile>> {
+    let all_files = find_markdown_files(paths, &PaveIgnore::default())?;
+
+    Ok(all_files
+        .iter()
+        .filter_map(|file| {
+            let rule = ignore.matching_rule(file).filter(|rule| !rule.negated)?;
+            Some(IgnoredFile {
+                file: display_file(file),
+                pattern: rule.pattern.clone(),
+                source: rule.source.clone(),
+                line: rule.line,
+            })
+        })
+        .collect())
 }
 
-/// Determine the base ref to compare against.
-fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
-    if let Some(base) = explicit_base {
-        return Ok(base.to_string());
+/// Output the files listed by `pave check --list-ignored`.
+fn output_ignored(ignored: &[IgnoredFile], format: OutputFormat) -> Result<()> {
+    if matches!(format, OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(ignored)?);
+        return Ok(());
     }
 
-    // Try origin/main first
-    if ref_exists("origin/main") {
-        return Ok("origin/main".to_string());
+    if ignored.is_empty() {
+        println!("No ignored files");
+        return Ok(());
     }
-
-    // Try origin/master
-    if ref_exists("origin/master") {
-        return Ok("origin/master".to_string());
+    for entry in ignored {
+        println!(
+            "{}: {}:{}: {}",
+            entry.file.display(),
+            entry.source,
+            entry.line,
+            entry.pattern
+        );
     }
-
-    // Fall back to HEAD~1
-    Ok("HEAD~1".to_string())
-}
-
-/// Check if a git ref exists.
-fn ref_exists(ref_name: &str) -> bool {
-    Command::new("git")
-        .args(["rev-parse", "--verify", "--quiet", ref_name])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+    println!();
+    println!("{} file(s) ignored", ignored.len());
+    Ok(())
 }
 
-/// Get the list of changed markdown files from git diff.
-fn get_changed_md_files(base_ref: &str, config_dir: &Path) -> Result<HashSet<PathBuf>> {
-    let output = Command::new("git")
-        .args(["diff", "--name-only", &format!("{}..HEAD", base_ref)])
-        .current_dir(config_dir)
-        .output()
-        .context("Failed to run git diff")?;
-
-    if !output.status.success() {
-        // Try without ..HEAD for cases like HEAD~1
-        let output = Command::new("git")
-            .args(["diff", "--name-only", base_ref])
-            .current_dir(config_dir)
-            .output()
-            .context("Failed to run git diff")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("git diff failed: {}", stderr);
-        }
-
-        return parse_changed_md_files(&output.stdout);
+/// Check a single file against the validation rules.
+pub(crate) fn check_file(
+    path: &Path,
+    config: &PaveConfig,
+    results: &mut CheckResults,
+) -> Result<()> {
+    if let Some(reason) = skip_reason(path) {
+        info!("Skipping {}: {}", path.display(), reason);
+        return Ok(());
     }
 
-    parse_changed_md_files(&output.stdout)
-}
-
-/// Parse git diff --name-only output into a set of markdown file paths.
-fn parse_changed_md_files(output: &[u8]) -> Result<HashSet<PathBuf>> {
-    let stdout = String::from_utf8_lossy(output);
-    let files: HashSet<PathBuf> = stdout
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter(|line| line.ends_with(".md"))
-        .map(PathBuf::from)
-        .collect();
-    Ok(files)
+    // Read file content once for parsing and type detection
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    check_content(path, &content, config, results)
 }
 
-/// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
-
-    for path in paths {
-        if path.is_file() {
-            if path.extension().is_some_and(|ext| ext == "md") {
-                files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        } else {
-            anyhow::bail!("Path does not exist: {}", path.display());
-        }
+/// Check a document like [`check_file`], reusing the issues cached for it
+/// when its content hasn't changed.
+fn check_file_cached(
+    path: &Path,
+    config: &PaveConfig,
+    cache: &Mutex<ResultCache<Issue>>,
+    results: &mut CheckResults,
+) -> Result<()> {
+    if skip_reason(path).is_some() {
+        return check_file(path, config, results);
     }
-
-    // Sort for consistent output
-    files.sort();
-    Ok(files)
-}
-
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    if let Some(issues) = cache.lock().unwrap().get(path, &content) {
+        for issue in issues {
+            results.add_issue(Issue {
+                file: path.to_path_buf(),
+                ..issue
+            });
         }
+        return Ok(());
     }
 
+    let (errors, warnings) = (results.errors.len(), results.warnings.len());
+    check_content(path, &content, config, results)?;
+    let issues = results.errors[errors..]
+        .iter()
+        .chain(&results.warnings[warnings..])
+        .cloned()
+        .collect();
+    cache.lock().unwrap().insert(path, &content, issues);
     Ok(())
 }
 
-/// Check a single file against the validation rules.
-fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> Result<()> {
-    // Skip validation of index.md files - they are navigation documents
-    // that don't need Verification and Examples sections
-    if path.file_name().is_some_and(|f| f == "index.md") {
-        return Ok(());
-    }
-
-    // Skip template files - they are scaffolds, not actual documentation
-    let path_str = path.to_string_lossy();
-    if path_str.contains("/templates/") || path_str.contains("\\templates\\") {
+/// Run the `[plugins] check` rule plugins and the `[plugins.wasm]` rules on
+/// a document.
+fn check_plugins(
+    path: &Path,
+    config: &PaveConfig,
+    config_dir: &Path,
+    wasm_rules: &[WasmRule],
+    results: &mut CheckResults,
+) -> Result<()> {
+    if (config.plugins.check.is_empty() && wasm_rules.is_empty()) || skip_reason(path).is_some() {
         return Ok(());
     }
-
-    // Read file content once for parsing and type detection
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
-    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    let relative = display_path(path, PathStyle::Workspace, config_dir, config_dir);
+    let mut add = |rule: &str, issues: Vec<PluginIssue>| {
+        for issue in issues {
+            results.add_issue(Issue {
+                file: path.to_path_buf(),
+                line: issue.line,
+                severity: match issue.severity {
+                    PluginSeverity::Error => Severity::Error,
+                    PluginSeverity::Warning => Severity::Warning,
+                },
+                message: issue.message,
+                hint: issue.hint,
+                converted_from_error: false,
+                rule: rule.to_string(),
+                docs_url: None,
+                blame: None,
+            });
+        }
+    };
+    for (rule, command) in &config.plugins.check {
+        add(
+            rule,
+            plugins::run_rule(rule, command, &relative, &content, config_dir)?,
+        );
+    }
+    for rule in wasm_rules {
+        add(&rule.name, rule.run(&relative, &content)?);
+    }
+    Ok(())
+}
+
+/// Check document content against the validation rules.
+///
+/// `path` is used for reporting and document type detection only.
+pub(crate) fn check_content(
+    path: &Path,
+    content: &str,
+    config: &PaveConfig,
+    results: &mut CheckResults,
+) -> Result<()> {
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), content)?;
+    let first_error = results.errors.len();
 
     // Check max lines
     if doc.line_count > config.rules.max_lines as usize {
@@ -462,11 +714,49 @@ fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> R
                 "Document exceeds {} line limit ({} lines)",
                 config.rules.max_lines, doc.line_count
             ),
-            hint: Some("Consider splitting into smaller, focused documents".to_string()),
+            hint: Some(format!(
+                "Consider splitting into smaller, focused documents (`pave split {}`)",
+                path.display()
+            )),
             converted_from_error: false,
+            rule: "max-lines".to_string(),
+            docs_url: None,
+            blame: None,
         });
     }
 
+    // Check for merge conflict markers left behind
+    for (line, marker) in find_conflict_markers(content) {
+        results.add_issue(Issue {
+            file: path.to_path_buf(),
+            line,
+            severity: Severity::Error,
+            message: format!("Merge conflict marker '{}' left in document", marker),
+            hint: Some("Resolve the conflict and remove the marker lines".to_string()),
+            converted_from_error: false,
+            rule: "conflict-markers".to_string(),
+            docs_url: None,
+            blame: None,
+        });
+    }
+
+    // Report what the parser skipped over
+    if config.rules.strict_parse {
+        for warning in strict_warnings(content) {
+            results.add_issue(Issue {
+                file: path.to_path_buf(),
+                line: warning.line,
+                severity: Severity::Warning,
+                message: warning.message,
+                hint: Some(warning.hint),
+                converted_from_error: false,
+                rule: "strict-parse".to_string(),
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+
     // Check for required Verification section
     if config.rules.require_verification && !doc.has_section("Verification") {
         results.add_issue(Issue {
@@ -476,6 +766,9 @@ fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> R
             message: "Missing required section 'Verification'".to_string(),
             hint: Some("Add a '## Verification' section with test commands".to_string()),
             converted_from_error: false,
+            rule: "require-section-verification".to_string(),
+            docs_url: None,
+            blame: None,
         });
     }
 
@@ -488,64 +781,532 @@ fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> R
             message: "Missing required section 'Examples'".to_string(),
             hint: Some("Add an '## Examples' section with concrete usage examples".to_string()),
             converted_from_error: false,
+            rule: "require-section-examples".to_string(),
+            docs_url: None,
+            blame: None,
         });
     }
 
     // Apply document-type-specific validation rules
-    let doc_type = detect_doc_type(path, &content);
+    let doc_type = detect_doc_type(path, content);
     let type_rules = get_type_specific_rules(doc_type, &config.rules);
+    debug!(
+        "{}: {:?} document, {} type-specific rules",
+        path.display(),
+        doc_type,
+        type_rules.len()
+    );
 
     if !type_rules.is_empty() {
         let engine = RulesEngine::new(type_rules);
         let validation_result = engine.validate(&doc);
+        add_validation_result(path, validation_result, results);
+    }
+
+    let mut state_result = ValidationResult::new(path);
+    validate_state(&doc, &mut state_result);
+    validate_metadata(&doc, &mut state_result);
+    validate_frontmatter(content, &mut state_result);
+    check_owner(&doc, &config.rules, &mut state_result);
+    check_review_date(
+        &doc,
+        &config.rules,
+        chrono::Local::now().date_naive(),
+        &mut state_result,
+    );
+    add_validation_result(path, state_result, results);
+
+    // Drafts are work in progress: report their errors as warnings
+    if doc.state() == DocState::Draft {
+        for mut error in results.errors.drain(first_error..).collect::<Vec<_>>() {
+            error.severity = Severity::Warning;
+            error.message = format!("{} (draft)", error.message);
+            results.warnings.push(error);
+        }
+    }
 
-        for error in validation_result.errors {
+    Ok(())
+}
+
+/// Show an issue's file as `display_file` formats it, in its hint too, such
+/// as the `pave split` command suggested for long documents.
+fn show_path(issue: &mut Issue, display_file: impl Fn(&Path) -> PathBuf) {
+    let shown = display_file(&issue.file);
+    if let Some(hint) = &mut issue.hint {
+        *hint = hint.replace(
+            &issue.file.display().to_string(),
+            &shown.display().to_string(),
+        );
+    }
+    issue.file = shown;
+}
+
+/// Add a rules engine result's errors and warnings to the check results.
+fn add_validation_result(path: &Path, result: ValidationResult, results: &mut CheckResults) {
+    for error in result.errors {
+        results.add_issue(Issue {
+            file: path.to_path_buf(),
+            line: error.line.unwrap_or(1),
+            severity: Severity::Error,
+            message: error.message,
+            hint: error.suggestion,
+            converted_from_error: false,
+            rule: error.rule,
+            docs_url: None,
+            blame: None,
+        });
+    }
+
+    for warning in result.warnings {
+        results.add_issue(Issue {
+            file: path.to_path_buf(),
+            line: warning.line.unwrap_or(1),
+            severity: Severity::Warning,
+            message: warning.message,
+            hint: None,
+            converted_from_error: false,
+            rule: warning.rule,
+            docs_url: None,
+            blame: None,
+        });
+    }
+}
+
+/// Check that a changed document's state only moved forward since `base_ref`.
+///
+/// Documents that didn't exist at `base_ref` may start in any state.
+fn check_state_transition(
+    path: &Path,
+    base_ref: &str,
+    config_dir: &Path,
+    results: &mut CheckResults,
+) -> Result<()> {
+    if should_skip_file(path) {
+        return Ok(());
+    }
+
+    let relative = path.strip_prefix(config_dir).unwrap_or(path);
+    let Some(old_content) = read_file_at_ref(base_ref, relative, config_dir) else {
+        return Ok(());
+    };
+    let old_state = ParsedDoc::parse_content(path.to_path_buf(), &old_content)?.state();
+    let new_state = ParsedDoc::parse(path)?.state();
+
+    if !old_state.can_transition_to(new_state) {
+        results.add_issue(Issue {
+            file: path.to_path_buf(),
+            line: 1,
+            severity: Severity::Error,
+            message: format!(
+                "Invalid state transition: {} -> {} (since {})",
+                old_state, new_state, base_ref
+            ),
+            hint: Some(
+                "Documents move forward from draft to published to deprecated; write a new doc instead of reverting the state"
+                    .to_string(),
+            ),
+            converted_from_error: false,
+            rule: "state-transition".to_string(),
+            docs_url: None,
+            blame: None,
+        });
+    }
+
+    Ok(())
+}
+
+/// Read a file's content at a git ref, or None if it didn't exist there.
+fn read_file_at_ref(base_ref: &str, relative: &Path, config_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!("{}:./{}", base_ref, relative.display()))
+        .current_dir(config_dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Check if a file should be skipped during validation.
+pub(crate) fn should_skip_file(path: &Path) -> bool {
+    skip_reason(path).is_some()
+}
+
+/// Why a file is skipped during validation, if it is.
+pub(crate) fn skip_reason(path: &Path) -> Option<&'static str> {
+    // Skip index.md files - they are navigation documents
+    // that don't need Verification and Examples sections
+    if path.file_name().is_some_and(|f| f == "index.md") {
+        return Some("index.md is a navigation page");
+    }
+
+    // Skip template files - they are scaffolds, not actual documentation
+    let path_str = path.to_string_lossy();
+    if path_str.contains("/templates/") || path_str.contains("\\templates\\") {
+        return Some("files in a templates directory are scaffolds");
+    }
+
+    // Skip snippets - they are checked as part of the docs that include them
+    path.components()
+        .any(|c| c.as_os_str() == INCLUDES_DIR)
+        .then_some("files in an _includes directory are snippets")
+}
+
+/// Report include markers whose files are missing, outside `docs_root`, or
+/// part of an include cycle.
+fn check_includes(files: &[PathBuf], docs_root: &Path, results: &mut CheckResults) -> Result<()> {
+    for file in files {
+        if should_skip_file(file) {
+            continue;
+        }
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        for (line, message) in include::check(&content, file, docs_root) {
             results.add_issue(Issue {
-                file: path.to_path_buf(),
-                line: error.line.unwrap_or(1),
+                file: file.clone(),
+                line,
                 severity: Severity::Error,
-                message: error.message,
-                hint: error.suggestion,
+                message: format!("Broken include: {}", message),
+                hint: Some(
+                    "Include a file inside the docs root, e.g. `<!-- pave:include _includes/setup.md -->`"
+                        .to_string(),
+                ),
                 converted_from_error: false,
+                rule: "include".to_string(),
+                docs_url: None,
+                blame: None,
             });
         }
+    }
+    Ok(())
+}
 
-        for warning in validation_result.warnings {
+/// Report `{{ vars.<name> }}` placeholders naming variables that aren't
+/// defined in `[vars]`.
+fn check_vars(
+    files: &[PathBuf],
+    vars: &BTreeMap<String, String>,
+    results: &mut CheckResults,
+) -> Result<()> {
+    for file in files {
+        if should_skip_file(file) {
+            continue;
+        }
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        for var in vars::check(&content, vars) {
             results.add_issue(Issue {
-                file: path.to_path_buf(),
-                line: warning.line.unwrap_or(1),
-                severity: Severity::Warning,
-                message: warning.message,
-                hint: None,
+                file: file.clone(),
+                line: var.line,
+                severity: Severity::Error,
+                message: format!("Undefined variable: vars.{}", var.name),
+                hint: Some(
+                    "Define it in .pave.toml, e.g. `[vars]` with `service_url = \"https://api.example.com\"`"
+                        .to_string(),
+                ),
                 converted_from_error: false,
+                rule: "undefined-vars".to_string(),
+                docs_url: None,
+                blame: None,
             });
         }
     }
-
     Ok(())
 }
 
-/// Output results in text format.
-fn output_text(results: &CheckResults, gradual_mode: bool) {
-    // Print all issues
-    for issue in results.errors.iter().chain(results.warnings.iter()) {
-        let severity = match issue.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
+/// Report required source docs missing from a translated doc set.
+fn check_translations(
+    files: &[PathBuf],
+    docs_root: &Path,
+    locales: &LocalesSection,
+    results: &mut CheckResults,
+) {
+    for file in files {
+        if should_skip_file(file) {
+            continue;
+        }
+        for counterpart in locales::counterparts(file, docs_root, locales) {
+            if !counterpart.required || counterpart.path.exists() {
+                continue;
+            }
+            let expected = counterpart
+                .path
+                .strip_prefix(docs_root)
+                .unwrap_or(&counterpart.path);
+            results.add_issue(Issue {
+                file: file.clone(),
+                line: 1,
+                severity: Severity::Error,
+                message: format!(
+                    "Missing '{}' translation: {}",
+                    counterpart.locale,
+                    expected.display()
+                ),
+                hint: Some(format!(
+                    "Add a '{}' translation of this doc, or leave it out of `docs.locales.required`",
+                    counterpart.locale
+                )),
+                converted_from_error: false,
+                rule: "missing-translation".to_string(),
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+}
+
+/// H1 titles of documents, for finding documents that shadow each other's
+/// titles.
+///
+/// Titles are compared case-insensitively. With `[docs.locales]` they only
+/// need to be unique within a locale, since a translation keeps its source's
+/// title.
+pub(crate) struct TitleIndex {
+    /// Title key -> (path, title, line), sorted by path.
+    titles: HashMap<TitleKey, Vec<(PathBuf, String, usize)>>,
+    /// Absolute path -> its key in `titles`.
+    keys: HashMap<PathBuf, TitleKey>,
+}
+
+/// Lowercase title and locale.
+type TitleKey = (String, Option<String>);
+
+/// A document whose title an earlier document already uses.
+pub(crate) struct DuplicateTitle<'a> {
+    pub title: &'a str,
+    /// Line of the document's H1.
+    pub line: usize,
+    /// The other documents with the same title.
+    pub others: Vec<&'a Path>,
+}
+
+impl TitleIndex {
+    /// Index every document under the docs root along with `files`, so a
+    /// run over some documents still finds the ones they shadow.
+    pub(crate) fn build(config: &PaveConfig, config_dir: &Path, files: &[PathBuf]) -> Result<Self> {
+        let docs_root = config_dir.join(&config.docs.root);
+        let mut all = find_markdown_files(
+            std::slice::from_ref(&docs_root),
+            &load_ignore(config_dir, config)?,
+        )?;
+        all.extend_from_slice(files);
+        Self::new(&all, &docs_root, config.docs.locales.as_ref())
+    }
+
+    /// Index the titles of `files`.
+    pub(crate) fn new(
+        files: &[PathBuf],
+        docs_root: &Path,
+        locales: Option<&LocalesSection>,
+    ) -> Result<Self> {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let mut files: Vec<PathBuf> = files.iter().map(|file| absolutize(file, &cwd)).collect();
+        files.sort();
+        files.dedup();
+
+        let mut index = Self {
+            titles: HashMap::new(),
+            keys: HashMap::new(),
         };
-        println!(
-            "{}:{}: {}: {}",
-            issue.file.display(),
-            issue.line,
+        for file in files {
+            if should_skip_file(&file) {
+                continue;
+            }
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            let doc = ParsedDoc::parse_content(file.clone(), &content)?;
+            let Some(title) = doc.title else {
+                continue;
+            };
+            let line = find_title_line(&content, &title);
+            let locale = locales.and_then(|locales| locales::locale_of(&file, docs_root, locales));
+            let key = (title.to_lowercase(), locale.map(str::to_string));
+            index.keys.insert(file.clone(), key.clone());
+            index
+                .titles
+                .entry(key)
+                .or_default()
+                .push((file, title, line));
+        }
+        Ok(index)
+    }
+
+    /// The title `file` shares with documents before it, if any.
+    pub(crate) fn duplicate(&self, file: &Path) -> Option<DuplicateTitle<'_>> {
+        let file = absolutize(file, &std::env::current_dir().unwrap_or_default());
+        let docs = &self.titles[self.keys.get(&file)?];
+        let position = docs.iter().position(|(path, _, _)| *path == file)?;
+        if position == 0 {
+            return None;
+        }
+        let (_, title, line) = &docs[position];
+        Some(DuplicateTitle {
+            title,
+            line: *line,
+            others: docs
+                .iter()
+                .filter(|(path, _, _)| *path != file)
+                .map(|(path, _, _)| path.as_path())
+                .collect(),
+        })
+    }
+}
+
+/// Report documents among `files` whose H1 titles shadow others in `index`.
+///
+/// Every document after the first one using a title gets an issue pointing
+/// at the other documents, shown with `display_file`.
+fn check_duplicate_titles(
+    files: &[PathBuf],
+    index: &TitleIndex,
+    severity: RuleSeverity,
+    display_file: impl Fn(&Path) -> PathBuf,
+    results: &mut CheckResults,
+) {
+    let severity = match severity {
+        RuleSeverity::Off => return,
+        RuleSeverity::Warning => Severity::Warning,
+        RuleSeverity::Error => Severity::Error,
+    };
+
+    for file in files {
+        let Some(duplicate) = index.duplicate(file) else {
+            continue;
+        };
+        let others: Vec<String> = duplicate
+            .others
+            .iter()
+            .map(|other| display_file(other).display().to_string())
+            .collect();
+        results.add_issue(Issue {
+            file: file.clone(),
+            line: duplicate.line,
             severity,
-            issue.message
-        );
-        if let Some(hint) = &issue.hint {
-            println!("  hint: {}", hint);
+            message: format!(
+                "Duplicate title '{}' (also used by {})",
+                duplicate.title,
+                others.join(", ")
+            ),
+            hint: Some(disambiguation_hint(file, duplicate.title)),
+            converted_from_error: false,
+            rule: "duplicate-titles".to_string(),
+            docs_url: None,
+            blame: None,
+        });
+    }
+}
+
+/// Find merge conflict markers outside code blocks, as (line, marker) pairs.
+///
+/// `<<<<<<<` and `>>>>>>>` always count. `=======` and `|||||||` only count
+/// after an opening marker, since `=======` also underlines setext headings.
+fn find_conflict_markers(content: &str) -> Vec<(usize, &'static str)> {
+    let mut markers = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+    let mut in_conflict = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        if tracker.process_line(line) || tracker.in_code_block() {
+            continue;
+        }
+        let Some(marker) = ["<<<<<<<", "|||||||", "=======", ">>>>>>>"]
+            .into_iter()
+            .find(|marker| {
+                line.strip_prefix(marker)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+            })
+        else {
+            continue;
+        };
+        match marker {
+            "<<<<<<<" => in_conflict = true,
+            ">>>>>>>" => in_conflict = false,
+            _ if !in_conflict => continue,
+            _ => {}
         }
-        // Show note only for issues converted from errors in gradual mode
-        if issue.converted_from_error {
-            println!("  note: This would be an error outside gradual mode");
+        markers.push((idx + 1, marker));
+    }
+
+    markers
+}
+
+/// Find the 1-indexed line of the H1 heading with the given title.
+fn find_title_line(content: &str, title: &str) -> usize {
+    content
+        .lines()
+        .position(|line| {
+            line.trim()
+                .strip_prefix("# ")
+                .is_some_and(|t| t.trim() == title)
+        })
+        .map(|idx| idx + 1)
+        .unwrap_or(1)
+}
+
+/// Suggest a more specific title using the document's parent folder.
+fn disambiguation_hint(path: &Path, title: &str) -> String {
+    match path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+    {
+        Some(folder) => format!(
+            "Give each document a distinct title, e.g. '{} ({})'",
+            title, folder
+        ),
+        None => "Give each document a distinct title".to_string(),
+    }
+}
+
+/// Width of the severity column in text output, fitting "warning".
+const SEVERITY_WIDTH: usize = 7;
+
+/// Output results in text format, grouped by file.
+fn output_text(results: &CheckResults, gradual_mode: bool) {
+    let issues_by_file = results.issues_by_file();
+
+    // Sort files for consistent output
+    let mut files: Vec<_> = issues_by_file.keys().collect();
+    files.sort();
+
+    for file in files {
+        println!("{}", color::bold(file.display()));
+
+        // Sort issues by line number, errors first on a shared line
+        let mut issues = issues_by_file[file].clone();
+        issues.sort_by_key(|i| (i.line, i.severity != Severity::Error));
+        let width = issues.iter().map(|i| i.line.to_string().len()).max();
+        let width = width.unwrap_or(1);
+        // Hints and notes line up under the message
+        let indent = " ".repeat(2 + width + 2 + SEVERITY_WIDTH + 2);
+
+        for issue in issues {
+            let severity = match issue.severity {
+                Severity::Error => color::red(format!("{:<SEVERITY_WIDTH$}", "error")),
+                Severity::Warning => color::yellow(format!("{:<SEVERITY_WIDTH$}", "warning")),
+            };
+            println!(
+                "  {:>width$}  {}  {}  {}",
+                issue.line,
+                severity,
+                issue.message,
+                color::dim(&issue.rule)
+            );
+            if let Some(hint) = &issue.hint {
+                println!("{}hint: {}", indent, hint);
+            }
+            if let Some(blame) = &issue.blame {
+                println!("{}{}", indent, color::dim(blame.describe()));
+            }
+            // Show note only for issues converted from errors in gradual mode
+            if issue.converted_from_error {
+                println!(
+                    "{}note: This would be an error outside gradual mode",
+                    indent
+                );
+            }
         }
         println!();
     }
@@ -561,23 +1322,33 @@ fn output_text(results: &CheckResults, gradual_mode: bool) {
     );
 
     if error_count == 0 && warning_count == 0 {
-        println!("all checks passed");
-    } else if gradual_mode {
-        println!(
-            "{} error{}, {} warning{} (gradual mode active)",
-            error_count,
-            if error_count == 1 { "" } else { "s" },
-            warning_count,
-            if warning_count == 1 { "" } else { "s" }
-        );
+        println!("{}", color::green("all checks passed"));
     } else {
-        println!(
-            "{} error{}, {} warning{}",
+        let errors = format!(
+            "{} error{}",
             error_count,
-            if error_count == 1 { "" } else { "s" },
+            if error_count == 1 { "" } else { "s" }
+        );
+        let warnings = format!(
+            "{} warning{}",
             warning_count,
             if warning_count == 1 { "" } else { "s" }
         );
+        let errors = if error_count > 0 {
+            color::red(errors)
+        } else {
+            errors
+        };
+        let warnings = if warning_count > 0 {
+            color::yellow(warnings)
+        } else {
+            warnings
+        };
+        if gradual_mode {
+            println!("{}, {} (gradual mode active)", errors, warnings);
+        } else {
+            println!("{}, {}", errors, warnings);
+        }
     }
 
     // In gradual mode, show how many issues would fail in strict mode
@@ -588,6 +1359,11 @@ fn output_text(results: &CheckResults, gradual_mode: bool) {
             if would_fail == 1 { "" } else { "s" }
         );
     }
+
+    if let Some(authors) = results.authors.as_ref().filter(|a| !a.is_empty()) {
+        println!();
+        blame::print_authors(authors);
+    }
 }
 
 /// Output results in JSON format.
@@ -604,11 +1380,17 @@ fn output_github(results: &CheckResults, _gradual_mode: bool) {
             Severity::Error => "error",
             Severity::Warning => "warning",
         };
-        let message = if issue.converted_from_error {
+        let mut message = if issue.converted_from_error {
             format!("{} (would be error outside gradual mode)", issue.message)
         } else {
             issue.message.clone()
         };
+        if let Some(url) = &issue.docs_url {
+            message.push_str(&format!(" ({})", url));
+        }
+        if let Some(blame) = &issue.blame {
+            message.push_str(&format!(" ({})", blame.describe()));
+        }
         println!(
             "::{} file={},line={}::{}",
             level,
@@ -711,7 +1493,7 @@ This document is missing required sections.
 
         let config = PaveConfig::load(&config_path).unwrap();
         let docs_dir = temp_dir.path().join("docs");
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], &PaveIgnore::default()).unwrap();
 
         let mut results = CheckResults::new();
         for file in &files {
@@ -747,6 +1529,50 @@ This document is missing required sections.
         );
     }
 
+    #[test]
+    fn check_draft_document_reports_errors_as_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc_path = temp_dir.path().join("draft.md");
+        fs::write(
+            &doc_path,
+            "---\npave:\n  state: draft\n---\n# Draft\n\n## Purpose\nNot done yet.\n",
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut results = CheckResults::new();
+        check_file(&doc_path, &config, &mut results).unwrap();
+
+        assert!(results.errors.is_empty());
+        assert_eq!(results.warnings.len(), 2);
+        assert!(
+            results.warnings[0]
+                .message
+                .ends_with("'Verification' (draft)")
+        );
+    }
+
+    #[test]
+    fn check_deprecated_document_requires_superseded_by() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc_path = create_valid_doc(&temp_dir, "old.md");
+        let content = fs::read_to_string(&doc_path).unwrap();
+        fs::write(
+            &doc_path,
+            format!("---\npave:\n  state: deprecated\n---\n{}", content),
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut results = CheckResults::new();
+        check_file(&doc_path, &config, &mut results).unwrap();
+
+        assert_eq!(results.errors.len(), 1);
+        assert!(results.errors[0].message.contains("superseded_by"));
+    }
+
     #[test]
     fn check_long_document_reports_warning() {
         let temp_dir = TempDir::new().unwrap();
@@ -764,6 +1590,17 @@ This document is missing required sections.
                 .iter()
                 .any(|w| w.message.contains("line limit"))
         );
+
+        // The suggested split command names the file the way it is shown
+        let mut warning = results.warnings[0].clone();
+        show_path(&mut warning, |path| {
+            display_path(path, PathStyle::Workspace, temp_dir.path(), temp_dir.path())
+        });
+        assert_eq!(warning.file, PathBuf::from("docs/long.md"));
+        assert_eq!(
+            warning.hint.as_deref(),
+            Some("Consider splitting into smaller, focused documents (`pave split docs/long.md`)")
+        );
     }
 
     #[test]
@@ -777,13 +1614,83 @@ This document is missing required sections.
         fs::write(nested_dir.join("doc2.md"), "# Doc 2").unwrap();
         fs::write(docs_dir.join("readme.txt"), "Not markdown").unwrap();
 
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], &PaveIgnore::default()).unwrap();
 
         assert_eq!(files.len(), 2);
         assert!(files.iter().any(|f| f.file_name().unwrap() == "doc1.md"));
         assert!(files.iter().any(|f| f.file_name().unwrap() == "doc2.md"));
     }
 
+    #[test]
+    fn paveignore_skips_files_and_lists_matching_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("archive")).unwrap();
+        fs::write(docs_dir.join("keep.md"), "# Keep").unwrap();
+        fs::write(docs_dir.join("notes.draft.md"), "# Draft").unwrap();
+        fs::write(docs_dir.join("archive/old.md"), "# Old").unwrap();
+        fs::write(
+            temp_dir.path().join(".paveignore"),
+            "# scratch files\n*.draft.md\ndocs/archive/\n",
+        )
+        .unwrap();
+        let ignore = PaveIgnore::load(temp_dir.path()).unwrap();
+
+        let files = find_markdown_files(std::slice::from_ref(&docs_dir), &ignore).unwrap();
+        assert_eq!(files, vec![docs_dir.join("keep.md")]);
+
+        let ignored = find_ignored_files(&[docs_dir], &ignore, |p| {
+            p.strip_prefix(temp_dir.path()).unwrap().to_path_buf()
+        })
+        .unwrap();
+        let listed: Vec<(String, &str, usize)> = ignored
+            .iter()
+            .map(|i| (i.file.display().to_string(), i.pattern.as_str(), i.line))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("docs/archive/old.md".to_string(), "docs/archive/", 3),
+                ("docs/notes.draft.md".to_string(), "*.draft.md", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_conflict_markers_outside_code_blocks() {
+        let content = "# Doc\n\nTitle\n=======\n\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n\n```text\n<<<<<<< HEAD\n=======\n>>>>>>> main\n```\n";
+
+        let markers = find_conflict_markers(content);
+
+        assert_eq!(
+            markers,
+            vec![(6, "<<<<<<<"), (8, "======="), (10, ">>>>>>>")]
+        );
+    }
+
+    #[test]
+    fn strict_parse_warnings_follow_config() {
+        let content = "# Doc\n\n<!-- pave:env FOO -->\n";
+        let mut config = PaveConfig::default();
+        config.rules.require_verification = false;
+        config.rules.require_examples = false;
+
+        let mut results = CheckResults::new();
+        check_content(Path::new("doc.md"), content, &config, &mut results).unwrap();
+        assert!(results.warnings.iter().all(|w| w.rule != "strict-parse"));
+
+        config.rules.strict_parse = true;
+        let mut results = CheckResults::new();
+        check_content(Path::new("doc.md"), content, &config, &mut results).unwrap();
+        let warning = results
+            .warnings
+            .iter()
+            .find(|w| w.rule == "strict-parse")
+            .unwrap();
+        assert_eq!(warning.line, 3);
+        assert!(warning.message.contains("has no '='"));
+    }
+
     #[test]
     fn check_results_success_without_strict() {
         let mut results = CheckResults::new();
@@ -794,10 +1701,14 @@ This document is missing required sections.
             message: "A warning".to_string(),
             hint: None,
             converted_from_error: false,
+            rule: "test".to_string(),
+            docs_url: None,
+            blame: None,
         });
 
-        assert!(results.is_success(false)); // Warnings OK without strict
-        assert!(!results.is_success(true)); // Warnings fail with strict
+        assert!(results.is_success(ErrorOn::Errors)); // Warnings OK by default
+        assert!(!results.is_success(ErrorOn::Warnings)); // Warnings fail with strict
+        assert!(results.is_success(ErrorOn::Never));
     }
 
     #[test]
@@ -810,10 +1721,14 @@ This document is missing required sections.
             message: "An error".to_string(),
             hint: None,
             converted_from_error: false,
+            rule: "test".to_string(),
+            docs_url: None,
+            blame: None,
         });
 
-        assert!(!results.is_success(false));
-        assert!(!results.is_success(true));
+        assert!(!results.is_success(ErrorOn::Errors));
+        assert!(!results.is_success(ErrorOn::Warnings));
+        assert!(results.is_success(ErrorOn::Never));
     }
 
     #[test]
@@ -827,6 +1742,9 @@ This document is missing required sections.
             message: "Test error".to_string(),
             hint: Some("Fix it".to_string()),
             converted_from_error: false,
+            rule: "require-section-verification".to_string(),
+            docs_url: Some(rule_docs::docs_url(None, "require-section-verification").unwrap()),
+            blame: None,
         });
 
         let json = serde_json::to_string(&results).unwrap();
@@ -836,6 +1754,11 @@ This document is missing required sections.
         assert_eq!(parsed["errors"].as_array().unwrap().len(), 1);
         assert_eq!(parsed["errors"][0]["severity"], "error");
         assert_eq!(parsed["errors"][0]["message"], "Test error");
+        assert_eq!(parsed["errors"][0]["rule"], "require-section-verification");
+        assert_eq!(
+            parsed["errors"][0]["docs_url"],
+            "https://tessro.github.io/pave/docs/rules/#require-section"
+        );
     }
 
     #[test]
@@ -879,37 +1802,88 @@ This document is missing required sections.
     }
 
     #[test]
-    fn parse_changed_md_files_filters_to_markdown() {
-        let output = b"src/cli.rs\ndocs/readme.md\nsrc/main.rs\ndocs/guide.md\n";
-        let files = parse_changed_md_files(output).unwrap();
-
-        assert_eq!(files.len(), 2);
-        assert!(files.contains(&PathBuf::from("docs/readme.md")));
-        assert!(files.contains(&PathBuf::from("docs/guide.md")));
+    fn check_reports_broken_includes_and_skips_snippets() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+        let docs_dir = temp_dir.path().join("docs");
+        let includes_dir = docs_dir.join("_includes");
+        fs::create_dir_all(&includes_dir).unwrap();
+        fs::write(includes_dir.join("setup.md"), "Run `make setup`.\n").unwrap();
+        let doc = docs_dir.join("deploy.md");
+        fs::write(
+            &doc,
+            "# Deploy\n\n<!-- pave:include _includes/setup.md -->\n\n<!-- pave:include _includes/gone.md -->\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            skip_reason(&includes_dir.join("setup.md")),
+            Some("files in an _includes directory are snippets")
+        );
+        let mut results = CheckResults::new();
+        check_includes(&[doc], &docs_dir, &mut results).unwrap();
+        assert_eq!(results.errors.len(), 1);
+        assert_eq!(results.errors[0].line, 5);
+        assert_eq!(results.errors[0].rule, "include");
+        assert!(results.errors[0].message.contains("_includes/gone.md"));
     }
 
     #[test]
-    fn parse_changed_md_files_empty_output() {
-        let output = b"";
-        let files = parse_changed_md_files(output).unwrap();
-        assert!(files.is_empty());
+    fn check_reports_undefined_vars() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("deploy.md");
+        fs::write(
+            &doc,
+            "# Deploy\n\n```bash\ncurl {{ vars.service_url }}/health\n```\n\nRegion: {{ vars.region }}\n",
+        )
+        .unwrap();
+        let vars = BTreeMap::from([(
+            "service_url".to_string(),
+            "https://api.example.com".to_string(),
+        )]);
 
-        let output = b"\n\n";
-        let files = parse_changed_md_files(output).unwrap();
-        assert!(files.is_empty());
+        let mut results = CheckResults::new();
+        check_vars(&[doc], &vars, &mut results).unwrap();
+        assert_eq!(results.errors.len(), 1);
+        assert_eq!(results.errors[0].line, 7);
+        assert_eq!(results.errors[0].rule, "undefined-vars");
+        assert_eq!(results.errors[0].message, "Undefined variable: vars.region");
     }
 
     #[test]
-    fn parse_changed_md_files_no_markdown() {
-        let output = b"src/cli.rs\nsrc/main.rs\nCargo.toml\n";
-        let files = parse_changed_md_files(output).unwrap();
-        assert!(files.is_empty());
-    }
+    fn check_reports_missing_required_translations() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        for dir in ["en/runbooks", "ja/runbooks"] {
+            fs::create_dir_all(docs_dir.join(dir)).unwrap();
+        }
+        let files: Vec<PathBuf> = [
+            "en/runbooks/deploy.md",
+            "en/runbooks/rollback.md",
+            "en/faq.md",
+        ]
+        .iter()
+        .map(|path| docs_dir.join(path))
+        .collect();
+        for file in &files {
+            fs::write(file, "# Doc\n").unwrap();
+        }
+        fs::write(docs_dir.join("ja/runbooks/deploy.md"), "# Doc\n").unwrap();
+        let locales = LocalesSection {
+            source: "en".to_string(),
+            translations: vec!["ja".to_string()],
+            required: vec!["runbooks/**".to_string()],
+        };
 
-    #[test]
-    fn determine_base_ref_uses_explicit() {
-        let result = determine_base_ref(Some("custom-branch")).unwrap();
-        assert_eq!(result, "custom-branch");
+        let mut results = CheckResults::new();
+        check_translations(&files, &docs_dir, &locales, &mut results);
+        assert_eq!(results.errors.len(), 1);
+        assert_eq!(results.errors[0].file, files[1]);
+        assert_eq!(results.errors[0].rule, "missing-translation");
+        assert_eq!(
+            results.errors[0].message,
+            "Missing 'ja' translation: ja/runbooks/rollback.md"
+        );
     }
 
     fn create_test_config_with_type_rules(temp_dir: &TempDir) -> PathBuf {
@@ -1371,8 +2345,15 @@ gradual_until = "{}"
             format: OutputFormat::Text,
             strict: false,
             gradual: false,
+            error_on: ErrorOn::Errors,
             changed: false,
             base: None,
+            path_style: PathStyle::Workspace,
+            list_ignored: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1400,8 +2381,15 @@ gradual_until = "{}"
             format: OutputFormat::Text,
             strict: true, // This should override gradual
             gradual: false,
+            error_on: ErrorOn::Errors,
             changed: false,
             base: None,
+            path_style: PathStyle::Workspace,
+            list_ignored: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
         assert!(!is_gradual_mode_active(&config, &args));
@@ -1418,8 +2406,15 @@ gradual_until = "{}"
             format: OutputFormat::Text,
             strict: false,
             gradual: true, // CLI flag should enable gradual mode
+            error_on: ErrorOn::Errors,
             changed: false,
             base: None,
+            path_style: PathStyle::Workspace,
+            list_ignored: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1437,8 +2432,15 @@ gradual_until = "{}"
             format: OutputFormat::Text,
             strict: false,
             gradual: false,
+            error_on: ErrorOn::Errors,
             changed: false,
             base: None,
+            path_style: PathStyle::Workspace,
+            list_ignored: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1456,8 +2458,15 @@ gradual_until = "{}"
             format: OutputFormat::Text,
             strict: false,
             gradual: false,
+            error_on: ErrorOn::Errors,
             changed: false,
             base: None,
+            path_style: PathStyle::Workspace,
+            list_ignored: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
         // Should be disabled due to past deadline
@@ -1480,4 +2489,105 @@ gradual_until = "{}"
         assert!(!is_gradual_deadline_passed("2024-13-01")); // Invalid month
         assert!(!is_gradual_deadline_passed("2024-01-32")); // Invalid day
     }
+
+    #[test]
+    fn duplicate_titles_reported_across_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let api_dir = temp_dir.path().join("docs/api");
+        let web_dir = temp_dir.path().join("docs/web");
+        fs::create_dir_all(&api_dir).unwrap();
+        fs::create_dir_all(&web_dir).unwrap();
+        fs::write(api_dir.join("auth.md"), "# Authentication\n\n## Purpose\n").unwrap();
+        fs::write(web_dir.join("auth.md"), "\n# authentication\n").unwrap();
+
+        let docs_dir = temp_dir.path().join("docs");
+        let files =
+            find_markdown_files(std::slice::from_ref(&docs_dir), &PaveIgnore::default()).unwrap();
+        let index = TitleIndex::new(&files, &docs_dir, None).unwrap();
+        let mut results = CheckResults::new();
+        check_duplicate_titles(
+            &files,
+            &index,
+            RuleSeverity::Warning,
+            Path::to_path_buf,
+            &mut results,
+        );
+
+        assert!(results.errors.is_empty());
+        assert_eq!(results.warnings.len(), 1);
+        let issue = &results.warnings[0];
+        assert_eq!(issue.file, web_dir.join("auth.md"));
+        assert_eq!(issue.line, 2);
+        assert!(issue.message.contains("api/auth.md"));
+        assert!(issue.hint.as_ref().unwrap().contains("(web)"));
+    }
+
+    #[test]
+    fn duplicate_titles_respect_severity() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        fs::write(docs_dir.join("a.md"), "# Same\n").unwrap();
+        fs::write(docs_dir.join("b.md"), "# Same\n").unwrap();
+        let files =
+            find_markdown_files(std::slice::from_ref(&docs_dir), &PaveIgnore::default()).unwrap();
+        let index = TitleIndex::new(&files, &docs_dir, None).unwrap();
+        let check = |severity| {
+            let mut results = CheckResults::new();
+            check_duplicate_titles(&files, &index, severity, Path::to_path_buf, &mut results);
+            results
+        };
+
+        assert_eq!(check(RuleSeverity::Error).errors.len(), 1);
+
+        let results = check(RuleSeverity::Off);
+        assert!(results.errors.is_empty());
+        assert!(results.warnings.is_empty());
+    }
+
+    #[test]
+    fn duplicate_titles_ignore_index_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("nested")).unwrap();
+        fs::write(docs_dir.join("index.md"), "# Docs\n").unwrap();
+        fs::write(docs_dir.join("nested/index.md"), "# Docs\n").unwrap();
+        let files =
+            find_markdown_files(std::slice::from_ref(&docs_dir), &PaveIgnore::default()).unwrap();
+        let index = TitleIndex::new(&files, &docs_dir, None).unwrap();
+
+        let mut results = CheckResults::new();
+        check_duplicate_titles(
+            &files,
+            &index,
+            RuleSeverity::Warning,
+            Path::to_path_buf,
+            &mut results,
+        );
+        assert!(results.warnings.is_empty());
+    }
+
+    #[test]
+    fn duplicate_titles_compare_against_the_whole_docs_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        fs::write(docs_dir.join("a.md"), "# Same\n").unwrap();
+        fs::write(docs_dir.join("b.md"), "# Same\n").unwrap();
+        let config = PaveConfig::default();
+
+        // Checking only the second document still finds the first
+        let files = vec![docs_dir.join("b.md")];
+        let index = TitleIndex::build(&config, temp_dir.path(), &files).unwrap();
+        let mut results = CheckResults::new();
+        check_duplicate_titles(
+            &files,
+            &index,
+            RuleSeverity::Warning,
+            Path::to_path_buf,
+            &mut results,
+        );
+        assert_eq!(results.warnings.len(), 1);
+        assert!(results.warnings[0].message.contains("a.md"));
+    }
 }
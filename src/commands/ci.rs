@@ -0,0 +1,256 @@
+//! Implementation of the `pave ci generate` command.
+//!
+//! Writes a CI configuration that installs pave and validates the docs: a
+//! non-blocking `pave check --gradual` over every doc, and a strict check of
+//! the docs a change touches, plus `pave lint` and optionally
+//! `pave verify --changed`. The two check modes run as a matrix so both show
+//! up as separate results.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::CiProvider;
+use crate::workspace::Workdir;
+
+/// Repository pave is installed from in CI.
+const PAVE_REPOSITORY: &str = "https://github.com/tessro/pave";
+
+/// Options for generating a CI configuration.
+#[derive(Debug, Clone, Default)]
+pub struct CiOptions {
+    /// Also run `pave verify` on changed docs.
+    pub verify: bool,
+    /// Where to write the configuration, relative to the project root.
+    pub output: Option<PathBuf>,
+    /// Overwrite an existing file.
+    pub force: bool,
+}
+
+/// Arguments for the `pave ci generate` command.
+pub struct CiArgs {
+    /// CI system to generate configuration for.
+    pub provider: CiProvider,
+    /// Generation options.
+    pub options: CiOptions,
+    /// Print the configuration instead of writing it.
+    pub stdout: bool,
+}
+
+/// Execute the `pave ci generate` command.
+pub fn execute(args: CiArgs, workdir: &Workdir) -> Result<()> {
+    if args.stdout {
+        print!("{}", render(args.provider, args.options.verify));
+        return Ok(());
+    }
+    write(&workdir.dir, args.provider, &args.options)?;
+    Ok(())
+}
+
+/// Where a provider's configuration is written by default.
+pub fn default_output(provider: CiProvider) -> PathBuf {
+    match provider {
+        CiProvider::Github => PathBuf::from(".github/workflows/docs.yml"),
+        CiProvider::Gitlab => PathBuf::from(".gitlab/ci/pave.yml"),
+    }
+}
+
+/// Write a provider's configuration under `base` and return its path.
+pub fn write(base: &Path, provider: CiProvider, options: &CiOptions) -> Result<PathBuf> {
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| default_output(provider));
+    let path = base.join(&output);
+    if path.exists() && !options.force {
+        bail!(
+            "{} already exists. Use --force to overwrite, or --stdout to print the configuration.",
+            output.display()
+        );
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(&path, render(provider, options.verify))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    println!("Wrote {}", output.display());
+    if provider == CiProvider::Gitlab {
+        println!("Include it from .gitlab-ci.yml:");
+        println!();
+        println!("  include:");
+        println!("    - local: {}", output.display());
+    }
+    Ok(output)
+}
+
+/// Render a provider's configuration.
+pub fn render(provider: CiProvider, verify: bool) -> String {
+    match provider {
+        CiProvider::Github => render_github(verify),
+        CiProvider::Gitlab => render_gitlab(verify),
+    }
+}
+
+fn render_github(verify: bool) -> String {
+    let verify_step = if verify {
+        r#"
+      - name: Verify changed docs
+        if: matrix.mode == 'strict'
+        run: pave verify --changed --base "$BASE" --keep-going --format github
+"#
+    } else {
+        ""
+    };
+
+    format!(
+        r#"# PAVED documentation checks, generated by `pave ci generate github`.
+name: Documentation
+
+on:
+  pull_request:
+  push:
+    branches: [main]
+
+jobs:
+  pave:
+    name: pave (${{{{ matrix.mode }}}})
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: false
+      matrix:
+        include:
+          # Every doc, reported as warnings while existing docs catch up
+          - mode: gradual
+            check: --gradual
+          # Docs changed by this PR must meet every rule
+          - mode: strict
+            check: --strict --changed --base "$BASE"
+    env:
+      BASE: origin/${{{{ github.base_ref || github.event.repository.default_branch }}}}
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0  # --changed compares against the base branch
+
+      - name: Cache pave
+        id: cache-pave
+        uses: actions/cache@v4
+        with:
+          path: ~/.cargo/bin/pave
+          key: pave-${{{{ runner.os }}}}
+
+      - name: Install pave
+        if: steps.cache-pave.outputs.cache-hit != 'true'
+        run: cargo install --git {PAVE_REPOSITORY} --locked
+
+      - name: Check docs
+        run: pave check ${{{{ matrix.check }}}} --format github
+
+      - name: Lint docs
+        if: matrix.mode == 'strict'
+        run: pave lint --format github
+{verify_step}"#
+    )
+}
+
+fn render_gitlab(verify: bool) -> String {
+    let verify_line = if verify {
+        "\n      pave verify --changed --base \"$BASE\" --keep-going"
+    } else {
+        ""
+    };
+
+    format!(
+        r#"# PAVED documentation checks, generated by `pave ci generate gitlab`.
+# Include this file from .gitlab-ci.yml:
+#
+#   include:
+#     - local: .gitlab/ci/pave.yml
+
+pave:
+  stage: test
+  image: rust:latest
+  parallel:
+    matrix:
+      # gradual: every doc, reported as warnings while existing docs catch up
+      # strict: docs changed by the merge request must meet every rule
+      - PAVE_MODE: [gradual, strict]
+  variables:
+    GIT_DEPTH: "0"  # --changed compares against the target branch
+    CARGO_HOME: "$CI_PROJECT_DIR/.cargo"
+  cache:
+    key: pave
+    paths:
+      - .cargo/bin/
+  before_script:
+    - export PATH="$CARGO_HOME/bin:$PATH"
+    - command -v pave || cargo install --git {PAVE_REPOSITORY} --locked
+  script:
+    - |
+      if [ "$PAVE_MODE" = gradual ]; then
+        pave check --gradual
+        exit 0
+      fi
+      BASE="origin/${{CI_MERGE_REQUEST_TARGET_BRANCH_NAME:-$CI_DEFAULT_BRANCH}}"
+      git fetch origin "${{BASE#origin/}}"
+      pave check --strict --changed --base "$BASE"
+      pave lint{verify_line}
+  rules:
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+    - if: $CI_COMMIT_BRANCH == $CI_DEFAULT_BRANCH
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn renders_valid_configs() {
+        for provider in [CiProvider::Github, CiProvider::Gitlab] {
+            for verify in [false, true] {
+                let config = render(provider, verify);
+                let yaml: serde_yaml::Value = serde_yaml::from_str(&config).unwrap();
+                assert!(yaml.is_mapping());
+                assert!(
+                    config.contains("pave check --gradual") || config.contains("check: --gradual")
+                );
+                assert!(config.contains("pave lint"));
+                assert_eq!(config.contains("pave verify --changed"), verify);
+            }
+        }
+
+        let github = render(CiProvider::Github, false);
+        assert!(github.contains("name: pave (${{ matrix.mode }})"));
+        assert!(github.contains("pave check ${{ matrix.check }} --format github"));
+        assert!(github.contains("key: pave-${{ runner.os }}"));
+        let gitlab = render(CiProvider::Gitlab, false);
+        assert!(gitlab.contains("${CI_MERGE_REQUEST_TARGET_BRANCH_NAME:-$CI_DEFAULT_BRANCH}"));
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = CiOptions::default();
+
+        let output = write(temp_dir.path(), CiProvider::Github, &options).unwrap();
+        assert_eq!(output, PathBuf::from(".github/workflows/docs.yml"));
+        assert!(temp_dir.path().join(&output).exists());
+
+        let result = write(temp_dir.path(), CiProvider::Github, &options);
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+
+        let options = CiOptions {
+            force: true,
+            output: Some(PathBuf::from("ci/docs.yml")),
+            ..Default::default()
+        };
+        write(temp_dir.path(), CiProvider::Gitlab, &options).unwrap();
+        assert!(temp_dir.path().join("ci/docs.yml").exists());
+    }
+}
@@ -1,31 +1,11 @@
 use anyhow::{Context, Result, anyhow};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use toml::Value;
 
-const CONFIG_FILENAME: &str = ".pave.toml";
-
-/// Find the config file by walking up from current directory.
-pub fn find_config_path() -> Result<PathBuf> {
-    let cwd = std::env::current_dir().context("Failed to get current directory")?;
-    find_config_path_from(&cwd)
-}
-
-fn find_config_path_from(start: &Path) -> Result<PathBuf> {
-    let mut current = start.to_path_buf();
-    loop {
-        let config_path = current.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-        if !current.pop() {
-            return Err(anyhow!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ));
-        }
-    }
-}
+use crate::config_validate;
+use crate::exit::Violations;
+use crate::workspace::Workdir;
 
 /// Load the config file as a TOML Value.
 fn load_config(path: &Path) -> Result<Value> {
@@ -45,8 +25,8 @@ fn save_config(path: &Path, value: &Value) -> Result<()> {
 }
 
 /// Get a value from the config using dot notation (e.g., "docs.root").
-pub fn get(key: &str) -> Result<()> {
-    let config_path = find_config_path()?;
+pub fn get(key: &str, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
     let config = load_config(&config_path)?;
 
     let value = get_nested_value(&config, key)?;
@@ -55,8 +35,8 @@ pub fn get(key: &str) -> Result<()> {
 }
 
 /// Set a value in the config using dot notation.
-pub fn set(key: &str, value: &str) -> Result<()> {
-    let config_path = find_config_path()?;
+pub fn set(key: &str, value: &str, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
     let mut config = load_config(&config_path)?;
 
     let parsed_value = parse_value(value);
@@ -67,8 +47,8 @@ pub fn set(key: &str, value: &str) -> Result<()> {
 }
 
 /// List all config values.
-pub fn list() -> Result<()> {
-    let config_path = find_config_path()?;
+pub fn list(workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
     let config = load_config(&config_path)?;
 
     print_config_values(&config, "");
@@ -76,12 +56,43 @@ pub fn list() -> Result<()> {
 }
 
 /// Print the path to the config file.
-pub fn path() -> Result<()> {
-    let config_path = find_config_path()?;
+pub fn path(workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
     println!("{}", config_path.display());
     Ok(())
 }
 
+/// Validate the config file, reporting each problem with its line and a fix.
+pub fn validate(workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+
+    let problems = config_validate::validate(&content, config_dir);
+    if problems.is_empty() {
+        println!("✓ {} is valid", config_path.display());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        match problem.line {
+            Some(line) => println!("{}:{}: {}", config_path.display(), line, problem.message),
+            None => println!("{}: {}", config_path.display(), problem.message),
+        }
+        if let Some(hint) = &problem.hint {
+            println!("  hint: {}", hint);
+        }
+    }
+    println!();
+    anyhow::bail!(Violations(format!(
+        "{} problem{} found in {}",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" },
+        config_path.display()
+    )))
+}
+
 /// Get a nested value using dot notation.
 fn get_nested_value<'a>(value: &'a Value, key: &str) -> Result<&'a Value> {
     let parts: Vec<&str> = key.split('.').collect();
@@ -5,16 +5,24 @@
 
 use anyhow::{Context, Result};
 use glob::Pattern;
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::env;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use crate::cli::CoverageOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::CodeBlockTracker;
+use crate::codeowners::CodeOwners;
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::ignore::PaveIgnore;
+use crate::parser::{CodeBlockTracker, ParsedDoc};
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore, under_dir_named};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
 
 /// Arguments for the `pave coverage` command.
+#[derive(Clone)]
 pub struct CoverageArgs {
     /// Path to analyze (defaults to project root).
     pub path: Option<PathBuf>,
@@ -26,10 +34,22 @@ pub struct CoverageArgs {
     pub include: Vec<String>,
     /// Patterns to exclude (skip these code files).
     pub exclude: Vec<String>,
+    /// Break down coverage by CODEOWNERS owner.
+    pub by_owner: bool,
+    /// Explicit CODEOWNERS file (default: auto-detect). Implies `by_owner`.
+    pub codeowners: Option<PathBuf>,
+    /// Write an SVG coverage badge to this path.
+    pub badge: Option<PathBuf>,
+    /// Write a shields.io endpoint JSON badge to this path.
+    pub badge_json: Option<PathBuf>,
+    /// Append a snapshot to the coverage history.
+    pub record: bool,
+    /// Compare against the coverage history and fail on regressions.
+    pub trend: bool,
 }
 
 /// Coverage statistics for a directory.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DirectoryCoverage {
     /// Directory path.
     pub path: String,
@@ -41,8 +61,83 @@ pub struct DirectoryCoverage {
     pub percentage: f64,
 }
 
+/// Coverage statistics for a CODEOWNERS owner.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct OwnerCoverage {
+    /// Owner handle (e.g. `@org/team`), or `(unowned)`.
+    pub owner: String,
+    /// Number of covered files owned.
+    pub covered: usize,
+    /// Total number of files owned.
+    pub total: usize,
+    /// Coverage percentage.
+    pub percentage: f64,
+    /// Owned files that no doc covers.
+    pub uncovered: Vec<PathBuf>,
+}
+
+/// Label used for files that no CODEOWNERS rule assigns.
+const UNOWNED: &str = "(unowned)";
+
+/// Label shown on the left side of coverage badges.
+const BADGE_LABEL: &str = "doc coverage";
+
+/// Coverage history file, relative to the config directory.
+pub const HISTORY_PATH: &str = ".pave/coverage-history.jsonl";
+
+/// Number of suggestions shown.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Number of past snapshots shown by `--trend`.
+const TREND_DISPLAY_LIMIT: usize = 10;
+
+/// A point-in-time coverage measurement stored in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CoverageSnapshot {
+    /// When the snapshot was taken (RFC 3339).
+    pub timestamp: String,
+    /// Overall coverage percentage.
+    pub coverage_percentage: f64,
+    /// Number of docs with path mappings.
+    pub docs_count: usize,
+    /// Total number of code files.
+    pub total_files: usize,
+    /// Number of uncovered code files.
+    pub uncovered_files: usize,
+}
+
+/// Coverage compared against the recorded history.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CoverageTrend {
+    /// Most recent snapshots, oldest first.
+    pub history: Vec<CoverageSnapshot>,
+    /// Change in percentage points since the last snapshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta: Option<f64>,
+    /// Largest allowed drop in percentage points.
+    pub max_drop: f64,
+    /// Whether the drop is within the allowed limit.
+    pub passed: bool,
+}
+
+/// shields.io endpoint badge schema.
+///
+/// See <https://shields.io/badges/endpoint-badge>.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeEndpoint {
+    /// Always 1.
+    pub schema_version: u32,
+    /// Left-hand text.
+    pub label: String,
+    /// Right-hand text.
+    pub message: String,
+    /// Right-hand background color.
+    pub color: String,
+}
+
 /// Information about an uncovered file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct UncoveredFile {
     /// Path to the uncovered file.
     pub path: PathBuf,
@@ -52,7 +147,7 @@ pub struct UncoveredFile {
 }
 
 /// A suggestion for improving coverage.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct CoverageSuggestion {
     /// Description of the suggestion.
     pub description: String,
@@ -61,8 +156,10 @@ pub struct CoverageSuggestion {
 }
 
 /// Results of the coverage analysis.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CoverageResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Number of covered files.
     pub covered_files: usize,
     /// Number of uncovered files.
@@ -73,6 +170,9 @@ pub struct CoverageResults {
     pub coverage_percentage: f64,
     /// Coverage by directory.
     pub by_directory: Vec<DirectoryCoverage>,
+    /// Coverage by CODEOWNERS owner (only with --by-owner).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub by_owner: Vec<OwnerCoverage>,
     /// List of uncovered files.
     pub uncovered: Vec<UncoveredFile>,
     /// Suggestions for improving coverage.
@@ -84,23 +184,165 @@ pub struct CoverageResults {
     /// The threshold that was checked.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub threshold: Option<u32>,
+    /// Comparison against coverage history (only with --trend).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend: Option<CoverageTrend>,
 }
 
 /// A documentation file with its path mappings.
 #[derive(Debug, Clone)]
-struct DocMapping {
+pub(crate) struct DocMapping {
+    /// Path to the documentation file.
+    pub(crate) doc: PathBuf,
     /// Glob patterns for code paths this doc covers.
-    patterns: Vec<String>,
+    pub(crate) patterns: Vec<String>,
+}
+
+impl Merge for CoverageResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        for dir in &mut self.by_directory {
+            dir.path = prefixed_dir(package, &dir.path);
+        }
+        for owner in &mut self.by_owner {
+            for file in &mut owner.uncovered {
+                *file = prefixed(package, file);
+            }
+        }
+        for file in &mut self.uncovered {
+            file.path = prefixed(package, &file.path);
+            if let Some(doc) = &mut file.suggested_doc {
+                *doc = prefixed(package, Path::new(doc))
+                    .to_string_lossy()
+                    .into_owned();
+            }
+        }
+        for suggestion in &mut self.suggestions {
+            // Suggestions group files by directory, so any file names it
+            if let Some(parent) = suggestion.files.first().and_then(|file| file.parent()) {
+                let dir = parent.to_string_lossy();
+                let dir = if dir.is_empty() { "." } else { &dir };
+                suggestion.description = format!(
+                    "Create {} covering {}/",
+                    prefixed(package, Path::new(&suggest_doc_name_for_dir(dir))).display(),
+                    prefixed_dir(package, dir)
+                );
+            }
+            for file in &mut suggestion.files {
+                *file = prefixed(package, file);
+            }
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.covered_files += other.covered_files;
+        self.uncovered_files += other.uncovered_files;
+        self.total_files += other.total_files;
+        self.coverage_percentage = if self.total_files > 0 {
+            (self.covered_files as f64 / self.total_files as f64) * 100.0
+        } else {
+            100.0
+        };
+        self.by_directory.extend(other.by_directory);
+        self.by_directory.sort_by(|a, b| a.path.cmp(&b.path));
+        for owner in other.by_owner {
+            match self.by_owner.iter_mut().find(|o| o.owner == owner.owner) {
+                Some(merged) => {
+                    merged.covered += owner.covered;
+                    merged.total += owner.total;
+                    merged.percentage = (merged.covered as f64 / merged.total as f64) * 100.0;
+                    merged.uncovered.extend(owner.uncovered);
+                    merged.uncovered.sort();
+                }
+                None => self.by_owner.push(owner),
+            }
+        }
+        sort_owners(&mut self.by_owner);
+        self.uncovered.extend(other.uncovered);
+        self.suggestions.extend(other.suggestions);
+        self.suggestions
+            .sort_by_key(|s| std::cmp::Reverse(s.files.len()));
+        self.suggestions.truncate(MAX_SUGGESTIONS);
+        self.threshold_met = match (self.threshold_met, other.threshold_met) {
+            (Some(a), Some(b)) => Some(a && b),
+            (a, b) => a.or(b),
+        };
+        // Each member's trend is against its own history, and has already
+        // decided its exit status
+        self.trend = None;
+    }
+}
+
+impl PackageCommand for CoverageArgs {
+    type Results = CoverageResults;
+
+    fn is_json(&self) -> bool {
+        self.format == CoverageOutputFormat::Json
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        self.path.as_slice()
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            path: paths.into_iter().next(),
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<CoverageResults>>> {
+        run(self, workdir).map(Some)
+    }
+
+    fn output(&self, results: CoverageResults) -> Result<()> {
+        match self.format {
+            CoverageOutputFormat::Text if results.total_files == 0 => {
+                println!("No code files found to analyze.");
+                if !self.include.is_empty() {
+                    println!("Include patterns: {:?}", self.include);
+                }
+            }
+            CoverageOutputFormat::Text => output_text(&results),
+            CoverageOutputFormat::Json => output_json(&results)?,
+        }
+        Ok(())
+    }
+}
+
+/// A directory from [`DirectoryCoverage::path`], `.` for the member's own,
+/// prefixed with the member's `package` directory.
+fn prefixed_dir(package: &Path, dir: &str) -> String {
+    let dir = if dir == "." {
+        package.to_path_buf()
+    } else {
+        prefixed(package, Path::new(dir))
+    };
+    dir.to_string_lossy().into_owned()
 }
 
 /// Execute the `pave coverage` command.
-pub fn execute(args: CoverageArgs) -> Result<()> {
+pub fn execute(args: CoverageArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Measure coverage, write badges and record history as `args` asks,
+/// leaving output to the caller.
+fn run(args: &CoverageArgs, workdir: &Workdir) -> Result<Outcome<CoverageResults>> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
     let docs_root = config_dir.join(&config.docs.root);
 
+    let resolve = |path: &Option<PathBuf>| path.as_ref().map(|path| workdir.resolve(path));
+    let args = CoverageArgs {
+        path: resolve(&args.path),
+        codeowners: resolve(&args.codeowners),
+        badge: resolve(&args.badge),
+        badge_json: resolve(&args.badge_json),
+        ..args.clone()
+    };
+
     // Determine the path to analyze
     let analyze_path = args.path.unwrap_or_else(|| config_dir.to_path_buf());
 
@@ -109,33 +351,34 @@ pub fn execute(args: CoverageArgs) -> Result<()> {
     exclude_patterns.extend(args.exclude.clone());
 
     // Collect code files
-    let code_files = collect_code_files(&analyze_path, &args.include, &exclude_patterns)?;
+    let ignore = PaveIgnore::load(config_dir)?;
+    let code_files = collect_code_files(&analyze_path, &args.include, &exclude_patterns, &ignore)?;
 
     if code_files.is_empty() {
-        if args.format == CoverageOutputFormat::Text {
-            println!("No code files found to analyze.");
-            if !args.include.is_empty() {
-                println!("Include patterns: {:?}", args.include);
-            }
-        } else {
-            let results = CoverageResults {
-                covered_files: 0,
-                uncovered_files: 0,
-                total_files: 0,
-                coverage_percentage: 100.0,
-                by_directory: vec![],
-                uncovered: vec![],
-                suggestions: vec![],
-                threshold_met: args.threshold.map(|_| true),
-                threshold: args.threshold,
-            };
-            output_json(&results)?;
-        }
-        return Ok(());
+        let results = CoverageResults {
+            schema_version: SCHEMA_VERSION,
+            covered_files: 0,
+            uncovered_files: 0,
+            total_files: 0,
+            coverage_percentage: 100.0,
+            by_directory: vec![],
+            by_owner: vec![],
+            uncovered: vec![],
+            suggestions: vec![],
+            threshold_met: args.threshold.map(|_| true),
+            threshold: args.threshold,
+            trend: None,
+        };
+        write_badges(args.badge.as_deref(), args.badge_json.as_deref(), 100.0)?;
+        return Ok(Outcome {
+            results,
+            status: Ok(()),
+        });
     }
 
     // Load all doc mappings
-    let doc_mappings = load_doc_mappings(&docs_root, config_dir)?;
+    let doc_mappings =
+        load_doc_mappings(&docs_root, config_dir, &load_ignore(config_dir, &config)?)?;
 
     // Determine coverage for each file
     let (covered, uncovered) = analyze_coverage(&code_files, &doc_mappings, config_dir);
@@ -143,6 +386,32 @@ pub fn execute(args: CoverageArgs) -> Result<()> {
     // Calculate directory-level coverage
     let by_directory = calculate_directory_coverage(&covered, &uncovered);
 
+    // Calculate owner-level coverage from CODEOWNERS
+    let by_owner = if args.by_owner || args.codeowners.is_some() {
+        let codeowners_path = match args.codeowners {
+            Some(ref path) => path.clone(),
+            None => CodeOwners::find(config_dir).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No CODEOWNERS file found (looked in .github/, the project root, and docs/)"
+                )
+            })?,
+        };
+        let codeowners = CodeOwners::load(&codeowners_path)?;
+        // CODEOWNERS paths are relative to the repository root, code files to analyze_path
+        let to_repo_path = |file: &PathBuf| {
+            let absolute = analyze_path.join(file);
+            absolute
+                .strip_prefix(config_dir)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| file.clone())
+        };
+        calculate_owner_coverage(&covered, &uncovered, |file| {
+            codeowners.owners_for(&to_repo_path(file)).to_vec()
+        })
+    } else {
+        Vec::new()
+    };
+
     // Generate suggestions
     let suggestions = generate_suggestions(&uncovered, config_dir);
 
@@ -159,12 +428,36 @@ pub fn execute(args: CoverageArgs) -> Result<()> {
     // Check threshold
     let threshold_met = args.threshold.map(|t| coverage_percentage >= t as f64);
 
+    let snapshot = CoverageSnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        coverage_percentage,
+        docs_count: doc_mappings.len(),
+        total_files,
+        uncovered_files: uncovered_count,
+    };
+    let history_path = config_dir.join(HISTORY_PATH);
+
+    // Compare against history before recording the current snapshot
+    let trend = if args.trend {
+        let history = load_history(&history_path)?;
+        Some(compute_trend(
+            &history,
+            coverage_percentage,
+            config.mapping.max_coverage_drop,
+        ))
+    } else {
+        None
+    };
+    let trend_passed = trend.as_ref().is_none_or(|t| t.passed);
+
     let results = CoverageResults {
+        schema_version: SCHEMA_VERSION,
         covered_files: covered_count,
         uncovered_files: uncovered_count,
         total_files,
         coverage_percentage,
         by_directory,
+        by_owner,
         uncovered: uncovered
             .iter()
             .map(|p| UncoveredFile {
@@ -175,51 +468,170 @@ pub fn execute(args: CoverageArgs) -> Result<()> {
         suggestions,
         threshold_met,
         threshold: args.threshold,
+        trend,
     };
 
-    // Output results
-    match args.format {
-        CoverageOutputFormat::Text => output_text(&results),
-        CoverageOutputFormat::Json => output_json(&results)?,
+    // Write badges before the threshold check so CI can publish a failing badge
+    write_badges(
+        args.badge.as_deref(),
+        args.badge_json.as_deref(),
+        coverage_percentage,
+    )?;
+
+    // Don't record a regression, so it keeps failing until coverage recovers
+    if args.record && trend_passed {
+        append_snapshot(&history_path, &snapshot)?;
     }
 
-    // Return error if threshold not met
-    if let Some(false) = threshold_met {
-        anyhow::bail!(
+    // Fail if the threshold isn't met or coverage dropped
+    let status = if let Some(false) = threshold_met {
+        Err(Violations(format!(
             "Coverage {:.1}% is below threshold {}%",
             coverage_percentage,
             args.threshold.unwrap()
-        );
-    }
+        ))
+        .into())
+    } else if let Some(trend) = results.trend.as_ref().filter(|t| !t.passed) {
+        Err(Violations(format!(
+            "Coverage dropped {:.1} points since the last snapshot (max allowed drop: {:.1})",
+            -trend.delta.unwrap_or_default(),
+            trend.max_drop
+        ))
+        .into())
+    } else {
+        Ok(())
+    };
+    Ok(Outcome { results, status })
+}
 
-    Ok(())
+/// Measure coverage of the whole project with the configured excludes.
+///
+/// Used by `pave nightly`, which records the same snapshots as
+/// `pave coverage --record`.
+pub(crate) fn take_snapshot(config: &PaveConfig, config_dir: &Path) -> Result<CoverageSnapshot> {
+    let ignore = PaveIgnore::load(config_dir)?;
+    let code_files = collect_code_files(config_dir, &[], &config.mapping.exclude, &ignore)?;
+    let docs_root = config_dir.join(&config.docs.root);
+    let doc_mappings =
+        load_doc_mappings(&docs_root, config_dir, &load_ignore(config_dir, config)?)?;
+    let (covered, uncovered) = analyze_coverage(&code_files, &doc_mappings, config_dir);
+
+    let total_files = code_files.len();
+    let coverage_percentage = if total_files > 0 {
+        (covered.len() as f64 / total_files as f64) * 100.0
+    } else {
+        100.0
+    };
+
+    Ok(CoverageSnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        coverage_percentage,
+        docs_count: doc_mappings.len(),
+        total_files,
+        uncovered_files: uncovered.len(),
+    })
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+/// Measure coverage as of `git_ref`, reading code files and docs from the
+/// ref's tree instead of the working directory.
+///
+/// Used by `pave summary` to report the change in coverage since a base ref.
+/// The current `.paveignore` and config apply to both sides.
+pub(crate) fn take_snapshot_at(
+    config: &PaveConfig,
+    config_dir: &Path,
+    git_ref: &str,
+) -> Result<CoverageSnapshot> {
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "-z", "--name-only", git_ref])
+        .current_dir(config_dir)
+        .output()
+        .context("Failed to run git ls-tree")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git ls-tree failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let files: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|f| !f.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    let ignore = PaveIgnore::load(config_dir)?;
+    let code_files: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| {
+            is_code_file(file)
+                && !file.components().any(|c| {
+                    let name = c.as_os_str().to_string_lossy();
+                    name.starts_with('.')
+                        || matches!(
+                            name.as_ref(),
+                            "target" | "node_modules" | "dist" | "build" | "__pycache__"
+                        )
+                })
+                && !ignore.is_excluded(
+                    &config_dir.join(file),
+                    matches_any_pattern(file, &config.mapping.exclude),
+                )
+        })
+        .cloned()
+        .collect();
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
+    let docs_ignore = load_ignore(config_dir, config)?;
+    let mut doc_mappings = Vec::new();
+    for file in &files {
+        let path = config_dir.join(file);
+        let is_doc = file.starts_with(&config.docs.root)
+            && file.extension().is_some_and(|ext| ext == "md")
+            && file.file_name().is_some_and(|name| name != "index.md")
+            && !under_dir_named(file, &config.docs.root, &["templates"])
+            && !docs_ignore.is_ignored(&path);
+        if !is_doc {
+            continue;
         }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
+        let output = Command::new("git")
+            .arg("show")
+            .arg(format!("{}:./{}", git_ref, file.display()))
+            .current_dir(config_dir)
+            .output()
+            .with_context(|| format!("Failed to read {} at {}", file.display(), git_ref))?;
+        let content = String::from_utf8_lossy(&output.stdout);
+        if let Some(mapping) = doc_mapping_from_content(&path, &content)? {
+            doc_mappings.push(mapping);
         }
     }
+
+    let (covered, uncovered) = analyze_coverage(&code_files, &doc_mappings, config_dir);
+    let total_files = code_files.len();
+    let coverage_percentage = if total_files > 0 {
+        (covered.len() as f64 / total_files as f64) * 100.0
+    } else {
+        100.0
+    };
+
+    Ok(CoverageSnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        coverage_percentage,
+        docs_count: doc_mappings.len(),
+        total_files,
+        uncovered_files: uncovered.len(),
+    })
 }
 
 /// Collect code files from the given path, applying include/exclude patterns.
-fn collect_code_files(root: &Path, include: &[String], exclude: &[String]) -> Result<Vec<PathBuf>> {
+///
+/// `.paveignore` rules take precedence over the exclude patterns.
+fn collect_code_files(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+    ignore: &PaveIgnore,
+) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
-    collect_code_files_recursive(root, root, include, exclude, &mut files)?;
+    collect_code_files_recursive(root, root, include, exclude, ignore, &mut files)?;
     files.sort();
     Ok(files)
 }
@@ -230,6 +642,7 @@ fn collect_code_files_recursive(
     current: &Path,
     include: &[String],
     exclude: &[String],
+    ignore: &PaveIgnore,
     files: &mut Vec<PathBuf>,
 ) -> Result<()> {
     let entries = match std::fs::read_dir(current) {
@@ -243,7 +656,7 @@ fn collect_code_files_recursive(
         let relative = path.strip_prefix(root).unwrap_or(&path);
 
         // Check exclusions first
-        if matches_any_pattern(relative, exclude) {
+        if ignore.is_excluded(&path, matches_any_pattern(relative, exclude)) {
             continue;
         }
 
@@ -264,7 +677,7 @@ fn collect_code_files_recursive(
         }
 
         if path.is_dir() {
-            collect_code_files_recursive(root, &path, include, exclude, files)?;
+            collect_code_files_recursive(root, &path, include, exclude, ignore, files)?;
         } else if is_code_file(&path) {
             // If include patterns specified, file must match at least one
             if !include.is_empty() && !matches_any_pattern(relative, include) {
@@ -278,7 +691,7 @@ fn collect_code_files_recursive(
 }
 
 /// Check if a file is a code file based on extension.
-fn is_code_file(path: &Path) -> bool {
+pub(crate) fn is_code_file(path: &Path) -> bool {
     let code_extensions = [
         "rs", "py", "js", "ts", "jsx", "tsx", "go", "java", "c", "cpp", "h", "hpp", "rb", "php",
         "swift", "kt", "scala", "sh", "bash", "zsh", "pl", "pm", "lua", "ex", "exs", "erl", "hrl",
@@ -292,65 +705,50 @@ fn is_code_file(path: &Path) -> bool {
 }
 
 /// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path, config_dir: &Path) -> Result<Vec<DocMapping>> {
-    let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, config_dir, &mut mappings)?;
-    Ok(mappings)
-}
-
-/// Recursively load documentation files.
-fn load_doc_mappings_recursive(
-    current: &Path,
+pub(crate) fn load_doc_mappings(
+    docs_root: &Path,
     config_dir: &Path,
-    mappings: &mut Vec<DocMapping>,
-) -> Result<()> {
-    let entries = match std::fs::read_dir(current) {
-        Ok(entries) => entries,
-        Err(_) => return Ok(()),
-    };
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip templates directory
-            if path.file_name().is_some_and(|n| n == "templates") {
-                continue;
-            }
-            load_doc_mappings_recursive(&path, config_dir, mappings)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            // Skip index.md
-            if path.file_name().is_some_and(|n| n == "index.md") {
-                continue;
-            }
-
-            if let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)? {
-                mappings.push(doc_mapping);
-            }
+    ignore: &PaveIgnore,
+) -> Result<Vec<DocMapping>> {
+    let mut mappings = Vec::new();
+    for path in find_docs(docs_root, ignore)? {
+        if let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)? {
+            mappings.push(doc_mapping);
         }
     }
-
-    Ok(())
+    Ok(mappings)
 }
 
 /// Parse a documentation file to extract path mappings.
 fn parse_doc_mapping(path: &Path, _config_dir: &Path) -> Result<Option<DocMapping>> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    doc_mapping_from_content(path, &content)
+}
 
-    let patterns = extract_paths_patterns(&content);
+/// Extract a document's path mappings from its content.
+fn doc_mapping_from_content(path: &Path, content: &str) -> Result<Option<DocMapping>> {
+    let mut patterns = extract_paths_patterns(content);
+
+    // Frontmatter `pave.paths` entries are also coverage mappings
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), content)?;
+    if let Some(frontmatter) = doc.frontmatter {
+        patterns.extend(frontmatter.paths);
+    }
 
     // Only include docs that have path mappings
     if patterns.is_empty() {
         return Ok(None);
     }
 
-    Ok(Some(DocMapping { patterns }))
+    Ok(Some(DocMapping {
+        doc: path.to_path_buf(),
+        patterns,
+    }))
 }
 
 /// Extract path patterns from the ## Paths section.
-fn extract_paths_patterns(content: &str) -> Vec<String> {
+pub(crate) fn extract_paths_patterns(content: &str) -> Vec<String> {
     let mut patterns = Vec::new();
     let mut in_paths_section = false;
     let mut tracker = CodeBlockTracker::new();
@@ -425,7 +823,7 @@ fn analyze_coverage(
 }
 
 /// Check if a path matches any of the glob patterns.
-fn matches_any_pattern<S: AsRef<str>>(path: &Path, patterns: &[S]) -> bool {
+pub(crate) fn matches_any_pattern<S: AsRef<str>>(path: &Path, patterns: &[S]) -> bool {
     let path_str = path.to_string_lossy();
 
     for pattern_str in patterns {
@@ -501,6 +899,71 @@ fn calculate_directory_coverage(
     result
 }
 
+/// Calculate coverage statistics per owner.
+///
+/// Files with several owners count toward each of them. Files without an
+/// owner are grouped under `(unowned)`.
+fn calculate_owner_coverage<F>(
+    covered: &[PathBuf],
+    uncovered: &[PathBuf],
+    owners_for: F,
+) -> Vec<OwnerCoverage>
+where
+    F: Fn(&PathBuf) -> Vec<String>,
+{
+    let mut stats: HashMap<String, OwnerCoverage> = HashMap::new();
+
+    let files = covered
+        .iter()
+        .map(|f| (f, true))
+        .chain(uncovered.iter().map(|f| (f, false)));
+
+    for (file, is_covered) in files {
+        let mut owners = owners_for(file);
+        if owners.is_empty() {
+            owners.push(UNOWNED.to_string());
+        }
+        for owner in owners {
+            let entry = stats.entry(owner.clone()).or_insert_with(|| OwnerCoverage {
+                owner,
+                covered: 0,
+                total: 0,
+                percentage: 0.0,
+                uncovered: Vec::new(),
+            });
+            entry.total += 1;
+            if is_covered {
+                entry.covered += 1;
+            } else {
+                entry.uncovered.push(file.clone());
+            }
+        }
+    }
+
+    let mut result: Vec<OwnerCoverage> = stats
+        .into_values()
+        .map(|mut owner| {
+            owner.percentage = (owner.covered as f64 / owner.total as f64) * 100.0;
+            owner.uncovered.sort();
+            owner
+        })
+        .collect();
+
+    sort_owners(&mut result);
+    result
+}
+
+/// Sort owners with the most undocumented files first, so doc debt is easy
+/// to assign.
+fn sort_owners(owners: &mut [OwnerCoverage]) {
+    owners.sort_by(|a, b| {
+        b.uncovered
+            .len()
+            .cmp(&a.uncovered.len())
+            .then_with(|| a.owner.cmp(&b.owner))
+    });
+}
+
 /// Generate suggestions for improving coverage.
 fn generate_suggestions(uncovered: &[PathBuf], _config_dir: &Path) -> Vec<CoverageSuggestion> {
     // Group uncovered files by directory
@@ -528,10 +991,9 @@ fn generate_suggestions(uncovered: &[PathBuf], _config_dir: &Path) -> Vec<Covera
         .collect();
 
     // Sort by number of files (most impactful first)
-    suggestions.sort_by(|a, b| b.files.len().cmp(&a.files.len()));
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.files.len()));
 
-    // Limit to top 5 suggestions
-    suggestions.truncate(5);
+    suggestions.truncate(MAX_SUGGESTIONS);
     suggestions
 }
 
@@ -550,6 +1012,158 @@ fn suggest_doc_name_for_dir(dir: &str) -> String {
     format!("docs/components/{}.md", name)
 }
 
+/// Load coverage snapshots from a JSON Lines history file.
+///
+/// A missing file is treated as an empty history.
+pub(crate) fn load_history(path: &Path) -> Result<Vec<CoverageSnapshot>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read coverage history: {}", path.display()))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).with_context(|| {
+                format!(
+                    "Invalid snapshot in coverage history {}:{}",
+                    path.display(),
+                    idx + 1
+                )
+            })
+        })
+        .collect()
+}
+
+/// Append a snapshot to the history file, creating it if needed.
+pub(crate) fn append_snapshot(path: &Path, snapshot: &CoverageSnapshot) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let line = serde_json::to_string(snapshot).context("Failed to serialize snapshot")?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open coverage history: {}", path.display()))?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Failed to write coverage history: {}", path.display()))
+}
+
+/// Compare current coverage against the most recent snapshot.
+fn compute_trend(history: &[CoverageSnapshot], current: f64, max_drop: f64) -> CoverageTrend {
+    let delta = history
+        .last()
+        .map(|last| current - last.coverage_percentage);
+    let passed = delta.is_none_or(|d| -d <= max_drop);
+    let start = history.len().saturating_sub(TREND_DISPLAY_LIMIT);
+
+    CoverageTrend {
+        history: history[start..].to_vec(),
+        delta,
+        max_drop,
+        passed,
+    }
+}
+
+/// Write the requested coverage badges.
+fn write_badges(svg: Option<&Path>, json: Option<&Path>, percentage: f64) -> Result<()> {
+    if let Some(path) = svg {
+        std::fs::write(
+            path,
+            render_badge_svg(
+                BADGE_LABEL,
+                &badge_message(percentage),
+                badge_color(percentage),
+            ),
+        )
+        .with_context(|| format!("Failed to write badge: {}", path.display()))?;
+    }
+    if let Some(path) = json {
+        let endpoint = BadgeEndpoint {
+            schema_version: 1,
+            label: BADGE_LABEL.to_string(),
+            message: badge_message(percentage),
+            color: badge_color(percentage).to_string(),
+        };
+        let content =
+            serde_json::to_string_pretty(&endpoint).context("Failed to serialize badge")?;
+        std::fs::write(path, content + "\n")
+            .with_context(|| format!("Failed to write badge: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Format a coverage percentage for display on a badge.
+fn badge_message(percentage: f64) -> String {
+    format!("{:.0}%", percentage.floor())
+}
+
+/// Pick a badge color using the same bands as common code coverage shields.
+fn badge_color(percentage: f64) -> &'static str {
+    match percentage {
+        p if p >= 90.0 => "brightgreen",
+        p if p >= 75.0 => "green",
+        p if p >= 60.0 => "yellowgreen",
+        p if p >= 40.0 => "yellow",
+        p if p >= 20.0 => "orange",
+        _ => "red",
+    }
+}
+
+/// Map a shields.io color name to its hex value.
+fn badge_color_hex(color: &str) -> &'static str {
+    match color {
+        "brightgreen" => "#4c1",
+        "green" => "#97ca00",
+        "yellowgreen" => "#a4a61d",
+        "yellow" => "#dfb317",
+        "orange" => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// Render a flat shields-style SVG badge.
+///
+/// Text widths are estimated from character count since no font metrics
+/// are available; this is close enough for the short strings we render.
+fn render_badge_svg(label: &str, message: &str, color: &str) -> String {
+    let text_width = |s: &str| s.chars().count() * 7 + 10;
+    let label_width = text_width(label);
+    let message_width = text_width(message);
+    let total_width = label_width + message_width;
+    let label_x = label_width * 5;
+    let message_x = (label_width * 10) + message_width * 5;
+    let fill = badge_color_hex(color);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{total_width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{fill}"/>
+    <rect width="{total_width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="110">
+    <text x="{label_x}" y="140" transform="scale(.1)">{label}</text>
+    <text x="{message_x}" y="140" transform="scale(.1)">{message}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
 /// Output results in text format.
 fn output_text(results: &CoverageResults) {
     println!("Code Coverage Report");
@@ -587,6 +1201,21 @@ fn output_text(results: &CoverageResults) {
         println!();
     }
 
+    if !results.by_owner.is_empty() {
+        println!("By Owner:");
+        for owner in &results.by_owner {
+            println!(
+                "  {:<30} {}/{} files ({:.0}%), {} undocumented",
+                owner.owner,
+                owner.covered,
+                owner.total,
+                owner.percentage,
+                owner.uncovered.len()
+            );
+        }
+        println!();
+    }
+
     if !results.uncovered.is_empty() {
         println!("Uncovered Files ({}):", results.uncovered.len());
         // Limit display to first 20 files
@@ -613,6 +1242,30 @@ fn output_text(results: &CoverageResults) {
         println!();
     }
 
+    if let Some(ref trend) = results.trend {
+        println!("Coverage Trend:");
+        let mut previous: Option<f64> = None;
+        for snapshot in &trend.history {
+            println!(
+                "  {:<24} {:>6.1}%{}",
+                snapshot.timestamp,
+                snapshot.coverage_percentage,
+                format_delta(previous.map(|p| snapshot.coverage_percentage - p))
+            );
+            previous = Some(snapshot.coverage_percentage);
+        }
+        println!(
+            "  {:<24} {:>6.1}%{}",
+            "now",
+            results.coverage_percentage,
+            format_delta(trend.delta)
+        );
+        if trend.history.is_empty() {
+            println!("  (no history yet; run with --record to start tracking)");
+        }
+        println!();
+    }
+
     if let Some(threshold) = results.threshold {
         let status = if results.threshold_met.unwrap_or(true) {
             "✓ PASS"
@@ -626,6 +1279,14 @@ fn output_text(results: &CoverageResults) {
     }
 }
 
+/// Format a percentage-point change for the trend table.
+fn format_delta(delta: Option<f64>) -> String {
+    match delta {
+        Some(d) => format!("  ({:+.1})", d),
+        None => String::new(),
+    }
+}
+
 /// Output results in JSON format.
 fn output_json(results: &CoverageResults) -> Result<()> {
     let json = serde_json::to_string_pretty(results).context("Failed to serialize results")?;
@@ -754,6 +1415,30 @@ No paths section.
         assert!(!is_code_file(Path::new("data.json")));
     }
 
+    #[test]
+    fn test_collect_code_files_paveignore_overrides_excludes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/generated.rs"), "").unwrap();
+        fs::write(root.join("scripts/release.sh"), "").unwrap();
+        fs::write(root.join(".paveignore"), "!src/generated.rs\nscripts/\n").unwrap();
+        let ignore = PaveIgnore::load(root).unwrap();
+
+        let exclude = vec!["src/generated.rs".to_string()];
+        let files = collect_code_files(root, &[], &exclude, &ignore).unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("src/generated.rs"),
+                PathBuf::from("src/main.rs")
+            ]
+        );
+    }
+
     #[test]
     fn test_calculate_directory_coverage() {
         let covered = vec![
@@ -791,6 +1476,7 @@ No paths section.
         ];
 
         let doc_mappings = vec![DocMapping {
+            doc: PathBuf::from("docs/cli.md"),
             patterns: vec!["src/cli.rs".to_string(), "src/main.rs".to_string()],
         }];
 
@@ -831,6 +1517,67 @@ Example content.
         assert_eq!(mapping.patterns[1], "src/lib/*.rs");
     }
 
+    #[test]
+    fn test_parse_doc_mapping_includes_frontmatter_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = temp_dir.path().join("test.md");
+
+        let content = r#"---
+pave:
+  paths:
+    - src/api/**
+---
+# Test Component
+
+## Purpose
+Test document.
+"#;
+        fs::write(&doc_path, content).unwrap();
+
+        let mapping = parse_doc_mapping(&doc_path, temp_dir.path())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(mapping.patterns, vec!["src/api/**".to_string()]);
+    }
+
+    #[test]
+    fn test_calculate_owner_coverage() {
+        let owners = CodeOwners::parse("src/api/ @org/api @org/platform\nsrc/web/ @org/web\n");
+        let covered = vec![
+            PathBuf::from("src/api/handlers.rs"),
+            PathBuf::from("src/web/app.ts"),
+        ];
+        let uncovered = vec![
+            PathBuf::from("src/api/routes.rs"),
+            PathBuf::from("scripts/deploy.sh"),
+        ];
+
+        let coverage =
+            calculate_owner_coverage(&covered, &uncovered, |f| owners.owners_for(f).to_vec());
+
+        let api = coverage.iter().find(|o| o.owner == "@org/api").unwrap();
+        assert_eq!(api.covered, 1);
+        assert_eq!(api.total, 2);
+        assert_eq!(api.uncovered, vec![PathBuf::from("src/api/routes.rs")]);
+
+        // Files with multiple owners count toward each
+        let platform = coverage
+            .iter()
+            .find(|o| o.owner == "@org/platform")
+            .unwrap();
+        assert_eq!(platform.total, 2);
+
+        let web = coverage.iter().find(|o| o.owner == "@org/web").unwrap();
+        assert_eq!(web.percentage, 100.0);
+
+        let unowned = coverage.iter().find(|o| o.owner == UNOWNED).unwrap();
+        assert_eq!(unowned.uncovered, vec![PathBuf::from("scripts/deploy.sh")]);
+
+        // Owners with the most undocumented files come first
+        assert!(coverage[0].uncovered.len() >= coverage.last().unwrap().uncovered.len());
+    }
+
     #[test]
     fn test_parse_doc_mapping_no_paths() {
         let temp_dir = TempDir::new().unwrap();
@@ -899,10 +1646,99 @@ No paths section.
         // Create an index.md (should be skipped)
         fs::write(docs_dir.join("index.md"), "# Index").unwrap();
 
-        let mappings = load_doc_mappings(&docs_dir, temp_dir.path()).unwrap();
+        let mappings =
+            load_doc_mappings(&docs_dir, temp_dir.path(), &PaveIgnore::default()).unwrap();
 
         // Should only include the doc with paths, not the one without or index.md
         assert_eq!(mappings.len(), 1);
         assert!(mappings[0].patterns.contains(&"src/*.rs".to_string()));
     }
+
+    #[test]
+    fn test_badge_color_bands() {
+        assert_eq!(badge_color(100.0), "brightgreen");
+        assert_eq!(badge_color(80.0), "green");
+        assert_eq!(badge_color(65.0), "yellowgreen");
+        assert_eq!(badge_color(45.0), "yellow");
+        assert_eq!(badge_color(25.0), "orange");
+        assert_eq!(badge_color(5.0), "red");
+    }
+
+    #[test]
+    fn test_badge_message_rounds_down() {
+        // Never show 100% unless everything is covered
+        assert_eq!(badge_message(99.6), "99%");
+        assert_eq!(badge_message(100.0), "100%");
+    }
+
+    #[test]
+    fn test_write_badges() {
+        let temp_dir = TempDir::new().unwrap();
+        let svg_path = temp_dir.path().join("coverage.svg");
+        let json_path = temp_dir.path().join("coverage.json");
+
+        write_badges(Some(&svg_path), Some(&json_path), 82.5).unwrap();
+
+        let svg = fs::read_to_string(&svg_path).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("doc coverage: 82%"));
+        assert!(svg.contains("#97ca00"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["label"], "doc coverage");
+        assert_eq!(json["message"], "82%");
+        assert_eq!(json["color"], "green");
+    }
+
+    fn snapshot(percentage: f64) -> CoverageSnapshot {
+        CoverageSnapshot {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            coverage_percentage: percentage,
+            docs_count: 3,
+            total_files: 10,
+            uncovered_files: 2,
+        }
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_PATH);
+
+        assert!(load_history(&path).unwrap().is_empty());
+
+        append_snapshot(&path, &snapshot(80.0)).unwrap();
+        append_snapshot(&path, &snapshot(85.0)).unwrap();
+
+        let history = load_history(&path).unwrap();
+        assert_eq!(history, vec![snapshot(80.0), snapshot(85.0)]);
+    }
+
+    #[test]
+    fn test_load_history_reports_bad_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.jsonl");
+        fs::write(&path, "not json\n").unwrap();
+
+        let err = load_history(&path).unwrap_err();
+        assert!(format!("{}", err).contains("history.jsonl:1"));
+    }
+
+    #[test]
+    fn test_compute_trend() {
+        let history = vec![snapshot(70.0), snapshot(80.0)];
+
+        let trend = compute_trend(&history, 79.5, 1.0);
+        assert_eq!(trend.delta, Some(-0.5));
+        assert!(trend.passed);
+
+        let trend = compute_trend(&history, 75.0, 1.0);
+        assert!(!trend.passed);
+
+        let trend = compute_trend(&[], 10.0, 0.0);
+        assert_eq!(trend.delta, None);
+        assert!(trend.passed);
+    }
 }
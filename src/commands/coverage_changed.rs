@@ -5,14 +5,20 @@
 
 use anyhow::{Context, Result};
 use glob::Pattern;
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::cli::CoverageOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::git::determine_base_ref;
+use crate::ignore::PaveIgnore;
 use crate::parser::CodeBlockTracker;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
 
 /// Arguments for the `pave coverage-changed` command.
 pub struct CoverageChangedArgs {
@@ -34,7 +40,7 @@ struct DocMapping {
 }
 
 /// Information about an uncovered new file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct UncoveredNewFile {
     /// Path to the uncovered file.
     pub path: PathBuf,
@@ -44,8 +50,10 @@ pub struct UncoveredNewFile {
 }
 
 /// Results of the coverage-changed analysis.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CoverageChangedResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Base ref that was compared against.
     pub base_ref: String,
     /// Total number of new files in the diff.
@@ -63,9 +71,9 @@ pub struct CoverageChangedResults {
 }
 
 /// Execute the `pave coverage-changed` command.
-pub fn execute(args: CoverageChangedArgs) -> Result<()> {
+pub fn execute(args: CoverageChangedArgs, workdir: &Workdir) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
     let docs_root = config_dir.join(&config.docs.root);
@@ -73,12 +81,13 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     // Build exclude patterns (merge config + CLI)
     let mut exclude_patterns = config.mapping.exclude.clone();
     exclude_patterns.extend(args.exclude.clone());
+    let ignore = PaveIgnore::load(config_dir)?;
 
     // Determine base ref
-    let base_ref = determine_base_ref(args.base.as_deref())?;
+    let base_ref = determine_base_ref(config_dir, args.base.as_deref())?;
 
     // Get added files from git
-    let added_files = get_added_files(&base_ref)?;
+    let added_files = get_added_files(&base_ref, config_dir)?;
     let new_files_count = added_files.len();
 
     if added_files.is_empty() {
@@ -86,6 +95,7 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
             println!("No new files found compared to {}", base_ref);
         } else {
             let results = CoverageChangedResults {
+                schema_version: SCHEMA_VERSION,
                 base_ref,
                 new_files_count: 0,
                 new_code_files_count: 0,
@@ -104,8 +114,11 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
         .into_iter()
         .filter(|p| is_code_file(p))
         .filter(|p| {
-            // Check exclusions
-            !matches_any_pattern(p, &exclude_patterns)
+            // Check exclusions; .paveignore overrides the exclude patterns
+            !ignore.is_excluded(
+                &config_dir.join(p),
+                matches_any_pattern(p, &exclude_patterns),
+            )
         })
         .filter(|p| {
             // If include patterns specified, file must match at least one
@@ -125,6 +138,7 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
             );
         } else {
             let results = CoverageChangedResults {
+                schema_version: SCHEMA_VERSION,
                 base_ref,
                 new_files_count: 0,
                 new_code_files_count: 0,
@@ -139,12 +153,13 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     }
 
     // Load all doc mappings
-    let doc_mappings = load_doc_mappings(&docs_root)?;
+    let doc_mappings = load_doc_mappings(&docs_root, &load_ignore(config_dir, &config)?)?;
 
     // Determine coverage for each new file
     let (covered, uncovered) = analyze_coverage(&new_code_files, &doc_mappings);
 
     let results = CoverageChangedResults {
+        schema_version: SCHEMA_VERSION,
         base_ref: base_ref.clone(),
         new_files_count,
         new_code_files_count: new_code_files.len(),
@@ -168,68 +183,22 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
 
     // Return error if any new code files are uncovered
     if !results.all_covered {
-        anyhow::bail!(
+        anyhow::bail!(Violations(format!(
             "{} new code file{} not covered by documentation",
             results.uncovered_count,
-            if results.uncovered_count == 1 { "" } else { "s" }
-        );
+            if results.uncovered_count == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )));
     }
 
     Ok(())
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
-/// Determine the base ref to compare against.
-fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
-    if let Some(base) = explicit_base {
-        return Ok(base.to_string());
-    }
-
-    // Try origin/main first
-    if ref_exists("origin/main") {
-        return Ok("origin/main".to_string());
-    }
-
-    // Try origin/master
-    if ref_exists("origin/master") {
-        return Ok("origin/master".to_string());
-    }
-
-    // Fall back to HEAD~1
-    Ok("HEAD~1".to_string())
-}
-
-/// Check if a git ref exists.
-fn ref_exists(ref_name: &str) -> bool {
-    Command::new("git")
-        .args(["rev-parse", "--verify", "--quiet", ref_name])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Get the list of added files from git diff.
-fn get_added_files(base_ref: &str) -> Result<Vec<PathBuf>> {
+/// Get the list of added files from git diff, run in `dir`.
+fn get_added_files(base_ref: &str, dir: &Path) -> Result<Vec<PathBuf>> {
     // Use --diff-filter=A to only get added files
     let output = Command::new("git")
         .args([
@@ -238,6 +207,7 @@ fn get_added_files(base_ref: &str) -> Result<Vec<PathBuf>> {
             "--diff-filter=A",
             &format!("{}..HEAD", base_ref),
         ])
+        .current_dir(dir)
         .output()
         .context("Failed to run git diff")?;
 
@@ -245,6 +215,7 @@ fn get_added_files(base_ref: &str) -> Result<Vec<PathBuf>> {
         // Try without ..HEAD for cases like HEAD~1
         let output = Command::new("git")
             .args(["diff", "--name-only", "--diff-filter=A", base_ref])
+            .current_dir(dir)
             .output()
             .context("Failed to run git diff")?;
 
@@ -285,45 +256,14 @@ fn is_code_file(path: &Path) -> bool {
 }
 
 /// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path) -> Result<Vec<DocMapping>> {
+fn load_doc_mappings(docs_root: &Path, ignore: &PaveIgnore) -> Result<Vec<DocMapping>> {
     let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, &mut mappings)?;
-    Ok(mappings)
-}
-
-/// Recursively load documentation files.
-fn load_doc_mappings_recursive(
-    current: &Path,
-    mappings: &mut Vec<DocMapping>,
-) -> Result<()> {
-    let entries = match std::fs::read_dir(current) {
-        Ok(entries) => entries,
-        Err(_) => return Ok(()),
-    };
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip templates directory
-            if path.file_name().is_some_and(|n| n == "templates") {
-                continue;
-            }
-            load_doc_mappings_recursive(&path, mappings)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            // Skip index.md
-            if path.file_name().is_some_and(|n| n == "index.md") {
-                continue;
-            }
-
-            if let Some(doc_mapping) = parse_doc_mapping(&path)? {
-                mappings.push(doc_mapping);
-            }
+    for path in find_docs(docs_root, ignore)? {
+        if let Some(doc_mapping) = parse_doc_mapping(&path)? {
+            mappings.push(doc_mapping);
         }
     }
-
-    Ok(())
+    Ok(mappings)
 }
 
 /// Parse a documentation file to extract path mappings.
@@ -388,7 +328,10 @@ fn extract_paths_patterns(content: &str) -> Vec<String> {
 }
 
 /// Analyze coverage of code files against doc patterns.
-fn analyze_coverage(code_files: &[PathBuf], doc_mappings: &[DocMapping]) -> (Vec<PathBuf>, Vec<PathBuf>) {
+fn analyze_coverage(
+    code_files: &[PathBuf],
+    doc_mappings: &[DocMapping],
+) -> (Vec<PathBuf>, Vec<PathBuf>) {
     let mut covered = Vec::new();
     let mut uncovered = Vec::new();
 
@@ -478,7 +421,11 @@ fn output_text(results: &CoverageChangedResults) {
     println!(
         "Uncovered: {} file{} ({:.1}%)",
         results.uncovered_count,
-        if results.uncovered_count == 1 { "" } else { "s" },
+        if results.uncovered_count == 1 {
+            ""
+        } else {
+            "s"
+        },
         if results.new_code_files_count > 0 {
             (results.uncovered_count as f64 / results.new_code_files_count as f64) * 100.0
         } else {
@@ -504,8 +451,16 @@ fn output_text(results: &CoverageChangedResults) {
         println!(
             "{} new code file{} need{} documentation coverage.",
             results.uncovered_count,
-            if results.uncovered_count == 1 { "" } else { "s" },
-            if results.uncovered_count == 1 { "s" } else { "" }
+            if results.uncovered_count == 1 {
+                ""
+            } else {
+                "s"
+            },
+            if results.uncovered_count == 1 {
+                "s"
+            } else {
+                ""
+            }
         );
     }
 }
@@ -736,7 +691,7 @@ No paths section.
         // Create an index.md (should be skipped)
         fs::write(docs_dir.join("index.md"), "# Index").unwrap();
 
-        let mappings = load_doc_mappings(&docs_dir).unwrap();
+        let mappings = load_doc_mappings(&docs_dir, &PaveIgnore::default()).unwrap();
 
         // Should only include the doc with paths, not the one without or index.md
         assert_eq!(mappings.len(), 1);
@@ -0,0 +1,445 @@
+//! Implementation of the `pave dedupe` command.
+//!
+//! Copy-pasted sections drift: a runbook step fixed in one doc stays wrong in
+//! every doc that copied it. `pave dedupe` compares each section with the
+//! sections of every other doc and reports groups of near-identical ones, so
+//! they can be kept in one place and linked from the rest.
+//!
+//! Sections are compared by their shingles, the runs of [`SHINGLE_SIZE`]
+//! consecutive words after lowercasing and dropping punctuation. Two
+//! sections' similarity is the share of shingles they have in common
+//! (Jaccard index), so rewording a sentence or fixing a typo in one copy
+//! only lowers it slightly.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{OutputFormat, PathStyle};
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::parser::ParsedDoc;
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
+
+/// Words per shingle.
+const SHINGLE_SIZE: usize = 5;
+
+/// Arguments for the `pave dedupe` command.
+pub struct DedupeArgs {
+    /// Output format.
+    pub format: OutputFormat,
+    /// Similarity (0 to 1) at which two sections count as duplicates.
+    pub threshold: f64,
+    /// Sections with fewer words are not compared.
+    pub min_words: usize,
+    /// Fail if any duplicates are found.
+    pub strict: bool,
+}
+
+/// A section that is near-identical to a section of another doc.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct DuplicateSection {
+    /// Path to the document.
+    pub file: PathBuf,
+    /// Section heading, without the `## `.
+    pub section: String,
+    /// Line of the section heading (1-indexed).
+    pub line: usize,
+    /// Number of words in the section.
+    pub words: usize,
+}
+
+/// Sections that are near-identical to each other.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicateGroup {
+    /// Lowest similarity (0 to 1) among the matched pairs in the group.
+    pub similarity: f64,
+    /// The sections, by file and line.
+    pub sections: Vec<DuplicateSection>,
+}
+
+/// Results of the dedupe command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DedupeResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Similarity at which sections count as duplicates.
+    pub threshold: f64,
+    /// Sections with fewer words were not compared.
+    pub min_words: usize,
+    /// Number of documents read.
+    pub docs_checked: usize,
+    /// Number of sections long enough to compare.
+    pub sections_compared: usize,
+    /// Groups of near-duplicate sections, most similar first.
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// A section with its shingles, ready to compare.
+struct Candidate {
+    section: DuplicateSection,
+    shingles: HashSet<u64>,
+}
+
+/// Execute the `pave dedupe` command.
+pub fn execute(args: DedupeArgs, workdir: &Workdir) -> Result<()> {
+    if !(args.threshold > 0.0 && args.threshold <= 1.0) {
+        anyhow::bail!(
+            "--threshold must be above 0 and at most 1, got {}",
+            args.threshold
+        );
+    }
+
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let docs_root = config_dir.join(&config.docs.root);
+    let docs = find_docs(&docs_root, &load_ignore(config_dir, &config)?)?;
+    let results = find_duplicates(&docs, config_dir, args.threshold, args.min_words)?;
+
+    match args.format {
+        OutputFormat::Text => output_text(&results),
+        OutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+        OutputFormat::Github => output_github(&results),
+    }
+
+    if args.strict && !results.groups.is_empty() {
+        anyhow::bail!(Violations(format!(
+            "{} group{} of duplicate sections",
+            results.groups.len(),
+            if results.groups.len() == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
+/// Find sections of different docs that are at least `threshold` similar.
+pub(crate) fn find_duplicates(
+    docs: &[PathBuf],
+    config_dir: &Path,
+    threshold: f64,
+    min_words: usize,
+) -> Result<DedupeResults> {
+    let mut candidates = Vec::new();
+    for path in docs {
+        let doc = ParsedDoc::parse(path)?;
+        let file = display_path(path, PathStyle::Workspace, config_dir, config_dir);
+        for section in &doc.sections {
+            let words = words(&section.content);
+            if words.len() < min_words.max(1) {
+                continue;
+            }
+            candidates.push(Candidate {
+                section: DuplicateSection {
+                    file: file.clone(),
+                    section: section.name.clone(),
+                    line: section.start_line,
+                    words: words.len(),
+                },
+                shingles: shingles(&words),
+            });
+        }
+    }
+
+    // Count shared shingles only for pairs that have any, via an inverted index
+    let mut postings: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (idx, candidate) in candidates.iter().enumerate() {
+        for &shingle in &candidate.shingles {
+            postings.entry(shingle).or_default().push(idx);
+        }
+    }
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for sections in postings.values() {
+        for (pos, &a) in sections.iter().enumerate() {
+            for &b in &sections[pos + 1..] {
+                if candidates[a].section.file != candidates[b].section.file {
+                    *shared.entry((a, b)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    // Join similar pairs into groups
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    let mut pairs = Vec::new();
+    for (&(a, b), &common) in &shared {
+        let total = candidates[a].shingles.len() + candidates[b].shingles.len() - common;
+        let similarity = common as f64 / total as f64;
+        if similarity >= threshold {
+            let (root_a, root_b) = (find_root(&mut parent, a), find_root(&mut parent, b));
+            parent[root_b] = root_a;
+            pairs.push((a, similarity));
+        }
+    }
+    let mut groups: HashMap<usize, DuplicateGroup> = HashMap::new();
+    for (a, similarity) in pairs {
+        let root = find_root(&mut parent, a);
+        let group = groups.entry(root).or_insert_with(|| DuplicateGroup {
+            similarity,
+            sections: Vec::new(),
+        });
+        group.similarity = group.similarity.min(similarity);
+    }
+    for (idx, candidate) in candidates.iter().enumerate() {
+        let root = find_root(&mut parent, idx);
+        if let Some(group) = groups.get_mut(&root) {
+            group.sections.push(candidate.section.clone());
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = groups.into_values().collect();
+    for group in &mut groups {
+        group
+            .sections
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    }
+    groups.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.sections[0].file.cmp(&b.sections[0].file))
+            .then_with(|| a.sections[0].line.cmp(&b.sections[0].line))
+    });
+
+    Ok(DedupeResults {
+        schema_version: SCHEMA_VERSION,
+        threshold,
+        min_words,
+        docs_checked: docs.len(),
+        sections_compared: candidates.len(),
+        groups,
+    })
+}
+
+/// Lowercased words of `text`, ignoring punctuation and markdown syntax.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Hashes of each run of [`SHINGLE_SIZE`] words. Shorter texts are one shingle.
+fn shingles(words: &[String]) -> HashSet<u64> {
+    words
+        .windows(SHINGLE_SIZE.min(words.len()))
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Representative of `idx`'s group, compressing the path to it.
+fn find_root(parent: &mut [usize], idx: usize) -> usize {
+    let mut root = idx;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut current = idx;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+    root
+}
+
+fn percent(similarity: f64) -> String {
+    format!("{:.0}%", similarity * 100.0)
+}
+
+fn output_text(results: &DedupeResults) {
+    if results.groups.is_empty() {
+        println!(
+            "No near-duplicate sections in {} docs ({} sections of {}+ words compared at {} similarity)",
+            results.docs_checked,
+            results.sections_compared,
+            results.min_words,
+            percent(results.threshold)
+        );
+        return;
+    }
+
+    println!(
+        "Found {} group{} of near-duplicate sections in {} docs:",
+        results.groups.len(),
+        if results.groups.len() == 1 { "" } else { "s" },
+        results.docs_checked
+    );
+    for group in &results.groups {
+        println!();
+        println!("{} similar:", percent(group.similarity));
+        for section in &group.sections {
+            println!(
+                "  {}:{}  ## {} ({} words)",
+                section.file.display(),
+                section.line,
+                section.section,
+                section.words
+            );
+        }
+    }
+    println!();
+    println!(
+        "Keep each section in one doc and link to it from the others, so fixes land everywhere."
+    );
+}
+
+fn output_github(results: &DedupeResults) {
+    for group in &results.groups {
+        for section in &group.sections {
+            let others: Vec<String> = group
+                .sections
+                .iter()
+                .filter(|other| *other != section)
+                .map(|other| format!("{}:{}", other.file.display(), other.line))
+                .collect();
+            println!(
+                "::warning file={},line={}::Section '{}' is {} similar to {}; keep one copy and link to it",
+                section.file.display(),
+                section.line,
+                section.section,
+                percent(group.similarity),
+                others.join(", ")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const ROLLBACK: &str = "Scale the deployment down to zero replicas, restore the previous \
+        image tag in the release manifest, apply it with kubectl, wait for every pod to report \
+        ready, then page the on-call engineer if the error rate has not recovered within ten \
+        minutes of the rollback.";
+
+    fn write_doc(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn similarity(a: &str, b: &str) -> f64 {
+        let (a, b) = (shingles(&words(a)), shingles(&words(b)));
+        a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+    }
+
+    #[test]
+    fn shingles_tolerate_small_edits() {
+        assert_eq!(similarity(ROLLBACK, ROLLBACK), 1.0);
+        // Case, punctuation, and markdown don't matter
+        let restyled = ROLLBACK.to_uppercase().replace(", ", " **,** ");
+        assert_eq!(similarity(ROLLBACK, &restyled), 1.0);
+
+        let edited = ROLLBACK.replace("ten minutes", "fifteen minutes");
+        assert!(similarity(ROLLBACK, &edited) > 0.7);
+
+        let unrelated = "Run cargo test before opening a pull request and fix any clippy warnings.";
+        assert_eq!(similarity(ROLLBACK, unrelated), 0.0);
+    }
+
+    #[test]
+    fn finds_near_duplicate_sections_across_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let edited = ROLLBACK.replace("ten minutes", "fifteen minutes");
+        let docs = vec![
+            write_doc(
+                dir,
+                "deploy.md",
+                &format!(
+                    "# Deploy\n\n## Purpose\n\nShip it.\n\n## Rollback\n\n{}\n",
+                    ROLLBACK
+                ),
+            ),
+            write_doc(
+                dir,
+                "hotfix.md",
+                &format!("# Hotfix\n\n## Undo\n\n{}\n", edited),
+            ),
+            write_doc(
+                dir,
+                "other.md",
+                "# Other\n\n## Purpose\n\nSomething else entirely, with enough words to be compared \
+                 against the others but no overlap with the rollback steps at all.\n",
+            ),
+        ];
+
+        let results = find_duplicates(&docs, dir, 0.7, 10).unwrap();
+
+        assert_eq!(results.docs_checked, 3);
+        assert_eq!(results.groups.len(), 1);
+        let group = &results.groups[0];
+        assert!(group.similarity > 0.7 && group.similarity < 1.0);
+        let found: Vec<_> = group
+            .sections
+            .iter()
+            .map(|s| (s.file.to_str().unwrap(), s.section.as_str(), s.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("deploy.md", "Rollback", 7), ("hotfix.md", "Undo", 3)]
+        );
+    }
+
+    #[test]
+    fn ignores_short_sections_and_repeats_within_a_doc() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let docs = vec![
+            write_doc(
+                dir,
+                "a.md",
+                &format!(
+                    "# A\n\n## One\n\n{}\n\n## Two\n\n{}\n\n## Tiny\n\nSee above.\n",
+                    ROLLBACK, ROLLBACK
+                ),
+            ),
+            write_doc(dir, "b.md", "# B\n\n## Tiny\n\nSee above.\n"),
+        ];
+
+        let results = find_duplicates(&docs, dir, 0.8, 10).unwrap();
+
+        assert_eq!(results.sections_compared, 2);
+        assert!(results.groups.is_empty());
+    }
+
+    #[test]
+    fn groups_join_every_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let docs: Vec<_> = ["a.md", "b.md", "c.md"]
+            .iter()
+            .map(|name| {
+                write_doc(
+                    dir,
+                    name,
+                    &format!("# Doc\n\n## Rollback\n\n{}\n", ROLLBACK),
+                )
+            })
+            .collect();
+
+        let results = find_duplicates(&docs, dir, 0.9, 10).unwrap();
+
+        assert_eq!(results.groups.len(), 1);
+        assert_eq!(results.groups[0].sections.len(), 3);
+        assert_eq!(results.groups[0].similarity, 1.0);
+    }
+}
@@ -5,17 +5,32 @@
 //! - Documentation structure
 //! - Verification command health
 //! - Code-to-documentation mapping
+//!
+//! With `--fix`, safe remediations are applied and the remaining problems
+//! are listed as next steps (see [`doctor_fix`]). With `--verify-runs`,
+//! verification commands are run repeatedly to find flaky, failing, and
+//! slow ones (see [`doctor_verify`]).
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::path::{Path, PathBuf};
-
-use crate::cli::OutputFormat;
+use tracing::warn;
+
+use crate::cli::{HookType, OutputFormat, PathStyle};
+use crate::commands::doctor_fix::{
+    self, AppliedFix, CONFIG_KEYS_CHECK, HOOKS_CHECK, INDEX_CHECK, NextStep,
+};
+use crate::commands::doctor_verify::{self, CommandHealth, CommandReport};
+use crate::commands::hooks::{find_git_hooks_dir_from, is_pave_hook};
 use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::ParsedDoc;
-use crate::verification::extract_verification_spec;
+use crate::exit::Violations;
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::stats_cache::{DocSummary, StatsCache};
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::Workdir;
 
 /// Arguments for the `pave doctor` command.
 pub struct DoctorArgs {
@@ -23,10 +38,21 @@ pub struct DoctorArgs {
     pub paths: Vec<PathBuf>,
     /// Output format.
     pub format: OutputFormat,
+    /// How to display file paths in output.
+    pub path_style: PathStyle,
+    /// Recompute every document instead of using cached summaries.
+    pub refresh: bool,
+    /// Apply safe fixes and list next steps for the rest.
+    pub fix: bool,
+    /// Run each doc's verification commands this many times and report
+    /// their health.
+    pub verify_runs: Option<usize>,
+    /// Only run the commands of this many docs.
+    pub verify_sample: Option<usize>,
 }
 
 /// Status of a diagnostic check.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CheckStatus {
     Pass,
@@ -35,7 +61,7 @@ pub enum CheckStatus {
 }
 
 /// A single diagnostic check result.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DiagnosticCheck {
     /// Name of the check.
     pub name: String,
@@ -52,7 +78,7 @@ pub struct DiagnosticCheck {
 }
 
 /// A category of diagnostic checks.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DiagnosticCategory {
     /// Name of the category.
     pub name: String,
@@ -61,8 +87,10 @@ pub struct DiagnosticCategory {
 }
 
 /// Overall results from the doctor command.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DoctorResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Categories of diagnostic checks.
     pub categories: Vec<DiagnosticCategory>,
     /// Total number of errors.
@@ -71,15 +99,28 @@ pub struct DoctorResults {
     pub warning_count: usize,
     /// Total number of passing checks.
     pub pass_count: usize,
+    /// Remediations applied by `--fix`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixed: Vec<AppliedFix>,
+    /// What to do about the problems left, with `--fix`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub next_steps: Vec<NextStep>,
+    /// Health of each verification command, with `--verify-runs`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub verify_health: Vec<CommandReport>,
 }
 
 impl DoctorResults {
     fn new() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             categories: Vec::new(),
             error_count: 0,
             warning_count: 0,
             pass_count: 0,
+            fixed: Vec::new(),
+            next_steps: Vec::new(),
+            verify_health: Vec::new(),
         }
     }
 
@@ -100,38 +141,29 @@ impl DoctorResults {
 }
 
 /// Execute the `pave doctor` command.
-pub fn execute(args: DoctorArgs) -> Result<()> {
-    // Find and load config
-    let config_result = find_config();
-    let mut results = DoctorResults::new();
+pub fn execute(args: DoctorArgs, workdir: &Workdir) -> Result<()> {
+    let mut results = diagnose(&args, args.verify_runs, workdir)?;
 
-    // Run configuration checks
-    let config_category = run_config_checks(&config_result);
-    results.add_category(config_category);
-
-    // If config exists and is valid, run further checks
-    if let Ok(ref config_path) = config_result {
-        let config = PaveConfig::load(config_path)?;
-        let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-
-        // Determine paths to check
-        let paths = if args.paths.is_empty() {
-            vec![config_dir.join(&config.docs.root)]
-        } else {
-            args.paths.clone()
+    if args.fix {
+        let fixed = match workdir.find_config() {
+            Ok(config_path) => doctor_fix::apply(&config_path, &results)?,
+            Err(_) => Vec::new(),
         };
-
-        // Run documentation structure checks
-        let docs_category = run_docs_checks(&paths, &config, config_dir)?;
-        results.add_category(docs_category);
-
-        // Run verification checks
-        let verify_category = run_verification_checks(&paths, config_dir)?;
-        results.add_category(verify_category);
-
-        // Run code coverage checks
-        let coverage_category = run_coverage_checks(&paths, &config, config_dir)?;
-        results.add_category(coverage_category);
+        // Diagnose again so the report shows what's left, keeping the
+        // command health rather than running every command again
+        if !fixed.is_empty() {
+            let previous = std::mem::replace(&mut results, diagnose(&args, None, workdir)?);
+            if let Some(health) = previous
+                .categories
+                .into_iter()
+                .find(|category| category.name == doctor_verify::CATEGORY)
+            {
+                results.add_category(health);
+            }
+            results.verify_health = previous.verify_health;
+        }
+        results.fixed = fixed;
+        results.next_steps = doctor_fix::next_steps(&results);
     }
 
     // Output results
@@ -144,35 +176,94 @@ pub fn execute(args: DoctorArgs) -> Result<()> {
     if results.is_healthy() {
         Ok(())
     } else {
-        anyhow::bail!(
+        anyhow::bail!(Violations(format!(
             "Doctor found issues: {} error{}, {} warning{}",
             results.error_count,
             if results.error_count == 1 { "" } else { "s" },
             results.warning_count,
             if results.warning_count == 1 { "" } else { "s" }
-        )
+        )))
     }
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+/// Run every diagnostic check, running verification commands `verify_runs`
+/// times if set.
+fn diagnose(
+    args: &DoctorArgs,
+    verify_runs: Option<usize>,
+    workdir: &Workdir,
+) -> Result<DoctorResults> {
+    // Find and load config
+    let config_result = workdir.find_config();
+    let mut results = DoctorResults::new();
+
+    // Run configuration checks
+    let config_category = run_config_checks(&config_result);
+    results.add_category(config_category);
+
+    // If config exists and is valid, run further checks
+    if let Ok(ref config_path) = config_result
+        && let Ok(config) = PaveConfig::load(config_path)
+    {
+        let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+        // Determine paths to check
+        let paths = if args.paths.is_empty() {
+            vec![config_dir.join(&config.docs.root)]
+        } else {
+            args.paths
+                .iter()
+                .map(|path| workdir.resolve(path))
+                .collect()
+        };
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
+        // Summarize each document once, reusing summaries of unchanged ones
+        let mut cache = StatsCache::load(config_path, args.refresh);
+        let docs = summarize_docs(&paths, &config, config_dir, &mut cache)?;
+        if let Err(e) = cache.save() {
+            warn!("Failed to write stats cache: {:#}", e);
         }
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
+        // Run documentation structure checks
+        let docs_category = run_docs_checks(&docs, &config);
+        results.add_category(docs_category);
+
+        // Run verification checks
+        let verify_category = run_verification_checks(&docs);
+        results.add_category(verify_category);
+
+        // Run verification commands to measure their health
+        if let Some(runs) = verify_runs {
+            let files: Vec<PathBuf> = docs.iter().map(|(file, _)| file.clone()).collect();
+            let (health_category, reports) = doctor_verify::run_health_checks(
+                &files,
+                &config,
+                config_dir,
+                runs,
+                args.verify_sample,
+                args.path_style,
+                &workdir.dir,
+            )?;
+            results.add_category(health_category);
+            results.verify_health = reports;
+        }
+
+        // Run code coverage checks
+        let coverage_category = run_coverage_checks(&docs, &config, config_dir);
+        results.add_category(coverage_category);
+
+        for check in results
+            .categories
+            .iter_mut()
+            .flat_map(|category| category.checks.iter_mut())
+        {
+            for file in &mut check.affected_files {
+                *file = display_path(file, args.path_style, config_dir, &workdir.dir);
+            }
         }
     }
+
+    Ok(results)
 }
 
 /// Run configuration health checks.
@@ -253,6 +344,44 @@ fn run_config_checks(config_result: &Result<PathBuf>) -> DiagnosticCategory {
                             suggestion: None,
                             affected_files: vec![],
                         });
+
+                        let index = docs_root.join("index.md");
+                        if !index.exists() {
+                            checks.push(DiagnosticCheck {
+                                name: INDEX_CHECK.to_string(),
+                                status: CheckStatus::Warning,
+                                message: "Docs root has no index.md".to_string(),
+                                suggestion: Some(
+                                    "Generate an index so readers can find every doc".to_string(),
+                                ),
+                                affected_files: vec![],
+                            });
+                        }
+                    }
+
+                    // Outside a git repository there are no hooks to install
+                    if let Ok(hooks_dir) = find_git_hooks_dir_from(config_dir) {
+                        let hook = hooks_dir.join(HookType::PreCommit.filename());
+                        if is_pave_hook(&hook) {
+                            checks.push(DiagnosticCheck {
+                                name: HOOKS_CHECK.to_string(),
+                                status: CheckStatus::Pass,
+                                message: "pre-commit hook installed".to_string(),
+                                suggestion: None,
+                                affected_files: vec![],
+                            });
+                        } else {
+                            checks.push(DiagnosticCheck {
+                                name: HOOKS_CHECK.to_string(),
+                                status: CheckStatus::Warning,
+                                message: "pre-commit hook not installed".to_string(),
+                                suggestion: Some(
+                                    "Install the hook so docs are checked before every commit"
+                                        .to_string(),
+                                ),
+                                affected_files: vec![],
+                            });
+                        }
                     }
 
                     // Check templates directory if configured
@@ -285,6 +414,26 @@ fn run_config_checks(config_result: &Result<PathBuf>) -> DiagnosticCategory {
                     });
                 }
             }
+
+            let missing_keys = std::fs::read_to_string(config_path)
+                .ok()
+                .and_then(|content| doctor_fix::missing_config_keys(&content))
+                .unwrap_or_default();
+            if !missing_keys.is_empty() {
+                checks.push(DiagnosticCheck {
+                    name: CONFIG_KEYS_CHECK.to_string(),
+                    status: CheckStatus::Warning,
+                    message: format!(
+                        "{} doesn't set {}",
+                        CONFIG_FILENAME,
+                        missing_keys.join(", ")
+                    ),
+                    suggestion: Some(
+                        "Set them explicitly; `pave doctor --fix` adds their defaults".to_string(),
+                    ),
+                    affected_files: vec![config_path.clone()],
+                });
+            }
         }
         Err(_) => {
             checks.push(DiagnosticCheck {
@@ -303,44 +452,6 @@ fn run_config_checks(config_result: &Result<PathBuf>) -> DiagnosticCategory {
     }
 }
 
-/// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
-
-    for path in paths {
-        if path.is_file() {
-            if path.extension().is_some_and(|ext| ext == "md") {
-                files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        }
-        // Skip non-existent paths silently for doctor command
-    }
-
-    files.sort();
-    Ok(files)
-}
-
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
-        }
-    }
-
-    Ok(())
-}
-
 /// Check if a file should be skipped for validation (index.md, templates).
 fn should_skip_file(path: &Path) -> bool {
     if path.file_name().is_some_and(|f| f == "index.md") {
@@ -351,17 +462,34 @@ fn should_skip_file(path: &Path) -> bool {
 }
 
 /// Run documentation structure checks.
-fn run_docs_checks(
+/// A documentation file and its summary.
+type DocEntry = (PathBuf, DocSummary);
+
+/// Summarize the documentation files under `paths`, leaving out index and
+/// template files and any that can't be read.
+fn summarize_docs(
     paths: &[PathBuf],
     config: &PaveConfig,
-    _config_dir: &Path,
-) -> Result<DiagnosticCategory> {
-    let mut checks = Vec::new();
+    config_dir: &Path,
+    cache: &mut StatsCache,
+) -> Result<Vec<DocEntry>> {
+    // Missing paths have no docs to summarize
+    let paths: Vec<PathBuf> = paths.iter().filter(|p| p.exists()).cloned().collect();
+    let files = find_markdown_files(&paths, &load_ignore(config_dir, config)?)?;
+    Ok(files
+        .into_iter()
+        .filter(|file| !should_skip_file(file))
+        .filter_map(|file| {
+            let summary = cache.summary(&file, config).ok()?;
+            Some((file, summary))
+        })
+        .collect())
+}
 
-    let files = find_markdown_files(paths)?;
-    let validatable_files: Vec<_> = files.iter().filter(|f| !should_skip_file(f)).collect();
+fn run_docs_checks(docs: &[DocEntry], config: &PaveConfig) -> DiagnosticCategory {
+    let mut checks = Vec::new();
 
-    if validatable_files.is_empty() {
+    if docs.is_empty() {
         checks.push(DiagnosticCheck {
             name: "Documentation files".to_string(),
             status: CheckStatus::Warning,
@@ -372,16 +500,16 @@ fn run_docs_checks(
             affected_files: vec![],
         });
 
-        return Ok(DiagnosticCategory {
+        return DiagnosticCategory {
             name: "Documentation Structure".to_string(),
             checks,
-        });
+        };
     }
 
     checks.push(DiagnosticCheck {
         name: "Documentation files".to_string(),
         status: CheckStatus::Pass,
-        message: format!("Found {} documentation file(s)", validatable_files.len()),
+        message: format!("Found {} documentation file(s)", docs.len()),
         suggestion: None,
         affected_files: vec![],
     });
@@ -391,19 +519,17 @@ fn run_docs_checks(
     let mut missing_examples = Vec::new();
     let mut exceeds_line_limit = Vec::new();
 
-    for file in &validatable_files {
-        if let Ok(doc) = ParsedDoc::parse(file) {
-            if config.rules.require_verification && !doc.has_section("Verification") {
-                missing_verification.push((*file).clone());
-            }
+    for (file, doc) in docs {
+        if config.rules.require_verification && !doc.has_section("Verification") {
+            missing_verification.push(file.clone());
+        }
 
-            if config.rules.require_examples && !doc.has_section("Examples") {
-                missing_examples.push((*file).clone());
-            }
+        if config.rules.require_examples && !doc.has_section("Examples") {
+            missing_examples.push(file.clone());
+        }
 
-            if doc.line_count > config.rules.max_lines as usize {
-                exceeds_line_limit.push(((*file).clone(), doc.line_count));
-            }
+        if doc.line_count > config.rules.max_lines as usize {
+            exceeds_line_limit.push((file.clone(), doc.line_count));
         }
     }
 
@@ -480,53 +606,43 @@ fn run_docs_checks(
                 details.join(", ")
             ),
             suggestion: Some(
-                "Consider splitting large documents into smaller, focused ones".to_string(),
+                "Consider splitting large documents into smaller, focused ones with `pave split`"
+                    .to_string(),
             ),
             affected_files: affected,
         });
     }
 
-    Ok(DiagnosticCategory {
+    DiagnosticCategory {
         name: "Documentation Structure".to_string(),
         checks,
-    })
+    }
 }
 
 /// Run verification command checks.
-fn run_verification_checks(paths: &[PathBuf], _config_dir: &Path) -> Result<DiagnosticCategory> {
+fn run_verification_checks(docs: &[DocEntry]) -> DiagnosticCategory {
     let mut checks = Vec::new();
 
-    let files = find_markdown_files(paths)?;
-    let validatable_files: Vec<_> = files.iter().filter(|f| !should_skip_file(f)).collect();
-
     let mut docs_with_commands = 0;
     let mut empty_verification_sections = Vec::new();
     let mut potentially_broken_commands = Vec::new();
 
-    for file in &validatable_files {
-        let Ok(doc) = ParsedDoc::parse(file) else {
-            continue;
-        };
+    for (file, doc) in docs {
         if doc.has_section("Verification") {
-            let spec = extract_verification_spec(&doc);
-            match spec {
-                Some(spec) if !spec.items.is_empty() => {
-                    docs_with_commands += 1;
-
-                    // Check for potentially broken commands
-                    for item in &spec.items {
-                        // Check for hardcoded paths that might not exist
-                        if item.command.contains("/home/")
-                            || item.command.contains("/Users/")
-                            || item.command.contains("C:\\")
-                        {
-                            potentially_broken_commands
-                                .push(((*file).clone(), item.command.clone()));
-                        }
-                    }
-                }
-                _ => {
-                    empty_verification_sections.push((*file).clone());
+            if doc.verification_commands.is_empty() {
+                empty_verification_sections.push(file.clone());
+                continue;
+            }
+            docs_with_commands += 1;
+
+            // Check for potentially broken commands
+            for command in &doc.verification_commands {
+                // Check for hardcoded paths that might not exist
+                if command.contains("/home/")
+                    || command.contains("/Users/")
+                    || command.contains("C:\\")
+                {
+                    potentially_broken_commands.push((file.clone(), command.clone()));
                 }
             }
         }
@@ -591,35 +707,29 @@ fn run_verification_checks(paths: &[PathBuf], _config_dir: &Path) -> Result<Diag
         });
     }
 
-    Ok(DiagnosticCategory {
+    DiagnosticCategory {
         name: "Verification Commands".to_string(),
         checks,
-    })
+    }
 }
 
 /// Run code coverage checks.
 fn run_coverage_checks(
-    paths: &[PathBuf],
+    docs: &[DocEntry],
     config: &PaveConfig,
     config_dir: &Path,
-) -> Result<DiagnosticCategory> {
+) -> DiagnosticCategory {
     let mut checks = Vec::new();
 
-    let files = find_markdown_files(paths)?;
-    let validatable_files: Vec<_> = files.iter().filter(|f| !should_skip_file(f)).collect();
-
     // Collect paths mentioned in documentation
     let mut documented_patterns: HashSet<String> = HashSet::new();
 
-    for file in &validatable_files {
-        let Ok(doc) = ParsedDoc::parse(file) else {
-            continue;
-        };
-        let Some(section) = doc.get_section("Paths") else {
+    for (_, doc) in docs {
+        let Some(section) = &doc.paths_section else {
             continue;
         };
         // Extract path patterns from the Paths section
-        for line in section.content.lines() {
+        for line in section.lines() {
             let trimmed = line.trim();
             // Skip empty lines, headings, and list markers
             if trimmed.is_empty()
@@ -710,10 +820,10 @@ fn run_coverage_checks(
         });
     }
 
-    Ok(DiagnosticCategory {
+    DiagnosticCategory {
         name: "Code Coverage".to_string(),
         checks,
-    })
+    }
 }
 
 /// Output results in text format.
@@ -755,6 +865,36 @@ fn output_text(results: &DoctorResults) {
         println!();
     }
 
+    let unhealthy: Vec<&CommandReport> = results
+        .verify_health
+        .iter()
+        .filter(|report| report.health != CommandHealth::Stable)
+        .collect();
+    if !unhealthy.is_empty() {
+        println!("Command Health");
+        for report in unhealthy {
+            let health = match report.health {
+                CommandHealth::Stable => "stable",
+                CommandHealth::Flaky => "flaky",
+                CommandHealth::Failing => "failing",
+                CommandHealth::TooSlow => "too slow",
+            };
+            println!(
+                "  {}: {} ({}, {}/{} passed, slowest {:.1}s)",
+                report.file.display(),
+                report.command.lines().next().unwrap_or_default(),
+                health,
+                report.passes,
+                report.runs,
+                report.slowest_ms as f64 / 1000.0
+            );
+            if let Some(recommendation) = &report.recommendation {
+                println!("    fix: {}", recommendation);
+            }
+        }
+        println!();
+    }
+
     // Print summary
     println!(
         "Summary: {} error{}, {} warning{}",
@@ -764,8 +904,27 @@ fn output_text(results: &DoctorResults) {
         if results.warning_count == 1 { "" } else { "s" }
     );
 
-    if results.error_count > 0 || results.warning_count > 0 {
-        println!("Run 'pave check' for detailed validation");
+    if !results.fixed.is_empty() {
+        println!();
+        println!("Fixed:");
+        for fix in &results.fixed {
+            println!("  \u{2713} {}", fix.action);
+        }
+    }
+
+    if !results.next_steps.is_empty() {
+        println!();
+        println!("Next steps:");
+        for (number, step) in results.next_steps.iter().enumerate() {
+            println!("  {}. {}", number + 1, step.message);
+            for command in &step.commands {
+                println!("     $ {}", command);
+            }
+        }
+    } else if results.error_count > 0 || results.warning_count > 0 {
+        println!(
+            "Run 'pave check' for detailed validation, or 'pave doctor --fix' to fix what can be fixed"
+        );
     }
 }
 
@@ -976,6 +1135,11 @@ Missing required sections.
         );
     }
 
+    fn summarize(paths: &[PathBuf], config: &PaveConfig, config_dir: &Path) -> Vec<DocEntry> {
+        let mut cache = StatsCache::load(&config_dir.join(CONFIG_FILENAME), true);
+        summarize_docs(paths, config, config_dir, &mut cache).unwrap()
+    }
+
     #[test]
     fn docs_check_reports_missing_sections() {
         let temp_dir = TempDir::new().unwrap();
@@ -985,7 +1149,7 @@ Missing required sections.
         let config = PaveConfig::load(&config_path).unwrap();
         let docs_dir = temp_dir.path().join("docs");
 
-        let category = run_docs_checks(&[docs_dir], &config, temp_dir.path()).unwrap();
+        let category = run_docs_checks(&summarize(&[docs_dir], &config, temp_dir.path()), &config);
 
         assert!(
             category
@@ -1010,7 +1174,7 @@ Missing required sections.
         let config = PaveConfig::load(&config_path).unwrap();
         let docs_dir = temp_dir.path().join("docs");
 
-        let category = run_docs_checks(&[docs_dir], &config, temp_dir.path()).unwrap();
+        let category = run_docs_checks(&summarize(&[docs_dir], &config, temp_dir.path()), &config);
 
         // All checks should pass
         assert!(
@@ -1038,7 +1202,11 @@ Example here.
 "#;
         fs::write(docs_dir.join("empty-verify.md"), content).unwrap();
 
-        let category = run_verification_checks(&[docs_dir], temp_dir.path()).unwrap();
+        let category = run_verification_checks(&summarize(
+            &[docs_dir],
+            &PaveConfig::default(),
+            temp_dir.path(),
+        ));
 
         assert!(
             category
@@ -1116,7 +1284,11 @@ Example here.
         fs::write(docs_dir.join("component.md"), content).unwrap();
 
         let config = PaveConfig::load(&config_path).unwrap();
-        let category = run_coverage_checks(&[docs_dir], &config, temp_dir.path()).unwrap();
+        let category = run_coverage_checks(
+            &summarize(&[docs_dir], &config, temp_dir.path()),
+            &config,
+            temp_dir.path(),
+        );
 
         // Should find the path patterns
         assert!(
@@ -1151,7 +1323,11 @@ Example here.
         fs::write(docs_dir.join("component.md"), content).unwrap();
 
         let config = PaveConfig::load(&config_path).unwrap();
-        let category = run_coverage_checks(&[docs_dir], &config, temp_dir.path()).unwrap();
+        let category = run_coverage_checks(
+            &summarize(&[docs_dir], &config, temp_dir.path()),
+            &config,
+            temp_dir.path(),
+        );
 
         // Should warn about missing Paths sections
         assert!(
@@ -0,0 +1,315 @@
+//! Remediation for `pave doctor --fix`.
+//!
+//! Safe fixes are applied directly: adding missing config keys with their
+//! defaults, creating the docs root, installing the pre-commit hook, and
+//! generating the docs index. Everything else becomes a next step that
+//! names the command to run.
+
+use anyhow::{Context, Result, bail};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::cli::HookType;
+use crate::commands::doctor::{CheckStatus, DiagnosticCheck, DoctorResults};
+use crate::commands::doctor_verify::{FAILING_CHECK, FLAKY_CHECK, SLOW_CHECK};
+use crate::commands::hooks::{find_git_hooks_dir_from, write_hook};
+use crate::commands::index::write_index;
+use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::walk::load_ignore;
+
+/// Name of the check for keys missing from the config.
+pub(crate) const CONFIG_KEYS_CHECK: &str = "Config keys";
+
+/// Name of the check for the pre-commit hook.
+pub(crate) const HOOKS_CHECK: &str = "Git hooks";
+
+/// Name of the check for the docs index.
+pub(crate) const INDEX_CHECK: &str = "Index";
+
+/// A remediation that was applied.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AppliedFix {
+    /// Name of the check it addressed.
+    pub check: String,
+    /// What was done.
+    pub action: String,
+}
+
+/// A problem left to the user, with the commands that address it.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct NextStep {
+    /// Name of the check that failed.
+    pub check: String,
+    /// What to do.
+    pub message: String,
+    /// Commands to run, in order.
+    pub commands: Vec<String>,
+}
+
+/// Keys every config should set, with their defaults as TOML values.
+fn recommended_keys() -> Vec<(&'static str, &'static str, String)> {
+    let defaults = PaveConfig::default();
+    let string = |value: &str| toml::Value::String(value.to_string()).to_string();
+    vec![
+        ("pave", "version", string(&defaults.pave.version)),
+        (
+            "docs",
+            "root",
+            string(&defaults.docs.root.to_string_lossy()),
+        ),
+        ("rules", "max_lines", defaults.rules.max_lines.to_string()),
+        (
+            "rules",
+            "require_verification",
+            defaults.rules.require_verification.to_string(),
+        ),
+        (
+            "rules",
+            "require_examples",
+            defaults.rules.require_examples.to_string(),
+        ),
+    ]
+}
+
+/// Recommended keys missing from a config, as `table.key`, or `None` if
+/// the config isn't valid TOML.
+pub(crate) fn missing_config_keys(content: &str) -> Option<Vec<String>> {
+    let table: toml::Table = content.parse().ok()?;
+    Some(
+        recommended_keys()
+            .into_iter()
+            .filter(|(section, key, _)| {
+                table
+                    .get(*section)
+                    .and_then(|value| value.as_table())
+                    .is_none_or(|section| !section.contains_key(*key))
+            })
+            .map(|(section, key, _)| format!("{}.{}", section, key))
+            .collect(),
+    )
+}
+
+/// Add the missing recommended keys to a config with their defaults, under
+/// their section's header or in a new section at the end. Keys of a section
+/// written without a `[section]` header are left out. Returns the new
+/// content and the keys added.
+fn add_missing_keys(content: &str) -> Result<(String, Vec<String>)> {
+    let table: toml::Table = content
+        .parse()
+        .with_context(|| format!("{} is not valid TOML", CONFIG_FILENAME))?;
+    let missing = missing_config_keys(content).unwrap_or_default();
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut added = Vec::new();
+
+    for (section, key, value) in recommended_keys() {
+        let name = format!("{}.{}", section, key);
+        if !missing.contains(&name) {
+            continue;
+        }
+        let entry = format!("{} = {}", key, value);
+        let header = format!("[{}]", section);
+        let start = lines
+            .iter()
+            .position(|line| line.split('#').next().unwrap_or("").trim() == header);
+        match start {
+            Some(start) => {
+                // After the section's last entry, before any blank lines
+                let end = lines[start + 1..]
+                    .iter()
+                    .position(|line| line.trim_start().starts_with('['))
+                    .map_or(lines.len(), |offset| start + 1 + offset);
+                let at = (start + 1..end)
+                    .rev()
+                    .find(|&idx| !lines[idx].trim().is_empty())
+                    .map_or(start + 1, |idx| idx + 1);
+                lines.insert(at, entry);
+            }
+            None if !table.contains_key(section) => {
+                if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(header);
+                lines.push(entry);
+            }
+            None => continue,
+        }
+        added.push(name);
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    if updated.parse::<toml::Table>().is_err() {
+        bail!(
+            "Adding {} would make {} invalid",
+            added.join(", "),
+            CONFIG_FILENAME
+        );
+    }
+    Ok((updated, added))
+}
+
+/// Apply the safe fixes for the checks that failed.
+pub(crate) fn apply(config_path: &Path, results: &DoctorResults) -> Result<Vec<AppliedFix>> {
+    let failed = |name: &str| {
+        results
+            .categories
+            .iter()
+            .flat_map(|category| &category.checks)
+            .any(|check| check.name == name && check.status != CheckStatus::Pass)
+    };
+    let fix = |check: &str, action: String| AppliedFix {
+        check: check.to_string(),
+        action,
+    };
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut fixes = Vec::new();
+
+    // Keys first, since a config missing a required key doesn't load
+    if failed(CONFIG_KEYS_CHECK) {
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+        let (updated, added) = add_missing_keys(&content)?;
+        if !added.is_empty() {
+            fs::write(config_path, updated).with_context(|| {
+                format!("Failed to write config file: {}", config_path.display())
+            })?;
+            fixes.push(fix(
+                CONFIG_KEYS_CHECK,
+                format!("Added {} to {}", added.join(", "), CONFIG_FILENAME),
+            ));
+        }
+    }
+    let Ok(config) = PaveConfig::load(config_path) else {
+        return Ok(fixes);
+    };
+
+    let docs_root = config_dir.join(&config.docs.root);
+    let create_root = failed("Docs root exists");
+    if create_root {
+        fs::create_dir_all(&docs_root)
+            .with_context(|| format!("Failed to create directory: {}", docs_root.display()))?;
+        fixes.push(fix(
+            "Docs root exists",
+            format!("Created {}", config.docs.root.display()),
+        ));
+    }
+
+    // A hook pave didn't install is left for `pave hooks install` to chain
+    if failed(HOOKS_CHECK)
+        && let Ok(hooks_dir) = find_git_hooks_dir_from(config_dir)
+        && !hooks_dir.join(HookType::PreCommit.filename()).exists()
+    {
+        fs::create_dir_all(&hooks_dir)
+            .with_context(|| format!("Failed to create directory: {}", hooks_dir.display()))?;
+        write_hook(&hooks_dir, HookType::PreCommit, config.hooks.run_verify)?;
+        fixes.push(fix(
+            HOOKS_CHECK,
+            "Installed the pre-commit hook".to_string(),
+        ));
+    }
+
+    // A new docs root gets an index too
+    if (failed(INDEX_CHECK) || create_root) && docs_root.is_dir() {
+        let index = docs_root.join("index.md");
+        write_index(&index, &docs_root, &load_ignore(config_dir, &config)?)?;
+        fixes.push(fix(
+            INDEX_CHECK,
+            format!("Generated {}", config.docs.root.join("index.md").display()),
+        ));
+    }
+
+    Ok(fixes)
+}
+
+/// Next steps for the checks that still fail.
+pub(crate) fn next_steps(results: &DoctorResults) -> Vec<NextStep> {
+    results
+        .categories
+        .iter()
+        .flat_map(|category| &category.checks)
+        .filter(|check| check.status != CheckStatus::Pass)
+        .map(|check| NextStep {
+            check: check.name.clone(),
+            message: check
+                .suggestion
+                .clone()
+                .unwrap_or_else(|| check.message.clone()),
+            commands: follow_up(check),
+        })
+        .collect()
+}
+
+/// Commands that address a failed check.
+fn follow_up(check: &DiagnosticCheck) -> Vec<String> {
+    let per_file = |command: &str| -> Vec<String> {
+        check
+            .affected_files
+            .iter()
+            .map(|file| format!("{} {}", command, file.display()))
+            .collect()
+    };
+    let commands: Vec<String> = match check.name.as_str() {
+        "Config file exists" => vec!["pave init".to_string()],
+        "Config file valid" | CONFIG_KEYS_CHECK => vec!["pave config validate".to_string()],
+        "Verification sections" if check.affected_files.is_empty() => {
+            vec!["pave config set rules.require_verification true".to_string()]
+        }
+        "Examples sections" if check.affected_files.is_empty() => {
+            vec!["pave config set rules.require_examples true".to_string()]
+        }
+        "Verification sections" | "Examples sections" => {
+            vec![
+                "pave migrate --dry-run".to_string(),
+                "pave migrate".to_string(),
+            ]
+        }
+        "Documentation files" => vec![
+            "pave adopt".to_string(),
+            "pave new component <name>".to_string(),
+        ],
+        "Line limits" => per_file("pave split"),
+        "Empty verification sections" | "Hardcoded paths" => per_file("pave verify"),
+        FLAKY_CHECK | FAILING_CHECK | SLOW_CHECK => per_file("pave verify --no-cache"),
+        "Code-to-doc mapping" => vec!["pave coverage".to_string()],
+        "Source directories" => per_file("pave coverage"),
+        HOOKS_CHECK => vec!["pave hooks install".to_string()],
+        INDEX_CHECK => vec!["pave index".to_string()],
+        _ => Vec::new(),
+    };
+    if commands.is_empty() {
+        vec!["pave check".to_string()]
+    } else {
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_missing_keys_under_their_sections() {
+        let content = "# Project docs\n[pave]\nversion = \"0.1\"\n\n[rules]\nmax_lines = 200 # short docs\n\n[hooks]\nrun_verify = true\n";
+        assert_eq!(
+            missing_config_keys(content).unwrap(),
+            [
+                "docs.root",
+                "rules.require_verification",
+                "rules.require_examples"
+            ]
+        );
+        assert!(missing_config_keys("[pave").is_none());
+
+        let (updated, added) = add_missing_keys(content).unwrap();
+        assert_eq!(added.len(), 3);
+        assert_eq!(
+            updated,
+            "# Project docs\n[pave]\nversion = \"0.1\"\n\n[rules]\nmax_lines = 200 # short docs\n\
+             require_verification = true\nrequire_examples = true\n\n[hooks]\nrun_verify = true\n\n\
+             [docs]\nroot = \"docs\"\n"
+        );
+        assert_eq!(missing_config_keys(&updated).unwrap(), Vec::<String>::new());
+    }
+}
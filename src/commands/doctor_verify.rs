@@ -0,0 +1,509 @@
+//! Verification health runner for `pave doctor --verify-runs`.
+//!
+//! Each doc's verification commands are run several times, uncached and
+//! without their `pave:retries`, and classified by how they behaved across
+//! the runs. The commands run in a throwaway copy of the project, so what
+//! they write doesn't touch the checkout, and docs and commands that reach
+//! hosts, containers, databases, HTTP endpoints, secrets, or services
+//! aren't run.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::cli::PathStyle;
+use crate::commands::doctor::{CheckStatus, DiagnosticCategory, DiagnosticCheck};
+use crate::commands::verify::{RunContext, VerifyStatus, render_vars, run_verification};
+use crate::config::{PaveConfig, VerifySection};
+use crate::parser::{DocState, ParsedDoc};
+use crate::paths::display_path;
+use crate::secrets::Secrets;
+use crate::verification::{
+    DEFAULT_TIMEOUT_SECS, Invocation, VerificationItem, extract_verification_spec,
+};
+
+/// Name of the category the health checks are reported under.
+pub(crate) const CATEGORY: &str = "Verification Health";
+
+/// Name of the check for commands that pass some runs and fail others.
+pub(crate) const FLAKY_CHECK: &str = "Flaky commands";
+
+/// Name of the check for commands that fail every run.
+pub(crate) const FAILING_CHECK: &str = "Failing commands";
+
+/// Name of the check for commands that run close to their timeout.
+pub(crate) const SLOW_CHECK: &str = "Slow commands";
+
+/// A command whose slowest run takes at least this share of its timeout is
+/// too slow.
+const SLOW_FRACTION: f64 = 0.5;
+
+/// Most retries recommended for a flaky command.
+const MAX_RETRIES: usize = 3;
+
+/// How a command behaved across the runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandHealth {
+    /// Passed every run.
+    Stable,
+    /// Passed some runs and failed others.
+    Flaky,
+    /// Failed every run.
+    Failing,
+    /// Timed out, or came within [`SLOW_FRACTION`] of its timeout.
+    TooSlow,
+}
+
+/// Health of one verification command.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CommandReport {
+    /// Doc the command is in.
+    pub file: PathBuf,
+    /// The command.
+    pub command: String,
+    /// How it behaved.
+    pub health: CommandHealth,
+    /// Runs it passed.
+    pub passes: usize,
+    /// Runs it was part of.
+    pub runs: usize,
+    /// Duration of its slowest run in milliseconds.
+    pub slowest_ms: u64,
+    /// Marker or command that addresses the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommendation: Option<String>,
+}
+
+/// Whether an item reaches beyond the project copy.
+fn leaves_sandbox(item: &VerificationItem, verify: &VerifySection) -> bool {
+    item.host.is_some()
+        || item.container.is_some()
+        || item.database.is_some()
+        || !item.secrets.is_empty()
+        || item
+            .working_dir
+            .as_ref()
+            .is_some_and(|dir| dir.is_absolute())
+        || matches!(item.invocation(verify), Ok(Invocation::Http))
+}
+
+/// A copy of the project for commands to run in, removed when dropped.
+struct Sandbox {
+    dir: PathBuf,
+}
+
+impl Sandbox {
+    /// Copy the files of the project at `project_dir`: those git tracks or
+    /// would add, or everything but `.git` outside a repository.
+    fn new(project_dir: &Path) -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("pave-doctor-{}", std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+        let sandbox = Self { dir };
+        for file in project_files(project_dir)? {
+            let dest = sandbox.dir.join(&file);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            let source = project_dir.join(&file);
+            // Files deleted but not yet staged are still listed
+            if source.is_file() {
+                fs::copy(&source, &dest)
+                    .with_context(|| format!("Failed to copy {}", source.display()))?;
+            }
+        }
+        fs::create_dir_all(&sandbox.dir)
+            .with_context(|| format!("Failed to create {}", sandbox.dir.display()))?;
+        Ok(sandbox)
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Files of the project at `project_dir`, relative to it.
+fn project_files(project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .current_dir(project_dir)
+        .output();
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        return Ok(String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .map(PathBuf::from)
+            .collect());
+    }
+
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(relative) = dirs.pop() {
+        let dir = project_dir.join(&relative);
+        for entry in
+            fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                if entry.file_name() != ".git" {
+                    dirs.push(path);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Run the verification commands of `files` `runs` times each, or only those
+/// of the first `sample` docs with commands, and report their health.
+/// Reports name their doc in `path_style`, with relative paths from `cwd`.
+pub(crate) fn run_health_checks(
+    files: &[PathBuf],
+    config: &PaveConfig,
+    config_dir: &Path,
+    runs: usize,
+    sample: Option<usize>,
+    path_style: PathStyle,
+    cwd: &Path,
+) -> Result<(DiagnosticCategory, Vec<CommandReport>)> {
+    let runs = runs.max(1);
+    let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS.into());
+    let mut reports = Vec::new();
+    let mut sampled_docs = 0;
+    let mut skipped = 0;
+    let mut sandbox = None;
+
+    for file in files {
+        if sample.is_some_and(|sample| sampled_docs >= sample) {
+            break;
+        }
+        let doc = ParsedDoc::parse(file)?;
+        if doc.state() == DocState::Draft {
+            continue;
+        }
+        let Some(mut spec) = extract_verification_spec(&doc) else {
+            continue;
+        };
+
+        // A fixture that leaves the sandbox rules out the whole doc
+        let leaves_sandbox = |item: &VerificationItem| leaves_sandbox(item, &config.verify);
+        let fixture = spec.setup.iter().chain(&spec.teardown);
+        if config.verify.container.is_some()
+            || spec.compose.is_some()
+            || !spec.services.is_empty()
+            || fixture.clone().any(leaves_sandbox)
+        {
+            skipped += spec.items.len();
+            continue;
+        }
+        let before = spec.items.len();
+        spec.items.retain(|item| !leaves_sandbox(item));
+        skipped += before - spec.items.len();
+        if spec.items.is_empty() {
+            continue;
+        }
+        sampled_docs += 1;
+
+        // Copy the project once, for the first doc that runs anything
+        if sandbox.is_none() {
+            sandbox = Some(Sandbox::new(config_dir)?);
+        }
+        let sandbox_dir = &sandbox.as_ref().expect("created above").dir;
+
+        // Run without retries, with working dirs inside the copy
+        render_vars(&mut spec, &config.vars);
+        for item in spec
+            .setup
+            .iter_mut()
+            .chain(&mut spec.items)
+            .chain(&mut spec.teardown)
+        {
+            item.retries = 0;
+            if let Some(dir) = &mut item.working_dir {
+                *dir = sandbox_dir.join(&*dir);
+            }
+        }
+
+        let mut statuses = vec![Vec::with_capacity(runs); spec.items.len()];
+        let mut durations = vec![0u64; spec.items.len()];
+        for _ in 0..runs {
+            let result = run_verification(
+                &spec,
+                timeout,
+                true,
+                &RunContext {
+                    working_dir: sandbox_dir,
+                    rules: &config.rules,
+                    verify: &config.verify,
+                    // Documents with secrets are skipped by `leaves_sandbox`
+                    secrets: &Secrets::default(),
+                },
+                None,
+            )?;
+            for (index, command) in result.commands.iter().enumerate().take(spec.items.len()) {
+                statuses[index].push(command.status);
+                durations[index] = durations[index].max(command.duration_ms.unwrap_or(0));
+            }
+        }
+
+        let file = display_path(file, path_style, config_dir, cwd);
+        for (index, item) in spec.items.iter().enumerate() {
+            let timeout_ms = u64::from(item.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)) * 1000;
+            let (health, recommendation) =
+                classify(&statuses[index], durations[index], timeout_ms, &file);
+            reports.push(CommandReport {
+                file: file.clone(),
+                command: item.command.clone(),
+                health,
+                passes: statuses[index].iter().filter(|s| passed(**s)).count(),
+                runs: statuses[index].len(),
+                slowest_ms: durations[index],
+                recommendation,
+            });
+        }
+    }
+
+    Ok((
+        health_category(&reports, runs, sampled_docs, skipped),
+        reports,
+    ))
+}
+
+/// Whether a run of a command counts as passing.
+fn passed(status: VerifyStatus) -> bool {
+    matches!(status, VerifyStatus::Pass | VerifyStatus::Warn)
+}
+
+/// Classify a command from the statuses of its runs and its slowest run,
+/// with what to do about it.
+fn classify(
+    statuses: &[VerifyStatus],
+    slowest_ms: u64,
+    timeout_ms: u64,
+    file: &Path,
+) -> (CommandHealth, Option<String>) {
+    let passes = statuses.iter().filter(|status| passed(**status)).count();
+    let timed_out = statuses.contains(&VerifyStatus::Timeout);
+    let slow = slowest_ms as f64 >= timeout_ms as f64 * SLOW_FRACTION;
+
+    if passes == 0 && !timed_out {
+        let command = format!("pave verify {}", file.display());
+        return (CommandHealth::Failing, Some(command));
+    }
+    if timed_out || slow {
+        // Twice the slowest run, in whole seconds
+        let secs = (slowest_ms * 2).div_ceil(1000).max(1);
+        return (
+            CommandHealth::TooSlow,
+            Some(format!("<!-- pave:timeout {} -->", secs)),
+        );
+    }
+    if passes < statuses.len() {
+        let retries = (statuses.len() - passes).min(MAX_RETRIES);
+        return (
+            CommandHealth::Flaky,
+            Some(format!("<!-- pave:retries {} -->", retries)),
+        );
+    }
+    (CommandHealth::Stable, None)
+}
+
+/// Summarize the command reports as diagnostic checks.
+fn health_category(
+    reports: &[CommandReport],
+    runs: usize,
+    sampled_docs: usize,
+    skipped: usize,
+) -> DiagnosticCategory {
+    let mut checks = Vec::new();
+    let with = |health: CommandHealth| -> Vec<&CommandReport> {
+        reports
+            .iter()
+            .filter(|report| report.health == health)
+            .collect()
+    };
+    let files = |reports: &[&CommandReport]| -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = reports.iter().map(|report| report.file.clone()).collect();
+        files.dedup();
+        files
+    };
+
+    let mut message = format!(
+        "Ran {} command(s) from {} document(s) {} time(s)",
+        reports.len(),
+        sampled_docs,
+        runs
+    );
+    if skipped > 0 {
+        message.push_str(&format!(
+            "; skipped {} that use hosts, containers, databases, HTTP, secrets, or services",
+            skipped
+        ));
+    }
+    let stable = with(CommandHealth::Stable).len();
+    checks.push(DiagnosticCheck {
+        name: "Stable commands".to_string(),
+        status: if reports.is_empty() {
+            CheckStatus::Warning
+        } else {
+            CheckStatus::Pass
+        },
+        message: if reports.is_empty() {
+            message
+        } else {
+            format!("{}: {} stable", message, stable)
+        },
+        suggestion: reports
+            .is_empty()
+            .then(|| "Add verification commands that run within the repository".to_string()),
+        affected_files: vec![],
+    });
+
+    let flaky = with(CommandHealth::Flaky);
+    if !flaky.is_empty() {
+        checks.push(DiagnosticCheck {
+            name: FLAKY_CHECK.to_string(),
+            status: CheckStatus::Warning,
+            message: format!("{} command(s) passed some runs and failed others", flaky.len()),
+            suggestion: Some(
+                "Make the commands deterministic, or add a <!-- pave:retries N --> marker before each block"
+                    .to_string(),
+            ),
+            affected_files: files(&flaky),
+        });
+    }
+
+    let failing = with(CommandHealth::Failing);
+    if !failing.is_empty() {
+        checks.push(DiagnosticCheck {
+            name: FAILING_CHECK.to_string(),
+            status: CheckStatus::Error,
+            message: format!("{} command(s) failed every run", failing.len()),
+            suggestion: Some("Run pave verify on each document to see why".to_string()),
+            affected_files: files(&failing),
+        });
+    }
+
+    let slow = with(CommandHealth::TooSlow);
+    if !slow.is_empty() {
+        checks.push(DiagnosticCheck {
+            name: SLOW_CHECK.to_string(),
+            status: CheckStatus::Warning,
+            message: format!(
+                "{} command(s) timed out or took over half their timeout",
+                slow.len()
+            ),
+            suggestion: Some(
+                "Speed the commands up, or add a <!-- pave:timeout SECS --> marker before each block"
+                    .to_string(),
+            ),
+            affected_files: files(&slow),
+        });
+    }
+
+    DiagnosticCategory {
+        name: CATEGORY.to_string(),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use VerifyStatus::{Fail, Pass, Timeout, Warn};
+    use tempfile::TempDir;
+
+    #[test]
+    fn runs_commands_in_a_copy_of_the_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("docs/deploy.md");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        fs::write(temp_dir.path().join("data.txt"), "ok\n").unwrap();
+        fs::write(
+            &doc,
+            "# Deploy\n\n## Verification\n\
+             ```bash\ngrep -q ok data.txt && rm data.txt && touch created.txt\n```\n\n\
+             ```bash\ntest \"{{ vars.region }}\" = eu\n```\n\n\
+             ```http\nGET http://localhost:9/health\n```\n",
+        )
+        .unwrap();
+        let mut config = PaveConfig::default();
+        config.vars.insert("region".to_string(), "eu".to_string());
+
+        let (_, reports) = run_health_checks(
+            &[doc],
+            &config,
+            temp_dir.path(),
+            2,
+            None,
+            PathStyle::Absolute,
+            temp_dir.path(),
+        )
+        .unwrap();
+
+        // The HTTP request isn't sent, and the first command only passes
+        // once since it removes its input
+        let health: Vec<CommandHealth> = reports.iter().map(|report| report.health).collect();
+        assert_eq!(health, [CommandHealth::Flaky, CommandHealth::Stable]);
+        assert!(temp_dir.path().join("data.txt").exists());
+        assert!(!temp_dir.path().join("created.txt").exists());
+    }
+
+    #[test]
+    fn classifies_commands_by_their_runs() {
+        let file = Path::new("docs/deploy.md");
+        let classify =
+            |statuses: &[VerifyStatus], slowest_ms| classify(statuses, slowest_ms, 30_000, file);
+
+        assert_eq!(
+            classify(&[Pass, Warn, Pass], 200),
+            (CommandHealth::Stable, None)
+        );
+        assert_eq!(
+            classify(&[Pass, Fail, Pass], 200),
+            (
+                CommandHealth::Flaky,
+                Some("<!-- pave:retries 1 -->".to_string())
+            )
+        );
+        assert_eq!(
+            classify(&[Fail, Fail, Fail], 200),
+            (
+                CommandHealth::Failing,
+                Some("pave verify docs/deploy.md".to_string())
+            )
+        );
+        assert_eq!(
+            classify(&[Pass, Pass, Pass], 16_200),
+            (
+                CommandHealth::TooSlow,
+                Some("<!-- pave:timeout 33 -->".to_string())
+            )
+        );
+        assert_eq!(
+            classify(&[Timeout, Timeout], 30_000).0,
+            CommandHealth::TooSlow
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Implementation of the `pave fleet` command for reporting on many repos.
+//!
+//! Each repository gets its own `pave status`, `pave check`, and
+//! `pave coverage` run, as pave processes started in its directory with JSON
+//! output, so every repository is judged by its own `.pave.toml`. The
+//! results are reported side by side with fleet-wide totals. A repository
+//! whose status or check can't run is reported with the error instead.
+
+use anyhow::{Context, Result, anyhow, bail};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::FleetOutputFormat;
+use crate::commands::report_html::{escape_html, page, stat};
+use crate::schema::SCHEMA_VERSION;
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave fleet` command.
+pub struct FleetArgs {
+    /// Repositories to report on.
+    pub repos: Vec<PathBuf>,
+    /// File listing more repositories, one per line.
+    pub manifest: Option<PathBuf>,
+    /// Output format.
+    pub format: FleetOutputFormat,
+    /// File to write the report to instead of stdout.
+    pub output: Option<PathBuf>,
+}
+
+/// Documentation health of one repository.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct RepoReport {
+    /// Name of the repository's directory.
+    pub name: String,
+    /// Path to the repository, as given.
+    pub path: PathBuf,
+    /// Number of documents.
+    pub docs: usize,
+    /// Documents without errors or warnings.
+    pub compliant_docs: usize,
+    /// Percentage of compliant documents.
+    pub compliance_percent: f64,
+    /// Errors reported by `pave check`.
+    pub errors: usize,
+    /// Warnings reported by `pave check`.
+    pub warnings: usize,
+    /// Percentage of source files covered by a doc, when `pave coverage`
+    /// ran.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage_percent: Option<f64>,
+    /// Why the repository couldn't be reported on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Totals across the repositories that could be reported on.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct FleetTotals {
+    /// Repositories reported on.
+    pub repos: usize,
+    /// Repositories that couldn't be reported on.
+    pub failed_repos: usize,
+    /// Number of documents.
+    pub docs: usize,
+    /// Documents without errors or warnings.
+    pub compliant_docs: usize,
+    /// Percentage of compliant documents, weighted by document count.
+    pub compliance_percent: f64,
+    /// Errors reported by `pave check`.
+    pub errors: usize,
+    /// Warnings reported by `pave check`.
+    pub warnings: usize,
+}
+
+/// Results of the fleet command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FleetResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// One report per repository, in the order given.
+    pub repos: Vec<RepoReport>,
+    /// Totals across the repositories.
+    pub totals: FleetTotals,
+}
+
+/// Execute the `pave fleet` command.
+pub fn execute(args: FleetArgs, workdir: &Workdir) -> Result<()> {
+    let mut repos = args.repos.clone();
+    if let Some(manifest) = &args.manifest {
+        repos.extend(read_manifest(manifest, &workdir.dir)?);
+    }
+    if repos.is_empty() {
+        bail!("No repositories given");
+    }
+
+    let pave = env::current_exe().context("Failed to locate the pave executable")?;
+    let reports: Vec<RepoReport> = repos
+        .iter()
+        .map(|repo| {
+            eprintln!("Checking {}...", repo.display());
+            report_repo(&pave, repo, &workdir.resolve(repo))
+        })
+        .collect();
+    let results = FleetResults {
+        schema_version: SCHEMA_VERSION,
+        totals: totals(&reports),
+        repos: reports,
+    };
+
+    let rendered = match args.format {
+        FleetOutputFormat::Text => render_text(&results),
+        FleetOutputFormat::Json => serde_json::to_string_pretty(&results)? + "\n",
+        FleetOutputFormat::Html => render_html(&results),
+    };
+    match &args.output {
+        Some(path) => {
+            fs::write(workdir.resolve(path), rendered)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    if results.totals.failed_repos > 0 {
+        bail!(
+            "{} of {} repositories could not be reported on",
+            results.totals.failed_repos,
+            results.repos.len()
+        );
+    }
+    Ok(())
+}
+
+/// Read the repositories listed in a manifest, one path per line relative to
+/// the manifest. A relative `path` is relative to `cwd`. Blank lines and
+/// lines starting with `#` are skipped.
+pub fn read_manifest(path: &Path, cwd: &Path) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(cwd.join(path))
+        .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base.join(line))
+        .collect())
+}
+
+/// Run `pave <args>` in `repo` and parse its JSON output. Commands that
+/// report problems still print their results, so only output that isn't
+/// JSON counts as a failure. A command that succeeds without output, as
+/// `pave check` does when there are no docs, gives `null`.
+fn run_json(pave: &Path, repo: &Path, args: &[&str]) -> Result<Value> {
+    let output = Command::new(pave)
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .with_context(|| format!("Failed to run pave in {}", repo.display()))?;
+    if output.status.success() && output.stdout.trim_ascii().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_slice(&output.stdout).map_err(|_| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .find_map(|line| line.strip_prefix("Error: "))
+            .unwrap_or("no JSON output");
+        anyhow!("pave {} failed: {}", args[0], reason)
+    })
+}
+
+/// Report on one repository with the pave executable `pave`. `repo` is the
+/// path as given and `dir` the directory it names.
+fn report_repo(pave: &Path, repo: &Path, dir: &Path) -> RepoReport {
+    let mut report = RepoReport {
+        name: dir
+            .canonicalize()
+            .ok()
+            .and_then(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| repo.display().to_string()),
+        path: repo.to_path_buf(),
+        docs: 0,
+        compliant_docs: 0,
+        compliance_percent: 0.0,
+        errors: 0,
+        warnings: 0,
+        coverage_percent: None,
+        error: None,
+    };
+    if !dir.is_dir() {
+        report.error = Some("not a directory".to_string());
+        return report;
+    }
+
+    let count = |value: &Value, key: &str| value[key].as_u64().unwrap_or(0) as usize;
+    let len = |value: &Value, key: &str| value[key].as_array().map_or(0, Vec::len);
+    let status = run_json(pave, dir, &["status", "--format", "json"]);
+    let check = run_json(pave, dir, &["check", "--format", "json"]);
+    match (status, check) {
+        (Ok(status), Ok(check)) => {
+            report.docs = count(&status, "total_docs");
+            report.compliant_docs = count(&status, "compliant_docs");
+            report.compliance_percent = status["compliance_percent"].as_f64().unwrap_or(0.0);
+            report.errors = len(&check, "errors");
+            report.warnings = len(&check, "warnings");
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            report.error = Some(e.to_string());
+            return report;
+        }
+    }
+    // Coverage needs code mappings, which not every repository has
+    report.coverage_percent = run_json(pave, dir, &["coverage", "--format", "json"])
+        .ok()
+        .and_then(|coverage| coverage["coverage_percentage"].as_f64());
+    report
+}
+
+/// Sum the reports of the repositories that could be reported on.
+fn totals(reports: &[RepoReport]) -> FleetTotals {
+    let ok: Vec<&RepoReport> = reports.iter().filter(|r| r.error.is_none()).collect();
+    let docs = ok.iter().map(|r| r.docs).sum();
+    let compliant_docs = ok.iter().map(|r| r.compliant_docs).sum();
+    FleetTotals {
+        repos: ok.len(),
+        failed_repos: reports.len() - ok.len(),
+        docs,
+        compliant_docs,
+        compliance_percent: if docs == 0 {
+            0.0
+        } else {
+            compliant_docs as f64 / docs as f64 * 100.0
+        },
+        errors: ok.iter().map(|r| r.errors).sum(),
+        warnings: ok.iter().map(|r| r.warnings).sum(),
+    }
+}
+
+/// Coverage column of a report.
+fn coverage_cell(report: &RepoReport) -> String {
+    report
+        .coverage_percent
+        .map_or_else(|| "-".to_string(), |percent| format!("{:.0}%", percent))
+}
+
+/// Render the results as an aligned table.
+fn render_text(results: &FleetResults) -> String {
+    let width = results
+        .repos
+        .iter()
+        .map(|r| r.name.len())
+        .chain(["Repository".len()])
+        .max()
+        .unwrap_or(0);
+    let mut out = format!(
+        "{:<width$}  {:>5}  {:>10}  {:>6}  {:>8}  {:>8}\n",
+        "Repository", "Docs", "Compliant", "Errors", "Warnings", "Coverage"
+    );
+    for report in &results.repos {
+        match &report.error {
+            Some(error) => {
+                out.push_str(&format!("{:<width$}  error: {}\n", report.name, error));
+            }
+            None => out.push_str(&format!(
+                "{:<width$}  {:>5}  {:>10}  {:>6}  {:>8}  {:>8}\n",
+                report.name,
+                report.docs,
+                format!("{:.0}%", report.compliance_percent),
+                report.errors,
+                report.warnings,
+                coverage_cell(report)
+            )),
+        }
+    }
+
+    let totals = &results.totals;
+    out.push_str(&format!(
+        "\n{} repositor{}: {} documents, {:.0}% compliant, {} errors, {} warnings\n",
+        totals.repos,
+        if totals.repos == 1 { "y" } else { "ies" },
+        totals.docs,
+        totals.compliance_percent,
+        totals.errors,
+        totals.warnings
+    ));
+    if totals.failed_repos > 0 {
+        out.push_str(&format!(
+            "{} repositor{} could not be reported on\n",
+            totals.failed_repos,
+            if totals.failed_repos == 1 { "y" } else { "ies" }
+        ));
+    }
+    out
+}
+
+/// Render the results as a standalone HTML page.
+fn render_html(results: &FleetResults) -> String {
+    let totals = &results.totals;
+    let mut body = String::from("<h1>Documentation fleet report</h1>\n<div class=\"stats\">\n");
+    body.push_str(&stat("Repositories", &totals.repos.to_string()));
+    body.push_str(&stat("Documents", &totals.docs.to_string()));
+    body.push_str(&stat(
+        "Compliant",
+        &format!("{:.1}%", totals.compliance_percent),
+    ));
+    body.push_str(&stat(
+        "Check",
+        &format!("{} errors, {} warnings", totals.errors, totals.warnings),
+    ));
+    body.push_str("</div>\n");
+
+    body.push_str(
+        "<table>\n<tr><th>Repository</th><th>Docs</th><th>Compliant</th>\
+         <th>Errors</th><th>Warnings</th><th>Coverage</th></tr>\n",
+    );
+    for report in &results.repos {
+        let name = format!(
+            "{}<br><code>{}</code>",
+            escape_html(&report.name),
+            escape_html(&report.path.to_string_lossy())
+        );
+        match &report.error {
+            Some(error) => body.push_str(&format!(
+                "<tr><td>{}</td><td colspan=\"5\"><span class=\"badge fail\">error</span> {}</td></tr>\n",
+                name,
+                escape_html(error)
+            )),
+            None => {
+                let class = if report.errors > 0 {
+                    "fail"
+                } else if report.warnings > 0 {
+                    "warn"
+                } else {
+                    "pass"
+                };
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td><span class=\"badge {}\">{:.0}%</span></td>\
+                     <td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    name,
+                    report.docs,
+                    class,
+                    report.compliance_percent,
+                    report.errors,
+                    report.warnings,
+                    coverage_cell(report)
+                ));
+            }
+        }
+    }
+    body.push_str("</table>\n");
+
+    page("Documentation fleet report", &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn report(name: &str, docs: usize, compliant_docs: usize, errors: usize) -> RepoReport {
+        RepoReport {
+            name: name.to_string(),
+            path: PathBuf::from(format!("../{}", name)),
+            docs,
+            compliant_docs,
+            compliance_percent: compliant_docs as f64 / docs as f64 * 100.0,
+            errors,
+            warnings: 1,
+            coverage_percent: Some(50.0),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn totals_weight_compliance_by_docs() {
+        let mut broken = report("legacy", 1, 0, 0);
+        broken.error = Some("pave status failed: No .pave.toml found".to_string());
+        let reports = vec![report("api", 10, 9, 1), report("web", 30, 15, 4), broken];
+        let results = FleetResults {
+            schema_version: SCHEMA_VERSION,
+            totals: totals(&reports),
+            repos: reports,
+        };
+        assert_eq!(
+            results.totals,
+            FleetTotals {
+                repos: 2,
+                failed_repos: 1,
+                docs: 40,
+                compliant_docs: 24,
+                compliance_percent: 60.0,
+                errors: 5,
+                warnings: 2,
+            }
+        );
+
+        let text = render_text(&results);
+        assert!(text.contains("api            10         90%       1         1       50%"));
+        assert!(text.contains("legacy      error: pave status failed: No .pave.toml found"));
+        assert!(text.contains("2 repositories: 40 documents, 60% compliant, 5 errors, 2 warnings"));
+        let html = render_html(&results);
+        assert!(html.contains("<span class=\"badge fail\">90%</span>"));
+        assert!(html.contains("<code>../legacy</code>"));
+    }
+
+    #[test]
+    fn manifest_paths_are_relative_to_the_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("repos.txt");
+        fs::write(&manifest, "# Platform repos\napi\n\n  ../web  \n").unwrap();
+        assert_eq!(
+            read_manifest(&manifest, Path::new(".")).unwrap(),
+            [temp_dir.path().join("api"), temp_dir.path().join("../web")]
+        );
+    }
+}
@@ -1,91 +1,280 @@
 //! Implementation of the `pave hooks` command.
+//!
+//! Installed hooks are small shell scripts that run any hook the user had
+//! before, then hand off to `pave hooks run <hook>`. The steps each hook runs
+//! live in the `[hooks]` section of `.pave.toml`, so changing them doesn't
+//! require reinstalling the hooks.
 
 use anyhow::{Context, Result, bail};
 use std::fs;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
-use crate::cli::HookType;
+use crate::cli::{HookType, PathStyle};
+use crate::config::{HooksSection, PaveConfig};
+use crate::exit::{self, Violations};
+use crate::paths::display_path;
+use crate::workspace::Workdir;
 
 /// Marker comment to identify pave-installed hooks.
 pub const PAVE_HOOK_MARKER: &str = "# Installed by pave";
 
+/// Suffix for a user's existing hook, which pave's hook runs first.
+pub const CHAINED_HOOK_SUFFIX: &str = ".pre-pave";
+
+/// Placeholder for the docs changed in the commit or push.
+const DOCS_PLACEHOLDER: &str = "{docs}";
+
+/// Placeholder for the arguments git passed to the hook.
+const ARGS_PLACEHOLDER: &str = "{args}";
+
+/// Git's name for a missing commit in pre-push input.
+const NULL_SHA: &str = "0000000000000000000000000000000000000000";
+
 /// Generate the hook script content for the given hook type.
 ///
-/// If `run_verify` is true, the hook will also run `pave verify --keep-going`
-/// after `pave check` passes.
+/// If `run_verify` is true, the hook also runs `pave verify --keep-going`
+/// unless `[hooks]` configures its steps.
 fn generate_hook_script(hook_type: HookType, run_verify: bool) -> String {
     let hook_name = hook_type.filename();
-    let verify_section = if run_verify {
-        r#"
-    echo ""
-    echo "Running PAVED verification..."
-    echo "$CHANGED_DOCS" | xargs pave verify --keep-going
-    exit $?
+    let verify_flag = if run_verify { " --verify" } else { "" };
+    let run = format!("pave hooks run{verify_flag} {hook_name} \"$@\"");
+
+    // pre-push reads the pushed refs from stdin, which both hooks need
+    let body = match hook_type {
+        HookType::PrePush => format!(
+            r#"INPUT=$(cat)
+if [ -x "$CHAINED" ]; then
+    printf '%s\n' "$INPUT" | "$CHAINED" "$@" || exit $?
+fi
+printf '%s\n' "$INPUT" | {run}
 "#
-    } else {
-        ""
+        ),
+        HookType::PreCommit | HookType::CommitMsg => format!(
+            r#"if [ -x "$CHAINED" ]; then
+    "$CHAINED" "$@" || exit $?
+fi
+exec {run}
+"#
+        ),
     };
 
-    match hook_type {
-        HookType::PreCommit => format!(
-            r#"#!/bin/sh
+    format!(
+        r#"#!/bin/sh
 {PAVE_HOOK_MARKER}
-# PAVED documentation validation hook ({hook_name})
+# PAVED documentation hook ({hook_name})
+# The steps it runs are set in the [hooks] section of .pave.toml.
 
-# Get docs root from pave config, default to "docs"
-DOCS_ROOT=$(pave config get docs.root 2>/dev/null || echo "docs")
+# Run the hook that was here before pave, if any
+CHAINED="$(dirname "$0")/{hook_name}{CHAINED_HOOK_SUFFIX}"
+{body}"#
+    )
+}
 
-# Get list of changed .md files in docs directory (staged files)
-CHANGED_DOCS=$(git diff --cached --name-only --diff-filter=ACM | grep "^$DOCS_ROOT/.*\.md$")
+/// The `pave` commands a hook runs, in order.
+///
+/// Configured steps replace the defaults: `pave check` on the changed docs for
+/// pre-commit and pre-push, plus `pave verify --keep-going` if `run_verify`,
+/// and nothing for commit-msg.
+pub fn pipeline(config: &HooksSection, hook_type: HookType, run_verify: bool) -> Vec<String> {
+    let configured = match hook_type {
+        HookType::PreCommit => &config.pre_commit,
+        HookType::PrePush => &config.pre_push,
+        HookType::CommitMsg => &config.commit_msg,
+    };
+    if let Some(steps) = configured {
+        return steps.clone();
+    }
 
-if [ -n "$CHANGED_DOCS" ]; then
-    echo "Validating PAVED documentation..."
-    echo "$CHANGED_DOCS" | xargs pave check
-    CHECK_STATUS=$?
-    if [ $CHECK_STATUS -ne 0 ]; then
-        exit $CHECK_STATUS
-    fi{verify_section}
-fi
-"#
-        ),
-        HookType::PrePush => format!(
-            r#"#!/bin/sh
-{PAVE_HOOK_MARKER}
-# PAVED documentation validation hook ({hook_name})
-
-# Get docs root from pave config, default to "docs"
-DOCS_ROOT=$(pave config get docs.root 2>/dev/null || echo "docs")
-
-# Pre-push receives: remote_name remote_url on stdin: local_ref local_sha remote_ref remote_sha
-# We check docs changed between remote ref and local ref
-while read local_ref local_sha remote_ref remote_sha; do
-    if [ "$local_sha" = "0000000000000000000000000000000000000000" ]; then
-        # Branch is being deleted, nothing to check
-        continue
-    fi
-
-    if [ "$remote_sha" = "0000000000000000000000000000000000000000" ]; then
-        # New branch, check all docs in the branch
-        CHANGED_DOCS=$(git diff --name-only --diff-filter=ACM "$local_sha" | grep "^$DOCS_ROOT/.*\.md$")
-    else
-        # Existing branch, check docs changed since remote
-        CHANGED_DOCS=$(git diff --name-only --diff-filter=ACM "$remote_sha".."$local_sha" | grep "^$DOCS_ROOT/.*\.md$")
-    fi
-
-    if [ -n "$CHANGED_DOCS" ]; then
-        echo "Validating PAVED documentation..."
-        echo "$CHANGED_DOCS" | xargs pave check
-        CHECK_STATUS=$?
-        if [ $CHECK_STATUS -ne 0 ]; then
-            exit $CHECK_STATUS
-        fi{verify_section}
-    fi
-done
-
-exit 0
-"#
-        ),
+    match hook_type {
+        HookType::PreCommit | HookType::PrePush => {
+            let mut steps = vec![format!("check {}", DOCS_PLACEHOLDER)];
+            if run_verify {
+                steps.push(format!("verify --keep-going {}", DOCS_PLACEHOLDER));
+            }
+            steps
+        }
+        HookType::CommitMsg => Vec::new(),
+    }
+}
+
+/// Split a step into `pave` arguments, expanding its placeholders.
+///
+/// Returns `None` if the step uses `{docs}` and no docs changed, since there
+/// is nothing for it to do.
+fn expand_step(step: &str, docs: &[PathBuf], hook_args: &[String]) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    for token in step.split_whitespace() {
+        match token {
+            DOCS_PLACEHOLDER if docs.is_empty() => return None,
+            DOCS_PLACEHOLDER => args.extend(docs.iter().map(|d| d.display().to_string())),
+            ARGS_PLACEHOLDER => args.extend(hook_args.iter().cloned()),
+            _ => args.push(token.to_string()),
+        }
     }
+    // A leading "pave" is allowed but not needed
+    if args.first().is_some_and(|arg| arg == "pave") {
+        args.remove(0);
+    }
+    Some(args)
+}
+
+/// Run the steps configured for a git hook.
+///
+/// `hook_args` are the arguments git passed to the hook, such as the message
+/// file for commit-msg. Stops at the first step that fails.
+pub fn run(
+    hook_type: HookType,
+    run_verify: bool,
+    hook_args: &[String],
+    workdir: &Workdir,
+) -> Result<()> {
+    let (config, config_dir) = match workdir.find_config() {
+        Ok(path) => {
+            let config = PaveConfig::load(&path)?;
+            let dir = path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf();
+            (config, dir)
+        }
+        Err(_) => (PaveConfig::default(), workdir.dir.clone()),
+    };
+    let steps = pipeline(
+        &config.hooks,
+        hook_type,
+        run_verify || config.hooks.run_verify,
+    );
+    if steps.is_empty() {
+        return Ok(());
+    }
+
+    let docs = if steps.iter().any(|step| step.contains(DOCS_PLACEHOLDER)) {
+        let files = match hook_type {
+            HookType::PreCommit | HookType::CommitMsg => git_files(
+                &workdir.dir,
+                &["diff", "--cached", "--name-only", "--diff-filter=ACM"],
+            )?,
+            HookType::PrePush => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read pushed refs")?;
+                pushed_files(&workdir.dir, &input)?
+            }
+        };
+        changed_docs(&files, &config.docs.root, &config_dir, &workdir.dir)
+    } else {
+        Vec::new()
+    };
+
+    let pave = std::env::current_exe().context("Failed to locate the pave executable")?;
+    for step in &steps {
+        let Some(args) = expand_step(step, &docs, hook_args) else {
+            continue;
+        };
+        println!("Running pave {}...", args.join(" "));
+        let status = Command::new(&pave)
+            .args(&args)
+            .current_dir(&workdir.dir)
+            .status()
+            .with_context(|| format!("Failed to run pave {}", step))?;
+        if !status.success() {
+            let message = format!("{} hook step 'pave {}' failed", hook_type.filename(), step);
+            if status.code() == Some(exit::VIOLATIONS.into()) {
+                bail!(Violations(message));
+            }
+            bail!(message);
+        }
+    }
+    Ok(())
+}
+
+/// Run git in `dir` and return the file names it prints, one per line.
+fn git_files(dir: &Path, args: &[&str]) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Files added, copied, or modified by the pushed commits, from pre-push's
+/// `<local ref> <local sha> <remote ref> <remote sha>` input lines.
+fn pushed_files(dir: &Path, input: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for (local_sha, remote_sha) in parse_push_input(input) {
+        let found = if remote_sha == NULL_SHA {
+            // New branch: commits not on any remote yet
+            git_files(
+                dir,
+                &[
+                    "log",
+                    "--name-only",
+                    "--format=",
+                    "--diff-filter=ACM",
+                    local_sha,
+                    "--not",
+                    "--remotes",
+                ],
+            )?
+        } else {
+            let range = format!("{}..{}", remote_sha, local_sha);
+            git_files(dir, &["diff", "--name-only", "--diff-filter=ACM", &range])?
+        };
+        for file in found {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// The local and remote commits of each pushed ref, skipping deleted refs.
+fn parse_push_input(input: &str) -> Vec<(&str, &str)> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, local_sha, _, remote_sha] if *local_sha != NULL_SHA => {
+                    Some((*local_sha, *remote_sha))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The markdown files under the docs root, as given relative to `cwd`.
+fn changed_docs(
+    files: &[PathBuf],
+    docs_root: &Path,
+    config_dir: &Path,
+    cwd: &Path,
+) -> Vec<PathBuf> {
+    files
+        .iter()
+        .filter(|file| file.extension().is_some_and(|ext| ext == "md"))
+        .filter(|file| {
+            display_path(file, PathStyle::Workspace, config_dir, cwd).starts_with(docs_root)
+        })
+        .cloned()
+        .collect()
 }
 
 /// Find the git hooks directory starting from the given base path.
@@ -121,10 +310,10 @@ pub fn find_git_hooks_dir_from(base: &Path) -> Result<std::path::PathBuf> {
     bail!("Not a git repository (no .git directory found)")
 }
 
-/// Find the git hooks directory, searching up from the current directory.
+/// Find the git hooks directory, searching up from `dir`.
 /// Supports both regular git repositories and git worktrees.
-fn find_git_hooks_dir() -> Result<std::path::PathBuf> {
-    let mut current = std::env::current_dir()?;
+fn find_git_hooks_dir(dir: &Path) -> Result<std::path::PathBuf> {
+    let mut current = dir.to_path_buf();
 
     loop {
         if let Ok(hooks_dir) = find_git_hooks_dir_from(&current) {
@@ -140,7 +329,7 @@ fn find_git_hooks_dir() -> Result<std::path::PathBuf> {
 }
 
 /// Check if a hook file was installed by pave.
-fn is_pave_hook(path: &Path) -> bool {
+pub(crate) fn is_pave_hook(path: &Path) -> bool {
     if let Ok(content) = fs::read_to_string(path) {
         content.contains(PAVE_HOOK_MARKER)
     } else {
@@ -152,8 +341,13 @@ fn is_pave_hook(path: &Path) -> bool {
 ///
 /// If `run_verify` is true, the hook will also run `pave verify --keep-going`
 /// after `pave check` passes.
-pub fn install(hook_type: HookType, force: bool, run_verify: bool) -> Result<()> {
-    let hooks_dir = find_git_hooks_dir()?;
+pub fn install(
+    hook_type: HookType,
+    force: bool,
+    run_verify: bool,
+    workdir: &Workdir,
+) -> Result<()> {
+    let hooks_dir = find_git_hooks_dir(&workdir.dir)?;
     install_hook_in_dir(&hooks_dir, hook_type, force, run_verify)
 }
 
@@ -173,51 +367,23 @@ pub fn install_at(
 ) -> Result<()> {
     let hooks_dir = find_git_hooks_dir_from(base)?;
     let hook_path = hooks_dir.join(hook_type.filename());
-
-    // Check if hook already exists
-    if hook_path.exists() {
-        if is_pave_hook(&hook_path) {
-            // Already installed by pave, nothing to do
-            return Ok(());
-        } else {
-            // Foreign hook exists
-            if init_mode {
-                println!("Warning: pre-commit hook already exists, skipping hook installation.");
-                println!(
-                    "Run 'pave hooks install --force' to overwrite, or add 'pave check' manually."
-                );
-                return Ok(());
-            } else {
-                bail!(
-                    "Hook '{}' already exists (not installed by pave). Use --force to overwrite.",
-                    hook_type.filename()
-                );
-            }
+    if init_mode && hook_path.exists() {
+        if !is_pave_hook(&hook_path) {
+            println!(
+                "Warning: {} hook already exists, skipping hook installation.",
+                hook_type.filename()
+            );
+            println!("Run 'pave hooks install' to run pave's checks after it.");
         }
+        return Ok(());
     }
-
-    let hook_content = generate_hook_script(hook_type, run_verify);
-    fs::write(&hook_path, &hook_content)
-        .with_context(|| format!("Failed to write {} hook", hook_type.filename()))?;
-
-    // Make the hook executable on Unix
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&hook_path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&hook_path, perms)?;
-    }
-
-    println!(
-        "Installed git {} hook for documentation validation.",
-        hook_type.filename()
-    );
-
-    Ok(())
+    install_hook_in_dir(&hooks_dir, hook_type, false, run_verify)
 }
 
 /// Internal function to install a hook in a specific hooks directory.
+///
+/// A hook that pave didn't install is kept and run first, unless `force` is
+/// set, in which case it's overwritten.
 fn install_hook_in_dir(
     hooks_dir: &Path,
     hook_type: HookType,
@@ -225,6 +391,7 @@ fn install_hook_in_dir(
     run_verify: bool,
 ) -> Result<()> {
     let hook_path = hooks_dir.join(hook_type.filename());
+    let chained_path = chained_hook_path(hooks_dir, hook_type);
 
     // Check if hook already exists
     if hook_path.exists() {
@@ -237,13 +404,37 @@ fn install_hook_in_dir(
                 return Ok(());
             }
         } else if !force {
-            bail!(
-                "Hook '{}' already exists (not installed by pave). Use --force to overwrite.",
-                hook_type.filename()
+            if chained_path.exists() {
+                bail!(
+                    "Hook '{}' already exists (not installed by pave), and so does {}. \
+                     Use --force to overwrite.",
+                    hook_type.filename(),
+                    chained_path.display()
+                );
+            }
+            fs::rename(&hook_path, &chained_path).with_context(|| {
+                format!("Failed to move existing {} hook", hook_type.filename())
+            })?;
+            println!(
+                "Moved existing {} hook to {}; pave's hook runs it first.",
+                hook_type.filename(),
+                chained_path.display()
             );
         }
     }
 
+    write_hook(hooks_dir, hook_type, run_verify)?;
+    println!(
+        "Installed git {} hook for documentation validation.",
+        hook_type.filename()
+    );
+
+    Ok(())
+}
+
+/// Write pave's hook script into `hooks_dir`, replacing any hook there.
+pub(crate) fn write_hook(hooks_dir: &Path, hook_type: HookType, run_verify: bool) -> Result<()> {
+    let hook_path = hooks_dir.join(hook_type.filename());
     let hook_content = generate_hook_script(hook_type, run_verify);
     fs::write(&hook_path, hook_content)
         .with_context(|| format!("Failed to write {} hook", hook_type.filename()))?;
@@ -256,18 +447,17 @@ fn install_hook_in_dir(
         perms.set_mode(0o755);
         fs::set_permissions(&hook_path, perms)?;
     }
-
-    println!(
-        "Installed git {} hook for documentation validation.",
-        hook_type.filename()
-    );
-
     Ok(())
 }
 
-/// Uninstall a git hook.
-pub fn uninstall(hook_type: HookType) -> Result<()> {
-    let hooks_dir = find_git_hooks_dir()?;
+/// Where a user's existing hook is kept once pave's hook replaces it.
+fn chained_hook_path(hooks_dir: &Path, hook_type: HookType) -> PathBuf {
+    hooks_dir.join(format!("{}{}", hook_type.filename(), CHAINED_HOOK_SUFFIX))
+}
+
+/// Uninstall a git hook, restoring the hook it replaced, if any.
+pub fn uninstall(hook_type: HookType, workdir: &Workdir) -> Result<()> {
+    let hooks_dir = find_git_hooks_dir(&workdir.dir)?;
     let hook_path = hooks_dir.join(hook_type.filename());
 
     if !hook_path.exists() {
@@ -288,6 +478,13 @@ pub fn uninstall(hook_type: HookType) -> Result<()> {
 
     println!("Uninstalled git {} hook.", hook_type.filename());
 
+    let chained_path = chained_hook_path(&hooks_dir, hook_type);
+    if chained_path.exists() {
+        fs::rename(&chained_path, &hook_path)
+            .with_context(|| format!("Failed to restore previous {} hook", hook_type.filename()))?;
+        println!("Restored the previous {} hook.", hook_type.filename());
+    }
+
     Ok(())
 }
 
@@ -295,12 +492,8 @@ pub fn uninstall(hook_type: HookType) -> Result<()> {
 mod tests {
     use super::*;
     use std::fs;
-    use std::sync::Mutex;
     use tempfile::TempDir;
 
-    // Mutex to ensure tests that change working directory don't run in parallel
-    static WORKING_DIR_MUTEX: Mutex<()> = Mutex::new(());
-
     /// Helper to create a fake git repo structure.
     fn setup_git_repo(temp_dir: &TempDir) {
         let git_dir = temp_dir.path().join(".git");
@@ -308,18 +501,9 @@ mod tests {
         fs::create_dir_all(&hooks_dir).unwrap();
     }
 
-    /// Helper to run tests in a specific directory.
-    /// Uses a mutex to prevent parallel execution of tests that change working dir.
-    fn with_working_dir<F, R>(path: &Path, f: F) -> R
-    where
-        F: FnOnce() -> R,
-    {
-        let _guard = WORKING_DIR_MUTEX.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
-        std::env::set_current_dir(path).unwrap();
-        let result = f();
-        std::env::set_current_dir(original).unwrap();
-        result
+    /// Helper to run commands in a specific directory.
+    fn workdir(path: &Path) -> Workdir {
+        Workdir::new(path).unwrap()
     }
 
     #[test]
@@ -327,16 +511,14 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         assert!(hook_path.exists());
 
         let content = fs::read_to_string(&hook_path).unwrap();
         assert!(content.contains(PAVE_HOOK_MARKER));
-        assert!(content.contains("pave check"));
+        assert!(content.contains("exec pave hooks run pre-commit \"$@\""));
     }
 
     #[test]
@@ -344,9 +526,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PrePush, false, false).unwrap();
-        });
+        install(HookType::PrePush, false, false, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = temp_dir.path().join(".git/hooks/pre-push");
         assert!(hook_path.exists());
@@ -361,9 +541,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         // No .git directory created
 
-        let result = with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false)
-        });
+        let result = install(HookType::PreCommit, false, false, &workdir(temp_dir.path()));
 
         assert!(result.is_err());
         assert!(
@@ -380,29 +558,51 @@ mod tests {
         setup_git_repo(&temp_dir);
 
         // Install once
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         // Install again - should succeed with warning (not error)
-        let result = with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false)
-        });
+        let result = install(HookType::PreCommit, false, false, &workdir(temp_dir.path()));
         assert!(result.is_ok());
     }
 
     #[test]
-    fn install_fails_if_foreign_hook_exists() {
+    fn install_chains_foreign_hook() {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
         // Create a non-pave hook
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
+        let chained_path = temp_dir.path().join(".git/hooks/pre-commit.pre-pave");
         fs::write(&hook_path, "#!/bin/sh\necho 'custom hook'").unwrap();
 
-        let result = with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false)
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
+
+        assert!(is_pave_hook(&hook_path));
+        assert_eq!(
+            fs::read_to_string(&chained_path).unwrap(),
+            "#!/bin/sh\necho 'custom hook'"
+        );
+
+        // Uninstalling puts the user's hook back
+        uninstall(HookType::PreCommit, &workdir(temp_dir.path())).unwrap();
+        assert!(!chained_path.exists());
+        assert!(!is_pave_hook(&hook_path));
+    }
+
+    #[test]
+    fn install_fails_if_foreign_hook_already_chained() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_git_repo(&temp_dir);
+
+        let hooks_dir = temp_dir.path().join(".git/hooks");
+        fs::write(
+            hooks_dir.join("pre-commit"),
+            "#!/bin/sh\necho 'custom hook'",
+        )
+        .unwrap();
+        fs::write(hooks_dir.join("pre-commit.pre-pave"), "#!/bin/sh\n").unwrap();
+
+        let result = install(HookType::PreCommit, false, false, &workdir(temp_dir.path()));
 
         assert!(result.is_err());
         assert!(
@@ -422,9 +622,7 @@ mod tests {
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         fs::write(&hook_path, "#!/bin/sh\necho 'custom hook'").unwrap();
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, true, false).unwrap();
-        });
+        install(HookType::PreCommit, true, false, &workdir(temp_dir.path())).unwrap();
 
         let content = fs::read_to_string(&hook_path).unwrap();
         assert!(content.contains(PAVE_HOOK_MARKER));
@@ -436,17 +634,13 @@ mod tests {
         setup_git_repo(&temp_dir);
 
         // Install first
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         assert!(hook_path.exists());
 
         // Uninstall
-        with_working_dir(temp_dir.path(), || {
-            uninstall(HookType::PreCommit).unwrap();
-        });
+        uninstall(HookType::PreCommit, &workdir(temp_dir.path())).unwrap();
 
         assert!(!hook_path.exists());
     }
@@ -456,7 +650,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
-        let result = with_working_dir(temp_dir.path(), || uninstall(HookType::PreCommit));
+        let result = uninstall(HookType::PreCommit, &workdir(temp_dir.path()));
         assert!(result.is_ok());
     }
 
@@ -469,7 +663,7 @@ mod tests {
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         fs::write(&hook_path, "#!/bin/sh\necho 'custom hook'").unwrap();
 
-        let result = with_working_dir(temp_dir.path(), || uninstall(HookType::PreCommit));
+        let result = uninstall(HookType::PreCommit, &workdir(temp_dir.path()));
 
         assert!(result.is_err());
         assert!(
@@ -488,9 +682,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         let perms = fs::metadata(&hook_path).unwrap().permissions();
@@ -498,20 +690,58 @@ mod tests {
     }
 
     #[test]
-    fn generated_pre_commit_hook_uses_cached_diff() {
-        let script = generate_hook_script(HookType::PreCommit, false);
-        assert!(script.contains("DOCS_ROOT=$(pave config get docs.root"));
-        assert!(script.contains("git diff --cached"));
-        assert!(script.contains("grep \"^$DOCS_ROOT/.*\\.md$\""));
+    fn generated_hooks_chain_and_delegate_to_pave() {
+        let script = generate_hook_script(HookType::CommitMsg, false);
+        assert!(script.contains("CHAINED=\"$(dirname \"$0\")/commit-msg.pre-pave\""));
+        assert!(script.contains("\"$CHAINED\" \"$@\" || exit $?"));
+        assert!(script.contains("exec pave hooks run commit-msg \"$@\""));
+
+        // pre-push input goes to both hooks
+        let script = generate_hook_script(HookType::PrePush, false);
+        assert!(script.contains("INPUT=$(cat)"));
+        assert!(script.contains("printf '%s\\n' \"$INPUT\" | \"$CHAINED\" \"$@\" || exit $?"));
+        assert!(script.contains("printf '%s\\n' \"$INPUT\" | pave hooks run pre-push \"$@\""));
     }
 
     #[test]
-    fn generated_pre_push_hook_uses_ref_diff() {
-        let script = generate_hook_script(HookType::PrePush, false);
-        assert!(script.contains("DOCS_ROOT=$(pave config get docs.root"));
-        assert!(script.contains("while read local_ref local_sha"));
-        assert!(script.contains("$remote_sha\"..\"$local_sha"));
-        assert!(script.contains("grep \"^$DOCS_ROOT/.*\\.md$\""));
+    fn expands_step_placeholders() {
+        let docs = vec![PathBuf::from("docs/a.md"), PathBuf::from("docs/b.md")];
+        let args = vec![".git/COMMIT_EDITMSG".to_string()];
+
+        assert_eq!(
+            expand_step("pave check --strict {docs}", &docs, &args).unwrap(),
+            vec!["check", "--strict", "docs/a.md", "docs/b.md"]
+        );
+        assert_eq!(
+            expand_step("lint {args}", &[], &args).unwrap(),
+            vec!["lint", ".git/COMMIT_EDITMSG"]
+        );
+        assert_eq!(expand_step("check {docs}", &[], &args), None);
+        assert_eq!(
+            expand_step("verify --changed", &[], &[]).unwrap(),
+            vec!["verify", "--changed"]
+        );
+    }
+
+    #[test]
+    fn parses_push_input_and_filters_docs() {
+        let input = format!(
+            "refs/heads/main abc123 refs/heads/main def456\n\
+             refs/heads/gone {NULL_SHA} refs/heads/gone def456\n\
+             \n"
+        );
+        assert_eq!(parse_push_input(&input), vec![("abc123", "def456")]);
+
+        let temp_dir = TempDir::new().unwrap();
+        let files = vec![
+            temp_dir.path().join("docs/a.md"),
+            temp_dir.path().join("docs/notes.txt"),
+            temp_dir.path().join("README.md"),
+        ];
+        assert_eq!(
+            changed_docs(&files, Path::new("docs"), temp_dir.path(), temp_dir.path()),
+            vec![temp_dir.path().join("docs/a.md")]
+        );
     }
 
     /// Helper to create a fake git worktree structure.
@@ -533,9 +763,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let main_repo = setup_git_worktree(&temp_dir);
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         // Hook should be in the worktree's git dir, not the main .git
         let hook_path = main_repo
@@ -553,9 +781,7 @@ mod tests {
         let main_repo = setup_git_worktree(&temp_dir);
 
         // Install first
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, false).unwrap();
-        });
+        install(HookType::PreCommit, false, false, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = main_repo
             .path()
@@ -563,33 +789,38 @@ mod tests {
         assert!(hook_path.exists());
 
         // Uninstall
-        with_working_dir(temp_dir.path(), || {
-            uninstall(HookType::PreCommit).unwrap();
-        });
+        uninstall(HookType::PreCommit, &workdir(temp_dir.path())).unwrap();
 
         assert!(!hook_path.exists());
     }
 
     #[test]
-    fn generated_hook_without_verify_omits_verify() {
-        let script = generate_hook_script(HookType::PreCommit, false);
-        assert!(script.contains("pave check"));
-        assert!(!script.contains("pave verify"));
+    fn default_pipeline_without_verify_omits_verify() {
+        let steps = pipeline(&HooksSection::default(), HookType::PreCommit, false);
+        assert_eq!(steps, vec!["check {docs}"]);
+        assert!(pipeline(&HooksSection::default(), HookType::CommitMsg, true).is_empty());
     }
 
     #[test]
-    fn generated_hook_with_verify_includes_verify() {
-        let script = generate_hook_script(HookType::PreCommit, true);
-        assert!(script.contains("pave check"));
-        assert!(script.contains("pave verify --keep-going"));
-        assert!(script.contains("Running PAVED verification"));
+    fn default_pipeline_with_verify_includes_verify() {
+        for hook_type in [HookType::PreCommit, HookType::PrePush] {
+            let steps = pipeline(&HooksSection::default(), hook_type, true);
+            assert_eq!(steps, vec!["check {docs}", "verify --keep-going {docs}"]);
+        }
     }
 
     #[test]
-    fn generated_pre_push_hook_with_verify() {
-        let script = generate_hook_script(HookType::PrePush, true);
-        assert!(script.contains("pave check"));
-        assert!(script.contains("pave verify --keep-going"));
+    fn configured_pipeline_replaces_default() {
+        let config = HooksSection {
+            pre_commit: Some(vec!["check --changed".to_string()]),
+            pre_push: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            pipeline(&config, HookType::PreCommit, true),
+            vec!["check --changed"]
+        );
+        assert!(pipeline(&config, HookType::PrePush, true).is_empty());
     }
 
     #[test]
@@ -597,13 +828,10 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         setup_git_repo(&temp_dir);
 
-        with_working_dir(temp_dir.path(), || {
-            install(HookType::PreCommit, false, true).unwrap();
-        });
+        install(HookType::PreCommit, false, true, &workdir(temp_dir.path())).unwrap();
 
         let hook_path = temp_dir.path().join(".git/hooks/pre-commit");
         let content = fs::read_to_string(&hook_path).unwrap();
-        assert!(content.contains("pave check"));
-        assert!(content.contains("pave verify --keep-going"));
+        assert!(content.contains("pave hooks run --verify pre-commit"));
     }
 }
@@ -0,0 +1,1173 @@
+//! Implementation of the `pave import` command.
+//!
+//! `pave import --from <source> <path>` brings another doc system's pages
+//! under the docs root, in the order of its navigation:
+//!
+//! - **MkDocs**: the pages listed in `nav` of `mkdocs.yml`, then any other
+//!   pages in `docs_dir`. `!!! note` admonitions become GitHub alerts.
+//! - **Docusaurus**: the pages in `docs/`, ordered as the autogenerated
+//!   sidebar orders them, by `sidebar_position` and `_category_` files. MDX
+//!   `import` and `export` lines are dropped.
+//! - **Confluence**: the pages of an HTML space export, converted to
+//!   markdown and nested as in the export's page tree.
+//!
+//! Every page gets `pave` frontmatter and a `# Title`, links between
+//! imported pages are rewritten to their new paths, and an `index.md`
+//! lists the pages as the original navigation did. A root `index.md` page is
+//! imported as `home.md` to make room for it. The report says which pages
+//! already fit PAVED; the rest are imported as drafts for
+//! `pave adopt --migrate` to finish.
+
+use anyhow::{Context, Result, bail};
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{ImportOutputFormat, ImportSource};
+use crate::commands::migrate::analyze_content;
+use crate::config::PaveConfig;
+use crate::html;
+use crate::parser::{CodeBlockTracker, ParsedDoc, frontmatter_len};
+use crate::paths::{absolutize, relative_path};
+use crate::schema::SCHEMA_VERSION;
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave import` command.
+pub struct ImportArgs {
+    /// Doc system the pages come from.
+    pub from: ImportSource,
+    /// The site to import: its directory or config file, or for Confluence,
+    /// the export directory.
+    pub path: PathBuf,
+    /// Directory to write the pages to [default: the docs root].
+    pub output: Option<PathBuf>,
+    /// Report what would be imported without writing files.
+    pub dry_run: bool,
+    /// Overwrite files that already exist.
+    pub force: bool,
+    /// Output format.
+    pub format: ImportOutputFormat,
+}
+
+/// A page or section in the imported site's navigation.
+#[derive(Debug, Clone, PartialEq)]
+struct NavItem {
+    /// Title from the navigation, if it gives one.
+    title: Option<String>,
+    /// The page's source file, or `None` for a section of pages.
+    source: Option<PathBuf>,
+    /// Pages and sections nested under this one.
+    children: Vec<NavItem>,
+}
+
+impl NavItem {
+    fn page(title: Option<String>, source: PathBuf) -> Self {
+        Self {
+            title,
+            source: Some(source),
+            children: Vec::new(),
+        }
+    }
+}
+
+/// The site being imported.
+struct Site {
+    /// Site name, for the index title.
+    name: Option<String>,
+    /// Directory the source paths are relative to.
+    root: PathBuf,
+    nav: Vec<NavItem>,
+}
+
+/// One imported page.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportedDoc {
+    /// Page title.
+    pub title: String,
+    /// Source file, relative to the imported site.
+    pub source: PathBuf,
+    /// Where the page was written, relative to the output directory.
+    pub path: PathBuf,
+    /// Whether the page already has the PAVED sections for its type.
+    pub fits_paved: bool,
+    /// Sections `pave adopt --migrate` would add or rename headings to.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_sections: Vec<String>,
+    /// Why the page wasn't written, if it wasn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+/// Results of the import command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ImportResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Directory the pages were written to.
+    pub output: PathBuf,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+    /// Number of pages imported.
+    pub imported: usize,
+    /// Number of imported pages that already fit PAVED.
+    pub fits_paved: usize,
+    /// The generated index, unless it was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<PathBuf>,
+    /// Every page, in navigation order.
+    pub documents: Vec<ImportedDoc>,
+}
+
+/// Execute the `pave import` command.
+pub fn execute(args: ImportArgs, workdir: &Workdir) -> Result<()> {
+    let output = match &args.output {
+        Some(output) => workdir.resolve(output),
+        None => match workdir.locate_config()? {
+            Some(config_path) => {
+                let config = PaveConfig::load(&config_path)?;
+                let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+                config_dir.join(&config.docs.root)
+            }
+            None => workdir.dir.join("docs"),
+        },
+    };
+
+    let path = workdir.resolve(&args.path);
+    let site = match args.from {
+        ImportSource::Mkdocs => read_mkdocs(&path)?,
+        ImportSource::Docusaurus => read_docusaurus(&path)?,
+        ImportSource::Confluence => read_confluence(&path)?,
+    };
+    if site.nav.is_empty() {
+        bail!("No pages found in {}", args.path.display());
+    }
+
+    let results = import(
+        &site,
+        args.from,
+        &output,
+        args.dry_run,
+        args.force,
+        &workdir.dir,
+    )?;
+    match args.format {
+        ImportOutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&results).context("Failed to serialize results")?
+        ),
+        ImportOutputFormat::Text => output_text(&results, args.from),
+    }
+    Ok(())
+}
+
+/// Convert and write every page of `site`, then the index. Relative paths
+/// are resolved against `cwd`.
+fn import(
+    site: &Site,
+    from: ImportSource,
+    output: &Path,
+    dry_run: bool,
+    force: bool,
+    cwd: &Path,
+) -> Result<ImportResults> {
+    let targets = match from {
+        ImportSource::Confluence => confluence_targets(&site.nav, Path::new(""), cwd),
+        _ => markdown_targets(&site.nav, &site.root, cwd),
+    };
+
+    let mut results = ImportResults {
+        schema_version: SCHEMA_VERSION,
+        output: output.to_path_buf(),
+        dry_run,
+        imported: 0,
+        fits_paved: 0,
+        index: None,
+        documents: Vec::new(),
+    };
+    let mut titles = BTreeMap::new();
+    for (item, source) in pages(&site.nav) {
+        let target = &targets[&absolutize(source, cwd)];
+        let relative_source = source.strip_prefix(&site.root).unwrap_or(source);
+        if !source.exists() {
+            let title = item.title.clone().unwrap_or_else(|| file_title(source));
+            titles.insert(target.clone(), title.clone());
+            results.documents.push(ImportedDoc {
+                title,
+                source: relative_source.to_path_buf(),
+                path: target.clone(),
+                fits_paved: false,
+                missing_sections: Vec::new(),
+                skipped: Some("the navigation lists it, but the file doesn't exist".to_string()),
+            });
+            continue;
+        }
+        let raw = fs::read_to_string(source)
+            .with_context(|| format!("failed to read file: {}", source.display()))?;
+        let page = match from {
+            ImportSource::Confluence => convert_confluence(&raw),
+            ImportSource::Mkdocs => convert_markdown(&raw, convert_mkdocs_admonitions),
+            ImportSource::Docusaurus => convert_markdown(&raw, drop_mdx_statements),
+        };
+        let title = item
+            .title
+            .clone()
+            .or(page.title)
+            .or_else(|| heading_title(&page.body))
+            .unwrap_or_else(|| file_title(source));
+        let mut body = rewrite_links(&page.body, source, target, &targets, cwd);
+        if heading_title(&body).is_none() {
+            body = format!("# {}\n\n{}", title, body.trim_start());
+        }
+
+        let analysis = analyze_content(target, &body, &None)?;
+        let missing_sections: Vec<String> = analysis
+            .iter()
+            .flat_map(|analysis| {
+                let renamed = analysis.renamed_sections.iter().map(|r| r.to.clone());
+                let missing = analysis.missing_sections.iter().map(|s| s.name.clone());
+                renamed.chain(missing)
+            })
+            .collect();
+        let fits_paved = missing_sections.is_empty();
+        let content = format!(
+            "{}{}",
+            frontmatter(fits_paved, &page.tags),
+            body.trim_end().to_string() + "\n"
+        );
+
+        let path = output.join(target);
+        let skipped = (path.exists() && !force)
+            .then(|| "already exists (use --force to overwrite)".to_string());
+        if skipped.is_none() {
+            results.imported += 1;
+            if fits_paved {
+                results.fits_paved += 1;
+            }
+            if !dry_run {
+                write(&path, &content)?;
+            }
+        }
+        titles.insert(target.clone(), title.clone());
+        results.documents.push(ImportedDoc {
+            title,
+            source: relative_source.to_path_buf(),
+            path: target.clone(),
+            fits_paved,
+            missing_sections,
+            skipped,
+        });
+    }
+
+    let index_path = output.join("index.md");
+    if force || !index_path.exists() {
+        let index = render_index(site, from, &targets, &titles, cwd);
+        if !dry_run {
+            write(&index_path, &index)?;
+        }
+        results.index = Some(index_path);
+    }
+    Ok(results)
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("failed to write file: {}", path.display()))
+}
+
+/// Pages in navigation order, with their source files.
+fn pages(nav: &[NavItem]) -> Vec<(&NavItem, &Path)> {
+    let mut pages = Vec::new();
+    for item in nav {
+        if let Some(source) = &item.source {
+            pages.push((item, source.as_path()));
+        }
+        pages.extend(self::pages(&item.children));
+    }
+    pages
+}
+
+/// Read an MkDocs site from its directory or `mkdocs.yml`.
+fn read_mkdocs(path: &Path) -> Result<Site> {
+    let config_path = if path.is_dir() {
+        ["mkdocs.yml", "mkdocs.yaml"]
+            .iter()
+            .map(|name| path.join(name))
+            .find(|candidate| candidate.exists())
+            .with_context(|| format!("No mkdocs.yml in {}", path.display()))?
+    } else {
+        path.to_path_buf()
+    };
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("failed to read file: {}", config_path.display()))?;
+    let config: Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("failed to parse {}", config_path.display()))?;
+
+    let site_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let docs_dir = site_dir.join(config["docs_dir"].as_str().unwrap_or("docs"));
+    let mut nav = match &config["nav"] {
+        Value::Sequence(entries) => mkdocs_nav(entries, &docs_dir),
+        _ => Vec::new(),
+    };
+
+    // MkDocs builds pages left out of nav too
+    let listed: BTreeSet<PathBuf> = pages(&nav)
+        .into_iter()
+        .map(|(_, source)| source.to_path_buf())
+        .collect();
+    let mut unlisted = Vec::new();
+    collect_pages(&docs_dir, &["md"], &mut unlisted)?;
+    unlisted.sort_by_key(|path| (path != &docs_dir.join("index.md"), path.clone()));
+    nav.extend(
+        unlisted
+            .into_iter()
+            .filter(|path| !listed.contains(path))
+            .map(|path| NavItem::page(None, path)),
+    );
+
+    Ok(Site {
+        name: config["site_name"].as_str().map(str::to_string),
+        root: docs_dir,
+        nav,
+    })
+}
+
+/// Nav entries: `page.md`, `Title: page.md`, or `Section: [entries]`.
+/// External links are left out.
+fn mkdocs_nav(entries: &[Value], docs_dir: &Path) -> Vec<NavItem> {
+    let page = |title: Option<&str>, page: &str| {
+        (!page.contains("://"))
+            .then(|| NavItem::page(title.map(str::to_string), docs_dir.join(page)))
+    };
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Value::String(path) => page(None, path),
+            Value::Mapping(mapping) => {
+                let (title, value) = mapping.iter().next()?;
+                let title = title.as_str()?;
+                match value {
+                    Value::String(path) => page(Some(title), path),
+                    Value::Sequence(children) => Some(NavItem {
+                        title: Some(title.to_string()),
+                        source: None,
+                        children: mkdocs_nav(children, docs_dir),
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Markdown files under `dir` with one of `extensions`, skipping hidden
+/// files and directories.
+fn collect_pages(dir: &Path, extensions: &[&str], pages: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("failed to read directory: {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            collect_pages(&path, extensions, pages)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| extensions.iter().any(|wanted| ext == *wanted))
+        {
+            pages.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Read a Docusaurus site from its directory or its `docs/` directory.
+fn read_docusaurus(path: &Path) -> Result<Site> {
+    let docs_dir = if path.join("docs").is_dir() {
+        path.join("docs")
+    } else {
+        path.to_path_buf()
+    };
+    if !docs_dir.is_dir() {
+        bail!("{} is not a directory", docs_dir.display());
+    }
+    Ok(Site {
+        name: None,
+        root: docs_dir.clone(),
+        nav: docusaurus_nav(&docs_dir)?,
+    })
+}
+
+/// A directory's pages and categories as the autogenerated sidebar orders
+/// them: by position, then name. Names starting with `_` are partials.
+fn docusaurus_nav(dir: &Path) -> Result<Vec<NavItem>> {
+    let mut entries = Vec::new();
+    let listing = fs::read_dir(dir)
+        .with_context(|| format!("failed to read directory: {}", dir.display()))?;
+    for entry in listing {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if name.starts_with(['_', '.']) {
+            continue;
+        }
+        if path.is_dir() {
+            let (label, position) = docusaurus_category(&path);
+            let children = docusaurus_nav(&path)?;
+            if !children.is_empty() {
+                let title = label.or_else(|| Some(file_title(&path)));
+                entries.push((
+                    position,
+                    name,
+                    NavItem {
+                        title,
+                        source: None,
+                        children,
+                    },
+                ));
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "md" || ext == "mdx")
+        {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read file: {}", path.display()))?;
+            let frontmatter = split_frontmatter(&content).0;
+            let position = frontmatter["sidebar_position"].as_f64();
+            let title = frontmatter["sidebar_label"]
+                .as_str()
+                .or(frontmatter["title"].as_str())
+                .map(str::to_string);
+            entries.push((position, name, NavItem::page(title, path)));
+        }
+    }
+    entries.sort_by(|a, b| {
+        let position = |p: Option<f64>| p.unwrap_or(f64::MAX);
+        position(a.0).total_cmp(&position(b.0)).then(a.1.cmp(&b.1))
+    });
+    Ok(entries.into_iter().map(|(_, _, item)| item).collect())
+}
+
+/// Label and position of a category, from its `_category_.json` or
+/// `_category_.yml`.
+fn docusaurus_category(dir: &Path) -> (Option<String>, Option<f64>) {
+    let category = ["_category_.json", "_category_.yml", "_category_.yaml"]
+        .iter()
+        .find_map(|name| fs::read_to_string(dir.join(name)).ok())
+        .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+        .unwrap_or_default();
+    (
+        category["label"].as_str().map(str::to_string),
+        category["position"].as_f64(),
+    )
+}
+
+/// Read a Confluence HTML space export from its directory.
+fn read_confluence(path: &Path) -> Result<Site> {
+    let index_path = path.join("index.html");
+    let index = fs::read_to_string(&index_path)
+        .with_context(|| format!("No Confluence export index.html in {}", path.display()))?;
+    Ok(Site {
+        name: page_title(&index),
+        root: path.to_path_buf(),
+        nav: confluence_nav(&index, path),
+    })
+}
+
+/// The page tree of an export's `index.html`: nested lists of links under
+/// "Available Pages".
+fn confluence_nav(index: &str, dir: &Path) -> Vec<NavItem> {
+    let tree = index
+        .find("Available Pages")
+        .map_or(index, |start| &index[start..]);
+    let mut levels: Vec<Vec<NavItem>> = vec![Vec::new()];
+    let mut link: Option<(String, String)> = None;
+    for token in html::tokens(tree) {
+        match token {
+            html::Token::Open { name, .. } if name == "ul" => levels.push(Vec::new()),
+            html::Token::Close(name) if name == "ul" && levels.len() > 1 => {
+                let children = levels.pop().unwrap_or_default();
+                let parent = levels.last_mut().expect("the root level is never popped");
+                match parent.last_mut() {
+                    Some(item) => item.children = children,
+                    None => parent.extend(children),
+                }
+                if levels.len() == 1 {
+                    break;
+                }
+            }
+            html::Token::Open { name, attrs } if name == "a" && levels.len() > 1 => {
+                link = html::attr(attrs, "href")
+                    .filter(|href| href.ends_with(".html") && !href.contains("://"))
+                    .map(|href| (href, String::new()));
+            }
+            html::Token::Text(text) => {
+                if let Some((_, title)) = &mut link {
+                    title.push_str(text);
+                }
+            }
+            html::Token::Close(name) if name == "a" => {
+                if let Some((href, title)) = link.take() {
+                    let title = html::decode_entities(title.trim());
+                    levels
+                        .last_mut()
+                        .expect("links are read inside a list")
+                        .push(NavItem::page(Some(title), dir.join(href)));
+                }
+            }
+            _ => {}
+        }
+    }
+    levels.swap_remove(0)
+}
+
+/// Target paths of markdown pages: their paths within the site, as `.md`
+/// files, with a root `index.md` renamed to `home.md`.
+fn markdown_targets(nav: &[NavItem], root: &Path, cwd: &Path) -> BTreeMap<PathBuf, PathBuf> {
+    pages(nav)
+        .into_iter()
+        .map(|(_, source)| {
+            let mut target = source
+                .strip_prefix(root)
+                .unwrap_or(source)
+                .with_extension("md");
+            if target == Path::new("index.md") {
+                target = PathBuf::from("home.md");
+            }
+            (absolutize(source, cwd), target)
+        })
+        .collect()
+}
+
+/// Target paths of Confluence pages: a file named after each page's title,
+/// with its child pages in a directory of the same name.
+fn confluence_targets(nav: &[NavItem], dir: &Path, cwd: &Path) -> BTreeMap<PathBuf, PathBuf> {
+    let mut targets = BTreeMap::new();
+    let mut used = BTreeSet::new();
+    for item in nav {
+        let mut slug = slugify(item.title.as_deref().unwrap_or("page"));
+        if slug == "index" {
+            slug = "home".to_string();
+        }
+        let mut name = slug.clone();
+        let mut suffix = 1;
+        while !used.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{}-{}", slug, suffix);
+        }
+        if let Some(source) = &item.source {
+            targets.insert(absolutize(source, cwd), dir.join(format!("{}.md", name)));
+        }
+        targets.extend(confluence_targets(&item.children, &dir.join(&name), cwd));
+    }
+    targets
+}
+
+/// A page converted to markdown, before frontmatter and links are added.
+struct Page {
+    body: String,
+    title: Option<String>,
+    tags: Vec<String>,
+}
+
+/// Convert a markdown page, replacing its frontmatter and passing the rest
+/// through `convert`.
+fn convert_markdown(content: &str, convert: fn(&str) -> String) -> Page {
+    let (frontmatter, body) = split_frontmatter(content);
+    let tags = match &frontmatter["tags"] {
+        Value::Sequence(tags) => tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Value::String(tag) => Some(tag.clone()),
+                // Docusaurus tags may be objects with a label
+                Value::Mapping(_) => tag["label"].as_str().map(str::to_string),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Page {
+        body: convert(body),
+        title: frontmatter["title"].as_str().map(str::to_string),
+        tags,
+    }
+}
+
+/// Convert a Confluence page's main content.
+fn convert_confluence(content: &str) -> Page {
+    Page {
+        body: html::to_markdown(main_content(content)),
+        title: page_title(content).map(|title| match title.split_once(" : ") {
+            Some((_, page)) => page.to_string(),
+            None => title,
+        }),
+        tags: Vec::new(),
+    }
+}
+
+/// The `main-content` element of a Confluence page, or the whole page.
+fn main_content(page: &str) -> &str {
+    let Some(start) = page.find("id=\"main-content\"") else {
+        return page;
+    };
+    let Some(open_end) = page[start..].find('>').map(|idx| start + idx + 1) else {
+        return page;
+    };
+    let body = &page[open_end..];
+    let tag = Regex::new(r"(?i)<(/?)div\b").unwrap();
+    let mut depth = 1;
+    for caps in tag.captures_iter(body) {
+        depth = if caps[1].is_empty() {
+            depth + 1
+        } else {
+            depth - 1
+        };
+        if depth == 0 {
+            return &body[..caps.get(0).map_or(body.len(), |m| m.start())];
+        }
+    }
+    body
+}
+
+/// Text of a page's `<title>` element.
+fn page_title(page: &str) -> Option<String> {
+    let title = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+    title
+        .captures(page)
+        .map(|caps| html::decode_entities(caps[1].trim()))
+        .filter(|title| !title.is_empty())
+}
+
+/// Split YAML frontmatter from a page, parsed leniently: a page whose
+/// frontmatter isn't valid YAML is imported without it.
+fn split_frontmatter(content: &str) -> (Value, &str) {
+    let lines: Vec<&str> = content.lines().collect();
+    let len = frontmatter_len(&lines);
+    if len == 0 {
+        return (Value::Null, content);
+    }
+    let yaml = lines[1..len - 1].join("\n");
+    let body_start = lines[..len]
+        .iter()
+        .map(|line| line.len() + 1)
+        .sum::<usize>()
+        .min(content.len());
+    (
+        serde_yaml::from_str(&yaml).unwrap_or_default(),
+        &content[body_start..],
+    )
+}
+
+/// Rewrite MkDocs admonitions, `!!! note "Title"` with an indented body,
+/// as GitHub alerts.
+fn convert_mkdocs_admonitions(body: &str) -> String {
+    let admonition =
+        Regex::new(r#"^(\s*)(?:!!!|\?\?\?\+?)\s+([\w-]+)(?:\s+"([^"]*)")?\s*$"#).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    let mut out = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        idx += 1;
+        let caps = match admonition.captures(line) {
+            Some(caps) if !tracker.in_code_block() => caps,
+            _ => {
+                tracker.process_line(line);
+                out.push(line.to_string());
+                continue;
+            }
+        };
+        let indent = &caps[1];
+        let body_indent = format!("{}    ", indent);
+        out.push(format!("{}> [!{}]", indent, alert_kind(&caps[2])));
+        if let Some(title) = caps.get(3).filter(|title| !title.as_str().is_empty()) {
+            out.push(format!("{}> **{}**", indent, title.as_str()));
+        }
+        while idx < lines.len() {
+            let next = lines[idx];
+            if let Some(text) = next.strip_prefix(body_indent.as_str()) {
+                out.push(format!("{}> {}", indent, text).trim_end().to_string());
+            } else if next.trim().is_empty()
+                && lines[idx + 1..]
+                    .iter()
+                    .find(|line| !line.trim().is_empty())
+                    .is_some_and(|line| line.starts_with(body_indent.as_str()))
+            {
+                out.push(format!("{}>", indent));
+            } else {
+                break;
+            }
+            idx += 1;
+        }
+    }
+    let mut converted = out.join("\n");
+    if body.ends_with('\n') {
+        converted.push('\n');
+    }
+    converted
+}
+
+/// The GitHub alert closest to an MkDocs admonition type.
+fn alert_kind(kind: &str) -> &'static str {
+    match kind.to_lowercase().as_str() {
+        "tip" | "hint" | "success" | "check" | "done" => "TIP",
+        "important" => "IMPORTANT",
+        "warning" | "caution" | "attention" => "WARNING",
+        "danger" | "error" | "failure" | "fail" | "missing" | "bug" => "CAUTION",
+        _ => "NOTE",
+    }
+}
+
+/// Drop the MDX `import` and `export` statements at the top level of a
+/// Docusaurus page, which markdown would render as text.
+fn drop_mdx_statements(body: &str) -> String {
+    let statement = Regex::new(r"^(?:import|export)\s").unwrap();
+    let mut tracker = CodeBlockTracker::new();
+    let mut out: Vec<&str> = Vec::new();
+    for line in body.lines() {
+        let in_code = tracker.in_code_block();
+        let fence = tracker.process_line(line);
+        if in_code || fence || !statement.is_match(line) {
+            out.push(line);
+        }
+    }
+    let mut converted = out.join("\n");
+    if body.ends_with('\n') {
+        converted.push('\n');
+    }
+    converted
+}
+
+/// Point links to imported pages at their new paths.
+fn rewrite_links(
+    body: &str,
+    source: &Path,
+    target: &Path,
+    targets: &BTreeMap<PathBuf, PathBuf>,
+    cwd: &Path,
+) -> String {
+    let link = Regex::new(r#"\]\(([^)\s#?]+)([#?][^)\s]*)?(\s+"[^"]*")?\)"#).unwrap();
+    let source_dir = source.parent().unwrap_or_else(|| Path::new(""));
+    let target_dir = target.parent().unwrap_or_else(|| Path::new(""));
+    link.replace_all(body, |caps: &Captures| {
+        let href = &caps[1];
+        if href.contains(':') || href.starts_with('/') {
+            return caps[0].to_string();
+        }
+        let linked = absolutize(&source_dir.join(href), cwd);
+        let found = [
+            linked.clone(),
+            linked.with_extension("md"),
+            linked.with_extension("mdx"),
+        ]
+        .into_iter()
+        .find_map(|candidate| targets.get(&candidate));
+        match found {
+            Some(linked_target) => {
+                let relative = relative_path(linked_target, target_dir);
+                format!(
+                    "]({}{}{})",
+                    relative.display(),
+                    caps.get(2).map_or("", |m| m.as_str()),
+                    caps.get(3).map_or("", |m| m.as_str())
+                )
+            }
+            None => caps[0].to_string(),
+        }
+    })
+    .into_owned()
+}
+
+/// Frontmatter for an imported page. Pages that don't fit PAVED yet are
+/// drafts, so `pave check` only warns about them.
+fn frontmatter(fits_paved: bool, tags: &[String]) -> String {
+    let state = if fits_paved { "published" } else { "draft" };
+    let mut frontmatter = format!("---\npave:\n  state: {}\n", state);
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|tag| {
+                serde_yaml::to_string(tag)
+                    .map_or_else(|_| tag.clone(), |yaml| yaml.trim().to_string())
+            })
+            .collect();
+        frontmatter.push_str(&format!("  tags: [{}]\n", tags.join(", ")));
+    }
+    frontmatter.push_str("---\n");
+    frontmatter
+}
+
+/// Text of the first `# ` heading outside code blocks.
+fn heading_title(body: &str) -> Option<String> {
+    ParsedDoc::parse_content(PathBuf::new(), body)
+        .ok()
+        .and_then(|doc| doc.title)
+}
+
+/// A title made from a file name: `getting-started.md` is "Getting started".
+fn file_title(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().replace(['-', '_'], " "))
+        .unwrap_or_default();
+    let mut chars = stem.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Untitled".to_string(),
+    }
+}
+
+/// A file name made from a title: lowercase words joined by `-`.
+fn slugify(title: &str) -> String {
+    let words: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        "page".to_string()
+    } else {
+        words.join("-")
+    }
+}
+
+/// The index: every imported page, nested as the original navigation was.
+fn render_index(
+    site: &Site,
+    from: ImportSource,
+    targets: &BTreeMap<PathBuf, PathBuf>,
+    titles: &BTreeMap<PathBuf, String>,
+    cwd: &Path,
+) -> String {
+    fn entries(
+        items: &[NavItem],
+        depth: usize,
+        targets: &BTreeMap<PathBuf, PathBuf>,
+        titles: &BTreeMap<PathBuf, String>,
+        cwd: &Path,
+        out: &mut String,
+    ) {
+        for item in items {
+            let indent = "  ".repeat(depth);
+            match &item.source {
+                Some(source) if !source.exists() => {}
+                Some(source) => {
+                    let target = &targets[&absolutize(source, cwd)];
+                    out.push_str(&format!(
+                        "{}- [{}](./{})\n",
+                        indent,
+                        titles[target],
+                        target.display()
+                    ));
+                }
+                None => out.push_str(&format!(
+                    "{}- {}\n",
+                    indent,
+                    item.title.as_deref().unwrap_or("Pages")
+                )),
+            }
+            entries(&item.children, depth + 1, targets, titles, cwd, out);
+        }
+    }
+
+    let mut index = format!(
+        "# {}\n\nPages imported from {} with `pave import`, in the order of its navigation.\n\n",
+        site.name.as_deref().unwrap_or("Documentation"),
+        source_name(from)
+    );
+    entries(&site.nav, 0, targets, titles, cwd, &mut index);
+    index
+}
+
+/// Name of a doc system, for messages.
+fn source_name(from: ImportSource) -> &'static str {
+    match from {
+        ImportSource::Mkdocs => "MkDocs",
+        ImportSource::Docusaurus => "Docusaurus",
+        ImportSource::Confluence => "Confluence",
+    }
+}
+
+fn output_text(results: &ImportResults, from: ImportSource) {
+    let verb = if results.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    println!(
+        "{} {} page(s) from {} into {} ({} already fit PAVED)",
+        verb,
+        results.imported,
+        source_name(from),
+        results.output.display(),
+        results.fits_paved
+    );
+
+    let imported = || results.documents.iter().filter(|doc| doc.skipped.is_none());
+    let fits: Vec<&ImportedDoc> = imported().filter(|doc| doc.fits_paved).collect();
+    if !fits.is_empty() {
+        println!();
+        println!("Already fit PAVED:");
+        for doc in fits {
+            println!("  {}", doc.path.display());
+        }
+    }
+    let drafts: Vec<&ImportedDoc> = imported().filter(|doc| !doc.fits_paved).collect();
+    if !drafts.is_empty() {
+        println!();
+        println!("Imported as drafts, missing PAVED sections:");
+        for doc in &drafts {
+            println!(
+                "  {}: {}",
+                doc.path.display(),
+                doc.missing_sections.join(", ")
+            );
+        }
+    }
+    let skipped: Vec<&ImportedDoc> = results
+        .documents
+        .iter()
+        .filter(|doc| doc.skipped.is_some())
+        .collect();
+    if !skipped.is_empty() {
+        println!();
+        println!("Skipped:");
+        for doc in skipped {
+            println!(
+                "  {}: {}",
+                doc.path.display(),
+                doc.skipped.as_deref().unwrap_or_default()
+            );
+        }
+    }
+
+    println!();
+    match &results.index {
+        Some(index) if results.dry_run => println!("Would write index {}", index.display()),
+        Some(index) => println!("Wrote index {}", index.display()),
+        None => println!("Kept the existing index.md; run `pave index --update` to refresh it"),
+    }
+    if !drafts.is_empty() {
+        println!("Next: run `pave adopt --migrate` to add the missing sections.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_file(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn imports_mkdocs_in_nav_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let site = temp_dir.path().join("site");
+        write_file(
+            &site,
+            "mkdocs.yml",
+            "site_name: Acme\nnav:\n  - Home: index.md\n  - Guides:\n    - guides/setup.md\n  \
+             - GitHub: https://github.com/acme\n",
+        );
+        write_file(
+            &site,
+            "docs/index.md",
+            "---\ntags: [intro]\n---\n# Welcome\n\nSee [setup](guides/setup.md#install).\n\n\
+             !!! warning \"Careful\"\n    Read this first.\n",
+        );
+        write_file(
+            &site,
+            "docs/guides/setup.md",
+            "# Setup\n\n## Purpose\nInstall it.\n\n## Verification\n```bash\ntrue\n```\n\n\
+             ## Examples\n```bash\nacme --help\n```\n\n[Home](../index.md)\n",
+        );
+        write_file(&site, "docs/extra.md", "Unlisted page.\n");
+
+        let out = temp_dir.path().join("docs");
+        let results = import(
+            &read_mkdocs(&site).unwrap(),
+            ImportSource::Mkdocs,
+            &out,
+            false,
+            false,
+            temp_dir.path(),
+        )
+        .unwrap();
+
+        let paths: Vec<(&str, bool)> = results
+            .documents
+            .iter()
+            .map(|doc| (doc.path.to_str().unwrap(), doc.fits_paved))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                ("home.md", false),
+                ("guides/setup.md", true),
+                ("extra.md", false)
+            ]
+        );
+        assert_eq!(results.fits_paved, 1);
+
+        let home = fs::read_to_string(out.join("home.md")).unwrap();
+        assert!(home.starts_with("---\npave:\n  state: draft\n  tags: [intro]\n---\n# Welcome\n"));
+        assert!(home.contains("[setup](guides/setup.md#install)"));
+        assert!(home.contains("> [!WARNING]\n> **Careful**\n> Read this first.\n"));
+        let setup = fs::read_to_string(out.join("guides/setup.md")).unwrap();
+        assert!(setup.contains("state: published"));
+        assert!(setup.contains("[Home](../home.md)"));
+        assert!(
+            fs::read_to_string(out.join("extra.md"))
+                .unwrap()
+                .contains("# Extra\n\nUnlisted page.")
+        );
+        assert_eq!(
+            fs::read_to_string(out.join("index.md")).unwrap(),
+            "# Acme\n\nPages imported from MkDocs with `pave import`, in the order of its \
+             navigation.\n\n- [Home](./home.md)\n- Guides\n  - [Setup](./guides/setup.md)\n\
+             - [Extra](./extra.md)\n"
+        );
+
+        // Existing files are kept
+        let again = import(
+            &read_mkdocs(&site).unwrap(),
+            ImportSource::Mkdocs,
+            &out,
+            false,
+            false,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(again.imported, 0);
+        assert!(again.index.is_none());
+    }
+
+    #[test]
+    fn orders_docusaurus_by_sidebar_position() {
+        let temp_dir = TempDir::new().unwrap();
+        let site = temp_dir.path();
+        write_file(
+            site,
+            "docs/intro.mdx",
+            "---\nsidebar_position: 2\ntitle: Introduction\n---\nimport Tabs from '@theme/Tabs';\n\n\
+             Read [the API](api/auth).\n",
+        );
+        write_file(
+            site,
+            "docs/quickstart.md",
+            "---\nsidebar_position: 1\n---\n# Quickstart\n",
+        );
+        write_file(
+            site,
+            "docs/api/_category_.json",
+            "{\"label\": \"API\", \"position\": 3}",
+        );
+        write_file(site, "docs/api/auth.md", "# Auth\n");
+        write_file(site, "docs/_partial.md", "Shared text.\n");
+
+        let nav = read_docusaurus(site).unwrap().nav;
+        let titles: Vec<Option<&str>> = nav.iter().map(|item| item.title.as_deref()).collect();
+        assert_eq!(titles, [None, Some("Introduction"), Some("API")]);
+
+        let out = site.join("out");
+        let results = import(
+            &read_docusaurus(site).unwrap(),
+            ImportSource::Docusaurus,
+            &out,
+            true,
+            false,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(results.imported, 3);
+        assert_eq!(results.documents[1].path, PathBuf::from("intro.md"));
+        assert!(!out.exists());
+
+        import(
+            &read_docusaurus(site).unwrap(),
+            ImportSource::Docusaurus,
+            &out,
+            false,
+            false,
+            temp_dir.path(),
+        )
+        .unwrap();
+        let intro = fs::read_to_string(out.join("intro.md")).unwrap();
+        assert!(intro.contains("# Introduction\n\nRead [the API](api/auth.md).\n"));
+        assert!(!intro.contains("import Tabs"));
+    }
+
+    #[test]
+    fn imports_confluence_page_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let export = temp_dir.path().join("export");
+        write_file(
+            &export,
+            "index.html",
+            "<html><head><title>Ops Space</title></head><body>\
+             <h2>Available Pages:</h2><ul><li><a href=\"Runbooks_1.html\">Runbooks</a>\
+             <ul><li><a href=\"Deploy_2.html\">Deploy &amp; Rollback</a></li></ul></li></ul>\
+             </body></html>",
+        );
+        write_file(
+            &export,
+            "Runbooks_1.html",
+            "<title>Ops : Runbooks</title><div id=\"main-content\" class=\"wiki-content\">\
+             <p>See <a href=\"Deploy_2.html\">deploying</a>.</p></div>\
+             <div class=\"pageSection\">Attachments</div>",
+        );
+        write_file(
+            &export,
+            "Deploy_2.html",
+            "<div id=\"main-content\"><h2>Steps</h2><ol><li>Ship</li></ol></div>",
+        );
+
+        let out = temp_dir.path().join("docs");
+        let results = import(
+            &read_confluence(&export).unwrap(),
+            ImportSource::Confluence,
+            &out,
+            false,
+            false,
+            temp_dir.path(),
+        )
+        .unwrap();
+        let paths: Vec<&str> = results
+            .documents
+            .iter()
+            .map(|doc| doc.path.to_str().unwrap())
+            .collect();
+        assert_eq!(paths, ["runbooks.md", "runbooks/deploy-rollback.md"]);
+
+        assert_eq!(
+            fs::read_to_string(out.join("runbooks.md")).unwrap(),
+            "---\npave:\n  state: draft\n---\n# Runbooks\n\n\
+             See [deploying](runbooks/deploy-rollback.md).\n"
+        );
+        let deploy = fs::read_to_string(out.join("runbooks/deploy-rollback.md")).unwrap();
+        assert!(deploy.contains("# Deploy & Rollback\n\n## Steps\n\n1. Ship\n"));
+        assert!(
+            fs::read_to_string(out.join("index.md"))
+                .unwrap()
+                .starts_with("# Ops Space\n")
+        );
+    }
+}
@@ -4,18 +4,32 @@
 //! document that serves as a map to all PAVED documentation.
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::cli::IndexFormat;
+use crate::commands::coverage::extract_paths_patterns;
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::ignore::PaveIgnore;
+use crate::parser::{CodeBlockTracker, DocState, ParsedDoc};
+use crate::rules::{is_api_path, is_postmortem_path};
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
 
 /// Document type detected from content or path.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum DocType {
     Component,
     Runbook,
     Adr,
+    Postmortem,
+    Api,
     Other,
 }
 
@@ -26,13 +40,27 @@ impl DocType {
             DocType::Component => "Components",
             DocType::Runbook => "Runbooks",
             DocType::Adr => "Architecture Decisions",
+            DocType::Postmortem => "Postmortems",
+            DocType::Api => "API Reference",
             DocType::Other => "Other Documents",
         }
     }
+
+    /// Anchor of the type's section in the index.
+    fn anchor(&self) -> &'static str {
+        match self {
+            DocType::Component => "components",
+            DocType::Runbook => "runbooks",
+            DocType::Adr => "architecture-decisions",
+            DocType::Postmortem => "postmortems",
+            DocType::Api => "api-reference",
+            DocType::Other => "other-documents",
+        }
+    }
 }
 
 /// Parsed information about a documentation file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DocInfo {
     /// Relative path from docs root to the file.
     pub path: PathBuf,
@@ -42,6 +70,64 @@ pub struct DocInfo {
     pub purpose: Option<String>,
     /// Detected document type.
     pub doc_type: DocType,
+    /// Lifecycle state from `pave.state` frontmatter.
+    pub state: DocState,
+    /// Owner from `pave.owner` frontmatter.
+    pub owner: Option<String>,
+    /// Tags from `pave.tags` frontmatter.
+    pub tags: Vec<String>,
+    /// Code paths the document covers, from its Paths section and
+    /// `pave.paths` frontmatter.
+    pub paths: Vec<String>,
+}
+
+impl DocInfo {
+    /// Markdown link to the document, labelled with its state unless published.
+    fn link(&self) -> String {
+        let link = format!("[{}](./{})", self.title, self.path.display());
+        if self.state == DocState::Published {
+            link
+        } else {
+            format!("{} *({})*", link, self.state)
+        }
+    }
+
+    /// List entry with the purpose, followed by a line of metadata if the
+    /// document has any.
+    fn entry(&self) -> String {
+        let mut entry = format!("- {}", self.link());
+        if let Some(purpose) = &self.purpose {
+            entry.push_str(&format!(": {}", purpose));
+        }
+        entry.push('\n');
+
+        let mut details = Vec::new();
+        if let Some(owner) = &self.owner {
+            details.push(format!("Owner: {}", owner));
+        }
+        if !self.tags.is_empty() {
+            details.push(format!("Tags: {}", self.tags.join(", ")));
+        }
+        if !self.paths.is_empty() {
+            let paths: Vec<String> = self.paths.iter().map(|p| format!("`{}`", p)).collect();
+            details.push(format!("Covers: {}", paths.join(", ")));
+        }
+        if !details.is_empty() {
+            entry.push_str(&format!("  - {}\n", details.join(" · ")));
+        }
+        entry
+    }
+}
+
+/// The index as printed by `pave index --format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct IndexResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Root directory of documentation.
+    pub docs_root: PathBuf,
+    /// Indexed documents, in index order.
+    pub documents: Vec<DocInfo>,
 }
 
 /// Custom section marker for update mode.
@@ -49,29 +135,59 @@ const CUSTOM_SECTION_START: &str = "<!-- CUSTOM CONTENT START -->";
 const CUSTOM_SECTION_END: &str = "<!-- CUSTOM CONTENT END -->";
 
 /// Run the index command.
-pub fn run(output: &Path, update: bool) -> Result<()> {
+///
+/// With JSON output, the index is printed instead of written to `output`.
+/// With `check`, the index is compared to `output` instead of written, and
+/// the command fails if it's out of date.
+pub fn run(
+    output: &Path,
+    update: bool,
+    check: bool,
+    format: IndexFormat,
+    workdir: &Workdir,
+) -> Result<()> {
     // Find and load config
-    let config = load_config()?;
-    let docs_root = &config.docs.root;
+    let config = load_config(workdir)?;
+    let docs_root = &workdir.resolve(&config.docs.root);
+    // Paths are shown as given
+    let shown = output;
+    let output = &workdir.resolve(output);
 
     // Check if docs directory exists
     if !docs_root.exists() {
         anyhow::bail!(
             "documentation directory '{}' does not exist",
-            docs_root.display()
+            config.docs.root.display()
         );
     }
 
     // Scan for markdown files
-    let docs = scan_docs(docs_root)?;
+    let ignore = load_ignore(&workdir.dir, &config)?;
+    let mut docs = scan_docs(docs_root, &ignore)?;
+    // The index doesn't list itself
+    docs.retain(|doc| docs_root.join(&doc.path) != *output);
+
+    if matches!(format, IndexFormat::Json) {
+        docs.sort_by(|a, b| a.path.cmp(&b.path));
+        let results = IndexResults {
+            schema_version: SCHEMA_VERSION,
+            docs_root: config.docs.root.clone(),
+            documents: docs,
+        };
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
 
     if docs.is_empty() {
-        println!("No documentation files found in '{}'", docs_root.display());
+        println!(
+            "No documentation files found in '{}'",
+            config.docs.root.display()
+        );
         return Ok(());
     }
 
-    // Load existing custom content if updating
-    let custom_content = if update && output.exists() {
+    // Load existing custom content if updating or checking
+    let custom_content = if (update || check) && output.exists() {
         extract_custom_content(output)?
     } else {
         None
@@ -80,6 +196,23 @@ pub fn run(output: &Path, update: bool) -> Result<()> {
     // Generate the index document
     let index_content = generate_index(&docs, custom_content.as_deref())?;
 
+    if check {
+        let existing = fs::read_to_string(output).with_context(|| {
+            format!(
+                "failed to read index {} (run `pave index` to create it)",
+                shown.display()
+            )
+        })?;
+        if without_timestamp(&existing) != without_timestamp(&index_content) {
+            anyhow::bail!(Violations(format!(
+                "{} is out of date; run `pave index --update` to regenerate it",
+                shown.display()
+            )));
+        }
+        println!("{} is up to date", shown.display());
+        return Ok(());
+    }
+
     // Ensure parent directory exists
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)
@@ -88,72 +221,74 @@ pub fn run(output: &Path, update: bool) -> Result<()> {
 
     // Write the index file
     fs::write(output, &index_content)
-        .with_context(|| format!("failed to write index file: {}", output.display()))?;
+        .with_context(|| format!("failed to write index file: {}", shown.display()))?;
 
-    println!("Generated index at: {}", output.display());
+    println!("Generated index at: {}", shown.display());
     println!("  - {} documents indexed", docs.len());
 
     Ok(())
 }
 
-/// Load pave configuration from current directory or parents.
-fn load_config() -> Result<PaveConfig> {
-    let cwd = std::env::current_dir().context("failed to get current directory")?;
-
-    // Search for config file in current directory and parents
-    let mut search_path = cwd.as_path();
-    loop {
-        let config_path = search_path.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return PaveConfig::load(&config_path);
-        }
+/// Index content without its "Last updated" footer, for comparing indexes
+/// generated on different days.
+fn without_timestamp(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !line.starts_with("*Generated by pave. Last updated:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-        match search_path.parent() {
-            Some(parent) => search_path = parent,
-            None => break,
-        }
+/// Regenerate the index at `output` after docs moved, keeping its custom
+/// content. An index pave didn't generate is left alone; returns whether
+/// it was rewritten.
+pub(crate) fn refresh(output: &Path, docs_root: &Path, ignore: &PaveIgnore) -> Result<bool> {
+    let Ok(existing) = fs::read_to_string(output) else {
+        return Ok(false);
+    };
+    if !existing
+        .lines()
+        .any(|line| line.starts_with("*Generated by pave. Last updated:"))
+    {
+        return Ok(false);
     }
-
-    // No config found, use defaults
-    Ok(PaveConfig::default())
+    write_index(output, docs_root, ignore)?;
+    Ok(true)
 }
 
-/// Scan the docs directory for markdown files.
-fn scan_docs(docs_root: &Path) -> Result<Vec<DocInfo>> {
-    let mut docs = Vec::new();
-    scan_docs_recursive(docs_root, docs_root, &mut docs)?;
-    Ok(docs)
+/// Write the index of the docs under `docs_root` to `output`, keeping the
+/// custom content of an existing index.
+pub(crate) fn write_index(output: &Path, docs_root: &Path, ignore: &PaveIgnore) -> Result<()> {
+    let mut docs = scan_docs(docs_root, ignore)?;
+    docs.retain(|doc| docs_root.join(&doc.path) != output);
+    let custom_content = if output.exists() {
+        extract_custom_content(output)?
+    } else {
+        None
+    };
+    let content = generate_index(&docs, custom_content.as_deref())?;
+    fs::write(output, content)
+        .with_context(|| format!("failed to write index file: {}", output.display()))
 }
 
-/// Recursively scan directory for markdown files.
-fn scan_docs_recursive(docs_root: &Path, current: &Path, docs: &mut Vec<DocInfo>) -> Result<()> {
-    let entries = fs::read_dir(current)
-        .with_context(|| format!("failed to read directory: {}", current.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip the templates directory - these are scaffolds, not actual documentation
-            if path.file_name().is_some_and(|n| n == "templates") {
-                continue;
-            }
-            scan_docs_recursive(docs_root, &path, docs)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            // Skip the index file itself
-            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            if file_name == "index.md" {
-                continue;
-            }
+/// Load pave configuration from the working directory or its parents.
+fn load_config(workdir: &Workdir) -> Result<PaveConfig> {
+    match workdir.locate_config()? {
+        Some(config_path) => PaveConfig::load(config_path),
+        // No config found, use defaults
+        None => Ok(PaveConfig::default()),
+    }
+}
 
-            if let Some(doc_info) = parse_doc(&path, docs_root)? {
-                docs.push(doc_info);
-            }
+/// Scan the docs directory for markdown files, skipping ignored paths.
+pub(crate) fn scan_docs(docs_root: &Path, ignore: &PaveIgnore) -> Result<Vec<DocInfo>> {
+    let mut docs = Vec::new();
+    for path in find_docs(docs_root, ignore)? {
+        if let Some(doc_info) = parse_doc(&path, docs_root)? {
+            docs.push(doc_info);
         }
     }
-
-    Ok(())
+    Ok(docs)
 }
 
 /// Parse a markdown document to extract metadata.
@@ -177,11 +312,27 @@ fn parse_doc(path: &Path, docs_root: &Path) -> Result<Option<DocInfo>> {
     // Detect document type
     let doc_type = detect_doc_type(&relative_path, &content);
 
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    let frontmatter = doc.frontmatter.clone().unwrap_or_default();
+    let mut paths = extract_paths_patterns(&content);
+    for pattern in frontmatter.paths {
+        if !paths.contains(&pattern) {
+            paths.push(pattern);
+        }
+    }
+
     Ok(Some(DocInfo {
         path: relative_path,
         title,
         purpose,
         doc_type,
+        state: doc.state(),
+        owner: frontmatter
+            .owner
+            .map(|owner| owner.trim().to_string())
+            .filter(|owner| !owner.is_empty()),
+        tags: frontmatter.tags,
+        paths,
     }))
 }
 
@@ -200,10 +351,17 @@ fn extract_title(content: &str) -> Option<String> {
 fn extract_purpose(content: &str) -> Option<String> {
     let mut in_purpose = false;
     let mut purpose_lines = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
 
     for line in content.lines() {
         let trimmed = line.trim();
 
+        // Headings and prose inside code blocks aren't the document's own
+        tracker.process_line(line);
+        if tracker.in_code_block() || trimmed.starts_with("```") {
+            continue;
+        }
+
         // Check if we're entering the Purpose section
         if trimmed.starts_with("## Purpose") || trimmed.starts_with("## What") {
             in_purpose = true;
@@ -255,13 +413,33 @@ fn detect_doc_type(relative_path: &Path, content: &str) -> DocType {
     if path_str.contains("runbook") {
         return DocType::Runbook;
     }
+    if is_postmortem_path(&path_str) {
+        return DocType::Postmortem;
+    }
     if path_str.contains("adr") || path_str.contains("decision") {
         return DocType::Adr;
     }
+    if is_api_path(relative_path) {
+        return DocType::Api;
+    }
 
     // Check content patterns
     let content_lower = content.to_lowercase();
 
+    // Postmortems walk through a timeline of the incident and its fallout
+    if content_lower.contains("## timeline")
+        && (content_lower.contains("## impact")
+            || content_lower.contains("## action items")
+            || content_lower.contains("## root cause"))
+    {
+        return DocType::Postmortem;
+    }
+
+    // API references list their endpoints
+    if content_lower.contains("## endpoints") {
+        return DocType::Api;
+    }
+
     // ADRs typically have a Status section
     if content_lower.contains("## status")
         && (content_lower.contains("accepted")
@@ -320,7 +498,7 @@ fn generate_index(docs: &[DocInfo], custom_content: Option<&str>) -> Result<Stri
 
     // Sort documents within each group by title
     for docs_in_group in grouped.values_mut() {
-        docs_in_group.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+        docs_in_group.sort_by_key(|d| d.title.to_lowercase());
     }
 
     // Identify top-level docs for Quick Links
@@ -330,64 +508,58 @@ fn generate_index(docs: &[DocInfo], custom_content: Option<&str>) -> Result<Stri
         .map(|d| &d.path)
         .collect();
 
-    // Generate Quick Links section for top-level docs
-    if !top_level_paths.is_empty() {
-        output.push_str("## Quick Links\n\n");
-        let mut top_level: Vec<_> = docs
-            .iter()
-            .filter(|d| top_level_paths.contains(&d.path))
-            .collect();
-        top_level.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
-        for doc in top_level {
-            output.push_str(&format!("- [{}](./{})\n", doc.title, doc.path.display()));
-        }
-        output.push('\n');
-    }
-
-    // Generate sections for each document type (in a specific order)
-    // Exclude top-level docs that are already in Quick Links
+    // Sections for each document type (in a specific order), leaving out
+    // top-level docs, which are in Quick Links
     let type_order = [
         DocType::Component,
         DocType::Runbook,
         DocType::Adr,
+        DocType::Postmortem,
+        DocType::Api,
         DocType::Other,
     ];
-
-    for doc_type in type_order {
-        if let Some(docs_in_group) = grouped.get(&doc_type) {
-            // Filter out top-level docs already shown in Quick Links
-            let nested_docs: Vec<_> = docs_in_group
+    let sections: Vec<(DocType, Vec<&DocInfo>)> = type_order
+        .into_iter()
+        .filter_map(|doc_type| {
+            let nested_docs: Vec<&DocInfo> = grouped
+                .get(&doc_type)?
                 .iter()
+                .copied()
                 .filter(|d| !top_level_paths.contains(&d.path))
                 .collect();
+            (!nested_docs.is_empty()).then_some((doc_type, nested_docs))
+        })
+        .collect();
 
-            if nested_docs.is_empty() {
-                continue;
-            }
+    // Contents, linking to each section's anchor
+    if sections.len() > 1 {
+        let contents: Vec<String> = sections
+            .iter()
+            .map(|(doc_type, _)| format!("[{}](#{})", doc_type.display_name(), doc_type.anchor()))
+            .collect();
+        output.push_str(&format!("**Contents:** {}\n\n", contents.join(" · ")));
+    }
 
-            output.push_str(&format!("## {}\n\n", doc_type.display_name()));
-
-            // Use table format for components, list for others
-            if doc_type == DocType::Component {
-                output.push_str("| Component | Purpose |\n");
-                output.push_str("|-----------|----------|\n");
-                for doc in nested_docs {
-                    let purpose = doc.purpose.as_deref().unwrap_or("-");
-                    output.push_str(&format!(
-                        "| [{}](./{}) | {} |\n",
-                        doc.title,
-                        doc.path.display(),
-                        purpose
-                    ));
-                }
-            } else {
-                for doc in nested_docs {
-                    output.push_str(&format!("- [{}](./{})\n", doc.title, doc.path.display()));
-                }
-            }
+    // Generate Quick Links section for top-level docs
+    if !top_level_paths.is_empty() {
+        output.push_str("## Quick Links\n\n");
+        let mut top_level: Vec<_> = docs
+            .iter()
+            .filter(|d| top_level_paths.contains(&d.path))
+            .collect();
+        top_level.sort_by_key(|d| d.title.to_lowercase());
+        for doc in top_level {
+            output.push_str(&doc.entry());
+        }
+        output.push('\n');
+    }
 
-            output.push('\n');
+    for (doc_type, nested_docs) in sections {
+        output.push_str(&format!("## {}\n\n", doc_type.display_name()));
+        for doc in nested_docs {
+            output.push_str(&doc.entry());
         }
+        output.push('\n');
     }
 
     // Custom content section
@@ -446,6 +618,9 @@ mod tests {
 
         let content = "# Title\n\nNo purpose section";
         assert_eq!(extract_purpose(content), None);
+
+        let content = "# Title\n\n```md\n## Purpose\nTemplate text.\n```\n";
+        assert_eq!(extract_purpose(content), None);
     }
 
     #[test]
@@ -459,6 +634,12 @@ mod tests {
         let path = PathBuf::from("adrs/001-use-rust.md");
         assert_eq!(detect_doc_type(&path, ""), DocType::Adr);
 
+        let path = PathBuf::from("postmortems/2024-03-db-outage.md");
+        assert_eq!(detect_doc_type(&path, ""), DocType::Postmortem);
+
+        let path = PathBuf::from("api/orders.md");
+        assert_eq!(detect_doc_type(&path, ""), DocType::Api);
+
         let path = PathBuf::from("random.md");
         assert_eq!(detect_doc_type(&path, ""), DocType::Other);
     }
@@ -503,18 +684,30 @@ mod tests {
                 title: "Auth Service".to_string(),
                 purpose: Some("Handles user authentication.".to_string()),
                 doc_type: DocType::Component,
+                state: DocState::Published,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
             },
             DocInfo {
                 path: PathBuf::from("runbooks/deploy.md"),
                 title: "Deploy to Production".to_string(),
                 purpose: None,
                 doc_type: DocType::Runbook,
+                state: DocState::Published,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
             },
             DocInfo {
                 path: PathBuf::from("adrs/001-use-rust.md"),
                 title: "ADR-001: Use Rust".to_string(),
                 purpose: None,
                 doc_type: DocType::Adr,
+                state: DocState::Published,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
             },
         ];
 
@@ -538,6 +731,10 @@ mod tests {
             title: "README".to_string(),
             purpose: None,
             doc_type: DocType::Other,
+            state: DocState::Published,
+            owner: None,
+            tags: Vec::new(),
+            paths: Vec::new(),
         }];
 
         let custom = "My preserved notes";
@@ -570,7 +767,7 @@ mod tests {
             "# Deploy Guide\n\n## When to Use\n\nWhen deploying...\n\n## Steps\n\n1. ...",
         );
 
-        let docs = scan_docs(docs_root).unwrap();
+        let docs = scan_docs(docs_root, &PaveIgnore::default()).unwrap();
 
         assert_eq!(docs.len(), 3);
 
@@ -590,12 +787,20 @@ mod tests {
                 title: "Auth".to_string(),
                 purpose: None,
                 doc_type: DocType::Component,
+                state: DocState::Published,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
             },
             DocInfo {
                 path: PathBuf::from("deep/nested/doc.md"),
                 title: "Nested".to_string(),
                 purpose: None,
                 doc_type: DocType::Other,
+                state: DocState::Published,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
             },
         ];
 
@@ -605,4 +810,92 @@ mod tests {
         assert!(result.contains("(./components/auth.md)"));
         assert!(result.contains("(./deep/nested/doc.md)"));
     }
+
+    #[test]
+    fn test_entries_carry_metadata_and_sections_have_anchors() {
+        let dir = TempDir::new().unwrap();
+        create_test_doc(
+            dir.path(),
+            "components/auth.md",
+            "---\npave:\n  owner: '@alice'\n  tags: [auth, security]\n  paths: [src/session.rs]\n---\n\
+             # Auth\n\n## Purpose\n\nLogs users in. Also out.\n\n## Paths\n\n- `src/auth/**`\n",
+        );
+        create_test_doc(
+            dir.path(),
+            "runbooks/deploy.md",
+            "# Deploy\n\n## Steps\n\n1. Ship it\n",
+        );
+
+        let mut docs = scan_docs(dir.path(), &PaveIgnore::default()).unwrap();
+        docs.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(docs[0].owner.as_deref(), Some("@alice"));
+        assert_eq!(docs[0].tags, vec!["auth", "security"]);
+        assert_eq!(docs[0].paths, vec!["src/auth/**", "src/session.rs"]);
+
+        let result = generate_index(&docs, None).unwrap();
+        assert!(
+            result.contains("**Contents:** [Components](#components) · [Runbooks](#runbooks)\n")
+        );
+        assert!(result.contains(
+            "- [Auth](./components/auth.md): Logs users in.\n  \
+             - Owner: @alice · Tags: auth, security · Covers: `src/auth/**`, `src/session.rs`\n"
+        ));
+        assert!(result.contains("## Runbooks\n\n- [Deploy](./runbooks/deploy.md)\n\n"));
+    }
+
+    #[test]
+    fn test_without_timestamp_ignores_generation_date() {
+        let docs = vec![DocInfo {
+            path: PathBuf::from("guide.md"),
+            title: "Guide".to_string(),
+            purpose: None,
+            doc_type: DocType::Other,
+            state: DocState::Published,
+            owner: None,
+            tags: Vec::new(),
+            paths: Vec::new(),
+        }];
+        let current = generate_index(&docs, None).unwrap();
+        let older = current.replace(
+            &chrono::Local::now().format("%Y-%m-%d").to_string(),
+            "2020-01-01",
+        );
+
+        assert_eq!(without_timestamp(&current), without_timestamp(&older));
+        assert_ne!(
+            without_timestamp(&current),
+            without_timestamp(&current.replace("Guide", "Manual"))
+        );
+    }
+
+    #[test]
+    fn test_generate_index_labels_unpublished_docs() {
+        let docs = vec![
+            DocInfo {
+                path: PathBuf::from("components/auth.md"),
+                title: "Auth".to_string(),
+                purpose: Some("Logs users in.".to_string()),
+                doc_type: DocType::Component,
+                state: DocState::Draft,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
+            },
+            DocInfo {
+                path: PathBuf::from("runbooks/deploy.md"),
+                title: "Deploy".to_string(),
+                purpose: None,
+                doc_type: DocType::Runbook,
+                state: DocState::Deprecated,
+                owner: None,
+                tags: Vec::new(),
+                paths: Vec::new(),
+            },
+        ];
+
+        let result = generate_index(&docs, None).unwrap();
+
+        assert!(result.contains("- [Auth](./components/auth.md) *(draft)*: Logs users in.\n"));
+        assert!(result.contains("- [Deploy](./runbooks/deploy.md) *(deprecated)*\n"));
+    }
 }
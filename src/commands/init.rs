@@ -4,8 +4,10 @@ use anyhow::{Context, Result, bail};
 use std::fs;
 use std::path::Path;
 
-use crate::cli::HookType;
+use crate::cli::{CiProvider, HookType};
+use crate::commands::ci::{self, CiOptions};
 use crate::commands::hooks;
+use crate::secrets::SECRETS_FILE;
 use crate::templates::{TemplateType, get_template};
 
 /// Default content for the .pave.toml configuration file.
@@ -14,6 +16,9 @@ fn default_config(docs_root: &str) -> String {
         r#"# Paver configuration file
 # See https://github.com/tessro/pave for documentation
 
+[pave]
+version = "0.1"
+
 [docs]
 # Root directory for documentation
 root = "{docs_root}"
@@ -41,6 +46,8 @@ pub struct InitArgs {
     pub skip_hooks: bool,
     /// Overwrite existing files
     pub force: bool,
+    /// Also generate CI configuration for this provider
+    pub ci: Option<CiProvider>,
     /// Working directory (for testing; uses current dir if None)
     pub working_dir: Option<std::path::PathBuf>,
 }
@@ -51,6 +58,7 @@ impl Default for InitArgs {
             docs_root: "docs".to_string(),
             skip_hooks: false,
             force: false,
+            ci: None,
             working_dir: None,
         }
     }
@@ -100,16 +108,35 @@ pub fn run(args: InitArgs) -> Result<()> {
         }
     }
 
+    // Caches and histories under .pave/ change on every run, and the
+    // secrets file must never be committed
+    ignore_local_files(&base)?;
+
     // Install git pre-commit hook by default (unless skipped)
     if !args.skip_hooks {
         install_git_hooks(&base)?;
     }
 
+    let ci_output = match args.ci {
+        Some(provider) => {
+            let options = CiOptions {
+                force: args.force,
+                ..Default::default()
+            };
+            Some(ci::write(&base, provider, &options)?)
+        }
+        None => None,
+    };
+
     // Print success message
     println!("Initialized PAVED documentation in {}/", args.docs_root);
     println!();
     println!("Created:");
     println!("  .pave.toml              - Configuration file");
+    println!(
+        "  .gitignore              - Ignores .pave/ and {}",
+        SECRETS_FILE
+    );
     println!(
         "  {}/index.md          - Documentation index",
         args.docs_root
@@ -118,6 +145,9 @@ pub fn run(args: InitArgs) -> Result<()> {
         "  {}/templates/        - Document templates",
         args.docs_root
     );
+    if let Some(output) = ci_output {
+        println!("  {:<24}- CI configuration", output.display());
+    }
     println!();
     println!("Next steps:");
     println!("  pave new component <name>  - Create a component doc");
@@ -127,6 +157,34 @@ pub fn run(args: InitArgs) -> Result<()> {
     Ok(())
 }
 
+/// Add `.pave/` and the secrets file to the project's `.gitignore`,
+/// creating it if needed. Entries already present are left alone.
+fn ignore_local_files(base: &Path) -> Result<()> {
+    let path = base.join(".gitignore");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let is_listed = |entry: &str| {
+        existing.lines().any(|line| {
+            let line = line.trim().trim_start_matches('/');
+            line == entry || line.strip_suffix('/') == Some(entry)
+        })
+    };
+    let missing: Vec<String> = [(".pave", ".pave/"), (SECRETS_FILE, SECRETS_FILE)]
+        .into_iter()
+        .filter(|(name, _)| !is_listed(name))
+        .map(|(_, entry)| format!("{entry}\n"))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let separator = if existing.is_empty() || existing.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&path, format!("{existing}{separator}{}", missing.concat()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
 /// Install git hooks for documentation validation.
 fn install_git_hooks(base: &Path) -> Result<()> {
     // Use the shared hook installation from the hooks module
@@ -164,6 +222,40 @@ mod tests {
         assert!(temp_dir.path().join("docs/templates/component.md").exists());
         assert!(temp_dir.path().join("docs/templates/runbook.md").exists());
         assert!(temp_dir.path().join("docs/templates/adr.md").exists());
+        let gitignore = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        assert_eq!(gitignore, ".pave/\n.pave.env\n");
+    }
+
+    #[test]
+    fn init_appends_local_files_to_gitignore_once() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "target/").unwrap();
+        let args = || InitArgs {
+            skip_hooks: true,
+            force: true,
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        run(args()).unwrap();
+        run(args()).unwrap();
+
+        let gitignore = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        assert_eq!(gitignore, "target/\n.pave/\n.pave.env\n");
+    }
+
+    #[test]
+    fn init_keeps_existing_gitignore_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "/.pave\n").unwrap();
+        let args = InitArgs {
+            skip_hooks: true,
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        run(args).unwrap();
+
+        let gitignore = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        assert_eq!(gitignore, "/.pave\n.pave.env\n");
     }
 
     #[test]
@@ -255,6 +347,35 @@ mod tests {
         assert!(parsed.is_ok(), "Generated config should be valid TOML");
     }
 
+    #[test]
+    fn init_config_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let args = InitArgs {
+            skip_hooks: true,
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        run(args).unwrap();
+
+        let config = crate::config::PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
+        assert_eq!(config.docs.root, std::path::PathBuf::from("docs"));
+    }
+
+    #[test]
+    fn init_with_ci_writes_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+        let args = InitArgs {
+            skip_hooks: true,
+            ci: Some(CiProvider::Github),
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        run(args).unwrap();
+
+        let workflow = temp_dir.path().join(".github/workflows/docs.yml");
+        assert!(fs::read_to_string(workflow).unwrap().contains("pave lint"));
+    }
+
     #[test]
     fn init_installs_hook_by_default_in_git_repo() {
         let temp_dir = TempDir::new().unwrap();
@@ -272,7 +393,7 @@ mod tests {
 
         let content = fs::read_to_string(&hook_path).unwrap();
         assert!(content.contains(PAVE_HOOK_MARKER));
-        assert!(content.contains("pave check"));
+        assert!(content.contains("pave hooks run pre-commit"));
     }
 
     #[test]
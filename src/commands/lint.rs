@@ -2,16 +2,44 @@
 
 use anyhow::{Context, Result};
 use regex::Regex;
-use serde::Serialize;
-use std::collections::{HashMap, HashSet};
-use std::env;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
-
-use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, LintSection, PaveConfig};
-use crate::parser::{CodeBlockTracker, ParsedDoc};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::admonition::find_admonitions;
+use crate::blame::{self, AuthorSummary, Blame};
+use crate::cli::{IssueGrouping, OutputFormat, PathStyle};
+use crate::color;
+use crate::commands::check::TitleIndex;
+use crate::config::{
+    AdmonitionStyle, LintSection, PaveConfig, ReadabilityLimits, ReadabilitySection, RuleSeverity,
+};
+use crate::exit::Violations;
+use crate::interface::{self, InterfaceSource};
+use crate::mermaid;
+use crate::parallel;
+use crate::parser::{ParsedDoc, code_block_lines, frontmatter_len, heading_anchor, prose_blocks};
+use crate::paths::display_path;
+use crate::plugins;
+use crate::progress::Progress;
+use crate::readability::Readability;
+use crate::result_cache::{self, LINT_CACHE_PATH, ResultCache};
+use crate::rule_docs;
+use crate::rules::{DocType, detect_doc_type};
+use crate::schema::SCHEMA_VERSION;
+use crate::spelling::Dictionary;
+use crate::terminology::Terminology;
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
 
 /// Arguments for the `pave lint` command.
+#[derive(Clone)]
 pub struct LintArgs {
     /// Specific files or directories to lint.
     pub paths: Vec<PathBuf>,
@@ -19,10 +47,24 @@ pub struct LintArgs {
     pub format: OutputFormat,
     /// Auto-fix simple issues.
     pub fix: bool,
+    /// Ask before applying each fix.
+    pub interactive: bool,
     /// Only run these rules (comma-separated).
     pub rules: Option<String>,
     /// Check external link validity (slow).
     pub external_links: bool,
+    /// Issues allowed before the command fails.
+    pub max_warnings: usize,
+    /// How to display file paths in output.
+    pub path_style: PathStyle,
+    /// Re-lint every document instead of using cached results.
+    pub no_cache: bool,
+    /// Documents to lint at once (default: one per CPU).
+    pub jobs: Option<usize>,
+    /// Annotate issues with who last changed their line.
+    pub blame: bool,
+    /// Count issues per author.
+    pub group_by: Option<IssueGrouping>,
 }
 
 /// All available lint rules.
@@ -42,8 +84,24 @@ pub enum LintRule {
     LongParagraphs,
     /// Same heading text at same level.
     DuplicateHeadings,
+    /// Same H1 title as another document.
+    DuplicateTitles,
     /// Trailing spaces on lines.
     TrailingWhitespace,
+    /// Callouts in a different syntax than the rest of the docs.
+    AdmonitionStyle,
+    /// Terms `[lint.terminology]` replaces with preferred ones.
+    Terminology,
+    /// Words missing from the spelling dictionaries.
+    Spelling,
+    /// Sections whose prose is harder to read than `[lint.readability]` allows.
+    Readability,
+    /// Skipped heading levels, missing or extra H1s, and stray subsections.
+    HeadingHierarchy,
+    /// Interface flags and functions that differ from `pave.interface` source.
+    InterfaceDrift,
+    /// `mermaid` diagrams that won't render.
+    Mermaid,
 }
 
 impl LintRule {
@@ -57,7 +115,15 @@ impl LintRule {
             LintRule::MissingAltText => "missing-alt-text",
             LintRule::LongParagraphs => "long-paragraphs",
             LintRule::DuplicateHeadings => "duplicate-headings",
+            LintRule::DuplicateTitles => "duplicate-titles",
             LintRule::TrailingWhitespace => "trailing-whitespace",
+            LintRule::AdmonitionStyle => "admonition-style",
+            LintRule::Terminology => "terminology",
+            LintRule::Spelling => "spelling",
+            LintRule::Readability => "readability",
+            LintRule::HeadingHierarchy => "heading-hierarchy",
+            LintRule::InterfaceDrift => "interface-drift",
+            LintRule::Mermaid => "mermaid",
         }
     }
 
@@ -71,7 +137,15 @@ impl LintRule {
             "missing-alt-text" => Some(LintRule::MissingAltText),
             "long-paragraphs" => Some(LintRule::LongParagraphs),
             "duplicate-headings" => Some(LintRule::DuplicateHeadings),
+            "duplicate-titles" => Some(LintRule::DuplicateTitles),
             "trailing-whitespace" => Some(LintRule::TrailingWhitespace),
+            "admonition-style" => Some(LintRule::AdmonitionStyle),
+            "terminology" => Some(LintRule::Terminology),
+            "spelling" => Some(LintRule::Spelling),
+            "readability" => Some(LintRule::Readability),
+            "heading-hierarchy" => Some(LintRule::HeadingHierarchy),
+            "interface-drift" => Some(LintRule::InterfaceDrift),
+            "mermaid" => Some(LintRule::Mermaid),
             _ => None,
         }
     }
@@ -86,18 +160,29 @@ impl LintRule {
             LintRule::MissingAltText,
             LintRule::LongParagraphs,
             LintRule::DuplicateHeadings,
+            LintRule::DuplicateTitles,
             LintRule::TrailingWhitespace,
+            LintRule::AdmonitionStyle,
+            LintRule::Terminology,
+            LintRule::Spelling,
+            LintRule::Readability,
+            LintRule::HeadingHierarchy,
+            LintRule::InterfaceDrift,
+            LintRule::Mermaid,
         ]
     }
 
     /// Check if this rule is auto-fixable.
     pub fn is_fixable(&self) -> bool {
-        matches!(self, LintRule::TrailingWhitespace)
+        matches!(
+            self,
+            LintRule::TrailingWhitespace | LintRule::AdmonitionStyle | LintRule::Terminology
+        )
     }
 }
 
 /// A lint issue found in a document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LintIssue {
     /// Path to the file with the issue.
     pub file: PathBuf,
@@ -110,11 +195,19 @@ pub struct LintIssue {
     /// Whether this issue can be auto-fixed.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub fixable: bool,
+    /// Link to the rule's documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// Who last changed the line, with `--blame`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<Blame>,
 }
 
 /// Results of linting documents.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct LintResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Number of files linted.
     pub files_linted: usize,
     /// List of issues found.
@@ -122,18 +215,100 @@ pub struct LintResults {
     /// Number of issues that were auto-fixed.
     #[serde(skip_serializing_if = "is_zero")]
     pub fixed_count: usize,
+    /// Re-check of the files that fixes were written to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_fix: Option<PostFixCheck>,
+    /// Issues per author, with `--group-by author`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<AuthorSummary>>,
+}
+
+/// Result of re-running the applied fixes' rules on the fixed files.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PostFixCheck {
+    /// Number of files re-checked.
+    pub files_checked: usize,
+    /// Rules that were re-run.
+    pub rules: Vec<String>,
+    /// Issues the fixes did not resolve.
+    pub issues: Vec<LintIssue>,
 }
 
 fn is_zero(n: &usize) -> bool {
     *n == 0
 }
 
+impl Merge for LintResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        let post_fix_issues = self.post_fix.iter_mut().flat_map(|p| &mut p.issues);
+        for issue in self.issues.iter_mut().chain(post_fix_issues) {
+            issue.file = prefixed(package, &issue.file);
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.files_linted += other.files_linted;
+        self.issues.extend(other.issues);
+        self.fixed_count += other.fixed_count;
+        self.post_fix = match (self.post_fix.take(), other.post_fix) {
+            (Some(mut merged), Some(other)) => {
+                merged.files_checked += other.files_checked;
+                merged.rules.extend(other.rules);
+                merged.rules.sort();
+                merged.rules.dedup();
+                merged.issues.extend(other.issues);
+                Some(merged)
+            }
+            (merged, other) => merged.or(other),
+        };
+        self.authors = match (self.authors.take(), other.authors) {
+            (None, None) => None,
+            (a, b) => Some(blame::merge_authors(a.into_iter().chain(b).flatten())),
+        };
+    }
+}
+
+impl PackageCommand for LintArgs {
+    type Results = LintResults;
+
+    fn is_json(&self) -> bool {
+        matches!(self.format, OutputFormat::Json)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<LintResults>>> {
+        run(self, workdir)
+    }
+
+    fn output(&self, results: LintResults) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => output_text(&results, self.fix || self.interactive),
+            OutputFormat::Json => output_json(&results)?,
+            OutputFormat::Github => output_github(&results),
+        }
+        Ok(())
+    }
+}
+
 impl LintResults {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             files_linted: 0,
             issues: Vec::new(),
             fixed_count: 0,
+            post_fix: None,
+            authors: None,
         }
     }
 
@@ -152,9 +327,14 @@ impl LintResults {
 }
 
 /// Execute the `pave lint` command.
-pub fn execute(args: LintArgs) -> Result<()> {
+pub fn execute(args: LintArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Lint the documents selected by `args`, leaving output to the caller.
+fn run(args: &LintArgs, workdir: &Workdir) -> Result<Option<Outcome<LintResults>>> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
 
@@ -162,87 +342,301 @@ pub fn execute(args: LintArgs) -> Result<()> {
     let paths = if args.paths.is_empty() {
         vec![config_dir.join(&config.docs.root)]
     } else {
-        args.paths.clone()
+        args.paths
+            .iter()
+            .map(|path| workdir.resolve(path))
+            .collect()
     };
 
     // Find all markdown files
-    let files = find_markdown_files(&paths)?;
+    let files = find_markdown_files(&paths, &load_ignore(config_dir, &config)?)?;
 
     if files.is_empty() {
         eprintln!("No markdown files found to lint");
-        return Ok(());
+        return Ok(None);
     }
 
     // Determine which rules to run
-    let rules = determine_rules(&args, &config.lint)?;
+    let rules = determine_rules(args.rules.as_deref(), &config.lint, &config.plugins.lint)?;
+    let plugin_rules = selected_plugins(args.rules.as_deref(), &config.lint, &config.plugins.lint);
+    let mut rule_names: Vec<&str> = rules.iter().map(LintRule::name).collect();
+    rule_names.sort();
+    info!("Running lint rules: {}", rule_names.join(", "));
+
+    // Without a configured callout style, the docs' most common one wins
+    let mut lint_config = config.lint.clone();
+    if rules.contains(&LintRule::AdmonitionStyle) && lint_config.admonition_style.is_none() {
+        lint_config.admonition_style = detect_admonition_style(&files)?;
+    }
+    let context = LintContext::new(lint_config, &rules, config_dir)?;
 
     // Check external links setting
     let check_external = args.external_links || config.lint.external_links;
+    let mut link_checker = check_external.then(LinkChecker::default);
+
+    let mut fixer = if args.interactive {
+        Some(Fixer::interactive(
+            Box::new(io::stdin().lock()),
+            Box::new(io::stdout()),
+            args.path_style,
+            config_dir,
+            &workdir.dir,
+        ))
+    } else {
+        args.fix.then(Fixer::automatic)
+    };
+
+    // Fixes and external links aren't reproducible from the content alone.
+    // Results also depend on the config, the rules, the spelling
+    // dictionaries, and the other docs, which links point at.
+    let cache = (fixer.is_none() && link_checker.is_none()).then(|| {
+        let mut names: Vec<&str> = rules.iter().map(LintRule::name).collect();
+        names.sort();
+        let dictionaries: Vec<Vec<u8>> = context
+            .config
+            .spelling_dictionaries
+            .iter()
+            .map(|path| std::fs::read(config_dir.join(path)).unwrap_or_default())
+            .collect();
+        let config_json = serde_json::to_vec(&(&config, &context.config)).unwrap_or_default();
+        let names = names.join(",");
+        let mut parts: Vec<&[u8]> = vec![&config_json, names.as_bytes()];
+        parts.extend(dictionaries.iter().map(Vec::as_slice));
+        let fingerprint = result_cache::fingerprint(&parts, &files);
+        Mutex::new(ResultCache::load(
+            config_dir,
+            LINT_CACHE_PATH,
+            fingerprint,
+            !args.no_cache,
+        ))
+    });
 
     // Lint each file
     let mut results = LintResults::new();
-    for file in &files {
-        lint_file(
-            file,
-            &rules,
-            &config.lint,
-            config_dir,
-            check_external,
-            args.fix,
-            &mut results,
-        )?;
+    let mut fixed_files = Vec::new();
+    let start = Instant::now();
+    // Interactive fixes prompt on stdout, which the bar would overwrite
+    let progress = if args.interactive {
+        Progress::hidden()
+    } else {
+        Progress::new(files.len(), matches!(args.format, OutputFormat::Text))
+    };
+    if let Some(cache) = &cache {
+        // Lint the files in parallel and merge their issues in file order
+        let per_file = parallel::map_files(&files, args.jobs, |file| {
+            progress.start(file.strip_prefix(config_dir).unwrap_or(file));
+            let file_start = Instant::now();
+            let mut file_results = LintResults::new();
+            lint_file_cached(file, &rules, &context, config_dir, cache, &mut file_results)?;
+            lint_plugins(file, &plugin_rules, config_dir, &mut file_results)?;
+            debug!("Linted {} in {:?}", file.display(), file_start.elapsed());
+            progress.finish();
+            Ok(file_results.issues)
+        })?;
+        results.issues.extend(per_file.into_iter().flatten());
+    } else {
+        // Fixes and the link checker keep state across files, so those runs
+        // lint one file at a time
+        for file in &files {
+            progress.start(file.strip_prefix(config_dir).unwrap_or(file));
+            let file_start = Instant::now();
+            let fixed = lint_file(
+                file,
+                &rules,
+                &context,
+                config_dir,
+                link_checker.as_mut(),
+                fixer.as_mut(),
+                &mut results,
+            )?;
+            lint_plugins(file, &plugin_rules, config_dir, &mut results)?;
+            debug!("Linted {} in {:?}", file.display(), file_start.elapsed());
+            if fixed {
+                fixed_files.push(file.clone());
+            }
+            progress.finish();
+        }
+    }
+    drop(progress);
+    info!("Linted {} files in {:?}", files.len(), start.elapsed());
+    if let Some(cache) = cache {
+        let mut cache = cache.into_inner().unwrap();
+        debug!("Reused cached results for {} files", cache.hits());
+        if let Err(e) = cache.save() {
+            warn!("Failed to write lint cache: {:#}", e);
+        }
     }
     results.files_linted = files.len();
 
-    // Output results in the requested format
-    match args.format {
-        OutputFormat::Text => output_text(&results, args.fix),
-        OutputFormat::Json => output_json(&results)?,
-        OutputFormat::Github => output_github(&results),
+    // Titles are compared across the docs root, so unlike the other rules
+    // they can change without the file changing
+    if rules.contains(&LintRule::DuplicateTitles)
+        && config.rules.duplicate_titles != RuleSeverity::Off
+    {
+        let index = TitleIndex::build(&config, config_dir, &files)?;
+        let display_file =
+            |path: &Path| display_path(path, args.path_style, config_dir, &workdir.dir);
+        check_duplicate_titles(&files, &index, display_file, &mut results)?;
     }
 
-    // Return error if there are unfixed issues
-    let unfixed = results.issues.len() - results.fixed_count;
-    if unfixed > 0 {
-        anyhow::bail!(
-            "Lint failed: {} issue{}",
-            unfixed,
-            if unfixed == 1 { "" } else { "s" }
-        );
+    // Confirm the fixes took by re-running their rules on the fixed files
+    if let Some(fixer) = &fixer
+        && !fixed_files.is_empty()
+    {
+        results.post_fix = Some(recheck_fixed_files(
+            &fixed_files,
+            &fixer.applied_rules,
+            &results.issues,
+            &context,
+            config_dir,
+        )?);
     }
 
-    Ok(())
+    // Blame reads the files, so it runs before their paths are shortened
+    if args.blame || args.group_by.is_some() {
+        let locations: Vec<(PathBuf, usize)> = results
+            .issues
+            .iter()
+            .map(|issue| (issue.file.clone(), issue.line))
+            .collect();
+        for (issue, blame) in results.issues.iter_mut().zip(blame::blame_all(&locations)) {
+            issue.blame = blame;
+        }
+        if args.group_by == Some(IssueGrouping::Author) {
+            let issues = results.issues.iter();
+            results.authors = Some(blame::group_by_author(
+                issues.map(|issue| issue.blame.as_ref()),
+            ));
+        }
+    }
+
+    let docs_base = config.rules.docs_url.as_deref();
+    let post_fix_issues = results.post_fix.iter_mut().flat_map(|p| &mut p.issues);
+    for issue in results.issues.iter_mut().chain(post_fix_issues) {
+        issue.file = display_path(&issue.file, args.path_style, config_dir, &workdir.dir);
+        issue.docs_url = rule_docs::docs_url(docs_base, &issue.rule);
+    }
+
+    // Fail if there are unfixed issues (fixed ones are never recorded)
+    let unfixed = results.issues.len() + results.post_fix.as_ref().map_or(0, |p| p.issues.len());
+    let status = if unfixed > args.max_warnings {
+        let allowed = if args.max_warnings > 0 {
+            format!(" (max {})", args.max_warnings)
+        } else {
+            String::new()
+        };
+        Err(Violations(format!(
+            "Lint failed: {} issue{}{}",
+            unfixed,
+            if unfixed == 1 { "" } else { "s" },
+            allowed
+        ))
+        .into())
+    } else {
+        Ok(())
+    };
+    Ok(Some(Outcome { results, status }))
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+/// Lint one document with the project's configured rules and return its
+/// issues, for callers that report them their own way. External links are
+/// not checked.
+pub(crate) fn lint_document(
+    path: &Path,
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<Vec<LintIssue>> {
+    let rules = determine_rules(None, &config.lint, &config.plugins.lint)?;
+
+    // The callout style is the docs' most common one, as in a full run
+    let mut lint_config = config.lint.clone();
+    let docs_root = config_dir.join(&config.docs.root);
+    if rules.contains(&LintRule::AdmonitionStyle)
+        && lint_config.admonition_style.is_none()
+        && docs_root.is_dir()
+    {
+        let docs = find_markdown_files(&[docs_root], &load_ignore(config_dir, config)?)?;
+        lint_config.admonition_style = detect_admonition_style(&docs)?;
+    }
+    let context = LintContext::new(lint_config, &rules, config_dir)?;
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
+    let mut results = LintResults::new();
+    lint_file(path, &rules, &context, config_dir, None, None, &mut results)?;
+    Ok(results.issues)
+}
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
+/// Time each of the project's lint rules on each document, for
+/// `pave bench`, keeping the fastest of `runs` runs. Returns the rules by
+/// name, each with one duration per document in the order of `files`.
+///
+/// Reading and parsing a document is timed with no rules enabled and left
+/// out of every rule's time. External links are not checked.
+pub(crate) fn time_rules(
+    files: &[PathBuf],
+    config: &PaveConfig,
+    config_dir: &Path,
+    runs: usize,
+) -> Result<Vec<(&'static str, Vec<Duration>)>> {
+    let rules = determine_rules(None, &config.lint, &config.plugins.lint)?;
+    let mut lint_config = config.lint.clone();
+    if rules.contains(&LintRule::AdmonitionStyle) && lint_config.admonition_style.is_none() {
+        lint_config.admonition_style = detect_admonition_style(files)?;
     }
+    let context = LintContext::new(lint_config, &rules, config_dir)?;
+    let time = |rules: &HashSet<LintRule>, file: &PathBuf| {
+        let mut fastest = Duration::MAX;
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            lint_file(
+                file,
+                rules,
+                &context,
+                config_dir,
+                None,
+                None,
+                &mut LintResults::new(),
+            )?;
+            fastest = fastest.min(start.elapsed());
+        }
+        Ok(fastest)
+    };
+
+    let baseline: Vec<Duration> = files
+        .iter()
+        .map(|file| time(&HashSet::new(), file))
+        .collect::<Result<_>>()?;
+    let mut rules: Vec<LintRule> = rules.into_iter().collect();
+    rules.sort_by_key(|rule| rule.name());
+    rules
+        .into_iter()
+        .map(|rule| {
+            let durations = files
+                .iter()
+                .zip(&baseline)
+                .map(|(file, baseline)| {
+                    Ok(time(&HashSet::from([rule]), file)?.saturating_sub(*baseline))
+                })
+                .collect::<Result<_>>()?;
+            Ok((rule.name(), durations))
+        })
+        .collect()
 }
 
 /// Determine which rules to run based on CLI args and config.
-fn determine_rules(args: &LintArgs, config: &LintSection) -> Result<HashSet<LintRule>> {
-    let mut rules: HashSet<LintRule> = if let Some(ref rules_str) = args.rules {
+///
+/// Names of rule `plugins` may be selected too; they are left to
+/// [`selected_plugins`].
+fn determine_rules(
+    selected: Option<&str>,
+    config: &LintSection,
+    plugins: &BTreeMap<String, String>,
+) -> Result<HashSet<LintRule>> {
+    let mut rules: HashSet<LintRule> = if let Some(rules_str) = selected {
         // Only run specified rules
         rules_str
             .split(',')
             .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
+            .filter(|s| !s.is_empty() && !plugins.contains_key(*s))
             .map(|name| {
                 LintRule::from_name(name)
                     .ok_or_else(|| anyhow::anyhow!("Unknown lint rule: {}", name))
@@ -253,6 +647,7 @@ fn determine_rules(args: &LintArgs, config: &LintSection) -> Result<HashSet<Lint
         config
             .enable
             .iter()
+            .filter(|name| !plugins.contains_key(*name))
             .map(|name| {
                 LintRule::from_name(name)
                     .ok_or_else(|| anyhow::anyhow!("Unknown lint rule in config: {}", name))
@@ -273,64 +668,140 @@ fn determine_rules(args: &LintArgs, config: &LintSection) -> Result<HashSet<Lint
     Ok(rules)
 }
 
-/// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
+/// Rule plugins from `[plugins] lint` to run, selected and disabled by name
+/// like the built-in rules.
+fn selected_plugins<'a>(
+    selected: Option<&str>,
+    config: &LintSection,
+    plugins: &'a BTreeMap<String, String>,
+) -> Vec<(&'a String, &'a String)> {
+    let chosen = |name: &str| match selected {
+        Some(selected) => selected.split(',').any(|s| s.trim() == name),
+        None => config.enable.is_empty() || config.enable.iter().any(|e| e == name),
+    };
+    plugins
+        .iter()
+        .filter(|(name, _)| chosen(name) && !config.disable.contains(name))
+        .collect()
+}
 
-    for path in paths {
-        if path.is_file() {
-            if path.extension().is_some_and(|ext| ext == "md") {
-                files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        } else {
-            anyhow::bail!("Path does not exist: {}", path.display());
+/// Run rule plugins on a document, reporting their issues under the rule
+/// name they're configured with.
+fn lint_plugins(
+    path: &Path,
+    plugin_rules: &[(&String, &String)],
+    project_root: &Path,
+    results: &mut LintResults,
+) -> Result<()> {
+    if plugin_rules.is_empty() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let relative = display_path(path, PathStyle::Workspace, project_root, project_root);
+    for (rule, command) in plugin_rules {
+        for issue in plugins::run_rule(rule, command, &relative, &content, project_root)? {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line: issue.line,
+                rule: rule.to_string(),
+                message: issue.message,
+                fixable: false,
+                docs_url: None,
+                blame: None,
+            });
         }
     }
+    Ok(())
+}
 
-    // Sort for consistent output
-    files.sort();
-    Ok(files)
+/// Configuration shared by every file's lint, compiled once per run.
+struct LintContext {
+    config: LintSection,
+    terminology: Terminology,
+    /// Loaded only when the spelling rule runs.
+    dictionary: Option<Dictionary>,
 }
 
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+impl LintContext {
+    fn new(config: LintSection, rules: &HashSet<LintRule>, project_root: &Path) -> Result<Self> {
+        let dictionary = if rules.contains(&LintRule::Spelling) {
+            Dictionary::load(project_root, &config.spelling_dictionaries)?
+        } else {
+            None
+        };
+        Ok(Self {
+            terminology: Terminology::new(&config.terminology)?,
+            dictionary,
+            config,
+        })
+    }
+}
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+/// Rules that read source files the result cache doesn't track: backtick
+/// paths and interface sources. Their results are never cached.
+const UNCACHED_RULES: [LintRule; 2] = [LintRule::StaleCodeRefs, LintRule::InterfaceDrift];
 
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
+/// Lint a file like [`lint_file`] without fixes or external links, reusing
+/// the issues cached for it when its content hasn't changed. The
+/// [`UNCACHED_RULES`] run every time.
+fn lint_file_cached(
+    path: &Path,
+    rules: &HashSet<LintRule>,
+    context: &LintContext,
+    project_root: &Path,
+    cache: &Mutex<ResultCache<LintIssue>>,
+    results: &mut LintResults,
+) -> Result<()> {
+    let (uncached, cached): (HashSet<LintRule>, HashSet<LintRule>) =
+        rules.iter().partition(|rule| UNCACHED_RULES.contains(rule));
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let hit = cache.lock().unwrap().get(path, &content);
+    if let Some(issues) = hit {
+        for issue in issues {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                ..issue
+            });
         }
+    } else {
+        let first = results.issues.len();
+        lint_file(path, &cached, context, project_root, None, None, results)?;
+        cache
+            .lock()
+            .unwrap()
+            .insert(path, &content, results.issues[first..].to_vec());
     }
 
+    if !uncached.is_empty() {
+        lint_file(path, &uncached, context, project_root, None, None, results)?;
+    }
     Ok(())
 }
 
 /// Lint a single file against the enabled rules.
+///
+/// Returns whether fixes were written to the file.
 fn lint_file(
     path: &Path,
     rules: &HashSet<LintRule>,
-    config: &LintSection,
+    context: &LintContext,
     project_root: &Path,
-    _check_external: bool,
-    fix: bool,
+    link_checker: Option<&mut LinkChecker>,
+    mut fixer: Option<&mut Fixer>,
     results: &mut LintResults,
-) -> Result<()> {
+) -> Result<bool> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
     let lines: Vec<&str> = content.lines().collect();
     let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    let config = &context.config;
+    let rules = &file_rules(rules, &lines);
 
     // Track fixes to apply
-    let mut fixed_lines: Option<Vec<String>> = if fix {
+    let mut fixed_lines: Option<Vec<String>> = if fixer.is_some() {
         Some(lines.iter().map(|s| s.to_string()).collect())
     } else {
         None
@@ -358,15 +829,80 @@ fn lint_file(
     }
 
     if rules.contains(&LintRule::LongParagraphs) {
-        check_long_paragraphs(path, &doc, config.max_paragraph_words, results);
+        check_long_paragraphs(path, &lines, config.max_paragraph_words, results);
     }
 
     if rules.contains(&LintRule::DuplicateHeadings) {
         check_duplicate_headings(path, &lines, results);
     }
 
+    if rules.contains(&LintRule::HeadingHierarchy) {
+        check_heading_hierarchy(path, &lines, &config.sections, results);
+    }
+
+    if rules.contains(&LintRule::InterfaceDrift)
+        && let Some(interface) = doc
+            .frontmatter
+            .as_ref()
+            .and_then(|fm| fm.interface.as_ref())
+    {
+        check_interface_drift(
+            path,
+            &doc,
+            interface,
+            &config.help_cmds,
+            project_root,
+            results,
+        )?;
+    }
+
+    if rules.contains(&LintRule::Mermaid) {
+        check_mermaid(path, &lines, config.mermaid_cmd.as_deref(), results)?;
+    }
+
+    if rules.contains(&LintRule::Readability) {
+        let limits = readability_limits(detect_doc_type(path, &content), &config.readability);
+        if !limits.is_empty() {
+            check_readability(path, &doc, limits, results);
+        }
+    }
+
+    if rules.contains(&LintRule::Spelling)
+        && let Some(dictionary) = &context.dictionary
+    {
+        check_spelling(path, &lines, dictionary, results);
+    }
+
     if rules.contains(&LintRule::TrailingWhitespace) {
-        check_trailing_whitespace(path, &lines, fix, &mut fixed_lines, results);
+        check_trailing_whitespace(
+            path,
+            &lines,
+            fixer.as_deref_mut(),
+            &mut fixed_lines,
+            results,
+        );
+    }
+
+    if rules.contains(&LintRule::Terminology) && !context.terminology.is_empty() {
+        check_terminology(
+            path,
+            &lines,
+            &context.terminology,
+            fixer.as_deref_mut(),
+            &mut fixed_lines,
+            results,
+        );
+    }
+
+    // Runs last: its fixes can add and remove lines
+    if rules.contains(&LintRule::AdmonitionStyle)
+        && let Some(style) = config.admonition_style
+    {
+        check_admonition_style(path, &lines, style, fixer, &mut fixed_lines, results);
+    }
+
+    if let Some(checker) = link_checker {
+        check_external_links(path, &lines, checker, results);
     }
 
     // Apply fixes if any
@@ -382,10 +918,86 @@ fn lint_file(
             };
             std::fs::write(path, new_content)
                 .with_context(|| format!("Failed to write fixed file: {}", path.display()))?;
+            return Ok(true);
         }
     }
 
-    Ok(())
+    Ok(false)
+}
+
+/// The rules left after a file's `<!-- pave-lint-disable rule, ... -->`
+/// comments. A comment without rule names turns off every rule.
+fn file_rules(rules: &HashSet<LintRule>, lines: &[&str]) -> HashSet<LintRule> {
+    let disable_re = Regex::new(r"<!--\s*pave-lint-disable\b(.*?)-->").unwrap();
+
+    let mut rules = rules.clone();
+    let in_code = code_block_lines(lines);
+    for (line_num, line) in lines.iter().enumerate() {
+        if in_code[line_num] {
+            continue;
+        }
+        for caps in disable_re.captures_iter(line) {
+            let names: Vec<&str> = caps[1]
+                .split([',', ' '])
+                .filter(|name| !name.is_empty())
+                .collect();
+            if names.is_empty() {
+                rules.clear();
+            }
+            for rule in names.into_iter().filter_map(LintRule::from_name) {
+                rules.remove(&rule);
+            }
+        }
+    }
+    rules
+}
+
+/// Re-run the rules whose fixes were applied on the files that were fixed.
+///
+/// Issues whose fixes were skipped are already in `reported`, so one match
+/// per reported issue is dropped; fixes can move lines, so matches compare
+/// file, rule, and message.
+fn recheck_fixed_files(
+    files: &[PathBuf],
+    rules: &HashSet<LintRule>,
+    reported: &[LintIssue],
+    context: &LintContext,
+    project_root: &Path,
+) -> Result<PostFixCheck> {
+    let mut results = LintResults::new();
+    for file in files {
+        lint_file(file, rules, context, project_root, None, None, &mut results)?;
+    }
+
+    let key = |issue: &LintIssue| {
+        (
+            issue.file.clone(),
+            issue.rule.clone(),
+            issue.message.clone(),
+        )
+    };
+    let mut skipped: HashMap<_, usize> = HashMap::new();
+    for issue in reported {
+        *skipped.entry(key(issue)).or_default() += 1;
+    }
+    results
+        .issues
+        .retain(|issue| match skipped.get_mut(&key(issue)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        });
+
+    let mut rule_names: Vec<String> = rules.iter().map(|r| r.name().to_string()).collect();
+    rule_names.sort();
+
+    Ok(PostFixCheck {
+        files_checked: files.len(),
+        rules: rule_names,
+        issues: results.issues,
+    })
 }
 
 /// Check for broken internal links (links to docs that don't exist).
@@ -397,13 +1009,10 @@ fn check_broken_internal_links(
 ) -> Result<()> {
     let link_re = Regex::new(r"\[([^\]]*)\]\(([^)]+)\)").unwrap();
 
-    let mut tracker = CodeBlockTracker::new();
+    let in_code = code_block_lines(lines);
 
     for (line_num, line) in lines.iter().enumerate() {
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(line);
-
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
@@ -443,6 +1052,8 @@ fn check_broken_internal_links(
                     rule: LintRule::BrokenInternalLinks.name().to_string(),
                     message: format!("broken link to '{}' (file not found)", file_path),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
@@ -451,59 +1062,142 @@ fn check_broken_internal_links(
     Ok(())
 }
 
+/// Rule name reported for unreachable external links.
+const EXTERNAL_LINKS_RULE: &str = "external-links";
+
+/// Checks external URLs, fetching each distinct URL once per run.
+#[derive(Debug, Default)]
+pub(crate) struct LinkChecker {
+    /// Result per URL: `None` if reachable, otherwise why it isn't.
+    checked: HashMap<String, Option<String>>,
+}
+
+impl LinkChecker {
+    /// Number of distinct URLs checked so far.
+    pub(crate) fn checked_count(&self) -> usize {
+        self.checked.len()
+    }
+
+    fn check(&mut self, url: &str) -> Option<String> {
+        self.checked
+            .entry(url.to_string())
+            .or_insert_with(|| fetch_url(url).err())
+            .clone()
+    }
+}
+
+/// Check that external links can be fetched.
+pub(crate) fn check_external_links(
+    path: &Path,
+    lines: &[&str],
+    checker: &mut LinkChecker,
+    results: &mut LintResults,
+) {
+    for (line, url) in extract_external_links(lines) {
+        if let Some(error) = checker.check(&url) {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line,
+                rule: EXTERNAL_LINKS_RULE.to_string(),
+                message: format!("external link '{}' is unreachable ({})", url, error),
+                fixable: false,
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+}
+
+/// Find http(s) links and autolinks outside code blocks.
+///
+/// Returns (1-indexed line, URL) pairs.
+fn extract_external_links(lines: &[&str]) -> Vec<(usize, String)> {
+    let link_re = Regex::new(r"\]\((https?://[^)\s]+)\)|<(https?://[^>\s]+)>").unwrap();
+    let in_code = code_block_lines(lines);
+    let mut links = Vec::new();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        if in_code[line_num] {
+            continue;
+        }
+
+        for cap in link_re.captures_iter(line) {
+            if let Some(url) = cap.get(1).or_else(|| cap.get(2)) {
+                links.push((line_num + 1, url.as_str().to_string()));
+            }
+        }
+    }
+
+    links
+}
+
+/// Fetch a URL with `curl`, describing the failure if it can't be fetched.
+///
+/// Shelling out keeps an HTTP client and TLS stack out of pave's dependencies.
+fn fetch_url(url: &str) -> std::result::Result<(), String> {
+    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--location", "--max-time", "20"])
+        .args(["--output", null_device, "--write-out", "%{http_code}", url])
+        .output()
+        .map_err(|e| format!("failed to run curl: {}", e))?;
+
+    if !output.status.success() {
+        // curl reports "curl: (7) Failed to connect ..."; keep the description
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim().trim_start_matches("curl: ");
+        let message = message
+            .split_once(") ")
+            .filter(|_| message.starts_with('('))
+            .map_or(message, |(_, rest)| rest);
+        return Err(message.to_string());
+    }
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    match status.trim().parse::<u16>() {
+        Ok(code) if code < 400 => Ok(()),
+        Ok(code) => Err(format!("HTTP {}", code)),
+        Err(_) => Err("no HTTP status".to_string()),
+    }
+}
+
 /// Check for dead anchors (links to sections that don't exist).
 fn check_dead_anchors(path: &Path, _content: &str, lines: &[&str], results: &mut LintResults) {
     // Build set of valid anchors from headings (skipping code blocks)
     let heading_re = Regex::new(r"^#{1,6}\s+(.+)$").unwrap();
     let mut valid_anchors: HashSet<String> = HashSet::new();
-    let mut tracker = CodeBlockTracker::new();
+    let in_code = code_block_lines(lines);
 
-    for line in lines {
-        tracker.process_line(line);
-        if tracker.in_code_block() {
+    for (line_num, line) in lines.iter().enumerate() {
+        if in_code[line_num] {
             continue;
         }
 
         if let Some(cap) = heading_re.captures(line) {
-            let heading = &cap[1];
-            // Convert heading to anchor format (lowercase, spaces to hyphens)
-            let anchor = heading
-                .to_lowercase()
-                .replace(' ', "-")
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-')
-                .collect::<String>();
-            valid_anchors.insert(anchor);
+            valid_anchors.insert(heading_anchor(&cap[1]));
         }
     }
 
     // Find all anchor links (also skipping code blocks)
     let anchor_link_re = Regex::new(r"\[([^\]]*)\]\(#([^)]+)\)").unwrap();
-    let mut tracker = CodeBlockTracker::new();
 
     for (line_num, line) in lines.iter().enumerate() {
-        tracker.process_line(line);
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
         for cap in anchor_link_re.captures_iter(line) {
             let anchor = &cap[2];
 
-            // Normalize the anchor for comparison
-            let normalized = anchor
-                .to_lowercase()
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-')
-                .collect::<String>();
-
-            if !valid_anchors.contains(&normalized) {
+            if !valid_anchors.contains(&heading_anchor(anchor)) {
                 results.add_issue(LintIssue {
                     file: path.to_path_buf(),
                     line: line_num + 1,
                     rule: LintRule::DeadAnchors.name().to_string(),
                     message: format!("dead anchor '#{}' (section not found)", anchor),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
@@ -511,11 +1205,9 @@ fn check_dead_anchors(path: &Path, _content: &str, lines: &[&str], results: &mut
 
     // Also check anchors in file links (e.g., file.md#section)
     let file_anchor_re = Regex::new(r"\[([^\]]*)\]\(([^)#]+)#([^)]+)\)").unwrap();
-    let mut tracker = CodeBlockTracker::new();
 
     for (line_num, line) in lines.iter().enumerate() {
-        tracker.process_line(line);
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
@@ -538,33 +1230,19 @@ fn check_dead_anchors(path: &Path, _content: &str, lines: &[&str], results: &mut
             {
                 let target_lines: Vec<&str> = target_content.lines().collect();
                 let mut target_anchors: HashSet<String> = HashSet::new();
-                let mut target_tracker = CodeBlockTracker::new();
+                let target_in_code = code_block_lines(&target_lines);
 
-                for tline in &target_lines {
-                    target_tracker.process_line(tline);
-                    if target_tracker.in_code_block() {
+                for (tline_num, tline) in target_lines.iter().enumerate() {
+                    if target_in_code[tline_num] {
                         continue;
                     }
 
                     if let Some(cap) = heading_re.captures(tline) {
-                        let heading = &cap[1];
-                        let anchor_id = heading
-                            .to_lowercase()
-                            .replace(' ', "-")
-                            .chars()
-                            .filter(|c| c.is_alphanumeric() || *c == '-')
-                            .collect::<String>();
-                        target_anchors.insert(anchor_id);
+                        target_anchors.insert(heading_anchor(&cap[1]));
                     }
                 }
 
-                let normalized = anchor
-                    .to_lowercase()
-                    .chars()
-                    .filter(|c| c.is_alphanumeric() || *c == '-')
-                    .collect::<String>();
-
-                if !target_anchors.contains(&normalized) {
+                if !target_anchors.contains(&heading_anchor(anchor)) {
                     results.add_issue(LintIssue {
                         file: path.to_path_buf(),
                         line: line_num + 1,
@@ -574,6 +1252,8 @@ fn check_dead_anchors(path: &Path, _content: &str, lines: &[&str], results: &mut
                             target_file, anchor
                         ),
                         fixable: false,
+                        docs_url: None,
+                        blame: None,
                     });
                 }
             }
@@ -582,10 +1262,8 @@ fn check_dead_anchors(path: &Path, _content: &str, lines: &[&str], results: &mut
 
     // Also handle HTML-style anchors (skipping code blocks)
     let html_anchor_re = Regex::new(r#"<a\s+[^>]*id\s*=\s*["']([^"']+)["'][^>]*>"#).unwrap();
-    let mut tracker = CodeBlockTracker::new();
-    for line in lines {
-        tracker.process_line(line);
-        if tracker.in_code_block() {
+    for (line_num, line) in lines.iter().enumerate() {
+        if in_code[line_num] {
             continue;
         }
         for cap in html_anchor_re.captures_iter(line) {
@@ -605,13 +1283,10 @@ fn check_stale_code_refs(
     let code_ref_re =
         Regex::new(r"(?:`([^`]+\.(rs|py|js|ts|go|java|rb|c|cpp|h|hpp))`|\[([^\]]*)\]\(([^)]+\.(rs|py|js|ts|go|java|rb|c|cpp|h|hpp))\))").unwrap();
 
-    let mut tracker = CodeBlockTracker::new();
+    let in_code = code_block_lines(lines);
 
     for (line_num, line) in lines.iter().enumerate() {
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(line);
-
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
@@ -648,55 +1323,212 @@ fn check_stale_code_refs(
                     rule: LintRule::StaleCodeRefs.name().to_string(),
                     message: format!("reference to '{}' (file not found)", code_path),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
     }
 }
 
-/// Check for inconsistent heading styles (ATX vs Setext, spacing variations).
-fn check_inconsistent_headings(path: &Path, lines: &[&str], results: &mut LintResults) {
-    let atx_re = Regex::new(r"^(#{1,6})\s").unwrap();
-
-    let mut first_style: Option<bool> = None; // true = ATX with space, false = ATX without space
-    let mut tracker = CodeBlockTracker::new();
-
-    for (line_num, line) in lines.iter().enumerate() {
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(line);
+/// Check the Interface section against the flags and functions of the
+/// source named in `pave.interface`.
+fn check_interface_drift(
+    path: &Path,
+    doc: &ParsedDoc,
+    interface: &InterfaceSource,
+    help_cmds: &BTreeMap<String, String>,
+    project_root: &Path,
+    results: &mut LintResults,
+) -> Result<()> {
+    let Some(section) = doc.get_section("Interface") else {
+        return Ok(());
+    };
+    let mut issue = |line: usize, message: String| {
+        results.add_issue(LintIssue {
+            file: path.to_path_buf(),
+            line,
+            rule: LintRule::InterfaceDrift.name().to_string(),
+            message,
+            fixable: false,
+            docs_url: None,
+            blame: None,
+        });
+    };
 
-        if tracker.in_code_block() {
-            continue;
+    let mut sources = Vec::new();
+    for pattern in &interface.source {
+        let full = project_root.join(pattern);
+        let matches = glob::glob(&full.to_string_lossy())
+            .with_context(|| format!("Invalid interface source pattern: {}", pattern))?;
+        for file in matches.flatten() {
+            let source = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            sources.push(source);
         }
+    }
+    if sources.is_empty() && !interface.source.is_empty() {
+        issue(
+            section.start_line,
+            format!(
+                "interface source '{}' matches no files",
+                interface.source.join(", ")
+            ),
+        );
+        return Ok(());
+    }
 
-        // Check for ATX headings
-        if line.starts_with('#') {
-            let has_space = atx_re.is_match(line);
-
-            match first_style {
-                None => first_style = Some(has_space),
-                Some(expected) if expected != has_space => {
-                    results.add_issue(LintIssue {
-                        file: path.to_path_buf(),
-                        line: line_num + 1,
-                        rule: LintRule::InconsistentHeadings.name().to_string(),
-                        message: if expected {
-                            "inconsistent heading style (missing space after #)".to_string()
-                        } else {
-                            "inconsistent heading style (unexpected space after #)".to_string()
-                        },
-                        fixable: false,
-                    });
-                }
-                _ => {}
+    let flags = match &interface.help_cmd {
+        Some(name) => {
+            // Only commands from the config run, never ones from a document
+            let Some(help_cmd) = help_cmds.get(name) else {
+                issue(
+                    section.start_line,
+                    format!("help command '{}' is not in [lint.help_cmds]", name),
+                );
+                return Ok(());
+            };
+            let output = Command::new("sh")
+                .arg("-c")
+                .arg(help_cmd)
+                .current_dir(project_root)
+                .output()
+                .with_context(|| format!("Failed to run help command: {}", help_cmd))?;
+            if !output.status.success() {
+                issue(
+                    section.start_line,
+                    format!("help command '{}' failed ({})", help_cmd, output.status),
+                );
+                return Ok(());
             }
+            interface::help_flags(&String::from_utf8_lossy(&output.stdout))
         }
-
-        // Check for Setext-style headings (underlines)
-        if line_num > 0 && (line.starts_with("===") || line.starts_with("---")) {
-            let prev_line = lines[line_num - 1];
-            if !prev_line.is_empty() && !prev_line.starts_with('#') {
-                // This looks like a Setext heading
+        None => {
+            let found: Vec<_> = sources
+                .iter()
+                .filter_map(|source| interface::clap_flags(source, interface.item.as_deref()))
+                .collect();
+            if found.is_empty()
+                && let Some(item) = &interface.item
+            {
+                issue(
+                    section.start_line,
+                    format!("interface item '{}' not found in its source", item),
+                );
+                return Ok(());
+            }
+            found.into_iter().flatten().collect()
+        }
+    };
+    let functions = (!sources.is_empty()).then(|| {
+        sources
+            .iter()
+            .flat_map(|source| interface::functions(source))
+            .collect()
+    });
+
+    // Section content starts on the line after the heading
+    let documented = interface::documented(&section.content);
+    let drift = interface::compare(&documented, &flags, functions.as_ref());
+    for (name, line) in drift.removed_flags {
+        issue(
+            section.start_line + 1 + line,
+            format!("'--{}' is documented but not in the source", name),
+        );
+    }
+    if !drift.undocumented_flags.is_empty() {
+        let names: Vec<String> = drift
+            .undocumented_flags
+            .iter()
+            .map(|name| format!("--{}", name))
+            .collect();
+        issue(
+            section.start_line,
+            format!("undocumented option(s): {}", names.join(", ")),
+        );
+    }
+    for (name, line) in drift.removed_functions {
+        issue(
+            section.start_line + 1 + line,
+            format!("'{}()' is documented but not defined in the source", name),
+        );
+    }
+    Ok(())
+}
+
+/// Check `mermaid` diagrams with the built-in checks, or with `command` when
+/// one is configured.
+fn check_mermaid(
+    path: &Path,
+    lines: &[&str],
+    command: Option<&str>,
+    results: &mut LintResults,
+) -> Result<()> {
+    for diagram in mermaid::diagrams(lines) {
+        let errors = match command {
+            Some(command) => mermaid::check_with_command(&diagram.source, command)
+                .with_context(|| format!("Failed to run mermaid command: {}", command))?
+                .into_iter()
+                .collect(),
+            None => mermaid::check(&diagram.source),
+        };
+        for error in errors {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line: diagram.first_line + error.line - 1,
+                rule: LintRule::Mermaid.name().to_string(),
+                message: format!("mermaid: {}", error.message),
+                fixable: false,
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check for inconsistent heading styles (ATX vs Setext, spacing variations).
+fn check_inconsistent_headings(path: &Path, lines: &[&str], results: &mut LintResults) {
+    let atx_re = Regex::new(r"^(#{1,6})\s").unwrap();
+
+    let mut first_style: Option<bool> = None; // true = ATX with space, false = ATX without space
+    let in_code = code_block_lines(lines);
+
+    for (line_num, line) in lines.iter().enumerate() {
+        if in_code[line_num] {
+            continue;
+        }
+
+        // Check for ATX headings
+        if line.starts_with('#') {
+            let has_space = atx_re.is_match(line);
+
+            match first_style {
+                None => first_style = Some(has_space),
+                Some(expected) if expected != has_space => {
+                    results.add_issue(LintIssue {
+                        file: path.to_path_buf(),
+                        line: line_num + 1,
+                        rule: LintRule::InconsistentHeadings.name().to_string(),
+                        message: if expected {
+                            "inconsistent heading style (missing space after #)".to_string()
+                        } else {
+                            "inconsistent heading style (unexpected space after #)".to_string()
+                        },
+                        fixable: false,
+                        docs_url: None,
+                        blame: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // Check for Setext-style headings (underlines)
+        if line_num > 0 && (line.starts_with("===") || line.starts_with("---")) {
+            let prev_line = lines[line_num - 1];
+            if !prev_line.is_empty() && !prev_line.starts_with('#') {
+                // This looks like a Setext heading
                 if first_style.is_some() {
                     results.add_issue(LintIssue {
                         file: path.to_path_buf(),
@@ -704,6 +1536,8 @@ fn check_inconsistent_headings(path: &Path, lines: &[&str], results: &mut LintRe
                         rule: LintRule::InconsistentHeadings.name().to_string(),
                         message: "mixed ATX and Setext heading styles".to_string(),
                         fixable: false,
+                        docs_url: None,
+                        blame: None,
                     });
                 }
             }
@@ -717,13 +1551,10 @@ fn check_missing_alt_text(path: &Path, lines: &[&str], results: &mut LintResults
     let html_img_re = Regex::new(r#"<img\s+[^>]*>"#).unwrap();
     let alt_attr_re = Regex::new(r#"alt\s*=\s*["']([^"']*)["']"#).unwrap();
 
-    let mut tracker = CodeBlockTracker::new();
+    let in_code = code_block_lines(lines);
 
     for (line_num, line) in lines.iter().enumerate() {
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(line);
-
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
@@ -737,6 +1568,8 @@ fn check_missing_alt_text(path: &Path, lines: &[&str], results: &mut LintResults
                     rule: LintRule::MissingAltText.name().to_string(),
                     message: "missing alt text for image".to_string(),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
@@ -756,6 +1589,8 @@ fn check_missing_alt_text(path: &Path, lines: &[&str], results: &mut LintResults
                     rule: LintRule::MissingAltText.name().to_string(),
                     message: "missing alt text for image".to_string(),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
@@ -763,62 +1598,18 @@ fn check_missing_alt_text(path: &Path, lines: &[&str], results: &mut LintResults
 }
 
 /// Check for paragraphs that are too long.
-fn check_long_paragraphs(path: &Path, doc: &ParsedDoc, max_words: u32, results: &mut LintResults) {
-    // Process each section's content
-    for section in &doc.sections {
-        let content = &section.content;
-        let paragraph_start_line = section.start_line;
-        let mut paragraph_words = 0;
-        let mut tracker = CodeBlockTracker::new();
-        let mut paragraph_line_offset = 0;
-
-        for (offset, line) in content.lines().enumerate() {
-            // Track code blocks (handles language tags and nested fences)
-            tracker.process_line(line);
-
-            if tracker.in_code_block() {
-                continue;
-            }
-
-            // Empty line ends a paragraph
-            if line.trim().is_empty() {
-                if paragraph_words > max_words as usize {
-                    results.add_issue(LintIssue {
-                        file: path.to_path_buf(),
-                        line: paragraph_start_line + paragraph_line_offset,
-                        rule: LintRule::LongParagraphs.name().to_string(),
-                        message: format!(
-                            "long paragraph ({} words, max {})",
-                            paragraph_words, max_words
-                        ),
-                        fixable: false,
-                    });
-                }
-                paragraph_words = 0;
-                paragraph_line_offset = offset + 1;
-            } else {
-                // Skip headings and list items for word counting
-                if !line.starts_with('#')
-                    && !line.trim_start().starts_with('-')
-                    && !line.trim_start().starts_with('*')
-                    && !line.trim_start().starts_with(|c: char| c.is_ascii_digit())
-                {
-                    paragraph_words += line.split_whitespace().count();
-                }
-            }
-        }
-
-        // Check final paragraph
-        if paragraph_words > max_words as usize {
+fn check_long_paragraphs(path: &Path, lines: &[&str], max_words: u32, results: &mut LintResults) {
+    for block in prose_blocks(lines) {
+        let words = block.text.split_whitespace().count();
+        if block.heading.is_none() && words > max_words as usize {
             results.add_issue(LintIssue {
                 file: path.to_path_buf(),
-                line: paragraph_start_line + paragraph_line_offset,
+                line: block.line,
                 rule: LintRule::LongParagraphs.name().to_string(),
-                message: format!(
-                    "long paragraph ({} words, max {})",
-                    paragraph_words, max_words
-                ),
+                message: format!("long paragraph ({} words, max {})", words, max_words),
                 fixable: false,
+                docs_url: None,
+                blame: None,
             });
         }
     }
@@ -831,13 +1622,10 @@ fn check_duplicate_headings(path: &Path, lines: &[&str], results: &mut LintResul
     // Track headings by level: level -> (text -> line_number)
     let mut headings_by_level: HashMap<usize, HashMap<String, usize>> = HashMap::new();
 
-    let mut tracker = CodeBlockTracker::new();
+    let in_code = code_block_lines(lines);
 
     for (line_num, line) in lines.iter().enumerate() {
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(line);
-
-        if tracker.in_code_block() {
+        if in_code[line_num] {
             continue;
         }
 
@@ -858,6 +1646,8 @@ fn check_duplicate_headings(path: &Path, lines: &[&str], results: &mut LintResul
                         first_line
                     ),
                     fixable: false,
+                    docs_url: None,
+                    blame: None,
                 });
             } else {
                 level_headings.insert(text, line_num + 1);
@@ -866,21 +1656,395 @@ fn check_duplicate_headings(path: &Path, lines: &[&str], results: &mut LintResul
     }
 }
 
+/// Report documents among `files` whose H1 titles shadow others in `index`,
+/// unless they turn the rule off.
+fn check_duplicate_titles(
+    files: &[PathBuf],
+    index: &TitleIndex,
+    display_file: impl Fn(&Path) -> PathBuf,
+    results: &mut LintResults,
+) -> Result<()> {
+    let rule = LintRule::DuplicateTitles;
+    for file in files {
+        let Some(duplicate) = index.duplicate(file) else {
+            continue;
+        };
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let lines: Vec<&str> = content.lines().collect();
+        if !file_rules(&HashSet::from([rule]), &lines).contains(&rule) {
+            continue;
+        }
+        let others: Vec<String> = duplicate
+            .others
+            .iter()
+            .map(|other| display_file(other).display().to_string())
+            .collect();
+        results.add_issue(LintIssue {
+            file: file.clone(),
+            line: duplicate.line,
+            rule: rule.name().to_string(),
+            message: format!(
+                "duplicate title '{}' (also used by {})",
+                duplicate.title,
+                others.join(", ")
+            ),
+            fixable: false,
+            docs_url: None,
+            blame: None,
+        });
+    }
+    Ok(())
+}
+
+/// Decides which proposed fixes are applied.
+///
+/// Automatic fixers accept everything. Interactive fixers show each fix as a
+/// diff hunk and ask, like `git add -p`, remembering per-rule answers.
+pub(crate) struct Fixer {
+    /// Where to ask, or `None` to accept every fix.
+    prompt: Option<(Box<dyn BufRead>, Box<dyn Write>)>,
+    /// How to display file paths in prompts.
+    path_style: PathStyle,
+    /// Directory containing .pave.toml, for workspace paths.
+    project_root: PathBuf,
+    /// Directory relative paths are shown from.
+    cwd: PathBuf,
+    /// Rules whose remaining fixes are all accepted.
+    accept_rules: HashSet<LintRule>,
+    /// Rules whose remaining fixes are all skipped.
+    skip_rules: HashSet<LintRule>,
+    /// Set once the user quits; every later fix is skipped.
+    quit: bool,
+    /// Rules with at least one applied fix, for the post-fix check.
+    applied_rules: HashSet<LintRule>,
+}
+
+impl Fixer {
+    /// A fixer that applies every fix.
+    pub(crate) fn automatic() -> Self {
+        Self {
+            prompt: None,
+            path_style: PathStyle::Workspace,
+            project_root: PathBuf::new(),
+            cwd: PathBuf::new(),
+            accept_rules: HashSet::new(),
+            skip_rules: HashSet::new(),
+            quit: false,
+            applied_rules: HashSet::new(),
+        }
+    }
+
+    /// A fixer that asks about each fix.
+    pub(crate) fn interactive(
+        input: Box<dyn BufRead>,
+        output: Box<dyn Write>,
+        path_style: PathStyle,
+        project_root: &Path,
+        cwd: &Path,
+    ) -> Self {
+        Self {
+            prompt: Some((input, output)),
+            path_style,
+            project_root: project_root.to_path_buf(),
+            cwd: cwd.to_path_buf(),
+            ..Self::automatic()
+        }
+    }
+
+    /// Decide whether to replace line `line` of `path` with `after`.
+    ///
+    /// `before` and `after` may span several lines.
+    fn accept(
+        &mut self,
+        path: &Path,
+        line: usize,
+        rule: LintRule,
+        before: &str,
+        after: &str,
+    ) -> bool {
+        let accepted = self.decide(path, line, rule, before, after);
+        if accepted {
+            self.applied_rules.insert(rule);
+        }
+        accepted
+    }
+
+    fn decide(
+        &mut self,
+        path: &Path,
+        line: usize,
+        rule: LintRule,
+        before: &str,
+        after: &str,
+    ) -> bool {
+        let Some((input, output)) = self.prompt.as_mut() else {
+            return true;
+        };
+        if self.quit || self.skip_rules.contains(&rule) {
+            return false;
+        }
+        if self.accept_rules.contains(&rule) {
+            return true;
+        }
+
+        // Prompt failures are treated like quitting: nothing more is changed
+        let shown = display_path(path, self.path_style, &self.project_root, &self.cwd);
+        let _ = writeln!(output, "\n{}:{} ({})", shown.display(), line, rule.name());
+        for line in before.split('\n') {
+            let _ = writeln!(output, "-{}", show_whitespace(line));
+        }
+        for line in after.split('\n') {
+            let _ = writeln!(output, "+{}", show_whitespace(line));
+        }
+
+        loop {
+            let _ = write!(output, "Apply this fix [y,n,a,d,q,?]? ");
+            let _ = output.flush();
+
+            let mut answer = String::new();
+            if !matches!(input.read_line(&mut answer), Ok(n) if n > 0) {
+                self.quit = true;
+                return false;
+            }
+
+            match answer.trim() {
+                "y" => return true,
+                "n" => return false,
+                "a" => {
+                    self.accept_rules.insert(rule);
+                    return true;
+                }
+                "d" => {
+                    self.skip_rules.insert(rule);
+                    return false;
+                }
+                "q" => {
+                    self.quit = true;
+                    return false;
+                }
+                _ => {
+                    let _ = writeln!(
+                        output,
+                        "y - apply this fix\n\
+                         n - skip this fix\n\
+                         a - apply this and all remaining {rule} fixes\n\
+                         d - skip this and all remaining {rule} fixes\n\
+                         q - quit; skip this and all remaining fixes",
+                        rule = rule.name()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Make trailing whitespace visible in a diff hunk.
+fn show_whitespace(line: &str) -> String {
+    let content = line.trim_end();
+    let trailing: String = line[content.len()..]
+        .chars()
+        .map(|c| if c == '\t' { '→' } else { '·' })
+        .collect();
+    format!("{}{}", content, trailing)
+}
+
+/// H2 sections of the PAVED templates, which may hold H3 subsections.
+const PAVED_SECTIONS: &[&str] = &[
+    "Purpose",
+    "Paths",
+    "Interface",
+    "Configuration",
+    "Verification",
+    "Examples",
+    "Gotchas",
+    "Decisions",
+    "When to Use",
+    "Preconditions",
+    "Steps",
+    "Rollback",
+    "Escalation",
+    "Status",
+    "Context",
+    "Decision",
+    "Consequences",
+    "Alternatives Considered",
+];
+
+/// Check that headings nest: one H1, no skipped levels, and H3 and deeper
+/// only under a PAVED H2 section or one listed in `lint.sections`.
+fn check_heading_hierarchy(
+    path: &Path,
+    lines: &[&str],
+    extra_sections: &[String],
+    results: &mut LintResults,
+) {
+    let is_known_section = |name: &str| {
+        PAVED_SECTIONS
+            .iter()
+            .copied()
+            .chain(extra_sections.iter().map(String::as_str))
+            .any(|known| known.eq_ignore_ascii_case(name))
+    };
+    let mut issue = |line: usize, message: String| {
+        results.add_issue(LintIssue {
+            file: path.to_path_buf(),
+            line,
+            rule: LintRule::HeadingHierarchy.name().to_string(),
+            message,
+            fixable: false,
+            docs_url: None,
+            blame: None,
+        });
+    };
+
+    let mut first_h1: Option<usize> = None;
+    let mut previous_level = 0;
+    // The current H2 and whether its subsections were already reported
+    let mut section: Option<(&str, bool)> = None;
+    let blocks = prose_blocks(lines);
+    for block in &blocks {
+        let Some(level) = block.heading else {
+            continue;
+        };
+        let text = block.text.as_str();
+        let line_num = block.line;
+
+        if previous_level > 0 && level > previous_level + 1 {
+            issue(
+                line_num,
+                format!(
+                    "heading skips from H{} to H{}; use {} instead",
+                    previous_level,
+                    level,
+                    "#".repeat(previous_level + 1)
+                ),
+            );
+        }
+        previous_level = level;
+
+        match level {
+            1 => match first_h1 {
+                None => first_h1 = Some(line_num),
+                Some(first) => issue(
+                    line_num,
+                    format!(
+                        "extra H1 '{}' (the title is on line {}); use ## for sections",
+                        text, first
+                    ),
+                ),
+            },
+            2 => section = Some((text, false)),
+            _ => match &mut section {
+                None => issue(
+                    line_num,
+                    format!(
+                        "'{}' is not inside an H2 section; add a ## heading above it",
+                        text
+                    ),
+                ),
+                Some((name, reported)) if !*reported && !is_known_section(name) => {
+                    *reported = true;
+                    issue(
+                        line_num,
+                        format!(
+                            "'{}' is under '{}', which is not a PAVED section; \
+                             rename the section or add it to lint.sections",
+                            text, name
+                        ),
+                    );
+                }
+                Some(_) => {}
+            },
+        }
+    }
+
+    if first_h1.is_none() {
+        issue(
+            1,
+            "no H1 title; start the document with `# Title`".to_string(),
+        );
+    }
+}
+
+/// Fewest words a section needs for its readability to be judged; scores
+/// of shorter sections swing too much on a single sentence.
+const READABILITY_MIN_WORDS: usize = 30;
+
+/// The readability limits for a document type.
+fn readability_limits(doc_type: DocType, config: &ReadabilitySection) -> ReadabilityLimits {
+    let overrides = match doc_type {
+        DocType::Runbook => config.runbook,
+        DocType::Adr => config.adr,
+        DocType::Component => config.component,
+        DocType::Postmortem | DocType::Api | DocType::Other => ReadabilityLimits::default(),
+    };
+    overrides.or(config.limits)
+}
+
+/// Check each section's sentence length and grade level against the limits.
+fn check_readability(
+    path: &Path,
+    doc: &ParsedDoc,
+    limits: ReadabilityLimits,
+    results: &mut LintResults,
+) {
+    for section in &doc.sections {
+        let readability = Readability::measure(&section.content);
+        if readability.words < READABILITY_MIN_WORDS {
+            continue;
+        }
+
+        let mut problems = Vec::new();
+        if let Some(max) = limits.max_sentence_words
+            && readability.sentence_words() > f64::from(max)
+        {
+            problems.push(format!(
+                "averages {:.1} words per sentence (max {})",
+                readability.sentence_words(),
+                max
+            ));
+        }
+        if let Some(max) = limits.max_grade_level
+            && readability.grade_level() > max
+        {
+            problems.push(format!(
+                "reads at grade level {:.1} (max {})",
+                readability.grade_level(),
+                max
+            ));
+        }
+        if !problems.is_empty() {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line: section.start_line,
+                rule: LintRule::Readability.name().to_string(),
+                message: format!("section '{}' {}", section.name, problems.join(" and ")),
+                fixable: false,
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+}
+
 /// Check for trailing whitespace.
 fn check_trailing_whitespace(
     path: &Path,
     lines: &[&str],
-    fix: bool,
+    mut fixer: Option<&mut Fixer>,
     fixed_lines: &mut Option<Vec<String>>,
     results: &mut LintResults,
 ) {
+    let rule = LintRule::TrailingWhitespace;
     for (line_num, line) in lines.iter().enumerate() {
         if line.ends_with(' ') || line.ends_with('\t') {
-            if fix {
-                if let Some(fixed) = fixed_lines {
-                    fixed[line_num] = line.trim_end().to_string();
-                    results.fixed_count += 1;
-                }
+            let trimmed = line.trim_end();
+            if let (Some(fixer), Some(fixed)) = (fixer.as_deref_mut(), fixed_lines.as_mut())
+                && fixer.accept(path, line_num + 1, rule, line, trimmed)
+            {
+                fixed[line_num] = trimmed.to_string();
+                results.fixed_count += 1;
             } else {
                 results.add_issue(LintIssue {
                     file: path.to_path_buf(),
@@ -888,12 +2052,189 @@ fn check_trailing_whitespace(
                     rule: LintRule::TrailingWhitespace.name().to_string(),
                     message: "trailing whitespace".to_string(),
                     fixable: true,
+                    docs_url: None,
+                    blame: None,
                 });
             }
         }
     }
 }
 
+/// Check prose for terms `[lint.terminology]` replaces.
+///
+/// Code blocks and frontmatter are skipped; see [`Terminology`] for what's
+/// skipped within a line.
+fn check_terminology(
+    path: &Path,
+    lines: &[&str],
+    terminology: &Terminology,
+    mut fixer: Option<&mut Fixer>,
+    fixed_lines: &mut Option<Vec<String>>,
+    results: &mut LintResults,
+) {
+    let rule = LintRule::Terminology;
+    let body_start = frontmatter_len(lines);
+    let in_code = code_block_lines(lines);
+    for (line_num, line) in lines.iter().enumerate().skip(body_start) {
+        if in_code[line_num] {
+            continue;
+        }
+        let matches = terminology.find(line);
+        if matches.is_empty() {
+            continue;
+        }
+
+        // Fix the line as earlier fixes left it
+        if let (Some(fixer), Some(fixed)) = (fixer.as_deref_mut(), fixed_lines.as_mut()) {
+            let replaced = terminology.replace(&fixed[line_num]);
+            if fixer.accept(path, line_num + 1, rule, &fixed[line_num], &replaced) {
+                fixed[line_num] = replaced;
+                results.fixed_count += matches.len();
+                continue;
+            }
+        }
+
+        for term in matches {
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line: line_num + 1,
+                rule: rule.name().to_string(),
+                message: format!("'{}' should be '{}'", term.found, term.replacement),
+                fixable: true,
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+}
+
+/// Check prose for words missing from the spelling dictionaries.
+fn check_spelling(path: &Path, lines: &[&str], dictionary: &Dictionary, results: &mut LintResults) {
+    let in_code = code_block_lines(lines);
+    for (line_num, line) in lines.iter().enumerate().skip(frontmatter_len(lines)) {
+        if in_code[line_num] {
+            continue;
+        }
+        for word in dictionary.unknown_words(line) {
+            let suggestions: Vec<String> = dictionary
+                .suggest(&word)
+                .iter()
+                .map(|s| format!("'{}'", s))
+                .collect();
+            let message = if suggestions.is_empty() {
+                format!("unknown word '{}'", word)
+            } else {
+                format!(
+                    "unknown word '{}' (did you mean {}?)",
+                    word,
+                    suggestions.join(", ")
+                )
+            };
+            results.add_issue(LintIssue {
+                file: path.to_path_buf(),
+                line: line_num + 1,
+                rule: LintRule::Spelling.name().to_string(),
+                message,
+                fixable: false,
+                docs_url: None,
+                blame: None,
+            });
+        }
+    }
+}
+
+/// Pick the callout style most of the files use.
+///
+/// Ties go to the earlier style in [`AdmonitionStyle::ALL`]. Returns `None`
+/// if the files have no callouts.
+fn detect_admonition_style(files: &[PathBuf]) -> Result<Option<AdmonitionStyle>> {
+    let mut counts: HashMap<AdmonitionStyle, usize> = HashMap::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let lines: Vec<&str> = content.lines().collect();
+        for admonition in find_admonitions(&lines) {
+            *counts.entry(admonition.style).or_default() += 1;
+        }
+    }
+
+    let mut best: Option<(AdmonitionStyle, usize)> = None;
+    for &style in AdmonitionStyle::ALL {
+        let count = counts.get(&style).copied().unwrap_or(0);
+        if count > 0 && best.is_none_or(|(_, most)| count > most) {
+            best = Some((style, count));
+        }
+    }
+    Ok(best.map(|(style, _)| style))
+}
+
+/// Check that callouts use the repository's style.
+///
+/// Fixes rewrite whole callouts, so this must run after every rule that
+/// fixes single lines.
+fn check_admonition_style(
+    path: &Path,
+    lines: &[&str],
+    style: AdmonitionStyle,
+    mut fixer: Option<&mut Fixer>,
+    fixed_lines: &mut Option<Vec<String>>,
+    results: &mut LintResults,
+) {
+    let rule = LintRule::AdmonitionStyle;
+    let mut replacements = Vec::new();
+    for admonition in find_admonitions(lines) {
+        if admonition.style == style {
+            continue;
+        }
+
+        // Rewrite the callout as earlier fixes left it
+        let converted = fixed_lines.as_ref().and_then(|fixed| {
+            let current: Vec<&str> = fixed[admonition.start..admonition.end]
+                .iter()
+                .map(String::as_str)
+                .collect();
+            let before = current.join("\n");
+            let after = find_admonitions(&current).first()?.render(style)?;
+            Some((before, after))
+        });
+        if let (Some(fixer), Some((before, after))) = (fixer.as_deref_mut(), converted)
+            && fixer.accept(path, admonition.start + 1, rule, &before, &after.join("\n"))
+        {
+            replacements.push((admonition.start..admonition.end, after));
+            results.fixed_count += 1;
+            continue;
+        }
+
+        let fixable = admonition.render(style).is_some();
+        results.add_issue(LintIssue {
+            file: path.to_path_buf(),
+            line: admonition.start + 1,
+            rule: rule.name().to_string(),
+            message: format!(
+                "{} callout uses {} style; use {} style (`{}`){}",
+                admonition.kind,
+                admonition.style.name(),
+                style.name(),
+                admonition.opening(style),
+                if fixable {
+                    ""
+                } else {
+                    " (its title can't be converted automatically)"
+                }
+            ),
+            fixable,
+            docs_url: None,
+            blame: None,
+        });
+    }
+
+    if let Some(fixed) = fixed_lines.as_mut() {
+        for (range, after) in replacements.into_iter().rev() {
+            fixed.splice(range, after);
+        }
+    }
+}
+
 /// Output results in text format.
 fn output_text(results: &LintResults, fix_mode: bool) {
     let issues_by_file = results.issues_by_file();
@@ -904,14 +2245,28 @@ fn output_text(results: &LintResults, fix_mode: bool) {
 
     for file in files {
         let issues = &issues_by_file[file];
-        println!("{}", file.display());
+        println!("{}", color::bold(file.display()));
 
         // Sort issues by line number
         let mut sorted_issues: Vec<_> = issues.iter().collect();
         sorted_issues.sort_by_key(|i| i.line);
+        let width = sorted_issues.iter().map(|i| i.line.to_string().len());
+        let width = width.max().unwrap_or(1);
+        // Blame lines up under the message
+        let indent = " ".repeat(2 + width + 2 + "warning".len() + 2);
 
         for issue in sorted_issues {
-            println!("  line {}: {}", issue.line, issue.message);
+            let fixable = if issue.fixable { " (fixable)" } else { "" };
+            println!(
+                "  {:>width$}  {}  {}  {}",
+                issue.line,
+                color::yellow("warning"),
+                issue.message,
+                color::dim(format!("{}{}", issue.rule, fixable))
+            );
+            if let Some(blame) = &issue.blame {
+                println!("{}{}", indent, color::dim(blame.describe()));
+            }
         }
         println!();
     }
@@ -922,15 +2277,19 @@ fn output_text(results: &LintResults, fix_mode: bool) {
 
     if issue_count == 0 {
         println!(
-            "Linted {} file{}: no issues found",
+            "Linted {} file{}: {}",
             results.files_linted,
-            if results.files_linted == 1 { "" } else { "s" }
+            if results.files_linted == 1 { "" } else { "s" },
+            color::green("no issues found")
         );
     } else {
         println!(
-            "Found {} issue{} in {} file{}.",
-            issue_count,
-            if issue_count == 1 { "" } else { "s" },
+            "Found {} in {} file{}.",
+            color::yellow(format!(
+                "{} issue{}",
+                issue_count,
+                if issue_count == 1 { "" } else { "s" }
+            )),
             issues_by_file.len(),
             if issues_by_file.len() == 1 { "" } else { "s" }
         );
@@ -949,6 +2308,39 @@ fn output_text(results: &LintResults, fix_mode: bool) {
             );
         }
     }
+
+    if let Some(post_fix) = &results.post_fix {
+        let remaining = post_fix.issues.len();
+        print!(
+            "Post-fix check: re-ran {} on {} fixed file{}, ",
+            post_fix.rules.join(", "),
+            post_fix.files_checked,
+            if post_fix.files_checked == 1 { "" } else { "s" }
+        );
+        if remaining == 0 {
+            println!("all fixes verified.");
+        } else {
+            println!(
+                "{} issue{} remain{}:",
+                remaining,
+                if remaining == 1 { "" } else { "s" },
+                if remaining == 1 { "s" } else { "" }
+            );
+            for issue in &post_fix.issues {
+                println!(
+                    "  {}:{}: {}",
+                    issue.file.display(),
+                    issue.line,
+                    issue.message
+                );
+            }
+        }
+    }
+
+    if let Some(authors) = results.authors.as_ref().filter(|a| !a.is_empty()) {
+        println!();
+        blame::print_authors(authors);
+    }
 }
 
 /// Output results in JSON format.
@@ -962,17 +2354,42 @@ fn output_json(results: &LintResults) -> Result<()> {
 fn output_github(results: &LintResults) {
     for issue in &results.issues {
         println!(
-            "::warning file={},line={}::{}",
+            "::warning file={},line={}::{}{}{}",
+            issue.file.display(),
+            issue.line,
+            issue.message,
+            docs_suffix(issue),
+            issue
+                .blame
+                .as_ref()
+                .map(|blame| format!(" ({})", blame.describe()))
+                .unwrap_or_default()
+        );
+    }
+    for issue in results.post_fix.iter().flat_map(|p| &p.issues) {
+        println!(
+            "::warning file={},line={}::{} (still present after --fix){}",
             issue.file.display(),
             issue.line,
-            issue.message
+            issue.message,
+            docs_suffix(issue)
         );
     }
 }
 
+/// Link to an issue's rule documentation, appended to annotation messages.
+fn docs_suffix(issue: &LintIssue) -> String {
+    issue
+        .docs_url
+        .as_ref()
+        .map(|url| format!(" ({})", url))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
     use std::fs;
     use tempfile::TempDir;
 
@@ -1064,45 +2481,194 @@ See [missing section](#nonexistent) for details.
     }
 
     #[test]
-    fn test_valid_anchors() {
+    fn test_valid_anchors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_doc(
+            &temp_dir,
+            "test.md",
+            r#"# Test
+## Introduction
+See [intro](#introduction) for details.
+"#,
+        );
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut results = LintResults::new();
+
+        check_dead_anchors(&path, &content, &lines, &mut results);
+
+        assert!(results.issues.is_empty());
+    }
+
+    #[test]
+    fn test_extract_external_links() {
+        let lines = vec![
+            "See [docs](https://example.com/docs) and <http://example.org>.",
+            "Local [link](./other.md) is not external.",
+            "```",
+            "curl https://example.com/in-code",
+            "```",
+            "[a](https://a.example) [b](https://b.example#frag)",
+        ];
+
+        let links = extract_external_links(&lines);
+
+        assert_eq!(
+            links,
+            vec![
+                (1, "https://example.com/docs".to_string()),
+                (1, "http://example.org".to_string()),
+                (6, "https://a.example".to_string()),
+                (6, "https://b.example#frag".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_code_refs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_doc(
+            &temp_dir,
+            "test.md",
+            r#"# Test
+See `src/missing.rs` for the implementation.
+"#,
+        );
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut results = LintResults::new();
+
+        check_stale_code_refs(&path, &lines, temp_dir.path(), &mut results);
+
+        assert_eq!(results.issues.len(), 1);
+        assert!(results.issues[0].message.contains("src/missing.rs"));
+    }
+
+    #[test]
+    fn test_interface_drift() {
         let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("cli.rs"),
+            "pub struct ServeArgs {\n    #[arg(long)]\n    port: u16,\n    \
+             #[arg(long = \"dry-run\")]\n    dry: bool,\n}\npub fn serve() {}\n",
+        )
+        .unwrap();
         let path = create_test_doc(
             &temp_dir,
-            "test.md",
-            r#"# Test
-## Introduction
-See [intro](#introduction) for details.
+            "serve.md",
+            r#"---
+pave:
+  interface:
+    source: [src/cli.rs]
+    item: ServeArgs
+---
+# Serve
+
+## Interface
+
+Run `serve()` with `--port <N>`.
+`--watch` reloads on change, and `reload()` restarts.
 "#,
         );
-
         let content = fs::read_to_string(&path).unwrap();
-        let lines: Vec<&str> = content.lines().collect();
+        let doc = ParsedDoc::parse_content(path.clone(), &content).unwrap();
+        let interface = doc.frontmatter.as_ref().unwrap().interface.clone().unwrap();
         let mut results = LintResults::new();
 
-        check_dead_anchors(&path, &content, &lines, &mut results);
+        let help_cmds = BTreeMap::new();
+        check_interface_drift(
+            &path,
+            &doc,
+            &interface,
+            &help_cmds,
+            temp_dir.path(),
+            &mut results,
+        )
+        .unwrap();
 
-        assert!(results.issues.is_empty());
+        let issues: Vec<(usize, &str)> = results
+            .issues
+            .iter()
+            .map(|issue| (issue.line, issue.message.as_str()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (12, "'--watch' is documented but not in the source"),
+                (9, "undocumented option(s): --dry-run"),
+                (12, "'reload()' is documented but not defined in the source"),
+            ]
+        );
+
+        // A help command from the config replaces the source's flags
+        let interface = InterfaceSource {
+            help_cmd: Some("serve".to_string()),
+            ..interface
+        };
+        let mut results = LintResults::new();
+        check_interface_drift(
+            &path,
+            &doc,
+            &interface,
+            &help_cmds,
+            temp_dir.path(),
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.issues.len(), 1, "{:?}", results.issues);
+        assert!(
+            results.issues[0]
+                .message
+                .contains("'serve' is not in [lint.help_cmds]")
+        );
+
+        let help_cmds = BTreeMap::from([(
+            "serve".to_string(),
+            "printf '  --port <N>\\n  --watch\\n'".to_string(),
+        )]);
+        let mut results = LintResults::new();
+        check_interface_drift(
+            &path,
+            &doc,
+            &interface,
+            &help_cmds,
+            temp_dir.path(),
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.issues.len(), 1, "{:?}", results.issues);
+        assert!(results.issues[0].message.contains("reload()"));
     }
 
     #[test]
-    fn test_stale_code_refs() {
+    fn test_mermaid() {
         let temp_dir = TempDir::new().unwrap();
         let path = create_test_doc(
             &temp_dir,
-            "test.md",
-            r#"# Test
-See `src/missing.rs` for the implementation.
-"#,
+            "flow.md",
+            "# Flow\n\n```mermaid\ngraph TD\n  A[Start --> B\n```\n\n\
+             ```mermaid\nsequenceDiagram\n  A->>B: hi\n```\n",
         );
-
         let content = fs::read_to_string(&path).unwrap();
         let lines: Vec<&str> = content.lines().collect();
-        let mut results = LintResults::new();
 
-        check_stale_code_refs(&path, &lines, temp_dir.path(), &mut results);
+        let mut results = LintResults::new();
+        check_mermaid(&path, &lines, None, &mut results).unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].line, 5);
+        assert_eq!(results.issues[0].message, "mermaid: unclosed '['");
 
+        // A configured command replaces the built-in checks
+        let mut results = LintResults::new();
+        let command = "grep -q sequenceDiagram || { echo 'Parse error on line 2' >&2; exit 1; }";
+        check_mermaid(&path, &lines, Some(command), &mut results).unwrap();
         assert_eq!(results.issues.len(), 1);
-        assert!(results.issues[0].message.contains("src/missing.rs"));
+        assert_eq!(results.issues[0].line, 5);
+        assert_eq!(results.issues[0].message, "mermaid: Parse error on line 2");
     }
 
     #[test]
@@ -1230,7 +2796,7 @@ Second section.
         let mut results = LintResults::new();
         let mut fixed_lines: Option<Vec<String>> = None;
 
-        check_trailing_whitespace(&path, &lines, false, &mut fixed_lines, &mut results);
+        check_trailing_whitespace(&path, &lines, None, &mut fixed_lines, &mut results);
 
         assert_eq!(results.issues.len(), 2);
         assert!(results.issues[0].fixable);
@@ -1247,7 +2813,13 @@ Second section.
         let mut fixed_lines: Option<Vec<String>> =
             Some(lines.iter().map(|s| s.to_string()).collect());
 
-        check_trailing_whitespace(&path, &lines, true, &mut fixed_lines, &mut results);
+        check_trailing_whitespace(
+            &path,
+            &lines,
+            Some(&mut Fixer::automatic()),
+            &mut fixed_lines,
+            &mut results,
+        );
 
         assert_eq!(results.fixed_count, 2);
         let fixed = fixed_lines.unwrap();
@@ -1255,6 +2827,519 @@ Second section.
         assert_eq!(fixed[1], "Some text.");
     }
 
+    #[test]
+    fn test_cached_lint_rechecks_code_refs() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = temp_dir.path().join("src/engine.rs");
+        fs::create_dir_all(engine.parent().unwrap()).unwrap();
+        fs::write(&engine, "pub fn run() {}\n").unwrap();
+        let path = create_test_doc(&temp_dir, "test.md", "# Test\n\nSee `src/engine.rs`.\n");
+        let rules = HashSet::from([LintRule::StaleCodeRefs, LintRule::TrailingWhitespace]);
+        let context = LintContext::new(LintSection::default(), &rules, temp_dir.path()).unwrap();
+        let lint = || {
+            let cache = Mutex::new(ResultCache::load(
+                temp_dir.path(),
+                LINT_CACHE_PATH,
+                "test".to_string(),
+                true,
+            ));
+            let mut results = LintResults::new();
+            lint_file_cached(
+                &path,
+                &rules,
+                &context,
+                temp_dir.path(),
+                &cache,
+                &mut results,
+            )
+            .unwrap();
+            cache.into_inner().unwrap().save().unwrap();
+            results.issues
+        };
+
+        assert!(lint().is_empty());
+        fs::remove_file(&engine).unwrap();
+        let issues = lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "stale-code-refs");
+    }
+
+    #[test]
+    fn test_recheck_fixed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_doc(&temp_dir, "test.md", "# Test \nSome text.  \n");
+        let mut fixer = Fixer::automatic();
+        let rules = HashSet::from([LintRule::TrailingWhitespace]);
+        let context = LintContext::new(LintSection::default(), &rules, temp_dir.path()).unwrap();
+        let mut results = LintResults::new();
+
+        let fixed = lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            Some(&mut fixer),
+            &mut results,
+        )
+        .unwrap();
+        assert!(fixed);
+        assert_eq!(fixer.applied_rules, rules);
+
+        let post_fix = recheck_fixed_files(
+            std::slice::from_ref(&path),
+            &fixer.applied_rules,
+            &results.issues,
+            &context,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(post_fix.files_checked, 1);
+        assert_eq!(post_fix.rules, vec!["trailing-whitespace"]);
+        assert!(post_fix.issues.is_empty());
+
+        // A fix that didn't take is reported
+        fs::write(&path, "# Test\nSome text. \n").unwrap();
+        let post_fix = recheck_fixed_files(
+            &[path],
+            &fixer.applied_rules,
+            &results.issues,
+            &context,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(post_fix.issues.len(), 1);
+        assert_eq!(post_fix.issues[0].line, 2);
+    }
+
+    #[test]
+    fn test_recheck_fixed_files_skips_declined_fixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_doc(&temp_dir, "test.md", "# Test \nSome text.  \n");
+        // Apply the first fix and skip the second
+        let mut fixer = Fixer::interactive(
+            Box::new(io::Cursor::new("y\nn\n")),
+            Box::new(Vec::new()),
+            PathStyle::Workspace,
+            temp_dir.path(),
+            temp_dir.path(),
+        );
+        let rules = HashSet::from([LintRule::TrailingWhitespace]);
+        let context = LintContext::new(LintSection::default(), &rules, temp_dir.path()).unwrap();
+        let mut results = LintResults::new();
+
+        let fixed = lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            Some(&mut fixer),
+            &mut results,
+        )
+        .unwrap();
+        assert!(fixed);
+        assert_eq!(results.issues.len(), 1);
+
+        let post_fix = recheck_fixed_files(
+            &[path],
+            &fixer.applied_rules,
+            &results.issues,
+            &context,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert!(post_fix.issues.is_empty());
+    }
+
+    #[test]
+    fn test_admonition_style_detects_majority_and_fixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = create_test_doc(
+            &temp_dir,
+            "a.md",
+            "# A\n\n> [!NOTE]\n> One.\n\n> [!TIP]\n> Two.\n",
+        );
+        let b = create_test_doc(
+            &temp_dir,
+            "b.md",
+            "# B\n\n:::warning\nHot. \n:::\n\nText.\n\n:::note Title\nKept.\n:::\n",
+        );
+        let style = detect_admonition_style(&[a, b.clone()]).unwrap();
+        assert_eq!(style, Some(AdmonitionStyle::Github));
+
+        let config = LintSection {
+            admonition_style: style,
+            ..Default::default()
+        };
+        let rules = HashSet::from([LintRule::AdmonitionStyle, LintRule::TrailingWhitespace]);
+        let context = LintContext::new(config, &rules, temp_dir.path()).unwrap();
+        let mut results = LintResults::new();
+        let fixed = lint_file(
+            &b,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            Some(&mut Fixer::automatic()),
+            &mut results,
+        )
+        .unwrap();
+
+        assert!(fixed);
+        assert_eq!(results.fixed_count, 2);
+        assert_eq!(
+            fs::read_to_string(&b).unwrap(),
+            "# B\n\n> [!WARNING]\n> Hot.\n\nText.\n\n:::note Title\nKept.\n:::\n"
+        );
+        // A titled directive can't become a GitHub alert
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].line, 9);
+        assert!(!results.issues[0].fixable);
+        assert_eq!(
+            results.issues[0].message,
+            "note callout uses directive style; use github style (`> [!NOTE]`) \
+             (its title can't be converted automatically)"
+        );
+    }
+
+    #[test]
+    fn test_terminology_flags_and_fixes_prose() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "---\npave:\n  owner: front-end\n---\n# Front-end on K8s\n\n```sh\nkubectl --context k8s apply\n```\n\nDeploy the front-end. \n";
+        let path = create_test_doc(&temp_dir, "test.md", content);
+        let config = LintSection {
+            terminology: BTreeMap::from([
+                ("front-end".to_string(), "frontend".to_string()),
+                ("K8s".to_string(), "Kubernetes".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let rules = HashSet::from([LintRule::Terminology, LintRule::TrailingWhitespace]);
+        let context = LintContext::new(config, &rules, temp_dir.path()).unwrap();
+
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        let messages: Vec<(usize, &str)> = results
+            .issues
+            .iter()
+            .filter(|i| i.rule == "terminology")
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (5, "'Front-end' should be 'Frontend'"),
+                (5, "'K8s' should be 'Kubernetes'"),
+                (11, "'front-end' should be 'frontend'"),
+            ]
+        );
+
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            Some(&mut Fixer::automatic()),
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.fixed_count, 4);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            content
+                .replace("# Front-end on K8s", "# Frontend on Kubernetes")
+                .replace("the front-end. ", "the frontend.")
+        );
+    }
+
+    #[test]
+    fn test_spelling_reports_unknown_words_unless_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("words.txt"),
+            "the\nsetup\nruns\nfirst\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join(".pave-dictionary.txt"), "pave\n").unwrap();
+        let config = LintSection {
+            spelling_dictionaries: vec![PathBuf::from("words.txt")],
+            ..Default::default()
+        };
+        let rules = HashSet::from([LintRule::Spelling, LintRule::TrailingWhitespace]);
+        let context = LintContext::new(config, &rules, temp_dir.path()).unwrap();
+
+        let path = create_test_doc(
+            &temp_dir,
+            "test.md",
+            "# Pave\n\nThe setpu runs frist.\n\n```sh\nsetpu --now\n```\n",
+        );
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        let messages: Vec<(usize, &str)> = results
+            .issues
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (3, "unknown word 'setpu' (did you mean 'setup'?)"),
+                (3, "unknown word 'frist' (did you mean 'first'?)"),
+            ]
+        );
+
+        let path = create_test_doc(
+            &temp_dir,
+            "quoted.md",
+            "<!-- pave-lint-disable spelling -->\n# Quoted\n\nTeh setpu. \n",
+        );
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].rule, "trailing-whitespace");
+
+        fs::write(&path, "<!-- pave-lint-disable -->\nTeh setpu. \n").unwrap();
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        assert!(results.issues.is_empty());
+    }
+
+    #[test]
+    fn test_readability_uses_limits_for_doc_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "# Restart\n\n## Background\n\n\
+            Operators should carefully investigate the authentication infrastructure, \
+            verifying that every intermediate certificate authority remains operational \
+            and that all dependent services have consistently reported healthy status \
+            before restarting anything in production environments.\n\n\
+            ## Notes\n\n\
+            Stop the job. Wait a bit. Then start it. Check the logs. Call the team if it fails. \
+            Write down what you saw. Close the page when you are done.\n";
+        let path = create_test_doc(&temp_dir, "runbook-restart.md", content);
+        let mut config = LintSection::default();
+        config.readability.limits.max_grade_level = Some(16.0);
+        config.readability.runbook.max_sentence_words = Some(20);
+        let rules = HashSet::from([LintRule::Readability]);
+        let context = LintContext::new(config, &rules, temp_dir.path()).unwrap();
+
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].line, 3);
+        assert!(
+            results.issues[0].message.starts_with(
+                "section 'Background' averages 31.0 words per sentence (max 20) and reads at grade level"
+            ),
+            "{}",
+            results.issues[0].message
+        );
+
+        // Outside runbooks, only the shared grade limit applies
+        let path = create_test_doc(&temp_dir, "guide.md", content);
+        let mut results = LintResults::new();
+        lint_file(
+            &path,
+            &rules,
+            &context,
+            temp_dir.path(),
+            None,
+            None,
+            &mut results,
+        )
+        .unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert!(
+            results.issues[0]
+                .message
+                .starts_with("section 'Background' reads at grade level")
+        );
+    }
+
+    #[test]
+    fn test_long_paragraphs_follow_markdown_structure() {
+        let path = PathBuf::from("test.md");
+        let long = vec!["word"; 20].join(" ");
+        let row = format!("| {} | {} |", long, long);
+        let content = format!(
+            "# Doc\n\n| a | b |\n|---|---|\n{row}\n{row}\n\n<div>\n#### Hidden\n</div>\n\n\
+             ## Purpose\n\n{long}\n{long}\n"
+        );
+        let lines: Vec<&str> = content.lines().collect();
+
+        let mut results = LintResults::new();
+        check_long_paragraphs(&path, &lines, 30, &mut results);
+        let issues: Vec<(usize, &str)> = results
+            .issues
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(issues, vec![(14, "long paragraph (40 words, max 30)")]);
+
+        let mut results = LintResults::new();
+        check_heading_hierarchy(&path, &lines, &[], &mut results);
+        assert!(results.issues.is_empty(), "{:?}", results.issues);
+    }
+
+    #[test]
+    fn test_heading_hierarchy() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "---\ntitle: x\n---\n### Orphan\n\n# Title\n\n## Purpose\n\n#### Deep\n\n\
+            ## Notes\n\n### One\n\n### Two\n\n```md\n# Not a heading\n```\n\n# Second\n";
+        let path = create_test_doc(&temp_dir, "test.md", content);
+
+        let mut results = LintResults::new();
+        check_heading_hierarchy(
+            &path,
+            &content.lines().collect::<Vec<_>>(),
+            &[],
+            &mut results,
+        );
+        let issues: Vec<(usize, &str)> = results
+            .issues
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    4,
+                    "'Orphan' is not inside an H2 section; add a ## heading above it"
+                ),
+                (10, "heading skips from H2 to H4; use ### instead"),
+                (
+                    14,
+                    "'One' is under 'Notes', which is not a PAVED section; \
+                     rename the section or add it to lint.sections"
+                ),
+                (
+                    22,
+                    "extra H1 'Second' (the title is on line 6); use ## for sections"
+                ),
+            ]
+        );
+
+        let mut results = LintResults::new();
+        let lines = ["## Notes", "", "### One"];
+        check_heading_hierarchy(&path, &lines, &["notes".to_string()], &mut results);
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(
+            results.issues[0].message,
+            "no H1 title; start the document with `# Title`"
+        );
+    }
+
+    fn fix_interactively(content: &str, answers: &str) -> (Vec<String>, LintResults) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_doc(&temp_dir, "test.md", content);
+        let lines: Vec<&str> = content.lines().collect();
+        let mut results = LintResults::new();
+        let mut fixed_lines = Some(lines.iter().map(|s| s.to_string()).collect());
+        let mut fixer = Fixer::interactive(
+            Box::new(io::Cursor::new(answers.as_bytes().to_vec())),
+            Box::new(io::sink()),
+            PathStyle::Workspace,
+            temp_dir.path(),
+            temp_dir.path(),
+        );
+
+        check_trailing_whitespace(
+            &path,
+            &lines,
+            Some(&mut fixer),
+            &mut fixed_lines,
+            &mut results,
+        );
+
+        (fixed_lines.unwrap(), results)
+    }
+
+    #[test]
+    fn test_interactive_fix_applies_only_accepted_fixes() {
+        let (fixed, results) = fix_interactively("one \ntwo \nthree \n", "n\n?\ny\nq\n");
+
+        assert_eq!(fixed, vec!["one ", "two", "three "]);
+        assert_eq!(results.fixed_count, 1);
+        // Skipped fixes are still reported
+        assert_eq!(results.issues.len(), 2);
+        assert_eq!(results.issues[0].line, 1);
+        assert_eq!(results.issues[1].line, 3);
+    }
+
+    #[test]
+    fn test_interactive_fix_remembers_per_rule_answers() {
+        let (fixed, results) = fix_interactively("one \ntwo \nthree \n", "a\n");
+        assert_eq!(fixed, vec!["one", "two", "three"]);
+        assert_eq!(results.fixed_count, 3);
+
+        let (fixed, results) = fix_interactively("one \ntwo \nthree \n", "y\nd\n");
+        assert_eq!(fixed, vec!["one", "two ", "three "]);
+        assert_eq!(results.issues.len(), 2);
+    }
+
+    #[test]
+    fn test_interactive_fix_skips_everything_at_end_of_input() {
+        let (fixed, results) = fix_interactively("one \ntwo \n", "");
+
+        assert_eq!(fixed, vec!["one ", "two "]);
+        assert_eq!(results.fixed_count, 0);
+        assert_eq!(results.issues.len(), 2);
+    }
+
+    #[test]
+    fn test_show_whitespace() {
+        assert_eq!(show_whitespace("text \t "), "text·→·");
+        assert_eq!(show_whitespace("a b"), "a b");
+    }
+
     #[test]
     fn test_lint_rule_from_name() {
         assert_eq!(
@@ -1273,9 +3358,16 @@ Second section.
             fix: false,
             rules: None,
             external_links: false,
+            max_warnings: 0,
+            path_style: PathStyle::Workspace,
+            interactive: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
-        let rules = determine_rules(&args, &config).unwrap();
+        let rules = determine_rules(args.rules.as_deref(), &config, &BTreeMap::new()).unwrap();
         assert_eq!(rules.len(), LintRule::all().len());
     }
 
@@ -1288,9 +3380,16 @@ Second section.
             fix: false,
             rules: Some("broken-internal-links,trailing-whitespace".to_string()),
             external_links: false,
+            max_warnings: 0,
+            path_style: PathStyle::Workspace,
+            interactive: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
-        let rules = determine_rules(&args, &config).unwrap();
+        let rules = determine_rules(args.rules.as_deref(), &config, &BTreeMap::new()).unwrap();
         assert_eq!(rules.len(), 2);
         assert!(rules.contains(&LintRule::BrokenInternalLinks));
         assert!(rules.contains(&LintRule::TrailingWhitespace));
@@ -1309,9 +3408,16 @@ Second section.
             fix: false,
             rules: None,
             external_links: false,
+            max_warnings: 0,
+            path_style: PathStyle::Workspace,
+            interactive: false,
+            no_cache: false,
+            jobs: None,
+            blame: false,
+            group_by: None,
         };
 
-        let rules = determine_rules(&args, &config).unwrap();
+        let rules = determine_rules(args.rules.as_deref(), &config, &BTreeMap::new()).unwrap();
         assert!(!rules.contains(&LintRule::LongParagraphs));
     }
 
@@ -1325,6 +3431,8 @@ Second section.
             rule: "broken-internal-links".to_string(),
             message: "broken link".to_string(),
             fixable: false,
+            docs_url: rule_docs::docs_url(None, "broken-internal-links"),
+            blame: None,
         });
 
         let json = serde_json::to_string(&results).unwrap();
@@ -1332,6 +3440,45 @@ Second section.
 
         assert_eq!(parsed["files_linted"], 1);
         assert_eq!(parsed["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            parsed["issues"][0]["docs_url"],
+            "https://tessro.github.io/pave/docs/rules/#broken-internal-links"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_titles_respect_disable_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = create_test_doc(&temp_dir, "a.md", "# Same\n");
+        let b = create_test_doc(&temp_dir, "b.md", "# Same\n");
+        let c = create_test_doc(
+            &temp_dir,
+            "c.md",
+            "<!-- pave-lint-disable duplicate-titles -->\n# Same\n",
+        );
+        let files = vec![a, b.clone(), c];
+        let index = TitleIndex::new(&files, temp_dir.path(), None).unwrap();
+
+        let mut results = LintResults::new();
+        check_duplicate_titles(&files, &index, Path::to_path_buf, &mut results).unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].file, b);
+        assert!(results.issues[0].message.contains("a.md"));
+    }
+
+    #[test]
+    fn test_every_lint_rule_is_documented() {
+        for rule in LintRule::all()
+            .iter()
+            .map(|rule| rule.name())
+            .chain([EXTERNAL_LINKS_RULE])
+        {
+            assert!(
+                rule_docs::lookup(rule).is_some(),
+                "{} is undocumented",
+                rule
+            );
+        }
     }
 
     #[test]
@@ -1376,6 +3523,26 @@ And [another link](another-missing.md) outside.
         );
     }
 
+    #[test]
+    fn test_skip_links_inside_tilde_and_indented_code_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+        let path = create_test_doc(
+            &temp_dir,
+            "test.md",
+            "# Test\n\n~~~\n[fake](tilde-missing.md)\n~~~\n\n    [fake](indented-missing.md)\n\nSee [real](missing.md).\n",
+        );
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut results = LintResults::new();
+
+        check_broken_internal_links(&path, &lines, temp_dir.path(), &mut results).unwrap();
+
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].line, 9);
+    }
+
     #[test]
     fn test_skip_headings_inside_nested_code_blocks() {
         let temp_dir = TempDir::new().unwrap();
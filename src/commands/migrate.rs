@@ -2,17 +2,26 @@
 //!
 //! This module implements the `pave migrate` command which helps bulk-update
 //! existing documentation by inserting missing PAVED sections with placeholder content.
+//! Headings that already cover a missing section under another name, such as
+//! `## Overview` for Purpose, are renamed instead. With `--interactive`, each
+//! rename and insertion is shown as a diff and applied only if accepted.
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::fs;
+use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::color;
+use crate::config::PaveConfig;
 use crate::parser::{CodeBlockTracker, ParsedDoc};
 use crate::rules::{DocType, detect_doc_type};
+use crate::schema::SCHEMA_VERSION;
+use crate::text_diff::{diff_lines, render_unified};
+use crate::walk::{NON_DOC_DIRS, find_markdown_files, load_ignore, under_dir_named};
+use crate::workspace::Workdir;
 
 /// Arguments for the migrate command.
 pub struct MigrateArgs {
@@ -24,7 +33,7 @@ pub struct MigrateArgs {
     pub dry_run: bool,
     /// Only add these sections (comma-separated).
     pub sections: Option<String>,
-    /// Confirm each file before modifying.
+    /// Review each section change before modifying.
     pub interactive: bool,
     /// Create .bak files before modifying (default: true).
     pub backup: bool,
@@ -39,7 +48,7 @@ pub enum MigrateOutputFormat {
 }
 
 /// A section that needs to be added to a document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MissingSection {
     /// Name of the section.
     pub name: String,
@@ -47,19 +56,33 @@ pub struct MissingSection {
     pub placeholder: String,
 }
 
+/// An existing heading that becomes a PAVED section when renamed.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct RenamedSection {
+    /// Heading text as written in the document.
+    pub from: String,
+    /// Section name it is renamed to.
+    pub to: String,
+    /// Line of the heading (1-indexed).
+    #[serde(skip)]
+    pub line: usize,
+}
+
 /// Analysis result for a single file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct FileAnalysis {
     /// Path to the file (relative).
     pub path: PathBuf,
     /// Detected document type.
     pub doc_type: String,
+    /// Headings to rename to the sections they cover.
+    pub renamed_sections: Vec<RenamedSection>,
     /// Sections that need to be added.
     pub missing_sections: Vec<MissingSection>,
 }
 
 /// Status of a file after migration.
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum MigrationStatus {
     /// File was modified successfully.
@@ -71,7 +94,7 @@ pub enum MigrationStatus {
 }
 
 /// Result for a single file after migration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct FileResult {
     /// Path to the file (relative).
     pub path: PathBuf,
@@ -79,6 +102,9 @@ pub struct FileResult {
     pub status: MigrationStatus,
     /// Message describing what happened.
     pub message: String,
+    /// Headings that were renamed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sections_renamed: Vec<RenamedSection>,
     /// Sections that were added.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub sections_added: Vec<String>,
@@ -88,8 +114,10 @@ pub struct FileResult {
 }
 
 /// Complete migration report.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MigrationReport {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Number of files scanned.
     pub files_scanned: usize,
     /// Number of files modified.
@@ -160,6 +188,44 @@ fn get_required_sections(doc_type: DocType) -> Vec<(&'static str, &'static str)>
                 "<!-- TODO: Describe the consequences of this decision -->",
             ),
         ],
+        DocType::Postmortem => vec![
+            (
+                "Purpose",
+                "<!-- TODO: Summarize the incident this postmortem covers -->",
+            ),
+            (
+                "Impact",
+                "<!-- TODO: Describe who and what was affected, and for how long -->",
+            ),
+            (
+                "Timeline",
+                "<!-- TODO: List timestamped events from detection to resolution -->",
+            ),
+            (
+                "Action Items",
+                "<!-- TODO: List follow-up work, each with an owner -->",
+            ),
+            (
+                "Verification",
+                "<!-- TODO: Add verification commands -->\n\n```bash\n# Add verification command here\n```",
+            ),
+        ],
+        DocType::Api => vec![
+            ("Purpose", "<!-- TODO: Describe the purpose of this API -->"),
+            (
+                "Authentication",
+                "<!-- TODO: Describe how callers authenticate -->",
+            ),
+            ("Endpoints", "<!-- TODO: List the API's endpoints -->"),
+            (
+                "Verification",
+                "<!-- TODO: Add verification commands -->\n\n```bash\n# Add verification command here\n```",
+            ),
+            (
+                "Examples",
+                "<!-- TODO: Add example requests -->\n\n```bash\n# Add example here\n```",
+            ),
+        ],
         DocType::Other => vec![
             (
                 "Purpose",
@@ -177,118 +243,92 @@ fn get_required_sections(doc_type: DocType) -> Vec<(&'static str, &'static str)>
     }
 }
 
+/// Common headings that cover a PAVED section under another name.
+const HEADING_ALIASES: &[(&str, &str)] = &[
+    ("overview", "Purpose"),
+    ("introduction", "Purpose"),
+    ("about", "Purpose"),
+    ("summary", "Purpose"),
+    ("description", "Purpose"),
+    ("api", "Interface"),
+    ("reference", "Interface"),
+    ("usage", "Examples"),
+    ("example", "Examples"),
+    ("testing", "Verification"),
+    ("tests", "Verification"),
+    ("how to test", "Verification"),
+    ("validation", "Verification"),
+    ("procedure", "Steps"),
+    ("instructions", "Steps"),
+    ("undo", "Rollback"),
+    ("revert", "Rollback"),
+    ("background", "Context"),
+];
+
+/// The PAVED section a heading stands in for, if it is a known alias.
+fn heading_alias(heading: &str) -> Option<&'static str> {
+    let heading = heading.trim().to_lowercase();
+    HEADING_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == heading)
+        .map(|(_, section)| *section)
+}
+
 /// Section ordering for insertion (lower number = earlier in document).
 fn section_order(name: &str) -> usize {
     match name.to_lowercase().as_str() {
         "purpose" => 1,
-        "status" => 2,        // ADR
-        "context" => 3,       // ADR
-        "decision" => 4,      // ADR
-        "consequences" => 5,  // ADR
-        "interface" => 6,     // Component
-        "configuration" => 7, // Component
-        "when to use" => 8,   // Runbook
-        "preconditions" => 9, // Runbook
-        "steps" => 10,        // Runbook
-        "rollback" => 11,     // Runbook
+        "status" => 2,          // ADR
+        "context" => 3,         // ADR
+        "decision" => 4,        // ADR
+        "consequences" => 5,    // ADR
+        "interface" => 6,       // Component
+        "configuration" => 7,   // Component
+        "when to use" => 8,     // Runbook
+        "preconditions" => 9,   // Runbook
+        "steps" => 10,          // Runbook
+        "rollback" => 11,       // Runbook
+        "impact" => 12,         // Postmortem
+        "timeline" => 13,       // Postmortem
+        "action items" => 14,   // Postmortem
+        "authentication" => 15, // API
+        "endpoints" => 16,      // API
         "verification" => 90,
         "examples" => 95,
         _ => 50,
     }
 }
 
-/// Find the config file by walking up the directory tree.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir()?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
-/// Recursively find markdown files in a directory.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
-
-    for path in paths {
-        if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path.clone());
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        }
-    }
-
-    files.sort();
-    Ok(files)
-}
-
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = fs::read_dir(dir)
-        .with_context(|| format!("failed to read directory: {}", dir.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip common non-doc directories
-            let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            if matches!(
-                dir_name,
-                "node_modules"
-                    | "target"
-                    | ".git"
-                    | ".github"
-                    | "templates"
-                    | "_site"
-                    | ".pave"
-                    | "vendor"
-                    | "build"
-            ) {
-                continue;
-            }
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
-        }
-    }
-
-    Ok(())
-}
-
 /// Analyze a file to determine which sections are missing.
-fn analyze_file(
+pub(crate) fn analyze_file(
     path: &Path,
     docs_root: &Path,
     filter_sections: &Option<HashSet<String>>,
 ) -> Result<Option<FileAnalysis>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("failed to read file: {}", path.display()))?;
+    let relative_path = path.strip_prefix(docs_root).unwrap_or(path);
+    analyze_content(relative_path, &content, filter_sections)
+}
 
-    let relative_path = path.strip_prefix(docs_root).unwrap_or(path).to_path_buf();
-
+/// Determine which sections are missing from a document's content, given
+/// its path relative to the docs root.
+pub(crate) fn analyze_content(
+    relative_path: &Path,
+    content: &str,
+    filter_sections: &Option<HashSet<String>>,
+) -> Result<Option<FileAnalysis>> {
     // Parse the document
-    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    let doc = ParsedDoc::parse_content(relative_path.to_path_buf(), content)?;
 
     // Detect document type
-    let doc_type = detect_doc_type(&relative_path, &content);
+    let doc_type = detect_doc_type(relative_path, content);
     let doc_type_str = match doc_type {
         DocType::Component => "component",
         DocType::Runbook => "runbook",
         DocType::Adr => "adr",
+        DocType::Postmortem => "postmortem",
+        DocType::Api => "api",
         DocType::Other => "other",
     }
     .to_string();
@@ -296,7 +336,8 @@ fn analyze_file(
     // Get required sections for this document type
     let required_sections = get_required_sections(doc_type);
 
-    // Find missing sections
+    // Find missing sections, preferring to rename a heading that covers one
+    let mut renamed_sections: Vec<RenamedSection> = Vec::new();
     let mut missing_sections = Vec::new();
     for (name, placeholder) in required_sections {
         // Skip if not in filter set
@@ -306,26 +347,75 @@ fn analyze_file(
             continue;
         }
 
-        if !doc.has_section(name) {
-            missing_sections.push(MissingSection {
+        if doc.has_section(name) {
+            continue;
+        }
+        let alias = doc.sections.iter().find(|section| {
+            heading_alias(&section.name) == Some(name)
+                && !renamed_sections
+                    .iter()
+                    .any(|r| r.line == section.start_line)
+        });
+        match alias {
+            Some(section) => renamed_sections.push(RenamedSection {
+                from: section.name.clone(),
+                to: name.to_string(),
+                line: section.start_line,
+            }),
+            None => missing_sections.push(MissingSection {
                 name: name.to_string(),
                 placeholder: placeholder.to_string(),
-            });
+            }),
         }
     }
 
-    // If no sections are missing, skip this file
-    if missing_sections.is_empty() {
+    // If nothing needs to change, skip this file
+    if renamed_sections.is_empty() && missing_sections.is_empty() {
         return Ok(None);
     }
 
     Ok(Some(FileAnalysis {
-        path: relative_path,
+        path: relative_path.to_path_buf(),
         doc_type: doc_type_str,
+        renamed_sections,
         missing_sections,
     }))
 }
 
+/// Apply heading renames, then insert missing sections.
+pub(crate) fn migrate_content(
+    content: &str,
+    renamed_sections: &[RenamedSection],
+    missing_sections: &[MissingSection],
+) -> String {
+    let renamed: Vec<String> = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            match renamed_sections
+                .iter()
+                .find(|rename| rename.line == idx + 1)
+            {
+                Some(rename) => rename_heading(line, &rename.to),
+                None => line.to_string(),
+            }
+        })
+        .collect();
+    insert_sections(&renamed.join("\n"), missing_sections)
+}
+
+/// Replace a heading line's text, keeping its `#` markers. A setext heading's
+/// text line has none and is replaced whole.
+fn rename_heading(line: &str, name: &str) -> String {
+    let trimmed = line.trim_start();
+    let markers = trimmed.len() - trimmed.trim_start_matches('#').len();
+    if markers == 0 {
+        name.to_string()
+    } else {
+        format!("{} {}", &trimmed[..markers], name)
+    }
+}
+
 /// Insert missing sections into a document.
 fn insert_sections(content: &str, missing_sections: &[MissingSection]) -> String {
     let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
@@ -410,45 +500,135 @@ fn create_backup(path: &Path) -> Result<PathBuf> {
     Ok(backup_path)
 }
 
-/// Prompt user for confirmation in interactive mode.
-fn prompt_user(file: &FileAnalysis) -> bool {
-    use std::io::{self, Write};
+/// Changes accepted for a file in interactive mode.
+#[derive(Debug, Default)]
+struct Accepted {
+    renamed_sections: Vec<RenamedSection>,
+    missing_sections: Vec<MissingSection>,
+    /// The user asked to stop migrating after this file.
+    quit: bool,
+}
+
+/// Walk through a file's changes one section at a time, showing the diff
+/// each would make and asking whether to apply it. A rename can be edited
+/// to use another heading. End of input counts as quitting.
+fn review_changes(
+    file: &FileAnalysis,
+    content: &str,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    color: bool,
+) -> io::Result<Accepted> {
+    let mut accepted = Accepted::default();
+    writeln!(out)?;
+    writeln!(out, "{} ({})", file.path.display(), file.doc_type)?;
+
+    for rename in &file.renamed_sections {
+        let mut rename = rename.clone();
+        loop {
+            show_diff(
+                out,
+                content,
+                &migrate_content(content, std::slice::from_ref(&rename), &[]),
+                color,
+            )?;
+            let question = format!(
+                "Rename '{}' to '{}'? [y]es/[n]o/[e]dit/[q]uit ",
+                rename.from, rename.to
+            );
+            match choose(input, out, &question)?.as_deref() {
+                Some("y" | "yes") => accepted.renamed_sections.push(rename),
+                Some("e" | "edit") => {
+                    let heading = ask(input, out, &format!("New heading for '{}': ", rename.from))?;
+                    if let Some(heading) = heading.filter(|heading| !heading.is_empty()) {
+                        rename.to = heading;
+                    }
+                    continue;
+                }
+                Some("q" | "quit") | None => {
+                    accepted.quit = true;
+                    return Ok(accepted);
+                }
+                Some(_) => {}
+            }
+            break;
+        }
+    }
 
-    println!();
-    println!("File: {}", file.path.display());
-    println!("Type: {}", file.doc_type);
-    println!("Missing sections:");
     for section in &file.missing_sections {
-        println!("  + {}", section.name);
+        show_diff(
+            out,
+            content,
+            &migrate_content(content, &[], std::slice::from_ref(section)),
+            color,
+        )?;
+        let question = format!("Add '{}'? [y]es/[n]o/[q]uit ", section.name);
+        match choose(input, out, &question)?.as_deref() {
+            Some("y" | "yes") => accepted.missing_sections.push(section.clone()),
+            Some("q" | "quit") | None => {
+                accepted.quit = true;
+                return Ok(accepted);
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(accepted)
+}
+
+/// Print the unified diff between a file's content and a proposed change.
+fn show_diff(out: &mut impl Write, before: &str, after: &str, color: bool) -> io::Result<()> {
+    for line in render_unified(&diff_lines(before, after), 2, color) {
+        writeln!(out, "{}", line)?;
     }
-    print!("Apply changes? [y/N] ");
-    io::stdout().flush().ok();
+    Ok(())
+}
 
-    let mut input = String::new();
-    if io::stdin().read_line(&mut input).is_ok() {
-        let input = input.trim().to_lowercase();
-        return input == "y" || input == "yes";
+/// Ask a question and read the trimmed answer, or `None` at end of input.
+fn ask(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    question: &str,
+) -> io::Result<Option<String>> {
+    write!(out, "{}", question)?;
+    out.flush()?;
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
     }
+    Ok(Some(answer.trim().to_string()))
+}
 
-    false
+/// Ask a question whose answer is one of a few letters or words.
+fn choose(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    question: &str,
+) -> io::Result<Option<String>> {
+    Ok(ask(input, out, question)?.map(|answer| answer.to_lowercase()))
 }
 
 /// Execute the migrate command.
-pub fn execute(args: MigrateArgs) -> Result<()> {
+pub fn execute(args: MigrateArgs, workdir: &Workdir) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
 
     // Determine paths to process
-    let paths = if let Some(path) = args.path {
-        vec![path]
+    let mut paths = if let Some(path) = &args.path {
+        vec![workdir.resolve(path)]
     } else {
         vec![config_dir.join(&config.docs.root)]
     };
+    paths.retain(|path| path.exists());
 
-    // Find markdown files
-    let files = find_markdown_files(&paths)?;
+    // Find markdown files, skipping common non-doc directories
+    let mut files = find_markdown_files(&paths, &load_ignore(config_dir, &config)?)?;
+    files.retain(|file| {
+        !paths
+            .iter()
+            .any(|root| under_dir_named(file, root, &NON_DOC_DIRS))
+    });
     let docs_root = config_dir.join(&config.docs.root);
 
     // Parse sections filter
@@ -466,6 +646,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
 
     // Build report
     let mut report = MigrationReport {
+        schema_version: SCHEMA_VERSION,
         files_scanned: files.len(),
         files_modified: 0,
         files_skipped: 0,
@@ -481,6 +662,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                 path: analysis.path.clone(),
                 status: MigrationStatus::Skipped,
                 message: "would be modified".to_string(),
+                sections_renamed: analysis.renamed_sections.clone(),
                 sections_added: analysis
                     .missing_sections
                     .iter()
@@ -496,18 +678,19 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
     }
 
     // Process files
+    let mut quit = false;
     for analysis in analyses {
         let full_path = docs_root.join(&analysis.path);
-
-        // Interactive mode: prompt for each file
-        if args.interactive && !prompt_user(&analysis) {
-            report.files.push(FileResult {
-                path: analysis.path.clone(),
-                status: MigrationStatus::Skipped,
-                message: "user declined".to_string(),
-                sections_added: Vec::new(),
-                backup_path: None,
-            });
+        let skipped = |message: &str| FileResult {
+            path: analysis.path.clone(),
+            status: MigrationStatus::Skipped,
+            message: message.to_string(),
+            sections_renamed: Vec::new(),
+            sections_added: Vec::new(),
+            backup_path: None,
+        };
+        if quit {
+            report.files.push(skipped("not reviewed"));
             report.files_skipped += 1;
             continue;
         }
@@ -517,28 +700,43 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
             Ok(c) => c,
             Err(e) => {
                 report.files.push(FileResult {
-                    path: analysis.path.clone(),
                     status: MigrationStatus::Failed,
-                    message: format!("failed to read file: {}", e),
-                    sections_added: Vec::new(),
-                    backup_path: None,
+                    ..skipped(&format!("failed to read file: {}", e))
                 });
                 report.files_failed += 1;
                 continue;
             }
         };
 
+        // Interactive mode: review each section change
+        let (renamed_sections, missing_sections) = if args.interactive {
+            let accepted = review_changes(
+                &analysis,
+                &content,
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+                color::enabled(),
+            )
+            .context("failed to read answer")?;
+            quit = accepted.quit;
+            (accepted.renamed_sections, accepted.missing_sections)
+        } else {
+            (analysis.renamed_sections, analysis.missing_sections)
+        };
+        if renamed_sections.is_empty() && missing_sections.is_empty() {
+            report.files.push(skipped("user declined"));
+            report.files_skipped += 1;
+            continue;
+        }
+
         // Create backup if requested
         let backup_path = if args.backup {
             match create_backup(&full_path) {
                 Ok(p) => Some(p.strip_prefix(&docs_root).unwrap_or(&p).to_path_buf()),
                 Err(e) => {
                     report.files.push(FileResult {
-                        path: analysis.path.clone(),
                         status: MigrationStatus::Failed,
-                        message: format!("failed to create backup: {}", e),
-                        sections_added: Vec::new(),
-                        backup_path: None,
+                        ..skipped(&format!("failed to create backup: {}", e))
                     });
                     report.files_failed += 1;
                     continue;
@@ -548,32 +746,34 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
             None
         };
 
-        // Insert missing sections
-        let new_content = insert_sections(&content, &analysis.missing_sections);
+        // Rename headings and insert missing sections
+        let new_content = migrate_content(&content, &renamed_sections, &missing_sections);
 
         // Write back
         match fs::write(&full_path, &new_content) {
             Ok(()) => {
+                let message = match (renamed_sections.len(), missing_sections.len()) {
+                    (0, added) => format!("added {} section(s)", added),
+                    (renamed, 0) => format!("renamed {} section(s)", renamed),
+                    (renamed, added) => {
+                        format!("renamed {} and added {} section(s)", renamed, added)
+                    }
+                };
                 report.files.push(FileResult {
                     path: analysis.path.clone(),
                     status: MigrationStatus::Modified,
-                    message: format!("added {} section(s)", analysis.missing_sections.len()),
-                    sections_added: analysis
-                        .missing_sections
-                        .iter()
-                        .map(|s| s.name.clone())
-                        .collect(),
+                    message,
+                    sections_renamed: renamed_sections,
+                    sections_added: missing_sections.iter().map(|s| s.name.clone()).collect(),
                     backup_path,
                 });
                 report.files_modified += 1;
             }
             Err(e) => {
                 report.files.push(FileResult {
-                    path: analysis.path.clone(),
                     status: MigrationStatus::Failed,
-                    message: format!("failed to write file: {}", e),
-                    sections_added: Vec::new(),
                     backup_path,
+                    ..skipped(&format!("failed to write file: {}", e))
                 });
                 report.files_failed += 1;
             }
@@ -610,6 +810,9 @@ fn output_text_report(report: &MigrationReport, dry_run: bool) {
 
         for file in &report.files {
             println!("{}", file.path.display());
+            for rename in &file.sections_renamed {
+                println!("  ~ Rename ## {} to ## {}", rename.from, rename.to);
+            }
             for section in &file.sections_added {
                 println!("  + Add ## {} section", section);
             }
@@ -664,6 +867,7 @@ fn output_text_report(report: &MigrationReport, dry_run: bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ignore::PaveIgnore;
     use tempfile::TempDir;
 
     fn create_test_config(temp_dir: &TempDir) -> PathBuf {
@@ -855,6 +1059,94 @@ root = "docs"
         assert_eq!(analysis.missing_sections[0].name, "Verification");
     }
 
+    #[test]
+    fn test_analyze_file_renames_alias_headings() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+
+        let content =
+            "# Auth Component\n\nOverview\n--------\nWhy.\n\n## Usage\n```bash\nauth login\n```\n";
+        let path = create_test_doc(&temp_dir, "docs/components/auth.md", content);
+
+        let analysis = analyze_file(&path, temp_dir.path().join("docs").as_path(), &None)
+            .unwrap()
+            .unwrap();
+        let renames: Vec<(&str, &str, usize)> = analysis
+            .renamed_sections
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str(), r.line))
+            .collect();
+        assert_eq!(
+            renames,
+            [("Overview", "Purpose", 3), ("Usage", "Examples", 7)]
+        );
+        let missing: Vec<&str> = analysis
+            .missing_sections
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(missing, ["Interface", "Verification"]);
+
+        let migrated = migrate_content(content, &analysis.renamed_sections, &[]);
+        assert_eq!(
+            migrated,
+            "# Auth Component\n\nPurpose\n--------\nWhy.\n\n## Examples\n```bash\nauth login\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_review_changes_accepts_rejects_and_edits() {
+        let content = "# Auth\n\n## Overview\nWhy.\n\n## Usage\nRun it.\n";
+        let rename = |from: &str, to: &str, line| RenamedSection {
+            from: from.to_string(),
+            to: to.to_string(),
+            line,
+        };
+        let missing = |name: &str| MissingSection {
+            name: name.to_string(),
+            placeholder: "TODO".to_string(),
+        };
+        let analysis = FileAnalysis {
+            path: PathBuf::from("components/auth.md"),
+            doc_type: "component".to_string(),
+            renamed_sections: vec![
+                rename("Overview", "Purpose", 3),
+                rename("Usage", "Examples", 6),
+            ],
+            missing_sections: vec![missing("Interface"), missing("Verification")],
+        };
+
+        // Edit the first rename, reject the second, add Interface, then quit
+        let mut input = "e\nGoals\ny\nn\ny\nq\n".as_bytes();
+        let mut out = Vec::new();
+        let accepted = review_changes(&analysis, content, &mut input, &mut out, false).unwrap();
+        assert_eq!(accepted.renamed_sections, [rename("Overview", "Goals", 3)]);
+        let added: Vec<&str> = accepted
+            .missing_sections
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(added, ["Interface"]);
+        assert!(accepted.quit);
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("-## Overview\n+## Purpose\n"));
+        assert!(out.contains("-## Overview\n+## Goals\n"));
+        assert!(out.contains("+## Interface"));
+
+        // Running out of input stops the review
+        let accepted = review_changes(
+            &analysis,
+            content,
+            &mut "y\n".as_bytes(),
+            &mut Vec::new(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(accepted.renamed_sections.len(), 1);
+        assert!(accepted.quit);
+    }
+
     #[test]
     fn test_analyze_file_no_missing_sections() {
         let temp_dir = TempDir::new().unwrap();
@@ -907,7 +1199,8 @@ example
         create_test_doc(&temp_dir, "docs/node_modules/c.md", "# C");
         create_test_doc(&temp_dir, "docs/test.txt", "Not markdown");
 
-        let files = find_markdown_files(&[temp_dir.path().join("docs")]).unwrap();
+        let files =
+            find_markdown_files(&[temp_dir.path().join("docs")], &PaveIgnore::default()).unwrap();
 
         assert_eq!(files.len(), 2);
         assert!(files.iter().any(|f| f.ends_with("a.md")));
@@ -919,6 +1212,7 @@ example
     #[test]
     fn test_migration_report_serialization() {
         let report = MigrationReport {
+            schema_version: SCHEMA_VERSION,
             files_scanned: 10,
             files_modified: 5,
             files_skipped: 3,
@@ -928,6 +1222,7 @@ example
                 path: PathBuf::from("test.md"),
                 status: MigrationStatus::Modified,
                 message: "added 2 section(s)".to_string(),
+                sections_renamed: Vec::new(),
                 sections_added: vec!["Purpose".to_string(), "Verification".to_string()],
                 backup_path: Some(PathBuf::from("test.md.bak")),
             }],
@@ -1,19 +1,43 @@
 //! Command implementations for pave CLI.
 
 pub mod adopt;
+pub mod audit;
+pub mod bench;
 pub mod build;
+pub mod build_mdbook;
 pub mod changed;
 pub mod check;
+pub mod ci;
 pub mod config;
 pub mod coverage;
 pub mod coverage_changed;
+pub mod dedupe;
 pub mod doctor;
+pub mod doctor_fix;
+pub mod doctor_verify;
+pub mod fleet;
 pub mod hooks;
+pub mod import;
 pub mod index;
 pub mod init;
 pub mod lint;
 pub mod migrate;
+pub mod mv;
 pub mod new;
+pub mod nightly;
 pub mod prompt;
+pub mod prompt_fix;
+pub mod publish;
+pub mod report;
+pub mod report_html;
+pub mod review;
+pub mod search;
+pub mod split;
+pub mod stale;
 pub mod status;
+pub mod status_worklist;
+pub mod summary;
+pub mod templates;
+pub mod translate_status;
 pub mod verify;
+pub mod which;
@@ -0,0 +1,299 @@
+//! Implementation of the `pave mv` command for moving or renaming a doc.
+//!
+//! Relative links, reference definitions, and include markers pointing at
+//! the doc are rewritten in every doc under the docs root, keeping their
+//! anchors. Links in the moved doc itself are rewritten to resolve from its
+//! new directory. A `pave index` generated index is regenerated afterwards.
+
+use anyhow::{Context, Result, bail};
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::commands::index;
+use crate::commands::split::LinkUpdate;
+use crate::config::PaveConfig;
+use crate::parser::CodeBlockTracker;
+use crate::paths::{absolutize, relative_path};
+use crate::walk::{collect_markdown_files, load_ignore};
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave mv` command.
+pub struct MvArgs {
+    /// Doc to move.
+    pub from: PathBuf,
+    /// New path, or an existing directory to move the doc into.
+    pub to: PathBuf,
+    /// Show the changes without modifying files.
+    pub dry_run: bool,
+}
+
+/// A proposed move of a single doc.
+#[derive(Debug)]
+pub struct MovePlan {
+    /// Current path of the doc.
+    pub from: PathBuf,
+    /// New path of the doc.
+    pub to: PathBuf,
+    /// Content of the doc at its new path.
+    pub content: String,
+    /// Links in the moved doc that were rewritten.
+    pub own_links: usize,
+    /// Other docs whose links to the moved doc change.
+    pub link_updates: Vec<LinkUpdate>,
+}
+
+/// Execute the `pave mv` command.
+pub fn execute(args: MvArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let cwd = &workdir.dir;
+
+    let from = workdir.resolve(&args.from);
+    if !from.is_file() {
+        bail!("No such doc: {}", args.from.display());
+    }
+    let to = match from.file_name() {
+        Some(name) if workdir.resolve(&args.to).is_dir() => args.to.join(name),
+        _ => args.to.clone(),
+    };
+    if workdir.resolve(&to).exists() {
+        bail!("File already exists: {}", to.display());
+    }
+
+    let docs_root = absolutize(&config_dir.join(&config.docs.root), cwd);
+    let ignore = load_ignore(config_dir, &config)?;
+    let mut files = Vec::new();
+    if docs_root.is_dir() {
+        collect_markdown_files(&docs_root, &ignore, &mut files)?;
+    }
+    files.sort();
+    let mut docs = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        docs.push((absolutize(&file, cwd), content));
+    }
+
+    let content = fs::read_to_string(&from)
+        .with_context(|| format!("Failed to read file: {}", from.display()))?;
+    let plan = plan_move(&from, &workdir.resolve(&to), &content, &docs);
+
+    output_plan(&plan, cwd);
+    if args.dry_run {
+        println!();
+        println!("Dry run: no files were changed.");
+        return Ok(());
+    }
+
+    if let Some(parent) = plan.to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(&plan.to, &plan.content)
+        .with_context(|| format!("Failed to write file: {}", plan.to.display()))?;
+    fs::remove_file(&plan.from)
+        .with_context(|| format!("Failed to remove file: {}", plan.from.display()))?;
+    for update in &plan.link_updates {
+        fs::write(&update.file, &update.content)
+            .with_context(|| format!("Failed to write file: {}", update.file.display()))?;
+    }
+
+    let index_path = docs_root.join("index.md");
+    if plan.from != index_path && index::refresh(&index_path, &docs_root, &ignore)? {
+        println!("Regenerated {}", relative_path(&index_path, cwd).display());
+    }
+    Ok(())
+}
+
+/// Plan moving the doc at `from` (holding `content`) to `to`, rewriting the
+/// links in `docs` that point at it. All paths are absolute.
+pub fn plan_move(from: &Path, to: &Path, content: &str, docs: &[(PathBuf, String)]) -> MovePlan {
+    let old_dir = from.parent().unwrap_or_else(|| Path::new("/"));
+    let new_dir = to.parent().unwrap_or_else(|| Path::new("/"));
+    let (moved, own_links) = rewrite_links(content, old_dir, new_dir, from, to);
+
+    let link_updates = docs
+        .iter()
+        .filter(|(path, _)| path != from)
+        .filter_map(|(path, content)| {
+            let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+            let (updated, links) = rewrite_links(content, dir, dir, from, to);
+            (links > 0).then(|| LinkUpdate {
+                file: path.clone(),
+                links,
+                content: updated,
+            })
+        })
+        .collect();
+
+    MovePlan {
+        from: from.to_path_buf(),
+        to: to.to_path_buf(),
+        content: moved,
+        own_links,
+        link_updates,
+    }
+}
+
+/// Rewrite the relative link targets in a doc that moves from `old_dir` to
+/// `new_dir` (the same directory for docs that stay), so that links to
+/// `from` point at `to` and the rest still resolve. Code blocks are left
+/// alone. Returns the updated content and the number of links changed.
+fn rewrite_links(
+    content: &str,
+    old_dir: &Path,
+    new_dir: &Path,
+    from: &Path,
+    to: &Path,
+) -> (String, usize) {
+    let inline_re = Regex::new(r"(\]\()([^)\s#]+)").unwrap();
+    let reference_re = Regex::new(r"^(\s{0,3}\[[^\]]+\]:\s*)([^\s#]+)").unwrap();
+    let include_re = Regex::new(r"^(\s*<!--\s*pave:include\s+)(\S+)").unwrap();
+
+    let mut count = 0;
+    let mut retarget = |caps: &Captures| -> String {
+        let target = &caps[2];
+        match retargeted(target, old_dir, new_dir, from, to) {
+            Some(new_target) => {
+                count += 1;
+                format!("{}{}", &caps[1], new_target)
+            }
+            None => caps[0].to_string(),
+        }
+    };
+
+    let mut tracker = CodeBlockTracker::new();
+    let mut output = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let in_code = tracker.in_code_block();
+        if tracker.process_line(line.trim_end_matches(['\n', '\r'])) || in_code {
+            output.push_str(line);
+            continue;
+        }
+        let line = inline_re.replace_all(line, &mut retarget);
+        let line = reference_re.replace(&line, &mut retarget);
+        let line = include_re.replace(&line, &mut retarget);
+        output.push_str(&line);
+    }
+    (output, count)
+}
+
+/// The new form of a link `target`, or `None` if it doesn't change.
+fn retargeted(
+    target: &str,
+    old_dir: &Path,
+    new_dir: &Path,
+    from: &Path,
+    to: &Path,
+) -> Option<String> {
+    if target.contains(':') || target.starts_with('/') {
+        return None;
+    }
+    let resolved = absolutize(&old_dir.join(target), old_dir);
+    let resolved = if resolved == from {
+        to.to_path_buf()
+    } else if old_dir != new_dir {
+        resolved
+    } else {
+        return None;
+    };
+
+    let mut link = relative_path(&resolved, new_dir)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if target.ends_with('/') && !link.is_empty() {
+        link.push('/');
+    }
+    (link != target).then_some(link)
+}
+
+/// Print the move plan.
+fn output_plan(plan: &MovePlan, cwd: &Path) {
+    let links = |count: usize| format!("{} link{}", count, if count == 1 { "" } else { "s" });
+    println!(
+        "Move {} -> {}",
+        relative_path(&plan.from, cwd).display(),
+        relative_path(&plan.to, cwd).display()
+    );
+    if plan.own_links > 0 {
+        println!(
+            "  {}: {} rewritten for the new location",
+            relative_path(&plan.to, cwd).display(),
+            links(plan.own_links)
+        );
+    }
+    for update in &plan.link_updates {
+        println!(
+            "  {}: {}",
+            relative_path(&update.file, cwd).display(),
+            links(update.links)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_links_to_the_moved_doc() {
+        let from = Path::new("/repo/docs/deploy.md");
+        let to = Path::new("/repo/docs/runbooks/deploy.md");
+        let docs = vec![
+            (
+                PathBuf::from("/repo/docs/index.md"),
+                "See [deploy](deploy.md#rollback) and [auth](auth.md).\n\n\
+                 [ref]: ./deploy.md\n\n```markdown\n[deploy](deploy.md)\n```\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("/repo/docs/adr/001.md"),
+                "[Deploy](../deploy.md) or [site](https://x.io/deploy.md)\n".to_string(),
+            ),
+            (
+                PathBuf::from("/repo/docs/auth.md"),
+                "No links here.\n".to_string(),
+            ),
+        ];
+        let content = "# Deploy\n\n[Auth](auth.md), [below](#steps), [self](deploy.md#steps)\n\
+                       <!-- pave:include _includes/env.md -->\n";
+
+        let plan = plan_move(from, to, content, &docs);
+        assert_eq!(
+            plan.content,
+            "# Deploy\n\n[Auth](../auth.md), [below](#steps), [self](deploy.md#steps)\n\
+             <!-- pave:include ../_includes/env.md -->\n"
+        );
+        assert_eq!(plan.own_links, 2);
+
+        let updates: Vec<(&Path, usize)> = plan
+            .link_updates
+            .iter()
+            .map(|update| (update.file.as_path(), update.links))
+            .collect();
+        assert_eq!(
+            updates,
+            [
+                (Path::new("/repo/docs/index.md"), 2),
+                (Path::new("/repo/docs/adr/001.md"), 1),
+            ]
+        );
+        assert_eq!(
+            plan.link_updates[0].content,
+            "See [deploy](runbooks/deploy.md#rollback) and [auth](auth.md).\n\n\
+             [ref]: runbooks/deploy.md\n\n```markdown\n[deploy](deploy.md)\n```\n"
+        );
+        assert_eq!(
+            plan.link_updates[1].content,
+            "[Deploy](../runbooks/deploy.md) or [site](https://x.io/deploy.md)\n"
+        );
+    }
+}
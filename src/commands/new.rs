@@ -1,51 +1,157 @@
 //! Implementation of the `pave new` command for scaffolding documents.
+//!
+//! Besides the document name, templates can be filled from variables given
+//! with `--var key=value` or prompted for with `--interactive`. `owner`,
+//! `tags`, and `paths` go into the document's `pave` frontmatter, `verify`
+//! commands replace the example Verification blocks, and any variable can be
+//! referenced in a template as `{{key}}`.
 
 use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
 
-use crate::templates::{TemplateType, get_template};
+use crate::config::PaveConfig;
+use crate::templates::{DocTemplate, TemplateType, load_template};
+use crate::workspace::Workdir;
 
 /// Arguments for the `pave new` command.
 pub struct NewArgs {
-    /// Document type: component, runbook, adr
-    pub doc_type: TemplateType,
+    /// Document type: component, runbook, adr, or a project template name
+    pub doc_type: String,
     /// Name for the document (used in filename and title)
     pub name: String,
     /// Where to create the file (optional, uses default if not specified)
     pub output: Option<PathBuf>,
+    /// Template variables, each as `key=value`
+    pub vars: Vec<String>,
+    /// Prompt for owner, paths, tags, and verification commands
+    pub interactive: bool,
+}
+
+/// Values substituted into a template by `pave new`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateVars {
+    /// Owner written to `pave.owner`.
+    pub owner: Option<String>,
+    /// Tags written to `pave.tags`.
+    pub tags: Vec<String>,
+    /// Covered code paths written to `pave.paths`.
+    pub paths: Vec<String>,
+    /// Verification commands, one bash block each.
+    pub verify: Vec<String>,
+    /// Other variables, substituted for `{{key}}`.
+    pub custom: BTreeMap<String, String>,
+}
+
+impl TemplateVars {
+    /// Parse `key=value` arguments. `tags` and `paths` take comma-separated
+    /// lists, and `verify` may be given more than once.
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut vars = Self::default();
+        for arg in args {
+            let Some((key, value)) = arg.split_once('=') else {
+                bail!("Invalid --var `{}`: expected key=value", arg);
+            };
+            vars.set(key.trim(), value.trim())?;
+        }
+        Ok(vars)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "owner" => self.owner = (!value.is_empty()).then(|| value.to_string()),
+            "tags" => self.tags.extend(split_list(value)),
+            "paths" => self.paths.extend(split_list(value)),
+            "verify" => {
+                if !value.is_empty() {
+                    self.verify.push(value.to_string());
+                }
+            }
+            "name" | "title" => bail!("`{}` is set from the document name", key),
+            _ => {
+                if key.is_empty()
+                    || !key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    bail!(
+                        "Invalid variable name `{}`: use letters, digits, `_`, and `-`",
+                        key
+                    );
+                }
+                self.custom.insert(key.to_string(), value.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Frontmatter written by `pave new`.
+#[derive(Serialize)]
+struct NewFrontmatter<'a> {
+    pave: NewPaveFrontmatter<'a>,
+}
+
+#[derive(Serialize)]
+struct NewPaveFrontmatter<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    paths: &'a [String],
 }
 
 /// Execute the `pave new` command.
-pub fn execute(args: NewArgs) -> Result<()> {
+pub fn execute(args: NewArgs, workdir: &Workdir) -> Result<()> {
+    // Project templates take precedence over the built-in ones
+    let template = match workdir.locate_config()? {
+        Some(config_path) => {
+            let config = PaveConfig::load(&config_path)?;
+            let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+            load_template(&config, config_dir, &args.doc_type)?
+        }
+        None => load_template(&PaveConfig::default(), &workdir.dir, &args.doc_type)?,
+    };
+
     // Determine output path
     let output_path = args
         .output
-        .unwrap_or_else(|| default_output_path(&args.doc_type, &args.name));
+        .unwrap_or_else(|| default_output_path(&template.name, &args.name));
+
+    let path = workdir.resolve(&output_path);
 
     // Check if file already exists
-    if output_path.exists() {
+    if path.exists() {
         bail!("File already exists: {}", output_path.display());
     }
 
-    // Get template and replace placeholders
-    let template = get_template(args.doc_type);
-    let content = substitute_placeholders(template, &args.name, args.doc_type);
+    let mut vars = TemplateVars::parse(&args.vars)?;
+    if args.interactive {
+        let stdin = std::io::stdin();
+        prompt_vars(&mut stdin.lock(), &mut std::io::stdout(), &mut vars)?;
+    }
+
+    // Replace placeholders
+    let content = render_template(&template.content, &args.name, template.builtin_type, &vars)?;
 
     // Create parent directories if needed
-    if let Some(parent) = output_path.parent() {
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
     }
 
     // Write the file
-    fs::write(&output_path, content)
+    fs::write(&path, content)
         .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
 
     // Print success message
     println!(
         "Created {} at {}",
-        type_name(args.doc_type),
+        type_name(&template),
         output_path.display()
     );
     println!("\nNext steps:");
@@ -56,17 +162,21 @@ pub fn execute(args: NewArgs) -> Result<()> {
 }
 
 /// Returns the default output path for a given document type and name.
-fn default_output_path(doc_type: &TemplateType, name: &str) -> PathBuf {
-    let subdir = match doc_type {
-        TemplateType::Component => "components",
-        TemplateType::Runbook => "runbooks",
-        TemplateType::Adr => "adrs",
-    };
-    Path::new("docs").join(subdir).join(format!("{}.md", name))
+///
+/// Each type gets a directory named after it, e.g. `docs/postmortems/` for a
+/// `postmortem` template.
+pub(crate) fn default_output_path(doc_type: &str, name: &str) -> PathBuf {
+    Path::new("docs")
+        .join(format!("{}s", doc_type))
+        .join(format!("{}.md", name))
 }
 
 /// Substitutes placeholders in the template.
-fn substitute_placeholders(template: &str, name: &str, doc_type: TemplateType) -> String {
+pub(crate) fn substitute_placeholders(
+    template: &str,
+    name: &str,
+    doc_type: TemplateType,
+) -> String {
     let title = to_title_case(name);
 
     // Replace the specific placeholder used in each template
@@ -74,7 +184,162 @@ fn substitute_placeholders(template: &str, name: &str, doc_type: TemplateType) -
         TemplateType::Component => template.replace("{Component Name}", &title),
         TemplateType::Runbook => template.replace("{Task Name}", &title),
         TemplateType::Adr => template.replace("{Title}", &title),
+        TemplateType::Postmortem => template.replace("{Incident Title}", &title),
+        TemplateType::Api => template.replace("{API Name}", &title),
+    }
+}
+
+/// Fills a template with the document name and `vars`. Templates for
+/// built-in types may also use that type's title placeholder.
+pub(crate) fn render_template(
+    template: &str,
+    name: &str,
+    builtin_type: Option<TemplateType>,
+    vars: &TemplateVars,
+) -> Result<String> {
+    let template = match builtin_type {
+        Some(doc_type) => substitute_placeholders(template, name, doc_type),
+        None => template.to_string(),
+    };
+    let mut content = template
+        .replace("{{name}}", name)
+        .replace("{{title}}", &to_title_case(name));
+    if let Some(owner) = &vars.owner {
+        content = content.replace("{{owner}}", owner);
+    }
+    for (key, value) in &vars.custom {
+        content = content.replace(&format!("{{{{{}}}}}", key), value);
     }
+    if !vars.verify.is_empty() {
+        content = replace_verification(&content, &vars.verify);
+    }
+
+    if vars.owner.is_none() && vars.tags.is_empty() && vars.paths.is_empty() {
+        return Ok(content);
+    }
+    let frontmatter = NewFrontmatter {
+        pave: NewPaveFrontmatter {
+            owner: vars.owner.as_deref(),
+            tags: &vars.tags,
+            paths: &vars.paths,
+        },
+    };
+    let yaml = serde_yaml::to_string(&frontmatter).context("Failed to write frontmatter")?;
+    Ok(format!("---\n{}---\n\n{}", yaml, content))
+}
+
+/// Replaces the example commands in the Verification section with a bash
+/// block per command, keeping the section's guidance comments.
+fn replace_verification(content: &str, commands: &[String]) -> String {
+    let mut blocks = String::from("\n");
+    for command in commands {
+        blocks.push_str(&format!("```bash\n{}\n```\n\n", command));
+    }
+
+    let mut out = String::new();
+    let mut in_section = false;
+    let mut in_code_block = false;
+    for line in content.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+        let is_heading = !in_code_block && !is_fence && line.starts_with("## ");
+        if is_fence {
+            in_code_block = !in_code_block;
+        }
+        if is_heading && in_section {
+            out.push_str(&blocks);
+            in_section = false;
+        }
+        if in_section {
+            if line.trim_start().starts_with("<!--") {
+                out.push_str(line);
+                out.push('\n');
+            }
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        if is_heading && line.trim() == "## Verification" {
+            in_section = true;
+        }
+    }
+    if in_section {
+        out.push_str(&blocks);
+    }
+    out
+}
+
+/// Prompts for owner, paths, tags, and verification commands, showing any
+/// values already set as defaults.
+fn prompt_vars(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    vars: &mut TemplateVars,
+) -> Result<()> {
+    let owner = prompt(input, output, "Owner", vars.owner.as_deref().unwrap_or(""))?;
+    vars.set("owner", &owner)?;
+
+    let paths = prompt(
+        input,
+        output,
+        "Covered paths (comma-separated)",
+        &vars.paths.join(", "),
+    )?;
+    vars.paths = split_list(&paths);
+
+    let tags = prompt(
+        input,
+        output,
+        "Tags (comma-separated)",
+        &vars.tags.join(", "),
+    )?;
+    vars.tags = split_list(&tags);
+
+    writeln!(
+        output,
+        "Verification commands, one per line (empty line to finish):"
+    )?;
+    for command in &vars.verify {
+        writeln!(output, "  {}", command)?;
+    }
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        vars.set("verify", line.trim())?;
+    }
+    Ok(())
+}
+
+/// Prompts for a single value, returning `default` if the answer is empty.
+fn prompt(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    label: &str,
+    default: &str,
+) -> Result<String> {
+    if default.is_empty() {
+        write!(output, "{}: ", label)?;
+    } else {
+        write!(output, "{} [{}]: ", label, default)?;
+    }
+    output.flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line).context("Failed to read input")?;
+    let answer = line.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+/// Splits a comma-separated list, dropping empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 /// Converts a kebab-case or snake_case name to Title Case.
@@ -91,12 +356,15 @@ fn to_title_case(name: &str) -> String {
         .join(" ")
 }
 
-/// Returns the human-readable name for a template type.
-fn type_name(doc_type: TemplateType) -> &'static str {
-    match doc_type {
-        TemplateType::Component => "component",
-        TemplateType::Runbook => "runbook",
-        TemplateType::Adr => "ADR",
+/// Returns the human-readable name for a template's document type.
+fn type_name(template: &DocTemplate) -> &str {
+    match template.builtin_type {
+        Some(TemplateType::Component) => "component",
+        Some(TemplateType::Runbook) => "runbook",
+        Some(TemplateType::Adr) => "ADR",
+        Some(TemplateType::Postmortem) => "postmortem",
+        Some(TemplateType::Api) => "API reference",
+        None => &template.name,
     }
 }
 
@@ -126,19 +394,19 @@ mod tests {
 
     #[test]
     fn default_output_path_component() {
-        let path = default_output_path(&TemplateType::Component, "auth-service");
+        let path = default_output_path("component", "auth-service");
         assert_eq!(path, Path::new("docs/components/auth-service.md"));
     }
 
     #[test]
     fn default_output_path_runbook() {
-        let path = default_output_path(&TemplateType::Runbook, "deploy-production");
+        let path = default_output_path("runbook", "deploy-production");
         assert_eq!(path, Path::new("docs/runbooks/deploy-production.md"));
     }
 
     #[test]
     fn default_output_path_adr() {
-        let path = default_output_path(&TemplateType::Adr, "use-postgresql");
+        let path = default_output_path("adr", "use-postgresql");
         assert_eq!(path, Path::new("docs/adrs/use-postgresql.md"));
     }
 
@@ -169,12 +437,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-component.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: "test-component".to_string(),
             output: Some(output_path.clone()),
+            vars: Vec::new(),
+            interactive: false,
         };
 
-        execute(args).unwrap();
+        execute(args, &Workdir::new(temp_dir.path()).unwrap()).unwrap();
 
         assert!(output_path.exists());
         let content = fs::read_to_string(&output_path).unwrap();
@@ -188,12 +458,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-runbook.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Runbook,
+            doc_type: "runbook".to_string(),
             name: "test-runbook".to_string(),
             output: Some(output_path.clone()),
+            vars: Vec::new(),
+            interactive: false,
         };
 
-        execute(args).unwrap();
+        execute(args, &Workdir::new(temp_dir.path()).unwrap()).unwrap();
 
         assert!(output_path.exists());
         let content = fs::read_to_string(&output_path).unwrap();
@@ -207,12 +479,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-adr.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Adr,
+            doc_type: "adr".to_string(),
             name: "test-adr".to_string(),
             output: Some(output_path.clone()),
+            vars: Vec::new(),
+            interactive: false,
         };
 
-        execute(args).unwrap();
+        execute(args, &Workdir::new(temp_dir.path()).unwrap()).unwrap();
 
         assert!(output_path.exists());
         let content = fs::read_to_string(&output_path).unwrap();
@@ -226,15 +500,112 @@ mod tests {
         let output_path = temp_dir.path().join("nested").join("dir").join("doc.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: "test".to_string(),
             output: Some(output_path.clone()),
+            vars: Vec::new(),
+            interactive: false,
         };
 
-        execute(args).unwrap();
+        execute(args, &Workdir::new(temp_dir.path()).unwrap()).unwrap();
         assert!(output_path.exists());
     }
 
+    #[test]
+    fn parse_vars_splits_lists_and_collects_custom_keys() {
+        let args: Vec<String> = [
+            "owner=team-auth",
+            "tags=auth, api",
+            "paths=src/auth/**,src/session.rs",
+            "verify=cargo test auth",
+            "verify=cargo clippy",
+            "service=auth-api",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let vars = TemplateVars::parse(&args).unwrap();
+        assert_eq!(vars.owner.as_deref(), Some("team-auth"));
+        assert_eq!(vars.tags, vec!["auth", "api"]);
+        assert_eq!(vars.paths, vec!["src/auth/**", "src/session.rs"]);
+        assert_eq!(vars.verify, vec!["cargo test auth", "cargo clippy"]);
+        assert_eq!(
+            vars.custom.get("service").map(String::as_str),
+            Some("auth-api")
+        );
+
+        assert!(TemplateVars::parse(&["owner".to_string()]).is_err());
+        assert!(TemplateVars::parse(&["title=Other".to_string()]).is_err());
+        assert!(TemplateVars::parse(&["bad key=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn render_template_writes_frontmatter_and_verification() {
+        let vars = TemplateVars {
+            owner: Some("team-auth".to_string()),
+            tags: vec!["auth".to_string()],
+            paths: vec!["src/auth/**".to_string()],
+            verify: vec!["cargo test auth".to_string()],
+            custom: BTreeMap::from([("service".to_string(), "auth-api".to_string())]),
+        };
+        let template = "# {Component Name}\n\nRuns {{service}}, owned by {{owner}}.\n\n\
+            ## Verification\n<!-- How do you know? -->\n\nRun it:\n```bash\n$ cargo test\n```\n\n\
+            ## Examples\n";
+        let content = render_template(
+            template,
+            "auth-service",
+            Some(TemplateType::Component),
+            &vars,
+        )
+        .unwrap();
+        assert_eq!(
+            content,
+            "---\npave:\n  owner: team-auth\n  tags:\n  - auth\n  paths:\n  - src/auth/**\n---\n\n\
+             # Auth Service\n\nRuns auth-api, owned by team-auth.\n\n\
+             ## Verification\n<!-- How do you know? -->\n\n```bash\ncargo test auth\n```\n\n\
+             ## Examples\n"
+        );
+
+        let doc =
+            crate::parser::ParsedDoc::parse_content(PathBuf::from("doc.md"), &content).unwrap();
+        let frontmatter = doc.frontmatter.unwrap();
+        assert_eq!(frontmatter.paths, vec!["src/auth/**"]);
+        assert_eq!(frontmatter.owner.as_deref(), Some("team-auth"));
+
+        // Without variables the template is unchanged apart from the name
+        let plain = render_template(
+            template,
+            "auth",
+            Some(TemplateType::Component),
+            &TemplateVars::default(),
+        )
+        .unwrap();
+        assert!(plain.starts_with("# Auth\n"));
+        assert!(plain.contains("$ cargo test"));
+    }
+
+    #[test]
+    fn prompt_vars_reads_answers_and_keeps_defaults() {
+        let mut vars = TemplateVars {
+            owner: Some("team-auth".to_string()),
+            ..Default::default()
+        };
+        let mut input =
+            "\nsrc/auth/**, src/session.rs\nauth\ncargo test\ncargo clippy\n\n".as_bytes();
+        let mut output = Vec::new();
+        prompt_vars(&mut input, &mut output, &mut vars).unwrap();
+
+        assert_eq!(vars.owner.as_deref(), Some("team-auth"));
+        assert_eq!(vars.paths, vec!["src/auth/**", "src/session.rs"]);
+        assert_eq!(vars.tags, vec!["auth"]);
+        assert_eq!(vars.verify, vec!["cargo test", "cargo clippy"]);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .starts_with("Owner [team-auth]: ")
+        );
+    }
+
     #[test]
     fn execute_errors_if_file_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -242,12 +613,14 @@ mod tests {
         fs::write(&output_path, "existing content").unwrap();
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: "existing".to_string(),
             output: Some(output_path),
+            vars: Vec::new(),
+            interactive: false,
         };
 
-        let result = execute(args);
+        let result = execute(args, &Workdir::new(temp_dir.path()).unwrap());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("already exists"));
     }
@@ -0,0 +1,550 @@
+//! Implementation of the `pave nightly` command.
+//!
+//! `pave nightly` is a single entry point for scheduled jobs. It runs every
+//! verification command, looks for docs that have fallen behind their code,
+//! records a coverage snapshot, checks external links, and writes one
+//! consolidated report, optionally posting it to a webhook.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::cli::{NightlyOutputFormat, OutputFormat, PathStyle};
+use crate::commands::coverage::{self, CoverageSnapshot};
+use crate::commands::lint::{self, LinkChecker, LintResults};
+use crate::commands::stale::{self, StaleDoc};
+use crate::commands::verify::{self, VerifyArgs, VerifyStatus};
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::Workdir;
+
+/// Nightly history file, relative to the config directory.
+pub const HISTORY_PATH: &str = ".pave/nightly-history.jsonl";
+
+/// Arguments for the `pave nightly` command.
+pub struct NightlyArgs {
+    /// Output format.
+    pub format: NightlyOutputFormat,
+    /// Write the report to this file instead of stdout.
+    pub output: Option<PathBuf>,
+    /// URL to POST the report to.
+    pub webhook: Option<String>,
+    /// Days a doc may lag behind its code before it counts as stale.
+    pub stale_days: u32,
+    /// Skip the external link check.
+    pub skip_links: bool,
+    /// Timeout per verification command in seconds.
+    pub timeout: u32,
+}
+
+/// Headline numbers from one nightly run, stored in the history file.
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize, PartialEq)]
+pub struct NightlySnapshot {
+    /// When the run started (RFC 3339).
+    pub timestamp: String,
+    /// Number of documents with verification sections.
+    pub documents_verified: usize,
+    /// Number of failed verification commands, including setup and teardown.
+    pub commands_failed: usize,
+    /// Number of stale documents, if staleness could be determined.
+    pub stale_docs: Option<usize>,
+    /// Overall coverage percentage.
+    pub coverage_percentage: f64,
+    /// Number of broken external links, if links were checked.
+    pub broken_links: Option<usize>,
+}
+
+/// A verification command that failed or timed out.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VerifyFailure {
+    /// Document the command belongs to.
+    pub file: PathBuf,
+    /// The failing command.
+    pub command: String,
+    /// How the command failed.
+    pub status: VerifyStatus,
+}
+
+/// Summary of the verification run.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct VerifySummary {
+    /// Number of documents with verification sections.
+    pub documents_verified: usize,
+    /// Number of commands executed.
+    pub commands_executed: usize,
+    /// Number of commands that passed.
+    pub commands_passed: usize,
+    /// Number of commands that failed, including setup and teardown.
+    pub commands_failed: usize,
+    /// Each failing command.
+    pub failures: Vec<VerifyFailure>,
+}
+
+/// Result of the external link check.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LinkSummary {
+    /// Number of distinct URLs checked.
+    pub urls_checked: usize,
+    /// Links that could not be fetched.
+    pub broken: Vec<lint::LintIssue>,
+}
+
+/// The consolidated nightly report.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NightlyReport {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// When the run started (RFC 3339).
+    pub generated_at: String,
+    /// Verification results.
+    pub verify: VerifySummary,
+    /// Threshold used for staleness, in days.
+    pub stale_days: u32,
+    /// Stale documents, or `None` if git history was unavailable.
+    pub stale: Option<Vec<StaleDoc>>,
+    /// Coverage snapshot recorded by this run.
+    pub coverage: CoverageSnapshot,
+    /// External link results, or `None` with `--skip-links`.
+    pub links: Option<LinkSummary>,
+    /// Headline numbers from the previous run, for comparison.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<NightlySnapshot>,
+}
+
+impl NightlyReport {
+    fn snapshot(&self) -> NightlySnapshot {
+        NightlySnapshot {
+            timestamp: self.generated_at.clone(),
+            documents_verified: self.verify.documents_verified,
+            commands_failed: self.verify.commands_failed,
+            stale_docs: self.stale.as_ref().map(Vec::len),
+            coverage_percentage: self.coverage.coverage_percentage,
+            broken_links: self.links.as_ref().map(|l| l.broken.len()),
+        }
+    }
+}
+
+/// Execute the `pave nightly` command.
+pub fn execute(args: NightlyArgs, workdir: &Workdir) -> Result<()> {
+    // Find and load config
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let generated_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let verify = run_verify(&args, &config, config_dir)?;
+    let stale =
+        stale::find_stale_docs(&config, config_dir, args.stale_days)?.map(|results| results.stale);
+    let coverage = coverage::take_snapshot(&config, config_dir)?;
+    let links = if args.skip_links {
+        None
+    } else {
+        Some(check_links(&config, config_dir)?)
+    };
+
+    let history_path = config_dir.join(HISTORY_PATH);
+    let report = NightlyReport {
+        schema_version: SCHEMA_VERSION,
+        generated_at,
+        verify,
+        stale_days: args.stale_days,
+        stale,
+        coverage,
+        links,
+        previous: load_previous(&history_path)?,
+    };
+
+    let markdown = render_markdown(&report);
+    let json = serde_json::to_string_pretty(&report).context("Failed to serialize report")?;
+    let output = match args.format {
+        NightlyOutputFormat::Markdown => &markdown,
+        NightlyOutputFormat::Json => &json,
+    };
+    match &args.output {
+        Some(path) => {
+            std::fs::write(workdir.resolve(path), format!("{}\n", output))
+                .with_context(|| format!("Failed to write report: {}", path.display()))?;
+            eprintln!("Report written to {}", path.display());
+        }
+        None => println!("{}", output),
+    }
+
+    // Record this run so the next one can show trends
+    append_history(&history_path, &report.snapshot())?;
+    coverage::append_snapshot(&config_dir.join(coverage::HISTORY_PATH), &report.coverage)?;
+
+    if let Some(url) = &args.webhook {
+        let payload = serde_json::json!({ "text": markdown, "report": report });
+        post_webhook(url, &payload.to_string())?;
+    }
+
+    let mut problems = Vec::new();
+    if report.verify.commands_failed > 0 {
+        problems.push(plural(report.verify.commands_failed, "failed command"));
+    }
+    if let Some(links) = report.links.as_ref().filter(|l| !l.broken.is_empty()) {
+        problems.push(plural(links.broken.len(), "broken link"));
+    }
+    if !problems.is_empty() {
+        anyhow::bail!(Violations(format!(
+            "Nightly run found {}",
+            problems.join(" and ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Run every verification command, bypassing the cache.
+fn run_verify(args: &NightlyArgs, config: &PaveConfig, config_dir: &Path) -> Result<VerifySummary> {
+    let verify_args = VerifyArgs {
+        paths: Vec::new(),
+        format: OutputFormat::Text,
+        report: None,
+        timeout: args.timeout,
+        keep_going: true,
+        path_style: PathStyle::Workspace,
+        no_cache: true,
+        no_services: false,
+        changed: false,
+        base: None,
+        export_script: None,
+        export_make: None,
+        include_drafts: false,
+        update_expected: false,
+        rerun_failed: None,
+    };
+    let Some(results) = verify::run_documents(&verify_args, config, config_dir)? else {
+        return Ok(VerifySummary::default());
+    };
+
+    let mut failures = Vec::new();
+    for doc in &results.documents {
+        let file = display_path(&doc.file, PathStyle::Workspace, config_dir, config_dir);
+        for command in doc.setup.iter().chain(&doc.commands).chain(&doc.teardown) {
+            if matches!(command.status, VerifyStatus::Fail | VerifyStatus::Timeout) {
+                failures.push(VerifyFailure {
+                    file: file.clone(),
+                    command: command.command.clone(),
+                    status: command.status,
+                });
+            }
+        }
+    }
+
+    Ok(VerifySummary {
+        documents_verified: results.documents_verified,
+        commands_executed: results.commands_executed,
+        commands_passed: results.commands_passed,
+        commands_failed: results.commands_failed + results.fixtures_failed,
+        failures,
+    })
+}
+
+/// Check every external link in the docs.
+fn check_links(config: &PaveConfig, config_dir: &Path) -> Result<LinkSummary> {
+    let ignore = load_ignore(config_dir, config)?;
+    let docs_root = config_dir.join(&config.docs.root);
+    let files = if docs_root.exists() {
+        find_markdown_files(&[docs_root], &ignore)?
+    } else {
+        Vec::new()
+    };
+    let mut checker = LinkChecker::default();
+    let mut results = LintResults::new();
+
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let display = display_path(file, PathStyle::Workspace, config_dir, config_dir);
+        lint::check_external_links(&display, &lines, &mut checker, &mut results);
+    }
+
+    Ok(LinkSummary {
+        urls_checked: checker.checked_count(),
+        broken: results.issues,
+    })
+}
+
+/// Render the report as markdown, suitable for chat webhooks and issues.
+fn render_markdown(report: &NightlyReport) -> String {
+    let previous = report.previous.as_ref();
+    let mut out = String::new();
+
+    out.push_str("# Documentation Health Report\n\n");
+    out.push_str(&format!("Generated {}", report.generated_at));
+    if let Some(previous) = previous {
+        out.push_str(&format!(" (previous run {})", previous.timestamp));
+    }
+    out.push_str(".\n\n");
+
+    let verify = &report.verify;
+    out.push_str("## Verification\n\n");
+    out.push_str(&format!(
+        "{} verified: {} of {} commands passed, {}{}.\n",
+        plural(verify.documents_verified, "document"),
+        verify.commands_passed,
+        verify.commands_executed,
+        plural(verify.commands_failed, "failure"),
+        format_change(verify.commands_failed, previous.map(|p| p.commands_failed))
+    ));
+    if !verify.failures.is_empty() {
+        out.push('\n');
+        for failure in &verify.failures {
+            let status = match failure.status {
+                VerifyStatus::Timeout => "timed out",
+                _ => "failed",
+            };
+            out.push_str(&format!(
+                "- `{}`: `{}` {}\n",
+                failure.file.display(),
+                failure.command,
+                status
+            ));
+        }
+    }
+
+    out.push_str("\n## Staleness\n\n");
+    match &report.stale {
+        None => out.push_str("Skipped: no git history available.\n"),
+        Some(stale) if stale.is_empty() => out.push_str(&format!(
+            "No docs lag their code by more than {} days.\n",
+            report.stale_days
+        )),
+        Some(stale) => {
+            out.push_str(&format!(
+                "{} lag their code by more than {} days{}.\n\n",
+                plural(stale.len(), "doc"),
+                report.stale_days,
+                format_change(stale.len(), previous.and_then(|p| p.stale_docs))
+            ));
+            out.push_str("| Document | Days behind |\n|----------|-------------|\n");
+            for doc in stale {
+                out.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    doc.file.display(),
+                    doc.days_behind
+                ));
+            }
+        }
+    }
+
+    let coverage = &report.coverage;
+    out.push_str("\n## Coverage\n\n");
+    out.push_str(&format!(
+        "{:.1}% of {} code files are documented ({} uncovered)",
+        coverage.coverage_percentage, coverage.total_files, coverage.uncovered_files
+    ));
+    if let Some(previous) = previous {
+        out.push_str(&format!(
+            ", {:+.1} points since the last run",
+            coverage.coverage_percentage - previous.coverage_percentage
+        ));
+    }
+    out.push_str(".\n");
+
+    out.push_str("\n## External Links\n\n");
+    match &report.links {
+        None => out.push_str("Skipped.\n"),
+        Some(links) => {
+            out.push_str(&format!(
+                "Checked {}: {} broken{}.\n",
+                plural(links.urls_checked, "URL"),
+                links.broken.len(),
+                format_change(links.broken.len(), previous.and_then(|p| p.broken_links))
+            ));
+            if !links.broken.is_empty() {
+                out.push('\n');
+                for issue in &links.broken {
+                    out.push_str(&format!(
+                        "- `{}:{}`: {}\n",
+                        issue.file.display(),
+                        issue.line,
+                        issue.message
+                    ));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Describe how a count changed since the previous run.
+fn format_change(current: usize, previous: Option<usize>) -> String {
+    match previous {
+        Some(previous) if previous != current => {
+            format!(
+                " ({:+} since the last run)",
+                current as i64 - previous as i64
+            )
+        }
+        _ => String::new(),
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+/// Read the most recent snapshot from the history file, if any.
+fn load_previous(path: &Path) -> Result<Option<NightlySnapshot>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read nightly history: {}", path.display()))?;
+
+    content
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid snapshot in nightly history: {}", path.display()))
+        })
+        .transpose()
+}
+
+/// Append a snapshot to the history file, creating it if needed.
+fn append_history(path: &Path, snapshot: &NightlySnapshot) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let line = serde_json::to_string(snapshot).context("Failed to serialize snapshot")?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open nightly history: {}", path.display()))?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Failed to write nightly history: {}", path.display()))
+}
+
+/// POST a JSON payload to a webhook with `curl`.
+fn post_webhook(url: &str, payload: &str) -> Result<()> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--max-time", "30"])
+        .args(["-X", "POST", "-H", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run curl for webhook notification")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(payload.as_bytes())
+            .context("Failed to send webhook payload to curl")?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for curl")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Webhook notification failed: {}", stderr.trim());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_report() -> NightlyReport {
+        NightlyReport {
+            schema_version: SCHEMA_VERSION,
+            generated_at: "2026-01-02T03:00:00Z".to_string(),
+            verify: VerifySummary {
+                documents_verified: 3,
+                commands_executed: 5,
+                commands_passed: 4,
+                commands_failed: 1,
+                failures: vec![VerifyFailure {
+                    file: PathBuf::from("docs/api.md"),
+                    command: "cargo test api".to_string(),
+                    status: VerifyStatus::Timeout,
+                }],
+            },
+            stale_days: 30,
+            stale: Some(vec![StaleDoc {
+                file: PathBuf::from("docs/cli.md"),
+                days_behind: 45,
+                doc_updated: "2025-11-01".to_string(),
+                code_updated: "2025-12-16".to_string(),
+                code_commits: 3,
+            }]),
+            coverage: CoverageSnapshot {
+                timestamp: "2026-01-02T03:00:00Z".to_string(),
+                coverage_percentage: 80.0,
+                docs_count: 4,
+                total_files: 10,
+                uncovered_files: 2,
+            },
+            links: None,
+            previous: None,
+        }
+    }
+
+    #[test]
+    fn markdown_report_has_each_section() {
+        let markdown = render_markdown(&sample_report());
+
+        assert!(markdown.contains("3 documents verified: 4 of 5 commands passed, 1 failure."));
+        assert!(markdown.contains("- `docs/api.md`: `cargo test api` timed out"));
+        assert!(markdown.contains("1 doc lag their code by more than 30 days."));
+        assert!(markdown.contains("| `docs/cli.md` | 45 |"));
+        assert!(markdown.contains("80.0% of 10 code files are documented (2 uncovered)."));
+        assert!(markdown.contains("## External Links\n\nSkipped."));
+    }
+
+    #[test]
+    fn markdown_report_compares_with_previous_run() {
+        let mut report = sample_report();
+        report.previous = Some(NightlySnapshot {
+            timestamp: "2026-01-01T03:00:00Z".to_string(),
+            documents_verified: 3,
+            commands_failed: 3,
+            stale_docs: Some(1),
+            coverage_percentage: 75.5,
+            broken_links: None,
+        });
+
+        let markdown = render_markdown(&report);
+
+        assert!(markdown.contains("(previous run 2026-01-01T03:00:00Z)"));
+        assert!(markdown.contains("1 failure (-2 since the last run)."));
+        assert!(markdown.contains("more than 30 days.\n"));
+        assert!(markdown.contains("+4.5 points since the last run"));
+    }
+
+    #[test]
+    fn history_returns_latest_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_PATH);
+        assert_eq!(load_previous(&path).unwrap(), None);
+
+        let mut report = sample_report();
+        append_history(&path, &report.snapshot()).unwrap();
+        report.generated_at = "2026-01-03T03:00:00Z".to_string();
+        report.verify.commands_failed = 0;
+        append_history(&path, &report.snapshot()).unwrap();
+
+        let previous = load_previous(&path).unwrap().unwrap();
+        assert_eq!(previous.timestamp, "2026-01-03T03:00:00Z");
+        assert_eq!(previous.commands_failed, 0);
+        assert_eq!(previous.stale_docs, Some(1));
+        assert_eq!(previous.broken_links, None);
+    }
+}
@@ -5,10 +5,20 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig, RulesSection};
-use crate::templates::{TemplateType, get_template};
+use crate::commands::new::default_output_path;
+use crate::commands::prompt_fix;
+use crate::config::{CONFIG_FILENAME, PaveConfig, RuleSeverity, RulesSection};
+use crate::rules::{DocType, Rule, detect_doc_type, get_type_specific_rules};
+use crate::templates::{TemplateType, load_template};
+use crate::workspace::Workdir;
+
+/// Prompt type asking an agent to fix a document's check and lint issues.
+pub const REVIEW_PROMPT: &str = "review";
+
+/// Prompt type asking an agent to fix a document's failing verification.
+pub const VERIFY_FIX_PROMPT: &str = "verify-fix";
 
 /// Output format for the generated prompt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -23,8 +33,9 @@ pub enum OutputFormat {
 /// Options for generating a prompt.
 #[derive(Debug, Clone)]
 pub struct PromptOptions {
-    /// The document type to generate.
-    pub doc_type: TemplateType,
+    /// The document type to generate: a built-in type or a project
+    /// template name.
+    pub doc_type: String,
     /// Name of the thing being documented.
     pub name: Option<String>,
     /// Path to existing document to update.
@@ -33,6 +44,11 @@ pub struct PromptOptions {
     pub context_paths: Vec<String>,
     /// Output format.
     pub output_format: OutputFormat,
+    /// Document to fix, for the `review` and `verify-fix` prompts.
+    pub doc_path: Option<String>,
+    /// `pave verify --report` file to read failures from instead of running
+    /// the document's commands, for the `verify-fix` prompt.
+    pub report_path: Option<String>,
 }
 
 /// JSON output structure for programmatic use.
@@ -49,12 +65,24 @@ pub struct PromptOutput {
 }
 
 /// Generate a prompt for AI agents to create PAVED documentation.
-pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
-    let config = load_config_or_default()?;
-    let template = get_template(options.doc_type);
-    let rules = format_rules(&config.rules);
-    let paved_sections = get_paved_sections(options.doc_type);
-    let doc_type_name = get_doc_type_name(options.doc_type);
+///
+/// The `review` and `verify-fix` types instead ask the agent to fix the
+/// problems `pave check`, `pave lint`, or `pave verify` find in a document.
+pub fn generate_prompt(options: &PromptOptions, workdir: &Workdir) -> Result<String> {
+    match options.doc_type.as_str() {
+        REVIEW_PROMPT => return prompt_fix::review_prompt(options, workdir),
+        VERIFY_FIX_PROMPT => return prompt_fix::verify_fix_prompt(options, workdir),
+        _ => {}
+    }
+
+    let config = load_config_or_default(&workdir.dir)?;
+    let doc_template = load_template(&config, &workdir.dir, &options.doc_type)?;
+    let template = doc_template.content.as_str();
+    let rules = format_rules(&config.rules, target_doc_type(options, template));
+    let (paved_sections, doc_type_name) = match doc_template.builtin_type {
+        Some(doc_type) => (get_paved_sections(doc_type), get_doc_type_name(doc_type)),
+        None => (get_template_sections(template), doc_template.name.as_str()),
+    };
 
     let mut prompt = String::new();
 
@@ -93,7 +121,7 @@ pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
 
     // Include existing document content if updating
     if let Some(update_path) = &options.update_path {
-        let existing_content = std::fs::read_to_string(update_path)
+        let existing_content = std::fs::read_to_string(workdir.resolve(Path::new(update_path)))
             .with_context(|| format!("failed to read existing document: {}", update_path))?;
         prompt.push_str("### Existing Document (to update)\n");
         prompt.push_str("```markdown\n");
@@ -103,7 +131,7 @@ pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
 
     // Include context files
     for path in &options.context_paths {
-        let content = std::fs::read_to_string(path)
+        let content = std::fs::read_to_string(workdir.resolve(Path::new(path)))
             .with_context(|| format!("failed to read context file: {}", path))?;
         prompt.push_str(&format!("### Context: {}\n", path));
         prompt.push_str("```\n");
@@ -152,29 +180,81 @@ pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
     }
 }
 
-/// Load configuration from .pave.toml or return defaults if not found.
-fn load_config_or_default() -> Result<PaveConfig> {
-    if Path::new(CONFIG_FILENAME).exists() {
-        PaveConfig::load(CONFIG_FILENAME)
+/// Load configuration from .pave.toml in `dir` or return defaults if not
+/// found.
+pub(crate) fn load_config_or_default(dir: &Path) -> Result<PaveConfig> {
+    let path = dir.join(CONFIG_FILENAME);
+    if path.exists() {
+        PaveConfig::load(path)
     } else {
         Ok(PaveConfig::default())
     }
 }
 
-/// Format rules section from configuration.
-fn format_rules(rules: &RulesSection) -> Vec<String> {
+/// Detect the document type `pave check` will see for the generated document.
+///
+/// Detection uses the path being updated, or where `pave new` would create
+/// the document, and falls back to the template's sections like
+/// `pave check` falls back to a document's content.
+fn target_doc_type(options: &PromptOptions, template: &str) -> DocType {
+    let target = match &options.update_path {
+        Some(path) => PathBuf::from(path),
+        None => default_output_path(
+            &options.doc_type,
+            options.name.as_deref().unwrap_or("new-doc"),
+        ),
+    };
+    detect_doc_type(&target, template)
+}
+
+/// Describe the rules `pave check` applies to a document of the given type.
+pub(crate) fn format_rules(rules: &RulesSection, doc_type: DocType) -> Vec<String> {
     let mut formatted = Vec::new();
 
-    formatted.push(format!("Maximum {} lines per document", rules.max_lines));
+    formatted.push(
+        Rule::MaxLines {
+            limit: rules.max_lines as usize,
+        }
+        .description(),
+    );
 
     if rules.require_verification {
+        formatted.push(
+            Rule::RequireSection {
+                name: "Verification".to_string(),
+            }
+            .description(),
+        );
         formatted.push("Verification section must include runnable commands".to_string());
     }
 
     if rules.require_examples {
+        formatted.push(
+            Rule::RequireSection {
+                name: "Examples".to_string(),
+            }
+            .description(),
+        );
         formatted.push("Examples must include expected output".to_string());
     }
 
+    for rule in get_type_specific_rules(doc_type, rules) {
+        let description = rule.description();
+        if !formatted.contains(&description) {
+            formatted.push(description);
+        }
+    }
+
+    if rules.strict_output_matching && !rules.skip_output_matching {
+        formatted.push(
+            "Expected output shown in Verification must match the real output exactly".to_string(),
+        );
+    }
+
+    if rules.duplicate_titles != RuleSeverity::Off {
+        formatted.push("The document title must not duplicate another document's".to_string());
+    }
+
     formatted
 }
 
@@ -208,33 +288,69 @@ fn get_paved_sections(doc_type: TemplateType) -> String {
              - **Alternatives Considered**: What else did we consider and why not?"
                 .to_string()
         }
+        TemplateType::Postmortem => {
+            "- **Summary**: What happened, in 2-3 sentences (blameless)\n\
+             - **Impact**: Who and what was affected, and for how long\n\
+             - **Timeline**: Timestamped events from first signal to resolution\n\
+             - **Root Cause**: Why it happened, past the first answer\n\
+             - **Action Items**: Follow-up work, each with an owner and tracking link\n\
+             - **Verification**: How to confirm the fix holds"
+                .to_string()
+        }
+        TemplateType::Api => {
+            "- **Purpose**: What the API does and who calls it\n\
+             - **Authentication**: How callers authenticate (tokens, scopes)\n\
+             - **Endpoints**: Methods, paths, and request/response schemas\n\
+             - **Errors**: Error codes and how callers should handle them\n\
+             - **Verification**: How do you know the API is working?\n\
+             - **Examples**: Copy/paste requests with realistic payloads"
+                .to_string()
+        }
     }
 }
 
+/// List a project template's `##` sections, for document types without a
+/// built-in description.
+fn get_template_sections(template: &str) -> String {
+    template
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .map(|heading| format!("- **{}**", heading.trim()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Get human-readable name for document type.
 fn get_doc_type_name(doc_type: TemplateType) -> &'static str {
     match doc_type {
         TemplateType::Component => "component",
         TemplateType::Runbook => "runbook",
         TemplateType::Adr => "architecture decision record (ADR)",
+        TemplateType::Postmortem => "incident postmortem",
+        TemplateType::Api => "API reference",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{
+        TypeSpecificRulesSection, VerificationCoverage, VerificationCoverageSection,
+    };
 
     #[test]
     fn prompt_includes_all_component_sections() {
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("auth service".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("## PAVED Structure"));
         assert!(prompt.contains("**Purpose**"));
@@ -249,14 +365,16 @@ mod tests {
     #[test]
     fn prompt_includes_all_runbook_sections() {
         let options = PromptOptions {
-            doc_type: TemplateType::Runbook,
+            doc_type: "runbook".to_string(),
             name: Some("deploy api".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("**When to Use**"));
         assert!(prompt.contains("**Preconditions**"));
@@ -269,14 +387,16 @@ mod tests {
     #[test]
     fn prompt_includes_all_adr_sections() {
         let options = PromptOptions {
-            doc_type: TemplateType::Adr,
+            doc_type: "adr".to_string(),
             name: Some("use postgres".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("**Status**"));
         assert!(prompt.contains("**Context**"));
@@ -288,14 +408,16 @@ mod tests {
     #[test]
     fn prompt_includes_project_rules() {
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("test".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("## Project Rules"));
         assert!(prompt.contains("Maximum 300 lines per document"));
@@ -306,14 +428,16 @@ mod tests {
     #[test]
     fn prompt_includes_template() {
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("test".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("## Template"));
         assert!(prompt.contains("{Component Name}"));
@@ -322,14 +446,16 @@ mod tests {
     #[test]
     fn prompt_includes_name_in_task() {
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("auth service".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("## Task"));
         assert!(prompt.contains("Create a PAVED component document for: auth service"));
@@ -338,14 +464,16 @@ mod tests {
     #[test]
     fn json_output_is_valid() {
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("test".to_string()),
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Json,
+            doc_path: None,
+            report_path: None,
         };
 
-        let output = generate_prompt(&options).unwrap();
+        let output = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
         let parsed: PromptOutput = serde_json::from_str(&output).unwrap();
 
         assert!(!parsed.prompt.is_empty());
@@ -364,14 +492,16 @@ mod tests {
         }
 
         let options = PromptOptions {
-            doc_type: TemplateType::Component,
+            doc_type: "component".to_string(),
             name: Some("test".to_string()),
             update_path: Some(temp_file.to_string_lossy().to_string()),
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
         };
 
-        let prompt = generate_prompt(&options).unwrap();
+        let prompt = generate_prompt(&options, &Workdir::current().unwrap()).unwrap();
 
         assert!(prompt.contains("Update the PAVED component document for: test"));
         assert!(prompt.contains("### Existing Document (to update)"));
@@ -394,12 +524,68 @@ mod tests {
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            ..Default::default()
         };
 
-        let formatted = format_rules(&rules);
+        let formatted = format_rules(&rules, DocType::Component);
 
         assert!(formatted.iter().any(|r| r.contains("500")));
-        assert!(!formatted.iter().any(|r| r.contains("Verification section")));
+        assert!(!formatted.iter().any(|r| r.contains("Verification")));
         assert!(formatted.iter().any(|r| r.contains("Examples")));
     }
+
+    #[test]
+    fn format_rules_includes_type_specific_rules() {
+        let rules = RulesSection {
+            type_specific: TypeSpecificRulesSection {
+                runbooks: true,
+                adrs: true,
+                components: false,
+                postmortems: false,
+                apis: false,
+            },
+            verification_coverage: VerificationCoverageSection {
+                runbook: VerificationCoverage::AllSteps,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let runbook = format_rules(&rules, DocType::Runbook);
+        assert!(runbook.contains(&"Must include a '## Rollback' section".to_string()));
+        assert!(
+            runbook
+                .iter()
+                .any(|r| r.contains("Every command in 'Steps'"))
+        );
+
+        let adr = format_rules(&rules, DocType::Adr);
+        assert!(adr.iter().any(|r| r.contains("proposed, accepted")));
+        assert!(!adr.iter().any(|r| r.contains("Rollback")));
+
+        let component = format_rules(&rules, DocType::Component);
+        assert!(!component.iter().any(|r| r.contains("'## Interface'")));
+    }
+
+    #[test]
+    fn target_doc_type_follows_update_path() {
+        let options = PromptOptions {
+            doc_type: "component".to_string(),
+            name: Some("deploy".to_string()),
+            update_path: Some("docs/runbooks/deploy.md".to_string()),
+            context_paths: vec![],
+            output_format: OutputFormat::Text,
+            doc_path: None,
+            report_path: None,
+        };
+        let template = crate::templates::get_template(TemplateType::Component);
+
+        assert_eq!(target_doc_type(&options, template), DocType::Runbook);
+
+        let options = PromptOptions {
+            update_path: None,
+            ..options
+        };
+        assert_eq!(target_doc_type(&options, template), DocType::Component);
+    }
 }
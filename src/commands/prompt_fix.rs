@@ -0,0 +1,472 @@
+//! Prompts asking an agent to fix an existing document.
+//!
+//! `pave prompt review --doc <path>` bundles the document with its
+//! `pave check` and `pave lint` issues; `pave prompt verify-fix --doc <path>`
+//! bundles it with its failing `pave verify` commands. Either way the agent
+//! is asked to fix exactly those problems, so a check → prompt → fix loop
+//! needs no human in between.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{OutputFormat as VerifyOutputFormat, PathStyle};
+use crate::commands::check::{CheckResults, Issue, check_file};
+use crate::commands::lint::{LintIssue, lint_document};
+use crate::commands::prompt::{OutputFormat, PromptOptions, format_rules, load_config_or_default};
+use crate::commands::verify::{self, CommandResult, VerifyArgs, VerifyResults, VerifyStatus};
+use crate::config::PaveConfig;
+use crate::rules::detect_doc_type;
+use crate::workspace::Workdir;
+
+/// Timeout per verification command, matching `pave verify`'s default.
+const VERIFY_TIMEOUT_SECS: u32 = 30;
+
+/// Longest command output included in a prompt, in lines. Longer output
+/// keeps its last lines, where errors usually are.
+const MAX_OUTPUT_LINES: usize = 40;
+
+/// JSON output of a fix prompt.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FixPromptOutput {
+    /// The full generated prompt, empty when there is nothing to fix.
+    pub prompt: String,
+    /// The document to fix.
+    pub document: String,
+    /// Project rules that apply to the document.
+    pub rules: Vec<String>,
+    /// The problems the agent is asked to fix.
+    pub problems: Vec<String>,
+}
+
+/// Generate a prompt to fix a document's `pave check` and `pave lint` issues.
+pub fn review_prompt(options: &PromptOptions, workdir: &Workdir) -> Result<String> {
+    review(
+        options,
+        &load_config_or_default(&workdir.dir)?,
+        &workdir.dir,
+    )
+}
+
+fn review(options: &PromptOptions, config: &PaveConfig, config_dir: &Path) -> Result<String> {
+    let doc = &config_dir.join(required_doc(options, "review")?);
+
+    let mut results = CheckResults::new();
+    check_file(doc, config, &mut results)?;
+    let lint_issues = lint_document(doc, config, config_dir)?;
+
+    let problems: Vec<String> = results
+        .errors
+        .iter()
+        .chain(&results.warnings)
+        .map(describe_issue)
+        .chain(lint_issues.iter().map(describe_lint_issue))
+        .collect();
+
+    fix_prompt(
+        options,
+        doc,
+        config,
+        problems,
+        FixKind {
+            tools: "`pave check` and `pave lint`",
+            heading: "Violations",
+            instruction: "Fix exactly the violations listed above.",
+        },
+    )
+}
+
+/// Generate a prompt to fix a document's failing verification commands.
+///
+/// The commands are run, as `pave verify` would run them, unless a
+/// `pave verify --report` file is given.
+pub fn verify_fix_prompt(options: &PromptOptions, workdir: &Workdir) -> Result<String> {
+    verify_fix(
+        options,
+        &load_config_or_default(&workdir.dir)?,
+        &workdir.dir,
+    )
+}
+
+fn verify_fix(options: &PromptOptions, config: &PaveConfig, config_dir: &Path) -> Result<String> {
+    let doc = &config_dir.join(required_doc(options, "verify-fix")?);
+
+    let results = match &options.report_path {
+        Some(report) => load_report(&config_dir.join(report))?,
+        None => run_verification(doc, config, config_dir)?,
+    };
+    let problems: Vec<String> = results
+        .documents
+        .iter()
+        .filter(|result| same_doc(&result.file, doc))
+        .flat_map(|result| {
+            result
+                .setup
+                .iter()
+                .map(|cmd| ("setup ", cmd))
+                .chain(result.commands.iter().map(|cmd| ("", cmd)))
+                .chain(result.teardown.iter().map(|cmd| ("teardown ", cmd)))
+        })
+        .filter(|(_, cmd)| is_failure(cmd))
+        .map(|(label, cmd)| describe_failure(label, cmd))
+        .collect();
+
+    fix_prompt(
+        options,
+        doc,
+        config,
+        problems,
+        FixKind {
+            tools: "`pave verify`",
+            heading: "Failing Verification",
+            instruction: "Fix the document so these commands pass: correct commands \
+                          that are wrong, and update expected output that is out of date. \
+                          If the output shows a bug in the code rather than the document, \
+                          say so instead of changing the expected output to match it.",
+        },
+    )
+}
+
+/// How a fix prompt describes its problems.
+struct FixKind {
+    /// The commands that found the problems.
+    tools: &'static str,
+    /// Heading of the problems section.
+    heading: &'static str,
+    /// What to do about them.
+    instruction: &'static str,
+}
+
+fn required_doc<'a>(options: &'a PromptOptions, prompt_type: &str) -> Result<&'a Path> {
+    match &options.doc_path {
+        Some(doc) => Ok(Path::new(doc)),
+        None => bail!(
+            "`pave prompt {}` needs the document to fix: pass --doc <path>",
+            prompt_type
+        ),
+    }
+}
+
+fn fix_prompt(
+    options: &PromptOptions,
+    doc: &Path,
+    config: &PaveConfig,
+    problems: Vec<String>,
+    kind: FixKind,
+) -> Result<String> {
+    let content = std::fs::read_to_string(doc)
+        .with_context(|| format!("failed to read document: {}", doc.display()))?;
+    let rules = format_rules(&config.rules, detect_doc_type(doc, &content));
+
+    let prompt = if problems.is_empty() {
+        eprintln!(
+            "{} has no problems reported by {}; nothing to fix",
+            doc.display(),
+            kind.tools
+        );
+        String::new()
+    } else {
+        render_fix_prompt(doc, &content, &rules, &problems, &kind)
+    };
+
+    match options.output_format {
+        OutputFormat::Text => Ok(prompt),
+        OutputFormat::Json => {
+            let output = FixPromptOutput {
+                prompt,
+                document: doc.display().to_string(),
+                rules,
+                problems,
+            };
+            serde_json::to_string_pretty(&output).context("failed to serialize JSON output")
+        }
+    }
+}
+
+fn render_fix_prompt(
+    doc: &Path,
+    content: &str,
+    rules: &[String],
+    problems: &[String],
+    kind: &FixKind,
+) -> String {
+    let mut prompt = String::new();
+
+    prompt.push_str(&format!(
+        "You are fixing a PAVED document that fails {}.\n\n",
+        kind.tools
+    ));
+
+    prompt.push_str("## Project Rules\n");
+    for rule in rules {
+        prompt.push_str(&format!("- {}\n", rule));
+    }
+    prompt.push('\n');
+
+    prompt.push_str(&format!("## Document: {}\n", doc.display()));
+    prompt.push_str("```markdown\n");
+    prompt.push_str(content);
+    if !content.ends_with('\n') {
+        prompt.push('\n');
+    }
+    prompt.push_str("```\n\n");
+
+    prompt.push_str(&format!("## {}\n", kind.heading));
+    for problem in problems {
+        prompt.push_str(&format!("- {}\n", problem));
+    }
+    prompt.push('\n');
+
+    prompt.push_str("## Task\n");
+    prompt.push_str(kind.instruction);
+    prompt.push_str(
+        " Leave the rest of the document unchanged, and reply with the complete \
+         corrected document.\n",
+    );
+    prompt
+}
+
+fn describe_issue(issue: &Issue) -> String {
+    let mut description = format!("Line {}: {} ({})", issue.line, issue.message, issue.rule);
+    if let Some(hint) = &issue.hint {
+        description.push_str(&format!(". Hint: {}", hint));
+    }
+    description
+}
+
+fn describe_lint_issue(issue: &LintIssue) -> String {
+    format!("Line {}: {} ({})", issue.line, issue.message, issue.rule)
+}
+
+/// Run the document's verification section, every command, without the cache.
+fn run_verification(doc: &Path, config: &PaveConfig, config_dir: &Path) -> Result<VerifyResults> {
+    let args = VerifyArgs {
+        paths: vec![doc.to_path_buf()],
+        format: VerifyOutputFormat::Text,
+        report: None,
+        timeout: VERIFY_TIMEOUT_SECS,
+        keep_going: true,
+        path_style: PathStyle::Workspace,
+        no_cache: true,
+        no_services: false,
+        changed: false,
+        base: None,
+        export_script: None,
+        export_make: None,
+        include_drafts: true,
+        update_expected: false,
+        rerun_failed: None,
+    };
+    let results = verify::run_documents(&args, config, config_dir)?;
+    Ok(results.unwrap_or_else(VerifyResults::new))
+}
+
+fn load_report(path: &Path) -> Result<VerifyResults> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read verify report: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse verify report: {}", path.display()))
+}
+
+/// Whether a report's document path and the requested one name the same
+/// file. Report paths may be absolute or relative to the project root.
+fn same_doc(reported: &Path, doc: &Path) -> bool {
+    let strip = |path: &Path| -> PathBuf { path.strip_prefix(".").unwrap_or(path).to_path_buf() };
+    let (reported, doc) = (strip(reported), strip(doc));
+    reported.ends_with(&doc) || doc.ends_with(&reported)
+}
+
+/// Whether `pave verify` reported a problem with the command: a failure, or
+/// a warning about output that no longer matches the document.
+fn is_failure(cmd: &CommandResult) -> bool {
+    match cmd.status {
+        VerifyStatus::Fail
+        | VerifyStatus::Timeout
+        | VerifyStatus::SetupFailed
+        | VerifyStatus::TeardownFailed => true,
+        VerifyStatus::Warn => cmd.output_mismatch.is_some(),
+        VerifyStatus::Pass | VerifyStatus::Skipped | VerifyStatus::Cached => false,
+    }
+}
+
+/// Describe a failed command with the output an agent needs to fix it.
+fn describe_failure(label: &str, cmd: &CommandResult) -> String {
+    let mut description = format!("{}command `{}`", label, cmd.command);
+
+    if let Some(reason) = &cmd.preflight_error {
+        description.push_str(&format!(" was not run: {}", reason));
+        return description;
+    }
+    match (cmd.status, cmd.exit_code) {
+        (VerifyStatus::Timeout, _) => description.push_str(" timed out"),
+        (VerifyStatus::Warn, _) => description.push_str(" printed unexpected output"),
+        (_, Some(code)) if code != cmd.expected_exit_code => description.push_str(&format!(
+            " exited with {} (expected {})",
+            code, cmd.expected_exit_code
+        )),
+        _ => description.push_str(" failed"),
+    }
+
+    if let Some(mismatch) = &cmd.output_mismatch {
+        description.push_str(&format!(
+            "\n  Expected output ({}):\n{}",
+            mismatch.strategy,
+            indent_block(&mismatch.expected)
+        ));
+        description.push_str(&format!(
+            "\n  Actual output:\n{}",
+            indent_block(&mismatch.actual)
+        ));
+    } else {
+        for (name, output) in [("stdout", &cmd.stdout), ("stderr", &cmd.stderr)] {
+            if let Some(output) = output.as_deref().filter(|o| !o.trim().is_empty()) {
+                description.push_str(&format!("\n  {}:\n{}", name, indent_block(output)));
+            }
+        }
+    }
+    description
+}
+
+/// Fence `text` as a code block nested in a list item, keeping its last
+/// [`MAX_OUTPUT_LINES`] lines.
+fn indent_block(text: &str) -> String {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    let skipped = lines.len().saturating_sub(MAX_OUTPUT_LINES);
+    let mut block = String::from("  ```\n");
+    if skipped > 0 {
+        block.push_str(&format!("  ... ({} earlier lines omitted)\n", skipped));
+    }
+    for line in &lines[skipped..] {
+        block.push_str(&format!("  {}\n", line));
+    }
+    block.push_str("  ```");
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn options(doc: &Path) -> PromptOptions {
+        PromptOptions {
+            doc_type: "review".to_string(),
+            name: None,
+            update_path: None,
+            context_paths: vec![],
+            output_format: OutputFormat::Json,
+            doc_path: Some(doc.to_string_lossy().into_owned()),
+            report_path: None,
+        }
+    }
+
+    #[test]
+    fn review_prompt_lists_check_and_lint_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("auth.md");
+        fs::write(
+            &doc,
+            "# Auth\n\n## Purpose\nHandles login.   \n\n## Examples\n```bash\n$ login\nok\n```\n",
+        )
+        .unwrap();
+
+        let json = review(&options(&doc), &PaveConfig::default(), temp_dir.path()).unwrap();
+        let output: FixPromptOutput = serde_json::from_str(&json).unwrap();
+
+        assert!(
+            output
+                .problems
+                .iter()
+                .any(|p| p.contains("Verification") && p.contains("require-section"))
+        );
+        assert!(
+            output
+                .problems
+                .iter()
+                .any(|p| p.starts_with("Line 4:") && p.contains("trailing-whitespace"))
+        );
+        assert!(output.prompt.contains("## Violations\n"));
+        assert!(output.prompt.contains("Handles login."));
+        assert!(
+            output
+                .prompt
+                .contains("Fix exactly the violations listed above.")
+        );
+    }
+
+    #[test]
+    fn verify_fix_prompt_includes_failing_output_from_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("docs/deploy.md");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        fs::write(
+            &doc,
+            "# Deploy\n\n## Verification\n```bash\n$ echo hi\nhello\n```\n",
+        )
+        .unwrap();
+        let report = temp_dir.path().join("verify.json");
+        let report_json = serde_json::json!({
+            "schema_version": 1,
+            "documents_verified": 1,
+            "commands_executed": 2,
+            "commands_passed": 1,
+            "commands_warned": 0,
+            "commands_failed": 1,
+            "commands_cached": 0,
+            "fixtures_failed": 0,
+            "documents": [{
+                "file": "docs/deploy.md",
+                "section_line": 3,
+                "status": "fail",
+                "commands": [
+                    {"command": "true", "status": "pass", "exit_code": 0, "expected_exit_code": 0},
+                    {
+                        "command": "echo hi",
+                        "status": "fail",
+                        "exit_code": 0,
+                        "expected_exit_code": 0,
+                        "output_mismatch": {
+                            "expected": "hello",
+                            "strategy": "contains",
+                            "actual": "hi\n",
+                            "expected_lines": ["hello"],
+                            "actual_lines": ["hi"]
+                        }
+                    }
+                ]
+            }]
+        });
+        fs::write(&report, report_json.to_string()).unwrap();
+
+        let mut options = options(&doc);
+        options.doc_type = "verify-fix".to_string();
+        options.report_path = Some(report.to_string_lossy().into_owned());
+        let json = verify_fix(&options, &PaveConfig::default(), temp_dir.path()).unwrap();
+        let output: FixPromptOutput = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(output.problems.len(), 1);
+        let problem = &output.problems[0];
+        assert!(problem.starts_with("command `echo hi` failed"));
+        assert!(problem.contains("Expected output (contains):\n  ```\n  hello\n  ```"));
+        assert!(problem.contains("Actual output:\n  ```\n  hi\n  ```"));
+        assert!(output.prompt.contains("## Failing Verification\n"));
+    }
+
+    #[test]
+    fn fix_prompts_need_a_doc() {
+        let mut options = options(Path::new("x.md"));
+        options.doc_path = None;
+        let error = review(&options, &PaveConfig::default(), Path::new(".")).unwrap_err();
+        assert!(error.to_string().contains("--doc"));
+    }
+
+    #[test]
+    fn long_output_keeps_its_last_lines() {
+        let output: String = (1..=50).map(|n| format!("line {}\n", n)).collect();
+        let block = indent_block(&output);
+        assert!(block.contains("(10 earlier lines omitted)"));
+        assert!(!block.contains("  line 10\n"));
+        assert!(block.contains("  line 50\n  ```"));
+    }
+}
@@ -0,0 +1,925 @@
+//! Implementation of the `pave publish` command.
+//!
+//! `pave publish --target confluence` mirrors the docs into the Confluence
+//! space configured in `[publish.confluence]`:
+//!
+//! ```toml
+//! [publish.confluence]
+//! base_url = "https://acme.atlassian.net/wiki"
+//! space = "ENG"
+//! parent_id = "123456"
+//! labels = ["pave"]
+//! ```
+//!
+//! Each doc that passes `pave check` becomes a page titled after the doc,
+//! with its `pave.tags` as labels. Drafts are left out unless asked for.
+//! The page ID of every published doc is kept in [`STATE_PATH`], so
+//! publishing again updates the same pages, and docs whose content hasn't
+//! changed since are skipped. Without a recorded ID, a page with the doc's
+//! title in the space is updated rather than duplicated.
+//!
+//! Requests go through `curl`, authenticated with the token in
+//! `CONFLUENCE_TOKEN`: as basic auth when `CONFLUENCE_USER` is set too
+//! (Confluence Cloud), otherwise as a bearer token (Data Center). Credentials
+//! and page bodies are passed on curl's stdin, not its command line.
+
+use anyhow::{Context, Result, bail};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd, html};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::info;
+
+use crate::cli::{PublishOutputFormat, PublishTarget};
+use crate::commands::check::{CheckResults, check_file, skip_reason};
+use crate::config::{ConfluencePublish, PaveConfig};
+use crate::include;
+use crate::parser::{CodeBlockTracker, DocState, ParsedDoc, frontmatter_len};
+use crate::paths::absolutize;
+use crate::schema::SCHEMA_VERSION;
+use crate::vars;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
+
+/// Page IDs of published docs, relative to the config directory. Commit it
+/// so every checkout updates the same pages.
+pub const STATE_PATH: &str = ".pave/publish-confluence.json";
+
+/// Arguments for the `pave publish` command.
+pub struct PublishArgs {
+    /// Where to publish.
+    pub target: PublishTarget,
+    /// Report what would be published without sending anything.
+    pub dry_run: bool,
+    /// Also publish docs with `pave.state: draft`.
+    pub include_drafts: bool,
+    /// Republish docs even if they haven't changed.
+    pub force: bool,
+    /// Output format.
+    pub format: PublishOutputFormat,
+}
+
+/// What publishing did with a doc.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishAction {
+    /// A new page was created.
+    Created,
+    /// An existing page was updated.
+    Updated,
+    /// The page is already up to date.
+    Unchanged,
+    /// The doc isn't published: it's a draft or fails `pave check`.
+    Skipped,
+    /// Confluence rejected the page, or couldn't be reached.
+    Failed,
+}
+
+/// A doc and its page.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PublishedDoc {
+    /// Path to the doc.
+    pub path: PathBuf,
+    /// Page title.
+    pub title: String,
+    /// What publishing did.
+    pub action: PublishAction,
+    /// ID of the page, once it exists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_id: Option<String>,
+    /// Why the doc was skipped or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Results of the publish command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PublishResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Key of the space published to.
+    pub space: String,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+    /// Every doc, in path order.
+    pub documents: Vec<PublishedDoc>,
+}
+
+impl PublishResults {
+    fn count(&self, action: PublishAction) -> usize {
+        self.documents
+            .iter()
+            .filter(|doc| doc.action == action)
+            .count()
+    }
+}
+
+/// A doc ready to publish.
+#[derive(Debug, Clone)]
+struct Page {
+    /// Path to the doc, relative to the config directory.
+    path: PathBuf,
+    title: String,
+    labels: Vec<String>,
+    /// Body in Confluence storage format.
+    body: String,
+}
+
+impl Page {
+    /// Hash of everything published, to tell whether the page changed.
+    fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [&self.title, &self.labels.join(","), &self.body] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// The page published for each doc.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PublishState {
+    /// Space the pages are in; IDs from another space are ignored.
+    space: String,
+    /// Pages by doc path, relative to the config directory.
+    pages: BTreeMap<String, PageState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct PageState {
+    id: String,
+    version: u64,
+    hash: String,
+}
+
+impl PublishState {
+    /// Load the state for `space`, starting empty if the file is missing,
+    /// unreadable, or for another space.
+    fn load(path: &Path, space: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|state| state.space == space)
+            .unwrap_or_else(|| Self {
+                space: space.to_string(),
+                pages: BTreeMap::new(),
+            })
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize state")?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write state: {}", path.display()))
+    }
+}
+
+/// Sends requests to the Confluence REST API.
+trait ConfluenceApi {
+    /// Send `method` to `path` under `/rest/api/`, with an optional JSON
+    /// body. Returns the status and the JSON response, or `Null` if empty.
+    fn send(&mut self, method: &str, path: &str, body: Option<&Value>) -> Result<(u16, Value)>;
+}
+
+/// The REST API reached with `curl`.
+struct Curl {
+    api_root: String,
+    user: Option<String>,
+    token: String,
+}
+
+impl ConfluenceApi for Curl {
+    fn send(&mut self, method: &str, path: &str, body: Option<&Value>) -> Result<(u16, Value)> {
+        // A curl config on stdin keeps the token out of the process list
+        let mut config = format!(
+            "url = \"{}\"\nrequest = \"{}\"\nheader = \"Accept: application/json\"\n\
+             write-out = \"\\n%{{http_code}}\"\n",
+            curl_quote(&format!("{}/{}", self.api_root, path)),
+            method
+        );
+        match &self.user {
+            Some(user) => config.push_str(&format!(
+                "user = \"{}\"\n",
+                curl_quote(&format!("{}:{}", user, self.token))
+            )),
+            None => config.push_str(&format!(
+                "header = \"Authorization: Bearer {}\"\n",
+                curl_quote(&self.token)
+            )),
+        }
+        if let Some(body) = body {
+            config.push_str("header = \"Content-Type: application/json\"\n");
+            config.push_str(&format!(
+                "data-binary = \"{}\"\n",
+                curl_quote(&body.to_string())
+            ));
+        }
+
+        let mut child = Command::new("curl")
+            .args(["-sS", "-K", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(config.as_bytes())
+                .context("Failed to pass the request to curl")?;
+        }
+        let output = child.wait_with_output().context("Failed to run curl")?;
+        if !output.status.success() {
+            bail!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (response, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        let status = status
+            .trim()
+            .parse()
+            .with_context(|| format!("Unexpected curl output: {}", stdout))?;
+        let json = if response.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(response).unwrap_or_else(|_| Value::String(response.into()))
+        };
+        Ok((status, json))
+    }
+}
+
+/// Quote a value for a double-quoted curl config string.
+fn curl_quote(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+/// Execute the `pave publish` command.
+pub fn execute(args: PublishArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let PublishTarget::Confluence = args.target;
+    let settings = config.publish.confluence.as_ref().context(
+        "pave publish --target confluence needs a [publish.confluence] section in .pave.toml",
+    )?;
+
+    let (pages, mut documents) = collect_pages(&config, config_dir, args.include_drafts)?;
+    let state_path = config_dir.join(STATE_PATH);
+    let mut state = PublishState::load(&state_path, &settings.space);
+
+    let mut api = if args.dry_run {
+        None
+    } else {
+        let token = env::var(&settings.token_env).with_context(|| {
+            format!(
+                "Set {} to a Confluence API token to publish",
+                settings.token_env
+            )
+        })?;
+        Some(Curl {
+            api_root: format!("{}/rest/api", settings.base_url.trim_end_matches('/')),
+            user: env::var(&settings.user_env)
+                .ok()
+                .filter(|user| !user.is_empty()),
+            token,
+        })
+    };
+    documents.extend(publish(
+        &pages,
+        settings,
+        &mut state,
+        api.as_mut().map(|api| api as &mut dyn ConfluenceApi),
+        args.force,
+    ));
+    documents.sort_by(|a, b| a.path.cmp(&b.path));
+    if !args.dry_run {
+        state.save(&state_path)?;
+    }
+
+    let results = PublishResults {
+        schema_version: SCHEMA_VERSION,
+        space: settings.space.clone(),
+        dry_run: args.dry_run,
+        documents,
+    };
+    match args.format {
+        PublishOutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&results).context("Failed to serialize results")?
+        ),
+        PublishOutputFormat::Text => output_text(&results, settings),
+    }
+
+    let failed = results.count(PublishAction::Failed);
+    if failed > 0 {
+        bail!("{} page(s) failed to publish", failed);
+    }
+    Ok(())
+}
+
+/// Read and convert every doc that should be published. Docs that
+/// shouldn't are returned as skipped.
+fn collect_pages(
+    config: &PaveConfig,
+    config_dir: &Path,
+    include_drafts: bool,
+) -> Result<(Vec<Page>, Vec<PublishedDoc>)> {
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, config)?;
+
+    let mut docs = Vec::new();
+    let mut skipped = Vec::new();
+    for path in find_docs(&docs_root, &ignore)? {
+        if let Some(reason) = skip_reason(&path) {
+            info!("Not publishing {}: {}", path.display(), reason);
+            continue;
+        }
+        let relative = path.strip_prefix(config_dir).unwrap_or(&path).to_path_buf();
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let doc = ParsedDoc::parse_content(path.clone(), &content)?;
+        let title = doc.title.clone().unwrap_or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let mut skip = |reason: String| {
+            skipped.push(PublishedDoc {
+                path: relative.clone(),
+                title: title.clone(),
+                action: PublishAction::Skipped,
+                page_id: None,
+                reason: Some(reason),
+            })
+        };
+        if doc.state() == DocState::Draft && !include_drafts {
+            skip("draft (publish it with --include-drafts)".to_string());
+            continue;
+        }
+        let mut results = CheckResults::new();
+        check_file(&path, config, &mut results)?;
+        if !results.errors.is_empty() {
+            skip(format!(
+                "fails pave check with {} error(s)",
+                results.errors.len()
+            ));
+            continue;
+        }
+
+        let content = include::expand(&content, &path, &docs_root)
+            .with_context(|| format!("failed to expand includes in {}", path.display()))?;
+        let content = vars::render(&content, &config.vars);
+        let labels = doc
+            .frontmatter
+            .as_ref()
+            .map(|frontmatter| frontmatter.tags.clone())
+            .unwrap_or_default();
+        docs.push((
+            absolutize(&path, config_dir),
+            relative,
+            title,
+            labels,
+            content,
+        ));
+    }
+
+    let titles: BTreeMap<PathBuf, String> = docs
+        .iter()
+        .map(|(absolute, _, title, _, _)| (absolute.clone(), title.clone()))
+        .collect();
+    let pages = docs
+        .into_iter()
+        .map(|(absolute, path, title, labels, content)| Page {
+            body: storage_format(&page_markdown(&content), &absolute, &titles, config_dir),
+            path,
+            title,
+            labels,
+        })
+        .collect();
+    Ok((pages, skipped))
+}
+
+/// A doc's markdown without its frontmatter and `# Title`, which becomes
+/// the page title instead.
+fn page_markdown(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let body = &lines[frontmatter_len(&lines)..];
+    let mut tracker = CodeBlockTracker::new();
+    let title = body.iter().position(|line| {
+        let in_code = tracker.in_code_block();
+        let fence = tracker.process_line(line);
+        !in_code && !fence && line.starts_with("# ")
+    });
+    body.iter()
+        .enumerate()
+        .filter(|(idx, _)| Some(*idx) != title)
+        .map(|(_, line)| *line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Convert markdown to Confluence storage format.
+///
+/// Code blocks become code macros, links to other published docs become
+/// links to their pages, and HTML comments such as `pave:` markers are
+/// dropped. `doc` and the keys of `titles` are absolute paths.
+fn storage_format(
+    markdown: &str,
+    doc: &Path,
+    titles: &BTreeMap<PathBuf, String>,
+    cwd: &Path,
+) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut events = Vec::new();
+    let mut code: Option<(String, String)> = None;
+    let mut page_links = Vec::new();
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code = Some((language, String::new()));
+            }
+            Event::Text(text) if code.is_some() => {
+                if let Some((_, body)) = &mut code {
+                    body.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, body)) = code.take() {
+                    events.push(Event::Html(code_macro(&language, &body).into()));
+                }
+            }
+            Event::Start(Tag::Link { ref dest_url, .. }) => {
+                match linked_title(dest_url, doc, titles, cwd) {
+                    Some(title) => {
+                        page_links.push(true);
+                        events.push(Event::Html(
+                            format!(
+                                "<ac:link><ri:page ri:content-title=\"{}\" /><ac:link-body>",
+                                escape_xml(title)
+                            )
+                            .into(),
+                        ));
+                    }
+                    None => {
+                        page_links.push(false);
+                        events.push(event);
+                    }
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if page_links.pop() == Some(true) {
+                    events.push(Event::Html("</ac:link-body></ac:link>".into()));
+                } else {
+                    events.push(event);
+                }
+            }
+            Event::Html(ref text) | Event::InlineHtml(ref text)
+                if text.trim_start().starts_with("<!--") => {}
+            other => events.push(other),
+        }
+    }
+
+    let mut storage = String::new();
+    html::push_html(&mut storage, events.into_iter());
+    storage
+}
+
+/// Title of the published doc a relative link points at.
+fn linked_title<'a>(
+    dest: &str,
+    doc: &Path,
+    titles: &'a BTreeMap<PathBuf, String>,
+    cwd: &Path,
+) -> Option<&'a String> {
+    let path = dest.split('#').next().unwrap_or(dest);
+    if path.is_empty() || path.contains(':') || path.starts_with('/') {
+        return None;
+    }
+    let dir = doc.parent().unwrap_or_else(|| Path::new(""));
+    titles.get(&absolutize(&dir.join(path), cwd))
+}
+
+/// A Confluence code macro. `]]>` can't appear in CDATA, so it is split
+/// across two sections.
+fn code_macro(language: &str, code: &str) -> String {
+    let language = match language {
+        "sh" | "shell" | "console" | "zsh" => "bash",
+        "py" | "python3" => "python",
+        "js" | "node" => "javascript",
+        "yml" => "yaml",
+        "rs" => "rust",
+        "ts" => "typescript",
+        other => other,
+    };
+    let parameter = if language.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+            escape_xml(language)
+        )
+    };
+    format!(
+        "<ac:structured-macro ac:name=\"code\">{}<ac:plain-text-body><![CDATA[{}]]>\
+         </ac:plain-text-body></ac:structured-macro>\n",
+        parameter,
+        code.trim_end_matches('\n')
+            .replace("]]>", "]]]]><![CDATA[>")
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A Confluence label: lowercase, with spaces replaced by `-`.
+fn label(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Publish every page that changed since it was last published, recording
+/// page IDs in `state`. Without `api`, report what would happen.
+fn publish(
+    pages: &[Page],
+    settings: &ConfluencePublish,
+    state: &mut PublishState,
+    mut api: Option<&mut dyn ConfluenceApi>,
+    force: bool,
+) -> Vec<PublishedDoc> {
+    let mut documents = Vec::new();
+    for page in pages {
+        let key = page.path.to_string_lossy().into_owned();
+        let known = state.pages.get(&key).cloned();
+        let hash = page.hash();
+        let mut doc = PublishedDoc {
+            path: page.path.clone(),
+            title: page.title.clone(),
+            action: PublishAction::Unchanged,
+            page_id: known.as_ref().map(|known| known.id.clone()),
+            reason: None,
+        };
+        if !force && known.as_ref().is_some_and(|known| known.hash == hash) {
+            documents.push(doc);
+            continue;
+        }
+
+        let Some(api) = api.as_deref_mut() else {
+            doc.action = if known.is_some() {
+                PublishAction::Updated
+            } else {
+                PublishAction::Created
+            };
+            documents.push(doc);
+            continue;
+        };
+        match publish_page(api, settings, page, known.as_ref().map(|k| k.id.as_str())) {
+            Ok((id, version, created)) => {
+                doc.action = if created {
+                    PublishAction::Created
+                } else {
+                    PublishAction::Updated
+                };
+                doc.page_id = Some(id.clone());
+                state.pages.insert(key, PageState { id, version, hash });
+            }
+            Err(err) => {
+                doc.action = PublishAction::Failed;
+                doc.reason = Some(format!("{:#}", err));
+            }
+        }
+        documents.push(doc);
+    }
+    documents
+}
+
+/// Create or update a page, then add its labels. Returns the page ID, its
+/// new version, and whether it was created.
+fn publish_page(
+    api: &mut dyn ConfluenceApi,
+    settings: &ConfluencePublish,
+    page: &Page,
+    known_id: Option<&str>,
+) -> Result<(String, u64, bool)> {
+    let version = |json: &Value| json["version"]["number"].as_u64().unwrap_or(1);
+
+    // The recorded page, unless it was deleted; else one with the same title
+    let mut existing = None;
+    if let Some(id) = known_id {
+        let (status, json) = api.send("GET", &format!("content/{}?expand=version", id), None)?;
+        match status {
+            200 => existing = Some((id.to_string(), version(&json))),
+            404 => {}
+            _ => bail!(api_error(status, &json)),
+        }
+    }
+    if existing.is_none() {
+        let query = format!(
+            "content?spaceKey={}&title={}&expand=version",
+            percent_encode(&settings.space),
+            percent_encode(&page.title)
+        );
+        let (status, json) = api.send("GET", &query, None)?;
+        if status != 200 {
+            bail!(api_error(status, &json));
+        }
+        if let Some(found) = json["results"].get(0)
+            && let Some(id) = found["id"].as_str()
+        {
+            existing = Some((id.to_string(), version(found)));
+        }
+    }
+
+    let mut body = json!({
+        "type": "page",
+        "title": page.title,
+        "space": {"key": settings.space},
+        "body": {"storage": {"value": page.body, "representation": "storage"}},
+    });
+    let (status, json) = match &existing {
+        Some((id, current)) => {
+            body["version"] = json!({"number": current + 1});
+            api.send("PUT", &format!("content/{}", id), Some(&body))?
+        }
+        None => {
+            if let Some(parent) = &settings.parent_id {
+                body["ancestors"] = json!([{"id": parent}]);
+            }
+            api.send("POST", "content", Some(&body))?
+        }
+    };
+    if !(200..300).contains(&status) {
+        bail!(api_error(status, &json));
+    }
+    let id = json["id"]
+        .as_str()
+        .context("Confluence didn't return a page ID")?
+        .to_string();
+
+    let labels: Vec<Value> = settings
+        .labels
+        .iter()
+        .chain(&page.labels)
+        .map(|name| json!({"prefix": "global", "name": label(name)}))
+        .collect();
+    if !labels.is_empty() {
+        let (status, json) = api.send(
+            "POST",
+            &format!("content/{}/label", id),
+            Some(&Value::Array(labels)),
+        )?;
+        if !(200..300).contains(&status) {
+            bail!(api_error(status, &json));
+        }
+    }
+    Ok((id, version(&json), existing.is_none()))
+}
+
+fn api_error(status: u16, json: &Value) -> String {
+    match json["message"].as_str() {
+        Some(message) => format!("Confluence returned {}: {}", status, message),
+        None => format!("Confluence returned {}", status),
+    }
+}
+
+/// Percent-encode a query parameter value.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn output_text(results: &PublishResults, settings: &ConfluencePublish) {
+    let base_url = settings.base_url.trim_end_matches('/');
+    for doc in &results.documents {
+        let detail = match (&doc.reason, &doc.page_id) {
+            (Some(reason), _) => reason.clone(),
+            (None, Some(id)) => format!("{}/pages/viewpage.action?pageId={}", base_url, id),
+            (None, None) => String::new(),
+        };
+        let action = match doc.action {
+            PublishAction::Created if results.dry_run => "create",
+            PublishAction::Updated if results.dry_run => "update",
+            PublishAction::Created => "created",
+            PublishAction::Updated => "updated",
+            PublishAction::Unchanged => "unchanged",
+            PublishAction::Skipped => "skipped",
+            PublishAction::Failed => "failed",
+        };
+        println!("  {:<9} {}  {}", action, doc.path.display(), detail);
+    }
+    println!();
+    println!(
+        "{} {} created, {} updated, {} unchanged, {} skipped, {} failed in space {}",
+        if results.dry_run {
+            "Would publish:"
+        } else {
+            "Published:"
+        },
+        results.count(PublishAction::Created),
+        results.count(PublishAction::Updated),
+        results.count(PublishAction::Unchanged),
+        results.count(PublishAction::Skipped),
+        results.count(PublishAction::Failed),
+        results.space
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Confluence space held in memory.
+    #[derive(Default)]
+    struct FakeSpace {
+        /// Pages by ID: title and version.
+        pages: BTreeMap<String, (String, u64)>,
+        requests: Vec<String>,
+    }
+
+    impl ConfluenceApi for FakeSpace {
+        fn send(&mut self, method: &str, path: &str, body: Option<&Value>) -> Result<(u16, Value)> {
+            self.requests.push(format!("{} {}", method, path));
+            let page = |id: &str, (title, version): &(String, u64)| json!({"id": id, "title": title, "version": {"number": version}});
+            Ok(
+                match (method, path.split_once('?').map_or(path, |(p, _)| p)) {
+                    ("GET", "content") => {
+                        let results: Vec<Value> = self
+                            .pages
+                            .iter()
+                            .filter(|(_, (title, _))| {
+                                path.contains(&format!("title={}", percent_encode(title)))
+                            })
+                            .map(|(id, entry)| page(id, entry))
+                            .collect();
+                        (200, json!({ "results": results }))
+                    }
+                    ("POST", "content") => {
+                        let id = (100 + self.pages.len()).to_string();
+                        let title = body.unwrap()["title"].as_str().unwrap().to_string();
+                        self.pages.insert(id.clone(), (title, 1));
+                        (200, page(&id, &self.pages[&id]))
+                    }
+                    ("GET", content) => {
+                        let id = content.trim_start_matches("content/");
+                        match self.pages.get(id) {
+                            Some(entry) => (200, page(id, entry)),
+                            None => (404, json!({"message": "No content found"})),
+                        }
+                    }
+                    ("PUT", content) => {
+                        let id = content.trim_start_matches("content/").to_string();
+                        let version = body.unwrap()["version"]["number"].as_u64().unwrap();
+                        let entry = self.pages.get_mut(&id).unwrap();
+                        entry.1 = version;
+                        (200, page(&id, &entry.clone()))
+                    }
+                    ("POST", _) => (200, json!({"results": []})),
+                    _ => (400, json!({"message": "unexpected request"})),
+                },
+            )
+        }
+    }
+
+    fn settings() -> ConfluencePublish {
+        ConfluencePublish {
+            base_url: "https://wiki.example.com".to_string(),
+            space: "ENG".to_string(),
+            parent_id: Some("42".to_string()),
+            user_env: "CONFLUENCE_USER".to_string(),
+            token_env: "CONFLUENCE_TOKEN".to_string(),
+            labels: vec!["pave".to_string()],
+        }
+    }
+
+    fn page(path: &str, title: &str, body: &str) -> Page {
+        Page {
+            path: PathBuf::from(path),
+            title: title.to_string(),
+            labels: vec!["Team Auth".to_string()],
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn republishing_updates_pages_instead_of_duplicating() {
+        let mut space = FakeSpace::default();
+        space
+            .pages
+            .insert("7".to_string(), ("Deploy".to_string(), 3));
+        let mut state = PublishState::load(Path::new("/nonexistent"), "ENG");
+        let pages = vec![
+            page("docs/auth.md", "Auth", "<p>v1</p>"),
+            page("docs/deploy.md", "Deploy", "<p>steps</p>"),
+        ];
+
+        let actions = |docs: Vec<PublishedDoc>| -> Vec<PublishAction> {
+            docs.into_iter().map(|doc| doc.action).collect()
+        };
+        let first = publish(&pages, &settings(), &mut state, Some(&mut space), false);
+        assert_eq!(
+            actions(first),
+            [PublishAction::Created, PublishAction::Updated]
+        );
+        assert_eq!(space.pages["7"], ("Deploy".to_string(), 4));
+        assert_eq!(state.pages["docs/deploy.md"].id, "7");
+        assert!(
+            space
+                .requests
+                .contains(&"POST content/101/label".to_string())
+        );
+
+        // Unchanged pages send nothing; changed ones update by recorded ID
+        space.requests.clear();
+        let changed = vec![page("docs/auth.md", "Auth", "<p>v2</p>"), pages[1].clone()];
+        let second = publish(&changed, &settings(), &mut state, Some(&mut space), false);
+        assert_eq!(
+            actions(second),
+            [PublishAction::Updated, PublishAction::Unchanged]
+        );
+        assert_eq!(
+            space.requests,
+            [
+                "GET content/101?expand=version",
+                "PUT content/101",
+                "POST content/101/label"
+            ]
+        );
+        assert_eq!(space.pages.len(), 2);
+
+        // A dry run sends nothing
+        let dry_run = publish(&pages, &settings(), &mut state, None, true);
+        assert_eq!(
+            actions(dry_run),
+            [PublishAction::Updated, PublishAction::Updated]
+        );
+    }
+
+    #[test]
+    fn converts_markdown_to_storage_format() {
+        let cwd = Path::new("/repo");
+        let titles = BTreeMap::from([(
+            PathBuf::from("/repo/docs/runbooks/deploy.md"),
+            "Deploy & Roll".to_string(),
+        )]);
+        let markdown = "Intro with [deploy](runbooks/deploy.md#steps) and [site](https://x.io).\n\n\
+                        <!-- pave:expect -->\n```sh\necho ']]>'\n```\n";
+        let storage = storage_format(markdown, Path::new("/repo/docs/index.md"), &titles, cwd);
+        assert_eq!(
+            storage,
+            "<p>Intro with <ac:link><ri:page ri:content-title=\"Deploy &amp; Roll\" />\
+             <ac:link-body>deploy</ac:link-body></ac:link> and <a href=\"https://x.io\">site</a>.</p>\n\
+             <ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">bash\
+             </ac:parameter><ac:plain-text-body><![CDATA[echo ']]]]><![CDATA[>']]>\
+             </ac:plain-text-body></ac:structured-macro>\n"
+        );
+        assert_eq!(
+            page_markdown("---\npave:\n  tags: [a]\n---\n# Title\n\nBody\n"),
+            "\nBody"
+        );
+        assert_eq!(label("Team Auth"), "team-auth");
+        assert_eq!(curl_quote("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+}
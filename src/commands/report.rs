@@ -0,0 +1,374 @@
+//! Implementation of the `pave report` commands.
+//!
+//! `pave report diff` compares two JSON reports written by `pave check
+//! --format json` or `pave verify --report`, typically one from the main
+//! branch and one from a pull request. Only findings that are new in the
+//! second report count as regressions.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::exit::Violations;
+use crate::schema::SCHEMA_VERSION;
+
+/// Which command produced a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    Check,
+    Verify,
+}
+
+impl ReportKind {
+    fn name(self) -> &'static str {
+        match self {
+            ReportKind::Check => "check",
+            ReportKind::Verify => "verify",
+        }
+    }
+}
+
+/// A failing item in a report: a `pave check` error or a failed command.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Finding {
+    /// Document the finding belongs to.
+    pub file: String,
+    /// Line of the issue, or of the Verification section for commands.
+    pub line: usize,
+    /// Issue message, or the failing command.
+    pub message: String,
+}
+
+impl Finding {
+    /// Identity used to match findings across reports.
+    ///
+    /// Line numbers are left out so that edits above an issue don't make it
+    /// look like a fix plus a regression.
+    fn key(&self) -> (String, String) {
+        (self.file.clone(), self.message.clone())
+    }
+}
+
+/// Comparison of two reports.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReportDiff {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Command that produced both reports.
+    pub kind: ReportKind,
+    /// Findings present only in the new report.
+    pub regressions: Vec<Finding>,
+    /// Findings present only in the old report.
+    pub fixes: Vec<Finding>,
+    /// Findings present in both reports.
+    pub unchanged: Vec<Finding>,
+}
+
+/// Execute the `pave report diff` command.
+pub fn diff(old_path: &Path, new_path: &Path, format: OutputFormat) -> Result<()> {
+    let (old_kind, old) = load_report(old_path)?;
+    let (new_kind, new) = load_report(new_path)?;
+    if old_kind != new_kind {
+        anyhow::bail!(
+            "Cannot compare a {} report with a {} report",
+            old_kind.name(),
+            new_kind.name()
+        );
+    }
+
+    let result = diff_findings(new_kind, old, new);
+
+    match format {
+        OutputFormat::Text => output_text(&result),
+        OutputFormat::Json => output_json(&result)?,
+        OutputFormat::Github => output_github(&result),
+    }
+
+    let count = result.regressions.len();
+    if count > 0 {
+        anyhow::bail!(Violations(format!(
+            "Report diff found {} regression{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        )));
+    }
+
+    Ok(())
+}
+
+/// Read a report file and extract its failing findings.
+fn load_report(path: &Path) -> Result<(ReportKind, Vec<Finding>)> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report: {}", path.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse report: {}", path.display()))?;
+    parse_report(&value).with_context(|| format!("Unrecognized report: {}", path.display()))
+}
+
+/// Extract findings from a `pave check` or `pave verify` JSON report.
+///
+/// Check reports contribute their errors; verify reports contribute
+/// commands, including setup and teardown, that failed or timed out.
+/// Warnings are not compared.
+fn parse_report(value: &Value) -> Result<(ReportKind, Vec<Finding>)> {
+    if let Some(errors) = value.get("errors").and_then(Value::as_array) {
+        let findings = errors
+            .iter()
+            .map(|issue| Finding {
+                file: string_field(issue, "file"),
+                line: line_field(issue, "line"),
+                message: string_field(issue, "message"),
+            })
+            .collect();
+        return Ok((ReportKind::Check, findings));
+    }
+
+    if let Some(documents) = value.get("documents").and_then(Value::as_array) {
+        let mut findings = Vec::new();
+        for doc in documents {
+            let file = string_field(doc, "file");
+            let line = line_field(doc, "section_line");
+            let commands = ["setup", "commands", "teardown"]
+                .iter()
+                .filter_map(|key| doc.get(key).and_then(Value::as_array))
+                .flatten();
+            for command in commands {
+                let status = command.get("status").and_then(Value::as_str);
+                if matches!(status, Some("fail") | Some("timeout")) {
+                    findings.push(Finding {
+                        file: file.clone(),
+                        line,
+                        message: string_field(command, "command"),
+                    });
+                }
+            }
+        }
+        return Ok((ReportKind::Verify, findings));
+    }
+
+    anyhow::bail!("expected JSON output from `pave check` or `pave verify`")
+}
+
+fn string_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn line_field(value: &Value, key: &str) -> usize {
+    value.get(key).and_then(Value::as_u64).unwrap_or(0) as usize
+}
+
+/// Classify findings as regressions, fixes, or unchanged.
+///
+/// Findings are matched one-to-one, so a document that goes from one to two
+/// copies of the same error has one unchanged finding and one regression.
+fn diff_findings(kind: ReportKind, old: Vec<Finding>, new: Vec<Finding>) -> ReportDiff {
+    let mut remaining: BTreeMap<(String, String), VecDeque<Finding>> = BTreeMap::new();
+    for finding in old {
+        remaining
+            .entry(finding.key())
+            .or_default()
+            .push_back(finding);
+    }
+
+    let mut regressions = Vec::new();
+    let mut unchanged = Vec::new();
+    for finding in new {
+        let matched = remaining
+            .get_mut(&finding.key())
+            .and_then(|bucket| bucket.pop_front());
+        if matched.is_some() {
+            unchanged.push(finding);
+        } else {
+            regressions.push(finding);
+        }
+    }
+
+    let fixes = remaining.into_values().flatten().collect();
+
+    ReportDiff {
+        schema_version: SCHEMA_VERSION,
+        kind,
+        regressions,
+        fixes,
+        unchanged,
+    }
+}
+
+/// Output results in text format.
+fn output_text(result: &ReportDiff) {
+    if !result.regressions.is_empty() {
+        println!("Regressions ({}):", result.regressions.len());
+        for finding in &result.regressions {
+            println!("  + {}:{}: {}", finding.file, finding.line, finding.message);
+        }
+        println!();
+    }
+
+    if !result.fixes.is_empty() {
+        println!("Fixed ({}):", result.fixes.len());
+        for finding in &result.fixes {
+            println!("  - {}:{}: {}", finding.file, finding.line, finding.message);
+        }
+        println!();
+    }
+
+    let regressions = result.regressions.len();
+    println!(
+        "Compared {} reports: {} regression{}, {} fixed, {} unchanged",
+        result.kind.name(),
+        regressions,
+        if regressions == 1 { "" } else { "s" },
+        result.fixes.len(),
+        result.unchanged.len()
+    );
+}
+
+/// Output results in JSON format.
+fn output_json(result: &ReportDiff) -> Result<()> {
+    let json = serde_json::to_string_pretty(result).context("Failed to serialize results")?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Output regressions in GitHub Actions annotation format.
+fn output_github(result: &ReportDiff) {
+    for finding in &result.regressions {
+        println!(
+            "::error file={},line={}::New {} failure: {}",
+            finding.file,
+            finding.line,
+            result.kind.name(),
+            finding.message
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn check_report(errors: &[(&str, usize, &str)]) -> Value {
+        let errors: Vec<Value> = errors
+            .iter()
+            .map(|(file, line, message)| {
+                json!({"file": file, "line": line, "severity": "error", "message": message})
+            })
+            .collect();
+        json!({"files_checked": 2, "errors": errors, "warnings": []})
+    }
+
+    #[test]
+    fn check_reports_classify_regressions_and_fixes() {
+        let old = check_report(&[
+            ("docs/a.md", 3, "Missing required section: Verification"),
+            ("docs/b.md", 10, "Broken link: ./gone.md"),
+        ]);
+        // docs/a.md's issue moved down two lines but is the same issue
+        let new = check_report(&[
+            ("docs/a.md", 5, "Missing required section: Verification"),
+            ("docs/c.md", 1, "Missing required section: Purpose"),
+        ]);
+
+        let (kind, old) = parse_report(&old).unwrap();
+        let (_, new) = parse_report(&new).unwrap();
+        let result = diff_findings(kind, old, new);
+
+        assert_eq!(result.kind, ReportKind::Check);
+        assert_eq!(result.regressions.len(), 1);
+        assert_eq!(result.regressions[0].file, "docs/c.md");
+        assert_eq!(result.fixes.len(), 1);
+        assert_eq!(result.fixes[0].file, "docs/b.md");
+        assert_eq!(result.unchanged.len(), 1);
+        assert_eq!(result.unchanged[0].line, 5);
+    }
+
+    #[test]
+    fn repeated_findings_are_matched_one_to_one() {
+        let old = check_report(&[("docs/a.md", 3, "Line too long")]);
+        let new = check_report(&[
+            ("docs/a.md", 3, "Line too long"),
+            ("docs/a.md", 9, "Line too long"),
+        ]);
+
+        let (kind, old) = parse_report(&old).unwrap();
+        let (_, new) = parse_report(&new).unwrap();
+        let result = diff_findings(kind, old, new);
+
+        assert_eq!(result.unchanged.len(), 1);
+        assert_eq!(result.regressions.len(), 1);
+        assert_eq!(result.regressions[0].line, 9);
+    }
+
+    #[test]
+    fn verify_reports_compare_failing_commands() {
+        let report = |statuses: &[(&str, &str)]| {
+            let commands: Vec<Value> = statuses
+                .iter()
+                .map(|(command, status)| json!({"command": command, "status": status}))
+                .collect();
+            json!({
+                "documents_verified": 1,
+                "documents": [{"file": "docs/a.md", "section_line": 12, "commands": commands}]
+            })
+        };
+        let old = report(&[("cargo test", "fail"), ("make lint", "pass")]);
+        let new = report(&[("cargo test", "timeout"), ("make lint", "fail")]);
+
+        let (kind, old) = parse_report(&old).unwrap();
+        let (_, new) = parse_report(&new).unwrap();
+        let result = diff_findings(kind, old, new);
+
+        assert_eq!(result.kind, ReportKind::Verify);
+        assert_eq!(result.unchanged[0].message, "cargo test");
+        assert_eq!(result.regressions[0].message, "make lint");
+        assert_eq!(result.regressions[0].line, 12);
+        assert!(result.fixes.is_empty());
+    }
+
+    #[test]
+    fn diff_fails_only_on_regressions() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = temp_dir.path().join("old.json");
+        let new = temp_dir.path().join("new.json");
+        let broken = check_report(&[("docs/a.md", 1, "Broken link: x.md")]);
+        fs::write(&old, broken.to_string()).unwrap();
+        fs::write(&new, check_report(&[]).to_string()).unwrap();
+
+        assert!(diff(&old, &new, OutputFormat::Json).is_ok());
+
+        let err = diff(&new, &old, OutputFormat::Json).unwrap_err();
+        assert!(err.to_string().contains("1 regression"));
+    }
+
+    #[test]
+    fn diff_rejects_mismatched_and_unknown_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        let check = temp_dir.path().join("check.json");
+        let verify = temp_dir.path().join("verify.json");
+        let other = temp_dir.path().join("other.json");
+        fs::write(&check, check_report(&[]).to_string()).unwrap();
+        fs::write(&verify, json!({"documents": []}).to_string()).unwrap();
+        fs::write(&other, json!({"hello": "world"}).to_string()).unwrap();
+
+        let err = diff(&check, &verify, OutputFormat::Text).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("check report with a verify report")
+        );
+
+        let err = diff(&check, &other, OutputFormat::Text).unwrap_err();
+        assert!(err.to_string().contains("Unrecognized report"));
+    }
+}
@@ -0,0 +1,499 @@
+//! Implementation of the `pave report html` command.
+//!
+//! Renders a static HTML site that CI can publish as a browsable artifact:
+//! an index of every document with its check status, the last `pave verify`
+//! result, and coverage stats, plus a page per document with its rendered
+//! markdown and any issues.
+
+use anyhow::{Context, Result};
+use pulldown_cmark::{Options, Parser, html};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::check::{CheckResults, Issue, check_file, should_skip_file};
+use crate::commands::coverage::{self, CoverageSnapshot};
+use crate::commands::verify::ARTIFACT_REPORT_PATH;
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::paths::relative_path;
+use crate::walk::{collect_markdown_files, load_ignore};
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave report html` command.
+#[derive(Debug)]
+pub struct HtmlReportArgs {
+    /// Directory to write the site to.
+    pub output: PathBuf,
+    /// JSON report from `pave verify --report` to show results from.
+    pub verify_report: Option<PathBuf>,
+}
+
+/// Everything the report shows about one document.
+#[derive(Debug)]
+struct DocReport {
+    /// Path relative to the config directory.
+    path: PathBuf,
+    /// H1 title, or the file name when there is none.
+    title: String,
+    /// Errors from `pave check`.
+    errors: Vec<Issue>,
+    /// Warnings from `pave check`.
+    warnings: Vec<Issue>,
+    /// Status from the verify report (`pass`, `warn`, `fail`, ...), if the
+    /// document was verified.
+    verify: Option<String>,
+    /// Rendered markdown body.
+    body_html: String,
+}
+
+impl DocReport {
+    fn check_status(&self) -> &'static str {
+        if !self.errors.is_empty() {
+            "fail"
+        } else if !self.warnings.is_empty() {
+            "warn"
+        } else {
+            "pass"
+        }
+    }
+
+    /// Page path relative to the site root, e.g. `docs/components/auth.html`.
+    fn page(&self) -> String {
+        self.path
+            .with_extension("html")
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Execute the `pave report html` command.
+pub fn html(args: HtmlReportArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let docs_root = config_dir.join(&config.docs.root);
+
+    // The report written by `pave verify` for artifact_cmd is used if present
+    let verify_statuses = match &args.verify_report {
+        Some(path) => Some(load_verify_statuses(&workdir.resolve(path), config_dir)?),
+        None => {
+            let default = config_dir.join(ARTIFACT_REPORT_PATH);
+            if default.is_file() {
+                Some(load_verify_statuses(&default, config_dir)?)
+            } else {
+                None
+            }
+        }
+    };
+
+    let ignore = load_ignore(config_dir, &config)?;
+    let mut files = Vec::new();
+    if docs_root.is_dir() {
+        collect_markdown_files(&docs_root, &ignore, &mut files)?;
+    }
+    files.sort();
+
+    let mut docs = Vec::new();
+    for file in files.iter().filter(|f| !should_skip_file(f)) {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let mut results = CheckResults::new();
+        check_file(file, &config, &mut results)?;
+
+        let path = file.strip_prefix(config_dir).unwrap_or(file).to_path_buf();
+        let title = ParsedDoc::parse_content(file.clone(), &content)?
+            .title
+            .unwrap_or_else(|| path.display().to_string());
+        let verify = verify_statuses
+            .as_ref()
+            .and_then(|statuses| statuses.get(&path).cloned());
+        docs.push(DocReport {
+            title,
+            errors: results.errors,
+            warnings: results.warnings,
+            verify,
+            body_html: render_markdown(&content),
+            path,
+        });
+    }
+
+    let coverage = coverage::take_snapshot(&config, config_dir)?;
+    let output = workdir.resolve(&args.output);
+    write_site(&output, &docs, &coverage, verify_statuses.is_some())?;
+
+    println!(
+        "Wrote HTML report for {} document{} to {}",
+        docs.len(),
+        if docs.len() == 1 { "" } else { "s" },
+        args.output.join("index.html").display()
+    );
+    Ok(())
+}
+
+/// Read per-document statuses from a `pave verify --report` file, keyed by
+/// path relative to the config directory.
+pub(crate) fn load_verify_statuses(
+    path: &Path,
+    config_dir: &Path,
+) -> Result<HashMap<PathBuf, String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read verify report: {}", path.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse verify report: {}", path.display()))?;
+    let documents = value
+        .get("documents")
+        .and_then(Value::as_array)
+        .with_context(|| format!("Not a `pave verify` report: {}", path.display()))?;
+
+    let mut statuses = HashMap::new();
+    for doc in documents {
+        let (Some(file), Some(status)) = (
+            doc.get("file").and_then(Value::as_str),
+            doc.get("status").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let file = Path::new(file);
+        let file = file.strip_prefix(config_dir).unwrap_or(file).to_path_buf();
+        statuses.insert(file, status.to_string());
+    }
+    Ok(statuses)
+}
+
+/// Write `index.html` and a page per document under `output`.
+fn write_site(
+    output: &Path,
+    docs: &[DocReport],
+    coverage: &CoverageSnapshot,
+    has_verify: bool,
+) -> Result<()> {
+    fs::create_dir_all(output)
+        .with_context(|| format!("Failed to create directory: {}", output.display()))?;
+    fs::write(
+        output.join("index.html"),
+        render_index(docs, coverage, has_verify),
+    )
+    .with_context(|| format!("Failed to write {}", output.join("index.html").display()))?;
+
+    for doc in docs {
+        let page = output.join(doc.page());
+        if let Some(parent) = page.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let root = relative_path(output, page.parent().unwrap_or(output));
+        fs::write(&page, render_doc_page(doc, &root))
+            .with_context(|| format!("Failed to write {}", page.display()))?;
+    }
+    Ok(())
+}
+
+/// Render the index page: summary stats and a table of documents.
+fn render_index(docs: &[DocReport], coverage: &CoverageSnapshot, has_verify: bool) -> String {
+    let count = |status: &str| docs.iter().filter(|d| d.check_status() == status).count();
+    let mut body = String::new();
+
+    body.push_str("<h1>Documentation report</h1>\n<div class=\"stats\">\n");
+    body.push_str(&stat("Documents", &docs.len().to_string()));
+    body.push_str(&stat(
+        "Check",
+        &format!(
+            "{} pass, {} warn, {} fail",
+            count("pass"),
+            count("warn"),
+            count("fail")
+        ),
+    ));
+    if has_verify {
+        let verified = |status: &str| {
+            docs.iter()
+                .filter(|d| d.verify.as_deref() == Some(status))
+                .count()
+        };
+        body.push_str(&stat(
+            "Verify",
+            &format!(
+                "{} pass, {} warn, {} fail",
+                verified("pass"),
+                verified("warn"),
+                docs.iter()
+                    .filter(|d| d.verify.as_deref().is_some_and(is_failed_status))
+                    .count()
+            ),
+        ));
+    }
+    body.push_str(&stat(
+        "Coverage",
+        &format!(
+            "{:.1}% ({} of {} files)",
+            coverage.coverage_percentage,
+            coverage.total_files - coverage.uncovered_files,
+            coverage.total_files
+        ),
+    ));
+    body.push_str("</div>\n");
+
+    body.push_str("<table>\n<tr><th>Document</th><th>Path</th><th>Check</th>");
+    if has_verify {
+        body.push_str("<th>Verify</th>");
+    }
+    body.push_str("</tr>\n");
+    for doc in docs {
+        body.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td><code>{}</code></td><td>{}</td>",
+            escape_html(&doc.page()),
+            escape_html(&doc.title),
+            escape_html(&doc.path.to_string_lossy()),
+            check_badge(doc)
+        ));
+        if has_verify {
+            body.push_str(&format!(
+                "<td>{}</td>",
+                badge(doc.verify.as_deref().unwrap_or("none"))
+            ));
+        }
+        body.push_str("</tr>\n");
+    }
+    body.push_str("</table>\n");
+
+    page("Documentation report", &body)
+}
+
+/// Render a document page: status badges, issues, and the document itself.
+fn render_doc_page(doc: &DocReport, root: &Path) -> String {
+    let mut body = String::new();
+    body.push_str(&format!(
+        "<p><a href=\"{}\">&larr; All documents</a></p>\n",
+        escape_html(&root.join("index.html").to_string_lossy())
+    ));
+    body.push_str(&format!(
+        "<p><code>{}</code> check {}",
+        escape_html(&doc.path.to_string_lossy()),
+        check_badge(doc)
+    ));
+    if let Some(verify) = &doc.verify {
+        body.push_str(&format!(" verify {}", badge(verify)));
+    }
+    body.push_str("</p>\n");
+
+    if !doc.errors.is_empty() || !doc.warnings.is_empty() {
+        body.push_str("<ul class=\"issues\">\n");
+        let issues = doc
+            .errors
+            .iter()
+            .map(|issue| ("fail", issue))
+            .chain(doc.warnings.iter().map(|issue| ("warn", issue)));
+        for (status, issue) in issues {
+            body.push_str(&format!(
+                "<li class=\"{}\">line {}: {}</li>\n",
+                status,
+                issue.line,
+                escape_html(&issue.message)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    body.push_str("<article>\n");
+    body.push_str(&doc.body_html);
+    body.push_str("</article>\n");
+
+    page(&doc.title, &body)
+}
+
+/// Render markdown to HTML, leaving out frontmatter and pointing `.md`
+/// links at the generated pages.
+fn render_markdown(content: &str) -> String {
+    let markdown = strip_frontmatter(content)
+        .replace(".md)", ".html)")
+        .replace(".md#", ".html#");
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new_ext(&markdown, options));
+    out
+}
+
+/// Remove a leading `---` YAML frontmatter block.
+pub(crate) fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return content;
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            after.strip_prefix('\n').unwrap_or(after)
+        }
+        None => content,
+    }
+}
+
+/// Whether a verify status means the document failed.
+fn is_failed_status(status: &str) -> bool {
+    matches!(
+        status,
+        "fail" | "timeout" | "setup_failed" | "teardown_failed"
+    )
+}
+
+fn check_badge(doc: &DocReport) -> String {
+    let status = doc.check_status();
+    let label = match status {
+        "fail" => format!("{} error(s)", doc.errors.len()),
+        "warn" => format!("{} warning(s)", doc.warnings.len()),
+        _ => "pass".to_string(),
+    };
+    format!("<span class=\"badge {}\">{}</span>", status, label)
+}
+
+fn badge(status: &str) -> String {
+    let class = if is_failed_status(status) {
+        "fail"
+    } else {
+        status
+    };
+    format!(
+        "<span class=\"badge {}\">{}</span>",
+        escape_html(class),
+        escape_html(status)
+    )
+}
+
+pub(crate) fn stat(label: &str, value: &str) -> String {
+    format!(
+        "<div class=\"stat\"><div class=\"label\">{}</div><div class=\"value\">{}</div></div>\n",
+        escape_html(label),
+        escape_html(value)
+    )
+}
+
+/// Wrap a body in a standalone HTML page with inline styles.
+pub(crate) fn page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid #ddd; }}
+pre {{ background: #f6f8fa; padding: 0.8rem; overflow-x: auto; }}
+.stats {{ display: flex; gap: 1rem; margin-bottom: 1.5rem; }}
+.stat {{ border: 1px solid #ddd; border-radius: 6px; padding: 0.6rem 1rem; }}
+.stat .label {{ font-size: 0.8rem; color: #666; }}
+.badge {{ border-radius: 4px; padding: 0.1rem 0.5rem; font-size: 0.85rem; color: #fff; background: #888; }}
+.badge.pass {{ background: #2da44e; }}
+.badge.warn {{ background: #bf8700; }}
+.badge.fail {{ background: #cf222e; }}
+.issues li.fail {{ color: #cf222e; }}
+.issues li.warn {{ color: #9a6700; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+        body = body
+    )
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn doc(path: &str, verify: Option<&str>) -> DocReport {
+        DocReport {
+            path: PathBuf::from(path),
+            title: "Auth <Service>".to_string(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            verify: verify.map(str::to_string),
+            body_html: render_markdown(
+                "---\npave:\n  paths: [src/]\n---\n# Auth\n\nSee [api](./api.md).\n",
+            ),
+        }
+    }
+
+    fn snapshot() -> CoverageSnapshot {
+        CoverageSnapshot {
+            timestamp: String::new(),
+            coverage_percentage: 75.0,
+            docs_count: 2,
+            total_files: 4,
+            uncovered_files: 1,
+        }
+    }
+
+    #[test]
+    fn markdown_is_rendered_without_frontmatter() {
+        let html = doc("docs/auth.md", None).body_html;
+
+        assert!(html.contains("<h1>Auth</h1>"));
+        assert!(html.contains("href=\"./api.html\""));
+        assert!(!html.contains("paths"));
+    }
+
+    #[test]
+    fn verify_statuses_are_keyed_by_relative_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let report = temp_dir.path().join("verify.json");
+        let absolute = temp_dir.path().join("docs/b.md");
+        let value = json!({"documents": [
+            {"file": "docs/a.md", "status": "pass"},
+            {"file": absolute, "status": "setup_failed"},
+        ]});
+        fs::write(&report, value.to_string()).unwrap();
+
+        let statuses = load_verify_statuses(&report, temp_dir.path()).unwrap();
+
+        assert_eq!(statuses[Path::new("docs/a.md")], "pass");
+        assert_eq!(statuses[Path::new("docs/b.md")], "setup_failed");
+    }
+
+    #[test]
+    fn site_has_index_and_doc_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut failing = doc("docs/components/auth.md", Some("fail"));
+        failing.errors.push(Issue {
+            file: PathBuf::from("docs/components/auth.md"),
+            line: 1,
+            severity: crate::commands::check::Severity::Error,
+            message: "Missing required section 'Examples'".to_string(),
+            hint: None,
+            converted_from_error: false,
+            rule: "require-section-examples".to_string(),
+            docs_url: None,
+            blame: None,
+        });
+        let docs = vec![failing, doc("docs/index2.md", None)];
+
+        write_site(temp_dir.path(), &docs, &snapshot(), true).unwrap();
+
+        let index = fs::read_to_string(temp_dir.path().join("index.html")).unwrap();
+        assert!(index.contains("Auth &lt;Service&gt;"));
+        assert!(index.contains("href=\"docs/components/auth.html\""));
+        assert!(index.contains("1 pass, 0 warn, 1 fail"));
+        assert!(index.contains("75.0% (3 of 4 files)"));
+        assert!(index.contains("<span class=\"badge none\">none</span>"));
+
+        let page = fs::read_to_string(temp_dir.path().join("docs/components/auth.html")).unwrap();
+        assert!(page.contains("href=\"../../index.html\""));
+        assert!(page.contains("line 1: Missing required section 'Examples'"));
+        assert!(page.contains("<span class=\"badge fail\">fail</span>"));
+    }
+}
@@ -0,0 +1,356 @@
+//! Implementation of the `pave review` command.
+//!
+//! Lists documents due for review, from their `pave.last_reviewed` date plus
+//! `pave.review_interval_days` (or `rules.review_interval_days`), grouped by
+//! owner. Documents without a `pave.owner` are grouped under their
+//! CODEOWNERS owners, if the project has a CODEOWNERS file. The markdown
+//! output is a checklist per owner that can be pasted into an issue, and the
+//! JSON output carries the same groups for scripts that open issues.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{PathStyle, ReviewOutputFormat};
+use crate::codeowners::CodeOwners;
+use crate::config::PaveConfig;
+use crate::parser::{DocState, ParsedDoc};
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave review` command.
+pub struct ReviewArgs {
+    /// Output format.
+    pub format: ReviewOutputFormat,
+    /// Also list docs that come due within this many days.
+    pub within_days: u32,
+    /// Only list docs owned by this owner.
+    pub owner: Option<String>,
+}
+
+/// A document due for review.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct ReviewDoc {
+    /// Path to the document.
+    pub file: PathBuf,
+    /// H1 title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Date of the last review (YYYY-MM-DD).
+    pub last_reviewed: String,
+    /// Date the next review is due (YYYY-MM-DD).
+    pub review_due: String,
+    /// Days since the review was due; negative if it's not due yet.
+    pub days_overdue: i64,
+}
+
+/// Documents due for review that share an owner.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct ReviewGroup {
+    /// Owner from `pave.owner` or CODEOWNERS; `None` for unowned docs.
+    pub owner: Option<String>,
+    /// Documents, earliest due first.
+    pub docs: Vec<ReviewDoc>,
+}
+
+/// Results of the review command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReviewResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Date reviews were checked against (YYYY-MM-DD).
+    pub as_of: String,
+    /// Days ahead that upcoming reviews were included for.
+    pub within_days: u32,
+    /// Number of documents due for review.
+    pub total_due: usize,
+    /// Documents grouped by owner, sorted by owner with unowned docs last.
+    pub groups: Vec<ReviewGroup>,
+}
+
+/// Execute the `pave review` command.
+pub fn execute(args: ReviewArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let today = chrono::Local::now().date_naive();
+
+    let mut results = find_due_docs(&config, config_dir, today, args.within_days)?;
+    if let Some(owner) = &args.owner {
+        let owner = owner.trim_start_matches('@');
+        results.groups.retain(|group| {
+            group
+                .owner
+                .as_deref()
+                .is_some_and(|o| o.split(", ").any(|o| o.trim_start_matches('@') == owner))
+        });
+        results.total_due = results.groups.iter().map(|g| g.docs.len()).sum();
+    }
+
+    match args.format {
+        ReviewOutputFormat::Text => output_text(&results),
+        ReviewOutputFormat::Markdown => print!("{}", render_markdown(&results)),
+        ReviewOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Find documents whose review is due by `today` plus `within_days`.
+///
+/// Deprecated documents are left out, since they're no longer maintained.
+pub fn find_due_docs(
+    config: &PaveConfig,
+    config_dir: &Path,
+    today: NaiveDate,
+    within_days: u32,
+) -> Result<ReviewResults> {
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, config)?;
+    let codeowners = match CodeOwners::find(config_dir) {
+        Some(path) => CodeOwners::load(&path)?,
+        None => CodeOwners::default(),
+    };
+    let horizon = today + chrono::Duration::days(i64::from(within_days));
+
+    let mut groups: Vec<ReviewGroup> = Vec::new();
+    for path in find_docs(&docs_root, &ignore)? {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let doc = ParsedDoc::parse_content(path.clone(), &content)?;
+        if doc.state() == DocState::Deprecated {
+            continue;
+        }
+        let Some(due) = doc.review_due(config.rules.review_interval_days) else {
+            continue;
+        };
+        if due > horizon {
+            continue;
+        }
+
+        let file = display_path(&path, PathStyle::Workspace, config_dir, config_dir);
+        let frontmatter = doc.frontmatter.clone().unwrap_or_default();
+        let owner = frontmatter
+            .owner
+            .map(|owner| owner.trim().to_string())
+            .filter(|owner| !owner.is_empty())
+            .or_else(|| {
+                let owners = codeowners.owners_for(&file);
+                (!owners.is_empty()).then(|| owners.join(", "))
+            });
+
+        let review_doc = ReviewDoc {
+            file,
+            title: doc.title.clone(),
+            last_reviewed: frontmatter
+                .last_reviewed
+                .map(|date| date.trim().to_string())
+                .unwrap_or_default(),
+            review_due: due.to_string(),
+            days_overdue: (today - due).num_days(),
+        };
+        match groups.iter_mut().find(|group| group.owner == owner) {
+            Some(group) => group.docs.push(review_doc),
+            None => groups.push(ReviewGroup {
+                owner,
+                docs: vec![review_doc],
+            }),
+        }
+    }
+
+    // Owners alphabetically, unowned docs last
+    groups.sort_by(|a, b| match (&a.owner, &b.owner) {
+        (Some(a), Some(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    });
+    for group in &mut groups {
+        group.docs.sort_by(|a, b| {
+            a.review_due
+                .cmp(&b.review_due)
+                .then_with(|| a.file.cmp(&b.file))
+        });
+    }
+
+    Ok(ReviewResults {
+        schema_version: SCHEMA_VERSION,
+        as_of: today.to_string(),
+        within_days,
+        total_due: groups.iter().map(|g| g.docs.len()).sum(),
+        groups,
+    })
+}
+
+/// How far past (or before) its due date a review is.
+fn describe_due(doc: &ReviewDoc) -> String {
+    match doc.days_overdue {
+        0 => "due today".to_string(),
+        days if days > 0 => format!("{} day{} overdue", days, if days == 1 { "" } else { "s" }),
+        days => format!("due in {} day{}", -days, if days == -1 { "" } else { "s" }),
+    }
+}
+
+fn group_name(group: &ReviewGroup) -> &str {
+    group.owner.as_deref().unwrap_or("Unowned")
+}
+
+fn output_text(results: &ReviewResults) {
+    if results.total_due == 0 {
+        println!("No docs due for review as of {}", results.as_of);
+        return;
+    }
+
+    println!(
+        "{} doc{} due for review as of {}:",
+        results.total_due,
+        if results.total_due == 1 { "" } else { "s" },
+        results.as_of
+    );
+    for group in &results.groups {
+        println!();
+        println!("{} ({})", group_name(group), group.docs.len());
+        for doc in &group.docs {
+            println!(
+                "  {}: {} (last reviewed {})",
+                doc.file.display(),
+                describe_due(doc),
+                doc.last_reviewed
+            );
+        }
+    }
+}
+
+/// Render a GitHub-flavored markdown checklist, one section per owner.
+fn render_markdown(results: &ReviewResults) -> String {
+    let mut out = format!("## Docs due for review ({})\n", results.as_of);
+    if results.total_due == 0 {
+        out.push_str("\nNo docs are due for review.\n");
+        return out;
+    }
+
+    for group in &results.groups {
+        out.push_str(&format!("\n### {}\n\n", group_name(group)));
+        for doc in &group.docs {
+            let file = doc.file.display().to_string();
+            let title = doc.title.as_deref().unwrap_or(&file);
+            out.push_str(&format!(
+                "- [ ] [{}]({}): {}, last reviewed {}\n",
+                title,
+                file,
+                describe_due(doc),
+                doc.last_reviewed
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_doc(root: &Path, name: &str, frontmatter: &str) {
+        let path = root.join("docs").join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            path,
+            format!("---\npave:\n{}---\n# {}\n", frontmatter, name),
+        )
+        .unwrap();
+    }
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_doc(
+            root,
+            "auth.md",
+            "  owner: '@alice'\n  last_reviewed: 2025-01-01\n  review_interval_days: 90\n",
+        );
+        write_doc(
+            root,
+            "api.md",
+            "  owner: '@alice'\n  last_reviewed: 2025-03-01\n  review_interval_days: 95\n",
+        );
+        write_doc(root, "cli.md", "  last_reviewed: 2025-01-01\n");
+        write_doc(
+            root,
+            "team/ops.md",
+            "  last_reviewed: 2024-12-01\n  review_interval_days: 30\n",
+        );
+        write_doc(
+            root,
+            "old.md",
+            "  state: deprecated\n  superseded_by: ./auth.md\n  last_reviewed: 2020-01-01\n  review_interval_days: 30\n",
+        );
+        write_doc(root, "misc.md", "  last_reviewed: 2025-05-01\n");
+        fs::create_dir_all(root.join(".github")).unwrap();
+        fs::write(root.join(".github/CODEOWNERS"), "/docs/team/ @ops-team\n").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn groups_due_docs_by_owner() {
+        let temp_dir = setup();
+        let mut config = PaveConfig::default();
+        config.rules.review_interval_days = Some(180);
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+
+        let results = find_due_docs(&config, temp_dir.path(), today, 0).unwrap();
+        assert_eq!(results.total_due, 2);
+        let summary: Vec<(Option<&str>, Vec<String>)> = results
+            .groups
+            .iter()
+            .map(|g| {
+                (
+                    g.owner.as_deref(),
+                    g.docs
+                        .iter()
+                        .map(|d| d.file.display().to_string())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("@alice"), vec!["docs/auth.md".to_string()]),
+                (Some("@ops-team"), vec!["docs/team/ops.md".to_string()]),
+            ]
+        );
+        assert_eq!(results.groups[0].docs[0].days_overdue, 61);
+
+        // Upcoming reviews, and the configured interval for docs without one
+        let results = find_due_docs(&config, temp_dir.path(), today, 30).unwrap();
+        assert_eq!(results.total_due, 4);
+        assert_eq!(results.groups[0].docs[1].file, PathBuf::from("docs/api.md"));
+        assert_eq!(results.groups[0].docs[1].days_overdue, -3);
+        assert_eq!(results.groups.last().unwrap().owner, None);
+        assert_eq!(
+            results.groups.last().unwrap().docs[0].file,
+            PathBuf::from("docs/cli.md")
+        );
+    }
+
+    #[test]
+    fn markdown_is_a_checklist_per_owner() {
+        let temp_dir = setup();
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let results = find_due_docs(&PaveConfig::default(), temp_dir.path(), today, 0).unwrap();
+
+        let markdown = render_markdown(&results);
+        assert!(markdown.starts_with("## Docs due for review (2025-06-01)\n"));
+        assert!(markdown.contains(
+            "### @alice\n\n- [ ] [auth.md](docs/auth.md): 61 days overdue, last reviewed 2025-01-01\n"
+        ));
+        assert!(markdown.contains("### @ops-team\n"));
+    }
+}
@@ -0,0 +1,431 @@
+//! Implementation of the `pave search` command.
+//!
+//! Searches document titles, tags, covered paths, and section content for
+//! every term of a query, ignoring case. Documents match only if each term
+//! appears somewhere in them, and are ranked by where the terms appear:
+//! title matches count most, then tags and paths, then section headings, then
+//! lines of content. `--type`, `--tag`, and `--section` narrow the search.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{DocType as DocTypeArg, PathStyle, SearchOutputFormat};
+use crate::commands::coverage::extract_paths_patterns;
+use crate::config::PaveConfig;
+use crate::parser::{CodeBlockTracker, ParsedDoc, frontmatter_len};
+use crate::paths::display_path;
+use crate::rules::{DocType, detect_doc_type};
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
+
+/// Most content lines reported per document.
+const MAX_LINE_MATCHES: usize = 5;
+
+/// Score of a term found in the title.
+const TITLE_SCORE: usize = 10;
+/// Score of a term found in a tag or covered path.
+const METADATA_SCORE: usize = 5;
+/// Score of a term found in a section heading.
+const HEADING_SCORE: usize = 3;
+
+/// Arguments for the `pave search` command.
+pub struct SearchArgs {
+    /// Words to search for.
+    pub query: String,
+    /// Only search documents of this type.
+    pub doc_type: Option<DocTypeArg>,
+    /// Only search content in this section.
+    pub section: Option<String>,
+    /// Only search documents with this tag.
+    pub tag: Option<String>,
+    /// Output format.
+    pub format: SearchOutputFormat,
+    /// How to display file paths.
+    pub path_style: PathStyle,
+}
+
+/// Filters that narrow a search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// Only documents of this type.
+    pub doc_type: Option<DocType>,
+    /// Only content in this section (case-insensitive).
+    pub section: Option<String>,
+    /// Only documents with this tag (case-insensitive).
+    pub tag: Option<String>,
+}
+
+/// A line of content containing a search term.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct LineMatch {
+    /// Line number (1-indexed).
+    pub line: usize,
+    /// H2 section the line is in, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// The line, trimmed.
+    pub text: String,
+}
+
+/// A document matching a search.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SearchResult {
+    /// Path to the document.
+    pub file: PathBuf,
+    /// H1 title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Detected document type.
+    pub doc_type: DocType,
+    /// Relevance; higher is better.
+    pub score: usize,
+    /// Tags containing a search term.
+    pub tags: Vec<String>,
+    /// Covered paths containing a search term.
+    pub paths: Vec<String>,
+    /// Content lines containing a search term, first few only.
+    pub lines: Vec<LineMatch>,
+}
+
+/// Results of the search command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// The query as given.
+    pub query: String,
+    /// Matching documents, most relevant first.
+    pub results: Vec<SearchResult>,
+}
+
+/// Execute the `pave search` command.
+pub fn execute(args: SearchArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let filters = SearchFilters {
+        doc_type: args.doc_type.map(|doc_type| match doc_type {
+            DocTypeArg::Component => DocType::Component,
+            DocTypeArg::Runbook => DocType::Runbook,
+            DocTypeArg::Adr => DocType::Adr,
+            DocTypeArg::Postmortem => DocType::Postmortem,
+            DocTypeArg::Api => DocType::Api,
+        }),
+        section: args.section,
+        tag: args.tag,
+    };
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, &config)?;
+    let mut results = search(&find_docs(&docs_root, &ignore)?, &args.query, &filters)?;
+    for result in &mut results {
+        result.file = display_path(&result.file, args.path_style, config_dir, &workdir.dir);
+    }
+
+    let results = SearchResults {
+        schema_version: SCHEMA_VERSION,
+        query: args.query,
+        results,
+    };
+    match args.format {
+        SearchOutputFormat::Text => output_text(&results),
+        SearchOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Search `files` for documents containing every term of `query`.
+///
+/// An empty query matches every document that passes the filters.
+pub fn search(
+    files: &[PathBuf],
+    query: &str,
+    filters: &SearchFilters,
+) -> Result<Vec<SearchResult>> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let section_filter = filters.section.as_deref().map(str::to_lowercase);
+
+    let mut results = Vec::new();
+    for path in files {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let doc = ParsedDoc::parse_content(path.clone(), &content)?;
+        let doc_type = detect_doc_type(path, &content);
+        let frontmatter = doc.frontmatter.clone().unwrap_or_default();
+
+        if filters.doc_type.is_some_and(|wanted| wanted != doc_type) {
+            continue;
+        }
+        if let Some(tag) = &filters.tag
+            && !frontmatter.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        {
+            continue;
+        }
+        if let Some(section) = &filters.section
+            && !doc
+                .sections
+                .iter()
+                .any(|s| s.name.eq_ignore_ascii_case(section))
+        {
+            continue;
+        }
+
+        let mut paths = extract_paths_patterns(&content);
+        paths.extend(frontmatter.paths);
+        let title = doc.title.clone().unwrap_or_default().to_lowercase();
+
+        // Where each term was found, scored by the best place
+        let mut found = vec![false; terms.len()];
+        let mut score = 0;
+        let mut mark = |text: &str, points: usize| {
+            let text = text.to_lowercase();
+            let mut hit = false;
+            for (term, found) in terms.iter().zip(found.iter_mut()) {
+                if text.contains(term.as_str()) {
+                    *found = true;
+                    hit = true;
+                    score += points;
+                }
+            }
+            hit
+        };
+
+        // With --section, only that section's content is searched
+        if section_filter.is_none() {
+            mark(&title, TITLE_SCORE);
+        }
+        let tags: Vec<String> = frontmatter
+            .tags
+            .iter()
+            .filter(|tag| section_filter.is_none() && mark(tag, METADATA_SCORE))
+            .cloned()
+            .collect();
+        let paths: Vec<String> = paths
+            .into_iter()
+            .filter(|path| section_filter.is_none() && mark(path, METADATA_SCORE))
+            .collect();
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut line_matches = Vec::new();
+        let mut section: Option<&str> = None;
+        let mut tracker = CodeBlockTracker::new();
+        for (idx, line) in lines.iter().enumerate().skip(frontmatter_len(&lines)) {
+            tracker.process_line(line);
+            let trimmed = line.trim();
+            if !tracker.in_code_block()
+                && let Some(heading) = trimmed.strip_prefix("## ")
+            {
+                section = Some(heading.trim());
+                if section_matches(section, section_filter.as_deref()) {
+                    mark(heading, HEADING_SCORE);
+                }
+                continue;
+            }
+            // The title was already searched
+            if trimmed.is_empty()
+                || (!tracker.in_code_block() && trimmed.starts_with("# "))
+                || !section_matches(section, section_filter.as_deref())
+            {
+                continue;
+            }
+            if mark(trimmed, 1) && line_matches.len() < MAX_LINE_MATCHES {
+                line_matches.push(LineMatch {
+                    line: idx + 1,
+                    section: section.map(str::to_string),
+                    text: trimmed.to_string(),
+                });
+            }
+        }
+
+        if !found.iter().all(|&found| found) {
+            continue;
+        }
+        results.push(SearchResult {
+            file: path.clone(),
+            title: doc.title.clone(),
+            doc_type,
+            score,
+            tags,
+            paths,
+            lines: line_matches,
+        });
+    }
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.file.cmp(&b.file)));
+    Ok(results)
+}
+
+/// Whether content in `section` is searched under the `--section` filter.
+fn section_matches(section: Option<&str>, filter: Option<&str>) -> bool {
+    match filter {
+        None => true,
+        Some(filter) => section.is_some_and(|s| s.to_lowercase() == filter),
+    }
+}
+
+fn type_name(doc_type: DocType) -> &'static str {
+    match doc_type {
+        DocType::Component => "component",
+        DocType::Runbook => "runbook",
+        DocType::Adr => "adr",
+        DocType::Postmortem => "postmortem",
+        DocType::Api => "api",
+        DocType::Other => "other",
+    }
+}
+
+fn output_text(results: &SearchResults) {
+    if results.results.is_empty() {
+        println!("No docs match \"{}\"", results.query);
+        return;
+    }
+
+    for result in &results.results {
+        println!(
+            "{} ({}){}",
+            result.file.display(),
+            type_name(result.doc_type),
+            result
+                .title
+                .as_deref()
+                .map(|title| format!(": {}", title))
+                .unwrap_or_default()
+        );
+        if !result.tags.is_empty() {
+            println!("  tags: {}", result.tags.join(", "));
+        }
+        if !result.paths.is_empty() {
+            println!("  paths: {}", result.paths.join(", "));
+        }
+        for line in &result.lines {
+            match &line.section {
+                Some(section) => println!("  {} [{}]: {}", line.line, section, line.text),
+                None => println!("  {}: {}", line.line, line.text),
+            }
+        }
+    }
+    println!();
+    println!(
+        "{} {} \"{}\"",
+        results.results.len(),
+        if results.results.len() == 1 {
+            "doc matches"
+        } else {
+            "docs match"
+        },
+        results.query
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Vec<PathBuf>) {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        let write = |name: &str, content: &str| {
+            let path = docs.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            path
+        };
+        let files = vec![
+            write(
+                "components/payments.md",
+                "---\npave:\n  tags: [payments, billing]\n---\n# Payments API\n\n\
+                 ## Purpose\n\nCharges cards.\n\n## Paths\n\n- `src/payments/**`\n\n\
+                 ## Verification\n\n```bash\ncurl localhost/payments/health\n```\n",
+            ),
+            write(
+                "runbooks/refund.md",
+                "# Issue a Refund\n\n## Steps\n\nOpen the payments console.\n\n\
+                 ## Verification\n\nCheck the refund in the ledger.\n",
+            ),
+            write(
+                "guide.md",
+                "# Guide\n\n## Purpose\n\nNothing about money.\n",
+            ),
+        ];
+        (temp_dir, files)
+    }
+
+    fn files_of(results: &[SearchResult]) -> Vec<String> {
+        results
+            .iter()
+            .map(|r| r.file.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn ranks_title_and_metadata_above_content() {
+        let (_temp_dir, files) = setup();
+
+        let results = search(&files, "Payments", &SearchFilters::default()).unwrap();
+        assert_eq!(files_of(&results), vec!["payments.md", "refund.md"]);
+        let payments = &results[0];
+        assert_eq!(payments.tags, vec!["payments"]);
+        assert_eq!(payments.paths, vec!["src/payments/**"]);
+        assert_eq!(
+            payments.lines,
+            vec![
+                LineMatch {
+                    line: 13,
+                    section: Some("Paths".to_string()),
+                    text: "- `src/payments/**`".to_string(),
+                },
+                LineMatch {
+                    line: 18,
+                    section: Some("Verification".to_string()),
+                    text: "curl localhost/payments/health".to_string(),
+                },
+            ]
+        );
+        assert_eq!(results[1].lines[0].line, 5);
+
+        // Every term must match somewhere
+        let results = search(&files, "payments ledger", &SearchFilters::default()).unwrap();
+        assert_eq!(files_of(&results), vec!["refund.md"]);
+    }
+
+    #[test]
+    fn filters_by_type_tag_and_section() {
+        let (_temp_dir, files) = setup();
+
+        let runbooks = SearchFilters {
+            doc_type: Some(DocType::Runbook),
+            ..Default::default()
+        };
+        assert_eq!(
+            files_of(&search(&files, "payments", &runbooks).unwrap()),
+            vec!["refund.md"]
+        );
+
+        let billing = SearchFilters {
+            tag: Some("Billing".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            files_of(&search(&files, "", &billing).unwrap()),
+            vec!["payments.md"]
+        );
+
+        let verification = SearchFilters {
+            section: Some("verification".to_string()),
+            ..Default::default()
+        };
+        let results = search(&files, "payments", &verification).unwrap();
+        assert_eq!(files_of(&results), vec!["payments.md"]);
+        assert!(results[0].tags.is_empty());
+        assert_eq!(results[0].lines.len(), 1);
+    }
+}
@@ -0,0 +1,1002 @@
+//! Implementation of the `pave split` command for breaking up oversized documents.
+//!
+//! The command proposes a plan that keeps the leading sections in the original
+//! document and moves the rest into new documents next to it, or moves just
+//! the sections named with `--section`, one new document each. Links to moved
+//! sections are rewritten and frontmatter `pave.paths` patterns follow the
+//! sections that mention them. Sections `pave check` requires stay in the
+//! original, and each new document links back to them from stub sections of
+//! its own so that it passes `pave check` too.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{PaveConfig, RulesSection};
+use crate::ignore::PaveIgnore;
+use crate::parser::{CodeBlockTracker, ParsedDoc, heading_anchor};
+use crate::rules::{Rule, detect_doc_type, get_type_specific_rules};
+use crate::walk::{collect_markdown_files, load_ignore};
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave split` command.
+pub struct SplitArgs {
+    /// Document to split.
+    pub doc: PathBuf,
+    /// Show the plan without modifying files.
+    pub dry_run: bool,
+    /// Apply the plan without asking for confirmation.
+    pub yes: bool,
+    /// Sections to move into their own documents, instead of packing by size.
+    pub sections: Vec<String>,
+}
+
+/// Sections that always stay in the original document, along with the
+/// sections `pave check` requires.
+const PINNED_SECTIONS: &[&str] = &["Purpose", "Paths", "See Also"];
+
+/// Lines reserved in the original document for the generated See Also list.
+const SEE_ALSO_RESERVE: usize = 8;
+
+/// Lines reserved in each new document for frontmatter, title, and back-link.
+const PART_OVERHEAD: usize = 8;
+
+/// Lines of each stub section linking back to a required section.
+const STUB_LINES: usize = 4;
+
+/// A new document produced by the split.
+#[derive(Debug)]
+pub struct SplitPart {
+    /// Path of the new document.
+    pub path: PathBuf,
+    /// H1 title of the new document.
+    pub title: String,
+    /// Names of the sections moved into it.
+    pub sections: Vec<String>,
+    /// Frontmatter path patterns moved into it.
+    pub paths: Vec<String>,
+    /// Full content of the new document.
+    pub content: String,
+}
+
+/// A document whose links to moved sections are rewritten.
+#[derive(Debug)]
+pub struct LinkUpdate {
+    /// Path of the document.
+    pub file: PathBuf,
+    /// Number of links rewritten.
+    pub links: usize,
+    /// Updated content.
+    pub content: String,
+}
+
+/// A proposed split of a single document.
+#[derive(Debug)]
+pub struct SplitPlan {
+    /// The document being split.
+    pub source: PathBuf,
+    /// Line count of the document before the split.
+    pub source_lines: usize,
+    /// Configured line limit.
+    pub max_lines: usize,
+    /// Sections that stay in the original document.
+    pub kept_sections: Vec<String>,
+    /// Rewritten content of the original document.
+    pub source_content: String,
+    /// New documents to create.
+    pub parts: Vec<SplitPart>,
+    /// Other documents whose links change.
+    pub link_updates: Vec<LinkUpdate>,
+    /// Sections that exceed the limit on their own, with their line counts.
+    pub oversized_sections: Vec<(String, usize)>,
+    /// Every H2 section of the original with its line count, in order.
+    pub section_lines: Vec<(String, usize)>,
+}
+
+/// An H2 section and its line range in the source document.
+#[derive(Debug, Clone)]
+struct SectionSpan {
+    name: String,
+    /// First line (the heading), 0-indexed.
+    start: usize,
+    /// One past the last line.
+    end: usize,
+    /// Anchors of all headings within the section.
+    anchors: Vec<String>,
+}
+
+impl SectionSpan {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn is_pinned(&self, required: &[String]) -> bool {
+        PINNED_SECTIONS
+            .iter()
+            .copied()
+            .chain(required.iter().map(String::as_str))
+            .any(|p| p.eq_ignore_ascii_case(&self.name))
+    }
+}
+
+/// Execute the `pave split` command.
+pub fn execute(args: SplitArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let max_lines = config.rules.max_lines as usize;
+    let doc = workdir.resolve(&args.doc);
+
+    let content = fs::read_to_string(&doc)
+        .with_context(|| format!("Failed to read file: {}", args.doc.display()))?;
+    let line_count = content.lines().count();
+    if line_count <= max_lines && args.sections.is_empty() {
+        println!(
+            "{} is within the {} line limit ({} lines); nothing to split",
+            args.doc.display(),
+            max_lines,
+            line_count
+        );
+        return Ok(());
+    }
+
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, &config)?;
+    let other_docs = load_other_docs(&docs_root, &doc, &ignore)?;
+    let plan = plan_split(&doc, &content, &config.rules, &args.sections, &other_docs)?;
+
+    output_plan(&plan);
+
+    if args.dry_run {
+        println!();
+        println!("Dry run: no files were changed.");
+        return Ok(());
+    }
+
+    if !args.yes && !prompt_user() {
+        println!("Split cancelled.");
+        return Ok(());
+    }
+
+    apply_plan(&plan)?;
+    println!(
+        "Split {} into {} documents. Run `pave check` to validate them.",
+        plan.source.display(),
+        plan.parts.len() + 1
+    );
+
+    Ok(())
+}
+
+/// Build a split plan for a document.
+///
+/// Sections are packed in document order: the original keeps its preamble,
+/// pinned sections, and as many leading sections as fit; the remaining
+/// sections are grouped into new documents that each stay under the limit.
+/// When `selected` names sections, each of those moves to a document of its
+/// own and everything else stays.
+///
+/// New documents keep the original's frontmatter apart from `pave.paths`,
+/// and get a stub for each section `rules` requires, linking to the original.
+pub fn plan_split(
+    source: &Path,
+    content: &str,
+    rules: &RulesSection,
+    selected: &[String],
+    other_docs: &[(PathBuf, String)],
+) -> Result<SplitPlan> {
+    let max_lines = rules.max_lines as usize;
+    let lines: Vec<&str> = content.lines().collect();
+    let frontmatter_end = frontmatter_end(&lines);
+    let sections = find_sections(&lines, frontmatter_end);
+    let preamble_end = sections.first().map(|s| s.start).unwrap_or(lines.len());
+    let required = required_sections(source, content, rules, &sections);
+    let part_overhead = PART_OVERHEAD + STUB_LINES * required.len();
+
+    let doc = ParsedDoc::parse_content(source.to_path_buf(), content)?;
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "doc".to_string());
+    let title = doc.title.clone().unwrap_or_else(|| stem.clone());
+
+    // Pack sections into the original and new groups
+    let mut kept: Vec<usize> = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut oversized_sections = Vec::new();
+    let mut original_used = preamble_end
+        + SEE_ALSO_RESERVE
+        + sections
+            .iter()
+            .filter(|s| s.is_pinned(&required))
+            .map(SectionSpan::len)
+            .sum::<usize>();
+    let mut original_open = selected.is_empty();
+    let mut current: Vec<usize> = Vec::new();
+    let mut current_used = part_overhead;
+
+    for name in selected {
+        let Some(section) = sections.iter().find(|s| s.name.eq_ignore_ascii_case(name)) else {
+            let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+            anyhow::bail!(
+                "No section '{}' in {} (sections: {})",
+                name,
+                source.display(),
+                names.join(", ")
+            );
+        };
+        if section.is_pinned(&required) {
+            anyhow::bail!(
+                "Section '{}' always stays in {}",
+                section.name,
+                source.display()
+            );
+        }
+    }
+
+    for (idx, section) in sections.iter().enumerate() {
+        if !selected.is_empty() {
+            if selected
+                .iter()
+                .any(|name| section.name.eq_ignore_ascii_case(name))
+            {
+                if section.len() + part_overhead > max_lines {
+                    oversized_sections.push((section.name.clone(), section.len()));
+                }
+                groups.push(vec![idx]);
+            } else {
+                kept.push(idx);
+            }
+            continue;
+        }
+        if section.is_pinned(&required) {
+            kept.push(idx);
+            continue;
+        }
+        let len = section.len();
+        if len + part_overhead > max_lines {
+            oversized_sections.push((section.name.clone(), len));
+        }
+        if original_open && original_used + len <= max_lines {
+            kept.push(idx);
+            original_used += len;
+            continue;
+        }
+        original_open = false;
+        if !current.is_empty() && current_used + len > max_lines {
+            groups.push(std::mem::take(&mut current));
+            current_used = part_overhead;
+        }
+        current.push(idx);
+        current_used += len;
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    if groups.is_empty() {
+        anyhow::bail!(
+            "Cannot split {}: no sections can be moved out (only the preamble and pinned sections remain)",
+            source.display()
+        );
+    }
+
+    // Name the new documents after their first section
+    let dir = source.parent().unwrap_or_else(|| Path::new(""));
+    let source_name = file_name(source);
+    let mut part_names: Vec<String> = Vec::new();
+    for group in &groups {
+        let base = format!("{}-{}", stem, heading_anchor(&sections[group[0]].name));
+        let mut name = format!("{}.md", base);
+        let mut n = 2;
+        while dir.join(&name).exists() || part_names.contains(&name) || name == source_name {
+            name = format!("{}-{}.md", base, n);
+            n += 1;
+        }
+        part_names.push(name);
+    }
+
+    // Map every heading anchor to the file it ends up in
+    let mut anchor_files: HashMap<String, String> = HashMap::new();
+    for (group, name) in groups.iter().zip(&part_names) {
+        for &idx in group {
+            for anchor in &sections[idx].anchors {
+                anchor_files.insert(anchor.clone(), name.clone());
+            }
+        }
+    }
+
+    // Partition frontmatter paths by which sections mention them
+    let frontmatter_paths = doc.frontmatter.map(|f| f.paths).unwrap_or_default();
+    let section_text = |indices: &[usize]| -> String {
+        indices
+            .iter()
+            .map(|&idx| lines[sections[idx].start..sections[idx].end].join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let kept_text = section_text(&kept);
+    let group_texts: Vec<String> = groups.iter().map(|g| section_text(g)).collect();
+    let mut part_paths: Vec<Vec<String>> = vec![Vec::new(); groups.len()];
+    for pattern in &frontmatter_paths {
+        let prefix = literal_prefix(pattern);
+        if prefix.is_empty() || kept_text.contains(prefix) {
+            continue;
+        }
+        if let Some(group_idx) = group_texts.iter().position(|text| text.contains(prefix)) {
+            part_paths[group_idx].push(pattern.clone());
+        }
+    }
+    let moved_paths: Vec<&String> = part_paths.iter().flatten().collect();
+    let frontmatter_lines = &lines[frontmatter_end.min(1)..frontmatter_end.saturating_sub(1)];
+
+    // Build the new documents
+    let mut parts = Vec::new();
+    for (group_idx, group) in groups.iter().enumerate() {
+        let name = &part_names[group_idx];
+        let part_title = format!("{}: {}", title, sections[group[0]].name);
+        let mut out = String::new();
+        let part_frontmatter = if frontmatter_end > 0 {
+            filter_frontmatter(frontmatter_lines, |pattern| {
+                !frontmatter_paths.iter().any(|p| p == pattern)
+                    || part_paths[group_idx].iter().any(|p| p == pattern)
+            })
+        } else if !part_paths[group_idx].is_empty() {
+            let mut block = "---\npave:\n  paths:\n".to_string();
+            for pattern in &part_paths[group_idx] {
+                block.push_str(&format!("    - \"{}\"\n", pattern));
+            }
+            block.push_str("---\n");
+            block
+        } else {
+            String::new()
+        };
+        if !part_frontmatter.is_empty() {
+            out.push_str(&part_frontmatter);
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "# {}\n\nSplit from [{}]({}).\n\n",
+            part_title, title, source_name
+        ));
+        out.push_str(&rewrite_local_anchors(
+            &section_text(group),
+            name,
+            &source_name,
+            &anchor_files,
+        ));
+        for section in &required {
+            let target = if sections
+                .iter()
+                .any(|s| s.name.eq_ignore_ascii_case(section))
+            {
+                format!("{}#{}", source_name, heading_anchor(section))
+            } else {
+                source_name.clone()
+            };
+            out = format!(
+                "{}\n\n## {}\n\nSee [{}]({}).\n",
+                out.trim_end(),
+                section,
+                section,
+                target
+            );
+        }
+        parts.push(SplitPart {
+            path: dir.join(name),
+            title: part_title,
+            sections: group
+                .iter()
+                .map(|&idx| sections[idx].name.clone())
+                .collect(),
+            paths: part_paths[group_idx].clone(),
+            content: format!("{}\n", out.trim_end()),
+        });
+    }
+
+    // Rebuild the original document
+    let mut original = if frontmatter_end > 0 {
+        filter_frontmatter(frontmatter_lines, |pattern| {
+            !moved_paths.iter().any(|p| p.as_str() == pattern)
+        })
+    } else {
+        String::new()
+    };
+    let see_also: String = parts
+        .iter()
+        .map(|p| format!("- [{}]({})\n", p.title, file_name(&p.path)))
+        .collect();
+    let mut body = lines[frontmatter_end..preamble_end].join("\n");
+    let mut has_see_also = false;
+    for &idx in &kept {
+        let mut text = lines[sections[idx].start..sections[idx].end].join("\n");
+        if sections[idx].name.eq_ignore_ascii_case("See Also") {
+            text = format!("{}\n{}\n", text.trim_end(), see_also.trim_end());
+            has_see_also = true;
+        }
+        body.push('\n');
+        body.push_str(&text);
+    }
+    if !has_see_also {
+        body = format!("{}\n\n## See Also\n\n{}", body.trim_end(), see_also);
+    }
+    original.push_str(&rewrite_local_anchors(
+        &body,
+        &source_name,
+        &source_name,
+        &anchor_files,
+    ));
+
+    // Rewrite links from other documents
+    let link_updates = other_docs
+        .iter()
+        .filter_map(|(path, content)| {
+            let (updated, links) = rewrite_external_links(path, content, source, &anchor_files);
+            (links > 0).then(|| LinkUpdate {
+                file: path.clone(),
+                links,
+                content: updated,
+            })
+        })
+        .collect();
+
+    Ok(SplitPlan {
+        source: source.to_path_buf(),
+        source_lines: lines.len(),
+        max_lines,
+        kept_sections: kept.iter().map(|&idx| sections[idx].name.clone()).collect(),
+        source_content: format!("{}\n", original.trim_end()),
+        parts,
+        link_updates,
+        oversized_sections,
+        section_lines: sections.iter().map(|s| (s.name.clone(), s.len())).collect(),
+    })
+}
+
+/// Return the line index just after the closing frontmatter delimiter, or 0.
+fn frontmatter_end(lines: &[&str]) -> usize {
+    if lines.first().is_some_and(|l| l.trim() == "---")
+        && let Some(pos) = lines.iter().skip(1).position(|l| l.trim() == "---")
+    {
+        return pos + 2;
+    }
+    0
+}
+
+/// Find H2 sections, ignoring headings inside code blocks.
+fn find_sections(lines: &[&str], start: usize) -> Vec<SectionSpan> {
+    let mut sections: Vec<SectionSpan> = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        if tracker.process_line(line) || tracker.in_code_block() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("## ") {
+            if let Some(last) = sections.last_mut() {
+                last.end = idx;
+            }
+            sections.push(SectionSpan {
+                name: name.trim().to_string(),
+                start: idx,
+                end: lines.len(),
+                anchors: Vec::new(),
+            });
+        } else if let Some(last) = sections.last_mut()
+            && let Some(heading) = subheading(line)
+        {
+            last.anchors.push(heading_anchor(heading));
+        }
+    }
+
+    for section in &mut sections {
+        section.anchors.insert(0, heading_anchor(&section.name));
+    }
+    sections
+}
+
+/// Return the text of an H3-H6 heading.
+fn subheading(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if (3..=6).contains(&hashes) {
+        line[hashes..].strip_prefix(' ').map(str::trim)
+    } else {
+        None
+    }
+}
+
+/// Sections `pave check` requires of a document like `source`: the
+/// Verification and Examples sections, when required, and the type-specific
+/// sections. Of a set of alternatives, the first one the document has is
+/// required.
+fn required_sections(
+    source: &Path,
+    content: &str,
+    rules: &RulesSection,
+    sections: &[SectionSpan],
+) -> Vec<String> {
+    let mut required = Vec::new();
+    if rules.require_verification {
+        required.push("Verification".to_string());
+    }
+    if rules.require_examples {
+        required.push("Examples".to_string());
+    }
+    let doc_type = detect_doc_type(source, content);
+    for rule in get_type_specific_rules(doc_type, rules) {
+        match rule {
+            Rule::RequireSection { name } => required.push(name),
+            Rule::RequireOneOf { sections: names } => {
+                let present = names
+                    .iter()
+                    .find(|name| sections.iter().any(|s| s.name.eq_ignore_ascii_case(name)));
+                if let Some(name) = present.or(names.first()) {
+                    required.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    required
+}
+
+/// Rebuild a frontmatter block from the lines between its delimiters,
+/// keeping only the list items `keep` accepts and dropping keys left without
+/// a value. Empty when nothing is left.
+fn filter_frontmatter(lines: &[&str], keep: impl Fn(&str) -> bool) -> String {
+    let kept: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| {
+            line.trim()
+                .strip_prefix("- ")
+                .is_none_or(|item| keep(unquote(item)))
+        })
+        .collect();
+
+    // Walk backwards so a key emptied by dropping its children goes too
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut out: Vec<&str> = Vec::new();
+    for line in kept.into_iter().rev() {
+        let is_empty_key = line.trim_end().ends_with(':') && !line.trim_start().starts_with('#');
+        if is_empty_key {
+            let child = out.iter().rev().find(|next| !next.trim().is_empty());
+            let has_child = child.is_some_and(|next| {
+                indent(next) > indent(line)
+                    || (indent(next) == indent(line) && next.trim_start().starts_with("- "))
+            });
+            if !has_child {
+                continue;
+            }
+        }
+        out.push(line);
+    }
+    out.reverse();
+
+    if out.iter().all(|line| line.trim().is_empty()) {
+        return String::new();
+    }
+    format!("---\n{}\n---\n", out.join("\n"))
+}
+
+/// The part of a glob pattern before its first wildcard, without a trailing `/`.
+fn literal_prefix(pattern: &str) -> &str {
+    let end = pattern.find(['*', '?', '[', '{']).unwrap_or(pattern.len());
+    pattern[..end].trim_end_matches('/')
+}
+
+/// Strip matching quotes from a YAML scalar.
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Point same-document anchor links at the file now holding the heading.
+///
+/// Anchors that are not in `anchor_files` stay in `source_name`.
+fn rewrite_local_anchors(
+    text: &str,
+    current: &str,
+    source_name: &str,
+    anchor_files: &HashMap<String, String>,
+) -> String {
+    let anchor_link_re = Regex::new(r"\]\(#([^)\s]+)\)").unwrap();
+    anchor_link_re
+        .replace_all(text, |caps: &regex::Captures| {
+            let anchor = &caps[1];
+            let target = anchor_files
+                .get(anchor)
+                .map(String::as_str)
+                .unwrap_or(source_name);
+            if target == current {
+                caps[0].to_string()
+            } else {
+                format!("]({}#{})", target, anchor)
+            }
+        })
+        .to_string()
+}
+
+/// Rewrite links in another document that point at moved sections.
+///
+/// Returns the updated content and the number of links changed.
+fn rewrite_external_links(
+    path: &Path,
+    content: &str,
+    source: &Path,
+    anchor_files: &HashMap<String, String>,
+) -> (String, usize) {
+    let file_anchor_re = Regex::new(r"\]\(([^)\s#]+)#([^)\s]+)\)").unwrap();
+    let source = fs::canonicalize(source).unwrap_or_else(|_| source.to_path_buf());
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut count = 0;
+
+    let updated = file_anchor_re.replace_all(content, |caps: &regex::Captures| {
+        let link_path = &caps[1];
+        let anchor = &caps[2];
+        let resolved = dir.join(link_path);
+        let resolved = fs::canonicalize(&resolved).unwrap_or(resolved);
+        match anchor_files.get(anchor) {
+            Some(target) if resolved == source => {
+                count += 1;
+                let new_path = Path::new(link_path).with_file_name(target);
+                format!("]({}#{})", new_path.display(), anchor)
+            }
+            _ => caps[0].to_string(),
+        }
+    });
+
+    (updated.to_string(), count)
+}
+
+/// Write all files in the plan. New documents are never overwritten.
+fn apply_plan(plan: &SplitPlan) -> Result<()> {
+    for part in &plan.parts {
+        if part.path.exists() {
+            anyhow::bail!("File already exists: {}", part.path.display());
+        }
+    }
+    for part in &plan.parts {
+        fs::write(&part.path, &part.content)
+            .with_context(|| format!("Failed to write file: {}", part.path.display()))?;
+    }
+    fs::write(&plan.source, &plan.source_content)
+        .with_context(|| format!("Failed to write file: {}", plan.source.display()))?;
+    for update in &plan.link_updates {
+        fs::write(&update.file, &update.content)
+            .with_context(|| format!("Failed to write file: {}", update.file.display()))?;
+    }
+    Ok(())
+}
+
+/// Load every other markdown document under the docs root.
+fn load_other_docs(
+    docs_root: &Path,
+    source: &Path,
+    ignore: &PaveIgnore,
+) -> Result<Vec<(PathBuf, String)>> {
+    let mut files = Vec::new();
+    if docs_root.is_dir() {
+        collect_markdown_files(docs_root, ignore, &mut files)?;
+    }
+    files.sort();
+
+    let source = fs::canonicalize(source).unwrap_or_else(|_| source.to_path_buf());
+    let mut docs = Vec::new();
+    for file in files {
+        if fs::canonicalize(&file).is_ok_and(|f| f == source) {
+            continue;
+        }
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        docs.push((file, content));
+    }
+    Ok(docs)
+}
+
+/// Prompt the user to confirm the split.
+fn prompt_user() -> bool {
+    use std::io::{self, Write};
+
+    println!();
+    print!("Apply this split? [y/N] ");
+    io::stdout().flush().ok();
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_ok() {
+        let input = input.trim().to_lowercase();
+        return input == "y" || input == "yes";
+    }
+
+    false
+}
+
+/// Print the split plan.
+fn output_plan(plan: &SplitPlan) {
+    let with_lines = |names: &[String]| -> String {
+        names
+            .iter()
+            .map(|name| {
+                let lines = plan
+                    .section_lines
+                    .iter()
+                    .find(|(section, _)| section == name)
+                    .map_or(0, |(_, lines)| *lines);
+                format!("{} ({} lines)", name, lines)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    println!(
+        "Split plan for {} ({} lines, limit {})",
+        plan.source.display(),
+        plan.source_lines,
+        plan.max_lines
+    );
+    println!();
+    println!(
+        "  {} (keeps {} lines)",
+        plan.source.display(),
+        plan.source_content.lines().count()
+    );
+    println!("    {}", with_lines(&plan.kept_sections));
+
+    for part in &plan.parts {
+        println!(
+            "  {} (new, {} lines)",
+            part.path.display(),
+            part.content.lines().count()
+        );
+        println!("    {}", with_lines(&part.sections));
+        if !part.paths.is_empty() {
+            println!("    paths: {}", part.paths.join(", "));
+        }
+    }
+
+    if !plan.link_updates.is_empty() {
+        println!();
+        println!("Link updates:");
+        for update in &plan.link_updates {
+            println!(
+                "  {}: {} link{}",
+                update.file.display(),
+                update.links,
+                if update.links == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    for (name, lines) in &plan.oversized_sections {
+        println!();
+        println!(
+            "Warning: section '{}' alone is {} lines; consider splitting it by hand",
+            name, lines
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::check::{CheckResults, check_file};
+    use tempfile::TempDir;
+
+    fn filler(n: usize) -> String {
+        (0..n).map(|i| format!("Line {}.\n", i)).collect()
+    }
+
+    fn rules(max_lines: u32) -> RulesSection {
+        RulesSection {
+            max_lines,
+            require_verification: false,
+            require_examples: false,
+            ..RulesSection::default()
+        }
+    }
+
+    #[test]
+    fn test_literal_prefix() {
+        assert_eq!(literal_prefix("src/api/**/*.rs"), "src/api");
+        assert_eq!(literal_prefix("src/lib.rs"), "src/lib.rs");
+        assert_eq!(literal_prefix("**/*.rs"), "");
+    }
+
+    #[test]
+    fn test_find_sections_ignores_code_blocks() {
+        let content = "# T\n\n## A\n```\n## Not a section\n```\n### Sub\n## B\n";
+        let lines: Vec<&str> = content.lines().collect();
+        let sections = find_sections(&lines, 0);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "A");
+        assert_eq!(sections[0].anchors, vec!["a", "sub"]);
+        assert_eq!((sections[1].start, sections[1].end), (7, 8));
+    }
+
+    #[test]
+    fn test_plan_split_moves_trailing_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("big.md");
+        let content = format!(
+            "---\npave:\n  paths:\n    - \"src/core/\"\n    - \"src/api/**\"\n---\n# Big\n\n## Purpose\nSee [the api](#api).\n\n## Core\nLives in src/core/.\n{}\n## API\nLives in src/api/.\nBack to [core](#core).\n{}\n",
+            filler(20),
+            filler(20)
+        );
+        fs::write(&source, &content).unwrap();
+
+        let plan = plan_split(&source, &content, &rules(45), &[], &[]).unwrap();
+
+        assert_eq!(plan.kept_sections, vec!["Purpose", "Core"]);
+        assert_eq!(plan.parts.len(), 1);
+        let part = &plan.parts[0];
+        assert_eq!(part.path, temp_dir.path().join("big-api.md"));
+        assert_eq!(part.title, "Big: API");
+        assert_eq!(part.paths, vec!["src/api/**"]);
+        assert!(part.content.contains("Split from [Big](big.md)."));
+        assert!(part.content.contains("[core](big.md#core)"));
+
+        assert!(plan.source_content.contains("[the api](big-api.md#api)"));
+        assert!(plan.source_content.contains("- [Big: API](big-api.md)"));
+        assert!(plan.source_content.contains("src/core/"));
+        assert!(!plan.source_content.contains("src/api/**"));
+        assert!(!plan.source_content.contains("## API"));
+    }
+
+    #[test]
+    fn test_plan_split_rewrites_links_in_other_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("big.md");
+        let content = format!(
+            "# Big\n\n## Purpose\nWhy.\n\n## Intro\n{}\n## Details\n{}",
+            filler(20),
+            filler(20)
+        );
+        fs::write(&source, &content).unwrap();
+        let other = temp_dir.path().join("other.md");
+        let other_content = "See [details](big.md#details) and [intro](big.md#intro).\n";
+
+        let plan = plan_split(
+            &source,
+            &content,
+            &rules(40),
+            &[],
+            &[(other.clone(), other_content.to_string())],
+        )
+        .unwrap();
+
+        assert_eq!(plan.link_updates.len(), 1);
+        assert_eq!(plan.link_updates[0].links, 1);
+        assert_eq!(
+            plan.link_updates[0].content,
+            "See [details](big-details.md#details) and [intro](big.md#intro).\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_split_moves_selected_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("big.md");
+        let content = format!(
+            "# Big\n\n## Purpose\nWhy.\n\n## Setup\n{}\n## Usage\n{}\n## FAQ\n{}",
+            filler(5),
+            filler(5),
+            filler(5)
+        );
+        fs::write(&source, &content).unwrap();
+
+        let selected = ["faq".to_string(), "Setup".to_string()];
+        let plan = plan_split(&source, &content, &rules(300), &selected, &[]).unwrap();
+        assert_eq!(plan.kept_sections, vec!["Purpose", "Usage"]);
+        let parts: Vec<(&Path, &[String])> = plan
+            .parts
+            .iter()
+            .map(|part| (part.path.as_path(), part.sections.as_slice()))
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                (
+                    temp_dir.path().join("big-setup.md").as_path(),
+                    &["Setup".to_string()][..]
+                ),
+                (
+                    temp_dir.path().join("big-faq.md").as_path(),
+                    &["FAQ".to_string()][..]
+                ),
+            ]
+        );
+        assert_eq!(plan.section_lines[1], ("Setup".to_string(), 7));
+
+        let missing = plan_split(&source, &content, &rules(300), &["Nope".to_string()], &[]);
+        assert!(
+            missing
+                .unwrap_err()
+                .to_string()
+                .contains("sections: Purpose, Setup")
+        );
+        assert!(
+            plan_split(
+                &source,
+                &content,
+                &rules(300),
+                &["Purpose".to_string()],
+                &[]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_plan_split_fails_without_movable_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("big.md");
+        let content = format!("# Big\n\n## Purpose\n{}", filler(50));
+
+        assert!(plan_split(&source, &content, &rules(20), &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_split_documents_pass_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("deploy-runbook.md");
+        let content = format!(
+            "---\npave:\n  owner: platform\n  paths:\n    - \"deploy/\"\n---\n# Deploy\n\n## Purpose\nWhy.\n\n## When to Use\nOn release.\n\n## Steps\n```bash\n$ make deploy\n```\n\n## Troubleshooting\nCheck deploy/ logs.\n{}\n## Rollback\nRevert.\n\n## Verification\n```bash\n$ make status\n```\n\n## Examples\n```bash\n$ make deploy ENV=staging\n```\n",
+            filler(40)
+        );
+        fs::write(&source, &content).unwrap();
+        let mut config = PaveConfig::default();
+        config.rules.max_lines = 50;
+        config.rules.require_owner = crate::config::RuleSeverity::Error;
+        config.rules.type_specific.runbooks = true;
+
+        let plan = plan_split(&source, &content, &config.rules, &[], &[]).unwrap();
+        assert_eq!(plan.parts.len(), 1);
+        assert!(plan.kept_sections.contains(&"Rollback".to_string()));
+        apply_plan(&plan).unwrap();
+
+        for path in [&source, &plan.parts[0].path] {
+            let mut results = CheckResults::new();
+            check_file(path, &config, &mut results).unwrap();
+            assert!(
+                results.errors.is_empty(),
+                "{}: {:?}",
+                path.display(),
+                results.errors
+            );
+        }
+        let part = &plan.parts[0].content;
+        assert!(part.contains("owner: platform"));
+        assert!(
+            part.contains("## Verification\n\nSee [Verification](deploy-runbook.md#verification).")
+        );
+    }
+
+    #[test]
+    fn test_apply_plan_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("big.md");
+        let content = format!("# Big\n\n## A\n{}\n## B\n{}", filler(20), filler(20));
+        fs::write(&source, &content).unwrap();
+
+        let plan = plan_split(&source, &content, &rules(40), &[], &[]).unwrap();
+        fs::write(&plan.parts[0].path, "existing").unwrap();
+
+        assert!(apply_plan(&plan).is_err());
+        assert_eq!(fs::read_to_string(&source).unwrap(), content);
+    }
+}
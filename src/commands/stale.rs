@@ -0,0 +1,360 @@
+//! Implementation of the `pave stale` command.
+//!
+//! A document is stale when the code it covers, through its Paths section or
+//! `pave.paths` frontmatter, was committed to well after the document itself.
+//! `pave stale` compares the last commit touching each document with the last
+//! commit touching its code and reports documents that lag by more than
+//! `--threshold-days`, along with how many commits touched the code since
+//! the document was last updated. `pave nightly` includes the same check.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::{OutputFormat, PathStyle};
+use crate::commands::coverage;
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::load_ignore;
+use crate::workspace::Workdir;
+
+/// Seconds in a day, for converting commit timestamps.
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Arguments for the `pave stale` command.
+pub struct StaleArgs {
+    /// Output format.
+    pub format: OutputFormat,
+    /// Days a doc may lag behind its code before it counts as stale.
+    pub threshold_days: u32,
+    /// Fail if any doc is stale.
+    pub strict: bool,
+}
+
+/// A document whose code changed well after the document itself.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct StaleDoc {
+    /// Path to the document.
+    pub file: PathBuf,
+    /// Days between the doc's last commit and its code's last commit.
+    pub days_behind: i64,
+    /// Date of the doc's last commit (YYYY-MM-DD).
+    pub doc_updated: String,
+    /// Date of the last commit touching the doc's code (YYYY-MM-DD).
+    pub code_updated: String,
+    /// Commits touching the doc's code since the doc's last commit.
+    pub code_commits: usize,
+}
+
+/// Results of the stale command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StaleResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Threshold used for staleness, in days.
+    pub threshold_days: u32,
+    /// Number of documents with code mappings and git history.
+    pub docs_checked: usize,
+    /// Stale documents, most behind first.
+    pub stale: Vec<StaleDoc>,
+}
+
+/// The last commit touching some paths.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LastCommit {
+    pub(crate) hash: String,
+    pub(crate) time: i64,
+}
+
+/// Execute the `pave stale` command.
+pub fn execute(args: StaleArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let results = find_stale_docs(&config, config_dir, args.threshold_days)?
+        .context("pave stale needs git history, but this is not a git repository")?;
+
+    match args.format {
+        OutputFormat::Text => output_text(&results),
+        OutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+        OutputFormat::Github => output_github(&results),
+    }
+
+    if args.strict && !results.stale.is_empty() {
+        anyhow::bail!(Violations(format!(
+            "{} stale document{}",
+            results.stale.len(),
+            if results.stale.len() == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
+/// Find docs whose mapped code was committed more than `threshold_days`
+/// after the doc itself.
+///
+/// Returns `None` if the project is not in a git repository.
+pub(crate) fn find_stale_docs(
+    config: &PaveConfig,
+    config_dir: &Path,
+    threshold_days: u32,
+) -> Result<Option<StaleResults>> {
+    if last_commit(config_dir, &["."]).is_none() {
+        return Ok(None);
+    }
+
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, config)?;
+    let mappings = coverage::load_doc_mappings(&docs_root, config_dir, &ignore)?;
+    let mut results = StaleResults {
+        schema_version: SCHEMA_VERSION,
+        threshold_days,
+        docs_checked: 0,
+        stale: Vec::new(),
+    };
+    for mapping in mappings {
+        let file = display_path(&mapping.doc, PathStyle::Workspace, config_dir, config_dir);
+        let doc_spec = file.to_string_lossy().into_owned();
+        let code_specs: Vec<String> = mapping
+            .patterns
+            .iter()
+            .map(|pattern| format!(":(glob){}", pattern))
+            .collect();
+
+        // Uncommitted docs and patterns without history can't be compared
+        let (Some(doc_commit), Some(code_commit)) = (
+            last_commit(config_dir, &[&doc_spec]),
+            last_commit(config_dir, &code_specs),
+        ) else {
+            continue;
+        };
+        results.docs_checked += 1;
+
+        if let Some(days_behind) = days_behind(doc_commit.time, code_commit.time, threshold_days) {
+            results.stale.push(StaleDoc {
+                file,
+                days_behind,
+                doc_updated: commit_date(doc_commit.time),
+                code_updated: commit_date(code_commit.time),
+                code_commits: commits_since(config_dir, &doc_commit.hash, &code_specs),
+            });
+        }
+    }
+
+    results
+        .stale
+        .sort_by_key(|doc| std::cmp::Reverse(doc.days_behind));
+    Ok(Some(results))
+}
+
+/// The most recent commit touching any of the pathspecs.
+pub(crate) fn last_commit<S: AsRef<str>>(config_dir: &Path, pathspecs: &[S]) -> Option<LastCommit> {
+    let stdout = git(
+        config_dir,
+        &["log", "-1", "--format=%H %ct", "--"],
+        pathspecs,
+    )?;
+    let (hash, time) = stdout.trim().split_once(' ')?;
+    Some(LastCommit {
+        hash: hash.to_string(),
+        time: time.parse().ok()?,
+    })
+}
+
+/// Number of commits after `since` touching any of the pathspecs.
+pub(crate) fn commits_since<S: AsRef<str>>(
+    config_dir: &Path,
+    since: &str,
+    pathspecs: &[S],
+) -> usize {
+    let range = format!("{}..HEAD", since);
+    git(
+        config_dir,
+        &["rev-list", "--count", &range, "--"],
+        pathspecs,
+    )
+    .and_then(|stdout| stdout.trim().parse().ok())
+    .unwrap_or(0)
+}
+
+/// Run git with `args` followed by `pathspecs`, returning its stdout.
+fn git<S: AsRef<str>>(config_dir: &Path, args: &[&str], pathspecs: &[S]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .args(pathspecs.iter().map(AsRef::as_ref))
+        .current_dir(config_dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whole days the doc lags its code, if that exceeds `threshold_days`.
+fn days_behind(doc_time: i64, code_time: i64, threshold_days: u32) -> Option<i64> {
+    let days = (code_time - doc_time) / SECONDS_PER_DAY;
+    (days > i64::from(threshold_days)).then_some(days)
+}
+
+/// UTC date of a commit timestamp.
+pub(crate) fn commit_date(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn output_text(results: &StaleResults) {
+    if results.stale.is_empty() {
+        println!(
+            "No docs lag their code by more than {} days ({} checked)",
+            results.threshold_days, results.docs_checked
+        );
+        return;
+    }
+
+    println!(
+        "{} of {} docs lag their code by more than {} days:",
+        results.stale.len(),
+        results.docs_checked,
+        results.threshold_days
+    );
+    for doc in &results.stale {
+        println!(
+            "  {}: {} days behind, {} code commit{} since {} (code last changed {})",
+            doc.file.display(),
+            doc.days_behind,
+            doc.code_commits,
+            if doc.code_commits == 1 { "" } else { "s" },
+            doc.doc_updated,
+            doc.code_updated
+        );
+    }
+}
+
+fn output_github(results: &StaleResults) {
+    for doc in &results.stale {
+        println!(
+            "::warning file={},line=1::Doc is {} days behind its code ({} commits since {})",
+            doc.file.display(),
+            doc.days_behind,
+            doc.code_commits,
+            doc.doc_updated
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn days_behind_respects_threshold() {
+        let doc_time = 1_700_000_000;
+
+        assert_eq!(
+            days_behind(doc_time, doc_time + 10 * SECONDS_PER_DAY, 30),
+            None
+        );
+        assert_eq!(
+            days_behind(doc_time, doc_time + 30 * SECONDS_PER_DAY, 30),
+            None
+        );
+        assert_eq!(
+            days_behind(doc_time, doc_time + 31 * SECONDS_PER_DAY, 30),
+            Some(31)
+        );
+        // Docs updated after their code are never stale
+        assert_eq!(
+            days_behind(doc_time + SECONDS_PER_DAY * 90, doc_time, 0),
+            None
+        );
+    }
+
+    fn commit(dir: &Path, path: &str, date: &str) {
+        let file = dir.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        let previous = fs::read_to_string(&file).unwrap_or_default();
+        fs::write(&file, format!("{}{}\n", previous, date)).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            path,
+        ]);
+    }
+
+    #[test]
+    fn finds_docs_whose_code_changed_after_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let config = PaveConfig::default();
+
+        assert!(find_stale_docs(&config, dir, 30).unwrap().is_none());
+
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(
+            dir.join("docs/api.md"),
+            "---\npave:\n  paths: [src/api/**]\n---\n# API\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("docs/cli.md"),
+            "# CLI\n\n## Paths\n\n- src/cli.rs\n",
+        )
+        .unwrap();
+        commit(dir, "src/api/mod.rs", "2025-01-01T12:00:00Z");
+        commit(dir, "src/api/mod.rs", "2025-03-01T12:00:00Z");
+        commit(dir, "src/api/routes.rs", "2025-03-15T12:00:00Z");
+        commit(dir, "src/cli.rs", "2025-03-16T12:00:00Z");
+        commit(dir, "docs/cli.md", "2025-03-20T12:00:00Z");
+
+        let results = find_stale_docs(&config, dir, 30).unwrap().unwrap();
+        assert_eq!(results.docs_checked, 2);
+        assert_eq!(
+            results.stale,
+            vec![StaleDoc {
+                file: PathBuf::from("docs/api.md"),
+                days_behind: 73,
+                doc_updated: "2025-01-01".to_string(),
+                code_updated: "2025-03-15".to_string(),
+                code_commits: 2,
+            }]
+        );
+
+        let results = find_stale_docs(&config, dir, 90).unwrap().unwrap();
+        assert!(results.stale.is_empty());
+    }
+}
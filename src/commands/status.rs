@@ -1,22 +1,28 @@
 //! Implementation of the `pave status` command for showing documentation health overview.
 
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use tracing::warn;
 
 use crate::cli::StatusOutputFormat;
 use crate::commands::hooks::{PAVE_HOOK_MARKER, find_git_hooks_dir_from};
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::ParsedDoc;
-use crate::rules::{DocType, RulesEngine, detect_doc_type};
-
-/// File analysis result: (is_compliant, has_warnings, error_count, warning_count, doc_type)
-type FileAnalysisResult = (bool, bool, usize, usize, DocType);
+use crate::commands::status_worklist::{DocIssues, WorkItem, build_worklist};
+use crate::config::PaveConfig;
+use crate::git::{changed_files, determine_base_ref};
+use crate::parser::DocState;
+use crate::rules::DocType;
+use crate::schema::SCHEMA_VERSION;
+use crate::stats_cache::{DocSummary, StatsCache};
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
 
 /// Arguments for the `pave status` command.
+#[derive(Clone)]
 pub struct StatusArgs {
     /// Specific files or directories to check.
     pub paths: Vec<PathBuf>,
@@ -26,10 +32,16 @@ pub struct StatusArgs {
     pub changed: bool,
     /// Base ref for --changed comparison.
     pub base: Option<String>,
+    /// Recompute every document instead of using cached summaries.
+    pub refresh: bool,
+    /// Rank this many docs and code files by risk.
+    pub worklist: Option<usize>,
+    /// Verify report whose failures count toward risk.
+    pub verify_report: Option<PathBuf>,
 }
 
 /// Statistics about document compliance by type.
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Serialize, JsonSchema)]
 pub struct TypeStats {
     /// Total documents of this type.
     pub total: usize,
@@ -38,12 +50,20 @@ pub struct TypeStats {
 }
 
 /// Information about a changed document.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ChangedDoc {
     /// Path to the document.
     pub path: PathBuf,
     /// Change type (Added, Modified).
     pub change_type: String,
+    /// Lifecycle state (draft, published, deprecated).
+    pub state: String,
+    /// Owner from frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Tags from frontmatter.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// Whether the document is compliant.
     pub is_compliant: bool,
     /// Number of errors.
@@ -54,9 +74,23 @@ pub struct ChangedDoc {
     pub summary: String,
 }
 
+/// A document past its review date.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OverdueDoc {
+    /// Path to the document.
+    pub path: PathBuf,
+    /// Date the review was due (YYYY-MM-DD).
+    pub review_due: String,
+    /// Owner from frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
 /// Results of the status command.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct StatusResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Root directory of documentation.
     pub docs_root: PathBuf,
     /// Total number of documents.
@@ -71,6 +105,14 @@ pub struct StatusResults {
     pub compliance_percent: f64,
     /// Statistics by document type.
     pub type_stats: HashMap<String, TypeStats>,
+    /// Number of documents in each lifecycle state.
+    pub state_counts: HashMap<String, usize>,
+    /// Number of documents with an owner.
+    pub owned_docs: usize,
+    /// Number of documents with each tag.
+    pub tag_counts: HashMap<String, usize>,
+    /// Documents past their review date, oldest first.
+    pub review_overdue: Vec<OverdueDoc>,
     /// Recent changes (when in git repo with --changed).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recent_changes: Option<Vec<ChangedDoc>>,
@@ -80,11 +122,15 @@ pub struct StatusResults {
     pub strict_mode_ready: bool,
     /// Whether pre-commit hook is installed.
     pub hooks_installed: bool,
+    /// Docs and code files to fix first, riskiest first (with --worklist).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worklist: Option<Vec<WorkItem>>,
 }
 
 impl StatusResults {
     fn new(docs_root: PathBuf) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             docs_root,
             total_docs: 0,
             compliant_docs: 0,
@@ -92,10 +138,15 @@ impl StatusResults {
             error_docs: 0,
             compliance_percent: 0.0,
             type_stats: HashMap::new(),
+            state_counts: HashMap::new(),
+            owned_docs: 0,
+            tag_counts: HashMap::new(),
+            review_overdue: Vec::new(),
             recent_changes: None,
             gradual_mode: false,
             strict_mode_ready: false,
             hooks_installed: false,
+            worklist: None,
         }
     }
 
@@ -105,20 +156,29 @@ impl StatusResults {
         }
     }
 
-    fn add_doc(&mut self, doc_type: DocType, is_compliant: bool, has_warnings: bool) {
+    fn add_doc(
+        &mut self,
+        doc_type: DocType,
+        state: DocState,
+        is_compliant: bool,
+        has_warnings: bool,
+    ) {
         self.total_docs += 1;
+        *self
+            .state_counts
+            .entry(state.as_str().to_string())
+            .or_default() += 1;
 
         let type_name = match doc_type {
             DocType::Component => "Components",
             DocType::Runbook => "Runbooks",
             DocType::Adr => "ADRs",
+            DocType::Postmortem => "Postmortems",
+            DocType::Api => "APIs",
             DocType::Other => "Other",
         };
 
-        let stats = self
-            .type_stats
-            .entry(type_name.to_string())
-            .or_default();
+        let stats = self.type_stats.entry(type_name.to_string()).or_default();
         stats.total += 1;
 
         if is_compliant {
@@ -131,25 +191,111 @@ impl StatusResults {
             self.error_docs += 1;
         }
     }
+
+    /// Count a document's owner and tags, and note it if its review was due
+    /// before `today`.
+    fn add_metadata(&mut self, path: &Path, summary: &DocSummary, today: NaiveDate) {
+        if summary.owner.is_some() {
+            self.owned_docs += 1;
+        }
+        let tags: HashSet<&str> = summary.tags.iter().map(|tag| tag.trim()).collect();
+        for tag in tags {
+            *self.tag_counts.entry(tag.to_string()).or_default() += 1;
+        }
+        if let Some(due) = &summary.review_due
+            && NaiveDate::parse_from_str(due, "%Y-%m-%d").is_ok_and(|due| due < today)
+        {
+            self.review_overdue.push(OverdueDoc {
+                path: path.to_path_buf(),
+                review_due: due.clone(),
+                owner: summary.owner.clone(),
+            });
+        }
+    }
+}
+
+impl Merge for StatusResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        self.docs_root = prefixed(package, &self.docs_root);
+        for doc in &mut self.review_overdue {
+            doc.path = prefixed(package, &doc.path);
+        }
+        for doc in self.recent_changes.iter_mut().flatten() {
+            doc.path = prefixed(package, &doc.path);
+        }
+        for item in self.worklist.iter_mut().flatten() {
+            item.path = prefixed(package, &item.path);
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        // Members' docs roots have nothing in common but the workspace root
+        if self.docs_root != other.docs_root {
+            self.docs_root = PathBuf::from(".");
+        }
+        self.total_docs += other.total_docs;
+        self.compliant_docs += other.compliant_docs;
+        self.warning_docs += other.warning_docs;
+        self.error_docs += other.error_docs;
+        for (name, stats) in other.type_stats {
+            let merged = self.type_stats.entry(name).or_default();
+            merged.total += stats.total;
+            merged.compliant += stats.compliant;
+        }
+        for (state, count) in other.state_counts {
+            *self.state_counts.entry(state).or_default() += count;
+        }
+        self.owned_docs += other.owned_docs;
+        for (tag, count) in other.tag_counts {
+            *self.tag_counts.entry(tag).or_default() += count;
+        }
+        self.review_overdue.extend(other.review_overdue);
+        self.review_overdue
+            .sort_by(|a, b| a.review_due.cmp(&b.review_due).then(a.path.cmp(&b.path)));
+        if let Some(changes) = other.recent_changes {
+            self.recent_changes.get_or_insert_default().extend(changes);
+        }
+        self.gradual_mode |= other.gradual_mode;
+        self.hooks_installed &= other.hooks_installed;
+        // Cut back to the limit on output
+        if let Some(items) = other.worklist {
+            let worklist = self.worklist.get_or_insert_default();
+            worklist.extend(items);
+            worklist.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+        }
+        self.update_compliance_percent();
+        self.strict_mode_ready = self.compliance_percent >= 50.0;
+    }
 }
 
 /// Execute the `pave status` command.
-pub fn execute(args: StatusArgs) -> Result<()> {
+pub fn execute(args: StatusArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Gather the status of the documents selected by `args`, leaving output to
+/// the caller.
+fn run(args: &StatusArgs, workdir: &Workdir) -> Result<StatusResults> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
 
     // Determine paths to check
     let docs_root = config_dir.join(&config.docs.root);
-    let paths = if args.paths.is_empty() {
+    let mut paths = if args.paths.is_empty() {
         vec![docs_root.clone()]
     } else {
-        args.paths.clone()
+        args.paths
+            .iter()
+            .map(|path| workdir.resolve(path))
+            .collect()
     };
+    // A path that doesn't exist yet could be a new docs folder
+    paths.retain(|path| path.exists());
 
     // Find all markdown files
-    let mut files = find_markdown_files(&paths)?;
+    let mut files = find_markdown_files(&paths, &load_ignore(config_dir, &config)?)?;
 
     // Initialize results
     let mut results = StatusResults::new(config.docs.root.clone());
@@ -162,8 +308,9 @@ pub fn execute(args: StatusArgs) -> Result<()> {
 
     // Handle --changed flag
     let changed_files = if args.changed {
-        let base_ref = determine_base_ref(args.base.as_deref())?;
-        let changed = get_changed_md_files(&base_ref, config_dir)?;
+        let base_ref = determine_base_ref(config_dir, args.base.as_deref())?;
+        let mut changed = changed_files(config_dir, &base_ref)?;
+        changed.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
 
         if changed.is_empty() {
             eprintln!("No changed markdown files found compared to {}", base_ref);
@@ -182,8 +329,7 @@ pub fn execute(args: StatusArgs) -> Result<()> {
 
     if files.is_empty() && !args.changed {
         eprintln!("No markdown files found in documentation root");
-        output_results(&results, args.format)?;
-        return Ok(());
+        return Ok(results);
     }
 
     // Get list of newly added files for accurate change_type detection
@@ -193,56 +339,79 @@ pub fn execute(args: StatusArgs) -> Result<()> {
         HashSet::new()
     };
 
-    // Analyze each file
+    // Analyze each file, reusing summaries of unchanged documents
     let mut recent_changes: Vec<ChangedDoc> = Vec::new();
+    let mut issues: Vec<DocIssues> = Vec::new();
+    let mut cache = StatsCache::load(&config_path, args.refresh);
+
+    let today = chrono::Local::now().date_naive();
 
     for file in &files {
         // Skip files that shouldn't be counted (index.md, templates)
-        let Some((is_compliant, has_warnings, error_count, warning_count, doc_type)) =
-            analyze_file(file, &config, config_dir)?
-        else {
+        let Some(doc) = analyze_file(file, &config, &mut cache)? else {
             continue;
         };
-
-        results.add_doc(doc_type, is_compliant, has_warnings);
+        let is_compliant = doc.is_compliant();
+        let (error_count, warning_count) = (doc.error_count, doc.warning_count);
+        let state = doc.state;
+
+        results.add_doc(doc.doc_type, state, is_compliant, warning_count > 0);
+        let relative = file.strip_prefix(config_dir).unwrap_or(file).to_path_buf();
+        results.add_metadata(&relative, &doc, today);
+        issues.push(DocIssues {
+            path: relative.clone(),
+            errors: error_count,
+            warnings: warning_count,
+        });
 
         // Track changed docs for recent changes display
-        if let Some(ref changed) = changed_files {
-            let relative = file.strip_prefix(config_dir).unwrap_or(file).to_path_buf();
-            if changed.contains(&relative) || changed.contains(file) {
-                let change_type = if added_files.contains(&relative) || added_files.contains(file) {
-                    "Added"
-                } else {
-                    "Modified"
-                };
-                let summary = if is_compliant && warning_count == 0 {
-                    "compliant".to_string()
-                } else if is_compliant {
-                    format!(
-                        "{} warning{}",
-                        warning_count,
-                        if warning_count == 1 { "" } else { "s" }
-                    )
-                } else {
-                    format!(
-                        "{} error{}",
-                        error_count,
-                        if error_count == 1 { "" } else { "s" }
-                    )
-                };
-
-                recent_changes.push(ChangedDoc {
-                    path: relative,
-                    change_type: change_type.to_string(),
-                    is_compliant,
-                    error_count,
+        if let Some(ref changed) = changed_files
+            && (changed.contains(&relative) || changed.contains(file))
+        {
+            let change_type = if added_files.contains(&relative) || added_files.contains(file) {
+                "Added"
+            } else {
+                "Modified"
+            };
+            let summary = if is_compliant && warning_count == 0 {
+                "compliant".to_string()
+            } else if is_compliant {
+                format!(
+                    "{} warning{}",
                     warning_count,
-                    summary,
-                });
-            }
+                    if warning_count == 1 { "" } else { "s" }
+                )
+            } else {
+                format!(
+                    "{} error{}",
+                    error_count,
+                    if error_count == 1 { "" } else { "s" }
+                )
+            };
+
+            recent_changes.push(ChangedDoc {
+                path: relative,
+                change_type: change_type.to_string(),
+                state: state.to_string(),
+                owner: doc.owner,
+                tags: doc.tags,
+                is_compliant,
+                error_count,
+                warning_count,
+                summary,
+            });
         }
     }
 
+    // A stale cache only costs a recomputation next time
+    if let Err(e) = cache.save() {
+        warn!("Failed to write stats cache: {:#}", e);
+    }
+
+    results
+        .review_overdue
+        .sort_by(|a, b| a.review_due.cmp(&b.review_due).then(a.path.cmp(&b.path)));
+
     // Update compliance percentage
     results.update_compliance_percent();
 
@@ -254,10 +423,20 @@ pub fn execute(args: StatusArgs) -> Result<()> {
         results.recent_changes = Some(recent_changes);
     }
 
-    // Output results
-    output_results(&results, args.format)?;
+    if let Some(limit) = args.worklist {
+        results.worklist = Some(build_worklist(
+            &config,
+            config_dir,
+            &issues,
+            args.verify_report
+                .as_ref()
+                .map(|path| workdir.resolve(path))
+                .as_deref(),
+            limit,
+        )?);
+    }
 
-    Ok(())
+    Ok(results)
 }
 
 /// Check if a file should be skipped from compliance tracking.
@@ -276,51 +455,19 @@ fn should_skip_file(path: &Path) -> bool {
     false
 }
 
-/// Analyze a single file and return compliance info.
+/// Analyze a single file and return its summary.
 /// Returns None for files that should be skipped (index.md, templates).
 fn analyze_file(
     path: &Path,
     config: &PaveConfig,
-    config_dir: &Path,
-) -> Result<Option<FileAnalysisResult>> {
+    cache: &mut StatsCache,
+) -> Result<Option<DocSummary>> {
     // Skip index.md and template files (they don't count toward compliance)
     if should_skip_file(path) {
         return Ok(None);
     }
 
-    // Read and parse the file
-    let content = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
-
-    // Detect document type
-    let doc_type = detect_doc_type(path, &content);
-
-    // Build rules engine with project root for path validation
-    let engine = RulesEngine::from_config_with_root(&config.rules, config_dir);
-
-    // Validate with type-specific rules
-    let result = engine.validate_with_type(&doc, doc_type, &config.rules);
-
-    // Also check base requirements (max lines, etc.)
-    let error_count = result.errors.len();
-    let mut warning_count = result.warnings.len();
-
-    // Check max lines (produces warning)
-    if doc.line_count > config.rules.max_lines as usize {
-        warning_count += 1;
-    }
-
-    let is_compliant = error_count == 0;
-    let has_warnings = warning_count > 0;
-
-    Ok(Some((
-        is_compliant,
-        has_warnings,
-        error_count,
-        warning_count,
-        doc_type,
-    )))
+    cache.summary(path, config).map(Some)
 }
 
 /// Check if pre-commit hook is installed by pave.
@@ -336,126 +483,6 @@ fn check_hooks_installed(config_dir: &Path) -> bool {
     false
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
-/// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
-
-    for path in paths {
-        if path.is_file() {
-            if path.extension().is_some_and(|ext| ext == "md") {
-                files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        } else if !path.exists() {
-            // Path doesn't exist yet - could be a new docs folder
-            continue;
-        } else {
-            anyhow::bail!("Path is not a file or directory: {}", path.display());
-        }
-    }
-
-    // Sort for consistent output
-    files.sort();
-    Ok(files)
-}
-
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
-        }
-    }
-
-    Ok(())
-}
-
-/// Determine the base ref to compare against.
-fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
-    if let Some(base) = explicit_base {
-        return Ok(base.to_string());
-    }
-
-    // Try origin/main first
-    if ref_exists("origin/main") {
-        return Ok("origin/main".to_string());
-    }
-
-    // Try origin/master
-    if ref_exists("origin/master") {
-        return Ok("origin/master".to_string());
-    }
-
-    // Fall back to HEAD~1
-    Ok("HEAD~1".to_string())
-}
-
-/// Check if a git ref exists.
-fn ref_exists(ref_name: &str) -> bool {
-    Command::new("git")
-        .args(["rev-parse", "--verify", "--quiet", ref_name])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Get the list of changed markdown files from git diff.
-fn get_changed_md_files(base_ref: &str, config_dir: &Path) -> Result<HashSet<PathBuf>> {
-    let output = Command::new("git")
-        .args(["diff", "--name-only", &format!("{}..HEAD", base_ref)])
-        .current_dir(config_dir)
-        .output()
-        .context("Failed to run git diff")?;
-
-    if !output.status.success() {
-        // Try without ..HEAD for cases like HEAD~1
-        let output = Command::new("git")
-            .args(["diff", "--name-only", base_ref])
-            .current_dir(config_dir)
-            .output()
-            .context("Failed to run git diff")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("git diff failed: {}", stderr);
-        }
-
-        return parse_changed_md_files(&output.stdout);
-    }
-
-    parse_changed_md_files(&output.stdout)
-}
-
 /// Parse git diff --name-only output into a set of markdown file paths.
 fn parse_changed_md_files(output: &[u8]) -> Result<HashSet<PathBuf>> {
     let stdout = String::from_utf8_lossy(output);
@@ -470,7 +497,7 @@ fn parse_changed_md_files(output: &[u8]) -> Result<HashSet<PathBuf>> {
 
 /// Get the list of newly added markdown files from git diff.
 fn get_added_files(explicit_base: Option<&str>, config_dir: &Path) -> Result<HashSet<PathBuf>> {
-    let base_ref = determine_base_ref(explicit_base)?;
+    let base_ref = determine_base_ref(config_dir, explicit_base)?;
 
     // Use --diff-filter=A to get only added files
     let output = Command::new("git")
@@ -502,13 +529,41 @@ fn get_added_files(explicit_base: Option<&str>, config_dir: &Path) -> Result<Has
     parse_changed_md_files(&output.stdout)
 }
 
-/// Output results in the requested format.
-fn output_results(results: &StatusResults, format: StatusOutputFormat) -> Result<()> {
-    match format {
-        StatusOutputFormat::Text => output_text(results),
-        StatusOutputFormat::Json => output_json(results)?,
+impl PackageCommand for StatusArgs {
+    type Results = StatusResults;
+
+    fn is_json(&self) -> bool {
+        matches!(self.format, StatusOutputFormat::Json)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<StatusResults>>> {
+        Ok(Some(Outcome {
+            results: run(self, workdir)?,
+            status: Ok(()),
+        }))
+    }
+
+    fn output(&self, mut results: StatusResults) -> Result<()> {
+        if let (Some(worklist), Some(limit)) = (&mut results.worklist, self.worklist) {
+            worklist.truncate(limit);
+        }
+        match self.format {
+            StatusOutputFormat::Text => output_text(&results),
+            StatusOutputFormat::Json => output_json(&results)?,
+        }
+        Ok(())
     }
-    Ok(())
 }
 
 /// Output results in text format.
@@ -530,6 +585,34 @@ fn output_text(results: &StatusResults) {
         println!("  Errors: {}", results.error_docs);
     }
 
+    // Only worth showing once some docs aren't published
+    let published = results.state_counts.get("published").copied().unwrap_or(0);
+    if published < results.total_docs {
+        let states: Vec<String> = DocState::ALL
+            .iter()
+            .filter_map(|state| {
+                let count = *results.state_counts.get(state.as_str())?;
+                Some(format!("{} {}", count, state))
+            })
+            .collect();
+        println!("  States: {}", states.join(", "));
+    }
+    if results.owned_docs > 0 {
+        println!(
+            "  Owners: {} of {} documents",
+            results.owned_docs, results.total_docs
+        );
+    }
+    if !results.tag_counts.is_empty() {
+        let mut tags: Vec<_> = results.tag_counts.iter().collect();
+        tags.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|(tag, count)| format!("{} ({})", tag, count))
+            .collect();
+        println!("  Tags: {}", tags.join(", "));
+    }
+
     // Document types breakdown
     if !results.type_stats.is_empty() {
         println!();
@@ -549,6 +632,20 @@ fn output_text(results: &StatusResults) {
         }
     }
 
+    // Overdue reviews
+    if !results.review_overdue.is_empty() {
+        println!();
+        println!("Review Overdue:");
+        for doc in &results.review_overdue {
+            let owner = doc
+                .owner
+                .as_ref()
+                .map(|owner| format!(", owner: {}", owner))
+                .unwrap_or_default();
+            println!("  {} (due {}{})", doc.path.display(), doc.review_due, owner);
+        }
+    }
+
     // Recent changes section
     if let Some(ref changes) = results.recent_changes
         && !changes.is_empty()
@@ -561,16 +658,46 @@ fn output_text(results: &StatusResults) {
             } else {
                 "✗"
             };
+            let state = if change.state == DocState::Published.as_str() {
+                String::new()
+            } else {
+                format!(" [{}]", change.state)
+            };
+            let owner = change
+                .owner
+                .as_ref()
+                .map(|owner| format!(" @{}", owner))
+                .unwrap_or_default();
             println!(
-                "  {}: {} ({} {})",
+                "  {}: {}{}{} ({} {})",
                 change.change_type,
                 change.path.display(),
+                state,
+                owner,
                 change.summary,
                 status_indicator
             );
         }
     }
 
+    // Worklist
+    if let Some(ref worklist) = results.worklist {
+        println!();
+        println!("Fix These First:");
+        if worklist.is_empty() {
+            println!("  Nothing to fix");
+        }
+        for (rank, item) in worklist.iter().enumerate() {
+            println!(
+                "  {}. {} (risk {}): {}",
+                rank + 1,
+                item.path.display(),
+                item.score,
+                item.reasons()
+            );
+        }
+    }
+
     // Mode and readiness info
     println!();
     if results.gradual_mode {
@@ -612,6 +739,7 @@ fn output_json(results: &StatusResults) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ignore::PaveIgnore;
     use std::fs;
     use tempfile::TempDir;
 
@@ -681,9 +809,9 @@ This document is missing required sections.
     fn status_results_tracks_compliance() {
         let mut results = StatusResults::new(PathBuf::from("docs"));
 
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, true, true);
-        results.add_doc(DocType::Component, false, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, true, true);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
 
         assert_eq!(results.total_docs, 3);
         assert_eq!(results.compliant_docs, 2);
@@ -698,11 +826,11 @@ This document is missing required sections.
     fn status_results_tracks_by_type() {
         let mut results = StatusResults::new(PathBuf::from("docs"));
 
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, false, false);
-        results.add_doc(DocType::Runbook, true, false);
-        results.add_doc(DocType::Adr, true, false);
-        results.add_doc(DocType::Other, false, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
+        results.add_doc(DocType::Runbook, DocState::Published, true, false);
+        results.add_doc(DocType::Adr, DocState::Published, true, false);
+        results.add_doc(DocType::Other, DocState::Published, false, false);
 
         assert_eq!(results.type_stats.get("Components").unwrap().total, 2);
         assert_eq!(results.type_stats.get("Components").unwrap().compliant, 1);
@@ -714,35 +842,131 @@ This document is missing required sections.
     #[test]
     fn analyze_valid_file() {
         let temp_dir = TempDir::new().unwrap();
-        let _config_path = create_test_config(&temp_dir);
+        let config_path = create_test_config(&temp_dir);
         let doc_path = create_valid_doc(&temp_dir, "valid.md");
 
         let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
-        let result = analyze_file(&doc_path, &config, temp_dir.path()).unwrap();
-
-        let (is_compliant, _, error_count, _, _) = result.expect("File should not be skipped");
-        assert!(is_compliant);
-        assert_eq!(error_count, 0);
+        let result = analyze_file(
+            &doc_path,
+            &config,
+            &mut StatsCache::load(&config_path, true),
+        )
+        .unwrap();
+
+        let summary = result.expect("File should not be skipped");
+        assert!(summary.is_compliant());
+        assert_eq!(summary.error_count, 0);
     }
 
     #[test]
     fn analyze_invalid_file() {
         let temp_dir = TempDir::new().unwrap();
-        let _config_path = create_test_config(&temp_dir);
+        let config_path = create_test_config(&temp_dir);
         let doc_path = create_invalid_doc(&temp_dir, "invalid.md");
 
         let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
-        let result = analyze_file(&doc_path, &config, temp_dir.path()).unwrap();
+        let result = analyze_file(
+            &doc_path,
+            &config,
+            &mut StatsCache::load(&config_path, true),
+        )
+        .unwrap();
+
+        let summary = result.expect("File should not be skipped");
+        assert!(!summary.is_compliant());
+        assert!(summary.error_count > 0);
+    }
+
+    #[test]
+    fn analyze_draft_file_only_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc_path = create_invalid_doc(&temp_dir, "draft.md");
+        let content = fs::read_to_string(&doc_path).unwrap();
+        fs::write(
+            &doc_path,
+            format!("---\npave:\n  state: draft\n---\n{}", content),
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
+        let result = analyze_file(
+            &doc_path,
+            &config,
+            &mut StatsCache::load(&config_path, true),
+        )
+        .unwrap();
+
+        let summary = result.expect("File should not be skipped");
+        assert!(summary.is_compliant());
+        assert!(summary.warning_count > 0);
+        assert_eq!(summary.error_count, 0);
+        assert_eq!(summary.state, DocState::Draft);
+    }
 
-        let (is_compliant, _, error_count, _, _) = result.expect("File should not be skipped");
-        assert!(!is_compliant);
-        assert!(error_count > 0);
+    #[test]
+    fn status_results_track_owners_tags_and_overdue_reviews() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut cache = StatsCache::load(&config_path, true);
+        let mut results = StatusResults::new(PathBuf::from("docs"));
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+
+        for (name, frontmatter) in [
+            (
+                "a.md",
+                "owner: alice
+  tags: [api, auth]
+  last_reviewed: 2025-01-01
+  review_interval_days: 90
+",
+            ),
+            (
+                "b.md",
+                "tags: [api]
+  last_reviewed: 2025-05-01
+  review_interval_days: 90
+",
+            ),
+        ] {
+            let path = create_valid_doc(&temp_dir, name);
+            let content = fs::read_to_string(&path).unwrap();
+            fs::write(
+                &path,
+                format!("---\npave:\n  {}---\n{}", frontmatter, content),
+            )
+            .unwrap();
+            let summary = analyze_file(&path, &config, &mut cache).unwrap().unwrap();
+            results.add_metadata(Path::new(name), &summary, today);
+        }
+
+        assert_eq!(results.owned_docs, 1);
+        assert_eq!(results.tag_counts.get("api"), Some(&2));
+        assert_eq!(results.review_overdue.len(), 1);
+        let overdue = &results.review_overdue[0];
+        assert_eq!(overdue.path, PathBuf::from("a.md"));
+        assert_eq!(overdue.review_due, "2025-04-01");
+        assert_eq!(overdue.owner.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn status_results_counts_states() {
+        let mut results = StatusResults::new(PathBuf::from("docs"));
+
+        results.add_doc(DocType::Component, DocState::Draft, true, true);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Runbook, DocState::Published, true, false);
+
+        assert_eq!(results.state_counts.get("draft"), Some(&1));
+        assert_eq!(results.state_counts.get("published"), Some(&2));
+        assert_eq!(results.state_counts.get("deprecated"), None);
     }
 
     #[test]
     fn analyze_skips_index_files() {
         let temp_dir = TempDir::new().unwrap();
-        let _config_path = create_test_config(&temp_dir);
+        let config_path = create_test_config(&temp_dir);
 
         // Create an index.md without required sections
         let docs_dir = temp_dir.path().join("docs");
@@ -751,7 +975,12 @@ This document is missing required sections.
         fs::write(&index_path, "# Index\n\nJust links here.").unwrap();
 
         let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
-        let result = analyze_file(&index_path, &config, temp_dir.path()).unwrap();
+        let result = analyze_file(
+            &index_path,
+            &config,
+            &mut StatsCache::load(&config_path, true),
+        )
+        .unwrap();
 
         // index.md should be skipped (None returned)
         assert!(result.is_none(), "index.md should be skipped");
@@ -760,7 +989,7 @@ This document is missing required sections.
     #[test]
     fn analyze_skips_template_files() {
         let temp_dir = TempDir::new().unwrap();
-        let _config_path = create_test_config(&temp_dir);
+        let config_path = create_test_config(&temp_dir);
 
         // Create a template file without required sections
         let templates_dir = temp_dir.path().join("docs").join("templates");
@@ -769,7 +998,12 @@ This document is missing required sections.
         fs::write(&template_path, "# {Name}\n\n## Purpose\nDescribe.").unwrap();
 
         let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
-        let result = analyze_file(&template_path, &config, temp_dir.path()).unwrap();
+        let result = analyze_file(
+            &template_path,
+            &config,
+            &mut StatsCache::load(&config_path, true),
+        )
+        .unwrap();
 
         // Templates should be skipped (None returned)
         assert!(result.is_none(), "template files should be skipped");
@@ -778,7 +1012,7 @@ This document is missing required sections.
     #[test]
     fn json_output_is_valid() {
         let mut results = StatusResults::new(PathBuf::from("docs"));
-        results.add_doc(DocType::Component, true, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
         results.update_compliance_percent();
 
         let json = serde_json::to_string(&results).unwrap();
@@ -800,7 +1034,7 @@ This document is missing required sections.
         fs::write(nested_dir.join("doc2.md"), "# Doc 2").unwrap();
         fs::write(docs_dir.join("readme.txt"), "Not markdown").unwrap();
 
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], &PaveIgnore::default()).unwrap();
 
         assert_eq!(files.len(), 2);
         assert!(files.iter().any(|f| f.file_name().unwrap() == "doc1.md"));
@@ -812,11 +1046,11 @@ This document is missing required sections.
         let mut results = StatusResults::new(PathBuf::from("docs"));
 
         // 3 out of 5 compliant = 60%
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, false, false);
-        results.add_doc(DocType::Component, false, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
 
         results.update_compliance_percent();
         results.strict_mode_ready = results.compliance_percent >= 50.0;
@@ -829,11 +1063,11 @@ This document is missing required sections.
         let mut results = StatusResults::new(PathBuf::from("docs"));
 
         // 2 out of 5 compliant = 40%
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, true, false);
-        results.add_doc(DocType::Component, false, false);
-        results.add_doc(DocType::Component, false, false);
-        results.add_doc(DocType::Component, false, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, true, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
+        results.add_doc(DocType::Component, DocState::Published, false, false);
 
         results.update_compliance_percent();
         results.strict_mode_ready = results.compliance_percent >= 50.0;
@@ -850,10 +1084,4 @@ This document is missing required sections.
         assert!(files.contains(&PathBuf::from("docs/readme.md")));
         assert!(files.contains(&PathBuf::from("docs/guide.md")));
     }
-
-    #[test]
-    fn determine_base_ref_uses_explicit() {
-        let result = determine_base_ref(Some("custom-branch")).unwrap();
-        assert_eq!(result, "custom-branch");
-    }
 }
@@ -0,0 +1,351 @@
+//! Risk-ranked worklist for `pave status --worklist`.
+//!
+//! Every doc gets a risk score from its check errors and warnings, the
+//! failed commands in the last verify report, and the commits to its mapped
+//! code since the doc itself last changed. Code files committed often in
+//! the last [`HOT_WINDOW_DAYS`] days that no doc covers are scored by their
+//! commit count. The highest scores come first.
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::warn;
+
+use crate::cli::PathStyle;
+use crate::commands::coverage::{is_code_file, load_doc_mappings, matches_any_pattern};
+use crate::commands::stale::{commits_since, last_commit};
+use crate::commands::verify::{ARTIFACT_REPORT_PATH, VerifyStatus, load_report};
+use crate::config::PaveConfig;
+use crate::paths::display_path;
+use crate::walk::load_ignore;
+
+/// Points per check error: the doc fails `pave check`.
+const CHECK_ERROR_WEIGHT: u32 = 10;
+
+/// Points per check warning.
+const CHECK_WARNING_WEIGHT: u32 = 1;
+
+/// Points per failed verification command: the doc's instructions don't work.
+const VERIFY_FAILURE_WEIGHT: u32 = 15;
+
+/// Points per commit to a doc's mapped code since the doc last changed.
+const STALE_COMMIT_WEIGHT: u32 = 2;
+
+/// Most points staleness alone can add.
+const STALE_MAX: u32 = 30;
+
+/// Days of history that make a code file hot.
+pub const HOT_WINDOW_DAYS: u32 = 90;
+
+/// Commits within the window before an uncovered code file is listed.
+const HOT_MIN_COMMITS: usize = 3;
+
+/// Points per recent commit to an uncovered code file.
+const HOT_COMMIT_WEIGHT: u32 = 2;
+
+/// What a worklist item asks for.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkKind {
+    /// Fix or update a doc.
+    Doc,
+    /// Document a hot code file no doc covers.
+    Code,
+}
+
+/// One entry in the worklist.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct WorkItem {
+    /// Doc or code file, relative to the config directory.
+    pub path: PathBuf,
+    /// What the item asks for.
+    pub kind: WorkKind,
+    /// Risk score; higher means fix first.
+    pub score: u32,
+    /// Check errors in the doc.
+    pub check_errors: usize,
+    /// Check warnings in the doc.
+    pub check_warnings: usize,
+    /// Failed commands in the last verify report.
+    pub verify_failures: usize,
+    /// Commits to the doc's mapped code since the doc last changed.
+    pub commits_behind: usize,
+    /// Commits to the code file in the last [`HOT_WINDOW_DAYS`] days.
+    pub recent_commits: usize,
+}
+
+impl WorkItem {
+    fn new(path: PathBuf, kind: WorkKind) -> Self {
+        Self {
+            path,
+            kind,
+            score: 0,
+            check_errors: 0,
+            check_warnings: 0,
+            verify_failures: 0,
+            commits_behind: 0,
+            recent_commits: 0,
+        }
+    }
+
+    fn score(&mut self) {
+        let points = |count: usize, weight: u32| {
+            u32::try_from(count)
+                .unwrap_or(u32::MAX)
+                .saturating_mul(weight)
+        };
+        self.score = points(self.check_errors, CHECK_ERROR_WEIGHT)
+            + points(self.check_warnings, CHECK_WARNING_WEIGHT)
+            + points(self.verify_failures, VERIFY_FAILURE_WEIGHT)
+            + points(self.commits_behind, STALE_COMMIT_WEIGHT).min(STALE_MAX)
+            + points(self.recent_commits, HOT_COMMIT_WEIGHT);
+    }
+
+    /// Why the item is on the list, e.g. "2 check errors, 3 verify failures".
+    pub fn reasons(&self) -> String {
+        let plural = |count: usize, what: &str| {
+            format!("{} {}{}", count, what, if count == 1 { "" } else { "s" })
+        };
+        let mut reasons = Vec::new();
+        if self.check_errors > 0 {
+            reasons.push(plural(self.check_errors, "check error"));
+        }
+        if self.verify_failures > 0 {
+            reasons.push(plural(self.verify_failures, "verify failure"));
+        }
+        if self.commits_behind > 0 {
+            reasons.push(format!(
+                "{} behind its code",
+                plural(self.commits_behind, "commit")
+            ));
+        }
+        if self.recent_commits > 0 {
+            reasons.push(format!(
+                "undocumented, {} in {} days",
+                plural(self.recent_commits, "commit"),
+                HOT_WINDOW_DAYS
+            ));
+        }
+        if self.check_warnings > 0 {
+            reasons.push(plural(self.check_warnings, "warning"));
+        }
+        reasons.join(", ")
+    }
+}
+
+/// Check results of a doc, relative to the config directory.
+pub(crate) struct DocIssues {
+    pub(crate) path: PathBuf,
+    pub(crate) errors: usize,
+    pub(crate) warnings: usize,
+}
+
+/// Rank the docs and hot uncovered code files by risk, keeping the `limit`
+/// highest. `verify_report` defaults to the report `pave verify` writes for
+/// `[verify] artifact_cmd`, and is skipped if missing.
+pub(crate) fn build_worklist(
+    config: &PaveConfig,
+    config_dir: &Path,
+    docs: &[DocIssues],
+    verify_report: Option<&Path>,
+    limit: usize,
+) -> Result<Vec<WorkItem>> {
+    let spec = |path: &Path| display_path(path, PathStyle::Workspace, config_dir, config_dir);
+    let mut items: HashMap<PathBuf, WorkItem> = HashMap::new();
+    for doc in docs {
+        let item = items
+            .entry(doc.path.clone())
+            .or_insert_with(|| WorkItem::new(doc.path.clone(), WorkKind::Doc));
+        item.check_errors = doc.errors;
+        item.check_warnings = doc.warnings;
+    }
+
+    let default_report = config_dir.join(ARTIFACT_REPORT_PATH);
+    let report_path = verify_report.unwrap_or(&default_report);
+    if verify_report.is_some() || report_path.exists() {
+        let report = load_report(report_path)?;
+        for doc in &report.documents {
+            let failures = doc
+                .commands
+                .iter()
+                .chain(&doc.setup)
+                .filter(|command| {
+                    matches!(command.status, VerifyStatus::Fail | VerifyStatus::Timeout)
+                })
+                .count();
+            if failures > 0 {
+                let path = spec(&config_dir.join(&doc.file));
+                items
+                    .entry(path.clone())
+                    .or_insert_with(|| WorkItem::new(path, WorkKind::Doc))
+                    .verify_failures = failures;
+            }
+        }
+    }
+
+    // Git history is optional: without it only check and verify count
+    let docs_root = config_dir.join(&config.docs.root);
+    let mappings = load_doc_mappings(&docs_root, config_dir, &load_ignore(config_dir, config)?)?;
+    if last_commit(config_dir, &["."]).is_some() {
+        for mapping in &mappings {
+            let path = spec(&mapping.doc);
+            let code_specs: Vec<String> = mapping
+                .patterns
+                .iter()
+                .map(|pattern| format!(":(glob){}", pattern))
+                .collect();
+            let Some(doc_commit) = last_commit(config_dir, &[path.to_string_lossy()]) else {
+                continue;
+            };
+            let behind = commits_since(config_dir, &doc_commit.hash, &code_specs);
+            if behind > 0 {
+                items
+                    .entry(path.clone())
+                    .or_insert_with(|| WorkItem::new(path, WorkKind::Doc))
+                    .commits_behind = behind;
+            }
+        }
+
+        let patterns: Vec<&str> = mappings
+            .iter()
+            .flat_map(|mapping| mapping.patterns.iter().map(String::as_str))
+            .collect();
+        for (path, commits) in recent_commit_counts(config_dir) {
+            let hot = commits >= HOT_MIN_COMMITS
+                && is_code_file(&path)
+                && config_dir.join(&path).is_file()
+                && !matches_any_pattern(&path, &config.mapping.exclude)
+                && !matches_any_pattern(&path, &patterns);
+            if hot {
+                let mut item = WorkItem::new(path.clone(), WorkKind::Code);
+                item.recent_commits = commits;
+                items.insert(path, item);
+            }
+        }
+    }
+
+    let mut worklist: Vec<WorkItem> = items
+        .into_values()
+        .map(|mut item| {
+            item.score();
+            item
+        })
+        .filter(|item| item.score > 0)
+        .collect();
+    worklist.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    worklist.truncate(limit);
+    Ok(worklist)
+}
+
+/// Commits per file in the last [`HOT_WINDOW_DAYS`] days, with paths
+/// relative to `config_dir`. Empty if git fails.
+fn recent_commit_counts(config_dir: &Path) -> HashMap<PathBuf, usize> {
+    let since = format!("--since={}.days", HOT_WINDOW_DAYS);
+    let output = Command::new("git")
+        .args(["log", &since, "--relative", "--name-only", "--format="])
+        .current_dir(config_dir)
+        .output();
+    let mut counts = HashMap::new();
+    match output {
+        Ok(output) if output.status.success() => {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                if !line.is_empty() {
+                    *counts.entry(PathBuf::from(line)).or_default() += 1;
+                }
+            }
+        }
+        _ => warn!("Failed to read git history; hot paths are not ranked"),
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    fn commit(dir: &Path, path: &str, content: &str) {
+        let file = dir.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, content).unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", path]);
+    }
+
+    #[test]
+    fn ranks_docs_and_hot_uncovered_code_by_risk() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        commit(dir, "docs/auth.md", "# Auth\n\n## Paths\n\n- src/auth/**\n");
+        commit(dir, "docs/guide.md", "# Guide\n");
+        for n in 0..4 {
+            commit(dir, "src/auth/token.rs", &format!("// {}\n", n));
+            commit(dir, "src/billing.rs", &format!("// {}\n", n));
+        }
+        commit(dir, "src/once.rs", "\n");
+
+        fs::create_dir_all(dir.join(".pave")).unwrap();
+        fs::write(
+            dir.join(ARTIFACT_REPORT_PATH),
+            r#"{"schema_version": 1, "documents_verified": 1, "commands_executed": 2,
+                "commands_passed": 1, "commands_warned": 0, "commands_failed": 1,
+                "commands_cached": 0, "fixtures_failed": 0, "documents": [
+                {"file": "docs/guide.md", "section_line": 3, "status": "fail", "commands": [
+                    {"command": "make", "status": "fail", "expected_exit_code": 0},
+                    {"command": "true", "status": "pass", "expected_exit_code": 0}]}]}"#,
+        )
+        .unwrap();
+
+        let docs = [
+            DocIssues {
+                path: PathBuf::from("docs/auth.md"),
+                errors: 0,
+                warnings: 1,
+            },
+            DocIssues {
+                path: PathBuf::from("docs/guide.md"),
+                errors: 1,
+                warnings: 0,
+            },
+        ];
+        let worklist = build_worklist(&PaveConfig::default(), dir, &docs, None, 10).unwrap();
+        let ranked: Vec<(&str, WorkKind, u32)> = worklist
+            .iter()
+            .map(|item| (item.path.to_str().unwrap(), item.kind, item.score))
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                ("docs/guide.md", WorkKind::Doc, 25),
+                ("docs/auth.md", WorkKind::Doc, 9),
+                ("src/billing.rs", WorkKind::Code, 8),
+            ]
+        );
+        assert_eq!(
+            worklist[1].reasons(),
+            "4 commits behind its code, 1 warning"
+        );
+        assert_eq!(
+            worklist[2].reasons(),
+            format!("undocumented, 4 commits in {} days", HOT_WINDOW_DAYS)
+        );
+
+        let top = build_worklist(&PaveConfig::default(), dir, &docs, None, 1).unwrap();
+        assert_eq!(top.len(), 1);
+    }
+}
@@ -0,0 +1,467 @@
+//! Implementation of the `pave summary` command.
+//!
+//! Produces a pull-request comment for CI bots to post: the docs added,
+//! changed, or removed since a base ref, the `pave check` result of each,
+//! their status in the last `pave verify` report, and how coverage moved.
+//! Issue details are folded into `<details>` blocks to keep the comment
+//! short.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::SummaryOutputFormat;
+use crate::commands::check::{CheckResults, Issue, Severity, check_file, should_skip_file};
+use crate::commands::coverage;
+use crate::commands::report_html::load_verify_statuses;
+use crate::commands::verify::ARTIFACT_REPORT_PATH;
+use crate::config::PaveConfig;
+use crate::git::determine_base_ref;
+use crate::parser::ParsedDoc;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::load_ignore;
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave summary` command.
+pub struct SummaryArgs {
+    /// Git ref to compare against.
+    pub base: Option<String>,
+    /// Output format.
+    pub format: SummaryOutputFormat,
+    /// JSON report from `pave verify --report` to show results from.
+    pub verify_report: Option<PathBuf>,
+}
+
+/// How a document changed since the base ref.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DocChange {
+    Added,
+    Modified,
+    Renamed,
+    Deleted,
+}
+
+impl DocChange {
+    fn label(self) -> &'static str {
+        match self {
+            DocChange::Added => "added",
+            DocChange::Modified => "modified",
+            DocChange::Renamed => "renamed",
+            DocChange::Deleted => "deleted",
+        }
+    }
+}
+
+/// A document changed since the base ref.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ChangedDoc {
+    /// Path relative to the project root.
+    pub file: PathBuf,
+    /// How the document changed.
+    pub change: DocChange,
+    /// Previous path, for renamed documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_file: Option<PathBuf>,
+    /// H1 title, unless the document was deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `pave check` result: `pass`, `warn`, or `fail`. Absent for deleted
+    /// documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<String>,
+    /// Errors from `pave check`.
+    pub errors: Vec<Issue>,
+    /// Warnings from `pave check`.
+    pub warnings: Vec<Issue>,
+    /// Status from the verify report (`pass`, `warn`, `fail`, ...), if the
+    /// document was verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify: Option<String>,
+}
+
+/// Coverage at the base ref and now.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CoverageDelta {
+    /// Coverage percentage at the base ref.
+    pub base_percentage: f64,
+    /// Coverage percentage of the working tree.
+    pub head_percentage: f64,
+    /// Change in percentage points.
+    pub delta: f64,
+    /// Total number of code files now.
+    pub total_files: usize,
+    /// Number of uncovered code files now.
+    pub uncovered_files: usize,
+}
+
+/// Results of the summary command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SummaryResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// The ref changes are compared against.
+    pub base: String,
+    /// Changed documents, by path.
+    pub docs: Vec<ChangedDoc>,
+    /// Coverage change, if coverage could be measured at the base ref.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageDelta>,
+    /// Whether verify statuses came from a report.
+    pub verified: bool,
+}
+
+/// Execute the `pave summary` command.
+pub fn execute(args: SummaryArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let base = determine_base_ref(config_dir, args.base.as_deref())?;
+    // Compare from where the branch forked, so commits on the base since
+    // then don't show up as changes here
+    let fork_point = merge_base(&base, config_dir).unwrap_or_else(|| base.clone());
+
+    let verify_statuses = match &args.verify_report {
+        Some(path) => Some(load_verify_statuses(&workdir.resolve(path), config_dir)?),
+        None => {
+            let default = config_dir.join(ARTIFACT_REPORT_PATH);
+            if default.is_file() {
+                Some(load_verify_statuses(&default, config_dir)?)
+            } else {
+                None
+            }
+        }
+    };
+
+    let ignore = load_ignore(config_dir, &config)?;
+    let mut docs = Vec::new();
+    for (file, change, previous_file) in changed_files(&fork_point, config_dir)? {
+        let path = config_dir.join(&file);
+        if !file.starts_with(&config.docs.root)
+            || file.extension().is_none_or(|ext| ext != "md")
+            || should_skip_file(&path)
+            || ignore.is_ignored(&path)
+        {
+            continue;
+        }
+
+        let mut doc = ChangedDoc {
+            file,
+            change,
+            previous_file,
+            title: None,
+            check: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            verify: None,
+        };
+        if change != DocChange::Deleted {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read file: {}", path.display()))?;
+            doc.title = ParsedDoc::parse_content(path.clone(), &content)?.title;
+
+            let mut results = CheckResults::new();
+            check_file(&path, &config, &mut results)?;
+            doc.check = Some(check_status(&results).to_string());
+            doc.errors = results.errors;
+            doc.warnings = results.warnings;
+            doc.verify = verify_statuses
+                .as_ref()
+                .and_then(|statuses| statuses.get(&doc.file).cloned());
+        }
+        docs.push(doc);
+    }
+    docs.sort_by(|a, b| a.file.cmp(&b.file));
+
+    let coverage = match coverage::take_snapshot_at(&config, config_dir, &fork_point) {
+        Ok(before) => {
+            let after = coverage::take_snapshot(&config, config_dir)?;
+            Some(CoverageDelta {
+                base_percentage: before.coverage_percentage,
+                head_percentage: after.coverage_percentage,
+                delta: after.coverage_percentage - before.coverage_percentage,
+                total_files: after.total_files,
+                uncovered_files: after.uncovered_files,
+            })
+        }
+        Err(e) => {
+            eprintln!("Skipping coverage: {:#}", e);
+            None
+        }
+    };
+
+    let results = SummaryResults {
+        schema_version: SCHEMA_VERSION,
+        base,
+        docs,
+        coverage,
+        verified: verify_statuses.is_some(),
+    };
+    match args.format {
+        SummaryOutputFormat::Markdown => print!("{}", render_markdown(&results)),
+        SummaryOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// The commit where HEAD forked from `base`, if git can find one.
+fn merge_base(base: &str, config_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["merge-base", base, "HEAD"])
+        .current_dir(config_dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Files changed between `base` and the working tree, relative to
+/// `config_dir`.
+fn changed_files(
+    base: &str,
+    config_dir: &Path,
+) -> Result<Vec<(PathBuf, DocChange, Option<PathBuf>)>> {
+    let output = Command::new("git")
+        .args(["diff", "--name-status", "-z", "-M", "--relative", base])
+        .current_dir(config_dir)
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_name_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git diff --name-status -z` output. Copies count as additions.
+fn parse_name_status(output: &str) -> Vec<(PathBuf, DocChange, Option<PathBuf>)> {
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    let mut files = Vec::new();
+    while let Some(status) = fields.next() {
+        let Some(path) = fields.next() else {
+            break;
+        };
+        let entry = match status.chars().next() {
+            Some('A') => (PathBuf::from(path), DocChange::Added, None),
+            Some('D') => (PathBuf::from(path), DocChange::Deleted, None),
+            Some('R') => match fields.next() {
+                Some(new) => (
+                    PathBuf::from(new),
+                    DocChange::Renamed,
+                    Some(PathBuf::from(path)),
+                ),
+                None => break,
+            },
+            Some('C') => match fields.next() {
+                Some(new) => (PathBuf::from(new), DocChange::Added, None),
+                None => break,
+            },
+            _ => (PathBuf::from(path), DocChange::Modified, None),
+        };
+        files.push(entry);
+    }
+    files
+}
+
+fn check_status(results: &CheckResults) -> &'static str {
+    if !results.errors.is_empty() {
+        "fail"
+    } else if !results.warnings.is_empty() {
+        "warn"
+    } else {
+        "pass"
+    }
+}
+
+/// Render the summary as a GitHub-flavored markdown comment.
+fn render_markdown(results: &SummaryResults) -> String {
+    let mut out = String::new();
+    out.push_str("## Documentation summary\n\n");
+
+    let count = |change: DocChange| results.docs.iter().filter(|d| d.change == change).count();
+    if results.docs.is_empty() {
+        out.push_str(&format!("No docs changed since `{}`.\n", results.base));
+    } else {
+        out.push_str(&format!(
+            "Since `{}`: {} added, {} modified, {} renamed, {} deleted.\n\n",
+            results.base,
+            count(DocChange::Added),
+            count(DocChange::Modified),
+            count(DocChange::Renamed),
+            count(DocChange::Deleted)
+        ));
+
+        out.push_str("| Document | Change | Check | Verify |\n");
+        out.push_str("|----------|--------|-------|--------|\n");
+        for doc in &results.docs {
+            let name = match &doc.title {
+                Some(title) => format!("{} (`{}`)", escape_cell(title), doc.file.display()),
+                None => format!("`{}`", doc.file.display()),
+            };
+            let change = match &doc.previous_file {
+                Some(previous) => format!("renamed from `{}`", previous.display()),
+                None => doc.change.label().to_string(),
+            };
+            let verify = match (&doc.verify, doc.change) {
+                (_, DocChange::Deleted) => "-",
+                (Some(status), _) => status.as_str(),
+                (None, _) if results.verified => "not verified",
+                (None, _) => "not run",
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                name,
+                change,
+                doc.check.as_deref().unwrap_or("-"),
+                verify
+            ));
+        }
+    }
+
+    if let Some(coverage) = &results.coverage {
+        out.push_str(&format!(
+            "\n**Coverage:** {:.1}% ({:+.1} points from {:.1}%), {} of {} code files uncovered.\n",
+            coverage.head_percentage,
+            coverage.delta,
+            coverage.base_percentage,
+            coverage.uncovered_files,
+            coverage.total_files
+        ));
+    }
+
+    let with_issues: Vec<&ChangedDoc> = results
+        .docs
+        .iter()
+        .filter(|d| !d.errors.is_empty() || !d.warnings.is_empty())
+        .collect();
+    if !with_issues.is_empty() {
+        let issues: usize = with_issues
+            .iter()
+            .map(|d| d.errors.len() + d.warnings.len())
+            .sum();
+        out.push_str(&format!(
+            "\n<details>\n<summary>Check issues ({})</summary>\n\n",
+            issues
+        ));
+        for doc in with_issues {
+            out.push_str(&format!("**`{}`**\n\n", doc.file.display()));
+            for issue in doc.errors.iter().chain(&doc.warnings) {
+                let severity = match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                out.push_str(&format!(
+                    "- {} line {}: {} (`{}`)\n",
+                    severity, issue.line, issue.message, issue.rule
+                ));
+            }
+            out.push('\n');
+        }
+        out.push_str("</details>\n");
+    }
+    out
+}
+
+/// Escape a value for a markdown table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_name_status_output() {
+        let output = "M\0docs/a.md\0A\0docs/b.md\0R087\0docs/old.md\0docs/new.md\0D\0src/x.rs\0";
+        let files = parse_name_status(output);
+        assert_eq!(
+            files,
+            vec![
+                (PathBuf::from("docs/a.md"), DocChange::Modified, None),
+                (PathBuf::from("docs/b.md"), DocChange::Added, None),
+                (
+                    PathBuf::from("docs/new.md"),
+                    DocChange::Renamed,
+                    Some(PathBuf::from("docs/old.md"))
+                ),
+                (PathBuf::from("src/x.rs"), DocChange::Deleted, None),
+            ]
+        );
+        assert!(parse_name_status("").is_empty());
+    }
+
+    #[test]
+    fn renders_markdown_comment() {
+        let issue = Issue {
+            file: PathBuf::from("docs/auth.md"),
+            line: 12,
+            severity: Severity::Error,
+            message: "Missing required section: ## Verification".to_string(),
+            hint: None,
+            converted_from_error: false,
+            rule: "required-sections".to_string(),
+            docs_url: None,
+            blame: None,
+        };
+        let results = SummaryResults {
+            schema_version: SCHEMA_VERSION,
+            base: "origin/main".to_string(),
+            docs: vec![
+                ChangedDoc {
+                    file: PathBuf::from("docs/auth.md"),
+                    change: DocChange::Added,
+                    previous_file: None,
+                    title: Some("Auth | Sessions".to_string()),
+                    check: Some("fail".to_string()),
+                    errors: vec![issue],
+                    warnings: Vec::new(),
+                    verify: None,
+                },
+                ChangedDoc {
+                    file: PathBuf::from("docs/old.md"),
+                    change: DocChange::Deleted,
+                    previous_file: None,
+                    title: None,
+                    check: None,
+                    errors: Vec::new(),
+                    warnings: Vec::new(),
+                    verify: None,
+                },
+            ],
+            coverage: Some(CoverageDelta {
+                base_percentage: 50.0,
+                head_percentage: 62.5,
+                delta: 12.5,
+                total_files: 8,
+                uncovered_files: 3,
+            }),
+            verified: false,
+        };
+
+        let markdown = render_markdown(&results);
+        assert!(
+            markdown.contains("Since `origin/main`: 1 added, 0 modified, 0 renamed, 1 deleted.")
+        );
+        assert!(
+            markdown.contains("| Auth \\| Sessions (`docs/auth.md`) | added | fail | not run |")
+        );
+        assert!(markdown.contains("| `docs/old.md` | deleted | - | - |"));
+        assert!(markdown.contains("**Coverage:** 62.5% (+12.5 points from 50.0%)"));
+        assert!(markdown.contains("<summary>Check issues (1)</summary>"));
+        assert!(markdown.contains(
+            "- error line 12: Missing required section: ## Verification (`required-sections`)"
+        ));
+    }
+}
@@ -0,0 +1,361 @@
+//! Implementation of the `pave templates` commands.
+//!
+//! `pave templates check` instantiates each document template the same way
+//! `pave new` does and validates the result with the rules `pave check`
+//! applies, so scaffolded documents pass validation out of the box.
+//! `pave templates list` shows which template each document type uses.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{OutputFormat, TemplatesListOutputFormat};
+use crate::commands::check::{CheckResults, Issue, Severity, check_content};
+use crate::commands::new::{TemplateVars, render_template};
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::templates::list_templates;
+use crate::workspace::Workdir;
+
+/// Name substituted into templates before validation.
+const SAMPLE_NAME: &str = "sample-doc";
+
+/// Validation results for a single template.
+#[derive(Debug, Serialize)]
+pub struct TemplateCheck {
+    /// Template type name (component, runbook, adr).
+    pub template: String,
+    /// Path to the template, relative to the config directory.
+    pub source: PathBuf,
+    /// Whether the built-in template was used (no project template found).
+    pub builtin: bool,
+    /// Rule violations that would fail `pave check`.
+    pub errors: Vec<Issue>,
+    /// Rule violations reported as warnings.
+    pub warnings: Vec<Issue>,
+}
+
+/// Results of checking all templates.
+#[derive(Debug, Serialize)]
+pub struct TemplatesCheckResults {
+    /// Number of templates checked.
+    pub templates_checked: usize,
+    /// Per-template results.
+    pub templates: Vec<TemplateCheck>,
+}
+
+impl TemplatesCheckResults {
+    fn error_count(&self) -> usize {
+        self.templates.iter().map(|t| t.errors.len()).sum()
+    }
+
+    fn warning_count(&self) -> usize {
+        self.templates.iter().map(|t| t.warnings.len()).sum()
+    }
+}
+
+/// Execute the `pave templates check` command.
+pub fn check(format: OutputFormat, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let results = check_templates(&config, config_dir)?;
+
+    match format {
+        OutputFormat::Text => output_text(&results),
+        OutputFormat::Json => output_json(&results)?,
+        OutputFormat::Github => output_github(&results),
+    }
+
+    let error_count = results.error_count();
+    if error_count > 0 {
+        anyhow::bail!(Violations(format!(
+            "Template check failed: {} error{}",
+            error_count,
+            if error_count == 1 { "" } else { "s" }
+        )));
+    }
+
+    Ok(())
+}
+
+/// Instantiate and validate every template, including the project's extra
+/// document types.
+fn check_templates(config: &PaveConfig, config_dir: &Path) -> Result<TemplatesCheckResults> {
+    let mut templates = Vec::new();
+
+    for template in list_templates(config, config_dir)? {
+        let rendered = render_template(
+            &template.content,
+            SAMPLE_NAME,
+            template.builtin_type,
+            &TemplateVars::default(),
+        )?;
+        let builtin = template.source.is_none();
+        let source = template
+            .source
+            .unwrap_or_else(|| Path::new("templates").join(format!("{}.md", template.name)));
+
+        let mut results = CheckResults::new();
+        check_content(&source, &rendered, config, &mut results)?;
+
+        templates.push(TemplateCheck {
+            template: template.name,
+            source,
+            builtin,
+            errors: results.errors,
+            warnings: results.warnings,
+        });
+    }
+
+    Ok(TemplatesCheckResults {
+        templates_checked: templates.len(),
+        templates,
+    })
+}
+
+/// A template listed by `pave templates list`.
+#[derive(Debug, Serialize)]
+pub struct TemplateListing {
+    /// Document type name, as passed to `pave new` and `pave prompt`.
+    pub name: String,
+    /// Path to the template, relative to the config directory. Absent for
+    /// built-in templates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    /// Whether the built-in template is used.
+    pub builtin: bool,
+}
+
+/// Execute the `pave templates list` command.
+pub fn list(format: TemplatesListOutputFormat, workdir: &Workdir) -> Result<()> {
+    let (config, config_dir) = match workdir.locate_config()? {
+        Some(path) => {
+            let config = PaveConfig::load(&path)?;
+            let dir = path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf();
+            (config, dir)
+        }
+        None => (PaveConfig::default(), workdir.dir.clone()),
+    };
+
+    let listings: Vec<TemplateListing> = list_templates(&config, &config_dir)?
+        .into_iter()
+        .map(|template| TemplateListing {
+            builtin: template.source.is_none(),
+            name: template.name,
+            source: template.source,
+        })
+        .collect();
+
+    match format {
+        TemplatesListOutputFormat::Text => {
+            for listing in &listings {
+                match &listing.source {
+                    Some(source) => println!("{:<12} {}", listing.name, source.display()),
+                    None => println!("{:<12} (built-in)", listing.name),
+                }
+            }
+        }
+        TemplatesListOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&listings).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Output results in text format.
+fn output_text(results: &TemplatesCheckResults) {
+    for template in &results.templates {
+        let status = if !template.errors.is_empty() {
+            "✗"
+        } else if !template.warnings.is_empty() {
+            "!"
+        } else {
+            "✓"
+        };
+        let origin = if template.builtin { " (built-in)" } else { "" };
+        println!(
+            "{} {:<10} {}{}",
+            status,
+            template.template,
+            template.source.display(),
+            origin
+        );
+
+        for issue in template.errors.iter().chain(template.warnings.iter()) {
+            let severity = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            println!("    line {}: {}: {}", issue.line, severity, issue.message);
+            if let Some(hint) = &issue.hint {
+                println!("      hint: {}", hint);
+            }
+        }
+    }
+
+    println!();
+    let error_count = results.error_count();
+    let warning_count = results.warning_count();
+    print!(
+        "Checked {} template{}: ",
+        results.templates_checked,
+        if results.templates_checked == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+    if error_count == 0 && warning_count == 0 {
+        println!("all templates produce passing documents");
+    } else {
+        println!(
+            "{} error{}, {} warning{}",
+            error_count,
+            if error_count == 1 { "" } else { "s" },
+            warning_count,
+            if warning_count == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Output results in JSON format.
+fn output_json(results: &TemplatesCheckResults) -> Result<()> {
+    let json = serde_json::to_string_pretty(results).context("Failed to serialize results")?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Output results in GitHub Actions annotation format.
+fn output_github(results: &TemplatesCheckResults) {
+    for template in &results.templates {
+        for issue in template.errors.iter().chain(template.warnings.iter()) {
+            let level = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            println!(
+                "::{} file={},line={}::{} template: {}",
+                level,
+                issue.file.display(),
+                issue.line,
+                template.template,
+                issue.message
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CONFIG_FILENAME;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn load_config(temp_dir: &TempDir, extra: &str) -> PaveConfig {
+        let content = format!(
+            r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+templates = "docs/templates"
+{}
+"#,
+            extra
+        );
+        let path = temp_dir.path().join(CONFIG_FILENAME);
+        fs::write(&path, content).unwrap();
+        PaveConfig::load(&path).unwrap()
+    }
+
+    #[test]
+    fn builtin_templates_pass_default_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load_config(&temp_dir, "");
+
+        let results = check_templates(&config, temp_dir.path()).unwrap();
+
+        assert_eq!(results.templates_checked, 5);
+        assert!(results.templates.iter().all(|t| t.builtin));
+        assert_eq!(results.error_count(), 0);
+
+        // Each template also has the sections its type-specific rules require
+        let config = load_config(
+            &temp_dir,
+            "[rules.type_specific]\nrunbooks = true\nadrs = true\ncomponents = true\n\
+             postmortems = true\napis = true\n",
+        );
+        let results = check_templates(&config, temp_dir.path()).unwrap();
+        assert_eq!(results.error_count(), 0);
+    }
+
+    #[test]
+    fn project_template_missing_required_section_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("docs/templates");
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(
+            templates_dir.join("component.md"),
+            "# {Component Name}\n\n## Purpose\nWhat it does.\n\n## Examples\n```bash\nrun\n```\n",
+        )
+        .unwrap();
+        let config = load_config(&temp_dir, "");
+
+        let results = check_templates(&config, temp_dir.path()).unwrap();
+
+        let component = &results.templates[0];
+        assert!(!component.builtin);
+        assert_eq!(
+            component.source,
+            PathBuf::from("docs/templates/component.md")
+        );
+        assert_eq!(component.errors.len(), 1);
+        assert!(component.errors[0].message.contains("Verification"));
+    }
+
+    #[test]
+    fn extra_project_templates_are_checked() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("tmpl");
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(
+            templates_dir.join("rfc.md"),
+            "# RFC: {{title}}\n\n## Verification\n```bash\nrun\n```\n\n## Examples\n```bash\nrun\n```\n",
+        )
+        .unwrap();
+        let config = load_config(&temp_dir, "[templates]\ndir = \"tmpl\"\n");
+
+        let results = check_templates(&config, temp_dir.path()).unwrap();
+
+        assert_eq!(results.templates_checked, 6);
+        let rfc = &results.templates[5];
+        assert_eq!(rfc.template, "rfc");
+        assert_eq!(rfc.source, PathBuf::from("tmpl/rfc.md"));
+        assert!(!rfc.builtin);
+        assert_eq!(rfc.errors.len(), 0);
+    }
+
+    #[test]
+    fn configured_template_filename_is_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("docs/templates");
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(templates_dir.join("service.md"), "# {Component Name}\n").unwrap();
+        let config = load_config(&temp_dir, "[templates]\ncomponent = \"service.md\"\n");
+
+        let results = check_templates(&config, temp_dir.path()).unwrap();
+
+        let component = &results.templates[0];
+        assert_eq!(component.source, PathBuf::from("docs/templates/service.md"));
+        assert_eq!(component.errors.len(), 2);
+    }
+}
@@ -0,0 +1,336 @@
+//! Implementation of the `pave translate-status` command.
+//!
+//! For every source doc in a `[docs.locales]` doc set, `pave translate-status`
+//! looks up each translation and compares the last commit touching it with
+//! the last commit touching its source. A translation committed before its
+//! source last changed is outdated, and the report counts the source commits
+//! it hasn't caught up with. Files without commits can't be compared and are
+//! reported as untracked.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{OutputFormat, PathStyle};
+use crate::commands::stale::{commit_date, commits_since, last_commit};
+use crate::config::PaveConfig;
+use crate::exit::Violations;
+use crate::locales::{self, locale_dir};
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::{find_docs, load_ignore};
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave translate-status` command.
+pub struct TranslateStatusArgs {
+    /// Output format.
+    pub format: OutputFormat,
+    /// Fail if a required translation is missing or any is outdated.
+    pub strict: bool,
+}
+
+/// How a translation compares with its source.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationState {
+    /// Committed after the source last changed.
+    Current,
+    /// Committed before the source last changed.
+    Outdated,
+    /// The translation doesn't exist.
+    Missing,
+    /// The translation or its source has no commits.
+    Untracked,
+}
+
+/// One translation of a source doc.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct Translation {
+    /// Path to the source doc.
+    pub source: PathBuf,
+    /// Locale of the translation.
+    pub locale: String,
+    /// Path where the translation belongs.
+    pub file: PathBuf,
+    /// Whether `[docs.locales] required` asks for this translation.
+    pub required: bool,
+    /// How the translation compares with its source.
+    pub state: TranslationState,
+    /// Date of the source's last commit (YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_updated: Option<String>,
+    /// Date of the translation's last commit (YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation_updated: Option<String>,
+    /// Commits touching the source since the translation's last commit.
+    pub source_commits: usize,
+}
+
+/// Results of the translate-status command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslateStatusResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// Locale the docs are written in.
+    pub source_locale: String,
+    /// Number of source docs.
+    pub docs_checked: usize,
+    /// Every translation of every source doc, by source path and locale.
+    pub translations: Vec<Translation>,
+}
+
+impl TranslateStatusResults {
+    /// Translations that need work: required but missing, or outdated.
+    fn behind(&self) -> impl Iterator<Item = &Translation> {
+        self.translations.iter().filter(|translation| {
+            translation.state == TranslationState::Outdated
+                || (translation.state == TranslationState::Missing && translation.required)
+        })
+    }
+}
+
+/// Execute the `pave translate-status` command.
+pub fn execute(args: TranslateStatusArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let results = translation_status(&config, config_dir)?;
+
+    match args.format {
+        OutputFormat::Text => output_text(&results),
+        OutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+        OutputFormat::Github => output_github(&results),
+    }
+
+    let behind = results.behind().count();
+    if args.strict && behind > 0 {
+        anyhow::bail!(Violations(format!(
+            "{} translation{} missing or outdated",
+            behind,
+            if behind == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
+/// Compare every translation in the project's doc set with its source.
+pub(crate) fn translation_status(
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<TranslateStatusResults> {
+    let locales = config
+        .docs
+        .locales
+        .as_ref()
+        .context("pave translate-status needs a [docs.locales] section in .pave.toml")?;
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, config)?;
+    let sources = find_docs(&locale_dir(&docs_root, &locales.source), &ignore)?;
+
+    let spec = |path: &Path| {
+        display_path(path, PathStyle::Workspace, config_dir, config_dir)
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut results = TranslateStatusResults {
+        schema_version: SCHEMA_VERSION,
+        source_locale: locales.source.clone(),
+        docs_checked: sources.len(),
+        translations: Vec::new(),
+    };
+    for source in &sources {
+        let source_spec = spec(source);
+        let source_commit = last_commit(config_dir, &[&source_spec]);
+        for counterpart in locales::counterparts(source, &docs_root, locales) {
+            let file_spec = spec(&counterpart.path);
+            let translation_commit = counterpart
+                .path
+                .exists()
+                .then(|| last_commit(config_dir, &[&file_spec]))
+                .flatten();
+            let (state, source_commits) = if !counterpart.path.exists() {
+                (TranslationState::Missing, 0)
+            } else {
+                match (&source_commit, &translation_commit) {
+                    (Some(source), Some(translation)) if source.time > translation.time => (
+                        TranslationState::Outdated,
+                        commits_since(config_dir, &translation.hash, &[&source_spec]),
+                    ),
+                    (Some(_), Some(_)) => (TranslationState::Current, 0),
+                    _ => (TranslationState::Untracked, 0),
+                }
+            };
+            results.translations.push(Translation {
+                source: PathBuf::from(&source_spec),
+                locale: counterpart.locale,
+                file: PathBuf::from(file_spec),
+                required: counterpart.required,
+                state,
+                source_updated: source_commit
+                    .as_ref()
+                    .map(|commit| commit_date(commit.time)),
+                translation_updated: translation_commit.map(|commit| commit_date(commit.time)),
+                source_commits,
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn output_text(results: &TranslateStatusResults) {
+    let count = |state| {
+        results
+            .translations
+            .iter()
+            .filter(|translation| translation.state == state)
+            .count()
+    };
+    println!(
+        "{} source docs ({}): {} current, {} outdated, {} missing, {} untracked translations",
+        results.docs_checked,
+        results.source_locale,
+        count(TranslationState::Current),
+        count(TranslationState::Outdated),
+        count(TranslationState::Missing),
+        count(TranslationState::Untracked)
+    );
+    for translation in &results.translations {
+        match translation.state {
+            TranslationState::Outdated => println!(
+                "  outdated  {}: {} source commit{} since {} (source last changed {})",
+                translation.file.display(),
+                translation.source_commits,
+                if translation.source_commits == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                translation.translation_updated.as_deref().unwrap_or("-"),
+                translation.source_updated.as_deref().unwrap_or("-")
+            ),
+            TranslationState::Missing => println!(
+                "  missing   {}{}",
+                translation.file.display(),
+                if translation.required {
+                    " (required)"
+                } else {
+                    ""
+                }
+            ),
+            TranslationState::Current | TranslationState::Untracked => {}
+        }
+    }
+}
+
+fn output_github(results: &TranslateStatusResults) {
+    for translation in results.behind() {
+        let message = match translation.state {
+            TranslationState::Outdated => format!(
+                "'{}' translation is {} source commits behind {}",
+                translation.locale,
+                translation.source_commits,
+                translation.source.display()
+            ),
+            _ => format!(
+                "Missing '{}' translation {}",
+                translation.locale,
+                translation.file.display()
+            ),
+        };
+        println!(
+            "::warning file={},line=1::{}",
+            translation.source.display(),
+            message
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LocalesSection;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn commit(dir: &Path, path: &str, date: &str) {
+        let file = dir.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        let previous = fs::read_to_string(&file).unwrap_or_default();
+        fs::write(&file, format!("{}{}\n", previous, date)).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            path,
+        ]);
+    }
+
+    #[test]
+    fn reports_outdated_and_missing_translations() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let mut config = PaveConfig::default();
+        assert!(translation_status(&config, dir).is_err());
+        config.docs.locales = Some(LocalesSection {
+            source: "en".to_string(),
+            translations: vec!["ja".to_string()],
+            required: vec!["runbooks/**".to_string()],
+        });
+
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        commit(dir, "docs/en/guide.md", "2025-01-01T12:00:00Z");
+        commit(dir, "docs/en/runbooks/deploy.md", "2025-01-01T12:00:00Z");
+        commit(dir, "docs/ja/guide.md", "2025-01-05T12:00:00Z");
+        commit(dir, "docs/ja/runbooks/deploy.md", "2025-01-05T12:00:00Z");
+        commit(dir, "docs/en/runbooks/deploy.md", "2025-02-01T12:00:00Z");
+        commit(dir, "docs/en/runbooks/deploy.md", "2025-02-02T12:00:00Z");
+        commit(dir, "docs/en/runbooks/rollback.md", "2025-02-03T12:00:00Z");
+
+        let results = translation_status(&config, dir).unwrap();
+        assert_eq!(results.docs_checked, 3);
+        let states: Vec<(&str, TranslationState, usize)> = results
+            .translations
+            .iter()
+            .map(|t| (t.file.to_str().unwrap(), t.state, t.source_commits))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("docs/ja/guide.md", TranslationState::Current, 0),
+                ("docs/ja/runbooks/deploy.md", TranslationState::Outdated, 2),
+                ("docs/ja/runbooks/rollback.md", TranslationState::Missing, 0),
+            ]
+        );
+        assert_eq!(
+            results.translations[1].source_updated.as_deref(),
+            Some("2025-02-02")
+        );
+        assert_eq!(results.behind().count(), 2);
+    }
+}
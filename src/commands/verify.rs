@@ -1,22 +1,59 @@
 //! Implementation of the `pave verify` command for running verification commands.
 
 use anyhow::{Context, Result};
+use glob::Pattern;
 use regex::Regex;
-use serde::Serialize;
-use std::env;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::LazyCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::Duration;
-
-use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig, RulesSection};
-use crate::parser::ParsedDoc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::audit::{AUDIT_LOG_PATH, AuditEntry, AuditLog, hash_output};
+use crate::cargo;
+use crate::cli::{OutputFormat, PathStyle};
+use crate::color;
+use crate::compose;
+use crate::config::{PaveConfig, RuleSeverity, RulesSection, VerifySection};
+use crate::container;
+use crate::exit::Violations;
+use crate::git::{changed_files, determine_base_ref};
+use crate::parser::{DocState, ParsedDoc};
+use crate::paths::{display_path, relative_path};
+use crate::progress::Progress;
+use crate::redact::Redactor;
+use crate::remote;
+use crate::schema::SCHEMA_VERSION;
+use crate::secrets::{SECRETS_FILE, Secrets};
+use crate::services::{self, Health, Service, ServicePlan};
+use crate::text_diff::{diff_lines, render_unified};
+use crate::vars;
 use crate::verification::{
-    OutputMatcher, VerificationItem, VerificationSpec, extract_verification_spec,
+    ComposeFixture, Invocation, OutputMatcher, VerificationItem, VerificationSpec, build_command,
+    check_shell_syntax, command_outcome, extract_verification_spec, find_suspicious_construct,
+    shell_command, spawn_with_input,
+};
+use crate::verify_cache::{
+    CACHE_PATH, CacheEntry, VerifyCache, cache_key, hash_path_inputs, project_files,
 };
+use crate::verify_export::{ExportDoc, quote, render_makefile, render_script};
+use crate::verify_update;
+use crate::walk::{find_markdown_files, load_ignore};
+use crate::workspace::{self, Merge, Outcome, PackageCommand, Workdir, prefixed};
+
+/// Unchanged lines shown around each change in output mismatch diffs.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Report written for `[verify] artifact_cmd` when `--report` isn't given.
+pub(crate) const ARTIFACT_REPORT_PATH: &str = ".pave/verify-report.json";
 
 /// Arguments for the `pave verify` command.
+#[derive(Clone)]
 pub struct VerifyArgs {
     /// Specific files or directories to verify.
     pub paths: Vec<PathBuf>,
@@ -28,10 +65,30 @@ pub struct VerifyArgs {
     pub timeout: u32,
     /// Continue running after first failure.
     pub keep_going: bool,
+    /// How to display file paths in output.
+    pub path_style: PathStyle,
+    /// Re-run every command, ignoring cached results.
+    pub no_cache: bool,
+    /// Don't start `pave.services` or compose services; assume they're running.
+    pub no_services: bool,
+    /// Only verify docs affected by changes since base ref.
+    pub changed: bool,
+    /// Base ref for --changed comparison.
+    pub base: Option<String>,
+    /// Write the planned commands to this shell script instead of running them.
+    pub export_script: Option<PathBuf>,
+    /// Write the planned commands to this Makefile instead of running them.
+    pub export_make: Option<PathBuf>,
+    /// Also verify documents in the draft state.
+    pub include_drafts: bool,
+    /// Rewrite stale expected outputs with the actual output.
+    pub update_expected: bool,
+    /// Previous JSON report whose failed commands are the only ones re-run.
+    pub rerun_failed: Option<PathBuf>,
 }
 
 /// Status of a verification command execution.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum VerifyStatus {
     Pass,
@@ -39,10 +96,18 @@ pub enum VerifyStatus {
     Fail,
     Timeout,
     Skipped,
+    /// Inputs unchanged since the command last passed; not re-run.
+    Cached,
+    /// A setup command failed, so the document's commands were skipped.
+    #[serde(rename = "setup_failed")]
+    SetupFailed,
+    /// A teardown command failed after the document's commands ran.
+    #[serde(rename = "teardown_failed")]
+    TeardownFailed,
 }
 
 /// Details about an output mismatch.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OutputMismatch {
     /// The expected output pattern.
     pub expected: String,
@@ -50,10 +115,71 @@ pub struct OutputMismatch {
     pub strategy: String,
     /// The actual output received.
     pub actual: String,
+    /// The expected output split into lines, for tooling that diffs them.
+    pub expected_lines: Vec<String>,
+    /// The actual output split into lines.
+    pub actual_lines: Vec<String>,
+}
+
+impl OutputMismatch {
+    fn new(expected: String, strategy: &str, actual: String) -> Self {
+        Self {
+            expected_lines: expected.lines().map(str::to_string).collect(),
+            actual_lines: actual.lines().map(str::to_string).collect(),
+            expected,
+            strategy: strategy.to_string(),
+            actual,
+        }
+    }
+
+    /// Unified diff of expected against actual output, for literal matches.
+    ///
+    /// Returns `None` for regex and forbidden-text matches, where a line diff
+    /// doesn't explain the failure.
+    fn diff(&self, color: bool) -> Option<Vec<String>> {
+        if self.strategy != "exact" && self.strategy != "contains" {
+            return None;
+        }
+        let diff = diff_lines(self.expected.trim(), self.actual.trim());
+        Some(render_unified(&diff, DIFF_CONTEXT_LINES, color))
+    }
+
+    /// Whether the output contained text it must not contain.
+    fn is_forbidden_match(&self) -> bool {
+        self.strategy == "not_contains"
+    }
+
+    /// The first line of output containing the forbidden text.
+    fn forbidden_line(&self) -> &str {
+        let needle = self.expected.lines().next().unwrap_or("");
+        self.actual
+            .lines()
+            .find(|line| line.contains(needle))
+            .unwrap_or("")
+            .trim()
+    }
+
+    /// One-line description for annotations.
+    fn summary(&self) -> String {
+        if self.is_forbidden_match() {
+            format!(
+                "Output contains forbidden text '{}': '{}'",
+                self.expected.lines().next().unwrap_or(""),
+                self.forbidden_line()
+            )
+        } else {
+            format!(
+                "Output mismatch ({}): expected '{}', got '{}'",
+                self.strategy,
+                self.expected.lines().next().unwrap_or(""),
+                self.actual.trim().lines().next().unwrap_or("")
+            )
+        }
+    }
 }
 
 /// Result of running a single verification command.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommandResult {
     /// The command that was run.
     pub command: String,
@@ -80,12 +206,21 @@ pub struct CommandResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<PathBuf>,
     /// Environment variables set for the command.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub env_vars: Vec<(String, String)>,
+    /// Reason the command was rejected before execution (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preflight_error: Option<String>,
+    /// Container image the command ran in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// `[verify.hosts]` name of the host the command ran on, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
 }
 
 /// Result of verifying a single document.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DocumentResult {
     /// Path to the document.
     pub file: PathBuf,
@@ -93,6 +228,12 @@ pub struct DocumentResult {
     pub section_line: usize,
     /// Results for each command.
     pub commands: Vec<CommandResult>,
+    /// Results of setup commands run before the document's commands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup: Vec<CommandResult>,
+    /// Results of teardown commands run after the document's commands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teardown: Vec<CommandResult>,
     /// Overall status of the document.
     pub status: VerifyStatus,
 }
@@ -103,6 +244,8 @@ impl DocumentResult {
             file: spec.source_file.clone(),
             section_line: spec.section_line,
             commands: Vec::new(),
+            setup: Vec::new(),
+            teardown: Vec::new(),
             status: VerifyStatus::Pass,
         }
     }
@@ -111,7 +254,10 @@ impl DocumentResult {
         // Fail/Timeout always override other statuses
         // Warn only upgrades from Pass
         match result.status {
-            VerifyStatus::Fail | VerifyStatus::Timeout => {
+            VerifyStatus::Fail
+            | VerifyStatus::Timeout
+            | VerifyStatus::SetupFailed
+            | VerifyStatus::TeardownFailed => {
                 self.status = result.status;
             }
             VerifyStatus::Warn => {
@@ -119,7 +265,7 @@ impl DocumentResult {
                     self.status = VerifyStatus::Warn;
                 }
             }
-            VerifyStatus::Pass | VerifyStatus::Skipped => {}
+            VerifyStatus::Pass | VerifyStatus::Skipped | VerifyStatus::Cached => {}
         }
         self.commands.push(result);
     }
@@ -131,8 +277,10 @@ impl DocumentResult {
 }
 
 /// Aggregate results of running all verifications.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct VerifyResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
     /// Number of documents with verification sections.
     pub documents_verified: usize,
     /// Number of commands executed.
@@ -143,109 +291,207 @@ pub struct VerifyResults {
     pub commands_warned: usize,
     /// Number of commands that failed.
     pub commands_failed: usize,
+    /// Number of commands skipped because a cached pass was still valid.
+    pub commands_cached: usize,
+    /// Number of setup or teardown commands that failed.
+    pub fixtures_failed: usize,
     /// Results per document.
     pub documents: Vec<DocumentResult>,
 }
 
+impl Merge for VerifyResults {
+    fn prefix_paths(&mut self, package: &Path) {
+        for doc in &mut self.documents {
+            doc.file = prefixed(package, &doc.file);
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.documents_verified += other.documents_verified;
+        self.commands_executed += other.commands_executed;
+        self.commands_passed += other.commands_passed;
+        self.commands_warned += other.commands_warned;
+        self.commands_failed += other.commands_failed;
+        self.commands_cached += other.commands_cached;
+        self.fixtures_failed += other.fixtures_failed;
+        self.documents.extend(other.documents);
+    }
+}
+
+impl PackageCommand for VerifyArgs {
+    type Results = VerifyResults;
+
+    fn is_json(&self) -> bool {
+        matches!(self.format, OutputFormat::Json)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            ..self.clone()
+        }
+    }
+
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<VerifyResults>>> {
+        run(self, workdir)
+    }
+
+    fn output(&self, results: VerifyResults) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => output_text(&results),
+            OutputFormat::Json => output_json(&results)?,
+            OutputFormat::Github => output_github(&results),
+        }
+        Ok(())
+    }
+}
+
 impl VerifyResults {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             documents_verified: 0,
             commands_executed: 0,
             commands_passed: 0,
             commands_warned: 0,
             commands_failed: 0,
+            commands_cached: 0,
+            fixtures_failed: 0,
             documents: Vec::new(),
         }
     }
 
     fn add_document(&mut self, doc_result: DocumentResult) {
         for cmd in &doc_result.commands {
+            if cmd.status == VerifyStatus::Cached {
+                self.commands_cached += 1;
+                continue;
+            }
             self.commands_executed += 1;
             match cmd.status {
                 VerifyStatus::Pass => self.commands_passed += 1,
                 VerifyStatus::Warn => self.commands_warned += 1,
                 VerifyStatus::Fail | VerifyStatus::Timeout => self.commands_failed += 1,
-                VerifyStatus::Skipped => {}
+                VerifyStatus::Skipped
+                | VerifyStatus::Cached
+                | VerifyStatus::SetupFailed
+                | VerifyStatus::TeardownFailed => {}
             }
         }
+        self.fixtures_failed += doc_result
+            .setup
+            .iter()
+            .chain(&doc_result.teardown)
+            .filter(|cmd| !is_fixture_success(cmd))
+            .count();
         self.documents_verified += 1;
         self.documents.push(doc_result);
     }
 
-    fn is_success(&self) -> bool {
-        self.commands_failed == 0
+    pub(crate) fn is_success(&self) -> bool {
+        self.commands_failed == 0 && self.fixtures_failed == 0
     }
 }
 
 /// Execute the `pave verify` command.
-pub fn execute(args: VerifyArgs) -> Result<()> {
+pub fn execute(args: VerifyArgs, workdir: &Workdir) -> Result<()> {
+    workspace::run_once(&args, workdir)
+}
+
+/// Verify the documents selected by `args` and write the report, leaving
+/// output to the caller. Exporting and updating expected output finish here
+/// and give `None`.
+fn run(args: &VerifyArgs, workdir: &Workdir) -> Result<Option<Outcome<VerifyResults>>> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = workdir.find_config()?;
     let config = PaveConfig::load(&config_path)?;
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
 
-    // Determine paths to verify
-    let paths = if args.paths.is_empty() {
-        vec![config_dir.join(&config.docs.root)]
-    } else {
-        args.paths.clone()
+    let resolve = |path: &Option<PathBuf>| path.as_ref().map(|path| workdir.resolve(path));
+    let args = VerifyArgs {
+        paths: args
+            .paths
+            .iter()
+            .map(|path| workdir.resolve(path))
+            .collect(),
+        report: resolve(&args.report),
+        export_script: resolve(&args.export_script),
+        export_make: resolve(&args.export_make),
+        rerun_failed: resolve(&args.rerun_failed),
+        ..args.clone()
     };
-
-    // Find all markdown files
-    let files = find_markdown_files(&paths)?;
-
-    if files.is_empty() {
-        eprintln!("No markdown files found to verify");
-        return Ok(());
-    }
-
-    // Collect verification specs from all documents
-    let mut specs: Vec<VerificationSpec> = Vec::new();
-    for file in &files {
-        let doc = ParsedDoc::parse(file)?;
-        if let Some(spec) = extract_verification_spec(&doc) {
-            specs.push(spec);
-        }
+    if args.export_script.is_some() || args.export_make.is_some() {
+        export(&args, &config, config_dir)?;
+        return Ok(None);
     }
-
-    if specs.is_empty() {
-        eprintln!("No verification sections found in documents");
-        return Ok(());
+    if args.update_expected {
+        update_expected(args, &config, config_dir, &workdir.dir)?;
+        return Ok(None);
     }
 
-    // Run verifications
-    let mut results = VerifyResults::new();
-    let timeout = Duration::from_secs(args.timeout as u64);
+    let Some(mut results) = run_documents(&args, &config, config_dir)? else {
+        return Ok(None);
+    };
 
-    for spec in &specs {
-        let doc_result =
-            run_verification(spec, timeout, args.keep_going, config_dir, &config.rules)?;
-        let should_stop = !doc_result.is_success() && !args.keep_going;
-        results.add_document(doc_result);
+    // The updated report keeps the previous outcomes of commands not re-run
+    let mut merged = match &args.rerun_failed {
+        Some(previous) => Some(merge_rerun(load_report(previous)?, &results, config_dir)),
+        None => None,
+    };
 
-        if should_stop {
-            break;
-        }
+    let merged_documents = merged.iter_mut().flat_map(|merged| &mut merged.documents);
+    for doc in results.documents.iter_mut().chain(merged_documents) {
+        doc.file = display_path(&doc.file, args.path_style, config_dir, &workdir.dir);
     }
 
-    // Output results in the requested format
-    match args.format {
-        OutputFormat::Text => output_text(&results),
-        OutputFormat::Json => output_json(&results)?,
-        OutputFormat::Github => output_github(&results),
+    // Write report file if requested, or for the artifact command to upload.
+    // A re-run updates the report it was taken from unless told otherwise.
+    let report_path = match (&args.report, &config.verify.artifact_cmd) {
+        (Some(path), _) => Some(path.clone()),
+        (None, _) if args.rerun_failed.is_some() => args.rerun_failed.clone(),
+        (None, Some(_)) => Some(config_dir.join(ARTIFACT_REPORT_PATH)),
+        (None, None) => None,
+    };
+    let report = merged.as_ref().unwrap_or(&results);
+    if let Some(report_path) = &report_path {
+        write_report(report, report_path)?;
     }
 
-    // Write report file if requested
-    if let Some(report_path) = &args.report {
-        write_report(&results, report_path)?;
+    // Archive the evidence; a failed upload is reported but doesn't change
+    // the verification result
+    if let (Some(artifact_cmd), Some(report_path)) = (&config.verify.artifact_cmd, &report_path) {
+        let status = if report.is_success() { 0 } else { 1 };
+        if let Err(e) = run_artifact_cmd(
+            artifact_cmd,
+            &config.verify,
+            report_path,
+            status,
+            config_dir,
+        ) {
+            warn!("artifact_cmd failed: {:#}", e);
+        }
     }
 
-    // Return error if verifications failed
-    if results.is_success() {
+    // Fail if verifications failed
+    let status = if results.is_success() {
         Ok(())
+    } else if results.commands_failed == 0 {
+        Err(Violations(format!(
+            "Verification failed: {} setup/teardown command{} failed",
+            results.fixtures_failed,
+            if results.fixtures_failed == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ))
+        .into())
     } else {
-        anyhow::bail!(
+        Err(Violations(format!(
             "Verification failed: {} of {} command{} failed",
             results.commands_failed,
             results.commands_executed,
@@ -254,287 +500,1551 @@ pub fn execute(args: VerifyArgs) -> Result<()> {
             } else {
                 "s"
             }
-        );
-    }
+        ))
+        .into())
+    };
+    Ok(Some(Outcome { results, status }))
 }
 
-/// Run verification commands for a single document.
-fn run_verification(
-    spec: &VerificationSpec,
-    timeout: Duration,
-    keep_going: bool,
-    working_dir: &Path,
-    rules: &RulesSection,
-) -> Result<DocumentResult> {
-    let mut doc_result = DocumentResult::new(spec);
+/// Run the verification sections of the documents selected by `args`.
+///
+/// Returns `None`, after explaining why on stderr, when there is nothing to
+/// verify. Output formatting, the report file, and the exit status are left
+/// to the caller.
+pub(crate) fn run_documents(
+    args: &VerifyArgs,
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<Option<VerifyResults>> {
+    let Some(specs) = collect_specs(args, config, config_dir)? else {
+        return Ok(None);
+    };
 
-    for item in &spec.items {
-        let cmd_result = run_command(item, timeout, working_dir, rules);
-        // Fail/Timeout stop execution unless keep_going; Warn does not stop execution
-        let is_failure =
-            cmd_result.status == VerifyStatus::Fail || cmd_result.status == VerifyStatus::Timeout;
-        doc_result.add_result(cmd_result);
+    // Run verifications
+    let mut results = VerifyResults::new();
+    let timeout = Duration::from_secs(args.timeout as u64);
+    let cache_path = config_dir.join(CACHE_PATH);
+    let mut cache = VerifyCache::load(&cache_path);
+    let mut audit = if config.verify.audit_log {
+        Some(AuditLog::open(&config_dir.join(AUDIT_LOG_PATH))?)
+    } else {
+        None
+    };
 
-        if is_failure && !keep_going {
-            // Mark remaining commands as skipped
-            for remaining in spec.items.iter().skip(doc_result.commands.len()) {
-                doc_result.add_result(CommandResult {
-                    command: remaining.command.clone(),
-                    status: VerifyStatus::Skipped,
-                    exit_code: None,
-                    expected_exit_code: remaining.expected_exit_code.unwrap_or(0),
-                    stdout: None,
-                    stderr: None,
-                    duration_ms: None,
-                    output_mismatch: None,
-                    working_dir: remaining.working_dir.clone(),
-                    env_vars: remaining.env_vars.clone(),
-                });
-            }
+    // Every command inherits the environment, so any command could print a
+    // secret that some document passes through; redact them all everywhere
+    let secret_names: Vec<&String> = specs
+        .iter()
+        .flat_map(|(spec, _)| spec.setup.iter().chain(&spec.items).chain(&spec.teardown))
+        .flat_map(|item| &item.secrets)
+        .collect();
+    let secrets = Secrets::load(config_dir)?;
+    let redactor = Redactor::new(&config.verify.redact)?.with_values(secrets.values(secret_names));
+
+    let progress = Progress::new(specs.len(), matches!(args.format, OutputFormat::Text));
+    let mut failed_docs = 0;
+    for (spec, inputs_hash) in &specs {
+        let doc = display_path(
+            &spec.source_file,
+            PathStyle::Workspace,
+            config_dir,
+            config_dir,
+        );
+        progress.start(&doc);
+        let scope = inputs_hash.as_deref().map(|inputs_hash| CacheScope {
+            cache: &mut cache,
+            doc: doc.to_string_lossy().into_owned(),
+            inputs_hash,
+            lookup: !args.no_cache,
+        });
+        let mut doc_result = run_verification(
+            spec,
+            timeout,
+            args.keep_going,
+            &RunContext {
+                working_dir: config_dir,
+                rules: &config.rules,
+                verify: &config.verify,
+                secrets: &secrets,
+            },
+            scope,
+        )?;
+        redact_document(&mut doc_result, &redactor);
+        if let Some(audit) = &mut audit {
+            record_audit(audit, &doc, &doc_result, config_dir)?;
+        }
+        if !doc_result.is_success() {
+            failed_docs += 1;
+            progress.set_failed(failed_docs);
+        }
+        let should_stop = !doc_result.is_success() && !args.keep_going;
+        results.add_document(doc_result);
+        progress.finish();
+
+        if should_stop {
             break;
         }
     }
+    drop(progress);
 
-    Ok(doc_result)
+    // A stale cache only costs a re-run next time, so don't fail on it
+    if let Err(e) = cache.save(&cache_path) {
+        warn!("Failed to write verification cache: {:#}", e);
+    }
+
+    Ok(Some(results))
 }
 
-/// Check if the output matches the expected pattern.
-/// Returns (matches, strategy_name) tuple.
-fn check_output_match(matcher: &OutputMatcher, stdout: &str) -> (bool, &'static str) {
-    match matcher {
-        OutputMatcher::Contains(substring) => (stdout.contains(substring), "contains"),
-        OutputMatcher::Regex(pattern) => {
-            let matches = Regex::new(pattern)
-                .map(|re| re.is_match(stdout))
-                .unwrap_or(false);
-            (matches, "regex")
+/// Append the commands a document ran to the audit log.
+fn record_audit(
+    log: &mut AuditLog,
+    doc: &Path,
+    result: &DocumentResult,
+    config_dir: &Path,
+) -> Result<()> {
+    let phases = [
+        ("setup", &result.setup),
+        ("command", &result.commands),
+        ("teardown", &result.teardown),
+    ];
+    for (phase, commands) in phases {
+        for cmd in commands {
+            // Only commands that actually ran are recorded
+            let ran = cmd.preflight_error.is_none()
+                && !matches!(cmd.status, VerifyStatus::Skipped | VerifyStatus::Cached);
+            if !ran {
+                continue;
+            }
+            let cwd = cmd.working_dir.as_deref().unwrap_or(config_dir);
+            log.append(AuditEntry {
+                doc: doc.to_string_lossy().into_owned(),
+                phase: phase.to_string(),
+                command: cmd.command.clone(),
+                cwd: relative_path(cwd, config_dir)
+                    .to_string_lossy()
+                    .into_owned(),
+                env: cmd.env_vars.iter().map(|(name, _)| name.clone()).collect(),
+                exit_code: cmd.exit_code,
+                status: serde_json::to_value(cmd.status)?
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                output_hash: hash_output(
+                    cmd.stdout.as_deref().unwrap_or_default(),
+                    cmd.stderr.as_deref().unwrap_or_default(),
+                ),
+                ..Default::default()
+            })?;
         }
-        OutputMatcher::Exact(expected) => (stdout.trim() == expected.trim(), "exact"),
-        OutputMatcher::ExitCodeOnly => (true, "exit_code_only"),
     }
+    Ok(())
 }
 
-/// Get the expected string from an OutputMatcher.
-fn get_expected_string(matcher: &OutputMatcher) -> String {
-    match matcher {
-        OutputMatcher::Contains(s) => s.clone(),
-        OutputMatcher::Regex(s) => s.clone(),
-        OutputMatcher::Exact(s) => s.clone(),
-        OutputMatcher::ExitCodeOnly => String::new(),
-    }
-}
+/// Run every command and rewrite the expectations whose output didn't match,
+/// printing a diff of each changed document. Paths are shown from `cwd`.
+fn update_expected(
+    args: VerifyArgs,
+    config: &PaveConfig,
+    config_dir: &Path,
+    cwd: &Path,
+) -> Result<()> {
+    // Every command has to run for its output to be recorded
+    let args = VerifyArgs {
+        keep_going: true,
+        no_cache: true,
+        no_services: false,
+        ..args
+    };
+    let Some(results) = run_documents(&args, config, config_dir)? else {
+        return Ok(());
+    };
 
-/// Run a single verification command.
-fn run_command(
-    item: &VerificationItem,
-    timeout: Duration,
-    working_dir: &Path,
-    rules: &RulesSection,
-) -> CommandResult {
-    let expected_exit_code = item.expected_exit_code.unwrap_or(0);
-    let start = std::time::Instant::now();
+    let color = color::enabled();
+    let mut updated = 0;
+    let mut files_updated = 0;
+    for doc in &results.documents {
+        let actual: Vec<Option<&str>> = doc
+            .commands
+            .iter()
+            .map(|cmd| cmd.output_mismatch.as_ref().map(|m| m.actual.as_str()))
+            .collect();
+        if actual.iter().all(Option::is_none) {
+            continue;
+        }
 
-    // Use item's working_dir if specified, otherwise use config_dir
-    let cmd_working_dir = item.working_dir.as_deref().unwrap_or(working_dir);
+        let content = std::fs::read_to_string(&doc.file)
+            .with_context(|| format!("Failed to read {}", doc.file.display()))?;
+        let outcome = verify_update::update_expected(&doc.file, &content, &actual)?;
+        let shown = display_path(&doc.file, args.path_style, config_dir, cwd);
+        for skip in &outcome.skipped {
+            println!(
+                "{}:{}: not updated: {}",
+                shown.display(),
+                skip.line,
+                skip.reason
+            );
+        }
+        if outcome.updated == 0 {
+            continue;
+        }
 
-    // Build the command
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c")
-        .arg(&item.command)
-        .current_dir(cmd_working_dir);
+        std::fs::write(&doc.file, &outcome.content)
+            .with_context(|| format!("Failed to write {}", doc.file.display()))?;
+        println!("--- {}", shown.display());
+        println!("+++ {}", shown.display());
+        for line in render_unified(
+            &diff_lines(&content, &outcome.content),
+            DIFF_CONTEXT_LINES,
+            color,
+        ) {
+            println!("{}", line);
+        }
+        updated += outcome.updated;
+        files_updated += 1;
+    }
 
-    // Set environment variables
-    for (key, value) in &item.env_vars {
-        cmd.env(key, value);
+    if updated == 0 {
+        println!("No expected outputs needed updating");
+    } else {
+        println!();
+        println!(
+            "Updated {} expected output{} in {} file{}",
+            updated,
+            if updated == 1 { "" } else { "s" },
+            files_updated,
+            if files_updated == 1 { "" } else { "s" }
+        );
     }
 
-    // Execute command via shell
-    let output = cmd.output();
+    // Output mismatches were just fixed; anything else still fails
+    let failed = results
+        .documents
+        .iter()
+        .flat_map(|doc| doc.commands.iter().chain(&doc.setup).chain(&doc.teardown))
+        .filter(|cmd| {
+            cmd.output_mismatch.is_none()
+                && matches!(cmd.status, VerifyStatus::Fail | VerifyStatus::Timeout)
+        })
+        .count();
+    if failed > 0 {
+        anyhow::bail!(Violations(format!(
+            "Verification failed: {} command{} failed for reasons other than output",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
 
-    let duration_ms = start.elapsed().as_millis() as u64;
+/// Write the commands that would run to a shell script and/or Makefile.
+fn export(args: &VerifyArgs, config: &PaveConfig, config_dir: &Path) -> Result<()> {
+    let Some(specs) = collect_specs(args, config, config_dir)? else {
+        return Ok(());
+    };
 
-    // Track the working dir and env vars for the result (only if non-default)
-    let result_working_dir = item.working_dir.clone();
-    let result_env_vars = item.env_vars.clone();
+    let files: Vec<PathBuf> = specs
+        .iter()
+        .map(|(spec, _)| {
+            display_path(
+                &spec.source_file,
+                PathStyle::Workspace,
+                config_dir,
+                config_dir,
+            )
+        })
+        .collect();
+    let docs: Vec<ExportDoc> = specs
+        .iter()
+        .zip(&files)
+        .map(|((spec, _), file)| ExportDoc { file, spec })
+        .collect();
+    let commands: usize = specs
+        .iter()
+        .map(|(spec, _)| spec.setup.len() + spec.items.len() + spec.teardown.len())
+        .sum();
+
+    let targets = [
+        (&args.export_script, render_script as ExportFn, true),
+        (&args.export_make, render_makefile as ExportFn, false),
+    ];
+    for (path, render, executable) in targets {
+        let Some(path) = path else {
+            continue;
+        };
+        let out_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let root = relative_path(config_dir, out_dir);
+        let content = render(&docs, &config.verify, &root)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        #[cfg(unix)]
+        if executable {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("Failed to make {} executable", path.display()))?;
+        }
+        eprintln!(
+            "Exported {} command{} from {} document{} to {}",
+            commands,
+            if commands == 1 { "" } else { "s" },
+            docs.len(),
+            if docs.len() == 1 { "" } else { "s" },
+            path.display()
+        );
+    }
 
-    match output {
-        Ok(output) => {
-            let exit_code = output.status.code();
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    Ok(())
+}
 
-            // Check if timed out (heuristic: check if duration exceeds timeout)
-            if duration_ms >= timeout.as_millis() as u64 {
-                return CommandResult {
-                    command: item.command.clone(),
-                    status: VerifyStatus::Timeout,
-                    exit_code,
-                    expected_exit_code,
-                    stdout: Some(stdout),
-                    stderr: Some(stderr),
-                    duration_ms: Some(duration_ms),
-                    output_mismatch: None,
-                    working_dir: result_working_dir,
-                    env_vars: result_env_vars,
-                };
-            }
+/// Signature shared by the export renderers.
+type ExportFn = fn(&[ExportDoc], &VerifySection, &Path) -> Result<String>;
+
+/// A document's verification spec and the hash of the files it covers, if
+/// it covers any.
+type DocSpec = (VerificationSpec, Option<String>);
+
+/// Find the verification specs of the documents selected by `args`, along
+/// with a hash of the files each document covers.
+///
+/// Returns `None`, after explaining why on stderr, when there is nothing to
+/// verify.
+fn collect_specs(
+    args: &VerifyArgs,
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<Option<Vec<DocSpec>>> {
+    // Determine paths to verify
+    let paths = if args.paths.is_empty() {
+        vec![config_dir.join(&config.docs.root)]
+    } else {
+        args.paths.clone()
+    };
 
-            // Check exit code first
-            let exit_code_matches = exit_code == Some(expected_exit_code);
+    // Find all markdown files
+    let files = find_markdown_files(&paths, &load_ignore(config_dir, config)?)?;
 
-            // If exit code doesn't match, fail immediately
-            if !exit_code_matches {
-                return CommandResult {
-                    command: item.command.clone(),
-                    status: VerifyStatus::Fail,
-                    exit_code,
-                    expected_exit_code,
-                    stdout: if stdout.is_empty() {
-                        None
-                    } else {
-                        Some(stdout)
-                    },
-                    stderr: if stderr.is_empty() {
-                        None
-                    } else {
-                        Some(stderr)
-                    },
-                    duration_ms: Some(duration_ms),
-                    output_mismatch: None,
-                    working_dir: result_working_dir,
-                    env_vars: result_env_vars,
-                };
-            }
+    if files.is_empty() {
+        eprintln!("No markdown files found to verify");
+        return Ok(None);
+    }
 
-            // Check output matching if expected_output is specified and not skipped
-            let (status, output_mismatch) = if rules.skip_output_matching {
-                // Skip output matching entirely
-                (VerifyStatus::Pass, None)
-            } else if let Some(ref matcher) = item.expected_output {
-                let (matches, strategy) = check_output_match(matcher, &stdout);
-                if matches {
-                    (VerifyStatus::Pass, None)
-                } else {
-                    // Output doesn't match
-                    let mismatch = OutputMismatch {
-                        expected: get_expected_string(matcher),
-                        strategy: strategy.to_string(),
-                        actual: stdout.clone(),
-                    };
-                    if rules.strict_output_matching {
-                        // Strict mode: fail on mismatch
-                        (VerifyStatus::Fail, Some(mismatch))
-                    } else {
-                        // Default mode: warn on mismatch
-                        (VerifyStatus::Warn, Some(mismatch))
-                    }
-                }
-            } else {
-                // No expected output, just pass
-                (VerifyStatus::Pass, None)
-            };
+    // With --changed, only docs that changed or cover changed files are verified
+    let changed = if args.changed {
+        let base_ref = determine_base_ref(config_dir, args.base.as_deref())?;
+        let changed_files = changed_files(config_dir, &base_ref)?;
 
-            CommandResult {
-                command: item.command.clone(),
-                status,
-                exit_code,
-                expected_exit_code,
-                stdout: if stdout.is_empty() {
-                    None
-                } else {
-                    Some(stdout)
-                },
-                stderr: if stderr.is_empty() {
-                    None
-                } else {
-                    Some(stderr)
-                },
-                duration_ms: Some(duration_ms),
-                output_mismatch,
-                working_dir: result_working_dir,
-                env_vars: result_env_vars,
-            }
+        if changed_files.is_empty() {
+            eprintln!("No changed files found compared to {}", base_ref);
+            return Ok(None);
         }
-        Err(e) => CommandResult {
-            command: item.command.clone(),
-            status: VerifyStatus::Fail,
-            exit_code: None,
-            expected_exit_code,
-            stdout: None,
-            stderr: Some(format!("Failed to execute command: {}", e)),
-            duration_ms: Some(duration_ms),
-            output_mismatch: None,
-            working_dir: result_working_dir,
-            env_vars: result_env_vars,
-        },
-    }
-}
+        Some((base_ref, changed_files))
+    } else {
+        None
+    };
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+    // With --rerun-failed, only commands that failed last time are run
+    let rerun = match &args.rerun_failed {
+        Some(report) => {
+            let failed = failed_commands(&load_report(report)?, config_dir);
+            if failed.is_empty() {
+                eprintln!("No failed commands to re-run in {}", report.display());
+                return Ok(None);
+            }
+            Some(failed)
+        }
+        None => None,
+    };
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
+    // Collect verification specs from all documents, along with a hash of
+    // the files each document covers so cached results can be invalidated
+    let mut specs: Vec<DocSpec> = Vec::new();
+    // Only docs with `pave.paths` need the project's files
+    let project_files = LazyCell::new(|| project_files(config_dir));
+    for file in &files {
+        let doc = ParsedDoc::parse(file)?;
+        if doc.state() == DocState::Draft && !args.include_drafts {
+            info!("Skipping {}: draft (use --include-drafts)", file.display());
+            continue;
+        }
+        let paths = doc
+            .frontmatter
+            .as_ref()
+            .map(|fm| fm.paths.as_slice())
+            .unwrap_or_default();
+
+        if let Some((_, changed_files)) = &changed
+            && !is_affected(file, paths, changed_files, config_dir)
+        {
+            debug!("Skipping {}: not affected by changes", file.display());
+            continue;
         }
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
+        let Some(mut spec) = extract_verification_spec(&doc) else {
+            debug!("Skipping {}: no verification commands", file.display());
+            continue;
+        };
+        if let Some(image) = &config.verify.container {
+            let items = spec
+                .setup
+                .iter_mut()
+                .chain(&mut spec.items)
+                .chain(&mut spec.teardown);
+            // Blocks for a remote host run there as written
+            for item in items.filter(|item| item.host.is_none()) {
+                item.container.get_or_insert_with(|| image.clone());
+            }
+        }
+        render_vars(&mut spec, &config.vars);
+        if args.no_services {
+            spec.services.clear();
+            spec.compose = None;
+        }
+        if let Some(rerun) = &rerun {
+            let Some(failed) = rerun.get(&canonical_doc_path(file, config_dir)) else {
+                continue;
+            };
+            spec.items.retain(|item| failed.contains(&item.command));
+            if spec.items.is_empty() {
+                continue;
+            }
         }
+        let inputs_hash = if paths.is_empty() {
+            None
+        } else {
+            hash_path_inputs(paths, config_dir, &project_files)
+        };
+        specs.push((spec, inputs_hash));
     }
-}
-
-/// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
 
-    for path in paths {
-        if path.is_file() {
-            if path.extension().is_some_and(|ext| ext == "md") {
-                files.push(path.clone());
+    if specs.is_empty() {
+        match &changed {
+            Some((base_ref, _)) => eprintln!(
+                "No verification sections found in docs affected by changes since {}",
+                base_ref
+            ),
+            None if rerun.is_some() => {
+                eprintln!("None of the failed commands were found in the documents")
             }
-        } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
-        } else {
-            anyhow::bail!("Path does not exist: {}", path.display());
+            None => eprintln!("No verification sections found in documents"),
         }
+        return Ok(None);
     }
 
-    // Sort for consistent output
-    files.sort();
-    Ok(files)
+    Ok(Some(specs))
 }
 
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+/// Substitute `[vars]` values into the commands of `spec` before they run.
+pub(crate) fn render_vars(spec: &mut VerificationSpec, values: &BTreeMap<String, String>) {
+    let items = spec
+        .setup
+        .iter_mut()
+        .chain(&mut spec.items)
+        .chain(&mut spec.teardown);
+    for item in items {
+        item.command = vars::render_command(&item.command, values);
+        if let Some(script) = &mut item.script {
+            *script = vars::render_command(script, values);
+        }
+    }
+}
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+/// Read a JSON report written by `pave verify --report`.
+pub(crate) fn load_report(path: &Path) -> Result<VerifyResults> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse verify report: {}", path.display()))
+}
 
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
+/// Commands to re-run from a previous report, keyed by document.
+///
+/// Commands that failed or timed out are re-run, along with those skipped
+/// because of an earlier failure, since they never got to run.
+fn failed_commands(report: &VerifyResults, config_dir: &Path) -> HashMap<PathBuf, HashSet<String>> {
+    let mut failed: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    for doc in &report.documents {
+        for command in &doc.commands {
+            if matches!(
+                command.status,
+                VerifyStatus::Fail | VerifyStatus::Timeout | VerifyStatus::Skipped
+            ) {
+                failed
+                    .entry(canonical_doc_path(&doc.file, config_dir))
+                    .or_default()
+                    .insert(command.command.clone());
+            }
         }
     }
+    failed
+}
 
-    Ok(())
+/// Resolve a document path from a report or file discovery to a comparable
+/// form. Report paths may be relative to the workspace or the current
+/// directory, depending on `--path-style`.
+fn canonical_doc_path(file: &Path, config_dir: &Path) -> PathBuf {
+    [config_dir.join(file), file.to_path_buf()]
+        .iter()
+        .find_map(|path| path.canonicalize().ok())
+        .unwrap_or_else(|| config_dir.join(file))
 }
 
-/// Print a debugging suggestion for a failed command.
-fn print_debug_suggestion(cmd: &CommandResult) {
-    println!("    suggestion: Try running manually:");
-    let mut suggestion = String::new();
+/// Merge the results of `--rerun-failed` into the report they were taken
+/// from, replacing the re-run commands' previous outcomes.
+fn merge_rerun(previous: VerifyResults, rerun: &VerifyResults, config_dir: &Path) -> VerifyResults {
+    let mut merged = VerifyResults::new();
+    for old in previous.documents {
+        let path = canonical_doc_path(&old.file, config_dir);
+        let Some(new) = rerun
+            .documents
+            .iter()
+            .find(|doc| canonical_doc_path(&doc.file, config_dir) == path)
+        else {
+            merged.add_document(old);
+            continue;
+        };
+
+        let mut doc = DocumentResult {
+            commands: Vec::new(),
+            setup: new.setup.clone(),
+            teardown: new.teardown.clone(),
+            status: VerifyStatus::Pass,
+            ..old
+        };
+        let mut replacements: Vec<&CommandResult> = new.commands.iter().collect();
+        for command in old.commands {
+            match replacements
+                .iter()
+                .position(|result| result.command == command.command)
+            {
+                Some(index) => doc.add_result(replacements.remove(index).clone()),
+                None => doc.add_result(command),
+            }
+        }
+        match new.status {
+            VerifyStatus::SetupFailed => doc.status = VerifyStatus::SetupFailed,
+            VerifyStatus::TeardownFailed if doc.is_success() => doc.status = new.status,
+            _ => {}
+        }
+        merged.add_document(doc);
+    }
+    merged
+}
+
+/// Check whether a doc changed itself or covers a changed file.
+fn is_affected(
+    doc_path: &Path,
+    patterns: &[String],
+    changed_files: &HashSet<PathBuf>,
+    config_dir: &Path,
+) -> bool {
+    let relative = doc_path.strip_prefix(config_dir).unwrap_or(doc_path);
+    if changed_files.contains(relative) || changed_files.contains(doc_path) {
+        return true;
+    }
+
+    changed_files
+        .iter()
+        .any(|file| matches_any_pattern(file, patterns))
+}
+
+/// Check if a path matches any of the glob patterns.
+fn matches_any_pattern(path: &Path, patterns: &[String]) -> bool {
+    let path_str = path.to_string_lossy();
+
+    for pattern_str in patterns {
+        if let Ok(pattern) = Pattern::new(pattern_str)
+            && pattern.matches(&path_str)
+        {
+            return true;
+        }
+
+        // Also do simple prefix matching for patterns like "src/foo/"
+        if pattern_str.ends_with('/') || pattern_str.ends_with('*') {
+            let prefix = pattern_str.trim_end_matches('*').trim_end_matches('/');
+            if path_str.starts_with(prefix) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// What every command of a verification run shares.
+pub(crate) struct RunContext<'a> {
+    /// Directory commands run in unless they set `pave:working-dir`.
+    pub working_dir: &'a Path,
+    pub rules: &'a RulesSection,
+    pub verify: &'a VerifySection,
+    /// Secrets loaded once for the run.
+    pub secrets: &'a Secrets,
+}
+
+/// Cache state for the document being verified.
+pub(crate) struct CacheScope<'a> {
+    cache: &'a mut VerifyCache,
+    /// Document path relative to the config directory.
+    doc: String,
+    /// Hash of the files matched by the document's `pave.paths`.
+    inputs_hash: &'a str,
+    /// Whether cached passes may be reused (false with `--no-cache`).
+    lookup: bool,
+}
+
+/// Run verification commands for a single document.
+///
+/// With a cache scope, passes are recorded, and a document whose commands
+/// all passed with unchanged inputs is reported as cached instead of run.
+pub(crate) fn run_verification(
+    spec: &VerificationSpec,
+    timeout: Duration,
+    keep_going: bool,
+    ctx: &RunContext,
+    mut cache: Option<CacheScope>,
+) -> Result<DocumentResult> {
+    let mut doc_result = DocumentResult::new(spec);
+
+    let keys: Option<Vec<String>> = cache.as_mut().map(|scope| {
+        scope.cache.mark_doc(&scope.doc);
+        spec.items
+            .iter()
+            .map(|item| cache_key(item, ctx.verify, scope.inputs_hash, ctx.secrets))
+            .collect()
+    });
+
+    // Skipping a command also skips its side effects, which later commands
+    // may rely on, so cached passes are only reused all together
+    if let (Some(scope), Some(keys)) = (cache.as_mut(), keys.as_ref())
+        && scope.lookup
+        && let Some(entries) = keys
+            .iter()
+            .map(|key| scope.cache.lookup(key))
+            .collect::<Option<Vec<_>>>()
+    {
+        for (item, entry) in spec.items.iter().zip(&entries) {
+            doc_result.add_result(cached_result(item, entry));
+        }
+        return Ok(doc_result);
+    }
+
+    let mut fixture_started = false;
+    let mut compose_file = None;
+    let mut services = Vec::new();
+    let mut service_env = Vec::new();
+
+    let mut index = 0;
+    while index < spec.items.len() {
+        // The prompts of a block run together
+        let end = group_end(&spec.items, index);
+        if !fixture_started {
+            fixture_started = true;
+            services = services_up(&spec.services, &mut doc_result.setup, timeout, ctx);
+            service_env = services
+                .iter()
+                .flat_map(|plan| plan.env.iter().cloned())
+                .collect();
+            let mut services_ready = is_fixture_ready(&doc_result.setup);
+            if services_ready && let Some(compose) = &spec.compose {
+                compose_file = compose_up(compose, &mut doc_result.setup, timeout, ctx);
+                services_ready = is_fixture_ready(&doc_result.setup);
+            }
+            let setup: Vec<VerificationItem> = spec
+                .setup
+                .iter()
+                .map(|item| with_service_env(item, &service_env).into_owned())
+                .collect();
+            let ready =
+                services_ready && run_fixture(&setup, true, &mut doc_result.setup, timeout, ctx);
+            if !ready {
+                for remaining in &spec.items[index..] {
+                    doc_result.add_result(skipped_result(remaining));
+                }
+                doc_result.status = VerifyStatus::SetupFailed;
+                break;
+            }
+        }
+
+        let group: Vec<VerificationItem> = spec.items[index..end]
+            .iter()
+            .map(|item| with_service_env(item, &service_env).into_owned())
+            .collect();
+        let results = run_group(&group, timeout, ctx);
+
+        let mut is_failure = false;
+        for (position, (item, cmd_result)) in spec.items[index..end].iter().zip(results).enumerate()
+        {
+            if cmd_result.status == VerifyStatus::Pass
+                && let (Some(scope), Some(keys)) = (cache.as_mut(), keys.as_ref())
+            {
+                scope.cache.record(
+                    keys[index + position].clone(),
+                    CacheEntry {
+                        doc: scope.doc.clone(),
+                        command: item.command.clone(),
+                        exit_code: cmd_result.exit_code,
+                        recorded_at: chrono::Utc::now().to_rfc3339(),
+                    },
+                );
+            }
+
+            // Fail/Timeout stop execution unless keep_going; Warn does not stop execution
+            is_failure |= cmd_result.status == VerifyStatus::Fail
+                || cmd_result.status == VerifyStatus::Timeout;
+            doc_result.add_result(cmd_result);
+        }
+
+        if is_failure && !keep_going {
+            // Mark remaining commands as skipped
+            for remaining in &spec.items[end..] {
+                doc_result.add_result(skipped_result(remaining));
+            }
+            break;
+        }
+        index = end;
+    }
+
+    if fixture_started {
+        let teardown: Vec<VerificationItem> = spec
+            .teardown
+            .iter()
+            .map(|item| with_service_env(item, &service_env).into_owned())
+            .collect();
+        let mut clean = run_fixture(&teardown, false, &mut doc_result.teardown, timeout, ctx);
+        if let (Some(compose), Some(file)) = (&spec.compose, &compose_file) {
+            let command = compose
+                .bootstrap
+                .down_command(ctx.verify.compose_cmd(), file);
+            let item = compose_item(compose, ctx.working_dir, command);
+            let result = run_command(&item, timeout, ctx);
+            clean &= is_fixture_success(&result);
+            doc_result.teardown.push(result);
+        }
+        clean &= services_down(&services, &mut doc_result.teardown, timeout, ctx);
+        if !clean && doc_result.is_success() {
+            doc_result.status = VerifyStatus::TeardownFailed;
+        }
+    }
+
+    Ok(doc_result)
+}
+
+/// Run setup or teardown commands, returning whether they all succeeded.
+///
+/// Setup stops at the first failure. Teardown runs every command so that a
+/// partially created fixture is still cleaned up.
+fn run_fixture(
+    items: &[VerificationItem],
+    stop_on_failure: bool,
+    results: &mut Vec<CommandResult>,
+    timeout: Duration,
+    ctx: &RunContext,
+) -> bool {
+    let mut success = true;
+    let mut index = 0;
+    while index < items.len() {
+        let end = group_end(items, index);
+        for result in run_group(&items[index..end], timeout, ctx) {
+            success &= is_fixture_success(&result);
+            results.push(result);
+        }
+        if !success && stop_on_failure {
+            break;
+        }
+        index = end;
+    }
+    success
+}
+
+/// End of the group of items starting at `start`: the prompts of the same
+/// block, or just the item itself.
+fn group_end(items: &[VerificationItem], start: usize) -> usize {
+    let Some(session) = items[start].session else {
+        return start + 1;
+    };
+    items[start..]
+        .iter()
+        .position(|item| item.session != Some(session))
+        .map_or(items.len(), |offset| start + offset)
+}
+
+/// Run a group of items from [`group_end`], one result per item.
+fn run_group(
+    items: &[VerificationItem],
+    timeout: Duration,
+    ctx: &RunContext,
+) -> Vec<CommandResult> {
+    let posix = |item: &VerificationItem| match item.invocation(ctx.verify) {
+        Ok(Invocation::Shell(shell)) => shell.is_posix(),
+        _ => false,
+    };
+    match items {
+        [first, ..] if first.session.is_some() && posix(first) => run_session(items, timeout, ctx),
+        _ => items
+            .iter()
+            .map(|item| run_with_retries(item, timeout, ctx))
+            .collect(),
+    }
+}
+
+/// Marker printed after each prompt of a session, followed by its exit
+/// status.
+const SESSION_MARKER: &str = "__PAVE_PROMPT_DONE__";
+
+/// Run the prompts of one block in a single shell and check each against
+/// its own output.
+///
+/// A marker line after each prompt carries its exit status and splits the
+/// output back up. A prompt that fails ends the session, and the prompts
+/// after it are skipped.
+fn run_session(
+    items: &[VerificationItem],
+    timeout: Duration,
+    ctx: &RunContext,
+) -> Vec<CommandResult> {
+    let last = items.len() - 1;
+    let mut script = String::new();
+    for (index, item) in items.iter().enumerate() {
+        script.push_str(&item.command);
+        script.push('\n');
+        if index < last {
+            script.push_str(&format!(
+                "__pave_status=$?\n\
+                 printf '\\n{marker} %s\\n' \"$__pave_status\"\n\
+                 printf '\\n{marker}\\n' >&2\n\
+                 [ \"$__pave_status\" -eq 0 ] || exit \"$__pave_status\"\n",
+                marker = SESSION_MARKER
+            ));
+        }
+    }
+    let session = VerificationItem {
+        command: script,
+        expected_output: None,
+        ..items[last].clone()
+    };
+    let result = run_with_retries(&session, timeout, ctx);
+    if result.preflight_error.is_some() {
+        let first = CommandResult {
+            command: items[0].command.clone(),
+            ..result
+        };
+        return std::iter::once(first)
+            .chain(items[1..].iter().map(skipped_result))
+            .collect();
+    }
+
+    let (stdouts, statuses) = split_session_output(result.stdout.as_deref().unwrap_or(""));
+    let (stderrs, _) = split_session_output(result.stderr.as_deref().unwrap_or(""));
+    let ran = statuses.len();
+    let finished = statuses.last().is_none_or(|status| *status == 0);
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let (exit_code, duration_ms, timed_out) = match index.cmp(&ran) {
+                std::cmp::Ordering::Less => (Some(statuses[index]), None, false),
+                std::cmp::Ordering::Equal if finished => (
+                    result.exit_code,
+                    result.duration_ms,
+                    result.status == VerifyStatus::Timeout,
+                ),
+                _ => return skipped_result(item),
+            };
+            let output = |outputs: &[String]| outputs.get(index).cloned().unwrap_or_default();
+            command_result(
+                item,
+                exit_code,
+                output(&stdouts),
+                output(&stderrs),
+                duration_ms,
+                timed_out,
+                ctx.rules,
+            )
+        })
+        .collect()
+}
+
+/// Split a session's output at its marker lines, returning the output of
+/// each prompt and the exit statuses the markers carry.
+fn split_session_output(output: &str) -> (Vec<String>, Vec<i32>) {
+    let needle = format!("\n{}", SESSION_MARKER);
+    let mut outputs = Vec::new();
+    let mut statuses = Vec::new();
+    let mut rest = output;
+    while let Some(pos) = rest.find(&needle) {
+        outputs.push(rest[..pos].to_string());
+        let after = &rest[pos + needle.len()..];
+        let (line, tail) = after.split_once('\n').unwrap_or((after, ""));
+        if let Ok(status) = line.trim().parse() {
+            statuses.push(status);
+        }
+        rest = tail;
+    }
+    outputs.push(rest.to_string());
+    (outputs, statuses)
+}
+
+/// Start a document's compose services and wait for their endpoints.
+///
+/// Results are added to the setup phase. Returns the compose file once its
+/// services were started, so that teardown stops them even if waiting fails.
+fn compose_up(
+    compose: &ComposeFixture,
+    results: &mut Vec<CommandResult>,
+    timeout: Duration,
+    ctx: &RunContext,
+) -> Option<String> {
+    let compose_dir = compose_dir(compose, ctx.working_dir);
+    let file = match compose.bootstrap.resolve_file(&compose_dir) {
+        Ok(file) => file,
+        Err(message) => {
+            let command = format!("{} up", ctx.verify.compose_cmd());
+            let item = compose_item(compose, ctx.working_dir, command);
+            results.push(CommandResult {
+                status: VerifyStatus::Fail,
+                duration_ms: None,
+                preflight_error: Some(message),
+                container: None,
+                host: None,
+                ..skipped_result(&item)
+            });
+            return None;
+        }
+    };
+
+    let command = compose
+        .bootstrap
+        .up_command(ctx.verify.compose_cmd(), &file);
+    let item = compose_item(compose, ctx.working_dir, command);
+    let result = run_command(&item, timeout, ctx);
+    let started = is_fixture_success(&result);
+    results.push(result);
+    if !started {
+        return Some(file);
+    }
+
+    wait_for_endpoints(
+        &compose.bootstrap.wait_for,
+        compose.bootstrap.wait_timeout(),
+        results,
+    );
+    Some(file)
+}
+
+/// Start a document's `pave.services` in order, stopping at the first that
+/// fails to start or become ready.
+///
+/// Results are added to the setup phase. Returns the services that were
+/// started, which need stopping even if they never became ready.
+fn services_up(
+    services: &[Service],
+    results: &mut Vec<CommandResult>,
+    timeout: Duration,
+    ctx: &RunContext,
+) -> Vec<ServicePlan> {
+    let mut started = Vec::new();
+    for (index, service) in services.iter().enumerate() {
+        let plan = match service.plan(index, ctx.verify.container_cmd(), ctx.verify.compose_cmd()) {
+            Ok(plan) => plan,
+            Err(message) => {
+                let item = VerificationItem {
+                    command: format!("start service {}", service.name().unwrap_or_default()),
+                    ..VerificationItem::default()
+                };
+                results.push(CommandResult {
+                    status: VerifyStatus::Fail,
+                    duration_ms: None,
+                    preflight_error: Some(message),
+                    ..skipped_result(&item)
+                });
+                break;
+            }
+        };
+
+        let item = VerificationItem {
+            command: plan.start.clone(),
+            ..VerificationItem::default()
+        };
+        let result = run_command(&item, timeout, ctx);
+        let ok = is_fixture_success(&result);
+        results.push(result);
+        if !ok {
+            break;
+        }
+        // The published port is a fallback for images without a healthcheck
+        let deadline = Instant::now() + plan.wait_timeout;
+        let mut endpoints = plan.wait_for.clone();
+        if !wait_for_health(&plan.health, deadline, results, ctx) {
+            endpoints.splice(0..0, plan.published.clone());
+        }
+        if is_fixture_ready(results) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            wait_for_endpoints(&endpoints, remaining, results);
+        }
+        started.push(plan);
+        if !is_fixture_ready(results) {
+            break;
+        }
+    }
+    started
+}
+
+/// Poll a started service's `health` command until it reports healthy,
+/// recording one setup result.
+///
+/// Returns `false`, recording nothing, if the service has no healthcheck.
+fn wait_for_health(
+    health: &str,
+    deadline: Instant,
+    results: &mut Vec<CommandResult>,
+    ctx: &RunContext,
+) -> bool {
+    let item = VerificationItem {
+        command: health.to_string(),
+        ..VerificationItem::default()
+    };
+    let start = Instant::now();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let result = run_command(&item, remaining.max(Duration::from_secs(1)), ctx);
+        let error = match services::parse_health(result.stdout.as_deref().unwrap_or_default()) {
+            None => return false,
+            Some(Health::Healthy) => None,
+            Some(Health::Unhealthy) => Some("service is unhealthy".to_string()),
+            Some(Health::Starting) if Instant::now() + HEALTH_POLL_INTERVAL < deadline => {
+                std::thread::sleep(HEALTH_POLL_INTERVAL);
+                continue;
+            }
+            Some(Health::Starting) => {
+                Some("timed out waiting for service to be healthy".to_string())
+            }
+        };
+        results.push(CommandResult {
+            status: if error.is_none() {
+                VerifyStatus::Pass
+            } else {
+                VerifyStatus::Fail
+            },
+            stderr: error,
+            duration_ms: Some(start.elapsed().as_millis() as u64),
+            ..result
+        });
+        return true;
+    }
+}
+
+/// How often to poll a starting service's healthcheck.
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Stop started services in reverse order, returning whether they all
+/// stopped cleanly.
+fn services_down(
+    started: &[ServicePlan],
+    results: &mut Vec<CommandResult>,
+    timeout: Duration,
+    ctx: &RunContext,
+) -> bool {
+    let stops: Vec<VerificationItem> = started
+        .iter()
+        .rev()
+        .map(|plan| VerificationItem {
+            command: plan.stop.clone(),
+            ..VerificationItem::default()
+        })
+        .collect();
+    run_fixture(&stops, false, results, timeout, ctx)
+}
+
+/// Poll `endpoints` until each accepts connections, recording one setup
+/// result per endpoint and stopping at the first that times out.
+///
+/// Endpoints share one deadline so the wait is bounded by `wait_timeout`.
+fn wait_for_endpoints(
+    endpoints: &[String],
+    wait_timeout: Duration,
+    results: &mut Vec<CommandResult>,
+) {
+    let deadline = Instant::now() + wait_timeout;
+    for endpoint in endpoints {
+        let start = Instant::now();
+        let outcome = compose::wait_for(endpoint, deadline);
+        let item = VerificationItem {
+            command: format!("{}{}", WAIT_PREFIX, endpoint),
+            ..VerificationItem::default()
+        };
+        results.push(CommandResult {
+            status: if outcome.is_ok() {
+                VerifyStatus::Pass
+            } else {
+                VerifyStatus::Fail
+            },
+            stderr: outcome.err(),
+            duration_ms: Some(start.elapsed().as_millis() as u64),
+            ..skipped_result(&item)
+        });
+        if !is_fixture_ready(results) {
+            break;
+        }
+    }
+}
+
+/// `item` with the variables exported by started services, unless the item
+/// sets them itself.
+fn with_service_env<'a>(
+    item: &'a VerificationItem,
+    env: &[(String, String)],
+) -> Cow<'a, VerificationItem> {
+    if env.is_empty() {
+        return Cow::Borrowed(item);
+    }
+    let mut item = item.clone();
+    let mut env_vars: Vec<(String, String)> = env
+        .iter()
+        .filter(|(key, _)| !item.env_vars.iter().any(|(own, _)| own == key))
+        .cloned()
+        .collect();
+    env_vars.append(&mut item.env_vars);
+    item.env_vars = env_vars;
+    Cow::Owned(item)
+}
+
+/// Command label of a compose `wait_for` step, which pave runs itself.
+const WAIT_PREFIX: &str = "wait for ";
+
+/// Directory a document's compose file is found in and compose runs from.
+fn compose_dir(compose: &ComposeFixture, working_dir: &Path) -> PathBuf {
+    match &compose.working_dir {
+        Some(dir) => working_dir.join(dir),
+        None => working_dir.to_path_buf(),
+    }
+}
+
+/// Build the item for a compose command.
+fn compose_item(compose: &ComposeFixture, working_dir: &Path, command: String) -> VerificationItem {
+    VerificationItem {
+        command,
+        working_dir: compose
+            .working_dir
+            .as_ref()
+            .map(|_| compose_dir(compose, working_dir)),
+        ..VerificationItem::default()
+    }
+}
+
+/// Whether every setup result so far succeeded.
+fn is_fixture_ready(results: &[CommandResult]) -> bool {
+    results.iter().all(is_fixture_success)
+}
+
+/// Whether a setup or teardown command succeeded. Output mismatches only warn.
+fn is_fixture_success(result: &CommandResult) -> bool {
+    matches!(result.status, VerifyStatus::Pass | VerifyStatus::Warn)
+}
+
+/// Build the result for a command that was not run.
+fn skipped_result(item: &VerificationItem) -> CommandResult {
+    CommandResult {
+        command: item.command.clone(),
+        status: VerifyStatus::Skipped,
+        exit_code: None,
+        expected_exit_code: item.expected_exit_code.unwrap_or(0),
+        stdout: None,
+        stderr: None,
+        duration_ms: None,
+        output_mismatch: None,
+        working_dir: item.working_dir.clone(),
+        env_vars: item.env_vars.clone(),
+        preflight_error: None,
+        container: None,
+        host: None,
+    }
+}
+
+/// Build the result for a command whose cached pass is still valid.
+fn cached_result(item: &VerificationItem, entry: &CacheEntry) -> CommandResult {
+    CommandResult {
+        command: item.command.clone(),
+        status: VerifyStatus::Cached,
+        exit_code: entry.exit_code,
+        expected_exit_code: item.expected_exit_code.unwrap_or(0),
+        stdout: None,
+        stderr: None,
+        duration_ms: None,
+        output_mismatch: None,
+        working_dir: item.working_dir.clone(),
+        env_vars: item.env_vars.clone(),
+        preflight_error: None,
+        container: None,
+        host: None,
+    }
+}
+
+/// Check if the output matches the expected pattern.
+/// Returns (matches, strategy_name) tuple.
+fn check_output_match(matcher: &OutputMatcher, stdout: &str) -> (bool, &'static str) {
+    match matcher {
+        OutputMatcher::Contains(substring) => (stdout.contains(substring), "contains"),
+        OutputMatcher::Regex(pattern) => {
+            let matches = Regex::new(pattern)
+                .map(|re| re.is_match(stdout))
+                .unwrap_or(false);
+            (matches, "regex")
+        }
+        OutputMatcher::Exact(expected) => (stdout.trim() == expected.trim(), "exact"),
+        OutputMatcher::NotContains(substring) => (!stdout.contains(substring), "not_contains"),
+        OutputMatcher::ExitCodeOnly => (true, "exit_code_only"),
+    }
+}
+
+/// Get the expected string from an OutputMatcher.
+fn get_expected_string(matcher: &OutputMatcher) -> String {
+    match matcher {
+        OutputMatcher::Contains(s) => s.clone(),
+        OutputMatcher::Regex(s) => s.clone(),
+        OutputMatcher::Exact(s) => s.clone(),
+        OutputMatcher::NotContains(s) => s.clone(),
+        OutputMatcher::ExitCodeOnly => String::new(),
+    }
+}
+
+/// Run a command, running it again up to its `pave:retries` count while it
+/// fails or times out. The last attempt's result is returned.
+fn run_with_retries(item: &VerificationItem, timeout: Duration, ctx: &RunContext) -> CommandResult {
+    let mut result = run_command(item, timeout, ctx);
+    for attempt in 1..=item.retries {
+        if !matches!(result.status, VerifyStatus::Fail | VerifyStatus::Timeout)
+            || result.preflight_error.is_some()
+        {
+            break;
+        }
+        debug!(
+            "Retrying `{}` (retry {} of {})",
+            item.command, attempt, item.retries
+        );
+        result = run_command(item, timeout, ctx);
+    }
+    result
+}
+
+/// Run a single verification command.
+fn run_command(item: &VerificationItem, timeout: Duration, ctx: &RunContext) -> CommandResult {
+    // A `pave:timeout` marker overrides the run's timeout
+    let timeout = item
+        .timeout_secs
+        .map_or(timeout, |secs| Duration::from_secs(secs.into()));
+    let expected_exit_code = item.expected_exit_code.unwrap_or(0);
+
+    // Reject commands that would fail to parse or look unsafe before running them.
+    // The checks assume POSIX syntax, so other shells and interpreters skip them.
+    let (mut invocation, mut preflight_error) = match item.invocation(ctx.verify) {
+        Ok(Invocation::Shell(shell)) if shell.is_posix() => {
+            let error = check_shell_syntax(&item.command, shell).or_else(|| {
+                let construct = match ctx.rules.suspicious_commands {
+                    RuleSeverity::Off => None,
+                    _ => find_suspicious_construct(&item.command),
+                }?;
+                if ctx.rules.suspicious_commands == RuleSeverity::Error {
+                    return Some(construct);
+                }
+                warn!("Suspicious command `{}`: {}", item.command, construct);
+                None
+            });
+            (Invocation::Shell(shell), error)
+        }
+        Ok(invocation) => (invocation, None),
+        Err(message) => (Invocation::Shell(ctx.verify.shell()), Some(message)),
+    };
+    let mut destination = None;
+    if preflight_error.is_none()
+        && let Some(host) = &item.host
+    {
+        if item.container.is_some() {
+            preflight_error = Some("pave:host can't be combined with pave:container".to_string());
+        } else {
+            destination = ctx.verify.hosts.get(host);
+            if destination.is_none() {
+                preflight_error = Some(format!("host '{}' is not in [verify.hosts]", host));
+            }
+        }
+    }
+    let mut secret_values = Vec::new();
+    if preflight_error.is_none() && !item.secrets.is_empty() {
+        match resolve_secrets(&item.secrets, ctx.secrets) {
+            Ok(values) => secret_values = values,
+            Err(message) => preflight_error = Some(message),
+        }
+    }
+    // Rust examples compile against the project's library, like doctests
+    if preflight_error.is_none()
+        && item.compile
+        && item.container.is_none()
+        && item.host.is_none()
+        && let Invocation::Interpreter(argv) = &mut invocation
+        && argv.first().is_some_and(|program| program == "rustc")
+    {
+        match cargo::library(item.working_dir.as_deref().unwrap_or(ctx.working_dir)) {
+            Ok(Some(library)) => library.add_to(argv),
+            Ok(None) => {}
+            Err(message) => preflight_error = Some(message),
+        }
+    }
+    if preflight_error.is_some() {
+        return CommandResult {
+            command: item.command.clone(),
+            status: VerifyStatus::Fail,
+            exit_code: None,
+            expected_exit_code,
+            stdout: None,
+            stderr: None,
+            duration_ms: None,
+            output_mismatch: None,
+            working_dir: item.working_dir.clone(),
+            env_vars: item.env_vars.clone(),
+            preflight_error,
+            container: None,
+            host: None,
+        };
+    }
+
+    let start = std::time::Instant::now();
+
+    // Use item's working_dir if specified, otherwise use config_dir
+    let cmd_working_dir = item.working_dir.as_deref().unwrap_or(ctx.working_dir);
+
+    // Build the command
+    let (mut cmd, mut input) = build_command(item, &invocation, &secret_values);
+    cmd.current_dir(cmd_working_dir);
+
+    // Set environment variables, then pass secrets through
+    for (key, value) in item.env_vars.iter().chain(&secret_values) {
+        cmd.env(key, value);
+    }
+
+    // Run inside the item's container image, with the project mounted
+    if let Some(image) = &item.container {
+        let mut wrapped = container::wrap(
+            &cmd,
+            ctx.verify.container_cmd(),
+            image,
+            ctx.working_dir,
+            cmd_working_dir,
+        );
+        wrapped.current_dir(cmd_working_dir);
+        cmd = wrapped;
+    }
+
+    // Or run on the item's remote host, where only its own working dir means
+    // anything
+    let mut wrapped_remote = Ok(());
+    if let Some(destination) = destination {
+        match remote::wrap(
+            &cmd,
+            ctx.verify.ssh_cmd(),
+            destination,
+            item.working_dir.as_deref(),
+            input.as_deref(),
+        ) {
+            Ok((mut wrapped, stdin)) => {
+                wrapped.current_dir(ctx.working_dir);
+                cmd = wrapped;
+                input = Some(Cow::Owned(stdin));
+            }
+            Err(e) => wrapped_remote = Err(e),
+        }
+    }
+
+    // Execute the command, feeding interpreter scripts through stdin
+    let output = wrapped_remote
+        .and_then(|()| spawn_with_input(&mut cmd, input.as_deref()))
+        .and_then(|child| child.wait_with_output());
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            let (exit_code, stdout) = command_outcome(&invocation, output.status.code(), stdout);
+
+            // Check if timed out (heuristic: check if duration exceeds timeout)
+            let timed_out = duration_ms >= timeout.as_millis() as u64;
+            command_result(
+                item,
+                exit_code,
+                stdout,
+                stderr,
+                Some(duration_ms),
+                timed_out,
+                ctx.rules,
+            )
+        }
+        Err(e) => CommandResult {
+            command: item.command.clone(),
+            status: VerifyStatus::Fail,
+            exit_code: None,
+            expected_exit_code,
+            stdout: None,
+            stderr: Some(format!("Failed to execute command: {}", e)),
+            duration_ms: Some(duration_ms),
+            output_mismatch: None,
+            working_dir: item.working_dir.clone(),
+            env_vars: item.env_vars.clone(),
+            preflight_error: None,
+            container: item.container.clone(),
+            host: item.host.clone(),
+        },
+    }
+}
+
+/// The result of an item that ran, from its exit code and output.
+fn command_result(
+    item: &VerificationItem,
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    duration_ms: Option<u64>,
+    timed_out: bool,
+    rules: &RulesSection,
+) -> CommandResult {
+    let expected_exit_code = item.expected_exit_code.unwrap_or(0);
+
+    if timed_out {
+        return CommandResult {
+            command: item.command.clone(),
+            status: VerifyStatus::Timeout,
+            exit_code,
+            expected_exit_code,
+            stdout: Some(stdout),
+            stderr: Some(stderr),
+            duration_ms,
+            output_mismatch: None,
+            working_dir: item.working_dir.clone(),
+            env_vars: item.env_vars.clone(),
+            preflight_error: None,
+            container: item.container.clone(),
+            host: item.host.clone(),
+        };
+    }
+
+    // Check exit code first
+    let exit_code_matches = exit_code == Some(expected_exit_code);
+
+    // If exit code doesn't match, fail immediately
+    if !exit_code_matches {
+        return CommandResult {
+            command: item.command.clone(),
+            status: VerifyStatus::Fail,
+            exit_code,
+            expected_exit_code,
+            stdout: if stdout.is_empty() {
+                None
+            } else {
+                Some(stdout)
+            },
+            stderr: if stderr.is_empty() {
+                None
+            } else {
+                Some(stderr)
+            },
+            duration_ms,
+            output_mismatch: None,
+            working_dir: item.working_dir.clone(),
+            env_vars: item.env_vars.clone(),
+            preflight_error: None,
+            container: item.container.clone(),
+            host: item.host.clone(),
+        };
+    }
+
+    // Check output matching if expected_output is specified and not skipped
+    let (status, output_mismatch) = if rules.skip_output_matching {
+        // Skip output matching entirely
+        (VerifyStatus::Pass, None)
+    } else if let Some(ref matcher) = item.expected_output {
+        let (matches, strategy) = check_output_match(matcher, &stdout);
+        if matches {
+            (VerifyStatus::Pass, None)
+        } else {
+            // Output doesn't match
+            let mismatch =
+                OutputMismatch::new(get_expected_string(matcher), strategy, stdout.clone());
+            if rules.strict_output_matching {
+                // Strict mode: fail on mismatch
+                (VerifyStatus::Fail, Some(mismatch))
+            } else {
+                // Default mode: warn on mismatch
+                (VerifyStatus::Warn, Some(mismatch))
+            }
+        }
+    } else {
+        // No expected output, just pass
+        (VerifyStatus::Pass, None)
+    };
+
+    CommandResult {
+        command: item.command.clone(),
+        status,
+        exit_code,
+        expected_exit_code,
+        stdout: if stdout.is_empty() {
+            None
+        } else {
+            Some(stdout)
+        },
+        stderr: if stderr.is_empty() {
+            None
+        } else {
+            Some(stderr)
+        },
+        duration_ms,
+        output_mismatch,
+        working_dir: item.working_dir.clone(),
+        env_vars: item.env_vars.clone(),
+        preflight_error: None,
+        container: item.container.clone(),
+        host: item.host.clone(),
+    }
+}
+
+/// Look up the values of an item's secrets, or explain which one is
+/// missing.
+fn resolve_secrets(names: &[String], secrets: &Secrets) -> Result<Vec<(String, String)>, String> {
+    names
+        .iter()
+        .map(|name| {
+            let value = secrets.get(name).ok_or_else(|| {
+                format!(
+                    "secret '{}' is not set (export it or add it to {})",
+                    name, SECRETS_FILE
+                )
+            })?;
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Replace secret values in a document's captured output.
+fn redact_document(doc: &mut DocumentResult, redactor: &Redactor) {
+    let commands = doc
+        .setup
+        .iter_mut()
+        .chain(&mut doc.commands)
+        .chain(&mut doc.teardown);
+    for cmd in commands {
+        for text in [&mut cmd.stdout, &mut cmd.stderr].into_iter().flatten() {
+            redactor.redact_in_place(text);
+        }
+        if let Some(mismatch) = &mut cmd.output_mismatch {
+            redactor.redact_in_place(&mut mismatch.actual);
+            for line in &mut mismatch.actual_lines {
+                redactor.redact_in_place(line);
+            }
+        }
+    }
+}
+
+/// Print a debugging suggestion for a failed command.
+fn print_debug_suggestion(cmd: &CommandResult) {
+    println!("    suggestion: Try running manually:");
+    let mut suggestion = String::new();
     // Add env vars
     for (key, value) in &cmd.env_vars {
         suggestion.push_str(&format!("{}={} ", key, value));
@@ -548,6 +2058,7 @@ fn print_debug_suggestion(cmd: &CommandResult) {
 }
 
 /// Truncate a string to a maximum number of lines.
+/// Whether to color diffs: only on a terminal, and never with `NO_COLOR` set.
 fn truncate_lines(s: &str, max_lines: usize) -> String {
     let lines: Vec<&str> = s.lines().collect();
     if lines.len() <= max_lines {
@@ -564,16 +2075,29 @@ fn truncate_lines(s: &str, max_lines: usize) -> String {
 
 /// Output results in text format.
 fn output_text(results: &VerifyResults) {
+    let color = color::enabled();
     for doc in &results.documents {
-        println!("{}:{}", doc.file.display(), doc.section_line);
+        println!(
+            "{}",
+            color::bold(format!("{}:{}", doc.file.display(), doc.section_line))
+        );
 
-        for cmd in &doc.commands {
+        let labeled = doc
+            .setup
+            .iter()
+            .map(|cmd| ("setup: ", cmd))
+            .chain(doc.commands.iter().map(|cmd| ("", cmd)))
+            .chain(doc.teardown.iter().map(|cmd| ("teardown: ", cmd)));
+        for (label, cmd) in labeled {
             let status_str = match cmd.status {
-                VerifyStatus::Pass => "PASS",
-                VerifyStatus::Warn => "WARN",
-                VerifyStatus::Fail => "FAIL",
-                VerifyStatus::Timeout => "TIMEOUT",
-                VerifyStatus::Skipped => "SKIPPED",
+                VerifyStatus::Pass => color::green("PASS"),
+                VerifyStatus::Warn => color::yellow("WARN"),
+                VerifyStatus::Fail => color::red("FAIL"),
+                VerifyStatus::Timeout => color::red("TIMEOUT"),
+                VerifyStatus::Skipped => color::dim("SKIPPED"),
+                VerifyStatus::Cached => color::dim("CACHED"),
+                VerifyStatus::SetupFailed => color::red("SETUP FAILED"),
+                VerifyStatus::TeardownFailed => color::red("TEARDOWN FAILED"),
             };
 
             let duration_str = cmd
@@ -581,14 +2105,25 @@ fn output_text(results: &VerifyResults) {
                 .map(|d| format!(" ({:.2}s)", d as f64 / 1000.0))
                 .unwrap_or_default();
 
-            println!("  [{}]{} {}", status_str, duration_str, cmd.command);
+            println!(
+                "  [{}]{} {}{}",
+                status_str, duration_str, label, cmd.command
+            );
 
             // Show failure details
-            if cmd.status == VerifyStatus::Fail || cmd.status == VerifyStatus::Timeout {
+            if let Some(ref reason) = cmd.preflight_error {
+                println!("    preflight: {}", reason);
+            } else if cmd.status == VerifyStatus::Fail || cmd.status == VerifyStatus::Timeout {
                 // Show working directory if specified
                 if let Some(ref wd) = cmd.working_dir {
                     println!("    working_dir: {}", wd.display());
                 }
+                if let Some(ref image) = cmd.container {
+                    println!("    container: {}", image);
+                }
+                if let Some(ref host) = cmd.host {
+                    println!("    host: {}", host);
+                }
                 // Show environment variables if any
                 if !cmd.env_vars.is_empty() {
                     for (key, value) in &cmd.env_vars {
@@ -620,18 +2155,31 @@ fn output_text(results: &VerifyResults) {
                         println!("      {}", line);
                     }
                 }
-                // Print debugging suggestion
-                print_debug_suggestion(cmd);
+                // Print debugging suggestion, except for pave's own wait steps
+                if !cmd.command.starts_with(WAIT_PREFIX) {
+                    print_debug_suggestion(cmd);
+                }
             }
 
             // Show output mismatch details for both warnings and failures
             if let Some(ref mismatch) = cmd.output_mismatch {
                 println!("    output mismatch ({}):", mismatch.strategy);
-                println!("      expected: {}", truncate_lines(&mismatch.expected, 3));
-                println!(
-                    "      actual:   {}",
-                    truncate_lines(mismatch.actual.trim(), 5)
-                );
+                if let Some(diff) = mismatch.diff(color) {
+                    println!("      --- expected");
+                    println!("      +++ actual");
+                    for line in diff {
+                        println!("      {}", line);
+                    }
+                } else if mismatch.is_forbidden_match() {
+                    println!("      forbidden: {}", truncate_lines(&mismatch.expected, 3));
+                    println!("      found in:  {}", mismatch.forbidden_line());
+                } else {
+                    println!("      expected: {}", truncate_lines(&mismatch.expected, 3));
+                    println!(
+                        "      actual:   {}",
+                        truncate_lines(mismatch.actual.trim(), 5)
+                    );
+                }
             }
         }
         println!();
@@ -648,27 +2196,34 @@ fn output_text(results: &VerifyResults) {
         }
     );
 
+    let passed = color::green(format!("{} passed", results.commands_passed));
+    let warned = color::yellow(format!("{} warned", results.commands_warned));
     if results.commands_failed == 0 && results.commands_warned == 0 {
-        println!(
-            "{} command{} passed",
-            results.commands_passed,
-            if results.commands_passed == 1 {
-                ""
-            } else {
-                "s"
-            }
+        print!(
+            "{}",
+            color::green(format!(
+                "{} command{} passed",
+                results.commands_passed,
+                if results.commands_passed == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            ))
         );
     } else if results.commands_failed == 0 {
-        println!(
-            "{} passed, {} warned",
-            results.commands_passed, results.commands_warned
-        );
+        print!("{}, {}", passed, warned);
     } else {
-        println!(
-            "{} passed, {} warned, {} failed",
-            results.commands_passed, results.commands_warned, results.commands_failed
-        );
+        let failed = color::red(format!("{} failed", results.commands_failed));
+        print!("{}, {}, {}", passed, warned, failed);
+    }
+    if results.commands_cached > 0 {
+        print!(", {} cached", results.commands_cached);
     }
+    if results.fixtures_failed > 0 {
+        print!(", {} setup/teardown failed", results.fixtures_failed);
+    }
+    println!();
 }
 
 /// Output results in JSON format.
@@ -681,23 +2236,23 @@ fn output_json(results: &VerifyResults) -> Result<()> {
 /// Output results in GitHub Actions annotation format.
 fn output_github(results: &VerifyResults) {
     for doc in &results.documents {
-        for cmd in &doc.commands {
+        for cmd in doc.setup.iter().chain(&doc.commands).chain(&doc.teardown) {
             if cmd.status != VerifyStatus::Pass {
                 let level = match cmd.status {
-                    VerifyStatus::Fail | VerifyStatus::Timeout => "error",
+                    VerifyStatus::Fail
+                    | VerifyStatus::Timeout
+                    | VerifyStatus::SetupFailed
+                    | VerifyStatus::TeardownFailed => "error",
                     VerifyStatus::Warn | VerifyStatus::Skipped => "warning",
-                    VerifyStatus::Pass => continue,
+                    VerifyStatus::Pass | VerifyStatus::Cached => continue,
                 };
 
                 let message = match cmd.status {
                     VerifyStatus::Fail => {
-                        if let Some(ref mismatch) = cmd.output_mismatch {
-                            format!(
-                                "Output mismatch ({}): expected '{}', got '{}'",
-                                mismatch.strategy,
-                                mismatch.expected.lines().next().unwrap_or(""),
-                                mismatch.actual.trim().lines().next().unwrap_or("")
-                            )
+                        if let Some(ref reason) = cmd.preflight_error {
+                            format!("Command rejected before execution: {}", reason)
+                        } else if let Some(ref mismatch) = cmd.output_mismatch {
+                            mismatch.summary()
                         } else {
                             format!(
                                 "Command failed: {} (exit code: {:?}, expected: {})",
@@ -707,12 +2262,7 @@ fn output_github(results: &VerifyResults) {
                     }
                     VerifyStatus::Warn => {
                         if let Some(ref mismatch) = cmd.output_mismatch {
-                            format!(
-                                "Output mismatch ({}): expected '{}', got '{}'",
-                                mismatch.strategy,
-                                mismatch.expected.lines().next().unwrap_or(""),
-                                mismatch.actual.trim().lines().next().unwrap_or("")
-                            )
+                            mismatch.summary()
                         } else {
                             format!("Command warning: {}", cmd.command)
                         }
@@ -723,7 +2273,10 @@ fn output_github(results: &VerifyResults) {
                     VerifyStatus::Skipped => {
                         format!("Command skipped: {}", cmd.command)
                     }
-                    VerifyStatus::Pass => continue,
+                    VerifyStatus::SetupFailed | VerifyStatus::TeardownFailed => {
+                        format!("Fixture failed: {}", cmd.command)
+                    }
+                    VerifyStatus::Pass | VerifyStatus::Cached => continue,
                 };
 
                 println!(
@@ -741,6 +2294,12 @@ fn output_github(results: &VerifyResults) {
 /// Write JSON report to file.
 fn write_report(results: &VerifyResults, path: &Path) -> Result<()> {
     let json = serde_json::to_string_pretty(results).context("Failed to serialize results")?;
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
     let mut file = std::fs::File::create(path)
         .with_context(|| format!("Failed to create {}", path.display()))?;
     file.write_all(json.as_bytes())
@@ -749,9 +2308,58 @@ fn write_report(results: &VerifyResults, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Run `[verify] artifact_cmd` with its placeholders filled in, from the
+/// project root and with the configured shell.
+fn run_artifact_cmd(
+    template: &str,
+    verify: &VerifySection,
+    report_path: &Path,
+    status: i32,
+    config_dir: &Path,
+) -> Result<()> {
+    let sha = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(config_dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let command = expand_artifact_cmd(template, report_path, status, &sha);
+
+    let output = shell_command(verify.shell(), &command)
+        .current_dir(config_dir)
+        .output()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "'{}' exited with {}: {}",
+            command,
+            output
+                .status
+                .code()
+                .map_or_else(|| "a signal".to_string(), |code| code.to_string()),
+            stderr.trim()
+        );
+    }
+    eprintln!("Artifacts archived with '{}'", command);
+    Ok(())
+}
+
+/// Fill in the `{report}`, `{status}`, and `{sha}` placeholders of an
+/// artifact command. The report path is shell-quoted.
+fn expand_artifact_cmd(template: &str, report_path: &Path, status: i32, sha: &str) -> String {
+    template
+        .replace("{report}", &quote(&report_path.to_string_lossy()))
+        .replace("{status}", &status.to_string())
+        .replace("{sha}", sha)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ignore::PaveIgnore;
     use std::fs;
     use tempfile::TempDir;
 
@@ -826,6 +2434,238 @@ Example here.
         }
     }
 
+    #[test]
+    fn expand_artifact_cmd_fills_placeholders() {
+        let command = expand_artifact_cmd(
+            "aws s3 cp {report} s3://bucket/{sha}/report.json --metadata status={status}",
+            Path::new("/tmp/my reports/verify.json"),
+            1,
+            "abc123",
+        );
+
+        assert_eq!(
+            command,
+            "aws s3 cp '/tmp/my reports/verify.json' s3://bucket/abc123/report.json --metadata status=1"
+        );
+    }
+
+    #[test]
+    fn run_artifact_cmd_reports_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let report = temp_dir.path().join("report.json");
+        fs::write(&report, "{}").unwrap();
+        let verify = VerifySection::default();
+
+        run_artifact_cmd(
+            "cp {report} archived-{status}.json",
+            &verify,
+            &report,
+            0,
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert!(temp_dir.path().join("archived-0.json").exists());
+
+        let err = run_artifact_cmd(
+            "echo denied >&2; exit 3",
+            &verify,
+            &report,
+            0,
+            temp_dir.path(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("exited with 3: denied"));
+    }
+
+    #[test]
+    fn collect_specs_skips_drafts_unless_included() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        create_doc_with_verification(&temp_dir, "published.md", &["true"]);
+        let draft = create_doc_with_verification(&temp_dir, "draft.md", &["false"]);
+        let content = fs::read_to_string(&draft).unwrap();
+        fs::write(
+            &draft,
+            format!("---\npave:\n  state: draft\n---\n{}", content),
+        )
+        .unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut args = VerifyArgs {
+            paths: vec![],
+            format: OutputFormat::Text,
+            report: None,
+            timeout: 30,
+            keep_going: true,
+            path_style: PathStyle::Workspace,
+            no_cache: true,
+            no_services: false,
+            changed: false,
+            base: None,
+            export_script: None,
+            export_make: None,
+            include_drafts: false,
+            update_expected: false,
+            rerun_failed: None,
+        };
+
+        let specs = collect_specs(&args, &config, temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(specs.len(), 1);
+        assert!(specs[0].0.source_file.ends_with("published.md"));
+
+        args.include_drafts = true;
+        let specs = collect_specs(&args, &config, temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(specs.len(), 2);
+    }
+
+    #[test]
+    fn render_vars_substitutes_into_commands() {
+        let mut spec = VerificationSpec {
+            source_file: PathBuf::from("doc.md"),
+            section_line: 1,
+            items: vec![VerificationItem {
+                command: "curl {{ vars.service_url }}/health".to_string(),
+                ..VerificationItem::default()
+            }],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
+        };
+        let values = BTreeMap::from([(
+            "service_url".to_string(),
+            "https://api.example.com".to_string(),
+        )]);
+
+        render_vars(&mut spec, &values);
+        assert_eq!(spec.items[0].command, "curl https://api.example.com/health");
+    }
+
+    #[test]
+    fn rerun_failed_runs_only_failed_commands_and_merges_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        create_doc_with_verification(&temp_dir, "ok.md", &["true"]);
+        create_doc_with_verification(&temp_dir, "flaky.md", &["echo first", "test -f ready"]);
+        let config = PaveConfig::load(&config_path).unwrap();
+        let report_path = temp_dir.path().join("report.json");
+        let mut args = VerifyArgs {
+            paths: vec![],
+            format: OutputFormat::Text,
+            report: None,
+            timeout: 30,
+            keep_going: true,
+            path_style: PathStyle::Workspace,
+            no_cache: true,
+            no_services: false,
+            changed: false,
+            base: None,
+            export_script: None,
+            export_make: None,
+            include_drafts: false,
+            update_expected: false,
+            rerun_failed: None,
+        };
+
+        let mut first = run_documents(&args, &config, temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.commands_failed, 1);
+        for doc in &mut first.documents {
+            doc.file = display_path(
+                &doc.file,
+                PathStyle::Workspace,
+                temp_dir.path(),
+                temp_dir.path(),
+            );
+        }
+        write_report(&first, &report_path).unwrap();
+
+        fs::write(temp_dir.path().join("ready"), "").unwrap();
+        args.rerun_failed = Some(report_path.clone());
+        let rerun = run_documents(&args, &config, temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(rerun.documents_verified, 1);
+        assert_eq!(rerun.commands_executed, 1);
+        assert_eq!(rerun.documents[0].commands[0].command, "test -f ready");
+
+        let merged = merge_rerun(load_report(&report_path).unwrap(), &rerun, temp_dir.path());
+        assert!(merged.is_success());
+        assert_eq!(merged.documents_verified, 2);
+        assert_eq!(merged.commands_passed, 3);
+        let flaky = merged
+            .documents
+            .iter()
+            .find(|doc| doc.file.ends_with("flaky.md"))
+            .unwrap();
+        assert_eq!(flaky.status, VerifyStatus::Pass);
+        assert_eq!(flaky.commands[0].command, "echo first");
+
+        // Nothing left to re-run once the merged report is clean
+        write_report(&merged, &report_path).unwrap();
+        assert!(
+            collect_specs(&args, &config, temp_dir.path())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn audit_log_records_commands_that_ran() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        create_doc_with_verification(&temp_dir, "auth.md", &["echo hi", "false"]);
+        let mut config = PaveConfig::load(&config_path).unwrap();
+        config.verify.audit_log = true;
+        let args = VerifyArgs {
+            paths: vec![],
+            format: OutputFormat::Text,
+            report: None,
+            timeout: 30,
+            keep_going: true,
+            path_style: PathStyle::Workspace,
+            no_cache: false,
+            no_services: false,
+            changed: false,
+            base: None,
+            export_script: None,
+            export_make: None,
+            include_drafts: false,
+            update_expected: false,
+            rerun_failed: None,
+        };
+
+        run_documents(&args, &config, temp_dir.path()).unwrap();
+        // A document with a failure isn't cached, so it all runs again
+        run_documents(&args, &config, temp_dir.path()).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join(AUDIT_LOG_PATH)).unwrap();
+        assert_eq!(crate::audit::verify_chain(&content).unwrap().entries, 4);
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, Option<i32>, &str)> = entries
+            .iter()
+            .map(|e| (e.command.as_str(), e.exit_code, e.status.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("echo hi", Some(0), "pass"),
+                ("false", Some(1), "fail"),
+                ("echo hi", Some(0), "pass"),
+                ("false", Some(1), "fail"),
+            ]
+        );
+        assert_eq!(entries[0].cwd, ".");
+        assert_eq!(entries[0].output_hash, hash_output("hi\n", ""));
+    }
+
     #[test]
     fn verify_status_serializes_lowercase() {
         let pass = serde_json::to_string(&VerifyStatus::Pass).unwrap();
@@ -833,12 +2673,14 @@ Example here.
         let fail = serde_json::to_string(&VerifyStatus::Fail).unwrap();
         let timeout = serde_json::to_string(&VerifyStatus::Timeout).unwrap();
         let skipped = serde_json::to_string(&VerifyStatus::Skipped).unwrap();
+        let cached = serde_json::to_string(&VerifyStatus::Cached).unwrap();
 
         assert_eq!(pass, "\"pass\"");
         assert_eq!(warn, "\"warn\"");
         assert_eq!(fail, "\"fail\"");
         assert_eq!(timeout, "\"timeout\"");
         assert_eq!(skipped, "\"skipped\"");
+        assert_eq!(cached, "\"cached\"");
     }
 
     #[test]
@@ -847,6 +2689,10 @@ Example here.
             source_file: PathBuf::from("test.md"),
             section_line: 10,
             items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
         };
 
         let mut doc_result = DocumentResult::new(&spec);
@@ -863,6 +2709,9 @@ Example here.
             output_mismatch: None,
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
         assert!(doc_result.is_success());
 
@@ -877,77 +2726,618 @@ Example here.
             output_mismatch: None,
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
         assert!(!doc_result.is_success());
     }
 
     #[test]
-    fn verify_results_aggregates_counts() {
-        let spec = VerificationSpec {
-            source_file: PathBuf::from("test.md"),
-            section_line: 10,
-            items: vec![],
+    fn verify_results_aggregates_counts() {
+        let spec = VerificationSpec {
+            source_file: PathBuf::from("test.md"),
+            section_line: 10,
+            items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
+        };
+
+        let mut results = VerifyResults::new();
+        let mut doc_result = DocumentResult::new(&spec);
+
+        doc_result.add_result(CommandResult {
+            command: "echo 1".to_string(),
+            status: VerifyStatus::Pass,
+            exit_code: Some(0),
+            expected_exit_code: 0,
+            stdout: None,
+            stderr: None,
+            duration_ms: Some(10),
+            output_mismatch: None,
+            working_dir: None,
+            env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
+        });
+
+        doc_result.add_result(CommandResult {
+            command: "false".to_string(),
+            status: VerifyStatus::Fail,
+            exit_code: Some(1),
+            expected_exit_code: 0,
+            stdout: None,
+            stderr: None,
+            duration_ms: Some(5),
+            output_mismatch: None,
+            working_dir: None,
+            env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
+        });
+
+        results.add_document(doc_result);
+
+        assert_eq!(results.documents_verified, 1);
+        assert_eq!(results.commands_executed, 2);
+        assert_eq!(results.commands_passed, 1);
+        assert_eq!(results.commands_failed, 1);
+        assert!(!results.is_success());
+    }
+
+    #[test]
+    fn run_command_success() {
+        let item = VerificationItem {
+            command: "echo hello".to_string(),
+            working_dir: None,
+            expected_exit_code: Some(0),
+            expected_output: None,
+            timeout_secs: Some(30),
+            retries: 0,
+            env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
+        };
+
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
+
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert_eq!(result.exit_code, Some(0));
+        assert!(result.stdout.as_ref().is_some_and(|s| s.contains("hello")));
+    }
+
+    #[test]
+    fn run_with_retries_reruns_failing_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let item = VerificationItem {
+            command: "test -f ran || { touch ran; exit 1; }".to_string(),
+            ..VerificationItem::default()
+        };
+        let run = |item: &VerificationItem| {
+            run_with_retries(
+                item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        assert_eq!(run(&item).status, VerifyStatus::Fail);
+        std::fs::remove_file(temp_dir.path().join("ran")).unwrap();
+        let retried = VerificationItem { retries: 2, ..item };
+        assert_eq!(run(&retried).status, VerifyStatus::Pass);
+    }
+
+    #[test]
+    fn run_command_passes_secrets_and_reports_missing_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(SECRETS_FILE),
+            "PAVE_TEST_SECRET_TOKEN=s3cr3t-value\n",
+        )
+        .unwrap();
+        let item = VerificationItem {
+            command: "echo token=$PAVE_TEST_SECRET_TOKEN".to_string(),
+            secrets: vec!["PAVE_TEST_SECRET_TOKEN".to_string()],
+            ..VerificationItem::default()
+        };
+        let secrets = Secrets::load(temp_dir.path()).unwrap();
+
+        let run = |item: &VerificationItem| {
+            run_command(
+                item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &secrets,
+                },
+            )
+        };
+        let result = run(&item);
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert!(result.env_vars.is_empty());
+
+        let mut doc = DocumentResult::new(&VerificationSpec {
+            source_file: PathBuf::from("doc.md"),
+            section_line: 1,
+            items: vec![item.clone()],
+            setup: Vec::new(),
+            teardown: Vec::new(),
+            compose: None,
+            services: Vec::new(),
+        });
+        doc.add_result(result);
+        let redactor = Redactor::default().with_values(secrets.values(&item.secrets));
+        redact_document(&mut doc, &redactor);
+        assert_eq!(doc.commands[0].stdout.as_deref(), Some("token=***\n"));
+
+        let missing = VerificationItem {
+            secrets: vec!["PAVE_TEST_SECRET_MISSING".to_string()],
+            ..item
+        };
+        let result = run(&missing);
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert!(
+            result
+                .preflight_error
+                .unwrap()
+                .contains("secret 'PAVE_TEST_SECRET_MISSING' is not set")
+        );
+    }
+
+    #[test]
+    fn run_command_runs_in_container_and_records_image() {
+        let temp_dir = TempDir::new().unwrap();
+        let item = VerificationItem {
+            command: "npm test".to_string(),
+            container: Some("node:20".to_string()),
+            ..VerificationItem::default()
+        };
+        // `echo` stands in for docker, printing the command it would run
+        let verify = VerifySection {
+            container_cmd: Some("echo".to_string()),
+            ..VerifySection::default()
+        };
+
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &verify,
+                secrets: &Secrets::default(),
+            },
+        );
+
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert_eq!(result.container.as_deref(), Some("node:20"));
+        let root = std::path::absolute(temp_dir.path()).unwrap();
+        let stdout = result.stdout.unwrap();
+        assert!(stdout.starts_with(&format!(
+            "run --rm -i -v {}:{} -w {} node:20 ",
+            root.display(),
+            root.display(),
+            root.display()
+        )));
+        assert!(stdout.trim_end().ends_with("-c npm test"));
+    }
+
+    #[test]
+    fn run_command_sends_http_requests() {
+        use std::io::{BufRead, BufReader, Read};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            for status in ["200 OK", "503 Service Unavailable"] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let body = format!("got {}", String::from_utf8(body).unwrap());
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                std::io::Write::write_all(reader.get_mut(), response.as_bytes()).unwrap();
+            }
+        });
+
+        let request = "POST http://127.0.0.1:{{PORT}}/items\n\nwidget".to_string();
+        let item = VerificationItem {
+            command: request.clone(),
+            script: Some(request),
+            language: Some("http".to_string()),
+            env_vars: vec![("PORT".to_string(), port.to_string())],
+            expected_output: Some(OutputMatcher::Exact("HTTP 200\ngot widget".to_string())),
+            ..VerificationItem::default()
+        };
+        let run = || {
+            run_command(
+                &item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: Path::new("."),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        let result = run();
+        assert_eq!(result.status, VerifyStatus::Pass, "{:?}", result.stderr);
+        let result = run();
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert_eq!(result.exit_code, Some(1));
+        assert_eq!(result.stdout.as_deref(), Some("HTTP 503\ngot widget"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn run_command_queries_sqlite_databases() {
+        let temp_dir = TempDir::new().unwrap();
+        let query = "create table items (id, name);\n\
+                     insert into items values (1, 'widget');\n\
+                     select * from items;"
+            .to_string();
+        let item = VerificationItem {
+            command: query.clone(),
+            script: Some(query),
+            language: Some("sql".to_string()),
+            expected_output: Some(OutputMatcher::Exact("id|name\n1|widget".to_string())),
+            ..VerificationItem::default()
+        };
+        let mut verify = VerifySection::default();
+        verify
+            .databases
+            .insert("default".to_string(), "sqlite:app.db".to_string());
+
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &verify,
+                secrets: &Secrets::default(),
+            },
+        );
+
+        assert_eq!(result.status, VerifyStatus::Pass, "{:?}", result.stderr);
+        assert!(temp_dir.path().join("app.db").exists());
+    }
+
+    #[test]
+    fn run_command_compiles_examples() {
+        let compile = |script: &str| {
+            let item = VerificationItem {
+                command: script.to_string(),
+                script: Some(script.to_string()),
+                language: Some("python".to_string()),
+                compile: true,
+                ..VerificationItem::default()
+            };
+            run_command(
+                &item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: Path::new("."),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        // Compiling doesn't run the example
+        let passed = compile("import does_not_exist\nprint('hi')");
+        assert_eq!(passed.status, VerifyStatus::Pass, "{:?}", passed.stderr);
+        assert_eq!(passed.stdout, None);
+
+        let failed = compile("def broken(:\n    pass");
+        assert_eq!(failed.status, VerifyStatus::Fail);
+        let stderr = failed.stderr.unwrap_or_default();
+        assert!(stderr.contains("SyntaxError"), "{}", stderr);
+    }
+
+    #[test]
+    fn run_command_compiles_rust_examples_against_the_crate() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo-lib\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src/lib.rs"),
+            "pub fn greet() -> String { \"hi\".to_string() }\n",
+        )
+        .unwrap();
+        let compile = |script: &str| {
+            let item = VerificationItem {
+                command: script.to_string(),
+                script: Some(script.to_string()),
+                language: Some("rust".to_string()),
+                compile: true,
+                ..VerificationItem::default()
+            };
+            run_command(
+                &item,
+                Duration::from_secs(120),
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        let passed = compile("pub fn example() -> String { demo_lib::greet() }");
+        assert_eq!(passed.status, VerifyStatus::Pass, "{:?}", passed.stderr);
+
+        let failed = compile("pub fn example() -> u32 { demo_lib::greet() }");
+        assert_eq!(failed.status, VerifyStatus::Fail);
+    }
+
+    #[test]
+    fn run_command_rejects_sql_without_a_database() {
+        let item = VerificationItem {
+            command: "select 1;".to_string(),
+            language: Some("sql".to_string()),
+            database: Some("warehouse".to_string()),
+            ..VerificationItem::default()
+        };
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert_eq!(
+            result.preflight_error.as_deref(),
+            Some("database 'warehouse' is not in [verify.databases]")
+        );
+    }
+
+    #[test]
+    fn run_command_runs_on_host_over_ssh() {
+        let temp_dir = TempDir::new().unwrap();
+        let item = VerificationItem {
+            command: "systemctl is-active api".to_string(),
+            host: Some("staging".to_string()),
+            env_vars: vec![("REGION".to_string(), "eu".to_string())],
+            ..VerificationItem::default()
+        };
+        // `cat` stands in for ssh, printing the script it would send
+        let mut verify = VerifySection {
+            ssh_cmd: Some("sh -c cat".to_string()),
+            ..VerifySection::default()
+        };
+        let run = |verify: &VerifySection| {
+            run_command(
+                &item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify,
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        let result = run(&verify);
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert_eq!(
+            result.preflight_error.as_deref(),
+            Some("host 'staging' is not in [verify.hosts]")
+        );
+
+        verify
+            .hosts
+            .insert("staging".to_string(), "deploy@bastion".to_string());
+        let result = run(&verify);
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert_eq!(result.host.as_deref(), Some("staging"));
+        assert_eq!(
+            result.stdout.as_deref(),
+            Some("export REGION=eu\nexec sh -c 'systemctl is-active api' </dev/null\n")
+        );
+    }
+
+    #[test]
+    fn run_command_rejects_syntax_errors_before_execution() {
+        let item = VerificationItem {
+            command: "echo 'unterminated".to_string(),
+            ..VerificationItem::default()
+        };
+
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
+
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert!(result.exit_code.is_none());
+        assert!(
+            result
+                .preflight_error
+                .as_ref()
+                .is_some_and(|e| e.contains("unbalanced single quote"))
+        );
+    }
+
+    #[test]
+    fn run_command_suspicious_construct_respects_config() {
+        let item = VerificationItem {
+            command: "echo `date`".to_string(),
+            ..VerificationItem::default()
+        };
+
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
+        // Suspicious constructs are only a warning by default
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert!(result.preflight_error.is_none());
+
+        let rules = RulesSection {
+            suspicious_commands: RuleSeverity::Error,
+            ..RulesSection::default()
+        };
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &rules,
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert!(result.preflight_error.is_some());
+    }
+
+    #[test]
+    fn run_command_uses_item_shell() {
+        // Process substitution is bash-only; plain sh rejects it
+        let item = VerificationItem {
+            command: "cat <(echo hello)".to_string(),
+            shell: Some("bash".to_string()),
+            ..VerificationItem::default()
         };
 
-        let mut results = VerifyResults::new();
-        let mut doc_result = DocumentResult::new(&spec);
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+        );
 
-        doc_result.add_result(CommandResult {
-            command: "echo 1".to_string(),
-            status: VerifyStatus::Pass,
-            exit_code: Some(0),
-            expected_exit_code: 0,
-            stdout: None,
-            stderr: None,
-            duration_ms: Some(10),
-            output_mismatch: None,
-            working_dir: None,
-            env_vars: Vec::new(),
-        });
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert!(result.stdout.as_ref().is_some_and(|s| s.contains("hello")));
+    }
 
-        doc_result.add_result(CommandResult {
-            command: "false".to_string(),
-            status: VerifyStatus::Fail,
-            exit_code: Some(1),
-            expected_exit_code: 0,
-            stdout: None,
-            stderr: None,
-            duration_ms: Some(5),
-            output_mismatch: None,
-            working_dir: None,
-            env_vars: Vec::new(),
-        });
+    #[test]
+    fn run_command_pipes_script_to_interpreter() {
+        let item = VerificationItem {
+            command: "hello".to_string(),
+            language: Some("text".to_string()),
+            script: Some("hello from stdin\n".to_string()),
+            expected_output: Some(OutputMatcher::Contains("from stdin".to_string())),
+            ..VerificationItem::default()
+        };
+        let verify = VerifySection {
+            interpreters: [("text".to_string(), "cat".to_string())].into(),
+            ..VerifySection::default()
+        };
 
-        results.add_document(doc_result);
+        let result = run_command(
+            &item,
+            Duration::from_secs(30),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &verify,
+                secrets: &Secrets::default(),
+            },
+        );
 
-        assert_eq!(results.documents_verified, 1);
-        assert_eq!(results.commands_executed, 2);
-        assert_eq!(results.commands_passed, 1);
-        assert_eq!(results.commands_failed, 1);
-        assert!(!results.is_success());
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert_eq!(result.stdout.as_deref(), Some("hello from stdin\n"));
     }
 
     #[test]
-    fn run_command_success() {
+    fn run_command_rejects_unknown_shell() {
         let item = VerificationItem {
-            command: "echo hello".to_string(),
-            working_dir: None,
-            expected_exit_code: Some(0),
-            expected_output: None,
-            timeout_secs: Some(30),
-            env_vars: Vec::new(),
+            command: "echo hi".to_string(),
+            shell: Some("fish".to_string()),
+            ..VerificationItem::default()
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &default_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
-        assert_eq!(result.status, VerifyStatus::Pass);
-        assert_eq!(result.exit_code, Some(0));
-        assert!(result.stdout.as_ref().is_some_and(|s| s.contains("hello")));
+        assert_eq!(result.status, VerifyStatus::Fail);
+        assert!(
+            result
+                .preflight_error
+                .as_ref()
+                .is_some_and(|e| e.contains("unknown shell"))
+        );
     }
 
     #[test]
@@ -958,14 +3348,28 @@ Example here.
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &default_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Fail);
@@ -981,14 +3385,28 @@ Example here.
             expected_exit_code: Some(1),
             expected_output: None,
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &default_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Pass);
@@ -1001,6 +3419,10 @@ Example here.
             source_file: PathBuf::from("test.md"),
             section_line: 10,
             items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
         };
 
         let mut results = VerifyResults::new();
@@ -1016,6 +3438,9 @@ Example here.
             output_mismatch: None,
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
         results.add_document(doc_result);
 
@@ -1037,7 +3462,7 @@ Example here.
         fs::write(docs_dir.join("doc1.md"), "# Doc 1").unwrap();
         fs::write(nested_dir.join("doc2.md"), "# Doc 2").unwrap();
 
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], &PaveIgnore::default()).unwrap();
 
         assert_eq!(files.len(), 2);
     }
@@ -1056,8 +3481,13 @@ Example here.
             &spec,
             Duration::from_secs(30),
             true,
-            temp_dir.path(),
-            &default_rules(),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
         )
         .unwrap();
 
@@ -1071,6 +3501,453 @@ Example here.
         );
     }
 
+    #[test]
+    fn is_affected_when_doc_itself_changed() {
+        let config_dir = Path::new("/repo");
+        let changed: HashSet<PathBuf> = [PathBuf::from("docs/a.md")].into_iter().collect();
+
+        assert!(is_affected(
+            Path::new("/repo/docs/a.md"),
+            &[],
+            &changed,
+            config_dir
+        ));
+        assert!(!is_affected(
+            Path::new("/repo/docs/b.md"),
+            &[],
+            &changed,
+            config_dir
+        ));
+    }
+
+    #[test]
+    fn is_affected_when_covered_code_changed() {
+        let config_dir = Path::new("/repo");
+        let changed: HashSet<PathBuf> = [PathBuf::from("src/auth/login.rs")].into_iter().collect();
+        let doc = Path::new("/repo/docs/auth.md");
+
+        assert!(is_affected(
+            doc,
+            &["src/auth/".to_string()],
+            &changed,
+            config_dir
+        ));
+        assert!(is_affected(
+            doc,
+            &["src/**/*.rs".to_string()],
+            &changed,
+            config_dir
+        ));
+        assert!(!is_affected(
+            doc,
+            &["src/billing/".to_string()],
+            &changed,
+            config_dir
+        ));
+    }
+
+    fn fixture_spec(setup: &[&str], items: &[&str], teardown: &[&str]) -> VerificationSpec {
+        let to_items = |commands: &[&str]| {
+            commands
+                .iter()
+                .map(|command| VerificationItem {
+                    command: command.to_string(),
+                    ..VerificationItem::default()
+                })
+                .collect()
+        };
+        VerificationSpec {
+            source_file: PathBuf::from("fixture.md"),
+            section_line: 1,
+            items: to_items(items),
+            setup: to_items(setup),
+            teardown: to_items(teardown),
+            compose: None,
+            services: Vec::new(),
+        }
+    }
+
+    fn run_spec(spec: &VerificationSpec, working_dir: &Path) -> DocumentResult {
+        run_verification(
+            spec,
+            Duration::from_secs(30),
+            false,
+            &RunContext {
+                working_dir,
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn setup_and_teardown_wrap_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = fixture_spec(&["touch ready"], &["test -f ready"], &["rm ready"]);
+
+        let result = run_spec(&spec, temp_dir.path());
+
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert_eq!(result.setup.len(), 1);
+        assert_eq!(result.commands[0].status, VerifyStatus::Pass);
+        assert_eq!(result.teardown[0].status, VerifyStatus::Pass);
+        assert!(!temp_dir.path().join("ready").exists());
+    }
+
+    #[test]
+    fn failed_setup_skips_commands_but_runs_teardown() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = fixture_spec(
+            &["touch partial", "false", "echo never"],
+            &["echo one", "echo two"],
+            &["rm partial"],
+        );
+
+        let result = run_spec(&spec, temp_dir.path());
+
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert_eq!(result.setup.len(), 2);
+        assert!(
+            result
+                .commands
+                .iter()
+                .all(|cmd| cmd.status == VerifyStatus::Skipped)
+        );
+        assert_eq!(result.teardown[0].status, VerifyStatus::Pass);
+        assert!(!temp_dir.path().join("partial").exists());
+
+        let mut results = VerifyResults::new();
+        results.add_document(result);
+        assert_eq!(results.fixtures_failed, 1);
+        assert!(!results.is_success());
+    }
+
+    #[test]
+    fn failed_teardown_fails_passing_document() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = fixture_spec(&[], &["true"], &["false"]);
+
+        let result = run_spec(&spec, temp_dir.path());
+        assert_eq!(result.status, VerifyStatus::TeardownFailed);
+        assert!(!result.is_success());
+
+        // A failing command takes precedence over the teardown failure
+        let spec = fixture_spec(&[], &["exit 3"], &["false"]);
+        let result = run_spec(&spec, temp_dir.path());
+        assert_eq!(result.status, VerifyStatus::Fail);
+    }
+
+    #[test]
+    fn compose_services_wrap_setup_and_teardown() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("compose.yaml"), "services: {}\n").unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+        let verify = VerifySection {
+            compose_cmd: Some("echo compose".to_string()),
+            ..VerifySection::default()
+        };
+        let mut spec = fixture_spec(&["true"], &["true"], &["true"]);
+        spec.compose = Some(ComposeFixture {
+            bootstrap: crate::compose::ComposeBootstrap {
+                file: None,
+                up: vec!["db".to_string()],
+                wait_for: vec![endpoint.clone()],
+                wait_timeout: 5,
+            },
+            working_dir: None,
+        });
+        let run = |spec: &VerificationSpec| {
+            let rules = default_rules();
+            let timeout = Duration::from_secs(30);
+            run_verification(
+                spec,
+                timeout,
+                false,
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &rules,
+                    verify: &verify,
+                    secrets: &Secrets::default(),
+                },
+                None,
+            )
+            .unwrap()
+        };
+
+        let result = run(&spec);
+        assert_eq!(result.status, VerifyStatus::Pass);
+        let setup: Vec<&str> = result.setup.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(
+            setup,
+            vec![
+                "echo compose -f compose.yaml up -d db",
+                &format!("wait for {}", endpoint),
+                "true"
+            ]
+        );
+        let teardown: Vec<&str> = result.teardown.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(teardown, vec!["true", "echo compose -f compose.yaml down"]);
+
+        // Services that never come up skip the commands but are still stopped
+        drop(listener);
+        spec.compose.as_mut().unwrap().bootstrap.wait_timeout = 0;
+        let result = run(&spec);
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert_eq!(result.setup[1].status, VerifyStatus::Fail);
+        assert_eq!(result.setup.len(), 2);
+        assert_eq!(
+            result.teardown.last().unwrap().command,
+            "echo compose -f compose.yaml down"
+        );
+    }
+
+    #[test]
+    fn services_start_first_and_export_variables_to_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let verify = VerifySection {
+            container_cmd: Some("echo docker".to_string()),
+            ..VerifySection::default()
+        };
+        let mut spec = fixture_spec(
+            &["test \"$CACHE_URL\" = redis://127.0.0.1"],
+            &["test \"$CACHE_URL\" = redis://127.0.0.1"],
+            &[],
+        );
+        spec.services = vec![
+            serde_yaml::from_str(
+                "image: redis:7\nname: cache\nexport: { CACHE_URL: 'redis://{host}' }",
+            )
+            .unwrap(),
+        ];
+        let run = |spec: &VerificationSpec| {
+            let rules = default_rules();
+            let timeout = Duration::from_secs(30);
+            run_verification(
+                spec,
+                timeout,
+                false,
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &rules,
+                    verify: &verify,
+                    secrets: &Secrets::default(),
+                },
+                None,
+            )
+            .unwrap()
+        };
+
+        let result = run(&spec);
+        assert_eq!(result.status, VerifyStatus::Pass);
+        let container = format!("pave-cache-{}-0", std::process::id());
+        assert_eq!(
+            result.setup[0].command,
+            format!("echo docker run -d --rm --name {} redis:7", container)
+        );
+        assert_eq!(
+            result.teardown.last().unwrap().command,
+            format!("echo docker rm -f {}", container)
+        );
+
+        // A service that never accepts connections skips the commands but
+        // is still stopped
+        spec.services[0].port = Some(6379);
+        spec.services[0].wait_timeout = 0;
+        let result = run(&spec);
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert_eq!(result.setup.len(), 2);
+        assert_eq!(result.setup[1].status, VerifyStatus::Fail);
+        assert_eq!(
+            result.teardown.last().unwrap().command,
+            format!("echo docker rm -f {}", container)
+        );
+
+        let mut invalid = spec.clone();
+        invalid.services[0].compose = Some("compose.yaml".to_string());
+        let result = run(&invalid);
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert!(result.teardown.is_empty());
+    }
+
+    #[test]
+    fn services_with_a_healthcheck_wait_for_it_instead_of_their_port() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("docker.sh"),
+            "if [ \"$1\" = inspect ]; then cat health; fi\n",
+        )
+        .unwrap();
+        let verify = VerifySection {
+            container_cmd: Some("sh docker.sh".to_string()),
+            ..VerifySection::default()
+        };
+        let mut spec = fixture_spec(&[], &["true"], &[]);
+        // Nothing listens on the published port
+        spec.services =
+            vec![serde_yaml::from_str("image: redis:7\nport: 6379\nwait_timeout: 1").unwrap()];
+        let run = |health: &str| {
+            fs::write(temp_dir.path().join("health"), health).unwrap();
+            let (dir, rules, timeout) = (temp_dir.path(), default_rules(), Duration::from_secs(30));
+            run_verification(
+                &spec,
+                timeout,
+                false,
+                &RunContext {
+                    working_dir: dir,
+                    rules: &rules,
+                    verify: &verify,
+                    secrets: &Secrets::default(),
+                },
+                None,
+            )
+            .unwrap()
+        };
+
+        let result = run("healthy\n");
+        assert_eq!(result.status, VerifyStatus::Pass);
+        assert!(result.setup[1].command.starts_with("sh docker.sh inspect"));
+
+        let result = run("unhealthy\n");
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert_eq!(result.setup.len(), 2);
+        let stderr = result.setup[1].stderr.as_deref();
+        assert_eq!(stderr, Some("service is unhealthy"));
+
+        // Without a healthcheck the port is waited for
+        let result = run("\n");
+        assert_eq!(result.status, VerifyStatus::SetupFailed);
+        assert!(result.setup[1].command.starts_with(WAIT_PREFIX));
+    }
+
+    #[test]
+    fn fully_cached_documents_skip_fixtures() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = fixture_spec(&["touch setup-ran"], &["true"], &[]);
+        let mut cache = VerifyCache::default();
+
+        for _ in 0..2 {
+            let _ = fs::remove_file(temp_dir.path().join("setup-ran"));
+            let scope = CacheScope {
+                cache: &mut cache,
+                doc: "fixture.md".to_string(),
+                inputs_hash: "inputs",
+                lookup: true,
+            };
+            run_verification(
+                &spec,
+                Duration::from_secs(30),
+                false,
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+                Some(scope),
+            )
+            .unwrap();
+        }
+
+        assert!(!temp_dir.path().join("setup-ran").exists());
+    }
+
+    #[test]
+    fn cached_passes_are_not_rerun() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+        let doc_path =
+            create_doc_with_verification(&temp_dir, "cached.md", &["echo hello", "false"]);
+
+        let doc = ParsedDoc::parse(&doc_path).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let passing = VerificationSpec {
+            items: spec.items[..1].to_vec(),
+            ..spec.clone()
+        };
+        let mut cache = VerifyCache::default();
+
+        let run = |spec: &VerificationSpec, cache: &mut VerifyCache, inputs_hash: &str, lookup| {
+            let scope = CacheScope {
+                cache,
+                doc: "cached.md".to_string(),
+                inputs_hash,
+                lookup,
+            };
+            run_verification(
+                spec,
+                Duration::from_secs(30),
+                true,
+                &RunContext {
+                    working_dir: temp_dir.path(),
+                    rules: &default_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+                Some(scope),
+            )
+            .unwrap()
+        };
+
+        let first = run(&spec, &mut cache, "inputs-1", true);
+        assert_eq!(first.commands[0].status, VerifyStatus::Pass);
+        assert_eq!(first.commands[1].status, VerifyStatus::Fail);
+
+        // Later commands may rely on earlier ones' side effects, so nothing
+        // is skipped until every command has passed
+        let second = run(&spec, &mut cache, "inputs-1", true);
+        assert_eq!(second.commands[0].status, VerifyStatus::Pass);
+        assert_eq!(second.commands[1].status, VerifyStatus::Fail);
+
+        let cached = run(&passing, &mut cache, "inputs-1", true);
+        assert_eq!(cached.commands[0].status, VerifyStatus::Cached);
+
+        // Changed inputs invalidate the cache
+        let changed = run(&passing, &mut cache, "inputs-2", true);
+        assert_eq!(changed.commands[0].status, VerifyStatus::Pass);
+
+        // --no-cache re-runs even when a cached pass exists
+        let forced = run(&passing, &mut cache, "inputs-1", false);
+        assert_eq!(forced.commands[0].status, VerifyStatus::Pass);
+    }
+
+    #[test]
+    fn cached_commands_are_not_counted_as_executed() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = VerificationSpec {
+            source_file: temp_dir.path().join("doc.md"),
+            section_line: 1,
+            items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
+        };
+        let item = VerificationItem {
+            command: "true".to_string(),
+            ..VerificationItem::default()
+        };
+        let entry = CacheEntry {
+            doc: "doc.md".to_string(),
+            command: "true".to_string(),
+            exit_code: Some(0),
+            recorded_at: String::new(),
+        };
+
+        let mut doc_result = DocumentResult::new(&spec);
+        doc_result.add_result(cached_result(&item, &entry));
+        let mut results = VerifyResults::new();
+        results.add_document(doc_result);
+
+        assert!(results.is_success());
+        assert_eq!(results.commands_cached, 1);
+        assert_eq!(results.commands_executed, 0);
+        assert_eq!(results.commands_passed, 0);
+    }
+
     #[test]
     fn integration_verify_failing_document() {
         let temp_dir = TempDir::new().unwrap();
@@ -1085,8 +3962,13 @@ Example here.
             &spec,
             Duration::from_secs(30),
             true,
-            temp_dir.path(),
-            &default_rules(),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
         )
         .unwrap();
 
@@ -1095,6 +3977,56 @@ Example here.
         assert_eq!(doc_result.commands[1].status, VerifyStatus::Fail);
     }
 
+    #[test]
+    fn prompts_of_a_block_share_one_shell() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+        fs::create_dir_all(temp_dir.path().join("sub")).unwrap();
+        let doc_path = temp_dir.path().join("session.md");
+        fs::write(
+            &doc_path,
+            "# Session\n\n## Verification\n<!-- pave:exit-code 3 -->\n```bash\n\
+             $ cd sub && export GREETING=hi\n\
+             $ basename \"$PWD\"\nsub\n\
+             $ echo \"$GREETING\"; exit 3\nhi\n```\n\n\
+             ```bash\n$ false\nnever\n$ echo skipped\nskipped\n```\n",
+        )
+        .unwrap();
+
+        let doc = ParsedDoc::parse(&doc_path).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let doc_result = run_verification(
+            &spec,
+            Duration::from_secs(30),
+            true,
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
+        )
+        .unwrap();
+
+        let outcomes: Vec<(VerifyStatus, Option<i32>, Option<&str>)> = doc_result
+            .commands
+            .iter()
+            .map(|result| (result.status, result.exit_code, result.stdout.as_deref()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (VerifyStatus::Pass, Some(0), None),
+                (VerifyStatus::Pass, Some(0), Some("sub\n")),
+                (VerifyStatus::Pass, Some(3), Some("hi\n")),
+                // A failing prompt ends its block's shell
+                (VerifyStatus::Fail, Some(1), None),
+                (VerifyStatus::Skipped, None, None),
+            ]
+        );
+    }
+
     #[test]
     fn integration_keep_going_false_skips_remaining() {
         let temp_dir = TempDir::new().unwrap();
@@ -1112,8 +4044,13 @@ Example here.
             &spec,
             Duration::from_secs(30),
             false,
-            temp_dir.path(),
-            &default_rules(),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
         )
         .unwrap();
 
@@ -1140,8 +4077,13 @@ Example here.
             &spec,
             Duration::from_secs(30),
             true,
-            temp_dir.path(),
-            &default_rules(),
+            &RunContext {
+                working_dir: temp_dir.path(),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
+            None,
         )
         .unwrap();
 
@@ -1160,14 +4102,28 @@ Example here.
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &default_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Warn);
@@ -1186,20 +4142,107 @@ Example here.
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &strict_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &strict_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Fail);
         assert!(result.output_mismatch.is_some());
     }
 
+    #[test]
+    fn exact_mismatch_renders_diff_and_line_arrays() {
+        let mismatch = OutputMismatch::new(
+            "one\ntwo\nthree".to_string(),
+            "exact",
+            "one\n2\nthree\n".to_string(),
+        );
+
+        assert_eq!(
+            mismatch.diff(false).unwrap(),
+            vec!["@@ -1,3 +1,3 @@", " one", "-two", "+2", " three"]
+        );
+        let json = serde_json::to_value(&mismatch).unwrap();
+        assert_eq!(
+            json["expected_lines"],
+            serde_json::json!(["one", "two", "three"])
+        );
+        assert_eq!(
+            json["actual_lines"],
+            serde_json::json!(["one", "2", "three"])
+        );
+
+        let regex = OutputMismatch::new("^t.o$".to_string(), "regex", "one".to_string());
+        assert!(regex.diff(false).is_none());
+    }
+
+    #[test]
+    fn forbidden_output_is_reported_as_mismatch() {
+        let item = |command: &str| VerificationItem {
+            command: command.to_string(),
+            working_dir: None,
+            expected_exit_code: Some(0),
+            expected_output: Some(OutputMatcher::NotContains("deprecated".to_string())),
+            timeout_secs: Some(30),
+            retries: 0,
+            env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
+        };
+        let run = |item: &VerificationItem| {
+            run_command(
+                item,
+                Duration::from_secs(30),
+                &RunContext {
+                    working_dir: Path::new("."),
+                    rules: &strict_rules(),
+                    verify: &VerifySection::default(),
+                    secrets: &Secrets::default(),
+                },
+            )
+        };
+
+        let result = run(&item("echo all good"));
+        assert_eq!(result.status, VerifyStatus::Pass);
+
+        let result = run(&item("printf 'ok\\nwarning: deprecated flag\\n'"));
+        assert_eq!(result.status, VerifyStatus::Fail);
+        let mismatch = result.output_mismatch.unwrap();
+        assert_eq!(mismatch.strategy, "not_contains");
+        assert_eq!(mismatch.forbidden_line(), "warning: deprecated flag");
+        assert_eq!(
+            mismatch.summary(),
+            "Output contains forbidden text 'deprecated': 'warning: deprecated flag'"
+        );
+    }
+
     #[test]
     fn output_mismatch_ignored_with_skip_mode() {
         let item = VerificationItem {
@@ -1208,14 +4251,28 @@ Example here.
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &skip_output_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &skip_output_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Pass);
@@ -1230,14 +4287,28 @@ Example here.
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("hello".to_string())),
             timeout_secs: Some(30),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_command(
             &item,
             Duration::from_secs(30),
-            Path::new("."),
-            &default_rules(),
+            &RunContext {
+                working_dir: Path::new("."),
+                rules: &default_rules(),
+                verify: &VerifySection::default(),
+                secrets: &Secrets::default(),
+            },
         );
 
         assert_eq!(result.status, VerifyStatus::Pass);
@@ -1250,6 +4321,10 @@ Example here.
             source_file: PathBuf::from("test.md"),
             section_line: 10,
             items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
         };
 
         let mut doc_result = DocumentResult::new(&spec);
@@ -1261,13 +4336,16 @@ Example here.
             stdout: Some("actual".to_string()),
             stderr: None,
             duration_ms: Some(10),
-            output_mismatch: Some(OutputMismatch {
-                expected: "expected".to_string(),
-                strategy: "contains".to_string(),
-                actual: "actual".to_string(),
-            }),
+            output_mismatch: Some(OutputMismatch::new(
+                "expected".to_string(),
+                "contains",
+                "actual".to_string(),
+            )),
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
 
         // Warn is still considered success
@@ -1282,6 +4360,10 @@ Example here.
             source_file: PathBuf::from("test.md"),
             section_line: 10,
             items: vec![],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
         };
 
         let mut results = VerifyResults::new();
@@ -1298,6 +4380,9 @@ Example here.
             output_mismatch: None,
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
 
         doc_result.add_result(CommandResult {
@@ -1308,13 +4393,16 @@ Example here.
             stdout: Some("actual".to_string()),
             stderr: None,
             duration_ms: Some(5),
-            output_mismatch: Some(OutputMismatch {
-                expected: "expected".to_string(),
-                strategy: "contains".to_string(),
-                actual: "actual".to_string(),
-            }),
+            output_mismatch: Some(OutputMismatch::new(
+                "expected".to_string(),
+                "contains",
+                "actual".to_string(),
+            )),
             working_dir: None,
             env_vars: Vec::new(),
+            preflight_error: None,
+            container: None,
+            host: None,
         });
 
         results.add_document(doc_result);
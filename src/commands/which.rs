@@ -0,0 +1,302 @@
+//! Implementation of the `pave which` command.
+//!
+//! The inverse of `pave coverage`: given a code file, lists the documents
+//! whose `## Paths` section or frontmatter `pave.paths` cover it. If none do,
+//! suggests the document whose patterns come closest, judged by how many
+//! leading directories a pattern shares with the file.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+use crate::cli::{PathStyle, WhichOutputFormat};
+use crate::commands::coverage::{DocMapping, load_doc_mappings, matches_any_pattern};
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::paths::display_path;
+use crate::schema::SCHEMA_VERSION;
+use crate::walk::load_ignore;
+use crate::workspace::Workdir;
+
+/// Arguments for the `pave which` command.
+pub struct WhichArgs {
+    /// Code file to look up.
+    pub path: PathBuf,
+    /// Output format.
+    pub format: WhichOutputFormat,
+    /// How to display file paths.
+    pub path_style: PathStyle,
+}
+
+/// A document covering the file.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct CoveringDoc {
+    /// Path to the document.
+    pub doc: PathBuf,
+    /// H1 title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The document's patterns that match the file.
+    pub patterns: Vec<String>,
+}
+
+/// The document closest to covering an uncovered file.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct NearestDoc {
+    /// Path to the document.
+    pub doc: PathBuf,
+    /// H1 title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The document's pattern sharing the most leading directories with the
+    /// file.
+    pub pattern: String,
+}
+
+/// Results of the which command.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct WhichResults {
+    /// Version of this output's JSON schema.
+    pub schema_version: u32,
+    /// The file looked up, relative to the project root.
+    pub file: PathBuf,
+    /// Documents covering the file, by path.
+    pub docs: Vec<CoveringDoc>,
+    /// Closest document, if no document covers the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nearest: Option<NearestDoc>,
+}
+
+/// Execute the `pave which` command.
+pub fn execute(args: WhichArgs, workdir: &Workdir) -> Result<()> {
+    let config_path = workdir.find_config()?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    // Patterns are relative to the project root, wherever pave is run from
+    let file = display_path(&args.path, PathStyle::Workspace, config_dir, &workdir.dir);
+    if file.is_absolute() {
+        anyhow::bail!(
+            "{} is outside the project at {}",
+            args.path.display(),
+            config_dir.display()
+        );
+    }
+
+    let docs_root = config_dir.join(&config.docs.root);
+    let ignore = load_ignore(config_dir, &config)?;
+    let mappings = load_doc_mappings(&docs_root, config_dir, &ignore)?;
+    let mut results = which(&file, &mappings)?;
+    for doc in &mut results.docs {
+        doc.doc = display_path(&doc.doc, args.path_style, config_dir, &workdir.dir);
+    }
+    if let Some(nearest) = &mut results.nearest {
+        nearest.doc = display_path(&nearest.doc, args.path_style, config_dir, &workdir.dir);
+    }
+
+    match args.format {
+        WhichOutputFormat::Text => output_text(&results),
+        WhichOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Find the documents covering `file`, a path relative to the project root.
+pub(crate) fn which(file: &Path, mappings: &[DocMapping]) -> Result<WhichResults> {
+    let mut docs = Vec::new();
+    for mapping in mappings {
+        let patterns: Vec<String> = mapping
+            .patterns
+            .iter()
+            .filter(|pattern| matches_any_pattern(file, &[pattern.as_str()]))
+            .cloned()
+            .collect();
+        if !patterns.is_empty() {
+            docs.push(CoveringDoc {
+                doc: mapping.doc.clone(),
+                title: doc_title(&mapping.doc)?,
+                patterns,
+            });
+        }
+    }
+    docs.sort_by(|a, b| a.doc.cmp(&b.doc));
+
+    let nearest = if docs.is_empty() {
+        nearest_doc(file, mappings)?
+    } else {
+        None
+    };
+    Ok(WhichResults {
+        schema_version: SCHEMA_VERSION,
+        file: file.to_path_buf(),
+        docs,
+        nearest,
+    })
+}
+
+/// The document with the pattern sharing the most leading directories with
+/// `file`, if any shares at least one. Ties go to the first document by path.
+fn nearest_doc(file: &Path, mappings: &[DocMapping]) -> Result<Option<NearestDoc>> {
+    let dirs: Vec<Component> = file
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .collect();
+
+    let mut best: Option<(usize, &DocMapping, &String)> = None;
+    for mapping in mappings {
+        for pattern in &mapping.patterns {
+            let shared = Path::new(pattern)
+                .components()
+                .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+                .zip(&dirs)
+                .take_while(|(a, b)| a == *b)
+                .count();
+            let is_better = match best {
+                None => shared > 0,
+                Some((best_shared, best_mapping, _)) => {
+                    shared > best_shared
+                        || (shared == best_shared && mapping.doc < best_mapping.doc)
+                }
+            };
+            if is_better {
+                best = Some((shared, mapping, pattern));
+            }
+        }
+    }
+
+    let Some((_, mapping, pattern)) = best else {
+        return Ok(None);
+    };
+    Ok(Some(NearestDoc {
+        doc: mapping.doc.clone(),
+        title: doc_title(&mapping.doc)?,
+        pattern: pattern.clone(),
+    }))
+}
+
+fn doc_title(path: &Path) -> Result<Option<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    Ok(ParsedDoc::parse_content(path.to_path_buf(), &content)?.title)
+}
+
+fn output_text(results: &WhichResults) {
+    if results.docs.is_empty() {
+        println!("No docs cover {}", results.file.display());
+        if let Some(nearest) = &results.nearest {
+            println!();
+            println!(
+                "Nearest: {}{} (covers {})",
+                nearest.doc.display(),
+                nearest
+                    .title
+                    .as_deref()
+                    .map(|title| format!(": {}", title))
+                    .unwrap_or_default(),
+                nearest.pattern
+            );
+            println!("Add the file to its ## Paths section if it belongs there.");
+        }
+        return;
+    }
+
+    println!(
+        "{} is covered by {} doc{}:",
+        results.file.display(),
+        results.docs.len(),
+        if results.docs.len() == 1 { "" } else { "s" }
+    );
+    for doc in &results.docs {
+        println!(
+            "  {}{}",
+            doc.doc.display(),
+            doc.title
+                .as_deref()
+                .map(|title| format!(": {}", title))
+                .unwrap_or_default()
+        );
+        println!("      via {}", doc.patterns.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Vec<DocMapping>) {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        let mut mappings = Vec::new();
+        for (name, patterns) in [
+            ("auth.md", vec!["src/auth/**", "src/session.rs"]),
+            ("api.md", vec!["src/api/*.rs", "src/auth/token.rs"]),
+            ("billing.md", vec!["services/billing/invoices/**"]),
+        ] {
+            let doc = docs.join(name);
+            fs::write(&doc, format!("# {}\n", name)).unwrap();
+            mappings.push(DocMapping {
+                doc,
+                patterns: patterns.into_iter().map(str::to_string).collect(),
+            });
+        }
+        (temp_dir, mappings)
+    }
+
+    #[test]
+    fn lists_docs_with_matching_patterns() {
+        let (_temp_dir, mappings) = setup();
+
+        let results = which(Path::new("src/auth/token.rs"), &mappings).unwrap();
+        let docs: Vec<(String, Vec<String>)> = results
+            .docs
+            .iter()
+            .map(|d| {
+                (
+                    d.doc.file_name().unwrap().to_string_lossy().into_owned(),
+                    d.patterns.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            docs,
+            vec![
+                ("api.md".to_string(), vec!["src/auth/token.rs".to_string()]),
+                ("auth.md".to_string(), vec!["src/auth/**".to_string()]),
+            ]
+        );
+        assert_eq!(results.docs[1].title.as_deref(), Some("auth.md"));
+        assert_eq!(results.nearest, None);
+    }
+
+    #[test]
+    fn suggests_nearest_doc_for_uncovered_file() {
+        let (_temp_dir, mappings) = setup();
+
+        let results = which(Path::new("services/billing/refunds/api.rs"), &mappings).unwrap();
+        assert!(results.docs.is_empty());
+        let nearest = results.nearest.unwrap();
+        assert!(nearest.doc.ends_with("billing.md"));
+        assert_eq!(nearest.pattern, "services/billing/invoices/**");
+
+        // Ties go to the first doc by path
+        let nearest = which(Path::new("src/cli/main.rs"), &mappings)
+            .unwrap()
+            .nearest
+            .unwrap();
+        assert!(nearest.doc.ends_with("api.md"));
+
+        assert_eq!(
+            which(Path::new("README.md"), &mappings).unwrap().nearest,
+            None
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Docker Compose bootstrap for service verification.
+//!
+//! Documents for services can set `pave.verify.compose` in frontmatter to
+//! bring up their dependencies before `pave verify` runs the doc's commands:
+//!
+//! ```yaml
+//! pave:
+//!   verify:
+//!     compose:
+//!       file: docker-compose.yml
+//!       up: [db, redis]
+//!       wait_for: ["tcp://localhost:5432"]
+//! ```
+//!
+//! The services are started before any setup commands, polled until every
+//! `wait_for` target accepts connections, and torn down after the teardown
+//! commands.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::verify_export::quote;
+
+/// Compose file names looked for when `file` isn't set, in order.
+pub const COMPOSE_FILENAMES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Compose command used when `[verify] compose_cmd` isn't set.
+pub const DEFAULT_COMPOSE_CMD: &str = "docker compose";
+
+/// How long to wait for `wait_for` targets by default, in seconds.
+const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 60;
+
+/// Delay between connection attempts while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Services to bring up around a document's verification commands.
+#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq)]
+pub struct ComposeBootstrap {
+    /// Compose file, relative to the document's working directory.
+    /// Detected from [`COMPOSE_FILENAMES`] when unset.
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Services to start. Empty starts every service in the file.
+    #[serde(default)]
+    pub up: Vec<String>,
+    /// Endpoints that must accept connections before commands run,
+    /// such as `tcp://localhost:5432`.
+    #[serde(default)]
+    pub wait_for: Vec<String>,
+    /// Seconds to wait for all `wait_for` endpoints.
+    #[serde(default = "default_wait_timeout")]
+    pub wait_timeout: u64,
+}
+
+fn default_wait_timeout() -> u64 {
+    DEFAULT_WAIT_TIMEOUT_SECS
+}
+
+impl ComposeBootstrap {
+    /// Resolve the compose file in `dir`: the configured file, or the first
+    /// of [`COMPOSE_FILENAMES`] that exists.
+    pub fn resolve_file(&self, dir: &Path) -> Result<String, String> {
+        if let Some(file) = &self.file {
+            return if dir.join(file).is_file() {
+                Ok(file.clone())
+            } else {
+                Err(format!("compose file not found: {}", file))
+            };
+        }
+        COMPOSE_FILENAMES
+            .iter()
+            .find(|name| dir.join(name).is_file())
+            .map(|name| name.to_string())
+            .ok_or_else(|| {
+                format!(
+                    "no compose file found (looked for {})",
+                    COMPOSE_FILENAMES.join(", ")
+                )
+            })
+    }
+
+    /// Command that starts the services in the background.
+    pub fn up_command(&self, compose_cmd: &str, file: &str) -> String {
+        let mut command = format!("{} -f {} up -d", compose_cmd, quote(file));
+        for service in &self.up {
+            command.push(' ');
+            command.push_str(&quote(service));
+        }
+        command
+    }
+
+    /// Command that stops and removes the services.
+    pub fn down_command(&self, compose_cmd: &str, file: &str) -> String {
+        format!("{} -f {} down", compose_cmd, quote(file))
+    }
+
+    /// Total time allowed for the `wait_for` endpoints.
+    pub fn wait_timeout(&self) -> Duration {
+        Duration::from_secs(self.wait_timeout)
+    }
+}
+
+/// Parse a `tcp://host:port` endpoint into its address.
+pub fn parse_endpoint(endpoint: &str) -> Result<String, String> {
+    let address = endpoint
+        .strip_prefix("tcp://")
+        .ok_or_else(|| format!("unsupported endpoint '{}': use tcp://host:port", endpoint))?;
+    let valid = address
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+    if !valid {
+        return Err(format!(
+            "invalid endpoint '{}': use tcp://host:port",
+            endpoint
+        ));
+    }
+    Ok(address.to_string())
+}
+
+/// Poll an endpoint until it accepts a connection or `deadline` passes.
+pub fn wait_for(endpoint: &str, deadline: Instant) -> Result<(), String> {
+    let address = parse_endpoint(endpoint)?;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if try_connect(&address, remaining.min(Duration::from_secs(1))) {
+            return Ok(());
+        }
+        if Instant::now() + POLL_INTERVAL >= deadline {
+            return Err(format!("timed out waiting for {}", endpoint));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Whether any address `address` resolves to accepts a TCP connection.
+fn try_connect(address: &str, timeout: Duration) -> bool {
+    let timeout = timeout.max(Duration::from_millis(50));
+    address
+        .to_socket_addrs()
+        .is_ok_and(|mut addrs| addrs.any(|addr| TcpStream::connect_timeout(&addr, timeout).is_ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    fn bootstrap(file: Option<&str>, up: &[&str]) -> ComposeBootstrap {
+        ComposeBootstrap {
+            file: file.map(str::to_string),
+            up: up.iter().map(|s| s.to_string()).collect(),
+            wait_for: vec![],
+            wait_timeout: DEFAULT_WAIT_TIMEOUT_SECS,
+        }
+    }
+
+    #[test]
+    fn detects_compose_file_and_builds_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let compose = bootstrap(None, &["db", "redis"]);
+        assert!(compose.resolve_file(temp_dir.path()).is_err());
+
+        fs::write(temp_dir.path().join("docker-compose.yml"), "services: {}\n").unwrap();
+        let file = compose.resolve_file(temp_dir.path()).unwrap();
+        assert_eq!(file, "docker-compose.yml");
+        assert_eq!(
+            compose.up_command(DEFAULT_COMPOSE_CMD, &file),
+            "docker compose -f docker-compose.yml up -d db redis"
+        );
+        assert_eq!(
+            compose.down_command("podman compose", &file),
+            "podman compose -f docker-compose.yml down"
+        );
+
+        let missing = bootstrap(Some("deploy/compose.yml"), &[]);
+        assert!(missing.resolve_file(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn parses_tcp_endpoints() {
+        assert_eq!(
+            parse_endpoint("tcp://localhost:5432").unwrap(),
+            "localhost:5432"
+        );
+        assert!(parse_endpoint("http://localhost:8080").is_err());
+        assert!(parse_endpoint("tcp://localhost").is_err());
+        assert!(parse_endpoint("tcp://:80").is_err());
+    }
+
+    #[test]
+    fn waits_until_endpoint_accepts_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let endpoint = format!("tcp://127.0.0.1:{}", port);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert!(wait_for(&endpoint, deadline).is_ok());
+
+        drop(listener);
+        let deadline = Instant::now() + Duration::from_millis(300);
+        let err = wait_for(&endpoint, deadline).unwrap_err();
+        assert!(err.contains("timed out"));
+    }
+}
@@ -4,15 +4,27 @@
 //! functions for loading, validating, and saving configuration files.
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::compose::DEFAULT_COMPOSE_CMD;
+use crate::container::DEFAULT_CONTAINER_CMD;
+use crate::presets;
+use crate::remote::DEFAULT_SSH_CMD;
+use crate::rules::DocType;
+
 /// The default configuration filename.
 pub const CONFIG_FILENAME: &str = ".pave.toml";
 
 /// Root configuration structure for a pave project.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct PaveConfig {
+    /// Presets this config inherits settings from, in order, e.g.
+    /// `"pave:recommended"` or `"github:acme/pave-rules@v1#sha256=<hex>"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
     /// Pave tool settings.
     pub pave: PaveSection,
     /// Documentation location settings.
@@ -32,27 +44,63 @@ pub struct PaveConfig {
     /// Lint configuration.
     #[serde(default)]
     pub lint: LintSection,
+    /// Verification command execution settings.
+    #[serde(default)]
+    pub verify: VerifySection,
+    /// Member packages when this config is a monorepo workspace root.
+    #[serde(default, skip_serializing_if = "WorkspaceSection::is_empty")]
+    pub workspace: WorkspaceSection,
+    /// Wikis `pave publish` mirrors the docs to.
+    #[serde(default, skip_serializing_if = "PublishSection::is_empty")]
+    pub publish: PublishSection,
+    /// External rule plugins run by `pave check` and `pave lint`.
+    #[serde(default, skip_serializing_if = "PluginsSection::is_empty")]
+    pub plugins: PluginsSection,
+    /// Values for `{{ vars.<name> }}` placeholders in docs, e.g.
+    /// `service_url = "https://api.example.com"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
 }
 
 /// Pave tool metadata section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct PaveSection {
     /// Configuration schema version.
     pub version: String,
 }
 
 /// Documentation paths section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct DocsSection {
     /// Root directory for documentation.
     pub root: PathBuf,
     /// Directory where templates are stored (optional).
     #[serde(default)]
     pub templates: Option<PathBuf>,
+    /// Paths that document discovery skips, in `.paveignore` syntax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Translated doc sets, one directory per locale under the root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locales: Option<LocalesSection>,
+}
+
+/// Locales of a translated doc set, from `[docs.locales]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct LocalesSection {
+    /// Locale the docs are written in, e.g. `en` for `docs/en/`.
+    pub source: String,
+    /// Locales translated from the source, each in its own directory.
+    #[serde(default)]
+    pub translations: Vec<String>,
+    /// Globs, relative to the source locale's directory, of the docs every
+    /// translation must have. Every source doc when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
 }
 
 /// Validation rules section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct RulesSection {
     /// Maximum lines per document.
     #[serde(default = "default_max_lines")]
@@ -95,10 +143,88 @@ pub struct RulesSection {
     /// After this date, gradual mode is ignored.
     #[serde(default)]
     pub gradual_until: Option<String>,
+    /// Severity for documents that share the same H1 title.
+    /// One of "off", "warning" (default), or "error".
+    #[serde(default)]
+    pub duplicate_titles: RuleSeverity,
+    /// Severity for backtick substitution and remote-fetching `$(curl ...)`
+    /// constructs in verification commands. One of "off", "warning"
+    /// (default), which logs them and runs the command, or "error", which
+    /// fails them pre-flight.
+    #[serde(default)]
+    pub suspicious_commands: RuleSeverity,
+    /// Minimum verification coverage required per document type.
+    #[serde(default)]
+    pub verification_coverage: VerificationCoverageSection,
+    /// Base URL of the rules reference page linked from issues' `docs_url`.
+    /// Defaults to the hosted pave documentation.
+    #[serde(default)]
+    pub docs_url: Option<String>,
+    /// Severity for documents without a `pave.owner`.
+    /// One of "off" (default), "warning", or "error".
+    #[serde(default = "default_off")]
+    pub require_owner: RuleSeverity,
+    /// Severity for documents past their review date.
+    /// One of "off", "warning" (default), or "error".
+    #[serde(default)]
+    pub review_overdue: RuleSeverity,
+    /// Days between reviews for documents with a `pave.last_reviewed` date
+    /// but no `pave.review_interval_days`.
+    #[serde(default)]
+    pub review_interval_days: Option<u32>,
+    /// Warn about input the parser would otherwise skip: unclosed frontmatter
+    /// or code blocks, unknown or malformed `pave:` markers, and `pave:env`
+    /// without `=`. Always on with `pave check --strict`.
+    #[serde(default)]
+    pub strict_parse: bool,
+    /// Metadata keys that sections must declare with
+    /// `<!-- pave:section key=value -->` annotations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub section_metadata: Vec<SectionMetadataRule>,
+}
+
+/// Severity level for configurable rules.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// Rule is disabled.
+    Off,
+    /// Violations are reported as warnings.
+    #[default]
+    Warning,
+    /// Violations are reported as errors.
+    Error,
+}
+
+/// How much of a document its Verification section must exercise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum VerificationCoverage {
+    /// No coverage requirement.
+    #[default]
+    None,
+    /// The Verification section must contain at least one command.
+    AtLeastOne,
+    /// Every Steps entry with a command must have that command in Verification.
+    AllSteps,
+}
+
+/// Verification coverage requirements per document type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct VerificationCoverageSection {
+    /// Coverage required for runbooks.
+    #[serde(default)]
+    pub runbook: VerificationCoverage,
+    /// Coverage required for ADRs.
+    #[serde(default)]
+    pub adr: VerificationCoverage,
+    /// Coverage required for components.
+    #[serde(default)]
+    pub component: VerificationCoverage,
 }
 
 /// Document-type-specific validation rules.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct TypeSpecificRulesSection {
     /// Enable validation of runbook-specific sections.
     #[serde(default)]
@@ -109,11 +235,33 @@ pub struct TypeSpecificRulesSection {
     /// Enable validation of component-specific sections.
     #[serde(default)]
     pub components: bool,
+    /// Enable validation of postmortem-specific sections.
+    #[serde(default)]
+    pub postmortems: bool,
+    /// Enable validation of API-reference-specific sections.
+    #[serde(default)]
+    pub apis: bool,
+}
+
+/// Metadata a section must declare, from `[[rules.section_metadata]]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct SectionMetadataRule {
+    /// Section name, matched case-insensitively, such as `Steps`.
+    pub section: String,
+    /// Keys the section's `pave:section` annotations must set.
+    pub require: Vec<String>,
+    /// Only check documents of this type. Every type when unset.
+    #[serde(default)]
+    pub doc_type: Option<DocType>,
 }
 
 /// Template file mappings section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct TemplatesSection {
+    /// Directory of project templates, used instead of `docs.templates`.
+    /// Markdown files other than the built-in types' add document types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
     /// Filename for component template.
     #[serde(default)]
     pub component: Option<String>,
@@ -123,26 +271,396 @@ pub struct TemplatesSection {
     /// Filename for ADR template.
     #[serde(default)]
     pub adr: Option<String>,
+    /// Filename for postmortem template.
+    #[serde(default)]
+    pub postmortem: Option<String>,
+    /// Filename for API reference template.
+    #[serde(default)]
+    pub api: Option<String>,
 }
 
 /// Code-to-documentation mapping section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct MappingSection {
     /// Global path patterns to exclude from mapping.
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Largest coverage drop (in percentage points) since the last recorded
+    /// snapshot that `pave coverage --trend` tolerates.
+    #[serde(default)]
+    pub max_coverage_drop: f64,
 }
 
 /// Git hooks configuration section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct HooksSection {
     /// Run pave verify in hooks (default: false).
     #[serde(default)]
     pub run_verify: bool,
+    /// `pave` commands the pre-commit hook runs, replacing the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_commit: Option<Vec<String>>,
+    /// `pave` commands the pre-push hook runs, replacing the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_push: Option<Vec<String>>,
+    /// `pave` commands the commit-msg hook runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_msg: Option<Vec<String>>,
+}
+
+/// Workspace section listing member packages.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct WorkspaceSection {
+    /// Glob patterns for package directories, relative to this config.
+    /// Each matching directory with its own `.pave.toml` is a member.
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+impl WorkspaceSection {
+    /// Whether no members are listed.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+/// Publishing targets section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct PublishSection {
+    /// Confluence space to publish to, from `[publish.confluence]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confluence: Option<ConfluencePublish>,
+}
+
+impl PublishSection {
+    /// Whether no target is configured.
+    pub fn is_empty(&self) -> bool {
+        self.confluence.is_none()
+    }
+}
+
+/// External rule plugins section.
+///
+/// Each `check` and `lint` entry maps a rule name to the command line of a
+/// plugin that reads a doc as JSON on stdin and writes its issues as JSON on
+/// stdout. Each `wasm` entry maps a rule name to a WebAssembly module.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct PluginsSection {
+    /// Rule plugins `pave check` runs on every doc, e.g.
+    /// `org-terms = "pave-org-terms --strict"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub check: BTreeMap<String, String>,
+    /// Rule plugins `pave lint` runs on every doc, selectable with
+    /// `--rules` and `[lint] disable` like the built-in rules.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lint: BTreeMap<String, String>,
+    /// Sandboxed WebAssembly rules `pave check` runs on every doc, e.g.
+    /// `org-terms = "plugins/org-terms.wasm"` (relative to the config file).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wasm: BTreeMap<String, PathBuf>,
+}
+
+impl PluginsSection {
+    /// Whether no plugin is configured.
+    pub fn is_empty(&self) -> bool {
+        self.check.is_empty() && self.lint.is_empty() && self.wasm.is_empty()
+    }
+}
+
+/// A Confluence space that `pave publish --target confluence` mirrors the
+/// docs to.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ConfluencePublish {
+    /// Wiki URL the REST API is under, e.g. `https://acme.atlassian.net/wiki`.
+    pub base_url: String,
+    /// Key of the space pages are published to.
+    pub space: String,
+    /// ID of the page published pages are created under (default: the
+    /// space's top level).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// Environment variable holding the user for basic auth. Without it,
+    /// the token is sent as a bearer token.
+    #[serde(default = "default_confluence_user_env")]
+    pub user_env: String,
+    /// Environment variable holding the API token.
+    #[serde(default = "default_confluence_token_env")]
+    pub token_env: String,
+    /// Labels added to every page, besides the doc's `pave.tags`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+/// Verification command execution section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct VerifySection {
+    /// Shell used to run verification commands (default: `sh`, or `cmd` on Windows).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<Shell>,
+    /// Interpreter command lines keyed by code fence language, merged over
+    /// the built-in defaults. The block content is piped to stdin.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub interpreters: BTreeMap<String, String>,
+    /// Compiler command lines for `pave:compile` examples keyed by code fence
+    /// language, merged over the built-in defaults. The block content is
+    /// piped to stdin.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub compilers: BTreeMap<String, String>,
+    /// Command run after `pave verify` to archive its results, e.g. uploading
+    /// the report. `{report}`, `{status}`, and `{sha}` are replaced with the
+    /// report path, pave's exit status, and the current git commit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_cmd: Option<String>,
+    /// Command used to start and stop `pave.verify.compose` services
+    /// (default: `docker compose`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_cmd: Option<String>,
+    /// Append every command `pave verify` runs to the hash-chained
+    /// `.pave/audit.log`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub audit_log: bool,
+    /// Container image every command runs in, unless a block names its own
+    /// with a `pave:container` marker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Command used to run containers (default: `docker`), e.g. `podman`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_cmd: Option<String>,
+    /// Command used to reach `hosts` (default: `ssh`), e.g. `ssh -i key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_cmd: Option<String>,
+    /// SSH destinations that `pave:host` markers name, e.g.
+    /// `staging-bastion = "deploy@bastion.example.com"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hosts: BTreeMap<String, String>,
+    /// Connection strings for `sql` blocks by name, e.g.
+    /// `default = "postgres://localhost/app"`. `${NAME}` in a value reads
+    /// that environment variable, e.g. `postgres://app:${DB_PASSWORD}@db/app`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub databases: BTreeMap<String, String>,
+    /// Patterns scrubbed from command output before it is shown or reported.
+    #[serde(default, skip_serializing_if = "RedactSection::is_default")]
+    pub redact: RedactSection,
+}
+
+/// Redaction of secrets printed by verification commands.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct RedactSection {
+    /// Regexes whose matches are replaced with `***`. A pattern with a
+    /// capture group only replaces the first group, e.g. `password=(\S+)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Also redact common secret formats such as AWS access keys and bearer
+    /// tokens (default: true).
+    #[serde(default = "default_true")]
+    pub builtin: bool,
+}
+
+impl Default for RedactSection {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            builtin: true,
+        }
+    }
+}
+
+impl RedactSection {
+    /// Whether this is the default configuration.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Built-in interpreters for non-shell code blocks.
+const DEFAULT_INTERPRETERS: &[(&str, &str)] = &[
+    ("python", "python3 -"),
+    ("python3", "python3 -"),
+    ("py", "python3 -"),
+    ("ruby", "ruby -"),
+    ("rb", "ruby -"),
+    ("node", "node -"),
+    ("javascript", "node -"),
+    ("js", "node -"),
+];
+
+/// Built-in compilers for `pave:compile` examples. Each checks the block
+/// without producing output; `tsc` can't read stdin, so the TypeScript one
+/// copies the block to a temporary file first.
+const DEFAULT_COMPILERS: &[(&str, &[&str])] = &[
+    ("rust", RUSTC),
+    ("rs", RUSTC),
+    ("python", PY_COMPILE),
+    ("python3", PY_COMPILE),
+    ("py", PY_COMPILE),
+    ("typescript", TSC),
+    ("ts", TSC),
+];
+
+const RUSTC: &[&str] = &[
+    "rustc",
+    "--edition",
+    "2021",
+    "--crate-type",
+    "lib",
+    "--emit=metadata",
+    "--cap-lints",
+    "allow",
+    "-o",
+    "/dev/null",
+    "-",
+];
+const PY_COMPILE: &[&str] = &[
+    "python3",
+    "-c",
+    "import sys; compile(sys.stdin.read(), '<example>', 'exec')",
+];
+const TSC: &[&str] = &[
+    "sh",
+    "-c",
+    "dir=$(mktemp -d) && cat > \"$dir/example.ts\" && tsc --noEmit \"$dir/example.ts\"; \
+     status=$?; rm -rf \"$dir\"; exit $status",
+];
+
+impl VerifySection {
+    /// Returns the configured shell, falling back to the platform default.
+    pub fn shell(&self) -> Shell {
+        self.shell.unwrap_or_else(Shell::platform_default)
+    }
+
+    /// Returns the interpreter command line for a code fence language.
+    ///
+    /// Configured entries override the defaults; an empty entry disables
+    /// the interpreter so the block runs in the shell instead.
+    pub fn interpreter(&self, language: &str) -> Option<Vec<String>> {
+        let language = language.to_lowercase();
+        let command = match self.interpreters.get(&language) {
+            Some(command) => command.as_str(),
+            None => DEFAULT_INTERPRETERS
+                .iter()
+                .find(|(lang, _)| *lang == language)
+                .map(|(_, command)| *command)?,
+        };
+        let argv: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+        (!argv.is_empty()).then_some(argv)
+    }
+
+    /// Returns the compiler command line for a `pave:compile` example's code
+    /// fence language.
+    ///
+    /// Configured entries override the defaults; an empty entry disables
+    /// compiling that language.
+    pub fn compiler(&self, language: &str) -> Option<Vec<String>> {
+        let language = language.to_lowercase();
+        let argv: Vec<String> = match self.compilers.get(&language) {
+            Some(command) => command.split_whitespace().map(str::to_string).collect(),
+            None => DEFAULT_COMPILERS
+                .iter()
+                .find(|(lang, _)| *lang == language)
+                .map(|(_, argv)| argv.iter().map(|arg| arg.to_string()).collect())?,
+        };
+        (!argv.is_empty()).then_some(argv)
+    }
+
+    /// Returns the configured compose command, falling back to `docker compose`.
+    pub fn compose_cmd(&self) -> &str {
+        self.compose_cmd.as_deref().unwrap_or(DEFAULT_COMPOSE_CMD)
+    }
+
+    /// Returns the configured container command, falling back to `docker`.
+    pub fn container_cmd(&self) -> &str {
+        self.container_cmd
+            .as_deref()
+            .unwrap_or(DEFAULT_CONTAINER_CMD)
+    }
+
+    /// Returns the configured SSH command, falling back to `ssh`.
+    pub fn ssh_cmd(&self) -> &str {
+        self.ssh_cmd.as_deref().unwrap_or(DEFAULT_SSH_CMD)
+    }
+}
+
+/// Shell used to run verification commands.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Shell {
+    /// POSIX `sh`.
+    Sh,
+    /// GNU Bash.
+    Bash,
+    /// Z shell.
+    Zsh,
+    /// PowerShell 7+ (`pwsh`).
+    Pwsh,
+    /// Windows PowerShell 5 (`powershell`).
+    Powershell,
+    /// Windows command prompt.
+    Cmd,
+}
+
+impl Shell {
+    /// All supported shells.
+    pub const ALL: &[Shell] = &[
+        Shell::Sh,
+        Shell::Bash,
+        Shell::Zsh,
+        Shell::Pwsh,
+        Shell::Powershell,
+        Shell::Cmd,
+    ];
+
+    /// The default shell for the current platform.
+    pub fn platform_default() -> Self {
+        if cfg!(windows) { Shell::Cmd } else { Shell::Sh }
+    }
+
+    /// Parse a shell name as used in config and `pave:shell` markers.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|shell| shell.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Shell implied by a code fence language, for languages that are not
+    /// POSIX shells (`bash`/`sh` blocks use the configured shell).
+    pub fn for_language(language: &str) -> Option<Self> {
+        match language.to_lowercase().as_str() {
+            "powershell" | "pwsh" | "ps1" => Some(Shell::Pwsh),
+            "bat" | "batch" | "cmd" => Some(Shell::Cmd),
+            _ => None,
+        }
+    }
+
+    /// The executable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Sh => "sh",
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Pwsh => "pwsh",
+            Shell::Powershell => "powershell",
+            Shell::Cmd => "cmd",
+        }
+    }
+
+    /// Arguments placed before the command string.
+    pub fn command_args(self) -> &'static [&'static str] {
+        match self {
+            Shell::Sh | Shell::Bash | Shell::Zsh => &["-c"],
+            Shell::Pwsh | Shell::Powershell => &["-NoProfile", "-NonInteractive", "-Command"],
+            Shell::Cmd => &["/C"],
+        }
+    }
+
+    /// Whether the shell understands POSIX syntax (enables pre-flight checks).
+    pub fn is_posix(self) -> bool {
+        matches!(self, Shell::Sh | Shell::Bash | Shell::Zsh)
+    }
 }
 
 /// Lint configuration section.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct LintSection {
     /// Rules to enable (default: all rules enabled).
     #[serde(default)]
@@ -156,6 +674,115 @@ pub struct LintSection {
     /// Check external links (slow, off by default).
     #[serde(default)]
     pub external_links: bool,
+    /// Callout syntax every document should use. Detected from the most
+    /// common style in the docs when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admonition_style: Option<AdmonitionStyle>,
+    /// Terms to avoid, mapped to the term to use instead
+    /// (e.g. `"front-end" = "frontend"`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub terminology: BTreeMap<String, String>,
+    /// Word lists the `spelling` rule checks against, one word per line
+    /// (relative to the project root). Spelling is off while this is empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spelling_dictionaries: Vec<PathBuf>,
+    /// H2 sections, beyond the PAVED ones, that may hold H3 subsections.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<String>,
+    /// Limits the `readability` rule holds each section's prose to.
+    #[serde(default, skip_serializing_if = "ReadabilitySection::is_empty")]
+    pub readability: ReadabilitySection,
+    /// Command the `mermaid` rule pipes each diagram to instead of its
+    /// built-in checks, e.g. `mmdc -i - -o /tmp/pave-mermaid.svg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mermaid_cmd: Option<String>,
+    /// Commands the `interface-drift` rule may run to list a command's
+    /// flags, by the name a document gives as `pave.interface.help_cmd`,
+    /// e.g. `verify = "pave verify --help"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub help_cmds: BTreeMap<String, String>,
+}
+
+/// Readability limits for a section's prose.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ReadabilityLimits {
+    /// Most words per sentence, on average.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sentence_words: Option<u32>,
+    /// Highest Flesch-Kincaid grade level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_grade_level: Option<f64>,
+}
+
+impl ReadabilityLimits {
+    /// These limits, with any unset ones taken from `defaults`.
+    pub fn or(self, defaults: ReadabilityLimits) -> ReadabilityLimits {
+        ReadabilityLimits {
+            max_sentence_words: self.max_sentence_words.or(defaults.max_sentence_words),
+            max_grade_level: self.max_grade_level.or(defaults.max_grade_level),
+        }
+    }
+
+    /// Whether no limit is set.
+    pub fn is_empty(&self) -> bool {
+        self.max_sentence_words.is_none() && self.max_grade_level.is_none()
+    }
+}
+
+/// Readability limits for every document, with overrides per document type.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ReadabilitySection {
+    /// Limits for every document type.
+    #[serde(flatten)]
+    pub limits: ReadabilityLimits,
+    /// Limits for runbooks, over the shared ones.
+    #[serde(default, skip_serializing_if = "ReadabilityLimits::is_empty")]
+    pub runbook: ReadabilityLimits,
+    /// Limits for ADRs, over the shared ones.
+    #[serde(default, skip_serializing_if = "ReadabilityLimits::is_empty")]
+    pub adr: ReadabilityLimits,
+    /// Limits for components, over the shared ones.
+    #[serde(default, skip_serializing_if = "ReadabilityLimits::is_empty")]
+    pub component: ReadabilityLimits,
+}
+
+impl ReadabilitySection {
+    /// Whether no limit is set for any document type.
+    pub fn is_empty(&self) -> bool {
+        [self.limits, self.runbook, self.adr, self.component]
+            .iter()
+            .all(ReadabilityLimits::is_empty)
+    }
+}
+
+/// Syntax for admonitions (callouts) such as notes and warnings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum AdmonitionStyle {
+    /// GitHub alerts: `> [!NOTE]`.
+    Github,
+    /// A bold label in a blockquote: `> **Note:**`.
+    Blockquote,
+    /// Directive fences: `:::note` ... `:::`.
+    Directive,
+}
+
+impl AdmonitionStyle {
+    /// All styles, in the order ties are broken when detecting one.
+    pub const ALL: &[AdmonitionStyle] = &[
+        AdmonitionStyle::Github,
+        AdmonitionStyle::Blockquote,
+        AdmonitionStyle::Directive,
+    ];
+
+    /// Name used in config and messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdmonitionStyle::Github => "github",
+            AdmonitionStyle::Blockquote => "blockquote",
+            AdmonitionStyle::Directive => "directive",
+        }
+    }
 }
 
 fn default_max_paragraph_words() -> u32 {
@@ -169,6 +796,13 @@ impl Default for LintSection {
             disable: Vec::new(),
             max_paragraph_words: default_max_paragraph_words(),
             external_links: false,
+            admonition_style: None,
+            terminology: BTreeMap::new(),
+            spelling_dictionaries: Vec::new(),
+            sections: Vec::new(),
+            readability: ReadabilitySection::default(),
+            mermaid_cmd: None,
+            help_cmds: BTreeMap::new(),
         }
     }
 }
@@ -177,10 +811,22 @@ fn default_max_lines() -> u32 {
     300
 }
 
+fn default_confluence_user_env() -> String {
+    "CONFLUENCE_USER".to_string()
+}
+
+fn default_confluence_token_env() -> String {
+    "CONFLUENCE_TOKEN".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_off() -> RuleSeverity {
+    RuleSeverity::Off
+}
+
 impl Default for PaveSection {
     fn default() -> Self {
         Self {
@@ -194,6 +840,8 @@ impl Default for DocsSection {
         Self {
             root: PathBuf::from("docs"),
             templates: None,
+            exclude: Vec::new(),
+            locales: None,
         }
     }
 }
@@ -212,6 +860,15 @@ impl Default for RulesSection {
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            duplicate_titles: RuleSeverity::default(),
+            suspicious_commands: RuleSeverity::default(),
+            verification_coverage: VerificationCoverageSection::default(),
+            docs_url: None,
+            require_owner: RuleSeverity::Off,
+            review_overdue: RuleSeverity::default(),
+            review_interval_days: None,
+            strict_parse: false,
+            section_metadata: Vec::new(),
         }
     }
 }
@@ -222,12 +879,22 @@ impl PaveConfig {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("failed to read config file: {}", path.display()))?;
-        Self::parse(&content)
+        Self::parse_in(&content, path.parent().unwrap_or_else(|| Path::new(".")))
     }
 
     /// Parse configuration from a TOML string.
     pub fn parse(content: &str) -> Result<Self> {
-        let config: PaveConfig = toml::from_str(content).context("failed to parse config file")?;
+        Self::parse_in(content, Path::new("."))
+    }
+
+    /// Parse configuration from a TOML string, applying the presets it
+    /// extends with fetched ones cached relative to `config_dir`.
+    pub fn parse_in(content: &str, config_dir: &Path) -> Result<Self> {
+        let table: toml::Table = toml::from_str(content).context("failed to parse config file")?;
+        let table = presets::resolve(table, config_dir)?;
+        let config: PaveConfig = toml::Value::Table(table)
+            .try_into()
+            .context("failed to parse config file")?;
         config.validate()?;
         Ok(config)
     }
@@ -471,6 +1138,26 @@ run_verify = true
         assert!(config.hooks.run_verify);
     }
 
+    #[test]
+    fn database_urls_expand_environment_variables() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[verify.databases]
+default = "sqlite:${CARGO_PKG_NAME}.db"
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        // cargo sets CARGO_PKG_NAME for test runs
+        assert_eq!(
+            crate::sql::expand(&config.verify.databases["default"]).unwrap(),
+            "sqlite:pave.db"
+        );
+    }
+
     #[test]
     fn parse_config_without_hooks_uses_default() {
         let toml = r#"
@@ -535,11 +1222,15 @@ root = "docs"
 runbooks = true
 adrs = true
 components = true
+postmortems = true
+apis = true
 "#;
         let config = PaveConfig::parse(toml).unwrap();
         assert!(config.rules.type_specific.runbooks);
         assert!(config.rules.type_specific.adrs);
         assert!(config.rules.type_specific.components);
+        assert!(config.rules.type_specific.postmortems);
+        assert!(config.rules.type_specific.apis);
     }
 
     #[test]
@@ -555,6 +1246,8 @@ root = "docs"
         assert!(!config.rules.type_specific.runbooks);
         assert!(!config.rules.type_specific.adrs);
         assert!(!config.rules.type_specific.components);
+        assert!(!config.rules.type_specific.postmortems);
+        assert!(!config.rules.type_specific.apis);
     }
 
     #[test]
@@ -656,6 +1349,158 @@ root = "docs"
         assert_eq!(config.rules.gradual_until, None);
     }
 
+    #[test]
+    fn parse_config_with_duplicate_titles_severity() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[rules]
+duplicate_titles = "error"
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        assert_eq!(config.rules.duplicate_titles, RuleSeverity::Error);
+    }
+
+    #[test]
+    fn parse_config_with_section_metadata() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[[rules.section_metadata]]
+section = "Steps"
+require = ["owner"]
+doc_type = "runbook"
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        let required = &config.rules.section_metadata[0];
+        assert_eq!(required.section, "Steps");
+        assert_eq!(required.require, vec!["owner".to_string()]);
+        assert_eq!(required.doc_type, Some(DocType::Runbook));
+    }
+
+    #[test]
+    fn default_duplicate_titles_is_warning() {
+        let config = PaveConfig::default();
+        assert_eq!(config.rules.duplicate_titles, RuleSeverity::Warning);
+    }
+
+    #[test]
+    fn parse_config_with_verification_coverage() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[rules.verification_coverage]
+runbook = "all-steps"
+component = "at-least-one"
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        let coverage = &config.rules.verification_coverage;
+        assert_eq!(coverage.runbook, VerificationCoverage::AllSteps);
+        assert_eq!(coverage.component, VerificationCoverage::AtLeastOne);
+        assert_eq!(coverage.adr, VerificationCoverage::None);
+    }
+
+    #[test]
+    fn parse_config_with_verify_shell() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[verify]
+shell = "pwsh"
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        assert_eq!(config.verify.shell(), Shell::Pwsh);
+        assert_eq!(
+            PaveConfig::default().verify.shell(),
+            Shell::platform_default()
+        );
+    }
+
+    #[test]
+    fn interpreters_merge_config_over_defaults() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[verify.interpreters]
+python = "uv run python -"
+php = "php"
+ruby = ""
+"#;
+        let verify = PaveConfig::parse(toml).unwrap().verify;
+        assert_eq!(
+            verify.interpreter("Python"),
+            Some(vec![
+                "uv".to_string(),
+                "run".to_string(),
+                "python".to_string(),
+                "-".to_string()
+            ])
+        );
+        assert_eq!(verify.interpreter("php"), Some(vec!["php".to_string()]));
+        assert_eq!(verify.interpreter("ruby"), None);
+        assert_eq!(
+            verify.interpreter("js"),
+            Some(vec!["node".to_string(), "-".to_string()])
+        );
+        assert_eq!(verify.interpreter("yaml"), None);
+    }
+
+    #[test]
+    fn compilers_merge_config_over_defaults() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[verify.compilers]
+typescript = "npx tsc-stdin"
+python = ""
+"#;
+        let verify = PaveConfig::parse(toml).unwrap().verify;
+        assert_eq!(
+            verify.compiler("TypeScript"),
+            Some(vec!["npx".to_string(), "tsc-stdin".to_string()])
+        );
+        assert_eq!(verify.compiler("python"), None);
+        let rustc = verify.compiler("rust").unwrap();
+        assert_eq!(rustc[..3], ["rustc", "--edition", "2021"]);
+        assert_eq!(rustc.last().map(String::as_str), Some("-"));
+        assert_eq!(verify.compiler("go"), None);
+    }
+
+    #[test]
+    fn shell_names_and_languages() {
+        assert_eq!(Shell::from_name("Bash"), Some(Shell::Bash));
+        assert_eq!(Shell::from_name("fish"), None);
+        assert_eq!(Shell::for_language("PowerShell"), Some(Shell::Pwsh));
+        assert_eq!(Shell::for_language("bat"), Some(Shell::Cmd));
+        assert_eq!(Shell::for_language("bash"), None);
+        assert!(Shell::Zsh.is_posix());
+        assert!(!Shell::Cmd.is_posix());
+    }
+
     #[test]
     fn config_roundtrip_with_gradual() {
         let mut config = PaveConfig::default();
@@ -665,4 +1510,44 @@ root = "docs"
         let deserialized = PaveConfig::parse(&serialized).unwrap();
         assert_eq!(config, deserialized);
     }
+
+    #[test]
+    fn parse_readability_limits() {
+        let config = PaveConfig::parse(
+            r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[lint.readability]
+max_grade_level = 14
+
+[lint.readability.runbook]
+max_sentence_words = 15
+"#,
+        )
+        .unwrap();
+        let readability = config.lint.readability;
+        assert_eq!(readability.limits.max_grade_level, Some(14.0));
+        assert_eq!(
+            readability.runbook.or(readability.limits),
+            ReadabilityLimits {
+                max_sentence_words: Some(15),
+                max_grade_level: Some(14.0),
+            }
+        );
+        assert!(readability.adr.is_empty());
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("[lint.readability.runbook]"));
+        assert!(!serialized.contains("[lint.readability.adr]"));
+        assert_eq!(PaveConfig::parse(&serialized).unwrap(), config);
+        assert!(
+            !toml::to_string_pretty(&PaveConfig::default())
+                .unwrap()
+                .contains("readability")
+        );
+    }
 }
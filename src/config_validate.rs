@@ -0,0 +1,680 @@
+//! Validation of `.pave.toml` files with actionable errors.
+//!
+//! `PaveConfig::parse` stops at the first problem and silently ignores keys
+//! it doesn't know. This module implements `pave config validate`, which
+//! reports every problem it can find with the line it is on and a hint:
+//!
+//! - TOML syntax errors and values of the wrong type
+//! - unknown keys, with the closest known key as a suggestion
+//! - options that contradict each other
+//! - glob patterns that don't compile
+//! - a docs root or templates directory that doesn't exist
+
+use chrono::NaiveDate;
+use glob::Pattern;
+use regex::Regex;
+use schemars::schema_for;
+use serde_json::{Map, Value};
+use std::ops::Range;
+use std::path::Path;
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+use crate::config::PaveConfig;
+use crate::ignore;
+use crate::presets;
+
+/// A problem found in a config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// 1-based line the problem is on, when it can be located.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub message: String,
+    /// How to fix it.
+    pub hint: Option<String>,
+}
+
+impl ConfigProblem {
+    fn new(line: Option<usize>, message: impl Into<String>, hint: Option<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+            hint,
+        }
+    }
+}
+
+/// Validate the contents of a config file.
+///
+/// Relative paths such as `docs.root` are resolved against `config_dir`.
+pub fn validate(content: &str, config_dir: &Path) -> Vec<ConfigProblem> {
+    let root = match DeTable::parse(content) {
+        Ok(root) => root,
+        Err(err) => {
+            let line = err.span().map(|span| line_of(content, span.start));
+            return vec![ConfigProblem::new(
+                line,
+                err.message().trim_end(),
+                Some("fix the TOML syntax; see https://toml.io".to_string()),
+            )];
+        }
+    };
+    let table = root.get_ref();
+    let schema = serde_json::to_value(schema_for!(PaveConfig)).unwrap_or_default();
+    let locator = Locator { content, table };
+
+    let mut problems = Vec::new();
+    check_unknown_keys(&locator, table, &schema, &schema, "", &mut problems);
+    check_presets(&locator, content, config_dir, &mut problems);
+
+    let config = match toml::from_str::<PaveConfig>(content) {
+        Ok(config) => config,
+        Err(err) => {
+            problems.push(type_problem(&locator, &schema, &err));
+            sort_problems(&mut problems);
+            return problems;
+        }
+    };
+
+    if let Err(err) = config.validate() {
+        let message = err.to_string();
+        let key = message.split_whitespace().next().unwrap_or_default();
+        problems.push(ConfigProblem::new(
+            locator.key_line(key),
+            message.clone(),
+            None,
+        ));
+    }
+    check_conflicts(&locator, &config, &mut problems);
+    check_globs(&locator, &config, &mut problems);
+    check_regexes(&locator, &config, &mut problems);
+    check_paths(&locator, &config, config_dir, &mut problems);
+
+    sort_problems(&mut problems);
+    problems
+}
+
+fn sort_problems(problems: &mut [ConfigProblem]) {
+    problems.sort_by_key(|problem| problem.line.unwrap_or(usize::MAX));
+}
+
+/// Finds the lines of keys and values in the parsed document.
+struct Locator<'a> {
+    content: &'a str,
+    table: &'a DeTable<'a>,
+}
+
+impl Locator<'_> {
+    /// Line of a dotted key such as `rules.max_lines`.
+    fn key_line(&self, path: &str) -> Option<usize> {
+        let (key, _) = self.entry(path)?;
+        Some(line_of(self.content, key.start))
+    }
+
+    /// Line of the `index`th element of the array at `path`.
+    fn element_line(&self, path: &str, index: usize) -> Option<usize> {
+        let (_, value) = self.entry(path)?;
+        let element = value.get_ref().as_array()?.get(index)?;
+        Some(line_of(self.content, element.span().start))
+    }
+
+    fn entry(&self, path: &str) -> Option<(Range<usize>, &Spanned<DeValue<'_>>)> {
+        let mut table = self.table;
+        let mut parts = path.split('.').peekable();
+        while let Some(part) = parts.next() {
+            let (key, value) = table.iter().find(|(key, _)| key.get_ref() == part)?;
+            if parts.peek().is_none() {
+                return Some((key.span(), value));
+            }
+            table = value.get_ref().as_table()?;
+        }
+        None
+    }
+
+    /// Dotted path of the innermost key whose value contains `offset`.
+    fn path_at(&self, offset: usize) -> Option<String> {
+        fn walk(table: &DeTable<'_>, offset: usize, prefix: &str) -> Option<String> {
+            // Header tables' spans don't cover their keys, so search every
+            // nested table before falling back to the table itself
+            table.iter().find_map(|(key, value)| {
+                let path = join(prefix, key.get_ref());
+                if let Some(nested) = value.get_ref().as_table()
+                    && let Some(found) = walk(nested, offset, &path)
+                {
+                    return Some(found);
+                }
+                let span = value.span();
+                let contains = key.span().contains(&offset)
+                    || (span.start <= offset && offset < span.end.max(span.start + 1));
+                contains.then_some(path)
+            })
+        }
+        walk(self.table, offset, "")
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// 1-based line number of a byte offset.
+fn line_of(content: &str, offset: usize) -> usize {
+    let offset = offset.min(content.len());
+    content.as_bytes()[..offset]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// Report keys that aren't part of the config schema.
+fn check_unknown_keys(
+    locator: &Locator<'_>,
+    table: &DeTable<'_>,
+    schema: &Value,
+    root: &Value,
+    prefix: &str,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    // Maps such as [verify.interpreters] accept any key
+    let Some(properties) = properties(schema, root) else {
+        return;
+    };
+    for (key, value) in table.iter() {
+        let name = key.get_ref().as_ref();
+        let path = join(prefix, name);
+        match properties.get(name) {
+            Some(field) => {
+                if let Some(nested) = value.get_ref().as_table() {
+                    check_unknown_keys(locator, nested, field, root, &path, problems);
+                }
+            }
+            None => problems.push(ConfigProblem::new(
+                Some(line_of(locator.content, key.span().start)),
+                format!("unknown key `{}`", path),
+                Some(unknown_key_hint(name, prefix, properties, root)),
+            )),
+        }
+    }
+}
+
+fn unknown_key_hint(name: &str, prefix: &str, known: &Map<String, Value>, root: &Value) -> String {
+    let closest = known
+        .keys()
+        .map(|key| (strsim::jaro_winkler(name, key), key))
+        .filter(|(score, _)| *score >= 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    if let Some((_, key)) = closest {
+        return format!("did you mean `{}`?", join(prefix, key));
+    }
+
+    // A known key placed in the wrong section
+    if let Some(sections) = properties(root, root) {
+        for (section, schema) in sections {
+            if section != prefix
+                && properties(schema, root).is_some_and(|fields| fields.contains_key(name))
+            {
+                return format!("`{}` belongs in the [{}] section", name, section);
+            }
+        }
+    }
+
+    let mut keys: Vec<&str> = known.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    let section = if prefix.is_empty() {
+        "top-level sections".to_string()
+    } else {
+        format!("keys in [{}]", prefix)
+    };
+    format!("remove it; valid {} are: {}", section, keys.join(", "))
+}
+
+/// The properties of an object schema, following `$ref`s and optional
+/// wrappers. `None` for schemas that aren't structs.
+pub(crate) fn properties<'a>(schema: &'a Value, root: &'a Value) -> Option<&'a Map<String, Value>> {
+    let schema = resolve(schema, root);
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        return Some(properties);
+    }
+    variants(schema).find_map(|variant| properties(variant, root))
+}
+
+/// Follow a local `$ref` to its definition.
+pub(crate) fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+        .and_then(|pointer| root.pointer(pointer))
+        .unwrap_or(schema)
+}
+
+fn variants(schema: &Value) -> impl Iterator<Item = &Value> {
+    ["anyOf", "oneOf"]
+        .into_iter()
+        .filter_map(|keyword| schema.get(keyword).and_then(Value::as_array))
+        .flatten()
+}
+
+/// Turn a deserialization error into a problem pointing at the bad value.
+fn type_problem(locator: &Locator<'_>, root: &Value, err: &toml::de::Error) -> ConfigProblem {
+    let message = err.message().trim_end().to_string();
+    let span = err.span();
+    let line = span
+        .clone()
+        .map(|span| line_of(locator.content, span.start));
+    let path = span.and_then(|span| locator.path_at(span.start));
+
+    let hint = if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        // The span of a missing top-level field covers the whole file, so
+        // the path there names whichever table comes first
+        let section = path
+            .filter(|section| {
+                field_schema(root, section)
+                    .and_then(|schema| properties(schema, root))
+                    .is_some_and(|fields| fields.contains_key(field))
+            })
+            .unwrap_or_default();
+        let top_level_table = properties(root, root)
+            .and_then(|fields| fields.get(field))
+            .is_some_and(|schema| properties(schema, root).is_some());
+        Some(if !section.is_empty() {
+            format!("add `{}` to the [{}] section", field, section)
+        } else if top_level_table {
+            format!("add a [{}] section", field)
+        } else {
+            format!("add `{}` at the top level", field)
+        })
+    } else {
+        path.and_then(|path| {
+            let schema = field_schema(root, &path)?;
+            Some(format!("`{}` expects {}", path, describe(schema, root)?))
+        })
+    };
+
+    ConfigProblem::new(line, message, hint)
+}
+
+/// Schema of the field at a dotted path.
+fn field_schema<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut schema = root;
+    for part in path.split('.') {
+        schema = properties(schema, root)?.get(part)?;
+    }
+    Some(schema)
+}
+
+/// Describe the values a schema accepts, e.g. "an integer".
+fn describe(schema: &Value, root: &Value) -> Option<String> {
+    let schema = resolve(schema, root);
+    let values: Vec<String> = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .chain(variants(schema).filter_map(|variant| resolve(variant, root).get("const")))
+        .filter_map(Value::as_str)
+        .map(|value| format!("\"{}\"", value))
+        .collect();
+    if !values.is_empty() {
+        return Some(format!("one of {}", values.join(", ")));
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => return variants(schema).find_map(|variant| describe(variant, root)),
+    };
+    let description = match *types.iter().find(|ty| **ty != "null")? {
+        "boolean" => "`true` or `false`",
+        "integer" => "a whole number",
+        "number" => "a number",
+        "string" => "a string",
+        "array" => "a list, e.g. [\"a\", \"b\"]",
+        "object" => "a table",
+        _ => return None,
+    };
+    Some(description.to_string())
+}
+
+/// Report options that contradict each other.
+fn check_conflicts(locator: &Locator<'_>, config: &PaveConfig, problems: &mut Vec<ConfigProblem>) {
+    let rules = &config.rules;
+    if rules.strict_output_matching && rules.skip_output_matching {
+        problems.push(ConfigProblem::new(
+            locator.key_line("rules.skip_output_matching"),
+            "rules.strict_output_matching and rules.skip_output_matching are both enabled",
+            Some(
+                "skip_output_matching turns output checks off, so strict matching never applies; \
+                 set one of them to false"
+                    .to_string(),
+            ),
+        ));
+    }
+    if rules.warn_empty_paths && !rules.validate_paths {
+        problems.push(ConfigProblem::new(
+            locator.key_line("rules.warn_empty_paths"),
+            "rules.warn_empty_paths has no effect without rules.validate_paths",
+            Some("set `validate_paths = true` in [rules]".to_string()),
+        ));
+    }
+    if let Some(until) = &rules.gradual_until
+        && NaiveDate::parse_from_str(until, "%Y-%m-%d").is_err()
+    {
+        problems.push(ConfigProblem::new(
+            locator.key_line("rules.gradual_until"),
+            format!("rules.gradual_until '{}' is not a valid date", until),
+            Some("use the YYYY-MM-DD format, e.g. \"2025-06-30\"".to_string()),
+        ));
+    }
+    for rule in &config.lint.enable {
+        if config.lint.disable.contains(rule) {
+            problems.push(ConfigProblem::new(
+                locator.key_line("lint.disable"),
+                format!("lint rule '{}' is both enabled and disabled", rule),
+                Some(format!(
+                    "remove '{}' from lint.enable or lint.disable",
+                    rule
+                )),
+            ));
+        }
+    }
+}
+
+/// Report glob patterns that don't compile.
+fn check_globs(locator: &Locator<'_>, config: &PaveConfig, problems: &mut Vec<ConfigProblem>) {
+    let required = config
+        .docs
+        .locales
+        .as_ref()
+        .map_or(&[][..], |locales| locales.required.as_slice());
+    let lists: [(&str, &[String]); 4] = [
+        ("mapping.exclude", &config.mapping.exclude),
+        ("docs.exclude", &config.docs.exclude),
+        ("docs.locales.required", required),
+        ("workspace.members", &config.workspace.members),
+    ];
+    for (key, patterns) in lists {
+        for (index, pattern) in patterns.iter().enumerate() {
+            // `[docs] exclude` uses ignore-file syntax; the rest are plain globs
+            let compiled = if key == "docs.exclude" {
+                ignore::validate_pattern(pattern)
+            } else {
+                Pattern::new(pattern).map(|_| ())
+            };
+            if let Err(err) = compiled {
+                problems.push(ConfigProblem::new(
+                    locator.element_line(key, index),
+                    format!("invalid glob pattern '{}' in {}: {}", pattern, key, err.msg),
+                    Some(
+                        "escape literal brackets and wildcards as [[], []], [*], or [?]"
+                            .to_string(),
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Report redaction patterns that don't compile.
+fn check_regexes(locator: &Locator<'_>, config: &PaveConfig, problems: &mut Vec<ConfigProblem>) {
+    for (index, pattern) in config.verify.redact.patterns.iter().enumerate() {
+        if let Err(err) = Regex::new(pattern) {
+            let message = err.to_string();
+            problems.push(ConfigProblem::new(
+                locator.element_line("verify.redact.patterns", index),
+                format!(
+                    "invalid regex '{}' in verify.redact.patterns: {}",
+                    pattern,
+                    message
+                        .lines()
+                        .last()
+                        .unwrap_or_default()
+                        .trim()
+                        .trim_start_matches("error: ")
+                ),
+                Some("escape literal metacharacters such as ( and [ with a backslash".to_string()),
+            ));
+        }
+    }
+}
+
+/// Report presets in `extends` that can't be loaded.
+fn check_presets(
+    locator: &Locator<'_>,
+    content: &str,
+    config_dir: &Path,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return;
+    };
+    if let Err(err) = presets::resolve(table, config_dir) {
+        problems.push(ConfigProblem::new(
+            locator.key_line("extends"),
+            err.to_string(),
+            Some(
+                "use pave:recommended, pave:strict, or github:<owner>/<repo>@<ref>#sha256=<hex>"
+                    .to_string(),
+            ),
+        ));
+    }
+}
+
+/// Report configured directories that don't exist.
+fn check_paths(
+    locator: &Locator<'_>,
+    config: &PaveConfig,
+    config_dir: &Path,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    let docs_root = &config.docs.root;
+    if !docs_root.as_os_str().is_empty() && !config_dir.join(docs_root).is_dir() {
+        problems.push(ConfigProblem::new(
+            locator.key_line("docs.root"),
+            format!("docs root '{}' does not exist", docs_root.display()),
+            Some(format!(
+                "create it with `mkdir -p {}` or point docs.root at your docs directory",
+                docs_root.display()
+            )),
+        ));
+    }
+    if let Some(templates) = &config.docs.templates
+        && !config_dir.join(templates).is_dir()
+    {
+        problems.push(ConfigProblem::new(
+            locator.key_line("docs.templates"),
+            format!(
+                "templates directory '{}' does not exist",
+                templates.display()
+            ),
+            Some("create it or remove docs.templates to use the built-in templates".to_string()),
+        ));
+    }
+    if let Some(templates) = &config.templates.dir
+        && !config_dir.join(templates).is_dir()
+    {
+        problems.push(ConfigProblem::new(
+            locator.key_line("templates.dir"),
+            format!(
+                "templates directory '{}' does not exist",
+                templates.display()
+            ),
+            Some("create it or remove templates.dir to use the built-in templates".to_string()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn validate_in_project(content: &str) -> Vec<ConfigProblem> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("docs")).unwrap();
+        validate(content, temp_dir.path())
+    }
+
+    #[test]
+    fn valid_config_has_no_problems() {
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n\
+             [rules]\nmax_lines = 200\nduplicate_titles = \"error\"\n\n\
+             [verify.interpreters]\ndeno = \"deno run -\"\n",
+        );
+        assert_eq!(problems, vec![]);
+    }
+
+    #[test]
+    fn reports_presets_that_cannot_load() {
+        let problems = validate_in_project(
+            "extends = [\"pave:recommended\", \"pave:lenient\"]\n\n\
+             [pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(1));
+        assert!(
+            problems[0]
+                .message
+                .contains("Unknown preset 'pave:lenient'")
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\nmax_lines = 10\n\n\
+             [rules]\nmax_line = 200\nfrobnicate = true\n",
+        );
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].line, Some(6));
+        assert_eq!(problems[0].message, "unknown key `docs.max_lines`");
+        assert_eq!(
+            problems[0].hint.as_deref(),
+            Some("`max_lines` belongs in the [rules] section")
+        );
+        assert_eq!(problems[1].line, Some(9));
+        assert_eq!(
+            problems[1].hint.as_deref(),
+            Some("did you mean `rules.max_lines`?")
+        );
+        assert_eq!(problems[2].message, "unknown key `rules.frobnicate`");
+        assert!(problems[2].hint.as_ref().unwrap().starts_with("remove it"));
+    }
+
+    #[test]
+    fn reports_type_errors_at_the_value() {
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nmax_lines = \"many\"\n",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(8));
+        assert!(problems[0].message.contains("invalid type"));
+        assert_eq!(
+            problems[0].hint.as_deref(),
+            Some("`rules.max_lines` expects a whole number")
+        );
+
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nduplicate_titles = \"loud\"\n",
+        );
+        assert_eq!(
+            problems[0].hint.as_deref(),
+            Some("`rules.duplicate_titles` expects one of \"off\", \"warning\", \"error\"")
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_and_missing_fields() {
+        let problems = validate_in_project("[pave]\nversion = \n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+
+        let problems = validate_in_project("[pave]\nversion = \"0.1\"\n\n[docs]\n");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("missing field `root`"));
+        assert_eq!(
+            problems[0].hint.as_deref(),
+            Some("add `root` to the [docs] section")
+        );
+
+        let problems = validate_in_project(
+            "[docs]
+root = \"docs\"\n",
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("missing field `pave`"));
+        assert_eq!(problems[0].hint.as_deref(), Some("add a [pave] section"));
+    }
+
+    #[test]
+    fn reports_conflicts_globs_and_missing_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let problems = validate(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n\
+             [rules]\nstrict_output_matching = true\nskip_output_matching = true\n\n\
+             [mapping]\nexclude = [\n  \"target/\",\n  \"src/[gen\",\n]\n",
+            temp_dir.path(),
+        );
+        let summary: Vec<(Option<usize>, &str)> = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect();
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[0], (Some(5), "docs root 'docs' does not exist"));
+        assert_eq!(summary[1].0, Some(9));
+        assert!(summary[1].1.contains("both enabled"));
+        assert_eq!(summary[2].0, Some(14));
+        assert!(summary[2].1.starts_with("invalid glob pattern 'src/[gen'"));
+    }
+
+    #[test]
+    fn reports_invalid_globs_in_every_list() {
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\
+             exclude = [\"logs**\", \"!drafts/[a\"]\n\n\
+             [docs.locales]\nsource = \"en\"\nrequired = [\"guide/[x\"]\n\n\
+             [workspace]\nmembers = [\"packages/*\", \"libs/[\"]\n",
+        );
+        let summary: Vec<(Option<usize>, &str)> = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect();
+        assert_eq!(summary.len(), 3, "{:?}", summary);
+        assert_eq!(summary[0].0, Some(6));
+        assert!(
+            summary[0]
+                .1
+                .starts_with("invalid glob pattern '!drafts/[a' in docs.exclude")
+        );
+        assert_eq!(summary[1].0, Some(10));
+        assert!(summary[1].1.contains("in docs.locales.required"));
+        assert_eq!(summary[2].0, Some(13));
+        assert!(summary[2].1.contains("in workspace.members"));
+    }
+
+    #[test]
+    fn reports_invalid_redact_patterns() {
+        let problems = validate_in_project(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n\
+             [verify.redact]\npatterns = [\"token=(\\\\S+)\", \"key=(\"]\n",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(8));
+        assert!(
+            problems[0]
+                .message
+                .starts_with("invalid regex 'key=(' in verify.redact.patterns")
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Running verification commands inside a container image.
+//!
+//! A `<!-- pave:container node:20 -->` marker before a code block, or
+//! `[verify] container` for every block, runs the block's command with
+//! `docker run` (or `[verify] container_cmd`, e.g. `podman`) instead of on
+//! the host:
+//!
+//! ```text
+//! docker run --rm -i -v <root>:<root> -w <working dir> -e NAME... <image> sh -c <command>
+//! ```
+//!
+//! The project root is mounted at the same path inside the container, so
+//! working directories and paths in commands mean the same thing as on the
+//! host. Environment variables are passed by name only, keeping their values
+//! (including secrets) off the command line.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Container command used when `[verify] container_cmd` isn't set.
+pub const DEFAULT_CONTAINER_CMD: &str = "docker";
+
+/// Wrap `inner` to run in `image`.
+///
+/// `container_cmd` is split on whitespace, so it can carry extra flags.
+/// `root` is mounted at the same path and the command starts in
+/// `working_dir`; both are made absolute against the current directory, as
+/// they would be for a command run on the host. Environment variables set on
+/// `inner` are forwarded.
+pub fn wrap(
+    inner: &Command,
+    container_cmd: &str,
+    image: &str,
+    root: &Path,
+    working_dir: &Path,
+) -> Command {
+    let mut parts = container_cmd.split_whitespace();
+    let mut cmd = Command::new(parts.next().unwrap_or(DEFAULT_CONTAINER_CMD));
+    cmd.args(parts);
+
+    let root = absolute(root);
+    let working_dir = absolute(working_dir);
+    cmd.args(["run", "--rm", "-i"])
+        .arg("-v")
+        .arg(format!("{}:{}", root.display(), root.display()))
+        .arg("-w")
+        .arg(&working_dir);
+    for (key, value) in inner.get_envs() {
+        if let Some(value) = value {
+            cmd.arg("-e").arg(key);
+            cmd.env(key, value);
+        }
+    }
+    cmd.arg(image)
+        .arg(inner.get_program())
+        .args(inner.get_args());
+    cmd
+}
+
+/// `path` made absolute against the current directory, without resolving
+/// symlinks.
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(cmd: &Command) -> Vec<String> {
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn wraps_command_in_container_run() {
+        let mut inner = Command::new("sh");
+        inner.args(["-c", "npm test"]).env("CI", "1");
+
+        let cmd = wrap(
+            &inner,
+            "podman --remote",
+            "node:20",
+            Path::new("/repo"),
+            Path::new("/repo/web"),
+        );
+        assert_eq!(
+            argv(&cmd),
+            [
+                "podman",
+                "--remote",
+                "run",
+                "--rm",
+                "-i",
+                "-v",
+                "/repo:/repo",
+                "-w",
+                "/repo/web",
+                "-e",
+                "CI",
+                "node:20",
+                "sh",
+                "-c",
+                "npm test",
+            ]
+        );
+        assert!(
+            cmd.get_envs()
+                .any(|(key, value)| key == "CI" && value == Some("1".as_ref()))
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Process exit codes.
+//!
+//! Every command follows the same contract so CI can tell a failed check
+//! from a broken invocation:
+//!
+//! - `0`: success, nothing over the failure threshold
+//! - `1`: the command ran and found problems (check errors, lint issues,
+//!   failed verifications, coverage below threshold, ...)
+//! - `2`: the command could not run (bad arguments, missing config, I/O or
+//!   git failures)
+//!
+//! Commands report problems by returning a [`Violations`] error; any other
+//! error is a tool error.
+
+use std::fmt;
+
+/// Exit code for a command that found problems.
+pub const VIOLATIONS: u8 = 1;
+
+/// Exit code for a command that could not run.
+pub const TOOL_ERROR: u8 = 2;
+
+/// A command ran to completion and found problems over its threshold.
+#[derive(Debug)]
+pub struct Violations(pub String);
+
+impl fmt::Display for Violations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Violations {}
+
+/// Whether `err` means problems were found rather than that the tool failed.
+pub fn is_violations(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<Violations>())
+}
+
+/// Exit code for a command that returned `err`.
+pub fn code(err: &anyhow::Error) -> u8 {
+    if is_violations(err) {
+        VIOLATIONS
+    } else {
+        TOOL_ERROR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn violations_exit_one_and_other_errors_exit_two() {
+        let found = anyhow::Error::new(Violations("Check failed: 2 errors".to_string()));
+        assert_eq!(code(&found), VIOLATIONS);
+        assert_eq!(found.to_string(), "Check failed: 2 errors");
+
+        let broken = anyhow::anyhow!("Path does not exist: docs");
+        assert_eq!(code(&broken), TOOL_ERROR);
+    }
+
+    #[test]
+    fn context_keeps_violations() {
+        let result: anyhow::Result<()> = Err(Violations("Lint failed: 1 issue".to_string()).into());
+        let err = result.context("In package api").unwrap_err();
+        assert_eq!(code(&err), VIOLATIONS);
+    }
+}
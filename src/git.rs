@@ -0,0 +1,101 @@
+//! Git helpers shared by the commands that compare against a base ref.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Determine the base ref to compare against in the repository at `dir`.
+///
+/// An explicit base wins; otherwise `origin/main`, then `origin/master`,
+/// then `HEAD~1`.
+pub fn determine_base_ref(dir: &Path, explicit_base: Option<&str>) -> Result<String> {
+    if let Some(base) = explicit_base {
+        return Ok(base.to_string());
+    }
+
+    // Try origin/main first
+    if ref_exists(dir, "origin/main") {
+        return Ok("origin/main".to_string());
+    }
+
+    // Try origin/master
+    if ref_exists(dir, "origin/master") {
+        return Ok("origin/master".to_string());
+    }
+
+    // Fall back to HEAD~1
+    Ok("HEAD~1".to_string())
+}
+
+/// Check if a git ref exists in the repository at `dir`.
+pub fn ref_exists(dir: &Path, ref_name: &str) -> bool {
+    Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", ref_name])
+        .current_dir(dir)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Files changed since `base_ref` in the repository at `dir`, relative to
+/// the repository root.
+///
+/// Compares `base_ref..HEAD`, or for refs where that fails, such as
+/// `HEAD~1` in a shallow clone, `base_ref` against the working tree.
+pub fn changed_files(dir: &Path, base_ref: &str) -> Result<HashSet<PathBuf>> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", &format!("{}..HEAD", base_ref)])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git diff")?;
+    if output.status.success() {
+        return Ok(parse_name_only(&output.stdout));
+    }
+
+    let output = Command::new("git")
+        .args(["diff", "--name-only", base_ref])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git diff failed: {}", stderr);
+    }
+    Ok(parse_name_only(&output.stdout))
+}
+
+/// Parse `git diff --name-only` output into a set of paths.
+fn parse_name_only(output: &[u8]) -> HashSet<PathBuf> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn determine_base_ref_uses_explicit() {
+        let result = determine_base_ref(Path::new("."), Some("custom-branch")).unwrap();
+        assert_eq!(result, "custom-branch");
+    }
+
+    #[test]
+    fn parse_name_only_collects_all_files() {
+        let files = parse_name_only(b"src/cli.rs\nsrc/main.rs\ndocs/readme.md\n");
+
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&PathBuf::from("src/cli.rs")));
+        assert!(files.contains(&PathBuf::from("docs/readme.md")));
+    }
+
+    #[test]
+    fn parse_name_only_skips_blank_lines() {
+        assert!(parse_name_only(b"").is_empty());
+        assert!(parse_name_only(b"\n\n").is_empty());
+    }
+}
@@ -0,0 +1,561 @@
+//! Just enough HTML to import Confluence exports as markdown.
+//!
+//! Exported pages are machine-written and regular, so a tag scanner covers
+//! them without a full HTML parser: headings, paragraphs, lists, tables,
+//! links, images, emphasis, code blocks, blockquotes, and Confluence's
+//! info/tip/note/warning panels, which become GitHub alerts. Anything else
+//! is dropped and its text kept.
+
+use regex::Regex;
+
+/// A piece of an HTML document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'a> {
+    /// An opening or self-closing tag, with its lowercased name and raw
+    /// attributes.
+    Open { name: String, attrs: &'a str },
+    /// A closing tag's lowercased name.
+    Close(String),
+    /// Text between tags, with entities still encoded.
+    Text(&'a str),
+}
+
+/// Split an HTML document into tags and text. Comments, doctypes, and the
+/// contents of `script` and `style` elements are skipped.
+pub fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let end = tag_end(rest);
+        let tag = &rest[1..end.saturating_sub(1).max(1)];
+        rest = &rest[end..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name_len = tag
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(tag.len());
+        let name = tag[..name_len].to_ascii_lowercase();
+        if name.is_empty() {
+            tokens.push(Token::Text("<"));
+            continue;
+        }
+        if closing {
+            tokens.push(Token::Close(name));
+        } else if name == "script" || name == "style" {
+            let close = format!("</{}", name);
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or("", |idx| &rest[idx + tag_end(&rest[idx..])..]);
+        } else {
+            tokens.push(Token::Open {
+                name,
+                attrs: tag[name_len..].trim_end_matches('/').trim(),
+            });
+        }
+    }
+    tokens
+}
+
+/// Length of the tag at the start of `text`, up to and including its `>`,
+/// skipping any `>` inside quoted attribute values.
+fn tag_end(text: &str) -> usize {
+    let mut quote = None;
+    for (idx, c) in text.char_indices().skip(1) {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('>', None) => return idx + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Value of the attribute `name` in a tag's raw attributes.
+pub fn attr(attrs: &str, name: &str) -> Option<String> {
+    let pattern = format!(
+        r#"(?i)(?:^|\s){}\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#,
+        regex::escape(name)
+    );
+    let caps = Regex::new(&pattern).ok()?.captures(attrs)?;
+    let value = caps.get(1).or(caps.get(2)).or(caps.get(3))?;
+    Some(decode_entities(value.as_str()))
+}
+
+/// Decode named and numeric character references.
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let entity = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+    entity
+        .replace_all(text, |caps: &regex::Captures| {
+            let name = &caps[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                "rsquo" => Some('’'),
+                "lsquo" => Some('‘'),
+                "rdquo" => Some('”'),
+                "ldquo" => Some('“'),
+                _ => {
+                    let number = name.strip_prefix('#');
+                    number
+                        .and_then(|number| match number.strip_prefix(['x', 'X']) {
+                            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                            None => number.parse().ok(),
+                        })
+                        .and_then(char::from_u32)
+                }
+            };
+            decoded.map_or_else(|| caps[0].to_string(), |c| c.to_string())
+        })
+        .into_owned()
+}
+
+/// Convert an HTML fragment to markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    for token in tokens(html) {
+        match token {
+            Token::Open { name, attrs } => converter.open(&name, attrs),
+            Token::Close(name) => converter.close(&name),
+            Token::Text(text) => converter.text(&decode_entities(text)),
+        }
+    }
+    converter.flush();
+    converter.out.trim_end().to_string() + "\n"
+}
+
+/// A list being converted.
+struct List {
+    /// Next number of an ordered list.
+    next_number: Option<usize>,
+    /// Column of the item markers.
+    indent: usize,
+    /// Column the current item's content starts at.
+    content_column: usize,
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    /// Inline text of the block being converted.
+    text: String,
+    heading: Option<usize>,
+    lists: Vec<List>,
+    /// Whether `text` starts a list item.
+    item: bool,
+    quote_depth: usize,
+    /// Whether the next block follows the last without a blank line.
+    tight: bool,
+    /// Whether the last block was in a list.
+    last_in_list: bool,
+    /// Blockquote depth of the last block.
+    last_quote_depth: usize,
+    /// Link targets of the open `a` tags.
+    links: Vec<Option<String>>,
+    /// Whether each open `div` is a callout panel.
+    divs: Vec<bool>,
+    /// Language of the open `pre` block, and its text.
+    pre: Option<(String, String)>,
+    /// Rows of the open table, the last one being filled.
+    table: Option<Vec<Vec<String>>>,
+    in_cell: bool,
+}
+
+impl Converter {
+    fn open(&mut self, name: &str, attrs: &str) {
+        if self.pre.is_some() {
+            if name == "br" {
+                self.push_code("\n");
+            }
+            return;
+        }
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" if !self.in_cell => {
+                self.flush();
+                self.heading = name[1..].parse().ok();
+            }
+            "p" | "section" | "article" => self.block_break(),
+            "div" => {
+                self.block_break();
+                let callout = callout_kind(attrs);
+                if let Some(kind) = callout {
+                    self.quote_depth += 1;
+                    self.emit(&format!("[!{}]", kind), false);
+                    self.tight = true;
+                }
+                self.divs.push(callout.is_some());
+            }
+            "br" => self.push("\n"),
+            "hr" => {
+                self.flush();
+                self.emit("---", false);
+            }
+            "strong" | "b" => self.push("**"),
+            "em" | "i" => self.push("*"),
+            "code" => self.push("`"),
+            "a" => {
+                let href = attr(attrs, "href").filter(|href| !href.starts_with('#'));
+                if href.is_some() {
+                    self.push("[");
+                }
+                self.links.push(href);
+            }
+            "img" => {
+                if let Some(src) = attr(attrs, "src") {
+                    let alt = attr(attrs, "alt").unwrap_or_default();
+                    self.push(&format!("![{}]({})", alt, src));
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let indent = self.lists.last().map_or(0, |list| list.content_column);
+                self.lists.push(List {
+                    next_number: (name == "ol").then_some(1),
+                    indent,
+                    content_column: indent,
+                });
+            }
+            "li" => {
+                self.flush();
+                self.item = true;
+            }
+            "pre" => {
+                self.flush();
+                self.pre = Some((code_language(attrs), String::new()));
+            }
+            "blockquote" => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            "table" => {
+                self.flush();
+                self.table = Some(Vec::new());
+            }
+            "tr" => {
+                if let Some(rows) = &mut self.table {
+                    rows.push(Vec::new());
+                }
+            }
+            "td" | "th" => {
+                if let Some(row) = self.table.as_mut().and_then(|rows| rows.last_mut()) {
+                    row.push(String::new());
+                    self.in_cell = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if let Some((language, code)) = &self.pre {
+            if name == "pre" {
+                let block = format!("```{}\n{}\n```", language, code.trim_matches('\n'));
+                self.pre = None;
+                self.emit(&block, false);
+            }
+            return;
+        }
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" if !self.in_cell => self.flush(),
+            "p" | "section" | "article" => self.block_break(),
+            "div" => {
+                self.block_break();
+                if self.divs.pop() == Some(true) {
+                    self.quote_depth = self.quote_depth.saturating_sub(1);
+                }
+            }
+            "strong" | "b" => self.push("**"),
+            "em" | "i" => self.push("*"),
+            "code" => self.push("`"),
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.push(&format!("]({})", href));
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                self.lists.pop();
+                self.item = false;
+            }
+            "li" => self.flush(),
+            "blockquote" => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            "td" | "th" => self.in_cell = false,
+            "table" => {
+                self.in_cell = false;
+                if let Some(rows) = self.table.take() {
+                    let table = render_table(&rows);
+                    if !table.is_empty() {
+                        self.emit(&table, false);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Append text with its whitespace collapsed as a browser would.
+    fn text(&mut self, text: &str) {
+        if self.pre.is_some() {
+            self.push_code(text);
+            return;
+        }
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if !c.is_whitespace() {
+                collapsed.push(c);
+            } else if !collapsed.ends_with(' ') {
+                collapsed.push(' ');
+            }
+        }
+        let current = if self.in_cell {
+            self.table
+                .as_ref()
+                .and_then(|rows| rows.last())
+                .and_then(|row| row.last())
+                .map_or("", String::as_str)
+        } else {
+            self.text.as_str()
+        };
+        if current.is_empty() || current.ends_with([' ', '\n']) {
+            collapsed = collapsed.trim_start().to_string();
+        }
+        self.push(&collapsed);
+    }
+
+    fn push(&mut self, text: &str) {
+        if self.in_cell {
+            if let Some(cell) = self
+                .table
+                .as_mut()
+                .and_then(|rows| rows.last_mut())
+                .and_then(|row| row.last_mut())
+            {
+                cell.push_str(&text.replace('\n', " ").replace('|', "\\|"));
+            }
+        } else if self.table.is_none() {
+            self.text.push_str(text);
+        }
+    }
+
+    fn push_code(&mut self, text: &str) {
+        if let Some((_, code)) = &mut self.pre {
+            code.push_str(text);
+        }
+    }
+
+    /// End a paragraph, or inside a table cell, separate its text.
+    fn block_break(&mut self) {
+        if self.in_cell {
+            self.push(" ");
+        } else {
+            self.flush();
+        }
+    }
+
+    /// Emit the pending inline text as a heading or paragraph.
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.text);
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            return;
+        }
+        match self.heading.take() {
+            Some(level) => {
+                let heading = format!("{} {}", "#".repeat(level), lines.join(" "));
+                self.emit(&heading, false);
+            }
+            None => self.emit(&lines.join("\n"), true),
+        }
+    }
+
+    /// Write a block, indented for the list and quoted for the blockquotes
+    /// it's in. A paragraph that starts a list item gets its marker.
+    fn emit(&mut self, block: &str, paragraph: bool) {
+        let quote = "> ".repeat(self.quote_depth);
+        let item = paragraph && std::mem::take(&mut self.item);
+        let (first_prefix, prefix) = match self.lists.last_mut() {
+            Some(list) if item => {
+                let marker = match &mut list.next_number {
+                    Some(number) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    None => "- ".to_string(),
+                };
+                list.content_column = list.indent + marker.len();
+                (
+                    format!("{}{}", " ".repeat(list.indent), marker),
+                    " ".repeat(list.content_column),
+                )
+            }
+            Some(list) => {
+                let prefix = " ".repeat(list.content_column);
+                (prefix.clone(), prefix)
+            }
+            None => (String::new(), String::new()),
+        };
+
+        // Blocks are separated by a blank line, except within a list
+        let in_list = !self.lists.is_empty();
+        let tight = std::mem::take(&mut self.tight) || (in_list && self.last_in_list);
+        if !self.out.is_empty() && !tight && !self.out.ends_with("\n\n") {
+            let depth = self.quote_depth.min(self.last_quote_depth);
+            self.out.push_str("> ".repeat(depth).trim_end());
+            self.out.push('\n');
+        }
+        self.last_in_list = in_list;
+        self.last_quote_depth = self.quote_depth;
+        for (idx, line) in block.lines().enumerate() {
+            let prefix = if idx == 0 { &first_prefix } else { &prefix };
+            let line = format!("{}{}{}", quote, prefix, line);
+            self.out.push_str(line.trim_end());
+            self.out.push('\n');
+        }
+    }
+}
+
+/// Alert kind of a Confluence info, tip, note, or warning panel.
+fn callout_kind(attrs: &str) -> Option<&'static str> {
+    let class = attr(attrs, "class")?;
+    class.split_whitespace().find_map(|class| {
+        match class.strip_prefix("confluence-information-macro-")? {
+            "information" => Some("NOTE"),
+            "tip" => Some("TIP"),
+            "note" => Some("IMPORTANT"),
+            "warning" => Some("WARNING"),
+            _ => None,
+        }
+    })
+}
+
+/// Language of a code block, from Confluence's `brush` parameter or a
+/// `language-*` class.
+fn code_language(attrs: &str) -> String {
+    let brush = Regex::new(r"brush:\s*([\w+#-]+)").unwrap();
+    if let Some(params) = attr(attrs, "data-syntaxhighlighter-params")
+        && let Some(caps) = brush.captures(&params)
+    {
+        return caps[1].to_string();
+    }
+    attr(attrs, "class")
+        .and_then(|class| {
+            class
+                .split_whitespace()
+                .find_map(|class| class.strip_prefix("language-").map(str::to_string))
+        })
+        .unwrap_or_default()
+}
+
+/// A markdown table with the first row as its header.
+fn render_table(rows: &[Vec<String>]) -> String {
+    let rows: Vec<&Vec<String>> = rows.iter().filter(|row| !row.is_empty()).collect();
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    for (idx, row) in rows.iter().enumerate() {
+        let cells: Vec<&str> = (0..columns)
+            .map(|column| row.get(column).map_or("", |cell| cell.trim()))
+            .collect();
+        table.push_str(&format!("| {} |\n", cells.join(" | ")));
+        if idx == 0 {
+            table.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_tags_and_text() {
+        let html = "<!DOCTYPE html><p class=\"a>b\">Hi<br/></p><!-- x --><script>1 < 2</script>ok";
+        assert_eq!(
+            tokens(html),
+            [
+                Token::Open {
+                    name: "p".to_string(),
+                    attrs: "class=\"a>b\"",
+                },
+                Token::Text("Hi"),
+                Token::Open {
+                    name: "br".to_string(),
+                    attrs: "",
+                },
+                Token::Close("p".to_string()),
+                Token::Text("ok"),
+            ]
+        );
+        assert_eq!(
+            attr("href='a.html' id=x", "href").as_deref(),
+            Some("a.html")
+        );
+        assert_eq!(attr("href='a.html' id=x", "id").as_deref(), Some("x"));
+        assert_eq!(
+            decode_entities("a &amp; b &lt;c&gt; &#39;d&#x27; &bogus;"),
+            "a & b <c> 'd' &bogus;"
+        );
+    }
+
+    #[test]
+    fn converts_confluence_content() {
+        let html = r#"
+<h2 id="Deploy-Steps">Steps</h2>
+<p>Run the <strong>deploy</strong> script:</p>
+<div class="code panel"><div class="codeContent">
+<pre class="syntaxhighlighter-pre" data-syntaxhighlighter-params="brush: bash; gutter: false">./deploy.sh &amp;&amp; echo ok
+</pre></div></div>
+<ol><li>Check <a href="Monitoring_123.html">the dashboard</a></li><li>Done
+<ul><li>really</li></ul></li></ol>
+<div class="confluence-information-macro confluence-information-macro-warning">
+<span class="aui-icon"></span><div class="confluence-information-macro-body"><p>Never on Fridays.</p></div></div>
+<table><tbody><tr><th>Env</th><th>URL</th></tr><tr><td><p>prod</p></td><td>a|b</td></tr></tbody></table>
+"#;
+        assert_eq!(
+            to_markdown(html),
+            "## Steps\n\n\
+             Run the **deploy** script:\n\n\
+             ```bash\n./deploy.sh && echo ok\n```\n\n\
+             1. Check [the dashboard](Monitoring_123.html)\n\
+             2. Done\n   \
+             - really\n\n\
+             > [!WARNING]\n\
+             > Never on Fridays.\n\n\
+             | Env | URL |\n| --- | --- |\n| prod | a\\|b |\n"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! HTTP request blocks in Verification sections.
+//!
+//! An `http` code block is a request in REST-client style: the method and
+//! URL, then headers up to a blank line, then the body.
+//!
+//! ```http
+//! POST http://localhost:8080/api/items
+//! Content-Type: application/json
+//!
+//! {"name": "widget"}
+//! ```
+//!
+//! The method defaults to `GET`, a trailing `HTTP/1.1` is ignored, and `#`
+//! or `//` lines before the request line are comments. `{{NAME}}` is replaced
+//! with the value of `NAME` from the block's env vars and secrets, or the
+//! environment.
+//!
+//! `pave verify` sends the request with `curl` and reports `HTTP <status>`
+//! followed by the response body as the command's output, for a
+//! `pave:expect` block to match. A status of 400 or above exits 1. The
+//! request goes to curl as a config file on stdin, so substituted secrets
+//! never appear on its command line.
+
+use regex::{Captures, Regex};
+use std::process::Command;
+use std::sync::LazyLock;
+
+/// Fence language of request blocks.
+pub const HTTP_LANGUAGE: &str = "http";
+
+/// Exit code reported for responses with an error status.
+pub const ERROR_STATUS_EXIT_CODE: i32 = 1;
+
+const METHODS: &[&str] = &[
+    "GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// A `{{NAME}}` reference to a variable.
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+/// A request parsed from an `http` block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    /// HTTP method, upper-cased.
+    pub method: String,
+    /// Request URL.
+    pub url: String,
+    /// Headers in the order written.
+    pub headers: Vec<(String, String)>,
+    /// Body after the blank line, if any.
+    pub body: Option<String>,
+}
+
+impl Request {
+    /// Parse a request block, or explain what's wrong with it.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .skip_while(|line| is_comment(line) || line.trim().is_empty());
+        let request_line = lines
+            .next()
+            .ok_or_else(|| "empty http block: expected a request line".to_string())?;
+
+        let mut words = request_line.split_whitespace();
+        let first = words.next().unwrap_or_default();
+        let (method, url) = if METHODS.contains(&first.to_uppercase().as_str()) {
+            let url = words
+                .next()
+                .ok_or_else(|| format!("missing URL in request line '{}'", request_line))?;
+            (first.to_uppercase(), url)
+        } else {
+            ("GET".to_string(), first)
+        };
+        if let Some(extra) = words.next().filter(|word| !word.starts_with("HTTP/")) {
+            return Err(format!(
+                "unexpected '{}' in request line '{}'",
+                extra, request_line
+            ));
+        }
+
+        let mut headers = Vec::new();
+        for line in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            if is_comment(line) {
+                continue;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .filter(|(name, _)| !name.trim().is_empty())
+                .ok_or_else(|| format!("invalid header '{}': expected 'Name: value'", line))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        let body = lines.collect::<Vec<_>>().join("\n");
+        let body = body.trim_end();
+        Ok(Self {
+            method,
+            url: url.to_string(),
+            headers,
+            body: (!body.is_empty()).then(|| body.to_string()),
+        })
+    }
+
+    /// The request with `{{NAME}}` replaced by `lookup(NAME)` in the URL,
+    /// headers, and body. Unknown names are left as written.
+    pub fn substitute(&self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let expand = |text: &str| {
+            VARIABLE
+                .replace_all(text, |caps: &Captures| {
+                    lookup(&caps[1]).unwrap_or_else(|| caps[0].to_string())
+                })
+                .into_owned()
+        };
+        Self {
+            method: self.method.clone(),
+            url: expand(&self.url),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), expand(value)))
+                .collect(),
+            body: self.body.as_deref().map(expand),
+        }
+    }
+
+    /// `curl` arguments after the output flags: method, headers, body from
+    /// stdin, and URL. Used for exported scripts; [`Request::command`]
+    /// passes the same options through [`Request::curl_config`] instead.
+    ///
+    /// `HEAD` uses `--head`: with `-X HEAD`, curl still waits for the body
+    /// the response announces, and hangs.
+    pub fn curl_args(&self) -> Vec<String> {
+        let mut args = if self.method == "HEAD" {
+            vec!["--head".to_string()]
+        } else {
+            vec!["-X".to_string(), self.method.clone()]
+        };
+        for (name, value) in &self.headers {
+            args.push("-H".to_string());
+            args.push(format!("{}: {}", name, value));
+        }
+        if self.body.is_some() {
+            args.push("--data-binary".to_string());
+            args.push("@-".to_string());
+        }
+        args.push("--url".to_string());
+        args.push(self.url.clone());
+        args
+    }
+
+    /// A curl config file with the method, headers, body, and URL, for
+    /// `curl --config -` to read from stdin.
+    pub fn curl_config(&self) -> String {
+        let mut config = if self.method == "HEAD" {
+            "head\n".to_string()
+        } else {
+            format!("request = {}\n", config_string(&self.method))
+        };
+        for (name, value) in &self.headers {
+            let header = format!("{}: {}", name, value);
+            config.push_str(&format!("header = {}\n", config_string(&header)));
+        }
+        if let Some(body) = &self.body {
+            // `data-raw` sends a leading `@` as written instead of reading a file
+            config.push_str(&format!("data-raw = {}\n", config_string(body)));
+        }
+        config.push_str(&format!("url = {}\n", config_string(&self.url)));
+        config
+    }
+
+    /// The `curl` process that sends this request, printing the response
+    /// headers so [`response`] can read the status. [`Request::curl_config`]
+    /// goes to its stdin.
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new("curl");
+        cmd.args(["-sS", "-i", "--config", "-"]);
+        cmd
+    }
+}
+
+/// Quote `value` as a curl config string.
+fn config_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Whether a line before the request line or among the headers is a comment.
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// Turn `curl -i` output into `HTTP <status>` and the body, with the exit
+/// code for that status. Interim `1xx` responses are skipped. Returns `None`
+/// if the output doesn't start with a response.
+pub fn response(raw: &str) -> Option<(String, i32)> {
+    let mut rest = raw;
+    loop {
+        let (head, body) = split_head(rest);
+        let status_line = head.lines().next()?;
+        if !status_line.starts_with("HTTP/") {
+            return None;
+        }
+        let status: u16 = status_line.split_whitespace().nth(1)?.parse().ok()?;
+        if (100..200).contains(&status) && body.starts_with("HTTP/") {
+            rest = body;
+            continue;
+        }
+        let exit_code = if status >= 400 {
+            ERROR_STATUS_EXIT_CODE
+        } else {
+            0
+        };
+        return Some((format!("HTTP {}\n{}", status, body), exit_code));
+    }
+}
+
+/// Split a response at the blank line ending its headers.
+fn split_head(text: &str) -> (&str, &str) {
+    let crlf = text.find("\r\n\r\n").map(|idx| (idx, 4));
+    let lf = text.find("\n\n").map(|idx| (idx, 2));
+    match [crlf, lf].into_iter().flatten().min() {
+        Some((idx, len)) => (&text[..idx], &text[idx + len..]),
+        None => (text, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_line_headers_and_body() {
+        let request = Request::parse(
+            "# create an item\npost {{API}}/items HTTP/1.1\nContent-Type: application/json\n\
+             // not sent\nX-Token: {{TOKEN}}\n\n{\"name\": \"{{NAME}}\"}\n\n",
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "{{API}}/items");
+        assert_eq!(request.headers.len(), 2);
+        assert_eq!(request.body.as_deref(), Some("{\"name\": \"{{NAME}}\"}"));
+
+        let request = request.substitute(|name| match name {
+            "API" => Some("http://localhost:8080".to_string()),
+            "TOKEN" => Some("abc".to_string()),
+            _ => None,
+        });
+        assert_eq!(
+            request.curl_args(),
+            [
+                "-X",
+                "POST",
+                "-H",
+                "Content-Type: application/json",
+                "-H",
+                "X-Token: abc",
+                "--data-binary",
+                "@-",
+                "--url",
+                "http://localhost:8080/items",
+            ]
+        );
+        assert_eq!(request.body.as_deref(), Some("{\"name\": \"{{NAME}}\"}"));
+
+        assert_eq!(
+            request.curl_config(),
+            "request = \"POST\"\n\
+             header = \"Content-Type: application/json\"\n\
+             header = \"X-Token: abc\"\n\
+             data-raw = \"{\\\"name\\\": \\\"{{NAME}}\\\"}\"\n\
+             url = \"http://localhost:8080/items\"\n"
+        );
+        // Nothing from the request is on curl's command line
+        assert_eq!(
+            request.command().get_args().collect::<Vec<_>>(),
+            ["-sS", "-i", "--config", "-"]
+        );
+
+        let get = Request::parse("http://localhost/health").unwrap();
+        assert_eq!((get.method.as_str(), get.body), ("GET", None));
+
+        let head = Request::parse("HEAD http://localhost/health").unwrap();
+        assert_eq!(
+            head.curl_args(),
+            ["--head", "--url", "http://localhost/health"]
+        );
+        assert_eq!(
+            head.curl_config(),
+            "head\nurl = \"http://localhost/health\"\n"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        assert!(Request::parse("# nothing\n").unwrap_err().contains("empty"));
+        assert!(Request::parse("GET").unwrap_err().contains("missing URL"));
+        assert!(
+            Request::parse("GET /a /b")
+                .unwrap_err()
+                .contains("unexpected '/b'")
+        );
+        assert!(
+            Request::parse("GET /a\nnot a header")
+                .unwrap_err()
+                .contains("invalid header")
+        );
+    }
+
+    #[test]
+    fn reads_status_and_body_from_curl_output() {
+        let raw = "HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 201 Created\r\n\
+                   Content-Type: application/json\r\n\r\n{\"id\": 1}";
+        assert_eq!(
+            response(raw),
+            Some(("HTTP 201\n{\"id\": 1}".to_string(), 0))
+        );
+        assert_eq!(
+            response("HTTP/2 503\nretry-after: 5\n\ndown\n"),
+            Some(("HTTP 503\ndown\n".to_string(), ERROR_STATUS_EXIT_CODE))
+        );
+        assert_eq!(response("curl: (7) Failed to connect"), None);
+    }
+}
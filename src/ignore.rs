@@ -0,0 +1,348 @@
+//! `.paveignore` parsing.
+//!
+//! A `.paveignore` file at the project root lists paths that every command's
+//! file discovery skips, using gitignore syntax: `#` comments, `!` to
+//! re-include, a trailing `/` for directories only, and a leading or interior
+//! `/` to anchor a pattern to the project root. Later rules take precedence
+//! over earlier ones, and a file inside an ignored directory can't be
+//! re-included.
+//!
+//! The project root's `.gitignore` is read first, so `.paveignore` can
+//! re-include what git ignores. Nested `.gitignore` files are not read, and
+//! a `.gitignore` line pave can't translate is skipped with a warning.
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Name of the ignore file, read from the project root.
+pub const IGNORE_FILENAME: &str = ".paveignore";
+
+/// Name of git's ignore file, read from the project root before [`IGNORE_FILENAME`].
+pub const GITIGNORE_FILENAME: &str = ".gitignore";
+
+/// A single pattern from an ignore file or config.
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    /// The raw pattern as written in the file.
+    pub pattern: String,
+    /// Where the rule comes from, such as `.paveignore`.
+    pub source: String,
+    /// Line number of the rule in the file (1-indexed).
+    pub line: usize,
+    /// Whether the rule re-includes matching paths (`!pattern`).
+    pub negated: bool,
+    /// Whether the rule only matches directories (`pattern/`).
+    dir_only: bool,
+    /// Whether the rule matches from the project root rather than any depth.
+    anchored: bool,
+    glob: Pattern,
+}
+
+/// Parsed ignore rules for a project.
+#[derive(Debug, Clone, Default)]
+pub struct PaveIgnore {
+    /// Project root that patterns are relative to.
+    root: PathBuf,
+    /// Rules in file order.
+    pub rules: Vec<IgnoreRule>,
+}
+
+impl PaveIgnore {
+    /// Load the `.gitignore` and `.paveignore` files in `project_root`;
+    /// missing files contribute no rules.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let mut rules = Vec::new();
+        for name in [GITIGNORE_FILENAME, IGNORE_FILENAME] {
+            let path = project_root.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            rules.extend(parse_rules(&content, name)?);
+        }
+        Ok(Self {
+            root: project_root.to_path_buf(),
+            rules,
+        })
+    }
+
+    /// Parse `.paveignore` content for a project root.
+    pub fn parse(project_root: &Path, content: &str) -> Result<Self> {
+        Ok(Self {
+            root: project_root.to_path_buf(),
+            rules: parse_rules(content, IGNORE_FILENAME)?,
+        })
+    }
+
+    /// Add patterns from config, such as `[docs] exclude`, ahead of the
+    /// ignore files so those can still re-include paths.
+    pub fn with_excludes(mut self, patterns: &[String], source: &str) -> Result<Self> {
+        let content = patterns.join("\n");
+        let mut rules = parse_rules(&content, source)?;
+        rules.append(&mut self.rules);
+        self.rules = rules;
+        Ok(self)
+    }
+
+    /// Whether discovery should skip `path`.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.matching_rule(path).is_some_and(|rule| !rule.negated)
+    }
+
+    /// Whether `path` is excluded, given whether config-based excludes such
+    /// as `mapping.exclude` match it. An ignore-file rule that matches the
+    /// path, including a `!` re-include, overrides the config.
+    pub fn is_excluded(&self, path: &Path, excluded_by_config: bool) -> bool {
+        match self.matching_rule(path) {
+            Some(rule) => !rule.negated,
+            None => excluded_by_config,
+        }
+    }
+
+    /// The rule that decides whether `path` is ignored, if any matches.
+    ///
+    /// This is the last rule matching the path itself, unless one of its
+    /// parent directories is ignored, in which case it is the rule that
+    /// ignored the directory.
+    pub fn matching_rule(&self, path: &Path) -> Option<&IgnoreRule> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let relative = self.relative(path)?;
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        for end in 1..=components.len() {
+            let is_last = end == components.len();
+            let is_dir = !is_last || path.is_dir();
+            let candidate = components[..end].join("/");
+            let name = &components[end - 1];
+            let rule = self
+                .rules
+                .iter()
+                .rev()
+                .find(|rule| rule.matches(&candidate, name, is_dir));
+
+            if is_last {
+                return rule;
+            }
+            if let Some(rule) = rule
+                && !rule.negated
+            {
+                return Some(rule);
+            }
+        }
+        None
+    }
+
+    /// Express `path` relative to the project root, or None if outside it.
+    fn relative(&self, path: &Path) -> Option<PathBuf> {
+        let root = std::path::absolute(&self.root).ok()?;
+        let path = std::path::absolute(path).ok()?;
+        path.strip_prefix(&root).ok().map(Path::to_path_buf)
+    }
+}
+
+/// Parse ignore rules in gitignore syntax, read from `source`.
+fn parse_rules(content: &str, source: &str) -> Result<Vec<IgnoreRule>> {
+    let mut rules = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (negated, body) = match trimmed.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('\\').unwrap_or(trimmed)),
+        };
+        let dir_only = body.ends_with('/');
+        let body = body.trim_end_matches('/');
+        let anchored = body.contains('/');
+        let body = body.trim_start_matches('/');
+        if body.is_empty() {
+            continue;
+        }
+
+        let glob = match compile(body) {
+            Ok(glob) => glob,
+            // Git owns .gitignore, so a line it accepts can't stop discovery
+            Err(e) if source == GITIGNORE_FILENAME => {
+                warn!(
+                    "Skipping pattern '{}' on line {} of {}: {}",
+                    trimmed,
+                    idx + 1,
+                    source,
+                    e
+                );
+                continue;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Invalid pattern '{}' on line {} of {}",
+                        trimmed,
+                        idx + 1,
+                        source
+                    )
+                });
+            }
+        };
+        rules.push(IgnoreRule {
+            pattern: trimmed.to_string(),
+            source: source.to_string(),
+            line: idx + 1,
+            negated,
+            dir_only,
+            anchored,
+            glob,
+        });
+    }
+    Ok(rules)
+}
+
+/// Check that an ignore-file pattern, such as a `[docs] exclude` entry,
+/// compiles.
+pub fn validate_pattern(pattern: &str) -> Result<(), glob::PatternError> {
+    let body = match pattern.trim().strip_prefix('!') {
+        Some(rest) => rest,
+        None => pattern.trim().strip_prefix('\\').unwrap_or(pattern.trim()),
+    };
+    compile(body.trim_matches('/')).map(|_| ())
+}
+
+/// Compile a pattern body, with `!` and slashes at either end removed.
+fn compile(body: &str) -> Result<Pattern, glob::PatternError> {
+    Pattern::new(&glob_syntax(body))
+}
+
+/// Translate a gitignore pattern to `glob` syntax. Git only gives `**`
+/// special meaning as a whole path component; elsewhere consecutive
+/// asterisks act as one `*`, which `glob` would reject.
+fn glob_syntax(pattern: &str) -> String {
+    pattern
+        .split('/')
+        .map(|component| {
+            if component == "**" {
+                return component.to_string();
+            }
+            let mut translated = String::with_capacity(component.len());
+            for c in component.chars() {
+                if !(c == '*' && translated.ends_with('*')) {
+                    translated.push(c);
+                }
+            }
+            translated
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl IgnoreRule {
+    /// Whether the rule matches a root-relative path whose last component is `name`.
+    fn matches(&self, candidate: &str, name: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let options = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        if self.anchored {
+            self.glob.matches_with(candidate, options)
+        } else {
+            self.glob.matches_with(name, options)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignore(content: &str) -> PaveIgnore {
+        PaveIgnore::parse(Path::new("/project"), content).unwrap()
+    }
+
+    fn ignored(ignore: &PaveIgnore, path: &str) -> bool {
+        ignore.is_ignored(&Path::new("/project").join(path))
+    }
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        let rules = ignore("# drafts\n*.draft.md\nscratch/\n");
+        assert!(ignored(&rules, "docs/api.draft.md"));
+        assert!(ignored(&rules, "docs/scratch/notes.md"));
+        assert!(!ignored(&rules, "docs/api.md"));
+        // `scratch/` only matches directories
+        assert!(!ignored(&rules, "docs/scratch"));
+    }
+
+    #[test]
+    fn anchored_patterns_match_from_root() {
+        let rules = ignore("/notes.md\ndocs/archive/*\ndocs/**/tmp.md\n");
+        assert!(ignored(&rules, "notes.md"));
+        assert!(!ignored(&rules, "docs/notes.md"));
+        assert!(ignored(&rules, "docs/archive/old.md"));
+        assert!(ignored(&rules, "docs/archive/deep/old.md"));
+        assert!(ignored(&rules, "docs/tmp.md"));
+        assert!(ignored(&rules, "docs/a/b/tmp.md"));
+    }
+
+    #[test]
+    fn negation_re_includes_unless_parent_is_ignored() {
+        let rules = ignore("*.md\n!keep.md\narchive/\n!archive/keep.md\n");
+        assert!(ignored(&rules, "docs/a.md"));
+        assert!(!ignored(&rules, "docs/keep.md"));
+        assert!(ignored(&rules, "archive/keep.md"));
+
+        let rule = rules
+            .matching_rule(Path::new("/project/archive/keep.md"))
+            .unwrap();
+        assert_eq!((rule.pattern.as_str(), rule.line), ("archive/", 3));
+    }
+
+    #[test]
+    fn paveignore_overrides_config_excludes() {
+        let rules = ignore("!vendor/patched.rs\ngenerated.rs\n");
+        let path = Path::new("/project/vendor/patched.rs");
+        assert!(!rules.is_excluded(path, true));
+        assert!(rules.is_excluded(Path::new("/project/src/generated.rs"), false));
+        assert!(rules.is_excluded(Path::new("/project/vendor/other.rs"), true));
+    }
+
+    #[test]
+    fn paths_outside_the_root_are_not_ignored() {
+        let rules = ignore("*.md\n");
+        assert!(!rules.is_ignored(Path::new("/elsewhere/a.md")));
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported() {
+        let err = PaveIgnore::parse(Path::new("/project"), "ok.md\n[\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn gitignore_asterisk_runs_act_as_one_asterisk() {
+        let rules = ignore("logs**\n");
+        assert!(ignored(&rules, "logs"));
+        assert!(ignored(&rules, "docs/logs-old.md"));
+        assert!(!ignored(&rules, "docs/a.md"));
+    }
+
+    #[test]
+    fn untranslatable_gitignore_lines_are_skipped() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(GITIGNORE_FILENAME), "[\n*.log\n").unwrap();
+        let rules = PaveIgnore::load(temp_dir.path()).unwrap();
+        assert_eq!(rules.rules.len(), 1);
+        assert!(rules.is_ignored(&temp_dir.path().join("debug.log")));
+    }
+}
@@ -0,0 +1,219 @@
+//! Shared snippets pulled into documents with include markers.
+//!
+//! A line holding only an include marker is replaced by the named file when
+//! the docs are built:
+//!
+//! ```markdown
+//! <!-- pave:include _includes/env-setup.md -->
+//! ```
+//!
+//! Paths are relative to the including document and must stay within the
+//! docs root. Snippets may include other snippets; their frontmatter is
+//! dropped. Markers inside code blocks are left alone so docs can show them.
+//! Snippets kept in an [`INCLUDES_DIR`] directory are not docs themselves,
+//! so `check`, `index`, and `build` skip them.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{Annotation, CodeBlockTracker, frontmatter_len};
+
+/// Directory name for snippets that are only ever included.
+pub const INCLUDES_DIR: &str = "_includes";
+
+/// An include marker in a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Include {
+    /// 1-indexed line of the marker.
+    pub line: usize,
+    /// Path as written in the marker.
+    pub target: String,
+}
+
+/// Include markers in `content`, skipping code blocks.
+pub fn find_includes(content: &str) -> Vec<Include> {
+    let mut tracker = CodeBlockTracker::new();
+    let mut includes = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        if tracker.process_line(line) || tracker.in_code_block() {
+            continue;
+        }
+        if let Some(Ok(Annotation::Include(target))) = Annotation::parse(line) {
+            includes.push(Include {
+                line: idx + 1,
+                target,
+            });
+        }
+    }
+    includes
+}
+
+/// Resolve an include `target` written in `doc`, requiring it to exist
+/// within `docs_root`.
+pub fn resolve(doc: &Path, target: &str, docs_root: &Path) -> Result<PathBuf> {
+    let dir = doc.parent().unwrap_or_else(|| Path::new("."));
+    let path = dir.join(target);
+    let resolved = path
+        .canonicalize()
+        .with_context(|| format!("included file not found: {}", target))?;
+    let root = docs_root
+        .canonicalize()
+        .with_context(|| format!("docs root not found: {}", docs_root.display()))?;
+    if !resolved.starts_with(&root) {
+        bail!("included file is outside the docs root: {}", target);
+    }
+    if !resolved.is_file() {
+        bail!("included path is not a file: {}", target);
+    }
+    Ok(resolved)
+}
+
+/// Replace the include markers in `content`, a document at `doc`, with the
+/// files they name, recursively.
+pub fn expand(content: &str, doc: &Path, docs_root: &Path) -> Result<String> {
+    let mut stack = vec![doc.canonicalize().unwrap_or_else(|_| doc.to_path_buf())];
+    expand_nested(content, doc, docs_root, &mut stack)
+}
+
+fn expand_nested(
+    content: &str,
+    doc: &Path,
+    docs_root: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<String> {
+    let includes = find_includes(content);
+    if includes.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut next = includes.iter().peekable();
+    for (idx, line) in content.lines().enumerate() {
+        let Some(include) = next.next_if(|include| include.line == idx + 1) else {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        };
+        let snippet = load(&include.target, doc, docs_root, stack).with_context(|| {
+            format!(
+                "line {}: failed to include {}",
+                include.line, include.target
+            )
+        })?;
+        result.push_str(&snippet);
+    }
+    if !content.ends_with('\n') {
+        result.pop();
+    }
+    Ok(result)
+}
+
+/// Read and expand one included file, ending with a newline.
+fn load(target: &str, doc: &Path, docs_root: &Path, stack: &mut Vec<PathBuf>) -> Result<String> {
+    let path = resolve(doc, target, docs_root)?;
+    if stack.contains(&path) {
+        bail!("include cycle: {} includes itself", target);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read file: {}", path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let body = lines[frontmatter_len(&lines)..].join("\n");
+
+    stack.push(path.clone());
+    let expanded = expand_nested(&body, &path, docs_root, stack);
+    stack.pop();
+    let mut expanded = expanded?;
+    if !expanded.ends_with('\n') {
+        expanded.push('\n');
+    }
+    Ok(expanded)
+}
+
+/// Problems with the includes in `content`, a document at `doc`, as
+/// (line, message) pairs: missing files, files outside `docs_root`, and
+/// cycles or problems in nested includes.
+pub fn check(content: &str, doc: &Path, docs_root: &Path) -> Vec<(usize, String)> {
+    let mut stack = vec![doc.canonicalize().unwrap_or_else(|_| doc.to_path_buf())];
+    find_includes(content)
+        .into_iter()
+        .filter_map(|include| {
+            load(&include.target, doc, docs_root, &mut stack)
+                .err()
+                .map(|err| (include.line, format!("{:#}", err)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn finds_markers_outside_code_blocks() {
+        let content = "<!-- pave:include a.md -->\n\
+                       <!--pave:include  b.md-->\n\
+                       ```\n<!-- pave:include c.md -->\n```\n\
+                       <!-- pave:includex d.md -->\n<!-- pave:include -->\n";
+        let targets: Vec<_> = find_includes(content)
+            .into_iter()
+            .map(|include| (include.line, include.target))
+            .collect();
+        assert_eq!(targets, [(1, "a.md".to_string()), (2, "b.md".to_string())]);
+    }
+
+    #[test]
+    fn expands_nested_includes_relative_to_each_file() {
+        let temp = TempDir::new().unwrap();
+        let docs = temp.path().join("docs");
+        write(
+            &docs,
+            "_includes/setup.md",
+            "---\npave:\n  owner: ops\n---\nInstall:\n<!-- pave:include env.md -->\n",
+        );
+        write(&docs, "_includes/env.md", "export ENV=prod");
+        let doc = write(
+            &docs,
+            "runbooks/deploy.md",
+            "# Deploy\n<!-- pave:include ../_includes/setup.md -->\nDone.",
+        );
+
+        let content = fs::read_to_string(&doc).unwrap();
+        let expanded = expand(&content, &doc, &docs).unwrap();
+        assert_eq!(expanded, "# Deploy\nInstall:\nexport ENV=prod\nDone.");
+    }
+
+    #[test]
+    fn reports_missing_escaping_and_cyclic_includes() {
+        let temp = TempDir::new().unwrap();
+        let docs = temp.path().join("docs");
+        write(temp.path(), "secret.md", "outside");
+        write(&docs, "_includes/a.md", "<!-- pave:include b.md -->");
+        write(&docs, "_includes/b.md", "<!-- pave:include a.md -->");
+        let content = "# Doc\n\
+                       <!-- pave:include _includes/missing.md -->\n\
+                       <!-- pave:include ../secret.md -->\n\
+                       <!-- pave:include _includes/a.md -->\n";
+        let doc = write(&docs, "doc.md", content);
+
+        let problems = check(content, &doc, &docs);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].0, 2);
+        assert!(problems[0].1.contains("not found: _includes/missing.md"));
+        assert!(
+            problems[1]
+                .1
+                .contains("outside the docs root: ../secret.md")
+        );
+        assert!(problems[2].1.contains("include cycle"));
+        assert!(expand(content, &doc, &docs).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Drift between a document's Interface section and the source it describes.
+//!
+//! A document opts in by naming its source in frontmatter:
+//!
+//! ```yaml
+//! pave:
+//!   interface:
+//!     source: [src/cli.rs]
+//!     item: Verify
+//!     help_cmd: verify
+//! ```
+//!
+//! `help_cmd` names a command in `[lint.help_cmds]`, so documents can't run
+//! commands of their own:
+//!
+//! ```toml
+//! [lint.help_cmds]
+//! verify = "pave verify --help"
+//! ```
+//!
+//! Flags come from that command's output when it is set, otherwise from
+//! `#[arg(long)]` and `#[clap(long)]` fields in the `source` files, limited
+//! to the struct or enum variant named by `item`. Every `--flag` written in
+//! the Interface section must still exist, and every flag in the source must
+//! be documented. Functions written as `` `name()` `` must still be defined
+//! with `fn name` in a source file.
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Flags clap adds to every command, which needn't be documented.
+const BUILTIN_FLAGS: &[&str] = &["help", "version"];
+
+/// Where a document's interface is defined, from `pave.interface`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct InterfaceSource {
+    /// Source files or globs, relative to the project root.
+    #[serde(default)]
+    pub source: Vec<String>,
+    /// Struct or enum variant whose fields are the flags, e.g. `VerifyArgs`.
+    /// Every flag in the source files counts when unset.
+    #[serde(default)]
+    pub item: Option<String>,
+    /// Name of the command in `[lint.help_cmds]` whose output lists the
+    /// flags. Run with `sh -c` from the project root.
+    #[serde(default)]
+    pub help_cmd: Option<String>,
+}
+
+/// Flags and functions named in an Interface section, each with the line it
+/// first appears on (0-based, within the section).
+#[derive(Debug, Default, PartialEq)]
+pub struct Documented {
+    /// Long flags without their leading dashes.
+    pub flags: BTreeMap<String, usize>,
+    /// Function names, without any path or arguments.
+    pub functions: BTreeMap<String, usize>,
+}
+
+/// Differences between the documented and the actual interface.
+#[derive(Debug, Default, PartialEq)]
+pub struct Drift {
+    /// Documented flags the source no longer has, with their lines.
+    pub removed_flags: Vec<(String, usize)>,
+    /// Flags in the source that the section doesn't mention.
+    pub undocumented_flags: Vec<String>,
+    /// Documented functions the source no longer defines, with their lines.
+    pub removed_functions: Vec<(String, usize)>,
+}
+
+/// Collect the flags and functions an Interface section mentions.
+///
+/// Flags count anywhere, including usage blocks; functions only in inline
+/// code such as `` `Request::parse(text)` ``.
+pub fn documented(section: &str) -> Documented {
+    let flag = Regex::new(r"(?:^|[^\w-])--([a-z][a-z0-9-]*)").unwrap();
+    let function = Regex::new(r"`(?:[A-Za-z_][A-Za-z0-9_]*::)*([A-Za-z_][A-Za-z0-9_]*)\(").unwrap();
+
+    let mut documented = Documented::default();
+    for (idx, line) in section.lines().enumerate() {
+        for caps in flag.captures_iter(line) {
+            documented.flags.entry(caps[1].to_string()).or_insert(idx);
+        }
+        for caps in function.captures_iter(line) {
+            documented
+                .functions
+                .entry(caps[1].to_string())
+                .or_insert(idx);
+        }
+    }
+    documented
+}
+
+/// Long flags listed in `--help` style output.
+pub fn help_flags(output: &str) -> BTreeSet<String> {
+    let flag = Regex::new(r"(?:^|[\s,\[])--([a-z][a-z0-9-]*)").unwrap();
+    output
+        .lines()
+        .flat_map(|line| flag.captures_iter(line))
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Long flags declared by clap derive attributes in Rust source.
+///
+/// With `item`, only the fields of that struct or enum variant count;
+/// returns `None` if it isn't in `source`.
+pub fn clap_flags(source: &str, item: Option<&str>) -> Option<BTreeSet<String>> {
+    let source = match item {
+        Some(item) => item_body(source, item)?,
+        None => source,
+    };
+    let field =
+        Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:r#)?([a-z_][a-z0-9_]*)\s*:[^:]").unwrap();
+
+    let mut flags = BTreeSet::new();
+    let mut rest = source;
+    while let Some(start) = ["#[arg(", "#[clap("]
+        .iter()
+        .filter_map(|attr| rest.find(attr).map(|idx| idx + attr.len()))
+        .min()
+    {
+        let args_len = closing_paren(&rest[start..]);
+        let args = &rest[start..start + args_len];
+        rest = &rest[start + args_len..];
+
+        let Some(long) = long_name(args) else {
+            continue;
+        };
+        let name = match long {
+            Some(name) => name,
+            None => match field.captures(rest) {
+                Some(caps) => caps[1].replace('_', "-"),
+                None => continue,
+            },
+        };
+        flags.insert(name);
+    }
+    Some(flags)
+}
+
+/// Names of the functions defined in Rust source.
+pub fn functions(source: &str) -> BTreeSet<String> {
+    let function = Regex::new(r"\bfn\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    function
+        .captures_iter(source)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Compare what a section documents with the flags and functions found in
+/// the source. Functions are skipped when `functions` is `None`.
+pub fn compare(
+    documented: &Documented,
+    flags: &BTreeSet<String>,
+    functions: Option<&BTreeSet<String>>,
+) -> Drift {
+    let builtin = |name: &str| BUILTIN_FLAGS.contains(&name);
+    Drift {
+        removed_flags: documented
+            .flags
+            .iter()
+            .filter(|(name, _)| !flags.contains(*name) && !builtin(name))
+            .map(|(name, line)| (name.clone(), *line))
+            .collect(),
+        undocumented_flags: flags
+            .iter()
+            .filter(|name| !documented.flags.contains_key(*name) && !builtin(name))
+            .cloned()
+            .collect(),
+        removed_functions: functions
+            .map(|defined| {
+                documented
+                    .functions
+                    .iter()
+                    .filter(|(name, _)| !defined.contains(*name))
+                    .map(|(name, line)| (name.clone(), *line))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// The braced body of the struct, enum, or enum variant named `item`.
+fn item_body<'a>(source: &'a str, item: &str) -> Option<&'a str> {
+    let pattern = format!(
+        r"(?m)(?:\b(?:struct|enum)\s+{item}\b|^\s*{item}\s*\{{)",
+        item = regex::escape(item)
+    );
+    let found = Regex::new(&pattern).ok()?.find(source)?;
+    let open = found.start() + source[found.start()..].find('{')?;
+    let mut depth = 0;
+    for (idx, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[open..open + idx]);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(&source[open..])
+}
+
+/// Length of an attribute's arguments, up to its unbalanced `)`.
+fn closing_paren(text: &str) -> usize {
+    let mut depth = 0;
+    let mut in_string = false;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' if !in_string => depth += 1,
+            ')' | ']' if !in_string => {
+                if depth == 0 {
+                    return idx;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// The `long` setting in attribute arguments: `Some(None)` for a bare `long`
+/// named after the field, `Some(Some(name))` for `long = "name"`.
+fn long_name(args: &str) -> Option<Option<String>> {
+    let long = Regex::new(r#"(?:^|[\s,])long\s*(?:=\s*"([^"]*)")?\s*(?:,|$)"#).unwrap();
+    long.captures(args)
+        .map(|caps| caps.get(1).map(|name| name.as_str().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLI: &str = r#"
+pub enum Commands {
+    /// Check documents.
+    Check {
+        #[arg(long, default_value = "text", value_enum)]
+        format: OutputFormat,
+        #[arg(short, long)]
+        strict: bool,
+    },
+    /// Verify documents.
+    Verify {
+        #[arg(
+            long = "for",
+            conflicts_with_all = ["changed", "strict"],
+        )]
+        for_paths: Vec<PathBuf>,
+        #[arg(long)]
+        pub no_services: bool,
+        #[arg(short = 'j')]
+        jobs: usize,
+    },
+}
+
+pub fn execute(args: Commands) {}
+"#;
+
+    #[test]
+    fn reads_clap_flags_of_an_item() {
+        let flags = |item| clap_flags(CLI, item).map(|flags| flags.into_iter().collect::<Vec<_>>());
+        assert_eq!(flags(Some("Verify")).unwrap(), ["for", "no-services"]);
+        assert_eq!(flags(Some("Check")).unwrap(), ["format", "strict"]);
+        assert_eq!(flags(None).unwrap().len(), 4);
+        assert_eq!(flags(Some("Lint")), None);
+    }
+
+    #[test]
+    fn reads_help_output() {
+        let help = "Usage: pave verify [OPTIONS] [PATHS]...\n\n\
+                    Options:\n  -j, --jobs <N>     Parallel jobs\n      \
+                    --no-services  Skip services\n  -h, --help         Print help\n";
+        let flags: Vec<String> = help_flags(help).into_iter().collect();
+        assert_eq!(flags, ["help", "jobs", "no-services"]);
+    }
+
+    #[test]
+    fn reports_drift_in_both_directions() {
+        let section = "\nRun `pave verify --for <PATH>` or call `commands::execute(args)`.\n\n\
+                       | `--keep-going` | Removed flag |\n\
+                       Also see `--help` and `plan()`.\n";
+        let documented = documented(section);
+        assert_eq!(documented.flags.get("keep-going"), Some(&3));
+        assert_eq!(documented.functions.get("execute"), Some(&1));
+
+        let flags = clap_flags(CLI, Some("Verify")).unwrap();
+        let drift = compare(&documented, &flags, Some(&functions(CLI)));
+        assert_eq!(drift.removed_flags, [("keep-going".to_string(), 3)]);
+        assert_eq!(drift.undocumented_flags, ["no-services"]);
+        assert_eq!(drift.removed_functions, [("plan".to_string(), 4)]);
+
+        let drift = compare(&documented, &flags, None);
+        assert!(drift.removed_functions.is_empty());
+    }
+}
@@ -1,7 +1,50 @@
+pub mod admonition;
+pub mod audit;
+pub mod blame;
+pub mod cargo;
 pub mod cli;
+pub mod codeowners;
+pub mod color;
 pub mod commands;
+pub mod compose;
 pub mod config;
+pub mod config_validate;
+pub mod container;
+pub mod exit;
+pub mod git;
+pub mod html;
+pub mod http;
+pub mod ignore;
+pub mod include;
+pub mod interface;
+pub mod locales;
+pub mod logging;
+pub mod mermaid;
+pub mod parallel;
 pub mod parser;
+pub mod paths;
+pub mod plugins;
+pub mod presets;
+pub mod progress;
+pub mod readability;
+pub mod redact;
+pub mod remote;
+pub mod result_cache;
+pub mod rule_docs;
 pub mod rules;
+pub mod schema;
+pub mod secrets;
+pub mod services;
+pub mod spelling;
+pub mod sql;
+pub mod stats_cache;
 pub mod templates;
+pub mod terminology;
+pub mod text_diff;
+pub mod vars;
 pub mod verification;
+pub mod verify_cache;
+pub mod verify_export;
+pub mod verify_update;
+pub mod walk;
+pub mod workspace;
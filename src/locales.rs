@@ -0,0 +1,150 @@
+//! Translated doc sets kept side by side under the docs root.
+//!
+//! With `[docs.locales]`, every locale has a directory below the docs root,
+//! and a translation sits at the same relative path as the doc it translates:
+//!
+//! ```toml
+//! [docs.locales]
+//! source = "en"
+//! translations = ["ja", "de"]
+//! required = ["runbooks/**"]
+//! ```
+//!
+//! Here `docs/en/runbooks/deploy.md` is translated by
+//! `docs/ja/runbooks/deploy.md` and `docs/de/runbooks/deploy.md`.
+//! `pave check` reports required source docs that a translation lacks, and
+//! `pave translate-status` also lists translations committed before the
+//! latest change to their source.
+
+use glob::Pattern;
+use std::path::{Path, PathBuf};
+
+use crate::config::LocalesSection;
+use crate::paths::absolutize;
+
+/// Where one translation of a source doc belongs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterpart {
+    /// The translation's locale.
+    pub locale: String,
+    /// Path of the translation.
+    pub path: PathBuf,
+    /// Whether `[docs.locales] required` asks for this translation.
+    pub required: bool,
+}
+
+/// Directory holding the docs of `locale`.
+pub fn locale_dir(docs_root: &Path, locale: &str) -> PathBuf {
+    docs_root.join(locale)
+}
+
+/// Counterparts of `source` in every translation, whether or not they
+/// exist. Empty when `source` isn't under the source locale's directory.
+/// Counterpart paths are under `docs_root` as given.
+pub fn counterparts(source: &Path, docs_root: &Path, locales: &LocalesSection) -> Vec<Counterpart> {
+    let source = normalize(source);
+    let Ok(relative) = source.strip_prefix(normalize(&locale_dir(docs_root, &locales.source)))
+    else {
+        return Vec::new();
+    };
+    let required = is_required(relative, &locales.required);
+    locales
+        .translations
+        .iter()
+        .map(|locale| Counterpart {
+            locale: locale.clone(),
+            path: locale_dir(docs_root, locale).join(relative),
+            required,
+        })
+        .collect()
+}
+
+/// The configured locale whose directory holds `path`, if any.
+pub fn locale_of<'a>(
+    path: &Path,
+    docs_root: &Path,
+    locales: &'a LocalesSection,
+) -> Option<&'a str> {
+    let path = normalize(path);
+    std::iter::once(&locales.source)
+        .chain(&locales.translations)
+        .find(|locale| path.starts_with(normalize(&locale_dir(docs_root, locale))))
+        .map(String::as_str)
+}
+
+/// `path` made absolute against the current directory, so a doc named on
+/// the command line matches the config's docs root however each is written.
+fn normalize(path: &Path) -> PathBuf {
+    absolutize(path, &std::env::current_dir().unwrap_or_default())
+}
+
+/// Whether a doc at `relative`, within the source locale's directory, is
+/// matched by the `required` globs. Everything is when there are none.
+fn is_required(relative: &Path, required: &[String]) -> bool {
+    required.is_empty()
+        || required
+            .iter()
+            .any(|glob| Pattern::new(glob).is_ok_and(|pattern| pattern.matches_path(relative)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_source_docs_to_translations() {
+        let locales = LocalesSection {
+            source: "en".to_string(),
+            translations: vec!["ja".to_string(), "de".to_string()],
+            required: vec!["runbooks/**".to_string()],
+        };
+        let root = Path::new("docs");
+
+        let found = counterparts(Path::new("docs/en/runbooks/deploy.md"), root, &locales);
+        assert_eq!(
+            found,
+            [
+                Counterpart {
+                    locale: "ja".to_string(),
+                    path: PathBuf::from("docs/ja/runbooks/deploy.md"),
+                    required: true,
+                },
+                Counterpart {
+                    locale: "de".to_string(),
+                    path: PathBuf::from("docs/de/runbooks/deploy.md"),
+                    required: true,
+                },
+            ]
+        );
+
+        let optional = counterparts(Path::new("docs/en/guide.md"), root, &locales);
+        assert!(optional.iter().all(|counterpart| !counterpart.required));
+        assert!(counterparts(Path::new("docs/ja/guide.md"), root, &locales).is_empty());
+
+        assert_eq!(
+            locale_of(Path::new("docs/ja/guide.md"), root, &locales),
+            Some("ja")
+        );
+        assert_eq!(locale_of(Path::new("docs/index.md"), root, &locales), None);
+    }
+
+    #[test]
+    fn relative_docs_match_an_absolute_docs_root() {
+        let locales = LocalesSection {
+            source: "en".to_string(),
+            translations: vec!["ja".to_string()],
+            required: Vec::new(),
+        };
+        // As with `pave check docs/en/deploy.md`, where the docs root comes
+        // from the config's directory
+        let root = std::env::current_dir().unwrap().join("docs");
+
+        let found = counterparts(Path::new("./docs/en/deploy.md"), &root, &locales);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, root.join("ja/deploy.md"));
+        assert_eq!(
+            locale_of(Path::new("docs/ja/deploy.md"), &root, &locales),
+            Some("ja")
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Diagnostic logging to stderr, controlled by the global `-v` and `--quiet`
+//! flags.
+//!
+//! Commands log through the `tracing` macros, keeping stdout for their
+//! results:
+//!
+//! - `warn!` for problems that don't stop the command, shown by default
+//! - `info!` (`-v`) for decisions a user may need explained, such as which
+//!   config was found and why a file was skipped
+//! - `debug!` (`-vv`) for per-document detail such as check timings
+//! - `trace!` (`-vvv`) for everything else, such as each directory walked
+//!
+//! `--quiet` leaves only errors.
+
+use tracing::level_filters::LevelFilter;
+
+/// The most verbose level shown for the given flags.
+pub fn level(verbose: u8, quiet: bool) -> LevelFilter {
+    if quiet {
+        return LevelFilter::ERROR;
+    }
+    match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Install the stderr logger. Call once, before running a command.
+pub fn init(verbose: u8, quiet: bool) {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(level(verbose, quiet))
+        .with_target(false)
+        .without_time()
+        .init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_flags_map_to_levels() {
+        assert_eq!(level(0, false), LevelFilter::WARN);
+        assert_eq!(level(1, false), LevelFilter::INFO);
+        assert_eq!(level(2, false), LevelFilter::DEBUG);
+        assert_eq!(level(5, false), LevelFilter::TRACE);
+        assert_eq!(level(0, true), LevelFilter::ERROR);
+    }
+}
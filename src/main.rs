@@ -1,30 +1,97 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use pave::cli::{
-    AdoptOutputFormat, Cli, Command, ConfigCommand, DocType, HooksCommand, MigrateOutputFormat,
-    PromptOutputFormat,
+    AdoptOutputFormat, AuditCommand, CiCommand, Cli, Command, ConfigCommand, HooksCommand,
+    IndexFormat, MigrateOutputFormat, PromptOutputFormat, ReportCommand, TemplatesCommand,
 };
 use pave::commands::adopt::{self, AdoptArgs};
+use pave::commands::audit;
+use pave::commands::bench::BenchArgs;
 use pave::commands::build;
 use pave::commands::changed::{self, ChangedArgs};
-use pave::commands::check::{self, CheckArgs};
+use pave::commands::check::CheckArgs;
+use pave::commands::ci::{self, CiArgs, CiOptions};
 use pave::commands::config;
-use pave::commands::coverage::{self, CoverageArgs};
+use pave::commands::coverage::CoverageArgs;
 use pave::commands::coverage_changed::{self, CoverageChangedArgs};
+use pave::commands::dedupe::{self, DedupeArgs};
 use pave::commands::doctor::{self, DoctorArgs};
+use pave::commands::fleet::{self, FleetArgs};
 use pave::commands::hooks;
+use pave::commands::import::{self, ImportArgs};
 use pave::commands::index;
 use pave::commands::init;
-use pave::commands::lint::{self, LintArgs};
+use pave::commands::lint::LintArgs;
 use pave::commands::migrate::{self, MigrateArgs};
+use pave::commands::mv::{self, MvArgs};
 use pave::commands::new::{self, NewArgs};
+use pave::commands::nightly::{self, NightlyArgs};
 use pave::commands::prompt::{OutputFormat, PromptOptions, generate_prompt};
-use pave::commands::status::{self, StatusArgs};
-use pave::commands::verify::{self, VerifyArgs};
-use pave::templates::TemplateType;
+use pave::commands::publish::{self, PublishArgs};
+use pave::commands::report;
+use pave::commands::report_html::{self, HtmlReportArgs};
+use pave::commands::review::{self, ReviewArgs};
+use pave::commands::search::{self, SearchArgs};
+use pave::commands::split::{self, SplitArgs};
+use pave::commands::stale::{self, StaleArgs};
+use pave::commands::status::StatusArgs;
+use pave::commands::summary::{self, SummaryArgs};
+use pave::commands::templates;
+use pave::commands::translate_status::{self, TranslateStatusArgs};
+use pave::commands::verify::VerifyArgs;
+use pave::commands::which::{self, WhichArgs};
+use pave::config::PaveConfig;
+use pave::workspace::{self, Workdir};
+use pave::{color, exit, logging, plugins};
+use std::process::ExitCode;
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
+    logging::init(cli.verbose, cli.quiet);
+    color::set_color_choice(cli.color);
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:?}", err);
+            ExitCode::from(exit::code(&err))
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    // --root is where commands run from; --config is resolved against the
+    // invocation directory, not the root
+    let mut workdir = Workdir::current()?;
+    if let Some(config_path) = &cli.config {
+        let config_path = workdir.resolve(config_path);
+        if !config_path.is_file() {
+            anyhow::bail!("Config file not found: {}", config_path.display());
+        }
+        workdir = workdir.with_config(config_path);
+    }
+    if let Some(root) = &cli.root {
+        let dir = workdir.resolve(root);
+        if !dir.is_dir() {
+            anyhow::bail!("Root directory not found: {}", root.display());
+        }
+        workdir.dir = dir;
+    }
+
+    // Only commands that run across workspace members take --package
+    let packages = cli.package;
+    let per_package = matches!(
+        cli.command,
+        Command::Check { .. }
+            | Command::Lint { .. }
+            | Command::Verify { .. }
+            | Command::Status { .. }
+            | Command::Coverage { .. }
+            | Command::Bench { .. }
+    );
+    if !packages.is_empty() && !per_package {
+        anyhow::bail!("--package only applies to check, lint, verify, status, coverage, and bench");
+    }
 
     match cli.command {
         Command::Adopt {
@@ -32,23 +99,49 @@ fn main() -> Result<()> {
             format,
             suggest_config,
             dry_run,
+            migrate,
         } => {
-            adopt::execute(AdoptArgs {
-                path,
-                format: match format {
-                    AdoptOutputFormat::Text => adopt::AdoptOutputFormat::Text,
-                    AdoptOutputFormat::Json => adopt::AdoptOutputFormat::Json,
+            adopt::execute(
+                AdoptArgs {
+                    path,
+                    format: match format {
+                        AdoptOutputFormat::Text => adopt::AdoptOutputFormat::Text,
+                        AdoptOutputFormat::Json => adopt::AdoptOutputFormat::Json,
+                    },
+                    suggest_config,
+                    dry_run,
+                    migrate,
                 },
-                suggest_config,
-                dry_run,
-            })?;
+                &workdir,
+            )?;
+        }
+        Command::Import {
+            from,
+            path,
+            output,
+            dry_run,
+            force,
+            format,
+        } => {
+            import::execute(
+                ImportArgs {
+                    from,
+                    path,
+                    output,
+                    dry_run,
+                    force,
+                    format,
+                },
+                &workdir,
+            )?;
         }
         Command::Init(args) => {
             init::run(init::InitArgs {
                 docs_root: args.docs_root,
                 skip_hooks: args.skip_hooks,
                 force: args.force,
-                working_dir: None,
+                ci: args.ci,
+                working_dir: Some(workdir.dir.clone()),
             })?;
         }
         Command::Check {
@@ -56,42 +149,62 @@ fn main() -> Result<()> {
             format,
             strict,
             gradual,
+            error_on,
             changed,
             base,
+            path_style,
+            list_ignored,
+            no_cache,
+            jobs,
+            blame,
+            group_by,
         } => {
-            check::execute(CheckArgs {
+            let args = CheckArgs {
                 paths,
                 format,
                 strict,
                 gradual,
+                error_on,
                 changed,
                 base,
-            })?;
+                path_style,
+                list_ignored,
+                no_cache,
+                jobs: jobs.map(usize::from),
+                blame,
+                group_by,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
         }
         Command::New {
             doc_type,
             name,
             output,
+            vars,
+            interactive,
         } => {
-            new::execute(NewArgs {
-                doc_type: doc_type.into(),
-                name,
-                output,
-            })?;
+            new::execute(
+                NewArgs {
+                    doc_type,
+                    name,
+                    output,
+                    vars,
+                    interactive,
+                },
+                &workdir,
+            )?;
         }
         Command::Prompt {
             doc_type,
             name,
             update,
             context,
+            doc,
+            report,
             output,
         } => {
             let options = PromptOptions {
-                doc_type: match doc_type {
-                    DocType::Component => TemplateType::Component,
-                    DocType::Runbook => TemplateType::Runbook,
-                    DocType::Adr => TemplateType::Adr,
-                },
+                doc_type,
                 name,
                 update_path: update.map(|p| p.to_string_lossy().to_string()),
                 context_paths: context
@@ -102,11 +215,33 @@ fn main() -> Result<()> {
                     PromptOutputFormat::Text => OutputFormat::Text,
                     PromptOutputFormat::Json => OutputFormat::Json,
                 },
+                doc_path: doc.map(|p| p.to_string_lossy().to_string()),
+                report_path: report.map(|p| p.to_string_lossy().to_string()),
             };
 
-            let prompt = generate_prompt(&options)?;
+            let prompt = generate_prompt(&options, &workdir)?;
             print!("{}", prompt);
         }
+        Command::Ci(CiCommand::Generate {
+            provider,
+            verify,
+            output,
+            force,
+            stdout,
+        }) => {
+            ci::execute(
+                CiArgs {
+                    provider,
+                    options: CiOptions {
+                        verify,
+                        output,
+                        force,
+                    },
+                    stdout,
+                },
+                &workdir,
+            )?;
+        }
         Command::Hooks(cmd) => match cmd {
             HooksCommand::Install {
                 hook,
@@ -115,42 +250,98 @@ fn main() -> Result<()> {
             } => {
                 // Use --verify flag if specified, otherwise check config
                 let run_verify = verify
-                    || pave::config::PaveConfig::load(pave::config::CONFIG_FILENAME)
-                        .map(|c| c.hooks.run_verify)
-                        .unwrap_or(false);
-                hooks::install(hook, force, run_verify)?;
+                    || workdir
+                        .locate_config()
+                        .ok()
+                        .flatten()
+                        .and_then(|path| PaveConfig::load(path).ok())
+                        .is_some_and(|c| c.hooks.run_verify);
+                hooks::install(hook, force, run_verify, &workdir)?;
             }
             HooksCommand::Uninstall { hook } => {
-                hooks::uninstall(hook)?;
+                hooks::uninstall(hook, &workdir)?;
+            }
+            HooksCommand::Run { hook, verify, args } => {
+                hooks::run(hook, verify, &args, &workdir)?;
             }
         },
         Command::Config(cmd) => match cmd {
             ConfigCommand::Get { key } => {
-                config::get(&key)?;
+                config::get(&key, &workdir)?;
             }
             ConfigCommand::Set { key, value } => {
-                config::set(&key, &value)?;
+                config::set(&key, &value, &workdir)?;
             }
             ConfigCommand::List => {
-                config::list()?;
+                config::list(&workdir)?;
             }
             ConfigCommand::Path => {
-                config::path()?;
+                config::path(&workdir)?;
+            }
+            ConfigCommand::Validate => {
+                config::validate(&workdir)?;
+            }
+        },
+        Command::Templates(cmd) => match cmd {
+            TemplatesCommand::Check { format } => {
+                templates::check(format, &workdir)?;
+            }
+            TemplatesCommand::List { format } => {
+                templates::list(format, &workdir)?;
             }
         },
-        Command::Index { output, update } => {
-            index::run(&output, update)?;
+        Command::Report(cmd) => match cmd {
+            ReportCommand::Diff { old, new, format } => {
+                report::diff(&workdir.resolve(&old), &workdir.resolve(&new), format)?;
+            }
+            ReportCommand::Html {
+                output,
+                verify_report,
+            } => {
+                report_html::html(
+                    HtmlReportArgs {
+                        output,
+                        verify_report,
+                    },
+                    &workdir,
+                )?;
+            }
+        },
+        Command::Audit(cmd) => match cmd {
+            AuditCommand::Verify { log } => {
+                audit::verify(log, &workdir)?;
+            }
+        },
+        Command::Index {
+            output,
+            update,
+            format,
+            check,
+        } => {
+            if matches!(format, IndexFormat::Json) && (update || check) {
+                anyhow::bail!("--format json can't be combined with --update or --check");
+            }
+            index::run(&output, update, check, format, &workdir)?;
+        }
+        Command::Schema { command } => {
+            let schema = pave::schema::schema(command);
+            println!("{}", serde_json::to_string_pretty(&schema)?);
         }
         Command::Changed {
             base,
             format,
             strict,
+            fail_on_uncovered,
         } => {
-            changed::execute(ChangedArgs {
-                base,
-                format,
-                strict,
-            })?;
+            changed::execute(
+                ChangedArgs {
+                    base,
+                    format,
+                    strict,
+                    fail_on_uncovered,
+                },
+                &workdir,
+            )?;
         }
         Command::Verify {
             paths,
@@ -158,17 +349,67 @@ fn main() -> Result<()> {
             report,
             timeout,
             keep_going,
+            path_style,
+            no_cache,
+            no_services,
+            changed,
+            base,
+            export_script,
+            export_make,
+            include_drafts,
+            update_expected,
+            rerun_failed,
         } => {
-            verify::execute(VerifyArgs {
+            let args = VerifyArgs {
                 paths,
                 format,
                 report,
                 timeout,
                 keep_going,
-            })?;
+                path_style,
+                no_cache,
+                no_services,
+                changed,
+                base,
+                export_script,
+                export_make,
+                include_drafts,
+                update_expected,
+                rerun_failed,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
+        }
+        Command::Publish {
+            target,
+            dry_run,
+            include_drafts,
+            force,
+            format,
+        } => {
+            publish::execute(
+                PublishArgs {
+                    target,
+                    dry_run,
+                    include_drafts,
+                    force,
+                    format,
+                },
+                &workdir,
+            )?;
         }
-        Command::Build { output } => {
-            build::execute(build::BuildArgs { output })?;
+        Command::Build {
+            output,
+            include_drafts,
+            format,
+        } => {
+            build::execute(
+                build::BuildArgs {
+                    output,
+                    include_drafts,
+                    format,
+                },
+                &workdir,
+            )?;
         }
         Command::Coverage {
             path,
@@ -176,14 +417,27 @@ fn main() -> Result<()> {
             threshold,
             include,
             exclude,
+            by_owner,
+            codeowners,
+            badge,
+            badge_json,
+            record,
+            trend,
         } => {
-            coverage::execute(CoverageArgs {
+            let args = CoverageArgs {
                 path,
                 format,
                 threshold,
                 include,
                 exclude,
-            })?;
+                by_owner,
+                codeowners,
+                badge,
+                badge_json,
+                record,
+                trend,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
         }
         Command::CoverageChanged {
             base,
@@ -191,43 +445,251 @@ fn main() -> Result<()> {
             include,
             exclude,
         } => {
-            coverage_changed::execute(CoverageChangedArgs {
-                base,
-                format,
-                include,
-                exclude,
-            })?;
+            coverage_changed::execute(
+                CoverageChangedArgs {
+                    base,
+                    format,
+                    include,
+                    exclude,
+                },
+                &workdir,
+            )?;
         }
         Command::Lint {
             paths,
             format,
             fix,
+            interactive,
             rules,
             external_links,
+            max_warnings,
+            path_style,
+            no_cache,
+            jobs,
+            blame,
+            group_by,
         } => {
-            lint::execute(LintArgs {
+            let args = LintArgs {
                 paths,
                 format,
                 fix,
+                interactive,
                 rules,
                 external_links,
-            })?;
+                max_warnings,
+                path_style,
+                no_cache,
+                jobs: jobs.map(usize::from),
+                blame,
+                group_by,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
         }
-        Command::Doctor { paths, format } => {
-            doctor::execute(DoctorArgs { paths, format })?;
+        Command::Doctor {
+            paths,
+            format,
+            path_style,
+            refresh,
+            fix,
+            verify_runs,
+            verify_sample,
+        } => {
+            doctor::execute(
+                DoctorArgs {
+                    paths,
+                    format,
+                    path_style,
+                    refresh,
+                    fix,
+                    verify_runs,
+                    verify_sample,
+                },
+                &workdir,
+            )?;
         }
         Command::Status {
             paths,
             format,
             changed,
             base,
+            refresh,
+            worklist,
+            verify_report,
         } => {
-            status::execute(StatusArgs {
+            let args = StatusArgs {
                 paths,
                 format,
                 changed,
                 base,
-            })?;
+                refresh,
+                worklist,
+                verify_report,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
+        }
+        Command::Fleet {
+            repos,
+            manifest,
+            format,
+            output,
+        } => {
+            fleet::execute(
+                FleetArgs {
+                    repos,
+                    manifest,
+                    format,
+                    output,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Bench {
+            paths,
+            format,
+            runs,
+            top,
+        } => {
+            let args = BenchArgs {
+                paths,
+                format,
+                runs: usize::from(runs),
+                top,
+            };
+            workspace::for_each_package(&workdir, &packages, &args)?;
+        }
+        Command::Stale {
+            format,
+            threshold_days,
+            strict,
+        } => {
+            stale::execute(
+                StaleArgs {
+                    format,
+                    threshold_days,
+                    strict,
+                },
+                &workdir,
+            )?;
+        }
+        Command::TranslateStatus { format, strict } => {
+            translate_status::execute(TranslateStatusArgs { format, strict }, &workdir)?;
+        }
+        Command::Dedupe {
+            format,
+            threshold,
+            min_words,
+            strict,
+        } => {
+            dedupe::execute(
+                DedupeArgs {
+                    format,
+                    threshold,
+                    min_words,
+                    strict,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Summary {
+            base,
+            format,
+            verify_report,
+        } => {
+            summary::execute(
+                SummaryArgs {
+                    base,
+                    format,
+                    verify_report,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Review {
+            format,
+            within_days,
+            owner,
+        } => {
+            review::execute(
+                ReviewArgs {
+                    format,
+                    within_days,
+                    owner,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Search {
+            query,
+            doc_type,
+            section,
+            tag,
+            format,
+            path_style,
+        } => {
+            search::execute(
+                SearchArgs {
+                    query,
+                    doc_type,
+                    section,
+                    tag,
+                    format,
+                    path_style,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Which {
+            path,
+            format,
+            path_style,
+        } => {
+            which::execute(
+                WhichArgs {
+                    path,
+                    format,
+                    path_style,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Nightly {
+            format,
+            output,
+            webhook,
+            stale_days,
+            skip_links,
+            timeout,
+        } => {
+            nightly::execute(
+                NightlyArgs {
+                    format,
+                    output,
+                    webhook,
+                    stale_days,
+                    skip_links,
+                    timeout,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Split {
+            doc,
+            dry_run,
+            yes,
+            sections,
+        } => {
+            split::execute(
+                SplitArgs {
+                    doc,
+                    dry_run,
+                    yes,
+                    sections,
+                },
+                &workdir,
+            )?;
+        }
+        Command::Mv { from, to, dry_run } => {
+            mv::execute(MvArgs { from, to, dry_run }, &workdir)?;
         }
         Command::Migrate {
             path,
@@ -237,17 +699,27 @@ fn main() -> Result<()> {
             interactive,
             backup,
         } => {
-            migrate::execute(MigrateArgs {
-                path,
-                format: match format {
-                    MigrateOutputFormat::Text => migrate::MigrateOutputFormat::Text,
-                    MigrateOutputFormat::Json => migrate::MigrateOutputFormat::Json,
+            migrate::execute(
+                MigrateArgs {
+                    path,
+                    format: match format {
+                        MigrateOutputFormat::Text => migrate::MigrateOutputFormat::Text,
+                        MigrateOutputFormat::Json => migrate::MigrateOutputFormat::Json,
+                    },
+                    dry_run,
+                    sections,
+                    interactive,
+                    backup,
                 },
-                dry_run,
-                sections,
-                interactive,
-                backup,
-            })?;
+                &workdir,
+            )?;
+        }
+        Command::External(args) => {
+            let builtins: Vec<String> = Cli::command()
+                .get_subcommands()
+                .map(|command| command.get_name().to_string())
+                .collect();
+            plugins::run_command_plugin(&args, &builtins, &workdir)?;
         }
     }
 
@@ -0,0 +1,368 @@
+//! Syntax checks for `mermaid` code blocks, for the `mermaid` lint rule.
+//!
+//! Mermaid fails to render a whole diagram over one mistake, and renderers
+//! rarely say so. The built-in check catches the common ones without a
+//! browser: an unknown diagram type, quotes left open on a line, unbalanced
+//! node brackets in flowcharts, and `subgraph`/`loop`/`alt`-style blocks
+//! without a matching `end`.
+//!
+//! For a full parse, set `lint.mermaid_cmd` to a command that reads a
+//! diagram on stdin and exits non-zero on errors, such as
+//! `mmdc -i - -o /tmp/pave-mermaid.svg`. Its first error line is reported,
+//! at the diagram line it names when it says `line N`.
+
+use regex::Regex;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::parser::{CodeBlockTracker, ParsedDoc};
+
+/// Fence language of diagram blocks.
+pub const MERMAID_LANGUAGE: &str = "mermaid";
+
+/// Diagram types mermaid knows, as written on a diagram's first line.
+const DIAGRAM_TYPES: &[&str] = &[
+    "graph",
+    "flowchart",
+    "sequenceDiagram",
+    "classDiagram",
+    "classDiagram-v2",
+    "stateDiagram",
+    "stateDiagram-v2",
+    "erDiagram",
+    "journey",
+    "gantt",
+    "pie",
+    "quadrantChart",
+    "requirementDiagram",
+    "gitGraph",
+    "C4Context",
+    "C4Container",
+    "C4Component",
+    "C4Dynamic",
+    "C4Deployment",
+    "mindmap",
+    "timeline",
+    "zenuml",
+    "sankey-beta",
+    "xychart-beta",
+    "block-beta",
+    "packet-beta",
+    "kanban",
+    "architecture-beta",
+];
+
+/// Flowchart directions.
+const DIRECTIONS: &[&str] = &["TB", "TD", "BT", "RL", "LR"];
+
+/// Keywords that open a block closed by `end`, per diagram type.
+const FLOWCHART_BLOCKS: &[&str] = &["subgraph"];
+const SEQUENCE_BLOCKS: &[&str] = &[
+    "loop", "alt", "opt", "par", "critical", "break", "rect", "box",
+];
+
+/// A `mermaid` block in a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagram {
+    /// Line of the first diagram line in the document (1-indexed).
+    pub first_line: usize,
+    /// The diagram source.
+    pub source: String,
+}
+
+/// A problem in a diagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramError {
+    /// Line within the diagram (1-indexed).
+    pub line: usize,
+    /// What's wrong.
+    pub message: String,
+}
+
+/// Find the `mermaid` code blocks in a document's lines.
+pub fn diagrams(lines: &[&str]) -> Vec<Diagram> {
+    let mut diagrams = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+    let mut current: Option<Diagram> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let was_in_block = tracker.in_code_block();
+        if tracker.process_line(line) {
+            if was_in_block {
+                diagrams.extend(current.take());
+            } else if let Some((_, Some(language))) =
+                ParsedDoc::parse_opening_fence(line.trim_start())
+                && language == MERMAID_LANGUAGE
+            {
+                current = Some(Diagram {
+                    first_line: idx + 2,
+                    source: String::new(),
+                });
+            }
+        } else if let Some(diagram) = &mut current {
+            diagram.source.push_str(line);
+            diagram.source.push('\n');
+        }
+    }
+    diagrams
+}
+
+/// Check a diagram's syntax with the built-in rules.
+pub fn check(source: &str) -> Vec<DiagramError> {
+    let mut errors = Vec::new();
+    let mut lines = source
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()));
+
+    // Frontmatter and `%%` comments or directives may come before the type
+    let mut in_frontmatter = false;
+    let header = lines.find(|(_, line)| {
+        if *line == "---" {
+            in_frontmatter = !in_frontmatter;
+            return false;
+        }
+        !in_frontmatter && !line.is_empty() && !line.starts_with("%%")
+    });
+    let Some((header_line, header)) = header else {
+        return vec![DiagramError {
+            line: 1,
+            message: "empty diagram".to_string(),
+        }];
+    };
+
+    let mut words = header.split_whitespace();
+    let kind = words.next().unwrap_or_default();
+    if !DIAGRAM_TYPES.contains(&kind) {
+        errors.push(DiagramError {
+            line: header_line,
+            message: format!("unknown diagram type '{}'", kind),
+        });
+        return errors;
+    }
+    let is_flowchart = matches!(kind, "graph" | "flowchart");
+    let block_keywords = match kind {
+        "graph" | "flowchart" => {
+            if let Some(direction) = words.next().filter(|dir| !DIRECTIONS.contains(dir)) {
+                errors.push(DiagramError {
+                    line: header_line,
+                    message: format!(
+                        "unknown direction '{}' (expected one of: {})",
+                        direction,
+                        DIRECTIONS.join(", ")
+                    ),
+                });
+            }
+            FLOWCHART_BLOCKS
+        }
+        "sequenceDiagram" => SEQUENCE_BLOCKS,
+        _ => &[],
+    };
+
+    let mut open_blocks: Vec<(usize, &str)> = Vec::new();
+    for (line_num, line) in lines {
+        if line.is_empty() || line.starts_with("%%") {
+            continue;
+        }
+        if let Some(message) = unbalanced(line, is_flowchart) {
+            errors.push(DiagramError {
+                line: line_num,
+                message,
+            });
+        }
+        if block_keywords.is_empty() {
+            continue;
+        }
+        let first = line.split_whitespace().next().unwrap_or_default();
+        if block_keywords.contains(&first) {
+            open_blocks.push((line_num, first));
+        } else if first == "end" && open_blocks.pop().is_none() {
+            errors.push(DiagramError {
+                line: line_num,
+                message: "'end' without an open block".to_string(),
+            });
+        }
+    }
+    for (line, keyword) in open_blocks {
+        errors.push(DiagramError {
+            line,
+            message: format!("'{}' is never closed with 'end'", keyword),
+        });
+    }
+    errors
+}
+
+/// Check a diagram with an external command that reads it on stdin.
+///
+/// Returns the command's first error line if it exits non-zero.
+pub fn check_with_command(source: &str, command: &str) -> std::io::Result<Option<DiagramError>> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits early closes stdin; its status tells us why
+        let _ = stdin.write_all(source.as_bytes());
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line_ref = Regex::new(r"(?i)\bline (\d+)").unwrap();
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("'{}' failed ({})", command, output.status));
+    let line = line_ref
+        .captures(&stderr)
+        .and_then(|caps| caps[1].parse().ok())
+        .unwrap_or(1);
+    Ok(Some(DiagramError { line, message }))
+}
+
+/// Describe the first quote a line leaves open, or with `brackets`, the
+/// first bracket it leaves open or closes without opening.
+///
+/// Brackets are only checked in flowcharts: other diagrams use them
+/// unpaired, such as ER cardinalities like `||--o{`.
+fn unbalanced(line: &str, brackets: bool) -> Option<String> {
+    let mut stack: Vec<char> = Vec::new();
+    let mut in_quote = false;
+    let mut prev = ' ';
+    for c in line.chars() {
+        let before = std::mem::replace(&mut prev, c);
+        if c == '"' {
+            in_quote = !in_quote;
+            continue;
+        }
+        if in_quote || !brackets {
+            continue;
+        }
+        match c {
+            '[' | '(' | '{' => stack.push(c),
+            // An asymmetric node, `A>label]`, opens with `>`
+            '>' if before.is_ascii_alphanumeric() => stack.push(c),
+            ']' | ')' | '}' => {
+                let matches = match stack.pop() {
+                    Some('[') | Some('>') => c == ']',
+                    Some('(') => c == ')',
+                    Some('{') => c == '}',
+                    _ => false,
+                };
+                if !matches {
+                    return Some(format!("unexpected '{}'", c));
+                }
+            }
+            _ => {}
+        }
+    }
+    if in_quote {
+        return Some("unclosed '\"'".to_string());
+    }
+    stack.last().map(|open| format!("unclosed '{}'", open))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_mermaid_blocks() {
+        let lines = [
+            "# Doc",
+            "```mermaid",
+            "graph TD",
+            "  A --> B",
+            "```",
+            "```bash",
+            "echo hi",
+            "```",
+        ];
+        assert_eq!(
+            diagrams(&lines),
+            [Diagram {
+                first_line: 3,
+                source: "graph TD\n  A --> B\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn accepts_valid_diagrams() {
+        assert_eq!(
+            check(
+                "---\ntitle: Flow\n---\n%% comment\nflowchart LR\n  A[Start] --> B{Ok?}\n  \
+                 subgraph inner\n    B -->|yes| C(\"Done (really)\")\n  end\n"
+            ),
+            []
+        );
+        assert_eq!(
+            check("sequenceDiagram\n  loop Every minute\n    A->>B: ping\n  end\n"),
+            []
+        );
+        assert_eq!(check("pie\n  \"Dogs\" : 386\n"), []);
+        assert_eq!(check("erDiagram\n  CUSTOMER ||--o{ ORDER : places\n"), []);
+        assert_eq!(check("graph LR\n  A>Flag] --> B\n"), []);
+    }
+
+    #[test]
+    fn reports_line_accurate_errors() {
+        let messages = |source: &str| -> Vec<(usize, String)> {
+            check(source)
+                .into_iter()
+                .map(|error| (error.line, error.message))
+                .collect()
+        };
+        assert_eq!(
+            messages("\ngrahp TD\n  A --> B\n"),
+            [(2, "unknown diagram type 'grahp'".to_string())]
+        );
+        assert_eq!(
+            messages("graph XY\n  A[Start --> B\n  B --> C)\n  subgraph one\n"),
+            [
+                (
+                    1,
+                    "unknown direction 'XY' (expected one of: TB, TD, BT, RL, LR)".to_string()
+                ),
+                (2, "unclosed '['".to_string()),
+                (3, "unexpected ')'".to_string()),
+                (4, "'subgraph' is never closed with 'end'".to_string()),
+            ]
+        );
+        assert_eq!(
+            messages("sequenceDiagram\n  A->>B: \"hi\n  end\n"),
+            [
+                (2, "unclosed '\"'".to_string()),
+                (3, "'end' without an open block".to_string()),
+            ]
+        );
+        assert_eq!(messages("%% only a comment\n")[0].1, "empty diagram");
+    }
+
+    #[test]
+    fn runs_an_external_checker() {
+        let error = check_with_command(
+            "graph TD\n",
+            "cat > /dev/null; echo 'Parse error on line 2:' >&2; exit 1",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            error,
+            DiagramError {
+                line: 2,
+                message: "Parse error on line 2:".to_string(),
+            }
+        );
+        assert_eq!(
+            check_with_command("graph TD\n", "cat > /dev/null").unwrap(),
+            None
+        );
+    }
+}
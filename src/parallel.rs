@@ -0,0 +1,48 @@
+//! Running per-document work on several threads.
+//!
+//! `pave check` and `pave lint` validate each document independently, so
+//! they spread documents over a thread pool. Results come back in the
+//! documents' order, so output doesn't depend on which thread finished
+//! first.
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+/// Run `f` on every file with up to `jobs` threads (default: one per CPU)
+/// and return the results in the order of `files`. Stops at the first
+/// error.
+pub fn map_files<T, F>(files: &[PathBuf], jobs: Option<usize>, f: F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&PathBuf) -> Result<T> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .context("Failed to start worker threads")?;
+    pool.install(|| files.par_iter().map(&f).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_order_of_the_files() {
+        let files: Vec<PathBuf> = (0..50)
+            .map(|i| PathBuf::from(format!("{}.md", i)))
+            .collect();
+        for jobs in [Some(1), Some(4), None] {
+            let names = map_files(&files, jobs, |file| Ok(file.display().to_string())).unwrap();
+            assert_eq!(names[0], "0.md");
+            assert_eq!(names[49], "49.md");
+        }
+
+        let err = map_files(&files, Some(4), |file| {
+            anyhow::ensure!(file != &PathBuf::from("7.md"), "bad doc");
+            Ok(())
+        });
+        assert!(err.is_err());
+    }
+}
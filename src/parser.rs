@@ -4,11 +4,20 @@
 //! about their sections, code blocks, and commands for validation purposes.
 
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use chrono::NaiveDate;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::compose::ComposeBootstrap;
+use crate::interface::InterfaceSource;
+use crate::services::Service;
 
 /// Pave-specific frontmatter configuration.
-#[derive(Debug, Clone, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct PaveFrontmatter {
     /// Code paths that this document covers.
     #[serde(default)]
@@ -16,6 +25,113 @@ pub struct PaveFrontmatter {
     /// Working directory for verification commands in this document.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Commands to run before the document's verification commands.
+    #[serde(default)]
+    pub setup: Vec<String>,
+    /// Commands to run after the document's verification commands.
+    #[serde(default)]
+    pub teardown: Vec<String>,
+    /// Lifecycle state: `draft`, `published` (or `active`), or `deprecated`.
+    /// Also accepted as `status`.
+    #[serde(default, alias = "status")]
+    pub state: Option<String>,
+    /// Document that replaces this one, required once it is deprecated.
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+    /// Person or team responsible for keeping the document current.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Free-form labels for grouping documents.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Date the document was last reviewed (YYYY-MM-DD).
+    #[serde(default)]
+    pub last_reviewed: Option<String>,
+    /// Days after `last_reviewed` that the document is due for review again.
+    /// Overrides `rules.review_interval_days`.
+    #[serde(default)]
+    pub review_interval_days: Option<i64>,
+    /// Verification bootstrap for the document (`pave.verify`).
+    #[serde(default)]
+    pub verify: Option<VerifyFrontmatter>,
+    /// Services to start around the document's verification commands
+    /// (`pave.services`).
+    #[serde(default)]
+    pub services: Vec<Service>,
+    /// Source the Interface section is checked against (`pave.interface`).
+    #[serde(default)]
+    pub interface: Option<InterfaceSource>,
+}
+
+/// Verification bootstrap from `pave.verify` in frontmatter.
+#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct VerifyFrontmatter {
+    /// Compose services to bring up around the document's commands.
+    #[serde(default)]
+    pub compose: Option<ComposeBootstrap>,
+}
+
+/// Lifecycle state of a document, from `pave.state` in frontmatter.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DocState {
+    /// Work in progress: rules only warn, and build and verify skip it.
+    Draft,
+    /// Fully enforced (default).
+    #[default]
+    Published,
+    /// Replaced by another document, named in `pave.superseded_by`.
+    Deprecated,
+}
+
+impl DocState {
+    /// All states, in lifecycle order.
+    pub const ALL: [DocState; 3] = [DocState::Draft, DocState::Published, DocState::Deprecated];
+
+    /// Parse a state name (case-insensitive). `active` means published.
+    pub fn parse(value: &str) -> Option<Self> {
+        if value.trim().eq_ignore_ascii_case("active") {
+            return Some(DocState::Published);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|state| state.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+
+    /// The state's name as written in frontmatter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocState::Draft => "draft",
+            DocState::Published => "published",
+            DocState::Deprecated => "deprecated",
+        }
+    }
+
+    /// Whether a document may move from this state to `next`.
+    ///
+    /// Documents only move forward through draft, published, and deprecated.
+    pub fn can_transition_to(&self, next: DocState) -> bool {
+        *self <= next
+    }
+}
+
+impl std::fmt::Display for DocState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// YAML frontmatter wrapper.
@@ -50,6 +166,8 @@ pub enum ExpectMatchStrategy {
     Regex,
     /// Match if output exactly equals expected (trimmed).
     Exact,
+    /// Match if output does not contain the expected string.
+    NotContains,
 }
 
 /// Expected output specification for a code block.
@@ -61,6 +179,265 @@ pub struct ExpectedOutput {
     pub strategy: ExpectMatchStrategy,
 }
 
+/// A prompt line in a block whose inline output is written between commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptCommand {
+    /// The command, without its `$ ` or `> ` prompt.
+    pub command: String,
+    /// Output written after this prompt and before the next one, if any.
+    pub expected_output: Option<ExpectedOutput>,
+}
+
+/// When a code block in a Verification section runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockPhase {
+    /// A verification command (default).
+    #[default]
+    Verify,
+    /// Runs before the verification commands (`<!-- pave:setup -->`).
+    Setup,
+    /// Runs after the verification commands (`<!-- pave:teardown -->`).
+    Teardown,
+}
+
+/// Annotations recognized in `<!-- pave:NAME -->` comments, with their usage,
+/// derived from [`Annotation::examples`].
+pub static ANNOTATIONS: LazyLock<Vec<(&'static str, String)>> = LazyLock::new(|| {
+    Annotation::examples()
+        .iter()
+        .map(|annotation| (annotation.name(), annotation.usage_text()))
+        .collect()
+});
+
+/// A `<!-- pave:NAME argument -->` comment on a line of its own.
+///
+/// Block annotations apply to the next code block in the section; spaces
+/// inside the comment are optional, so `<!--pave:run-->` works too.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation {
+    /// `pave:run`: the block runs even if its language isn't a shell.
+    Run,
+    /// `pave:compile`: the Examples block is compiled during verification.
+    Compile,
+    /// `pave:expect[:strategy]`: the block is the previous command's
+    /// expected output.
+    Expect(ExpectMatchStrategy),
+    /// `pave:working_dir DIR`: the directory the block runs in.
+    WorkingDir(String),
+    /// `pave:env NAME=value`: an environment variable for the block.
+    Env(String, String),
+    /// `pave:env-from NAME`: a secret passed through to the block.
+    EnvFrom(String),
+    /// `pave:shell NAME`: the shell that runs the block.
+    Shell(String),
+    /// `pave:container IMAGE`: the image the block runs in.
+    Container(String),
+    /// `pave:host NAME`: the `[verify.hosts]` entry the block runs on.
+    Host(String),
+    /// `pave:database NAME`: the `[verify.databases]` entry a `sql` block
+    /// queries.
+    Database(String),
+    /// `pave:exit-code N`: the exit code the block should return.
+    ExitCode(i32),
+    /// `pave:timeout SECS`: how long the block may run.
+    Timeout(u32),
+    /// `pave:retries N`: how many more times a failing block is run.
+    Retries(u32),
+    /// `pave:setup` or `pave:teardown`: when the block runs. Never holds
+    /// [`BlockPhase::Verify`].
+    Phase(BlockPhase),
+    /// `pave:include PATH`: a file spliced into the document.
+    Include(String),
+    /// `pave:section key=value ...`: metadata for the enclosing section.
+    /// Values with spaces are written in double quotes.
+    Section(BTreeMap<String, String>),
+}
+
+/// Why a `pave:` comment isn't a valid annotation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationError {
+    /// The comment isn't closed with `-->` on the same line.
+    Unclosed,
+    /// No annotation has this name.
+    Unknown(String),
+    /// The annotation's argument is missing or invalid.
+    Malformed(String),
+}
+
+impl Annotation {
+    /// Parse an annotation comment on a line of its own.
+    ///
+    /// Returns `None` if the line isn't a `<!-- pave:... -->` comment.
+    pub fn parse(line: &str) -> Option<Result<Self, AnnotationError>> {
+        let inner = line
+            .trim()
+            .strip_prefix("<!--")?
+            .trim_start()
+            .strip_prefix("pave:")?;
+        let Some(inner) = inner.trim_end().strip_suffix("-->") else {
+            return Some(Err(AnnotationError::Unclosed));
+        };
+        let inner = inner.trim();
+        let (name, arg) = inner
+            .split_once(char::is_whitespace)
+            .map_or((inner, ""), |(name, arg)| (name, arg.trim()));
+        Some(Self::from_parts(name, arg))
+    }
+
+    /// One of each annotation, with the argument its usage shows. A new
+    /// annotation needs a variant, an entry here, and the cases the compiler
+    /// asks for.
+    fn examples() -> Vec<Self> {
+        vec![
+            Self::Run,
+            Self::Compile,
+            Self::Expect(ExpectMatchStrategy::Contains),
+            Self::Expect(ExpectMatchStrategy::Regex),
+            Self::Expect(ExpectMatchStrategy::Exact),
+            Self::Expect(ExpectMatchStrategy::NotContains),
+            Self::WorkingDir("path/to/dir".to_string()),
+            Self::Env("NAME".to_string(), "value".to_string()),
+            Self::EnvFrom("NAME".to_string()),
+            Self::Shell("pwsh".to_string()),
+            Self::Container("image".to_string()),
+            Self::Host("name".to_string()),
+            Self::Database("name".to_string()),
+            Self::ExitCode(1),
+            Self::Timeout(120),
+            Self::Retries(2),
+            Self::Phase(BlockPhase::Setup),
+            Self::Phase(BlockPhase::Teardown),
+            Self::Include("path/to/file.md".to_string()),
+            Self::Section(BTreeMap::from([
+                ("owner".to_string(), "infra".to_string()),
+                ("priority".to_string(), "high".to_string()),
+            ])),
+        ]
+    }
+
+    /// The name written after `pave:`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Compile => "compile",
+            Self::Expect(ExpectMatchStrategy::Contains) => "expect",
+            Self::Expect(ExpectMatchStrategy::Regex) => "expect:regex",
+            Self::Expect(ExpectMatchStrategy::Exact) => "expect:exact",
+            Self::Expect(ExpectMatchStrategy::NotContains) => "expect:not",
+            Self::WorkingDir(_) => "working_dir",
+            Self::Env(..) => "env",
+            Self::EnvFrom(_) => "env-from",
+            Self::Shell(_) => "shell",
+            Self::Container(_) => "container",
+            Self::Host(_) => "host",
+            Self::Database(_) => "database",
+            Self::ExitCode(_) => "exit-code",
+            Self::Timeout(_) => "timeout",
+            Self::Retries(_) => "retries",
+            Self::Phase(BlockPhase::Teardown) => "teardown",
+            Self::Phase(_) => "setup",
+            Self::Include(_) => "include",
+            Self::Section(_) => "section",
+        }
+    }
+
+    /// The annotation as written in a document, e.g.
+    /// `<!-- pave:env NAME=value -->`.
+    pub fn usage_text(&self) -> String {
+        let arg = match self {
+            Self::Run | Self::Compile | Self::Expect(_) | Self::Phase(_) => String::new(),
+            Self::WorkingDir(arg)
+            | Self::EnvFrom(arg)
+            | Self::Shell(arg)
+            | Self::Container(arg)
+            | Self::Host(arg)
+            | Self::Database(arg)
+            | Self::Include(arg) => arg.clone(),
+            Self::Env(key, value) => format!("{}={}", key, value),
+            Self::ExitCode(code) => code.to_string(),
+            Self::Timeout(secs) => secs.to_string(),
+            Self::Retries(retries) => retries.to_string(),
+            Self::Section(pairs) => pairs
+                .iter()
+                .map(|(key, value)| {
+                    if value.contains(char::is_whitespace) {
+                        format!("{}=\"{}\"", key, value)
+                    } else {
+                        format!("{}={}", key, value)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        if arg.is_empty() {
+            format!("<!-- pave:{} -->", self.name())
+        } else {
+            format!("<!-- pave:{} {} -->", self.name(), arg)
+        }
+    }
+
+    /// Build the annotation called `name` from its argument.
+    fn from_parts(name: &str, arg: &str) -> Result<Self, AnnotationError> {
+        let malformed = || AnnotationError::Malformed(name.to_string());
+        let required = || {
+            (!arg.is_empty())
+                .then(|| arg.to_string())
+                .ok_or_else(malformed)
+        };
+        // `expect:contains` spells out the default strategy
+        let canonical = if name == "expect:contains" {
+            "expect"
+        } else {
+            name
+        };
+        let Some(example) = Self::examples()
+            .into_iter()
+            .find(|example| example.name() == canonical)
+        else {
+            return Err(AnnotationError::Unknown(name.to_string()));
+        };
+        let annotation = match example {
+            Self::Run | Self::Compile | Self::Expect(_) | Self::Phase(_) => example,
+            Self::WorkingDir(_) => Self::WorkingDir(required()?),
+            Self::Shell(_) => Self::Shell(required()?),
+            Self::Container(_) => Self::Container(required()?),
+            Self::Host(_) => Self::Host(required()?),
+            Self::Database(_) => Self::Database(required()?),
+            Self::Include(_) => Self::Include(required()?),
+            Self::Env(..) => {
+                let (key, value) = arg
+                    .split_once('=')
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .ok_or_else(malformed)?;
+                Self::Env(key.trim().to_string(), value.trim().to_string())
+            }
+            Self::EnvFrom(_) => {
+                let secret = required()?;
+                if secret.contains(char::is_whitespace) {
+                    return Err(malformed());
+                }
+                Self::EnvFrom(secret)
+            }
+            Self::ExitCode(_) => Self::ExitCode(arg.parse().map_err(|_| malformed())?),
+            Self::Timeout(_) => match arg.parse() {
+                Ok(secs) if secs > 0 => Self::Timeout(secs),
+                _ => return Err(malformed()),
+            },
+            Self::Retries(_) => Self::Retries(arg.parse().map_err(|_| malformed())?),
+            Self::Section(_) => Self::Section(parse_pairs(arg).ok_or_else(malformed)?),
+        };
+        Ok(annotation)
+    }
+
+    /// Usage of the annotation named `name`, as listed in [`ANNOTATIONS`].
+    pub fn usage(name: &str) -> Option<&'static str> {
+        ANNOTATIONS
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, usage)| usage.as_str())
+    }
+}
+
 /// A fenced code block extracted from a section.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CodeBlock {
@@ -74,10 +451,42 @@ pub struct CodeBlock {
     pub is_executable: bool,
     /// Expected output for this code block, if specified.
     pub expected_output: Option<ExpectedOutput>,
+    /// Each prompt with its own output, when output is written between
+    /// prompts. Empty otherwise; the block then runs as one command.
+    pub prompts: Vec<PromptCommand>,
     /// Working directory override for this code block.
     pub working_dir: Option<String>,
     /// Environment variables to set for this code block.
     pub env_vars: Vec<(String, String)>,
+    /// Secrets to pass through from the environment or `.pave.env`
+    /// (from `pave:env-from` markers), by name.
+    pub secrets: Vec<String>,
+    /// Shell override for this code block (from a `pave:shell` marker).
+    pub shell: Option<String>,
+    /// Container image to run this code block in (from a `pave:container`
+    /// marker).
+    pub container: Option<String>,
+    /// Remote host to run this code block on (from a `pave:host` marker),
+    /// named in `[verify.hosts]`.
+    pub host: Option<String>,
+    /// Database from `[verify.databases]` to run this `sql` block against
+    /// (from a `pave:database` marker).
+    pub database: Option<String>,
+    /// Expected exit code for this code block (from a `pave:exit-code` marker).
+    pub exit_code: Option<i32>,
+    /// Timeout in seconds for this code block (from a `pave:timeout`
+    /// marker), overriding the run's default.
+    pub timeout_secs: Option<u32>,
+    /// How many more times to run this code block if it fails (from a
+    /// `pave:retries` marker).
+    pub retries: u32,
+    /// Whether this block is an example to compile during verification
+    /// (from a `pave:compile` marker).
+    pub compile: bool,
+    /// Whether this block is a setup, teardown, or verification command.
+    pub phase: BlockPhase,
+    /// Annotations written before the block, in order.
+    pub annotations: Vec<Annotation>,
 }
 
 /// A section of a PAVED document (H2 heading and its content).
@@ -95,6 +504,69 @@ pub struct Section {
     pub has_commands: bool,
     /// Extracted code blocks from this section.
     pub code_blocks: Vec<CodeBlock>,
+    /// Every valid annotation in the section, with its line (1-indexed).
+    pub annotations: Vec<(usize, Annotation)>,
+    /// Metadata from `pave:section` annotations in the section. A key set
+    /// more than once keeps its last value.
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// A heading found while parsing.
+#[derive(Debug)]
+struct Heading {
+    level: HeadingLevel,
+    /// Heading text as written, without `#` markers or a setext underline.
+    text: String,
+    /// First and last lines of the heading (0-indexed).
+    start: usize,
+    end: usize,
+}
+
+/// Annotations written before a code block, waiting for the block they
+/// apply to.
+#[derive(Debug, Default)]
+struct PendingMarkers {
+    annotations: Vec<Annotation>,
+    run: bool,
+    compile: bool,
+    expect: Option<ExpectMatchStrategy>,
+    working_dir: Option<String>,
+    env_vars: Vec<(String, String)>,
+    secrets: Vec<String>,
+    shell: Option<String>,
+    container: Option<String>,
+    host: Option<String>,
+    database: Option<String>,
+    exit_code: Option<i32>,
+    timeout_secs: Option<u32>,
+    retries: u32,
+    phase: Option<BlockPhase>,
+}
+
+impl PendingMarkers {
+    /// Record an annotation for the next code block.
+    fn apply(&mut self, annotation: Annotation) {
+        match &annotation {
+            Annotation::Run => self.run = true,
+            Annotation::Compile => self.compile = true,
+            Annotation::Expect(strategy) => self.expect = Some(strategy.clone()),
+            Annotation::WorkingDir(dir) => self.working_dir = Some(dir.clone()),
+            Annotation::Env(key, value) => self.env_vars.push((key.clone(), value.clone())),
+            Annotation::EnvFrom(name) => self.secrets.push(name.clone()),
+            Annotation::Shell(shell) => self.shell = Some(shell.clone()),
+            Annotation::Container(image) => self.container = Some(image.clone()),
+            Annotation::Host(host) => self.host = Some(host.clone()),
+            Annotation::Database(database) => self.database = Some(database.clone()),
+            Annotation::ExitCode(code) => self.exit_code = Some(*code),
+            Annotation::Timeout(secs) => self.timeout_secs = Some(*secs),
+            Annotation::Retries(retries) => self.retries = *retries,
+            Annotation::Phase(phase) => self.phase = Some(*phase),
+            // Includes are spliced in before parsing, and section metadata
+            // belongs to the section, so neither applies to a block
+            Annotation::Include(_) | Annotation::Section(_) => return,
+        }
+        self.annotations.push(annotation);
+    }
 }
 
 impl Section {
@@ -124,8 +596,9 @@ impl ParsedDoc {
         let line_count = lines.len();
 
         let frontmatter = Self::extract_frontmatter(content);
-        let title = Self::extract_title(&lines);
-        let sections = Self::extract_sections(&lines);
+        let headings = Self::extract_headings(content);
+        let title = Self::extract_title(&headings);
+        let sections = Self::extract_sections(&lines, &headings);
 
         Ok(ParsedDoc {
             path,
@@ -143,6 +616,34 @@ impl ParsedDoc {
             .any(|s| s.name.eq_ignore_ascii_case(name))
     }
 
+    /// The document's lifecycle state.
+    ///
+    /// Documents without a `pave.state`, or with an unknown one, are published.
+    pub fn state(&self) -> DocState {
+        self.frontmatter
+            .as_ref()
+            .and_then(|fm| fm.state.as_deref())
+            .and_then(DocState::parse)
+            .unwrap_or_default()
+    }
+
+    /// Date the document is next due for review, if it records when it was
+    /// last reviewed.
+    ///
+    /// `pave.review_interval_days` wins over `default_interval_days`. Without
+    /// either, or with an invalid date, there is no due date.
+    pub fn review_due(&self, default_interval_days: Option<u32>) -> Option<NaiveDate> {
+        let frontmatter = self.frontmatter.as_ref()?;
+        let reviewed =
+            NaiveDate::parse_from_str(frontmatter.last_reviewed.as_deref()?.trim(), "%Y-%m-%d")
+                .ok()?;
+        let interval = frontmatter
+            .review_interval_days
+            .or(default_interval_days.map(i64::from))
+            .filter(|days| *days > 0)?;
+        reviewed.checked_add_signed(chrono::Duration::days(interval))
+    }
+
     /// Get a section by name (case-insensitive).
     pub fn get_section(&self, name: &str) -> Option<&Section> {
         self.sections
@@ -151,82 +652,81 @@ impl ParsedDoc {
     }
 
     /// Extract the H1 title from the document.
-    fn extract_title(lines: &[&str]) -> Option<String> {
-        for line in lines {
-            let trimmed = line.trim();
-            if let Some(title) = trimmed.strip_prefix("# ") {
-                // Ensure it's not an H2 (## ) heading
-                if !title.starts_with("# ") {
-                    return Some(title.trim().to_string());
-                }
-            }
-        }
-        None
+    fn extract_title(headings: &[Heading]) -> Option<String> {
+        headings
+            .iter()
+            .find(|heading| heading.level == HeadingLevel::H1)
+            .map(|heading| heading.text.clone())
+    }
+
+    /// Find the document's headings with CommonMark rules, so `#` lines in
+    /// code blocks or HTML blocks don't count and setext headings do.
+    fn extract_headings(content: &str) -> Vec<Heading> {
+        let line_starts = line_starts(content);
+        let options = Options::ENABLE_TABLES | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+        Parser::new_ext(content, options)
+            .into_offset_iter()
+            .filter_map(|(event, range)| match event {
+                Event::Start(Tag::Heading { level, .. }) => Some(Heading {
+                    level,
+                    text: heading_text(&content[range.clone()]),
+                    start: line_of(&line_starts, range.start),
+                    end: line_of(&line_starts, range.end.saturating_sub(1).max(range.start)),
+                }),
+                _ => None,
+            })
+            .collect()
     }
 
     /// Extract all H2 sections from the document.
-    fn extract_sections(lines: &[&str]) -> Vec<Section> {
-        let mut sections = Vec::new();
-        let mut section_starts: Vec<(usize, String)> = Vec::new();
-        let mut in_code_block = false;
-
-        // Find all H2 headings and their positions, skipping content inside code blocks
-        for (idx, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            // Track code block state
-            if trimmed.starts_with("```") {
-                in_code_block = !in_code_block;
-                continue;
-            }
-
-            // Skip headings inside code blocks
-            if in_code_block {
-                continue;
-            }
-
-            if let Some(heading) = trimmed.strip_prefix("## ") {
-                // Ensure it's not an H3 (### ) heading
-                if !heading.starts_with("# ") {
-                    let name = heading.trim().to_string();
-                    section_starts.push((idx, name));
-                }
-            }
-        }
+    fn extract_sections(lines: &[&str], headings: &[Heading]) -> Vec<Section> {
+        let h2s: Vec<&Heading> = headings
+            .iter()
+            .filter(|heading| heading.level == HeadingLevel::H2)
+            .collect();
 
-        // Extract content for each section
-        for (i, (start_idx, name)) in section_starts.iter().enumerate() {
-            let end_idx = if i + 1 < section_starts.len() {
-                section_starts[i + 1].0
-            } else {
-                lines.len()
-            };
+        let mut sections = Vec::new();
+        for (i, heading) in h2s.iter().enumerate() {
+            let end_idx = h2s.get(i + 1).map_or(lines.len(), |next| next.start);
 
-            // Content starts after the heading line
-            let content_lines = &lines[start_idx + 1..end_idx];
+            // Content starts after the heading, including a setext underline
+            let content_start = (heading.end + 1).min(end_idx);
+            let content_lines = &lines[content_start..end_idx];
             let content = content_lines.join("\n");
 
-            let has_code_blocks = Self::detect_code_blocks(content_lines);
+            let has_code_blocks = Self::detect_code_blocks(&content);
             let has_commands = Self::detect_commands(content_lines);
-            // Base line for content is start_idx + 2 (1-indexed: line after heading)
-            let code_blocks = Self::extract_code_blocks(content_lines, start_idx + 2);
+            // Base line for content is content_start + 1 (1-indexed)
+            let (code_blocks, annotations) =
+                Self::extract_code_blocks(content_lines, content_start + 1);
+            let metadata = annotations
+                .iter()
+                .filter_map(|(_, annotation)| match annotation {
+                    Annotation::Section(pairs) => Some(pairs.clone()),
+                    _ => None,
+                })
+                .flatten()
+                .collect();
 
             sections.push(Section {
-                name: name.clone(),
-                start_line: start_idx + 1, // Convert to 1-indexed
+                name: heading.text.clone(),
+                start_line: heading.start + 1, // Convert to 1-indexed
                 content,
                 has_code_blocks,
                 has_commands,
                 code_blocks,
+                annotations,
+                metadata,
             });
         }
 
         sections
     }
 
-    /// Detect if content contains code blocks (triple backticks).
-    fn detect_code_blocks(lines: &[&str]) -> bool {
-        lines.iter().any(|line| line.trim().starts_with("```"))
+    /// Detect if content contains code blocks, fenced or indented.
+    fn detect_code_blocks(content: &str) -> bool {
+        Parser::new_ext(content, Options::ENABLE_TABLES)
+            .any(|event| matches!(event, Event::Start(Tag::CodeBlock(_))))
     }
 
     /// Detect if content contains executable commands.
@@ -252,125 +752,131 @@ impl ParsedDoc {
 
     /// Extract code blocks from section content.
     ///
-    /// Parses fenced code blocks (``` markers) and extracts:
-    /// - Language tag (if present after opening ```)
+    /// Parses fenced and indented code blocks with CommonMark rules and extracts:
+    /// - Language tag (if present after the opening fence)
     /// - Content between the fences
     /// - Line number of the opening fence
     /// - Whether the block is executable (shell language, prompts, or pave:run marker)
     /// - Expected output (inline or from explicit blocks)
     ///
+    /// Annotations are read from HTML comments outside code blocks, and apply
+    /// to the next code block. A block left open runs to the end of the section.
+    ///
     /// The `base_line` parameter is the 1-indexed line number of the first line in `lines`.
-    fn extract_code_blocks(lines: &[&str], base_line: usize) -> Vec<CodeBlock> {
+    /// Returns the blocks and every annotation with its line.
+    fn extract_code_blocks(
+        lines: &[&str],
+        base_line: usize,
+    ) -> (Vec<CodeBlock>, Vec<(usize, Annotation)>) {
+        let text = lines.join("\n");
+        let line_starts = line_starts(&text);
         let mut code_blocks: Vec<CodeBlock> = Vec::new();
-        let mut in_code_block = false;
-        let mut current_block_start: usize = 0;
-        let mut current_language: Option<String> = None;
-        let mut current_content: Vec<&str> = Vec::new();
-        let mut opening_fence_len: usize = 0;
-        let mut has_run_marker = false;
-        let mut pending_expect_marker: Option<ExpectMatchStrategy> = None;
-        let mut pending_working_dir: Option<String> = None;
-        let mut pending_env_vars: Vec<(String, String)> = Vec::new();
-
-        for (idx, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            if !in_code_block {
-                // Check for pave:run marker before the code block
-                if Self::has_pave_run_marker(trimmed) {
-                    has_run_marker = true;
-                }
-                // Check for pave:expect marker before a code block
-                else if let Some(strategy) = Self::parse_expect_marker(trimmed) {
-                    pending_expect_marker = Some(strategy);
-                }
-                // Check for pave:working_dir marker
-                else if let Some(dir) = Self::parse_working_dir_marker(trimmed) {
-                    pending_working_dir = Some(dir);
-                }
-                // Check for pave:env marker
-                else if let Some(env_var) = Self::parse_env_marker(trimmed) {
-                    pending_env_vars.push(env_var);
+        let mut annotations = Vec::new();
+        let mut pending = PendingMarkers::default();
+        // Language, start line, content, and whether the block is indented,
+        // for the block being read
+        let mut current: Option<(Option<String>, usize, String, bool)> = None;
+
+        for (event, range) in Parser::new_ext(&text, Options::ENABLE_TABLES).into_offset_iter() {
+            match event {
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let (language, indented) = match kind {
+                        CodeBlockKind::Fenced(info) => {
+                            (info.split_whitespace().next().map(str::to_string), false)
+                        }
+                        CodeBlockKind::Indented => (None, true),
+                    };
+                    let start_line = base_line + line_of(&line_starts, range.start);
+                    current = Some((language, start_line, String::new(), indented));
                 }
-                // Check for opening fence (at least 3 backticks)
-                else if let Some(fence_content) = Self::parse_opening_fence(trimmed) {
-                    in_code_block = true;
-                    opening_fence_len = fence_content.0;
-                    current_block_start = base_line + idx;
-                    current_language = fence_content.1;
-                    current_content.clear();
+                Event::Text(text) => {
+                    if let Some((_, _, content, _)) = &mut current {
+                        content.push_str(&text);
+                    }
                 }
-            } else {
-                // Check for closing fence (at least as many backticks as opening, nothing after)
-                if Self::is_closing_fence(trimmed, opening_fence_len) {
-                    let content = current_content.join("\n");
+                Event::End(TagEnd::CodeBlock) => {
+                    let Some((language, start_line, content, indented)) = current.take() else {
+                        continue;
+                    };
+                    let content = content.strip_suffix('\n').unwrap_or(&content);
+                    let markers = std::mem::take(&mut pending);
 
                     // If there's a pending expect marker, this block is expected output
-                    if let Some(strategy) = pending_expect_marker.take() {
-                        // Attach expected output to the last executable block
+                    if let Some(strategy) = markers.expect {
+                        let expected = ExpectedOutput {
+                            content: content.to_string(),
+                            strategy,
+                        };
+                        // Attach expected output to the last executable block,
+                        // or to its last prompt when it runs per prompt
                         if let Some(last_block) = code_blocks.last_mut()
                             && last_block.is_executable
-                            && last_block.expected_output.is_none()
                         {
-                            last_block.expected_output = Some(ExpectedOutput {
-                                content: content.clone(),
-                                strategy,
-                            });
+                            if let Some(prompt) = last_block.prompts.last_mut() {
+                                prompt.expected_output = Some(expected);
+                            } else if last_block.expected_output.is_none() {
+                                last_block.expected_output = Some(expected);
+                            }
                         }
-                        // This block is not added as a code block itself
-                        // Also clear working_dir/env since they were for an expect block
-                        pending_working_dir = None;
-                        pending_env_vars.clear();
+                        // This block is not added as a code block itself, and
+                        // the other markers were for it too
+                        continue;
+                    }
+
+                    // Setup and teardown blocks always run. Indented blocks
+                    // are prose examples and only run when marked.
+                    let has_run_marker = markers.run || markers.phase.is_some();
+                    let is_executable = if indented {
+                        has_run_marker
                     } else {
-                        let is_executable =
-                            Self::is_block_executable(&current_language, &content, has_run_marker);
-
-                        // Extract inline expected output from shell-style blocks
-                        let (command_content, inline_output) =
-                            Self::extract_inline_expected_output(&content);
-
-                        code_blocks.push(CodeBlock {
-                            language: current_language.take(),
-                            content: command_content,
-                            start_line: current_block_start,
-                            is_executable,
-                            expected_output: inline_output,
-                            working_dir: pending_working_dir.take(),
-                            env_vars: std::mem::take(&mut pending_env_vars),
-                        });
+                        Self::is_block_executable(&language, content, has_run_marker)
+                    };
+
+                    // Extract inline expected output from shell-style blocks
+                    let (command_content, inline_output, prompts) =
+                        Self::extract_inline_expected_output(content);
+
+                    code_blocks.push(CodeBlock {
+                        language,
+                        content: command_content,
+                        start_line,
+                        is_executable,
+                        expected_output: inline_output,
+                        prompts,
+                        working_dir: markers.working_dir,
+                        env_vars: markers.env_vars,
+                        secrets: markers.secrets,
+                        shell: markers.shell,
+                        container: markers.container,
+                        host: markers.host,
+                        database: markers.database,
+                        exit_code: markers.exit_code,
+                        timeout_secs: markers.timeout_secs,
+                        retries: markers.retries,
+                        compile: markers.compile,
+                        phase: markers.phase.unwrap_or_default(),
+                        annotations: markers.annotations,
+                    });
+                }
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    let first_line = base_line + line_of(&line_starts, range.start);
+                    for (offset, line) in html.lines().enumerate() {
+                        if let Some(Ok(annotation)) = Annotation::parse(line) {
+                            annotations.push((first_line + offset, annotation.clone()));
+                            pending.apply(annotation);
+                        }
                     }
-                    in_code_block = false;
-                    current_content.clear();
-                    has_run_marker = false;
-                } else {
-                    current_content.push(line);
                 }
+                _ => {}
             }
         }
 
-        // Handle unclosed code block at end of section (treat as if closed)
-        if in_code_block && !current_content.is_empty() {
-            let content = current_content.join("\n");
-            let is_executable =
-                Self::is_block_executable(&current_language, &content, has_run_marker);
-            let (command_content, inline_output) = Self::extract_inline_expected_output(&content);
-            code_blocks.push(CodeBlock {
-                language: current_language,
-                content: command_content,
-                start_line: current_block_start,
-                is_executable,
-                expected_output: inline_output,
-                working_dir: pending_working_dir,
-                env_vars: pending_env_vars,
-            });
-        }
-
-        code_blocks
+        (code_blocks, annotations)
     }
 
     /// Parse an opening fence line, returning (fence_length, optional_language).
     /// Returns None if not an opening fence.
-    fn parse_opening_fence(trimmed: &str) -> Option<(usize, Option<String>)> {
+    pub(crate) fn parse_opening_fence(trimmed: &str) -> Option<(usize, Option<String>)> {
         if !trimmed.starts_with("```") {
             return None;
         }
@@ -391,7 +897,7 @@ impl ParsedDoc {
     }
 
     /// Check if a line is a closing fence (at least `min_len` backticks, nothing else).
-    fn is_closing_fence(trimmed: &str, min_len: usize) -> bool {
+    pub(crate) fn is_closing_fence(trimmed: &str, min_len: usize) -> bool {
         if !trimmed.starts_with("```") {
             return false;
         }
@@ -403,8 +909,9 @@ impl ParsedDoc {
 
     /// Determine if a code block is executable based on language, content, and markers.
     ///
-    /// A code block is considered executable if:
-    /// 1. Language tag is a shell language: `bash`, `sh`, `shell`, `zsh`
+    /// A fenced code block is considered executable if:
+    /// 1. Language tag is a shell language: `bash`, `sh`, `shell`, `zsh`,
+    ///    `powershell`, `pwsh`, `ps1`, `bat`, `batch`, `cmd`
     /// 2. Content contains lines starting with `$ ` or `> ` (shell prompts)
     /// 3. The block is preceded by a `<!-- pave:run -->` HTML comment marker
     fn is_block_executable(language: &Option<String>, content: &str, has_run_marker: bool) -> bool {
@@ -416,7 +923,20 @@ impl ParsedDoc {
         // Check shell language tags
         if let Some(lang) = language {
             let lang_lower = lang.to_lowercase();
-            if matches!(lang_lower.as_str(), "bash" | "sh" | "shell" | "zsh") {
+            if matches!(
+                lang_lower.as_str(),
+                "bash"
+                    | "sh"
+                    | "shell"
+                    | "zsh"
+                    | "powershell"
+                    | "pwsh"
+                    | "ps1"
+                    | "bat"
+                    | "batch"
+                    | "cmd"
+                    | "http"
+            ) {
                 return true;
             }
         }
@@ -428,109 +948,6 @@ impl ParsedDoc {
         })
     }
 
-    /// Check if a line contains the pave:run marker.
-    fn has_pave_run_marker(line: &str) -> bool {
-        let trimmed = line.trim();
-        trimmed.contains("<!-- pave:run -->") || trimmed.contains("<!--pave:run-->")
-    }
-
-    /// Parse a pave:expect marker and return the matching strategy.
-    ///
-    /// Supports:
-    /// - `<!-- pave:expect -->` or `<!-- pave:expect:contains -->` - contains matching (default)
-    /// - `<!-- pave:expect:regex -->` - regex matching
-    /// - `<!-- pave:expect:exact -->` - exact matching
-    fn parse_expect_marker(line: &str) -> Option<ExpectMatchStrategy> {
-        let trimmed = line.trim();
-
-        // Check for markers with and without spaces
-        let patterns = [
-            (
-                "<!-- pave:expect:contains -->",
-                ExpectMatchStrategy::Contains,
-            ),
-            (
-                "<!--pave:expect:contains-->",
-                ExpectMatchStrategy::Contains,
-            ),
-            ("<!-- pave:expect:regex -->", ExpectMatchStrategy::Regex),
-            ("<!--pave:expect:regex-->", ExpectMatchStrategy::Regex),
-            ("<!-- pave:expect:exact -->", ExpectMatchStrategy::Exact),
-            ("<!--pave:expect:exact-->", ExpectMatchStrategy::Exact),
-            ("<!-- pave:expect -->", ExpectMatchStrategy::Contains),
-            ("<!--pave:expect-->", ExpectMatchStrategy::Contains),
-        ];
-
-        for (pattern, strategy) in patterns {
-            if trimmed.contains(pattern) {
-                return Some(strategy);
-            }
-        }
-
-        None
-    }
-
-    /// Parse a pave:working_dir marker and return the directory path.
-    ///
-    /// Supports:
-    /// - `<!-- pave:working_dir path/to/dir -->`
-    /// - `<!--pave:working_dir path/to/dir-->`
-    fn parse_working_dir_marker(line: &str) -> Option<String> {
-        let trimmed = line.trim();
-
-        // Try with spaces first
-        if let Some(rest) = trimmed.strip_prefix("<!-- pave:working_dir ")
-            && let Some(dir) = rest.strip_suffix(" -->")
-        {
-            let dir = dir.trim();
-            if !dir.is_empty() {
-                return Some(dir.to_string());
-            }
-        }
-
-        // Try without spaces
-        if let Some(rest) = trimmed.strip_prefix("<!--pave:working_dir ")
-            && let Some(dir) = rest.strip_suffix("-->")
-        {
-            let dir = dir.trim();
-            if !dir.is_empty() {
-                return Some(dir.to_string());
-            }
-        }
-
-        None
-    }
-
-    /// Parse a pave:env marker and return the environment variable (key, value).
-    ///
-    /// Supports:
-    /// - `<!-- pave:env KEY=VALUE -->`
-    /// - `<!--pave:env KEY=VALUE-->`
-    fn parse_env_marker(line: &str) -> Option<(String, String)> {
-        let trimmed = line.trim();
-
-        let env_str = if let Some(rest) = trimmed.strip_prefix("<!-- pave:env ") {
-            rest.strip_suffix(" -->")
-        } else if let Some(rest) = trimmed.strip_prefix("<!--pave:env ") {
-            rest.strip_suffix("-->")
-        } else {
-            None
-        };
-
-        if let Some(env_str) = env_str {
-            let env_str = env_str.trim();
-            if let Some(eq_pos) = env_str.find('=') {
-                let key = env_str[..eq_pos].trim().to_string();
-                let value = env_str[eq_pos + 1..].trim().to_string();
-                if !key.is_empty() {
-                    return Some((key, value));
-                }
-            }
-        }
-
-        None
-    }
-
     /// Extract inline expected output from a code block with shell prompts.
     ///
     /// In a code block like:
@@ -545,8 +962,14 @@ impl ParsedDoc {
     /// This only applies to blocks that contain shell prompt lines (`$ ` or `> `).
     /// Other blocks are returned unchanged.
     ///
-    /// Returns (command_content, optional_expected_output).
-    fn extract_inline_expected_output(content: &str) -> (String, Option<ExpectedOutput>) {
+    /// When output is written between prompts, each prompt gets its own
+    /// expected output (see [`Self::split_prompt_outputs`]) and the block's
+    /// expected output is left empty.
+    ///
+    /// Returns (command_content, optional_expected_output, prompts).
+    fn extract_inline_expected_output(
+        content: &str,
+    ) -> (String, Option<ExpectedOutput>, Vec<PromptCommand>) {
         // First, check if content has shell prompt lines
         let has_shell_prompts = content.lines().any(|line| {
             let trimmed = line.trim();
@@ -555,7 +978,7 @@ impl ParsedDoc {
 
         // If no shell prompts, return content unchanged
         if !has_shell_prompts {
-            return (content.to_string(), None);
+            return (content.to_string(), None, Vec::new());
         }
 
         let lines: Vec<&str> = content.lines().collect();
@@ -586,6 +1009,11 @@ impl ParsedDoc {
 
         let command_content = command_lines.join("\n");
 
+        let prompts = Self::split_prompt_outputs(content);
+        if !prompts.is_empty() {
+            return (command_content, None, prompts);
+        }
+
         // Only create expected output if we have non-empty output lines
         let output_content: String = output_lines.to_vec().join("\n");
 
@@ -598,7 +1026,69 @@ impl ParsedDoc {
             None
         };
 
-        (command_content, expected_output)
+        (command_content, expected_output, Vec::new())
+    }
+
+    /// Split a block into its prompts and the output written after each one.
+    ///
+    /// In a block like:
+    /// ```bash
+    /// $ pave --version
+    /// pave 0.1.0
+    /// $ pave check
+    /// Checked 5 documents
+    /// ```
+    ///
+    /// `pave 0.1.0` belongs to the first command and `Checked 5 documents` to
+    /// the second. Blank and comment lines around each output are dropped.
+    ///
+    /// Returns an empty list unless the block starts with a prompt and a
+    /// prompt other than the last has output of its own.
+    fn split_prompt_outputs(content: &str) -> Vec<PromptCommand> {
+        let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let prompt = trimmed
+                .strip_prefix("$ ")
+                .or_else(|| trimmed.strip_prefix("> "));
+            match (prompt, sections.last_mut()) {
+                (Some(command), _) => sections.push((command.trim().to_string(), Vec::new())),
+                (None, Some((_, output))) => output.push(line),
+                (None, None) if trimmed.is_empty() || trimmed.starts_with('#') => {}
+                (None, None) => return Vec::new(),
+            }
+        }
+
+        let is_filler = |line: &&str| {
+            let trimmed = line.trim();
+            trimmed.is_empty() || trimmed.starts_with('#')
+        };
+        let outputs: Vec<Option<String>> = sections
+            .iter()
+            .map(|(_, output)| {
+                let start = output.iter().position(|line| !is_filler(line))?;
+                let end = output.iter().rposition(|line| !is_filler(line))?;
+                Some(output[start..=end].join("\n"))
+            })
+            .collect();
+        let sectioned = outputs
+            .split_last()
+            .is_some_and(|(_, earlier)| earlier.iter().any(Option::is_some));
+        if !sectioned {
+            return Vec::new();
+        }
+
+        sections
+            .into_iter()
+            .zip(outputs)
+            .map(|((command, _), output)| PromptCommand {
+                command,
+                expected_output: output.map(|content| ExpectedOutput {
+                    content,
+                    strategy: ExpectMatchStrategy::Contains,
+                }),
+            })
+            .collect()
     }
 
     /// Extract pave frontmatter from document content.
@@ -614,9 +1104,287 @@ impl ParsedDoc {
         let yaml_content = &after_first[..close_pos];
 
         // Parse the YAML and extract pave section
-        let wrapper: FrontmatterWrapper = serde_yaml::from_str(yaml_content).ok()?;
-        wrapper.pave
+        parse_pave_frontmatter(yaml_content).ok()?
+    }
+}
+
+/// Parse the `pave` settings from frontmatter YAML.
+pub fn parse_pave_frontmatter(yaml: &str) -> Result<Option<PaveFrontmatter>, serde_yaml::Error> {
+    serde_yaml::from_str::<FrontmatterWrapper>(yaml).map(|wrapper| wrapper.pave)
+}
+
+/// Parse `key=value` pairs separated by whitespace, where a value may be
+/// double-quoted. `None` if there are no pairs or one is malformed.
+fn parse_pairs(text: &str) -> Option<BTreeMap<String, String>> {
+    let mut pairs = BTreeMap::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let (key, after) = rest.split_once('=')?;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => after.split_at(after.find(char::is_whitespace).unwrap_or(after.len())),
+        };
+        pairs.insert(key.to_string(), value.to_string());
+        rest = after.trim_start();
+    }
+    (!pairs.is_empty()).then_some(pairs)
+}
+
+/// Byte offset of the start of each line in `text`.
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect()
+}
+
+/// Line (0-indexed) containing a byte offset.
+fn line_of(line_starts: &[usize], offset: usize) -> usize {
+    line_starts.partition_point(|&start| start <= offset) - 1
+}
+
+/// The text of a heading's source: an ATX heading without its opening and
+/// closing `#` runs, or a setext heading's lines without the underline.
+fn heading_text(source: &str) -> String {
+    let mut lines: Vec<&str> = source.lines().map(str::trim).collect();
+    let Some(first) = lines.first() else {
+        return String::new();
+    };
+    if first.starts_with('#') {
+        let text = first.trim_start_matches('#').trim();
+        let unclosed = text.trim_end_matches('#');
+        return if unclosed.is_empty() {
+            String::new()
+        } else if unclosed.ends_with(char::is_whitespace) {
+            unclosed.trim_end().to_string()
+        } else {
+            text.to_string()
+        };
+    }
+    lines.pop();
+    lines.join(" ")
+}
+
+/// Number of lines in a leading YAML frontmatter block, if there is one.
+pub fn frontmatter_len(lines: &[&str]) -> usize {
+    if lines.first().map(|line| line.trim_end()) != Some("---") {
+        return 0;
+    }
+    lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim_end() == "---")
+        .map_or(0, |end| end + 2)
+}
+
+/// Convert heading text or a link fragment to the anchor it names:
+/// lowercase, spaces as hyphens, and everything but letters, digits, and
+/// hyphens dropped.
+pub fn heading_anchor(text: &str) -> String {
+    text.to_lowercase()
+        .replace(' ', "-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect()
+}
+
+/// Something the parser skipped over instead of reporting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// Line number (1-indexed).
+    pub line: usize,
+    /// What was skipped.
+    pub message: String,
+    /// How to fix it.
+    pub hint: String,
+}
+
+/// Find what the lenient parser silently accepts: frontmatter without a
+/// closing `---`, code blocks that are never closed, unknown or malformed
+/// `pave:` markers, and `pave:env` markers without `=`.
+///
+/// YAML errors in closed frontmatter are left to the `valid-frontmatter`
+/// check rule.
+pub fn strict_warnings(content: &str) -> Vec<ParseWarning> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut warnings = Vec::new();
+    let body_start = frontmatter_len(&lines);
+    if body_start == 0 && lines.first().map(|line| line.trim_end()) == Some("---") {
+        warnings.push(ParseWarning {
+            line: 1,
+            message: "Frontmatter is never closed, so it is ignored".to_string(),
+            hint: "End the frontmatter with a '---' line".to_string(),
+        });
+    }
+
+    let mut tracker = CodeBlockTracker::new();
+    let mut open_fence = 0;
+    for (idx, line) in lines.iter().enumerate().skip(body_start) {
+        let was_in_block = tracker.in_code_block();
+        if tracker.process_line(line) {
+            if !was_in_block {
+                open_fence = idx + 1;
+            }
+            continue;
+        }
+        if was_in_block {
+            continue;
+        }
+        if let Some(warning) = marker_warning(line.trim()) {
+            warnings.push(ParseWarning {
+                line: idx + 1,
+                ..warning
+            });
+        }
+    }
+    if tracker.in_code_block() {
+        warnings.push(ParseWarning {
+            line: open_fence,
+            message: "Code block is never closed".to_string(),
+            hint: "Add a closing ``` fence after the block".to_string(),
+        });
+    }
+    warnings
+}
+
+/// The problem with a `<!-- pave:... -->` annotation line, if it is one that
+/// the parser doesn't understand. The returned line is 0.
+fn marker_warning(trimmed: &str) -> Option<ParseWarning> {
+    let error = Annotation::parse(trimmed)?.err()?;
+    let (message, hint) = match error {
+        AnnotationError::Unclosed => (
+            "Marker comment is not closed on the same line".to_string(),
+            "End the marker with '-->'".to_string(),
+        ),
+        AnnotationError::Unknown(name) => {
+            let closest = ANNOTATIONS
+                .iter()
+                .map(|(known, _)| (strsim::jaro_winkler(&name, known), known))
+                .filter(|(score, _)| *score >= 0.8)
+                .max_by(|a, b| a.0.total_cmp(&b.0));
+            let hint = match closest {
+                Some((_, known)) => format!("Did you mean 'pave:{}'?", known),
+                None => format!(
+                    "Known markers: {}",
+                    ANNOTATIONS
+                        .iter()
+                        .map(|(known, _)| format!("pave:{}", known))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            (format!("Unknown marker 'pave:{}'", name), hint)
+        }
+        AnnotationError::Malformed(name) => {
+            let message = if name == "env" {
+                "Marker 'pave:env' has no '=', so no variable is set".to_string()
+            } else {
+                format!("Marker 'pave:{}' is malformed and is ignored", name)
+            };
+            let usage = Annotation::usage(&name).unwrap_or_default();
+            (message, format!("Write it as {}", usage))
+        }
+    };
+    Some(ParseWarning {
+        line: 0,
+        message,
+        hint,
+    })
+}
+
+/// Which of `lines` belong to a code block, fences included, as the parser
+/// sees them: fenced blocks with backticks or tildes and indented blocks.
+/// Frontmatter is never code.
+pub fn code_block_lines(lines: &[&str]) -> Vec<bool> {
+    let body_start = frontmatter_len(lines);
+    let text = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| if idx < body_start { "" } else { line })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let starts = line_starts(&text);
+    let mut in_code = vec![false; lines.len()];
+    for (event, range) in Parser::new_ext(&text, Options::ENABLE_TABLES).into_offset_iter() {
+        if let Event::Start(Tag::CodeBlock(_)) = event {
+            let first = line_of(&starts, range.start);
+            let last = line_of(&starts, range.end.saturating_sub(1).max(range.start));
+            for flag in in_code.iter_mut().take(last + 1).skip(first) {
+                *flag = true;
+            }
+        }
+    }
+    in_code
+}
+
+/// A heading or paragraph of prose, as CommonMark reads the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProseBlock {
+    /// Line the block starts on (1-indexed).
+    pub line: usize,
+    /// Heading level (1-6), or `None` for a paragraph.
+    pub heading: Option<usize>,
+    /// The block's text without markup.
+    pub text: String,
+}
+
+/// Headings and paragraphs in `lines`, in order. Code blocks, HTML blocks,
+/// tables, and list items are not prose, and frontmatter is skipped.
+pub fn prose_blocks(lines: &[&str]) -> Vec<ProseBlock> {
+    let body_start = frontmatter_len(lines);
+    let text = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| if idx < body_start { "" } else { line })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let starts = line_starts(&text);
+    let mut blocks = Vec::new();
+    let mut current: Option<ProseBlock> = None;
+    let mut list_depth = 0;
+    for (event, range) in Parser::new_ext(&text, Options::ENABLE_TABLES).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Item) => list_depth += 1,
+            Event::End(TagEnd::Item) => list_depth -= 1,
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(ProseBlock {
+                    line: line_of(&starts, range.start) + 1,
+                    heading: Some(level as usize),
+                    text: String::new(),
+                });
+            }
+            Event::Start(Tag::Paragraph) if list_depth == 0 => {
+                current = Some(ProseBlock {
+                    line: line_of(&starts, range.start) + 1,
+                    heading: None,
+                    text: String::new(),
+                });
+            }
+            Event::Text(part) | Event::Code(part) => {
+                if let Some(block) = &mut current {
+                    block.text.push_str(&part);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(block) = &mut current {
+                    block.text.push(' ');
+                }
+            }
+            Event::End(TagEnd::Heading(_) | TagEnd::Paragraph) => {
+                if let Some(mut block) = current.take() {
+                    block.text = block.text.trim().to_string();
+                    blocks.push(block);
+                }
+            }
+            _ => {}
+        }
     }
+    blocks
 }
 
 /// Tracks whether we're inside a code block while iterating through lines.
@@ -1169,15 +1937,85 @@ $ make test
     }
 
     #[test]
-    fn pave_run_marker_makes_block_executable() {
-        let content = r#"# Test
+    fn indented_blocks_only_run_with_a_marker() {
+        let content = "# Test\n\n## Verification\n\nRun this:\n\n    $ echo INDENTED\n\n<!-- pave:run -->\n\n    echo MARKED\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let blocks = &doc.get_section("Verification").unwrap().code_blocks;
 
-## Example
-<!-- pave:run -->
-```python
-print("hello")
-```
-"#;
+        assert_eq!(blocks.len(), 2);
+        assert!(!blocks[0].is_executable);
+        assert!(blocks[1].is_executable);
+    }
+
+    #[test]
+    fn code_block_lines_match_the_parser() {
+        let lines = [
+            "---",
+            "pave:",
+            "    paths: [src]",
+            "---",
+            "# Doc",
+            "",
+            "    indented code",
+            "",
+            "~~~",
+            "```",
+            "~~~",
+            "text",
+        ];
+        assert_eq!(
+            code_block_lines(&lines),
+            vec![
+                false, false, false, false, false, false, true, false, true, true, true, false
+            ]
+        );
+    }
+
+    #[test]
+    fn prose_blocks_skip_tables_html_and_lists() {
+        let lines = [
+            "# Doc",
+            "",
+            "Some `inline`",
+            "text.",
+            "",
+            "| a | b |",
+            "|---|---|",
+            "| c | d |",
+            "",
+            "<div>",
+            "## Not a heading",
+            "</div>",
+            "",
+            "- a list item",
+            "",
+            "## Next",
+        ];
+        let block = |line, heading, text: &str| ProseBlock {
+            line,
+            heading,
+            text: text.to_string(),
+        };
+        assert_eq!(
+            prose_blocks(&lines),
+            vec![
+                block(1, Some(1), "Doc"),
+                block(3, None, "Some inline text."),
+                block(16, Some(2), "Next"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pave_run_marker_makes_block_executable() {
+        let content = r#"# Test
+
+## Example
+<!-- pave:run -->
+```python
+print("hello")
+```
+"#;
 
         let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
         let section = doc.get_section("Example").unwrap();
@@ -1505,6 +2343,29 @@ hello
         assert_eq!(expected.content.trim(), "hello");
     }
 
+    #[test]
+    fn explicit_expect_not_marker() {
+        let content = r#"# Test
+
+## Verification
+```bash
+cargo build
+```
+<!-- pave:expect:not -->
+```
+deprecated
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        assert_eq!(section.code_blocks.len(), 1);
+        let expected = section.code_blocks[0].expected_output.as_ref().unwrap();
+        assert_eq!(expected.strategy, ExpectMatchStrategy::NotContains);
+        assert_eq!(expected.content.trim(), "deprecated");
+    }
+
     #[test]
     fn expect_marker_without_spaces() {
         let content = r#"# Test
@@ -1592,6 +2453,70 @@ $ echo world
         assert!(block.content.contains("$ echo world"));
         // No expected output since there's nothing after the commands
         assert!(block.expected_output.is_none());
+        assert!(block.prompts.is_empty());
+    }
+
+    #[test]
+    fn output_between_prompts_belongs_to_each_command() {
+        let content = r#"# Test
+
+## Verification
+```bash
+# Check the version
+$ pave --version
+pave 0.1.0
+
+# Then the docs
+$ pave check
+Checked 5 documents
+$ true
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let block = &doc.get_section("Verification").unwrap().code_blocks[0];
+
+        assert!(block.expected_output.is_none());
+        let commands: Vec<&str> = block.prompts.iter().map(|p| p.command.as_str()).collect();
+        assert_eq!(commands, vec!["pave --version", "pave check", "true"]);
+        let outputs: Vec<Option<&str>> = block
+            .prompts
+            .iter()
+            .map(|p| p.expected_output.as_ref().map(|e| e.content.as_str()))
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![Some("pave 0.1.0"), Some("Checked 5 documents"), None]
+        );
+    }
+
+    #[test]
+    fn expect_block_after_prompts_belongs_to_the_last_prompt() {
+        let content = r#"# Test
+
+## Verification
+```bash
+$ pave --version
+pave 0.1.0
+$ pave check
+```
+<!-- pave:expect:regex -->
+```
+Checked \d+ documents
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+        assert_eq!(section.code_blocks.len(), 1);
+        let last = section.code_blocks[0].prompts.last().unwrap();
+        assert_eq!(
+            last.expected_output,
+            Some(ExpectedOutput {
+                content: "Checked \\d+ documents".to_string(),
+                strategy: ExpectMatchStrategy::Regex,
+            })
+        );
     }
 
     #[test]
@@ -1613,6 +2538,44 @@ API service.
         assert_eq!(frontmatter.working_dir, Some("packages/api".to_string()));
     }
 
+    #[test]
+    fn parse_document_state_from_frontmatter() {
+        let content = r#"---
+pave:
+  state: Deprecated
+  superseded_by: ./auth-v2.md
+---
+# Auth
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+
+        assert_eq!(doc.state(), DocState::Deprecated);
+        let frontmatter = doc.frontmatter.unwrap();
+        assert_eq!(frontmatter.superseded_by, Some("./auth-v2.md".to_string()));
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), "# Auth\n").unwrap();
+        assert_eq!(doc.state(), DocState::Published);
+
+        let content = "---\npave:\n  status: active\n  owner: alice\n  tags: [auth]\n---\n# Auth\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert_eq!(doc.state(), DocState::Published);
+        let frontmatter = doc.frontmatter.unwrap();
+        assert_eq!(frontmatter.owner.as_deref(), Some("alice"));
+        assert_eq!(frontmatter.tags, vec!["auth"]);
+    }
+
+    #[test]
+    fn doc_state_transitions_only_move_forward() {
+        assert!(DocState::Draft.can_transition_to(DocState::Published));
+        assert!(DocState::Draft.can_transition_to(DocState::Deprecated));
+        assert!(DocState::Published.can_transition_to(DocState::Deprecated));
+        assert!(DocState::Published.can_transition_to(DocState::Published));
+        assert!(!DocState::Published.can_transition_to(DocState::Draft));
+        assert!(!DocState::Deprecated.can_transition_to(DocState::Published));
+        assert_eq!(DocState::parse("bogus"), None);
+    }
+
     #[test]
     fn parse_pave_working_dir_inline_marker() {
         let content = r#"# Test
@@ -1652,6 +2615,124 @@ npm test
         assert_eq!(block.working_dir, Some("src/components".to_string()));
     }
 
+    #[test]
+    fn parse_pave_shell_marker() {
+        let content = r#"# Test
+
+## Verification
+<!-- pave:shell pwsh -->
+```bash
+Get-ChildItem
+```
+<!--pave:shell bash-->
+```bash
+echo hi
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        assert_eq!(section.code_blocks.len(), 2);
+        assert_eq!(section.code_blocks[0].shell, Some("pwsh".to_string()));
+        assert_eq!(section.code_blocks[1].shell, Some("bash".to_string()));
+    }
+
+    #[test]
+    fn parse_pave_exit_code_marker() {
+        let content = r#"# Test
+
+## Verification
+<!-- pave:exit-code 2 -->
+```bash
+pave check missing.md
+```
+```bash
+pave check
+```
+<!--pave:exit-code 1-->
+<!-- pave:expect -->
+```
+ignored
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        assert_eq!(section.code_blocks.len(), 2);
+        assert_eq!(section.code_blocks[0].exit_code, Some(2));
+        assert_eq!(section.code_blocks[1].exit_code, None);
+        assert_eq!(
+            Annotation::parse("<!-- pave:exit-code x -->"),
+            Some(Err(AnnotationError::Malformed("exit-code".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_pave_timeout_and_retries_markers() {
+        let content = "# Test\n\n## Verification\n<!-- pave:timeout 120 -->\n<!-- pave:retries 2 -->\n\
+                       ```bash\nmake integration\n```\n```bash\nmake lint\n```\n";
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let blocks = &doc.get_section("Verification").unwrap().code_blocks;
+
+        assert_eq!((blocks[0].timeout_secs, blocks[0].retries), (Some(120), 2));
+        assert_eq!((blocks[1].timeout_secs, blocks[1].retries), (None, 0));
+    }
+
+    #[test]
+    fn parse_pave_setup_and_teardown_markers() {
+        let content = r#"# Test
+
+## Verification
+<!-- pave:setup -->
+```text
+make seed
+```
+```bash
+cargo test
+```
+<!--pave:teardown-->
+```bash
+make clean
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        assert_eq!(section.code_blocks[0].phase, BlockPhase::Setup);
+        assert!(section.code_blocks[0].is_executable);
+        assert_eq!(section.code_blocks[1].phase, BlockPhase::Verify);
+        assert_eq!(section.code_blocks[2].phase, BlockPhase::Teardown);
+    }
+
+    #[test]
+    fn powershell_and_batch_fences_are_executable() {
+        let content = r#"# Test
+
+## Verification
+```powershell
+Get-ChildItem
+```
+```bat
+dir
+```
+```python
+print("hi")
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        assert!(section.code_blocks[0].is_executable);
+        assert!(section.code_blocks[1].is_executable);
+        assert!(!section.code_blocks[2].is_executable);
+        assert_eq!(section.code_blocks[0].shell, None);
+    }
+
     #[test]
     fn parse_pave_env_inline_marker() {
         let content = r#"# Test
@@ -1706,6 +2787,25 @@ cargo test
         );
     }
 
+    #[test]
+    fn parse_pave_env_from_markers() {
+        let content = r#"# Test
+
+## Verification
+<!--pave:env-from API_TOKEN-->
+<!-- pave:env-from DB_PASSWORD -->
+<!-- pave:env DEBUG=1 -->
+```bash
+curl -H "Authorization: Bearer $API_TOKEN" localhost
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let block = &doc.get_section("Verification").unwrap().code_blocks[0];
+        assert_eq!(block.secrets, ["API_TOKEN", "DB_PASSWORD"]);
+        assert_eq!(block.env_vars, [("DEBUG".to_string(), "1".to_string())]);
+    }
+
     #[test]
     fn parse_pave_env_with_working_dir() {
         let content = r#"# Test
@@ -1870,4 +2970,283 @@ echo test
         assert!(tracker.process_line("  ```"));
         assert!(!tracker.in_code_block());
     }
+
+    #[test]
+    fn parse_annotations() {
+        let parse = |line| Annotation::parse(line).unwrap();
+        assert_eq!(parse("<!-- pave:run -->"), Ok(Annotation::Run));
+        assert_eq!(parse("  <!--pave:run-->"), Ok(Annotation::Run));
+        assert_eq!(
+            parse("<!--   pave:expect:regex   -->"),
+            Ok(Annotation::Expect(ExpectMatchStrategy::Regex))
+        );
+        assert_eq!(
+            parse("<!-- pave:env URL = http://a?b=c -->"),
+            Ok(Annotation::Env(
+                "URL".to_string(),
+                "http://a?b=c".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("<!--pave:include _includes/setup.md-->"),
+            Ok(Annotation::Include("_includes/setup.md".to_string()))
+        );
+        assert_eq!(
+            parse("<!-- pave:env-from TWO WORDS -->"),
+            Err(AnnotationError::Malformed("env-from".to_string()))
+        );
+        assert_eq!(
+            parse("<!-- pave:timeout 0 -->"),
+            Err(AnnotationError::Malformed("timeout".to_string()))
+        );
+        assert_eq!(parse("<!-- pave:retries 2 -->"), Ok(Annotation::Retries(2)));
+        assert_eq!(
+            parse("<!-- pave:sleep 30 -->"),
+            Err(AnnotationError::Unknown("sleep".to_string()))
+        );
+        assert_eq!(parse("<!-- pave:run"), Err(AnnotationError::Unclosed));
+        assert_eq!(Annotation::parse("<!-- note -->"), None);
+        assert_eq!(Annotation::parse("text <!-- pave:run -->"), None);
+        assert_eq!(
+            Annotation::usage("env"),
+            Some("<!-- pave:env NAME=value -->")
+        );
+    }
+
+    #[test]
+    fn annotation_usages_parse_back() {
+        for annotation in Annotation::examples() {
+            let usage = annotation.usage_text();
+            assert_eq!(Annotation::parse(&usage), Some(Ok(annotation)), "{}", usage);
+        }
+        assert_eq!(
+            Annotation::usage("section"),
+            Some("<!-- pave:section owner=infra priority=high -->")
+        );
+    }
+
+    #[test]
+    fn annotations_attach_to_blocks_and_sections() {
+        let content = r#"# Test
+
+## Verification
+
+<!-- pave:shell zsh -->
+<!-- pave:env MODE=ci -->
+<!-- pave:bogus -->
+```bash
+echo $MODE
+```
+
+<!-- pave:run -->
+"#;
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+        assert_eq!(
+            section.code_blocks[0].annotations,
+            vec![
+                Annotation::Shell("zsh".to_string()),
+                Annotation::Env("MODE".to_string(), "ci".to_string()),
+            ]
+        );
+        let lines: Vec<usize> = section.annotations.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![5, 6, 12]);
+        assert_eq!(section.annotations[2].1, Annotation::Run);
+    }
+
+    #[test]
+    fn section_annotations_become_metadata() {
+        let content = r#"# Runbook
+
+## Steps
+<!-- pave:section owner=infra priority=high -->
+<!-- pave:section reviewer="Platform Team" priority=low -->
+
+1. Deploy
+
+## Rollback
+"#;
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let steps = doc.get_section("Steps").unwrap();
+        let metadata: Vec<(&str, &str)> = steps
+            .metadata
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            metadata,
+            vec![
+                ("owner", "infra"),
+                ("priority", "low"),
+                ("reviewer", "Platform Team")
+            ]
+        );
+        assert!(doc.get_section("Rollback").unwrap().metadata.is_empty());
+
+        for malformed in [
+            "<!-- pave:section -->",
+            "<!-- pave:section owner -->",
+            "<!-- pave:section owner=\"open -->",
+        ] {
+            assert_eq!(
+                Annotation::parse(malformed),
+                Some(Err(AnnotationError::Malformed("section".to_string())))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_follows_commonmark_block_structure() {
+        let content = r#"---
+pave:
+  owner: ops
+---
+    # Indented code, not a title
+
+Real Title
+==========
+
+## Purpose ##
+
+<pre>
+## Not a section
+</pre>
+
+| Column | `## not a heading` |
+|--------|--------------------|
+| a      | b                  |
+
+Usage
+-----
+
+    pave check
+
+~~~bash
+pave verify
+~~~
+
+## Verification
+
+Don't copy `<!-- pave:run -->` from prose.
+
+```python
+print("not run")
+```
+"#;
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Real Title"));
+        let names: Vec<(&str, usize)> = doc
+            .sections
+            .iter()
+            .map(|s| (s.name.as_str(), s.start_line))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Purpose", 10), ("Usage", 20), ("Verification", 29)]
+        );
+        assert_eq!(
+            doc.frontmatter.as_ref().unwrap().owner.as_deref(),
+            Some("ops")
+        );
+
+        let usage = doc.get_section("Usage").unwrap();
+        assert!(!usage.content.starts_with("---"));
+        assert!(usage.has_code_blocks);
+        let blocks: Vec<(Option<&str>, &str, usize, bool)> = usage
+            .code_blocks
+            .iter()
+            .map(|b| {
+                (
+                    b.language.as_deref(),
+                    b.content.as_str(),
+                    b.start_line,
+                    b.is_executable,
+                )
+            })
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                (None, "pave check", 23, false),
+                (Some("bash"), "pave verify", 25, true)
+            ]
+        );
+
+        let verification = doc.get_section("Verification").unwrap();
+        assert!(!verification.code_blocks[0].is_executable);
+    }
+
+    #[test]
+    fn strict_warnings_report_skipped_input() {
+        let content = r#"---
+pave:
+  owner: ops
+---
+# Doc
+
+<!-- pave:run -->
+<!--pave:env-from TOKEN-->
+<!-- pave:include _includes/setup.md -->
+<!-- pave:envv FOO=1 -->
+<!-- pave:env FOO -->
+<!-- pave:shell -->
+<!-- pave:run
+<!-- pave:nonsense -->
+
+```text
+<!-- pave:bogus -->
+```
+
+```bash
+echo never closed
+"#;
+        let warnings: Vec<(usize, String, String)> = strict_warnings(content)
+            .into_iter()
+            .map(|w| (w.line, w.message, w.hint))
+            .collect();
+        assert_eq!(warnings.len(), 6);
+        assert_eq!(
+            warnings[0],
+            (
+                10,
+                "Unknown marker 'pave:envv'".to_string(),
+                "Did you mean 'pave:env'?".to_string()
+            )
+        );
+        assert_eq!(
+            warnings[1],
+            (
+                11,
+                "Marker 'pave:env' has no '=', so no variable is set".to_string(),
+                "Write it as <!-- pave:env NAME=value -->".to_string()
+            )
+        );
+        assert_eq!(
+            warnings[2].1,
+            "Marker 'pave:shell' is malformed and is ignored"
+        );
+        assert_eq!(
+            warnings[3].1,
+            "Marker comment is not closed on the same line"
+        );
+        assert!(warnings[4].2.starts_with("Known markers: pave:run"));
+        assert_eq!(
+            (warnings[5].0, warnings[5].1.as_str()),
+            (20, "Code block is never closed")
+        );
+
+        let warnings = strict_warnings("---\npave:\n  owner: ops\n# Doc\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "Frontmatter is never closed, so it is ignored"
+        );
+        assert!(strict_warnings("# Doc\n\n<!-- pave:expect:regex -->\n").is_empty());
+    }
+
+    #[test]
+    fn heading_anchor_matches_rendered_anchors() {
+        assert_eq!(heading_anchor("When to Use"), "when-to-use");
+        assert_eq!(heading_anchor("API (v2)"), "api-v2");
+    }
 }
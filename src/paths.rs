@@ -0,0 +1,152 @@
+//! Path display helpers.
+//!
+//! File discovery yields a mix of absolute paths (from the config root) and
+//! relative paths (from CLI arguments). These helpers normalize paths into a
+//! single style before they are shown to the user.
+
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+use crate::cli::PathStyle;
+
+/// Format a path for output according to `style`, resolving relative paths
+/// against `cwd`.
+///
+/// `workspace_root` is the directory containing `.pave.toml`. Paths that
+/// cannot be expressed relative to the requested base are shown absolute.
+pub fn display_path(path: &Path, style: PathStyle, workspace_root: &Path, cwd: &Path) -> PathBuf {
+    let absolute = absolutize(path, cwd);
+    match style {
+        PathStyle::Absolute => absolute,
+        PathStyle::Workspace => {
+            let root = absolutize(workspace_root, cwd);
+            absolute
+                .strip_prefix(&root)
+                .map(Path::to_path_buf)
+                .unwrap_or(absolute)
+        }
+        PathStyle::Relative => relative_to(&absolute, cwd).unwrap_or(absolute),
+    }
+}
+
+/// Express `path` relative to the directory `base`, e.g. for locating the
+/// project root from a generated file.
+///
+/// Returns `.` for the same directory, and the absolute path if the two
+/// share no common root.
+pub fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let absolute = absolutize(path, &cwd);
+    match relative_to(&absolute, &absolutize(base, &cwd)) {
+        Some(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+        Some(relative) => relative,
+        None => absolute,
+    }
+}
+
+/// Make a path absolute and lexically remove `.` and `..` components.
+pub(crate) fn absolutize(path: &Path, cwd: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Express an absolute path relative to an absolute base directory.
+///
+/// Returns `None` if the paths share no common root (e.g. different drives).
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    let path_components: Vec<Component> = path.components().collect();
+    let base_components: Vec<Component> = base.components().collect();
+
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return None;
+    }
+
+    let mut result = PathBuf::new();
+    for _ in common..base_components.len() {
+        result.push("..");
+    }
+    for component in &path_components[common..] {
+        result.push(component.as_os_str());
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_style_strips_config_root() {
+        let path = display_path(
+            Path::new("/repo/docs/a.md"),
+            PathStyle::Workspace,
+            Path::new("/repo"),
+            Path::new("/repo/src"),
+        );
+        assert_eq!(path, PathBuf::from("docs/a.md"));
+    }
+
+    #[test]
+    fn workspace_style_resolves_cwd_relative_paths() {
+        let path = display_path(
+            Path::new("../docs/./a.md"),
+            PathStyle::Workspace,
+            Path::new("/repo"),
+            Path::new("/repo/src"),
+        );
+        assert_eq!(path, PathBuf::from("docs/a.md"));
+    }
+
+    #[test]
+    fn workspace_style_keeps_outside_paths_absolute() {
+        let path = display_path(
+            Path::new("/elsewhere/a.md"),
+            PathStyle::Workspace,
+            Path::new("/repo"),
+            Path::new("/repo"),
+        );
+        assert_eq!(path, PathBuf::from("/elsewhere/a.md"));
+    }
+
+    #[test]
+    fn relative_style_walks_up_from_cwd() {
+        let path = display_path(
+            Path::new("/repo/docs/a.md"),
+            PathStyle::Relative,
+            Path::new("/repo"),
+            Path::new("/repo/src/cli"),
+        );
+        assert_eq!(path, PathBuf::from("../../docs/a.md"));
+    }
+
+    #[test]
+    fn absolute_style_resolves_against_cwd() {
+        let path = display_path(
+            Path::new("docs/a.md"),
+            PathStyle::Absolute,
+            Path::new("/repo"),
+            Path::new("/repo"),
+        );
+        assert_eq!(path, PathBuf::from("/repo/docs/a.md"));
+    }
+}
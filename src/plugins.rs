@@ -0,0 +1,438 @@
+//! External plugins.
+//!
+//! An executable named `pave-<name>` on `PATH` runs as `pave <name>`, with
+//! the remaining arguments passed through. Rule plugins declared in
+//! `[plugins]` are run by `pave check` and `pave lint` once per document:
+//! a [`RuleRequest`] goes in as JSON on stdin and a [`RuleResponse`] comes
+//! back as JSON on stdout.
+//!
+//! WebAssembly rules declared in `[plugins.wasm]` take the same request and
+//! response, but run in-process in a sandbox: a module gets no imports, so
+//! it has no filesystem, network, clock, or environment, and its memory and
+//! instruction count are bounded. A module exports:
+//!
+//! - `memory`: its linear memory;
+//! - `alloc(len: i32) -> i32`: reserves `len` bytes for the request;
+//! - `validate(ptr: i32, len: i32) -> i64`: reads the request JSON at `ptr`
+//!   and returns the response JSON's pointer in the high 32 bits and its
+//!   length in the low 32 bits.
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use wasmi::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::exit::Violations;
+use crate::verification::spawn_with_input;
+use crate::workspace::Workdir;
+
+/// Prefix of command plugin executables.
+pub const PLUGIN_PREFIX: &str = "pave-";
+
+/// Version of the rule plugin protocol, sent with every request.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Instructions a WebAssembly rule may execute per document, roughly.
+pub const WASM_FUEL: u64 = 1_000_000_000;
+
+/// Most linear memory a WebAssembly rule may grow to, in bytes.
+pub const WASM_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// A document sent to a rule plugin.
+#[derive(Debug, Serialize)]
+pub struct RuleRequest<'a> {
+    /// Protocol version ([`PROTOCOL_VERSION`]).
+    pub protocol: u32,
+    /// Name the rule is configured under.
+    pub rule: &'a str,
+    /// Path of the document relative to the config directory.
+    pub file: &'a Path,
+    /// Content of the document.
+    pub content: &'a str,
+}
+
+/// Issues a rule plugin found in a document.
+#[derive(Debug, Deserialize)]
+pub struct RuleResponse {
+    #[serde(default)]
+    pub issues: Vec<PluginIssue>,
+}
+
+/// An issue reported by a rule plugin.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PluginIssue {
+    /// Line of the issue (1-indexed, default 1).
+    #[serde(default = "first_line")]
+    pub line: usize,
+    /// Description of the issue.
+    pub message: String,
+    /// Severity for `pave check` (default: error). `pave lint` reports
+    /// every issue alike.
+    #[serde(default)]
+    pub severity: PluginSeverity,
+    /// Hint for fixing the issue.
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+/// Severity of a plugin issue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+fn first_line() -> usize {
+    1
+}
+
+/// Path of the `pave-<name>` executable on `PATH`, if there is one.
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    let file_name = format!("{}{}", PLUGIN_PREFIX, name);
+    env::split_paths(&path).find_map(|dir| {
+        let candidates = [dir.join(&file_name), dir.join(format!("{}.exe", file_name))];
+        candidates
+            .into_iter()
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Run `pave <name> [args...]` as the `pave-<name>` plugin.
+///
+/// `args` starts with the plugin name, as clap passes external subcommands.
+/// The plugin gets `PAVE_BIN` (this executable) and, inside a project,
+/// `PAVE_CONFIG` in its environment. Exit status 1 is reported as found
+/// problems and any other failure as a tool error, matching pave's own
+/// commands. `builtins` are the built-in command names, used to suggest one
+/// when no plugin matches. The plugin runs from `workdir`.
+pub fn run_command_plugin(args: &[OsString], builtins: &[String], workdir: &Workdir) -> Result<()> {
+    let Some((name, rest)) = args.split_first() else {
+        bail!("No command given");
+    };
+    let name = name.to_string_lossy();
+    let Some(plugin) = find_plugin(&name) else {
+        let closest = builtins
+            .iter()
+            .map(|builtin| (strsim::levenshtein(&name, builtin), builtin))
+            .filter(|(distance, _)| *distance <= 2)
+            .min();
+        match closest {
+            Some((_, builtin)) => bail!("Unknown command '{}' (did you mean '{}'?)", name, builtin),
+            None => bail!(
+                "Unknown command '{}': no {}{} executable found on PATH",
+                name,
+                PLUGIN_PREFIX,
+                name
+            ),
+        }
+    };
+
+    let mut cmd = Command::new(&plugin);
+    cmd.args(rest).current_dir(&workdir.dir);
+    if let Ok(exe) = env::current_exe() {
+        cmd.env("PAVE_BIN", exe);
+    }
+    if let Ok(Some(config)) = workdir.locate_config() {
+        cmd.env("PAVE_CONFIG", config);
+    }
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to run plugin: {}", plugin.display()))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(1) => bail!(Violations(format!(
+            "{}{} found problems",
+            PLUGIN_PREFIX, name
+        ))),
+        Some(code) => bail!("{}{} exited with status {}", PLUGIN_PREFIX, name, code),
+        None => bail!("{}{} was terminated by a signal", PLUGIN_PREFIX, name),
+    }
+}
+
+/// Run the rule plugin `rule` (command line `command`) on a document, from
+/// `config_dir`. `file` is the document's path relative to `config_dir`.
+pub fn run_rule(
+    rule: &str,
+    command: &str,
+    file: &Path,
+    content: &str,
+    config_dir: &Path,
+) -> Result<Vec<PluginIssue>> {
+    let argv: Vec<&str> = command.split_whitespace().collect();
+    let Some((program, args)) = argv.split_first() else {
+        bail!("Rule plugin '{}' has an empty command", rule);
+    };
+    let request = serde_json::to_string(&RuleRequest {
+        protocol: PROTOCOL_VERSION,
+        rule,
+        file,
+        content,
+    })?;
+
+    let mut cmd = Command::new(program);
+    cmd.args(args).current_dir(config_dir);
+    let output = spawn_with_input(&mut cmd, Some(&request))
+        .and_then(|child| child.wait_with_output())
+        .with_context(|| format!("Failed to run rule plugin '{}': {}", rule, command))?;
+    if !output.status.success() {
+        bail!(
+            "Rule plugin '{}' failed on {}: {}",
+            rule,
+            file.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let response: RuleResponse = serde_json::from_slice(&output.stdout).with_context(|| {
+        format!(
+            "Rule plugin '{}' wrote invalid JSON for {}",
+            rule,
+            file.display()
+        )
+    })?;
+    Ok(response.issues)
+}
+
+/// A compiled WebAssembly rule.
+pub struct WasmRule {
+    /// Name the rule is configured under.
+    pub name: String,
+    engine: Engine,
+    module: Module,
+    /// Instructions the rule may execute per document.
+    fuel: u64,
+}
+
+impl WasmRule {
+    /// Compile the WebAssembly module `bytes` as the rule `name`.
+    pub fn new(name: &str, bytes: &[u8]) -> Result<Self> {
+        let mut wasm_config = wasmi::Config::default();
+        wasm_config.consume_fuel(true);
+        let engine = Engine::new(&wasm_config);
+        let module = Module::new(&engine, bytes)
+            .map_err(|e| anyhow!("WASM rule '{}' is not a valid module: {}", name, e))?;
+        if let Some(import) = module.imports().next() {
+            bail!(
+                "WASM rule '{}' imports {}::{}, but rules run without host access",
+                name,
+                import.module(),
+                import.name()
+            );
+        }
+        Ok(Self {
+            name: name.to_string(),
+            engine,
+            module,
+            fuel: WASM_FUEL,
+        })
+    }
+
+    /// Run the rule on a document, in a fresh instance. `file` is the
+    /// document's path relative to the config directory.
+    pub fn run(&self, file: &Path, content: &str) -> Result<Vec<PluginIssue>> {
+        let request = serde_json::to_vec(&RuleRequest {
+            protocol: PROTOCOL_VERSION,
+            rule: &self.name,
+            file,
+            content,
+        })?;
+        let fail = |e: &dyn std::fmt::Display| {
+            anyhow!(
+                "WASM rule '{}' failed on {}: {}",
+                self.name,
+                file.display(),
+                e
+            )
+        };
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(WASM_MEMORY_LIMIT)
+            .instances(1)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel).map_err(|e| fail(&e))?;
+        let instance = Linker::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| fail(&e))?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| fail(&"module exports no memory"))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|e| fail(&e))?;
+        let validate = instance
+            .get_typed_func::<(i32, i32), i64>(&store, "validate")
+            .map_err(|e| fail(&e))?;
+
+        let len = i32::try_from(request.len()).map_err(|e| fail(&e))?;
+        let ptr = alloc.call(&mut store, len).map_err(|e| fail(&e))?;
+        memory
+            .write(&mut store, ptr as u32 as usize, &request)
+            .map_err(|e| fail(&e))?;
+        let packed = validate
+            .call(&mut store, (ptr, len))
+            .map_err(|e| fail(&e))? as u64;
+        let (start, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let output = memory
+            .data(&store)
+            .get(start..start + len)
+            .ok_or_else(|| fail(&"response is out of bounds"))?;
+        let response: RuleResponse = serde_json::from_slice(output).with_context(|| {
+            format!(
+                "WASM rule '{}' returned invalid JSON for {}",
+                self.name,
+                file.display()
+            )
+        })?;
+        Ok(response.issues)
+    }
+}
+
+/// Load the `[plugins.wasm]` rules, with module paths relative to
+/// `config_dir`.
+pub fn load_wasm_rules(
+    modules: &BTreeMap<String, PathBuf>,
+    config_dir: &Path,
+) -> Result<Vec<WasmRule>> {
+    modules
+        .iter()
+        .map(|(name, path)| {
+            let path = config_dir.join(path);
+            let bytes = std::fs::read(&path).with_context(|| {
+                format!("Failed to read WASM rule '{}': {}", name, path.display())
+            })?;
+            WasmRule::new(name, &bytes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn rule_plugin_reads_the_doc_and_reports_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("no-todo");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             if grep -q TODO; then\n\
+             echo '{\"issues\": [{\"line\": 3, \"message\": \"TODO left\", \"severity\": \"warning\"}, {\"message\": \"x\"}]}'\n\
+             else echo '{\"issues\": []}'; fi\n",
+        )
+        .unwrap();
+        // Run through sh so the script needn't be executable
+        let command = format!("sh {}", script.display());
+        let run = |content| {
+            run_rule(
+                "no-todo",
+                &command,
+                Path::new("docs/a.md"),
+                content,
+                temp_dir.path(),
+            )
+        };
+
+        let issues = run("# A\n\nTODO: finish\n").unwrap();
+        assert_eq!(
+            issues,
+            [
+                PluginIssue {
+                    line: 3,
+                    message: "TODO left".to_string(),
+                    severity: PluginSeverity::Warning,
+                    hint: None,
+                },
+                PluginIssue {
+                    line: 1,
+                    message: "x".to_string(),
+                    severity: PluginSeverity::Error,
+                    hint: None,
+                },
+            ]
+        );
+        assert!(run("# A\n").unwrap().is_empty());
+
+        std::fs::write(&script, "#!/bin/sh\necho oops\n").unwrap();
+        let err = run("# A\n").unwrap_err();
+        assert!(err.to_string().contains("invalid JSON"));
+    }
+
+    #[test]
+    fn wasm_rule_runs_sandboxed() {
+        // Reports an issue for requests over 80 bytes
+        let issues = r#"{"issues":[{"line":2,"message":"Too long","hint":"Split it"}]}"#;
+        let wat = format!(
+            r#"(module
+                 (memory (export "memory") 1)
+                 (data (i32.const 8) "{{}}")
+                 (data (i32.const 16) "{}")
+                 (func (export "alloc") (param i32) (result i32) i32.const 1024)
+                 (func (export "validate") (param $ptr i32) (param $len i32) (result i64)
+                   (if (result i64) (i32.gt_u (local.get $len) (i32.const 80))
+                     (then (i64.const {}))
+                     (else (i64.const {})))))"#,
+            issues.replace('"', "\\\""),
+            (16u64 << 32) | issues.len() as u64,
+            (8u64 << 32) | 2,
+        );
+        let mut rule = WasmRule::new("long-doc", &wat::parse_str(&wat).unwrap()).unwrap();
+        let file = Path::new("docs/a.md");
+
+        assert!(rule.run(file, "# A\n").unwrap().is_empty());
+        assert_eq!(
+            rule.run(file, "# A\n\nA much longer paragraph of text.\n")
+                .unwrap(),
+            [PluginIssue {
+                line: 2,
+                message: "Too long".to_string(),
+                severity: PluginSeverity::Error,
+                hint: Some("Split it".to_string()),
+            }]
+        );
+
+        // A runaway rule stops when it runs out of fuel
+        let spin = r#"(module
+                 (memory (export "memory") 1)
+                 (func (export "alloc") (param i32) (result i32) i32.const 0)
+                 (func (export "validate") (param i32 i32) (result i64)
+                   (loop $spin (br $spin))
+                   i64.const 0))"#;
+        rule = WasmRule::new("spin", &wat::parse_str(spin).unwrap()).unwrap();
+        rule.fuel = 10_000;
+        let err = rule.run(file, "# A\n").unwrap_err();
+        assert!(err.to_string().contains("WASM rule 'spin' failed"));
+
+        // Modules that import anything from the host are rejected
+        let wasi = r#"(module
+                 (import "wasi_snapshot_preview1" "fd_write"
+                   (func (param i32 i32 i32 i32) (result i32))))"#;
+        let err = WasmRule::new("io", &wat::parse_str(wasi).unwrap())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("without host access"));
+    }
+}
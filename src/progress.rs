@@ -0,0 +1,88 @@
+//! Progress bars for commands that walk many documents.
+//!
+//! The bar is drawn on stderr and only in an interactive terminal: it is
+//! hidden when stdout or stderr is redirected, when the command writes
+//! JSON or GitHub annotations, and when `-v` or `--quiet` changes the log
+//! level (log lines would tear through the bar). It is cleared when the run
+//! ends, so the command's own output is all that remains.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::Duration;
+use tracing::level_filters::LevelFilter;
+
+/// A progress bar over a run of documents.
+pub struct Progress {
+    bar: ProgressBar,
+}
+
+impl Progress {
+    /// A bar over `len` documents, shown only if `text_output` and the
+    /// terminal allow it.
+    pub fn new(len: usize, text_output: bool) -> Self {
+        if !is_enabled(text_output) {
+            return Self::hidden();
+        }
+        let style = ProgressStyle::with_template(
+            "{spinner} [{elapsed}] {bar:30} {pos}/{len} {prefix}{wide_msg}",
+        )
+        .expect("valid progress template");
+        let bar = ProgressBar::new(len as u64).with_style(style);
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self { bar }
+    }
+
+    /// A bar that never draws.
+    pub fn hidden() -> Self {
+        Self {
+            bar: ProgressBar::hidden(),
+        }
+    }
+
+    /// Show `doc` as the document being worked on.
+    pub fn start(&self, doc: &Path) {
+        self.bar.set_message(doc.display().to_string());
+    }
+
+    /// Count a document as done.
+    pub fn finish(&self) {
+        self.bar.inc(1);
+    }
+
+    /// Show a running failure count ahead of the current document.
+    pub fn set_failed(&self, failed: usize) {
+        if failed > 0 {
+            self.bar.set_prefix(format!("{} failed · ", failed));
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Whether a bar should be drawn for a command writing text output.
+fn is_enabled(text_output: bool) -> bool {
+    text_output
+        && LevelFilter::current() == LevelFilter::WARN
+        && std::io::stdout().is_terminal()
+        && std::io::stderr().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_output_never_shows_a_bar() {
+        assert!(!is_enabled(false));
+
+        let progress = Progress::new(3, false);
+        progress.start(Path::new("docs/a.md"));
+        progress.finish();
+        assert!(progress.bar.is_hidden());
+    }
+}
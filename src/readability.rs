@@ -0,0 +1,170 @@
+//! Readability metrics for the `readability` lint rule.
+//!
+//! Prose is measured by its average sentence length and its Flesch-Kincaid
+//! grade level, the US school grade a reader needs to follow it:
+//!
+//! ```text
+//! grade = 0.39 * words / sentences + 11.8 * syllables / words - 15.59
+//! ```
+//!
+//! Syllables are estimated from vowel groups, so scores are approximate.
+//! Code blocks, headings, tables, HTML, inline code, and link targets are left
+//! out; each list item counts as at least one sentence.
+
+use regex::Regex;
+
+use crate::parser::CodeBlockTracker;
+use crate::terminology::NON_PROSE;
+
+/// Word, sentence, and syllable counts of some prose.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Readability {
+    /// Number of words.
+    pub words: usize,
+    /// Number of sentences.
+    pub sentences: usize,
+    /// Estimated number of syllables.
+    pub syllables: usize,
+}
+
+impl Readability {
+    /// Measure the prose in a markdown fragment.
+    pub fn measure(markdown: &str) -> Self {
+        let skipped = Regex::new(&format!("{}|<[^>]*>", NON_PROSE)).unwrap();
+        let list_item = Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+").unwrap();
+
+        let mut readability = Self::default();
+        let mut tracker = CodeBlockTracker::new();
+        let mut block = String::new();
+        for line in markdown.lines() {
+            tracker.process_line(line);
+            let trimmed = line.trim();
+            let is_prose = !tracker.in_code_block()
+                && !trimmed.starts_with("```")
+                && !trimmed.starts_with('#')
+                && !trimmed.starts_with('|');
+            // Blank lines, list items, and non-prose end the current block
+            if !is_prose || trimmed.is_empty() || list_item.is_match(line) {
+                readability.add_block(&block);
+                block.clear();
+            }
+            if is_prose {
+                let text = list_item.replace(line, "");
+                block.push_str(&skipped.replace_all(&text, " "));
+                block.push(' ');
+            }
+        }
+        readability.add_block(&block);
+        readability
+    }
+
+    /// Count a paragraph or list item, which ends a sentence even without
+    /// closing punctuation.
+    fn add_block(&mut self, block: &str) {
+        let mut words_in_sentence = 0;
+        for token in block.split_whitespace() {
+            let word: String = token.chars().filter(|c| c.is_alphabetic()).collect();
+            if word.is_empty() {
+                continue;
+            }
+            self.words += 1;
+            self.syllables += syllables(&word);
+            words_in_sentence += 1;
+            if token
+                .trim_end_matches(['"', '\'', ')', '*', '_'])
+                .ends_with(['.', '!', '?'])
+            {
+                self.sentences += 1;
+                words_in_sentence = 0;
+            }
+        }
+        if words_in_sentence > 0 {
+            self.sentences += 1;
+        }
+    }
+
+    /// Average words per sentence.
+    pub fn sentence_words(&self) -> f64 {
+        if self.sentences == 0 {
+            return 0.0;
+        }
+        self.words as f64 / self.sentences as f64
+    }
+
+    /// Flesch-Kincaid grade level.
+    pub fn grade_level(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        0.39 * self.sentence_words() + 11.8 * (self.syllables as f64 / self.words as f64) - 15.59
+    }
+}
+
+/// Estimate a word's syllables from its vowel groups.
+fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let is_vowel = |c: char| "aeiouy".contains(c);
+
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    // A final silent "e", as in "make", but not "le", as in "table"
+    if word.ends_with('e') && !word.ends_with("le") && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_syllables() {
+        let counts: Vec<usize> = ["the", "make", "table", "deploy", "configuration", "rhythm"]
+            .iter()
+            .map(|word| syllables(word))
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2, 2, 5, 1]);
+    }
+
+    #[test]
+    fn measures_prose_only() {
+        let readability = Readability::measure(
+            "Restart the service. Check the logs!\n\
+             \n\
+             - Open the [dashboard](https://example.com/a.b.c)\n\
+             - Run `systemctl restart api.service`\n\
+             \n\
+             ```sh\n\
+             echo this is not prose.\n\
+             ```\n\
+             \n\
+             ### Rollback\n\
+             \n\
+             | Step | Command |\n",
+        );
+        assert_eq!(readability.sentences, 4);
+        assert_eq!(readability.words, 10);
+        assert_eq!(readability.sentence_words(), 2.5);
+    }
+
+    #[test]
+    fn grades_simple_prose_lower() {
+        let simple = Readability::measure("Stop the job. Wait a bit. Start it.");
+        let dense = Readability::measure(
+            "Administrators should immediately reconfigure the authentication \
+             infrastructure, verifying that every intermediate certificate \
+             authority remains operational throughout the transition.",
+        );
+        assert!(simple.grade_level() < 2.0);
+        assert!(dense.grade_level() > 16.0);
+        assert_eq!(Readability::default().grade_level(), 0.0);
+    }
+}
@@ -0,0 +1,202 @@
+//! Running verification commands on another machine over SSH.
+//!
+//! Some runbooks can only be checked from a particular host. A
+//! `<!-- pave:host staging-bastion -->` marker before a code block runs it
+//! there, with the name looked up in `[verify.hosts]`:
+//!
+//! ```toml
+//! [verify.hosts]
+//! staging-bastion = "deploy@bastion.staging.example.com"
+//! ```
+//!
+//! The command runs as `ssh -T <destination> sh -s` (or `[verify] ssh_cmd`),
+//! so output and exit codes come back as if it had run locally. The remote
+//! shell reads a short script from stdin that exports the block's
+//! environment variables, changes directory, and then `exec`s the command,
+//! with any input it reads from stdin as a here-document. Values, secrets
+//! included, never appear on a command line where `ps` on either machine
+//! would show them. Variable names must be valid shell identifiers. A
+//! block's `pave:working_dir` is used as written, relative to the remote
+//! login directory. Other blocks still run locally.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::verify_export::quote;
+
+/// SSH command used when `[verify] ssh_cmd` isn't set.
+pub const DEFAULT_SSH_CMD: &str = "ssh";
+
+/// Names the remote shell accepts after `export`.
+static SHELL_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+/// Wrap `inner`, which reads `input` from stdin, to run on `destination`.
+///
+/// `ssh_cmd` is split on whitespace, so it can carry extra flags such as
+/// `-i key`. Returns the SSH command and the script to write to its stdin,
+/// or an error if an environment variable name can't be exported.
+pub fn wrap(
+    inner: &Command,
+    ssh_cmd: &str,
+    destination: &str,
+    working_dir: Option<&Path>,
+    input: Option<&str>,
+) -> io::Result<(Command, String)> {
+    let script = remote_script(inner, working_dir, input)?;
+    let mut parts = ssh_cmd.split_whitespace();
+    let mut cmd = Command::new(parts.next().unwrap_or(DEFAULT_SSH_CMD));
+    cmd.args(parts);
+    cmd.arg("-T").arg(destination).args(["sh", "-s"]);
+    Ok((cmd, script))
+}
+
+/// The script the remote shell reads from stdin.
+fn remote_script(
+    inner: &Command,
+    working_dir: Option<&Path>,
+    input: Option<&str>,
+) -> io::Result<String> {
+    let mut script = String::new();
+    for (key, value) in inner.get_envs() {
+        let Some(value) = value else {
+            continue;
+        };
+        let key = key.to_string_lossy();
+        if !SHELL_NAME.is_match(&key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "environment variable '{}' is not a valid shell name for the remote host",
+                    key
+                ),
+            ));
+        }
+        script.push_str(&format!(
+            "export {}={}\n",
+            key,
+            quote(&value.to_string_lossy())
+        ));
+    }
+    if let Some(dir) = working_dir {
+        script.push_str(&format!("cd {} || exit 1\n", quote(&dir.to_string_lossy())));
+    }
+    let mut words = vec![quote(&inner.get_program().to_string_lossy())];
+    words.extend(inner.get_args().map(|arg| quote(&arg.to_string_lossy())));
+    let command = words.join(" ");
+    match input {
+        Some(input) => {
+            // A delimiter no line of the input matches
+            let mut delimiter = "PAVE_INPUT".to_string();
+            while input.lines().any(|line| line == delimiter) {
+                delimiter.push('_');
+            }
+            let newline = if input.ends_with('\n') { "" } else { "\n" };
+            script.push_str(&format!(
+                "exec {} <<'{}'\n{}{}{}\n",
+                command, delimiter, input, newline, delimiter
+            ));
+        }
+        None => script.push_str(&format!("exec {} </dev/null\n", command)),
+    }
+    Ok(script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(cmd: &Command) -> Vec<String> {
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn wraps_command_in_ssh() {
+        let mut inner = Command::new("sh");
+        inner
+            .args(["-c", "systemctl status api"])
+            .env("API_TOKEN", "s3cr3t value");
+
+        let (cmd, stdin) = wrap(
+            &inner,
+            "ssh -i ~/.ssh/staging",
+            "deploy@bastion",
+            Some(Path::new("/srv/api")),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            argv(&cmd),
+            [
+                "ssh",
+                "-i",
+                "~/.ssh/staging",
+                "-T",
+                "deploy@bastion",
+                "sh",
+                "-s"
+            ]
+        );
+        // Secrets go over stdin, never on the command line
+        assert!(cmd.get_envs().next().is_none());
+        assert_eq!(
+            stdin,
+            "export API_TOKEN='s3cr3t value'\ncd /srv/api || exit 1\nexec sh -c 'systemctl status api' </dev/null\n"
+        );
+
+        let (_, stdin) = wrap(
+            &Command::new("python3"),
+            "ssh",
+            "bastion",
+            None,
+            Some("print(1)\n"),
+        )
+        .unwrap();
+        assert_eq!(stdin, "exec python3 <<'PAVE_INPUT'\nprint(1)\nPAVE_INPUT\n");
+    }
+
+    #[test]
+    fn rejects_env_names_the_shell_cannot_export() {
+        let mut inner = Command::new("true");
+        inner.env("API-TOKEN;id", "x");
+        let err = wrap(&inner, "ssh", "bastion", None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable 'API-TOKEN;id' is not a valid shell name for the remote host"
+        );
+    }
+
+    #[test]
+    fn remote_shell_runs_the_command_with_its_env_and_input() {
+        let mut inner = Command::new("sh");
+        inner
+            .args(["-c", "echo \"$TOKEN\"; cat"])
+            .env("TOKEN", "a b");
+        // Stand in for ssh with a script that runs the remote command locally
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fake_ssh = temp_dir.path().join("ssh");
+        std::fs::write(&fake_ssh, "shift 2\nexec \"$@\"\n").unwrap();
+        let (mut cmd, stdin) = wrap(
+            &inner,
+            &format!("sh {}", fake_ssh.display()),
+            "bastion",
+            None,
+            Some("from stdin\nPAVE_INPUT"),
+        )
+        .unwrap();
+        let output = crate::verification::spawn_with_input(&mut cmd, Some(&stdin))
+            .and_then(|child| child.wait_with_output())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "a b\nfrom stdin\nPAVE_INPUT\n"
+        );
+    }
+}
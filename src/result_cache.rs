@@ -0,0 +1,275 @@
+//! Per-document result cache for `pave check` and `pave lint`.
+//!
+//! Re-validating an unchanged document gives the same issues, so each
+//! command stores the issues it found per document under `.pave/cache/`,
+//! keyed by a hash of the document's contents and of the files its links
+//! and images point at: whether each exists and when it was last modified.
+//! A later run only re-checks documents whose contents or link targets
+//! changed, so deleting `../img.png` re-checks the documents showing it.
+//!
+//! Everything else a result depends on goes into the cache's fingerprint:
+//! the pave version, the effective configuration, the set of documents
+//! (links and includes point at them), and whatever the command adds, such
+//! as the day for review dates. A different fingerprint discards the whole
+//! cache. Other files outside the docs, such as code matched by `## Paths`,
+//! are not tracked: `pave lint` re-runs the rules that read source files on
+//! every run, and `--no-cache` re-checks everything.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::paths::absolutize;
+use crate::verify_cache::hash_bytes;
+
+/// `pave check` cache location, relative to the config directory.
+pub const CHECK_CACHE_PATH: &str = ".pave/cache/check.json";
+
+/// `pave lint` cache location, relative to the config directory.
+pub const LINT_CACHE_PATH: &str = ".pave/cache/lint.json";
+
+/// Bump when the cache layout changes so old caches are ignored.
+const CACHE_VERSION: u32 = 2;
+
+/// Target of a markdown link or image.
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\]\(\s*<?([^)\s>]+)").unwrap());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry<T> {
+    /// Hash of the document's contents and link targets when it was
+    /// checked.
+    hash: String,
+    /// Issues found in the document.
+    issues: Vec<T>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+struct CacheFile<T> {
+    version: u32,
+    fingerprint: String,
+    entries: BTreeMap<String, Entry<T>>,
+}
+
+/// Issues per document, loaded from disk.
+pub struct ResultCache<T> {
+    path: PathBuf,
+    config_dir: PathBuf,
+    fingerprint: String,
+    entries: BTreeMap<String, Entry<T>>,
+    /// Whether any entry was added or replaced in this run.
+    changed: bool,
+    /// Number of documents served from the cache in this run.
+    hits: usize,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> ResultCache<T> {
+    /// Load the cache at `cache_path` under `config_dir`.
+    ///
+    /// Without `lookup`, or when the fingerprint changed, the cache starts
+    /// empty; results are still stored for the next run. A missing or
+    /// corrupt cache file is not an error.
+    pub fn load(config_dir: &Path, cache_path: &str, fingerprint: String, lookup: bool) -> Self {
+        let config_dir = absolutize(config_dir, &cwd());
+        let path = config_dir.join(cache_path);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .filter(|_| lookup)
+            .and_then(|content| serde_json::from_str::<CacheFile<T>>(&content).ok())
+            .filter(|file| file.version == CACHE_VERSION && file.fingerprint == fingerprint)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            config_dir,
+            fingerprint,
+            entries,
+            changed: !lookup,
+            hits: 0,
+        }
+    }
+
+    /// Issues cached for a document with `content`, if neither it nor its
+    /// link targets changed.
+    pub fn get(&mut self, file: &Path, content: &str) -> Option<Vec<T>> {
+        let entry = self.entries.get(&self.key(file))?;
+        if entry.hash != self.hash(file, content) {
+            return None;
+        }
+        self.hits += 1;
+        Some(entry.issues.clone())
+    }
+
+    /// Store the issues found in a document with `content`.
+    pub fn insert(&mut self, file: &Path, content: &str, issues: Vec<T>) {
+        let entry = Entry {
+            hash: self.hash(file, content),
+            issues,
+        };
+        self.entries.insert(self.key(file), entry);
+        self.changed = true;
+    }
+
+    /// Number of documents served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Write the cache if anything changed, dropping deleted documents.
+    pub fn save(&mut self) -> Result<()> {
+        let config_dir = &self.config_dir;
+        let before = self.entries.len();
+        self.entries.retain(|key, _| config_dir.join(key).is_file());
+        if !self.changed && self.entries.len() == before {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            fingerprint: self.fingerprint.clone(),
+            entries: std::mem::take(&mut self.entries),
+        };
+        let json = serde_json::to_string(&file).context("Failed to serialize cache")?;
+        self.entries = file.entries;
+        self.changed = false;
+        std::fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write cache: {}", self.path.display()))
+    }
+
+    /// Hash of `content` and the state of the local files it links to.
+    /// Targets starting with `/` are relative to the config directory.
+    fn hash(&self, file: &Path, content: &str) -> String {
+        let base_dir = absolutize(file, &cwd())
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let mut bytes = content.as_bytes().to_vec();
+        for cap in LINK.captures_iter(content) {
+            let target = cap[1].split('#').next().unwrap_or_default();
+            if target.is_empty() || target.contains("://") || target.starts_with("mailto:") {
+                continue;
+            }
+            let resolved = match target.strip_prefix('/') {
+                Some(rest) => self.config_dir.join(rest),
+                None => base_dir.join(target),
+            };
+            let modified = std::fs::metadata(&resolved)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok());
+            bytes.push(0);
+            bytes.extend_from_slice(target.as_bytes());
+            bytes.extend_from_slice(format!("={:?}", modified).as_bytes());
+        }
+        hash_bytes(&bytes)
+    }
+
+    /// Cache key: the document's path relative to the config directory.
+    fn key(&self, file: &Path) -> String {
+        let file = absolutize(file, &cwd());
+        file.strip_prefix(&self.config_dir)
+            .unwrap_or(&file)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn cwd() -> PathBuf {
+    std::env::current_dir().unwrap_or_default()
+}
+
+/// Fingerprint of a run: the pave version, then a hash of `parts` and of
+/// the documents' paths.
+pub fn fingerprint(parts: &[&[u8]], files: &[PathBuf]) -> String {
+    let mut bytes = Vec::new();
+    for part in parts {
+        bytes.extend_from_slice(part);
+        bytes.push(0);
+    }
+    let mut files: Vec<_> = files.iter().map(|file| file.to_string_lossy()).collect();
+    files.sort();
+    for file in files {
+        bytes.extend_from_slice(file.as_bytes());
+        bytes.push(0);
+    }
+    format!("{}:{}", env!("CARGO_PKG_VERSION"), hash_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn serves_unchanged_documents_from_the_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("docs/a.md");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        fs::write(&doc, "# A\n").unwrap();
+        let files = vec![doc.clone()];
+        let print = fingerprint(&[b"max_lines = 300"], &files);
+        let load = |print: &str, lookup| {
+            ResultCache::<String>::load(
+                temp_dir.path(),
+                CHECK_CACHE_PATH,
+                print.to_string(),
+                lookup,
+            )
+        };
+
+        let mut cache = load(&print, true);
+        assert_eq!(cache.get(&doc, "# A\n"), None);
+        cache.insert(&doc, "# A\n", vec!["missing section".to_string()]);
+        cache.save().unwrap();
+
+        let mut cache = load(&print, true);
+        assert_eq!(
+            cache.get(&doc, "# A\n"),
+            Some(vec!["missing section".to_string()])
+        );
+        assert_eq!(cache.get(&doc, "# A\n\nMore.\n"), None);
+        assert_eq!(cache.hits(), 1);
+
+        // Another config, another document set, or --no-cache starts over
+        let other = fingerprint(&[b"max_lines = 200"], &files);
+        assert_eq!(load(&other, true).get(&doc, "# A\n"), None);
+        let more = fingerprint(
+            &[b"max_lines = 300"],
+            &[doc.clone(), doc.with_file_name("b.md")],
+        );
+        assert_ne!(more, print);
+        assert_eq!(load(&print, false).get(&doc, "# A\n"), None);
+    }
+
+    #[test]
+    fn rechecks_documents_whose_link_targets_changed() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("docs/a.md");
+        let image = temp_dir.path().join("img.png");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        fs::write(&image, "png").unwrap();
+        let content = "# A\n\n![Diagram](../img.png)\n";
+        fs::write(&doc, content).unwrap();
+        let print = fingerprint(&[], std::slice::from_ref(&doc));
+        let load =
+            || ResultCache::<String>::load(temp_dir.path(), LINT_CACHE_PATH, print.clone(), true);
+
+        let mut cache = load();
+        cache.insert(&doc, content, Vec::new());
+        cache.save().unwrap();
+        assert_eq!(load().get(&doc, content), Some(Vec::new()));
+
+        fs::remove_file(&image).unwrap();
+        assert_eq!(load().get(&doc, content), None);
+    }
+}
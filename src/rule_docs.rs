@@ -0,0 +1,286 @@
+//! Registry of documented rules.
+//!
+//! Every issue `pave check` and `pave lint` emit names the rule that raised
+//! it. This registry maps those names to an entry on the rules reference
+//! page, so that machine output can carry a `docs_url` that CI annotations
+//! and editors can link to.
+
+/// Rules reference page used when `rules.docs_url` isn't set.
+pub const DEFAULT_DOCS_URL: &str = "https://tessro.github.io/pave/docs/rules/";
+
+/// Metadata for a documented rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleDoc {
+    /// Rule id, also the anchor of its entry on the rules page.
+    pub id: &'static str,
+    /// Command that reports the rule: `check` or `lint`.
+    pub command: &'static str,
+    /// One-line summary of what the rule enforces.
+    pub summary: &'static str,
+}
+
+/// Every documented rule.
+///
+/// Rules parameterized by a section or limit, such as
+/// `require-section-verification` or `max-lines-300`, are listed once under
+/// their common prefix.
+pub const RULES: &[RuleDoc] = &[
+    RuleDoc {
+        id: "max-lines",
+        command: "check",
+        summary: "Documents stay under `rules.max_lines` lines",
+    },
+    RuleDoc {
+        id: "require-section",
+        command: "check",
+        summary: "Required sections such as Verification and Examples are present",
+    },
+    RuleDoc {
+        id: "require-one-of",
+        command: "check",
+        summary: "At least one of a set of sections is present",
+    },
+    RuleDoc {
+        id: "require-code-block-in",
+        command: "check",
+        summary: "A section contains a code block",
+    },
+    RuleDoc {
+        id: "require-command-in",
+        command: "check",
+        summary: "A section contains a runnable command",
+    },
+    RuleDoc {
+        id: "require-valid-adr-status",
+        command: "check",
+        summary: "ADRs have a recognized status",
+    },
+    RuleDoc {
+        id: "validate-paths",
+        command: "check",
+        summary: "Paths entries are valid, relative glob patterns",
+    },
+    RuleDoc {
+        id: "require-verification-command",
+        command: "check",
+        summary: "The Verification section has an executable command",
+    },
+    RuleDoc {
+        id: "require-step-verification",
+        command: "check",
+        summary: "Every command in Steps is also run in Verification",
+    },
+    RuleDoc {
+        id: "require-section-metadata",
+        command: "check",
+        summary: "Sections declare the `pave:section` keys configured for them",
+    },
+    RuleDoc {
+        id: "valid-state",
+        command: "check",
+        summary: "`pave.state` is draft, published, or deprecated",
+    },
+    RuleDoc {
+        id: "require-superseded-by",
+        command: "check",
+        summary: "Deprecated documents name an existing replacement",
+    },
+    RuleDoc {
+        id: "valid-metadata",
+        command: "check",
+        summary: "Owner, tags, and review dates in frontmatter are well-formed",
+    },
+    RuleDoc {
+        id: "valid-frontmatter",
+        command: "check",
+        summary: "Frontmatter parses and `pave` settings have known keys and valid values",
+    },
+    RuleDoc {
+        id: "strict-parse",
+        command: "check",
+        summary: "Nothing is silently skipped by the parser (`--strict` or `rules.strict_parse`)",
+    },
+    RuleDoc {
+        id: "require-owner",
+        command: "check",
+        summary: "Documents name an owner (`rules.require_owner`)",
+    },
+    RuleDoc {
+        id: "review-overdue",
+        command: "check",
+        summary: "Documents are reviewed within their review interval",
+    },
+    RuleDoc {
+        id: "state-transition",
+        command: "check",
+        summary: "A document's state only moves forward",
+    },
+    RuleDoc {
+        id: "conflict-markers",
+        command: "check",
+        summary: "No merge conflict markers are left outside code blocks",
+    },
+    RuleDoc {
+        id: "duplicate-titles",
+        command: "check",
+        summary: "No two documents share an H1 title",
+    },
+    RuleDoc {
+        id: "include",
+        command: "check",
+        summary: "Include markers name files that exist within the docs root",
+    },
+    RuleDoc {
+        id: "undefined-vars",
+        command: "check",
+        summary: "Variable placeholders name variables defined in `[vars]`",
+    },
+    RuleDoc {
+        id: "missing-translation",
+        command: "check",
+        summary: "Required docs have a counterpart in every translated locale",
+    },
+    RuleDoc {
+        id: "broken-internal-links",
+        command: "lint",
+        summary: "Links to other docs point at files that exist",
+    },
+    RuleDoc {
+        id: "external-links",
+        command: "lint",
+        summary: "External links respond successfully (`--external-links`)",
+    },
+    RuleDoc {
+        id: "dead-anchors",
+        command: "lint",
+        summary: "Links to sections point at headings that exist",
+    },
+    RuleDoc {
+        id: "stale-code-refs",
+        command: "lint",
+        summary: "Referenced code files exist",
+    },
+    RuleDoc {
+        id: "inconsistent-headings",
+        command: "lint",
+        summary: "Headings use one style throughout a document",
+    },
+    RuleDoc {
+        id: "missing-alt-text",
+        command: "lint",
+        summary: "Images have alt text",
+    },
+    RuleDoc {
+        id: "long-paragraphs",
+        command: "lint",
+        summary: "Paragraphs stay under the word limit",
+    },
+    RuleDoc {
+        id: "duplicate-headings",
+        command: "lint",
+        summary: "No heading text repeats at the same level",
+    },
+    RuleDoc {
+        id: "trailing-whitespace",
+        command: "lint",
+        summary: "Lines have no trailing spaces",
+    },
+    RuleDoc {
+        id: "admonition-style",
+        command: "lint",
+        summary: "Callouts use one syntax across the docs",
+    },
+    RuleDoc {
+        id: "terminology",
+        command: "lint",
+        summary: "Prose uses the terms preferred in [lint.terminology]",
+    },
+    RuleDoc {
+        id: "spelling",
+        command: "lint",
+        summary: "Prose words are in the spelling dictionaries",
+    },
+    RuleDoc {
+        id: "readability",
+        command: "lint",
+        summary: "Sections stay within [lint.readability] sentence length and grade level",
+    },
+    RuleDoc {
+        id: "heading-hierarchy",
+        command: "lint",
+        summary: "Headings nest under one H1 without skipping levels",
+    },
+    RuleDoc {
+        id: "interface-drift",
+        command: "lint",
+        summary: "Interface flags and functions match the `pave.interface` source",
+    },
+    RuleDoc {
+        id: "mermaid",
+        command: "lint",
+        summary: "Mermaid diagrams parse",
+    },
+];
+
+/// Find the registry entry for a rule name as it appears in an issue.
+///
+/// Parameterized names match the longest registered prefix.
+pub fn lookup(rule: &str) -> Option<&'static RuleDoc> {
+    RULES
+        .iter()
+        .filter(|doc| {
+            rule == doc.id
+                || rule
+                    .strip_prefix(doc.id)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|doc| doc.id.len())
+}
+
+/// Link to a rule's entry on the rules page at `base`, or None if the rule
+/// isn't documented.
+pub fn docs_url(base: Option<&str>, rule: &str) -> Option<String> {
+    let doc = lookup(rule)?;
+    Some(format!("{}#{}", base.unwrap_or(DEFAULT_DOCS_URL), doc.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameterized_rules_match_their_prefix() {
+        assert_eq!(lookup("max-lines-300").unwrap().id, "max-lines");
+        assert_eq!(
+            lookup("require-section-verification").unwrap().id,
+            "require-section"
+        );
+        assert_eq!(
+            lookup("require-superseded-by").unwrap().id,
+            "require-superseded-by"
+        );
+        assert!(lookup("max-linesx").is_none());
+        assert!(lookup("unknown").is_none());
+    }
+
+    #[test]
+    fn urls_use_the_configured_base() {
+        assert_eq!(
+            docs_url(None, "dead-anchors").unwrap(),
+            "https://tessro.github.io/pave/docs/rules/#dead-anchors"
+        );
+        assert_eq!(
+            docs_url(Some("https://docs.example.com/pave/rules"), "max-lines-80").unwrap(),
+            "https://docs.example.com/pave/rules#max-lines"
+        );
+        assert!(docs_url(None, "custom").is_none());
+    }
+
+    #[test]
+    fn rule_ids_are_unique() {
+        let mut ids: Vec<&str> = RULES.iter().map(|doc| doc.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), RULES.len());
+    }
+}
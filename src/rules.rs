@@ -4,18 +4,30 @@
 //! against configurable rules from `.pave.toml`.
 
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use chrono::NaiveDate;
 use glob::Pattern;
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
 
-use crate::config::RulesSection;
-use crate::parser::ParsedDoc;
+use crate::config::{RuleSeverity, RulesSection, VerificationCoverage};
+use crate::config_validate::{properties, resolve};
+use crate::parser::{
+    BlockPhase, CodeBlockTracker, DocState, ParsedDoc, PaveFrontmatter, Section, frontmatter_len,
+    parse_pave_frontmatter,
+};
 
 /// Document type for type-specific validation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum DocType {
     Component,
     Runbook,
     Adr,
+    Postmortem,
+    Api,
     Other,
 }
 
@@ -42,6 +54,12 @@ pub enum Rule {
         /// Whether to warn when patterns match no files.
         warn_empty: bool,
     },
+    /// Require the Verification section to contain at least one executable command.
+    RequireVerificationCommand,
+    /// Require every Steps entry with a command to have that command in Verification.
+    RequireStepVerification,
+    /// Require a section to declare a metadata key with a `pave:section` annotation.
+    RequireSectionMetadata { section: String, key: String },
 }
 
 impl Rule {
@@ -62,6 +80,57 @@ impl Rule {
             }
             Rule::RequireValidAdrStatus => "require-valid-adr-status".to_string(),
             Rule::ValidatePaths { .. } => "validate-paths".to_string(),
+            Rule::RequireVerificationCommand => "require-verification-command".to_string(),
+            Rule::RequireStepVerification => "require-step-verification".to_string(),
+            Rule::RequireSectionMetadata { section, key } => {
+                format!(
+                    "require-section-metadata-{}-{}",
+                    section.to_lowercase(),
+                    key
+                )
+            }
+        }
+    }
+
+    /// Returns a sentence describing what this rule requires of a document.
+    pub fn description(&self) -> String {
+        match self {
+            Rule::RequireSection { name } => format!("Must include a '## {}' section", name),
+            Rule::MaxLines { limit } => format!("Maximum {} lines per document", limit),
+            Rule::RequireCodeBlock { in_section } => {
+                format!("The '{}' section must contain a code block", in_section)
+            }
+            Rule::RequireCommand { in_section } => {
+                format!(
+                    "The '{}' section must contain a runnable command",
+                    in_section
+                )
+            }
+            Rule::RequireOneOf { sections } => {
+                let names: Vec<_> = sections.iter().map(|s| format!("'## {}'", s)).collect();
+                format!("Must include at least one of: {}", names.join(", "))
+            }
+            Rule::RequireValidAdrStatus => format!(
+                "The 'Status' section must be one of: {}",
+                VALID_ADR_STATUSES.join(", ")
+            ),
+            Rule::ValidatePaths { warn_empty, .. } => {
+                if *warn_empty {
+                    "Paths patterns must be valid globs that match existing files".to_string()
+                } else {
+                    "Paths patterns must be valid globs".to_string()
+                }
+            }
+            Rule::RequireVerificationCommand => {
+                "The 'Verification' section must contain at least one runnable command".to_string()
+            }
+            Rule::RequireStepVerification => {
+                "Every command in 'Steps' must also appear in 'Verification'".to_string()
+            }
+            Rule::RequireSectionMetadata { section, key } => format!(
+                "The '{}' section must declare '{}' in a pave:section annotation",
+                section, key
+            ),
         }
     }
 }
@@ -123,6 +192,37 @@ impl ValidationResult {
     pub fn has_warnings(&self) -> bool {
         !self.warnings.is_empty()
     }
+
+    /// Reports a configurable rule's violation at `severity`.
+    ///
+    /// Warnings have no suggestion, so it's only kept for errors.
+    pub fn push(&mut self, severity: RuleSeverity, rule: &str, message: String, suggestion: &str) {
+        match severity {
+            RuleSeverity::Off => {}
+            RuleSeverity::Warning => self.warnings.push(ValidationWarning {
+                rule: rule.to_string(),
+                message,
+                line: Some(1),
+            }),
+            RuleSeverity::Error => self.errors.push(ValidationError {
+                rule: rule.to_string(),
+                message,
+                line: Some(1),
+                suggestion: Some(suggestion.to_string()),
+            }),
+        }
+    }
+
+    /// Turns every error into a warning, as for draft documents.
+    pub fn downgrade_errors(&mut self) {
+        for error in self.errors.drain(..) {
+            self.warnings.push(ValidationWarning {
+                rule: error.rule,
+                message: error.message,
+                line: error.line,
+            });
+        }
+    }
 }
 
 /// The rules engine that validates documents against a set of rules.
@@ -222,7 +322,14 @@ impl RulesEngine {
         let mut result = ValidationResult::new(&doc.path);
 
         for rule in &self.rules {
+            let start = Instant::now();
             self.apply_rule(rule, doc, &mut result);
+            trace!(
+                "{}: {} took {:?}",
+                doc.path.display(),
+                rule.name(),
+                start.elapsed()
+            );
         }
 
         result
@@ -384,6 +491,77 @@ impl RulesEngine {
                     }
                 }
             }
+            Rule::RequireVerificationCommand => {
+                let section = doc.get_section("Verification");
+                // Setup and teardown blocks don't verify anything themselves
+                let has_command = section.is_some_and(|s| {
+                    s.code_blocks
+                        .iter()
+                        .any(|block| block.is_executable && block.phase == BlockPhase::Verify)
+                });
+                if !has_command {
+                    result.errors.push(ValidationError {
+                        rule: rule.name(),
+                        message: "verification coverage requires at least one verification command"
+                            .to_string(),
+                        line: section.map(|s| s.start_line),
+                        suggestion: Some(
+                            "add a ```bash code block to the '## Verification' section".to_string(),
+                        ),
+                    });
+                }
+            }
+            Rule::RequireStepVerification => {
+                let Some(steps) = doc.get_section("Steps") else {
+                    return;
+                };
+                let verified: Vec<String> = doc
+                    .get_section("Verification")
+                    .map(|s| {
+                        s.code_blocks
+                            .iter()
+                            .filter(|block| block.is_executable)
+                            .flat_map(|block| command_lines(&block.content))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                for step in extract_steps(steps) {
+                    if step.commands.is_empty()
+                        || step.commands.iter().any(|cmd| verified.contains(cmd))
+                    {
+                        continue;
+                    }
+                    result.errors.push(ValidationError {
+                        rule: rule.name(),
+                        message: format!(
+                            "step {} has no corresponding verification command: {}",
+                            step.number, step.commands[0]
+                        ),
+                        line: Some(step.line),
+                        suggestion: Some(
+                            "add a command exercising this step to the '## Verification' section"
+                                .to_string(),
+                        ),
+                    });
+                }
+            }
+            Rule::RequireSectionMetadata { section, key } => {
+                if let Some(found) = doc.get_section(section)
+                    && !found.metadata.contains_key(key)
+                {
+                    result.errors.push(ValidationError {
+                        rule: rule.name(),
+                        message: format!("section '{}' does not declare '{}'", found.name, key),
+                        line: Some(found.start_line),
+                        suggestion: Some(format!(
+                            "add '<!-- pave:section {}=... -->' below the '## {}' heading",
+                            key, found.name
+                        )),
+                    });
+                }
+                // Note: If section doesn't exist, RequireSection rule will catch it
+            }
         }
     }
 
@@ -462,6 +640,353 @@ impl RulesEngine {
     }
 }
 
+/// Validates a document's `pave.state` and `pave.superseded_by` frontmatter.
+///
+/// Deprecated documents must name their replacement, and a relative
+/// `superseded_by` path must point at an existing file.
+pub fn validate_state(doc: &ParsedDoc, result: &mut ValidationResult) {
+    let Some(frontmatter) = &doc.frontmatter else {
+        return;
+    };
+
+    if let Some(state) = &frontmatter.state
+        && DocState::parse(state).is_none()
+    {
+        let states: Vec<_> = DocState::ALL.iter().map(|s| s.as_str()).collect();
+        result.errors.push(ValidationError {
+            rule: "valid-state".to_string(),
+            message: format!("Unknown document state '{}'", state),
+            line: Some(1),
+            suggestion: Some(format!("Use one of: {}", states.join(", "))),
+        });
+    }
+
+    let state = doc.state();
+    match (&frontmatter.superseded_by, state) {
+        (None, DocState::Deprecated) => result.errors.push(ValidationError {
+            rule: "require-superseded-by".to_string(),
+            message: "Deprecated document is missing 'superseded_by'".to_string(),
+            line: Some(1),
+            suggestion: Some(
+                "Add 'superseded_by: <path to the replacement doc>' under 'pave:' in the frontmatter"
+                    .to_string(),
+            ),
+        }),
+        (Some(target), DocState::Deprecated) => {
+            let file = target.split('#').next().unwrap_or_default();
+            let base = doc.path.parent().unwrap_or_else(|| Path::new(""));
+            if !target.contains("://") && !base.join(file).exists() {
+                result.errors.push(ValidationError {
+                    rule: "require-superseded-by".to_string(),
+                    message: format!("'superseded_by' target '{}' does not exist", target),
+                    line: Some(1),
+                    suggestion: Some(
+                        "Use a path relative to this document, e.g. ./auth-v2.md".to_string(),
+                    ),
+                });
+            }
+        }
+        (Some(_), _) => result.warnings.push(ValidationWarning {
+            rule: "require-superseded-by".to_string(),
+            message: format!(
+                "'superseded_by' is set but the document is {}, not deprecated",
+                state
+            ),
+            line: Some(1),
+        }),
+        (None, _) => {}
+    }
+}
+
+/// Validates a document's `pave.owner`, `pave.tags`, `pave.last_reviewed`,
+/// and `pave.review_interval_days` frontmatter values.
+pub fn validate_metadata(doc: &ParsedDoc, result: &mut ValidationResult) {
+    let Some(frontmatter) = &doc.frontmatter else {
+        return;
+    };
+    let mut invalid = |message: String, suggestion: &str| {
+        result.errors.push(ValidationError {
+            rule: "valid-metadata".to_string(),
+            message,
+            line: Some(1),
+            suggestion: Some(suggestion.to_string()),
+        })
+    };
+
+    if frontmatter
+        .owner
+        .as_deref()
+        .is_some_and(|owner| owner.trim().is_empty())
+    {
+        invalid(
+            "'owner' is empty".to_string(),
+            "Name the person or team that owns this document, or remove 'owner'",
+        );
+    }
+
+    let mut seen = Vec::new();
+    for tag in &frontmatter.tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            invalid(
+                "'tags' has an empty tag".to_string(),
+                "Remove the empty tag",
+            );
+        } else if seen.contains(&tag) {
+            invalid(
+                format!("Tag '{}' is listed more than once", tag),
+                "Remove the duplicate tag",
+            );
+        } else {
+            seen.push(tag);
+        }
+    }
+
+    if let Some(date) = &frontmatter.last_reviewed
+        && NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").is_err()
+    {
+        invalid(
+            format!("'last_reviewed' date '{}' is not valid", date),
+            "Use the YYYY-MM-DD format, e.g. 2025-06-30",
+        );
+    }
+
+    if let Some(days) = frontmatter.review_interval_days
+        && days <= 0
+    {
+        invalid(
+            format!("'review_interval_days' must be positive, got {}", days),
+            "Use the number of days between reviews, e.g. 90",
+        );
+    }
+}
+
+/// Keys accepted under `pave:` in place of a field, as `(alias, field)`.
+const FRONTMATTER_ALIASES: &[(&str, &str)] = &[("status", "state")];
+
+/// Validates a document's frontmatter block: the YAML parses, every key under
+/// `pave:` is known, `paths` is a list of strings, `working_dir` is relative,
+/// and each value has the type its setting expects.
+pub fn validate_frontmatter(content: &str, result: &mut ValidationResult) {
+    let lines: Vec<&str> = content.lines().collect();
+    let len = frontmatter_len(&lines);
+    if len == 0 {
+        return;
+    }
+    // Line n of the YAML is line n + 1 of the document
+    let yaml_lines = &lines[1..len - 1];
+    let yaml = yaml_lines.join("\n");
+    let mut errors = Vec::new();
+    let mut invalid = |line: usize, message: String, suggestion: String| {
+        errors.push(ValidationError {
+            rule: "valid-frontmatter".to_string(),
+            message,
+            line: Some(line),
+            suggestion: Some(suggestion),
+        })
+    };
+    let error_line =
+        |err: &serde_yaml::Error| err.location().map_or(1, |location| location.line() + 1);
+
+    let value: serde_yaml::Value = match serde_yaml::from_str(&yaml) {
+        Ok(value) => value,
+        Err(err) => {
+            invalid(
+                error_line(&err),
+                format!("Frontmatter is not valid YAML: {}", err),
+                "Fix the YAML between the '---' lines".to_string(),
+            );
+            result.errors.extend(errors);
+            return;
+        }
+    };
+    let Some(pave) = value.get("pave").filter(|pave| !pave.is_null()) else {
+        return;
+    };
+    let Some(settings) = pave.as_mapping() else {
+        invalid(
+            frontmatter_key_line(yaml_lines, &["pave"]),
+            "'pave' is not a mapping of settings".to_string(),
+            "Indent the settings under 'pave:', e.g. '  paths: [src/]'".to_string(),
+        );
+        result.errors.extend(errors);
+        return;
+    };
+
+    let schema = serde_json::to_value(schema_for!(PaveFrontmatter)).unwrap_or_default();
+    let mut unknown = Vec::new();
+    unknown_frontmatter_keys(settings, &schema, &schema, "pave", &mut unknown);
+    for (path, hint) in unknown {
+        let keys: Vec<&str> = path.split('.').collect();
+        invalid(
+            frontmatter_key_line(yaml_lines, &keys),
+            format!("Unknown frontmatter key '{}'", path),
+            hint,
+        );
+    }
+
+    let mut mistyped = false;
+    match settings.get("paths") {
+        Some(serde_yaml::Value::Sequence(entries)) => {
+            for entry in entries.iter().filter(|entry| !entry.is_string()) {
+                mistyped = true;
+                invalid(
+                    frontmatter_key_line(yaml_lines, &["pave", "paths"]),
+                    format!(
+                        "'paths' entry {} is not a string",
+                        serde_yaml::to_string(entry).unwrap_or_default().trim()
+                    ),
+                    "Quote the entry, e.g. - \"src/**\"".to_string(),
+                );
+            }
+        }
+        Some(serde_yaml::Value::Null) | None => {}
+        Some(_) => {
+            mistyped = true;
+            invalid(
+                frontmatter_key_line(yaml_lines, &["pave", "paths"]),
+                "'paths' is not a list".to_string(),
+                "List each path on its own '- ' line under 'paths:'".to_string(),
+            );
+        }
+    }
+
+    if let Some(dir) = settings.get("working_dir").and_then(|dir| dir.as_str())
+        && Path::new(dir).is_absolute()
+    {
+        invalid(
+            frontmatter_key_line(yaml_lines, &["pave", "working_dir"]),
+            format!("'working_dir' is an absolute path: {}", dir),
+            "Use a path relative to the project root so it works on every machine".to_string(),
+        );
+    }
+
+    if !mistyped && let Err(err) = parse_pave_frontmatter(&yaml) {
+        invalid(
+            error_line(&err),
+            format!("Invalid frontmatter value: {}", err),
+            "Give the setting a value of the type it expects".to_string(),
+        );
+    }
+    result.errors.extend(errors);
+}
+
+/// Collect the keys of `mapping` that `schema` doesn't define, as dotted
+/// paths with a hint for each.
+fn unknown_frontmatter_keys(
+    mapping: &serde_yaml::Mapping,
+    schema: &serde_json::Value,
+    root: &serde_json::Value,
+    prefix: &str,
+    unknown: &mut Vec<(String, String)>,
+) {
+    // Maps such as a service's `env` accept any key
+    let Some(properties) = properties(schema, root) else {
+        return;
+    };
+    for (key, value) in mapping {
+        let Some(name) = key.as_str() else {
+            continue;
+        };
+        let path = format!("{}.{}", prefix, name);
+        let field = FRONTMATTER_ALIASES
+            .iter()
+            .find(|(alias, _)| prefix == "pave" && *alias == name)
+            .map_or(name, |(_, field)| *field);
+        let Some(field) = properties.get(field) else {
+            let closest = properties
+                .keys()
+                .map(|known| (strsim::jaro_winkler(name, known), known))
+                .filter(|(score, _)| *score >= 0.8)
+                .max_by(|a, b| a.0.total_cmp(&b.0));
+            let hint = match closest {
+                Some((_, known)) => format!("Did you mean '{}.{}'?", prefix, known),
+                None => {
+                    let mut keys: Vec<&str> = properties.keys().map(String::as_str).collect();
+                    keys.sort_unstable();
+                    format!("Remove it; valid keys are: {}", keys.join(", "))
+                }
+            };
+            unknown.push((path, hint));
+            continue;
+        };
+        match value {
+            serde_yaml::Value::Mapping(nested) => {
+                unknown_frontmatter_keys(nested, field, root, &path, unknown);
+            }
+            serde_yaml::Value::Sequence(items) => {
+                let Some(item_schema) = resolve(field, root).get("items") else {
+                    continue;
+                };
+                for item in items.iter().filter_map(serde_yaml::Value::as_mapping) {
+                    unknown_frontmatter_keys(item, item_schema, root, &path, unknown);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Document line of the key at the end of `keys`, found by looking for each
+/// key in turn below the previous one. Falls back to the last key found.
+fn frontmatter_key_line(yaml_lines: &[&str], keys: &[&str]) -> usize {
+    let mut found = 0;
+    for key in keys {
+        let prefix = format!("{}:", key);
+        let Some(offset) = yaml_lines[found..].iter().position(|line| {
+            line.trim_start()
+                .trim_start_matches("- ")
+                .starts_with(&prefix)
+        }) else {
+            break;
+        };
+        found += offset;
+    }
+    found + 2
+}
+
+/// Flags a document without a `pave.owner` at the severity set in
+/// `rules.require_owner`.
+pub fn check_owner(doc: &ParsedDoc, rules: &RulesSection, result: &mut ValidationResult) {
+    let has_owner = doc
+        .frontmatter
+        .as_ref()
+        .and_then(|fm| fm.owner.as_deref())
+        .is_some_and(|owner| !owner.trim().is_empty());
+    if !has_owner {
+        result.push(
+            rules.require_owner,
+            "require-owner",
+            "Document has no owner".to_string(),
+            "Add 'owner: <person or team>' under 'pave:' in the frontmatter",
+        );
+    }
+}
+
+/// Flags a document past its review date as of `today`, at the severity set
+/// in `rules.review_overdue`.
+pub fn check_review_date(
+    doc: &ParsedDoc,
+    rules: &RulesSection,
+    today: NaiveDate,
+    result: &mut ValidationResult,
+) {
+    if let Some(due) = doc.review_due(rules.review_interval_days)
+        && due < today
+    {
+        result.push(
+            rules.review_overdue,
+            "review-overdue",
+            format!(
+                "Review overdue since {} ({} days)",
+                due,
+                (today - due).num_days()
+            ),
+            "Review the document and update 'last_reviewed' under 'pave:' in the frontmatter",
+        );
+    }
+}
+
 /// Detects the document type from path and content.
 pub fn detect_doc_type(path: &Path, content: &str) -> DocType {
     let path_str = path.to_string_lossy().to_lowercase();
@@ -473,13 +998,33 @@ pub fn detect_doc_type(path: &Path, content: &str) -> DocType {
     if path_str.contains("runbook") {
         return DocType::Runbook;
     }
+    if is_postmortem_path(&path_str) {
+        return DocType::Postmortem;
+    }
     if path_str.contains("adr") || path_str.contains("decision") {
         return DocType::Adr;
     }
+    if is_api_path(path) {
+        return DocType::Api;
+    }
 
     // Check content patterns
     let content_lower = content.to_lowercase();
 
+    // Postmortems walk through a timeline of the incident and its fallout
+    if content_lower.contains("## timeline")
+        && (content_lower.contains("## impact")
+            || content_lower.contains("## action items")
+            || content_lower.contains("## root cause"))
+    {
+        return DocType::Postmortem;
+    }
+
+    // API references list their endpoints
+    if content_lower.contains("## endpoints") {
+        return DocType::Api;
+    }
+
     // ADRs typically have a Status section
     if content_lower.contains("## status")
         && (content_lower.contains("accepted")
@@ -505,6 +1050,109 @@ pub fn detect_doc_type(path: &Path, content: &str) -> DocType {
     DocType::Other
 }
 
+/// Check if a lowercased path names a postmortem or incident review.
+pub(crate) fn is_postmortem_path(path_str: &str) -> bool {
+    path_str.contains("postmortem")
+        || path_str.contains("post-mortem")
+        || path_str.contains("incident")
+}
+
+/// Check if a path names an API reference: an `api` or `apis` directory, or
+/// a file such as `api.md` or `api-reference.md`.
+///
+/// Matches whole path components so names that merely contain "api", like
+/// `rapid.md` or `capital/`, are not API references.
+pub(crate) fn is_api_path(path: &Path) -> bool {
+    path.components().any(|component| {
+        let name = component.as_os_str().to_string_lossy().to_lowercase();
+        let stem = name.strip_suffix(".md").unwrap_or(&name);
+        stem == "api" || stem == "apis" || stem.starts_with("api-") || stem.starts_with("api_")
+    })
+}
+
+/// A numbered entry in a Steps section.
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    /// Position of the step within the section (1-indexed).
+    number: usize,
+    /// Line where the step starts (1-indexed).
+    line: usize,
+    /// Commands from the step's executable code blocks.
+    commands: Vec<String>,
+}
+
+/// Split a Steps section into entries.
+///
+/// A step starts at a top-level numbered list item (`1.`) or an H3 heading
+/// and owns every code block up to the next step.
+fn extract_steps(section: &Section) -> Vec<Step> {
+    let mut starts = Vec::new();
+    let mut tracker = CodeBlockTracker::new();
+
+    for (idx, line) in section.content.lines().enumerate() {
+        if tracker.process_line(line) || tracker.in_code_block() {
+            continue;
+        }
+        if is_step_start(line) {
+            // Content starts on the line after the heading
+            starts.push(section.start_line + 1 + idx);
+        }
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &line)| {
+            let end = starts.get(i + 1).copied().unwrap_or(usize::MAX);
+            let commands = section
+                .code_blocks
+                .iter()
+                .filter(|block| block.is_executable && block.start_line > line)
+                .filter(|block| block.start_line < end)
+                .flat_map(|block| command_lines(&block.content))
+                .collect();
+            Step {
+                number: i + 1,
+                line,
+                commands,
+            }
+        })
+        .collect()
+}
+
+/// Check if a line starts a step: `1. ...` at column 0 or `### ...`.
+fn is_step_start(line: &str) -> bool {
+    if line.starts_with("### ") {
+        return true;
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+/// Extract normalized command lines from a code block.
+///
+/// Skips blank and comment lines and strips `$ ` prompts so the same command
+/// compares equal wherever it appears.
+fn command_lines(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.strip_prefix("$ ").unwrap_or(line).trim().to_string())
+        .collect()
+}
+
+/// Returns the verification coverage required for a document type.
+fn verification_coverage(doc_type: DocType, config: &RulesSection) -> VerificationCoverage {
+    let coverage = &config.verification_coverage;
+    match doc_type {
+        DocType::Runbook => coverage.runbook,
+        DocType::Adr => coverage.adr,
+        DocType::Component => coverage.component,
+        DocType::Postmortem | DocType::Api | DocType::Other => VerificationCoverage::None,
+    }
+}
+
 /// Returns the type-specific rules for a given document type.
 pub fn get_type_specific_rules(doc_type: DocType, config: &RulesSection) -> Vec<Rule> {
     let mut rules = Vec::new();
@@ -544,9 +1192,51 @@ pub fn get_type_specific_rules(doc_type: DocType, config: &RulesSection) -> Vec<
                 sections: vec!["Interface".to_string(), "Configuration".to_string()],
             });
         }
+        DocType::Postmortem if config.type_specific.postmortems => {
+            // Postmortems require: Timeline, Impact, Action Items
+            rules.push(Rule::RequireSection {
+                name: "Timeline".to_string(),
+            });
+            rules.push(Rule::RequireSection {
+                name: "Impact".to_string(),
+            });
+            rules.push(Rule::RequireSection {
+                name: "Action Items".to_string(),
+            });
+        }
+        DocType::Api if config.type_specific.apis => {
+            // API references require: Endpoints, Authentication
+            rules.push(Rule::RequireSection {
+                name: "Endpoints".to_string(),
+            });
+            rules.push(Rule::RequireSection {
+                name: "Authentication".to_string(),
+            });
+        }
         _ => {}
     }
 
+    for required in &config.section_metadata {
+        if required.doc_type.is_some_and(|only| only != doc_type) {
+            continue;
+        }
+        for key in &required.require {
+            rules.push(Rule::RequireSectionMetadata {
+                section: required.section.clone(),
+                key: key.clone(),
+            });
+        }
+    }
+
+    match verification_coverage(doc_type, config) {
+        VerificationCoverage::None => {}
+        VerificationCoverage::AtLeastOne => rules.push(Rule::RequireVerificationCommand),
+        VerificationCoverage::AllSteps => {
+            rules.push(Rule::RequireVerificationCommand);
+            rules.push(Rule::RequireStepVerification);
+        }
+    }
+
     rules
 }
 
@@ -683,6 +1373,7 @@ Missing verification.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            ..Default::default()
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -723,6 +1414,7 @@ Missing verification.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            ..Default::default()
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -1014,6 +1706,38 @@ A test ADR.
         );
     }
 
+    #[test]
+    fn detect_doc_type_postmortem_and_api() {
+        for path in [
+            "docs/postmortems/db-outage.md",
+            "docs/incidents/2024-03-01.md",
+        ] {
+            assert_eq!(
+                detect_doc_type(Path::new(path), ""),
+                DocType::Postmortem,
+                "{path}"
+            );
+        }
+        for path in [
+            "docs/api/orders.md",
+            "docs/apis/orders.md",
+            "docs/api-reference.md",
+        ] {
+            assert_eq!(detect_doc_type(Path::new(path), ""), DocType::Api, "{path}");
+        }
+        // "api" inside a word is not an API reference
+        assert_eq!(
+            detect_doc_type(Path::new("docs/rapid-setup.md"), ""),
+            DocType::Other
+        );
+
+        let path = PathBuf::from("docs/misc/outage.md");
+        let postmortem = "## Impact\nCheckout failed.\n\n## Timeline\n14:02 alert fired";
+        assert_eq!(detect_doc_type(&path, postmortem), DocType::Postmortem);
+        let api = "## Authentication\nBearer tokens.\n\n## Endpoints\nGET /v1/items";
+        assert_eq!(detect_doc_type(&path, api), DocType::Api);
+    }
+
     #[test]
     fn get_type_specific_rules_runbook() {
         let config = RulesSection {
@@ -1021,6 +1745,8 @@ A test ADR.
                 runbooks: true,
                 adrs: false,
                 components: false,
+                postmortems: false,
+                apis: false,
             },
             ..Default::default()
         };
@@ -1047,6 +1773,8 @@ A test ADR.
                 runbooks: false,
                 adrs: true,
                 components: false,
+                postmortems: false,
+                apis: false,
             },
             ..Default::default()
         };
@@ -1082,6 +1810,8 @@ A test ADR.
                 runbooks: false,
                 adrs: false,
                 components: true,
+                postmortems: false,
+                apis: false,
             },
             ..Default::default()
         };
@@ -1092,15 +1822,151 @@ A test ADR.
         );
     }
 
+    #[test]
+    fn get_type_specific_rules_postmortem_and_api() {
+        let config = RulesSection {
+            type_specific: crate::config::TypeSpecificRulesSection {
+                postmortems: true,
+                apis: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let section_names = |doc_type| -> Vec<String> {
+            get_type_specific_rules(doc_type, &config)
+                .into_iter()
+                .filter_map(|rule| match rule {
+                    Rule::RequireSection { name } => Some(name),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            section_names(DocType::Postmortem),
+            vec!["Timeline", "Impact", "Action Items"]
+        );
+        assert_eq!(
+            section_names(DocType::Api),
+            vec!["Endpoints", "Authentication"]
+        );
+    }
+
     #[test]
     fn get_type_specific_rules_disabled() {
         let config = RulesSection::default(); // All type-specific rules disabled
         assert!(get_type_specific_rules(DocType::Runbook, &config).is_empty());
         assert!(get_type_specific_rules(DocType::Adr, &config).is_empty());
         assert!(get_type_specific_rules(DocType::Component, &config).is_empty());
+        assert!(get_type_specific_rules(DocType::Postmortem, &config).is_empty());
+        assert!(get_type_specific_rules(DocType::Api, &config).is_empty());
         assert!(get_type_specific_rules(DocType::Other, &config).is_empty());
     }
 
+    #[test]
+    fn get_type_specific_rules_verification_coverage() {
+        let config = RulesSection {
+            verification_coverage: crate::config::VerificationCoverageSection {
+                runbook: VerificationCoverage::AllSteps,
+                component: VerificationCoverage::AtLeastOne,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            get_type_specific_rules(DocType::Runbook, &config),
+            vec![
+                Rule::RequireVerificationCommand,
+                Rule::RequireStepVerification
+            ]
+        );
+        assert_eq!(
+            get_type_specific_rules(DocType::Component, &config),
+            vec![Rule::RequireVerificationCommand]
+        );
+        assert!(get_type_specific_rules(DocType::Adr, &config).is_empty());
+    }
+
+    #[test]
+    fn require_verification_command_fails_without_commands() {
+        let doc = parse_doc("# Service\n\n## Verification\nLooks fine.\n");
+        let engine = RulesEngine::new(vec![Rule::RequireVerificationCommand]);
+        let result = engine.validate(&doc);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].rule, "require-verification-command");
+        assert_eq!(result.errors[0].line, Some(3));
+
+        let doc = parse_doc("# Service\n\n## Verification\n```bash\ncargo test\n```\n");
+        assert!(engine.validate(&doc).is_valid());
+    }
+
+    #[test]
+    fn require_step_verification_reports_unverified_steps() {
+        let content = r#"# Runbook: Deploy
+
+## Steps
+1. Build the release
+   ```bash
+   $ cargo build --release
+   ```
+2. Notify the team in chat.
+3. Restart the service
+   ```bash
+   systemctl restart app
+   ```
+
+## Verification
+```bash
+cargo build --release
+```
+"#;
+        let doc = parse_doc(content);
+        let engine = RulesEngine::new(vec![Rule::RequireStepVerification]);
+        let result = engine.validate(&doc);
+
+        // Step 1 is verified and step 2 has no command
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line, Some(9));
+        assert!(result.errors[0].message.contains("step 3"));
+        assert!(result.errors[0].message.contains("systemctl restart app"));
+    }
+
+    #[test]
+    fn require_section_metadata_checks_declared_keys() {
+        let config = RulesSection {
+            section_metadata: vec![crate::config::SectionMetadataRule {
+                section: "Steps".to_string(),
+                require: vec!["owner".to_string(), "priority".to_string()],
+                doc_type: Some(DocType::Runbook),
+            }],
+            ..Default::default()
+        };
+        let rules = get_type_specific_rules(DocType::Runbook, &config);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].name(), "require-section-metadata-steps-owner");
+        assert!(get_type_specific_rules(DocType::Component, &config).is_empty());
+
+        let content = "# Runbook\n\n## Steps\n<!-- pave:section owner=infra -->\n1. Deploy\n";
+        let result = RulesEngine::new(rules).validate(&parse_doc(content));
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line, Some(3));
+        assert_eq!(
+            result.errors[0].message,
+            "section 'Steps' does not declare 'priority'"
+        );
+    }
+
+    #[test]
+    fn extract_steps_supports_h3_steps() {
+        let content = "# Runbook\n\n## Steps\n### Drain\n```bash\ndrain.sh\n```\n### Upgrade\n```bash\n1. not a step\n```\n";
+        let doc = parse_doc(content);
+        let steps = extract_steps(doc.get_section("Steps").unwrap());
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].line, 4);
+        assert_eq!(steps[0].commands, vec!["drain.sh".to_string()]);
+        assert_eq!(steps[1].commands, vec!["1. not a step".to_string()]);
+    }
+
     #[test]
     fn validate_with_type_applies_type_specific_rules() {
         let content = r#"# Runbook: Deploy
@@ -1124,6 +1990,8 @@ $ deploy.sh
                 runbooks: true,
                 adrs: false,
                 components: false,
+                postmortems: false,
+                apis: false,
             },
             ..Default::default()
         };
@@ -1174,6 +2042,8 @@ $ deploy.sh
                 runbooks: true,
                 adrs: false,
                 components: false,
+                postmortems: false,
+                apis: false,
             },
             ..Default::default()
         };
@@ -1434,6 +2304,7 @@ This is a component.
             warn_empty_paths: true,
             gradual: false,
             gradual_until: None,
+            ..Default::default()
         };
         let engine = RulesEngine::from_config_with_root(&config, "/project/root");
 
@@ -1462,6 +2333,7 @@ This is a component.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            ..Default::default()
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -1489,4 +2361,185 @@ This is a component.
         assert_eq!(patterns[1], (3, "src/cli.rs".to_string()));
         assert_eq!(patterns[2], (4, "docs/".to_string()));
     }
+
+    #[test]
+    fn validate_state_requires_superseded_by_for_deprecated_docs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("v2.md"), "# V2\n").unwrap();
+        let validate = |frontmatter: &str| {
+            let content = format!("---\npave:\n{}---\n# Doc\n", frontmatter);
+            let doc = ParsedDoc::parse_content(temp_dir.path().join("v1.md"), &content).unwrap();
+            let mut result = ValidationResult::new(&doc.path);
+            validate_state(&doc, &mut result);
+            result
+        };
+
+        let result = validate("  state: deprecated\n");
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("missing 'superseded_by'"));
+
+        let result = validate("  state: deprecated\n  superseded_by: ./v2.md\n");
+        assert!(result.is_valid());
+
+        let result = validate("  state: deprecated\n  superseded_by: ./v3.md\n");
+        assert!(
+            result.errors[0]
+                .message
+                .contains("'./v3.md' does not exist")
+        );
+
+        let result = validate("  state: published\n  superseded_by: ./v2.md\n");
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = validate("  state: final\n");
+        assert_eq!(result.errors[0].message, "Unknown document state 'final'");
+    }
+
+    fn parse_frontmatter(frontmatter: &str) -> ParsedDoc {
+        let content = format!("---\npave:\n{}---\n# Doc\n", frontmatter);
+        ParsedDoc::parse_content(PathBuf::from("doc.md"), &content).unwrap()
+    }
+
+    #[test]
+    fn validate_metadata_checks_values() {
+        let validate = |frontmatter: &str| {
+            let mut result = ValidationResult::new(Path::new("doc.md"));
+            validate_metadata(&parse_frontmatter(frontmatter), &mut result);
+            result
+        };
+
+        let result = validate(
+            "  owner: team-auth\n  tags: [api, auth]\n  last_reviewed: 2025-01-31\n  review_interval_days: 90\n",
+        );
+        assert!(result.is_valid());
+
+        let result = validate(
+            "  owner: ' '\n  tags: [api, '', api]\n  last_reviewed: 31/01/2025\n  review_interval_days: 0\n",
+        );
+        let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'owner' is empty",
+                "'tags' has an empty tag",
+                "Tag 'api' is listed more than once",
+                "'last_reviewed' date '31/01/2025' is not valid",
+                "'review_interval_days' must be positive, got 0",
+            ]
+        );
+        assert!(result.errors.iter().all(|e| e.rule == "valid-metadata"));
+    }
+
+    #[test]
+    fn validate_frontmatter_checks_keys_and_types() {
+        let validate = |content: &str| {
+            let mut result = ValidationResult::new(Path::new("doc.md"));
+            validate_frontmatter(content, &mut result);
+            result
+                .errors
+                .into_iter()
+                .map(|e| (e.line.unwrap(), e.message, e.suggestion.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        let valid = "---\ntitle: Auth\npave:\n  status: draft\n  paths: [src/auth/]\n  working_dir: app\n  \
+                     services:\n    - image: redis:7\n      env:\n        ANY_KEY: '1'\n---\n# Auth\n";
+        assert_eq!(validate(valid), []);
+        assert_eq!(validate("# No frontmatter\n"), []);
+
+        let errors = validate(
+            "---\npave:\n  working_dr: app\n  paths:\n    - src/\n    - 42\n  \
+             verify:\n    compose:\n      wait_fr: [tcp://localhost:5432]\n---\n",
+        );
+        assert_eq!(
+            errors,
+            [
+                (
+                    3,
+                    "Unknown frontmatter key 'pave.working_dr'".to_string(),
+                    "Did you mean 'pave.working_dir'?".to_string()
+                ),
+                (
+                    9,
+                    "Unknown frontmatter key 'pave.verify.compose.wait_fr'".to_string(),
+                    "Did you mean 'pave.verify.compose.wait_for'?".to_string()
+                ),
+                (
+                    4,
+                    "'paths' entry 42 is not a string".to_string(),
+                    "Quote the entry, e.g. - \"src/**\"".to_string()
+                ),
+            ]
+        );
+
+        let errors = validate("---\npave:\n  working_dir: /srv/app\n  setup: true\n---\n");
+        assert_eq!(errors[0].1, "'working_dir' is an absolute path: /srv/app");
+        assert_eq!(errors[1].0, 4);
+        assert!(
+            errors[1]
+                .1
+                .starts_with("Invalid frontmatter value: pave.setup: invalid type")
+        );
+
+        let errors = validate("---\npave: [a\n---\n");
+        assert!(errors[0].1.starts_with("Frontmatter is not valid YAML"));
+    }
+
+    #[test]
+    fn check_owner_and_review_date_use_configured_severity() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let mut rules = RulesSection::default();
+        let check = |rules: &RulesSection, frontmatter: &str| {
+            let doc = parse_frontmatter(frontmatter);
+            let mut result = ValidationResult::new(Path::new("doc.md"));
+            check_owner(&doc, rules, &mut result);
+            check_review_date(&doc, rules, today, &mut result);
+            result
+        };
+
+        // Owners are optional by default; overdue reviews warn
+        let result = check(
+            &rules,
+            "  last_reviewed: 2025-01-01\n  review_interval_days: 90\n",
+        );
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].rule, "review-overdue");
+        assert_eq!(
+            result.warnings[0].message,
+            "Review overdue since 2025-04-01 (61 days)"
+        );
+
+        // Without an interval there's no due date, unless the config sets one
+        let result = check(&rules, "  last_reviewed: 2025-01-01\n");
+        assert!(result.warnings.is_empty());
+        rules.review_interval_days = Some(200);
+        let result = check(&rules, "  last_reviewed: 2025-01-01\n");
+        assert!(result.warnings.is_empty());
+        rules.review_interval_days = Some(30);
+        let result = check(&rules, "  last_reviewed: 2025-01-01\n");
+        assert_eq!(result.warnings.len(), 1);
+
+        rules.require_owner = RuleSeverity::Error;
+        rules.review_overdue = RuleSeverity::Off;
+        let result = check(&rules, "  last_reviewed: 2025-01-01\n");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].rule, "require-owner");
+        assert!(result.warnings.is_empty());
+        assert!(check(&rules, "  owner: alice\n").is_valid());
+    }
+
+    #[test]
+    fn downgrade_errors_keeps_messages_as_warnings() {
+        let doc = parse_doc("# Draft\n");
+        let mut result = RulesEngine::with_defaults().validate(&doc);
+        let error_count = result.errors.len();
+        assert!(error_count > 0);
+
+        result.downgrade_errors();
+
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), error_count);
+    }
 }
@@ -0,0 +1,124 @@
+//! JSON Schemas for machine-readable command output.
+//!
+//! Every `--format json` payload carries a `schema_version`, and
+//! `pave schema <command>` prints the JSON Schema it conforms to, generated
+//! from the same types that are serialized.
+
+use schemars::{JsonSchema, Schema, schema_for};
+
+use crate::cli::SchemaTarget;
+use crate::commands::adopt::{AdoptionReport, BulkMigrationReport};
+use crate::commands::bench::BenchResults;
+use crate::commands::changed::ChangedResults;
+use crate::commands::check::CheckResults;
+use crate::commands::coverage::CoverageResults;
+use crate::commands::coverage_changed::CoverageChangedResults;
+use crate::commands::dedupe::DedupeResults;
+use crate::commands::doctor::DoctorResults;
+use crate::commands::fleet::FleetResults;
+use crate::commands::import::ImportResults;
+use crate::commands::index::IndexResults;
+use crate::commands::lint::LintResults;
+use crate::commands::migrate::MigrationReport;
+use crate::commands::nightly::NightlyReport;
+use crate::commands::publish::PublishResults;
+use crate::commands::report::ReportDiff;
+use crate::commands::review::ReviewResults;
+use crate::commands::search::SearchResults;
+use crate::commands::stale::StaleResults;
+use crate::commands::status::StatusResults;
+use crate::commands::summary::SummaryResults;
+use crate::commands::translate_status::TranslateStatusResults;
+use crate::commands::verify::VerifyResults;
+use crate::commands::which::WhichResults;
+
+/// Version of the JSON output schemas.
+///
+/// Bumped when a field is removed, renamed, or changes type. Adding a field
+/// doesn't change it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The JSON Schema of a command's JSON output.
+pub fn schema(target: SchemaTarget) -> Schema {
+    match target {
+        SchemaTarget::Check => versioned::<CheckResults>(),
+        SchemaTarget::Verify => versioned::<VerifyResults>(),
+        SchemaTarget::Lint => versioned::<LintResults>(),
+        SchemaTarget::Coverage => versioned::<CoverageResults>(),
+        SchemaTarget::Status => versioned::<StatusResults>(),
+        SchemaTarget::Stale => versioned::<StaleResults>(),
+        SchemaTarget::TranslateStatus => versioned::<TranslateStatusResults>(),
+        SchemaTarget::Import => versioned::<ImportResults>(),
+        SchemaTarget::Publish => versioned::<PublishResults>(),
+        SchemaTarget::Dedupe => versioned::<DedupeResults>(),
+        SchemaTarget::Review => versioned::<ReviewResults>(),
+        SchemaTarget::Index => versioned::<IndexResults>(),
+        SchemaTarget::Search => versioned::<SearchResults>(),
+        SchemaTarget::Which => versioned::<WhichResults>(),
+        SchemaTarget::Summary => versioned::<SummaryResults>(),
+        SchemaTarget::Fleet => versioned::<FleetResults>(),
+        SchemaTarget::Bench => versioned::<BenchResults>(),
+        SchemaTarget::Doctor => versioned::<DoctorResults>(),
+        SchemaTarget::Nightly => versioned::<NightlyReport>(),
+        SchemaTarget::Adopt => versioned::<AdoptionReport>(),
+        SchemaTarget::AdoptMigrate => versioned::<BulkMigrationReport>(),
+        SchemaTarget::Changed => versioned::<ChangedResults>(),
+        SchemaTarget::CoverageChanged => versioned::<CoverageChangedResults>(),
+        SchemaTarget::Migrate => versioned::<MigrationReport>(),
+        SchemaTarget::ReportDiff => versioned::<ReportDiff>(),
+    }
+}
+
+/// Schema for `T`, with `schema_version` pinned to [`SCHEMA_VERSION`].
+fn versioned<T: JsonSchema>() -> Schema {
+    let mut schema = schema_for!(T);
+    if let Some(version) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.get_mut("schema_version"))
+        .and_then(|version| version.as_object_mut())
+    {
+        version.insert("const".to_string(), SCHEMA_VERSION.into());
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn every_schema_pins_its_version() {
+        for target in SchemaTarget::value_variants() {
+            let schema = serde_json::to_value(schema(*target)).unwrap();
+            assert_eq!(
+                schema["properties"]["schema_version"]["const"], SCHEMA_VERSION,
+                "{:?}",
+                target
+            );
+            assert!(
+                schema["required"]
+                    .as_array()
+                    .unwrap()
+                    .contains(&"schema_version".into())
+            );
+        }
+    }
+
+    #[test]
+    fn check_schema_describes_serialized_fields() {
+        let schema = serde_json::to_value(schema(SchemaTarget::Check)).unwrap();
+        let output = serde_json::to_value(CheckResults::new()).unwrap();
+
+        let properties = schema["properties"].as_object().unwrap();
+        for key in output.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "{} missing from schema", key);
+        }
+        let issue = &schema["$defs"]["Issue"]["properties"];
+        assert!(issue["rule"].is_object());
+        assert_eq!(
+            schema["$defs"]["Severity"]["enum"],
+            serde_json::json!(["error", "warning"])
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Secrets passed to verification commands without writing them in docs.
+//!
+//! A `<!-- pave:env-from NAME -->` marker before a code block passes `NAME`
+//! to the block's commands. The value comes from the invoking environment,
+//! or failing that from a [`SECRETS_FILE`] next to `.pave.toml`, which should
+//! be gitignored:
+//!
+//! ```text
+//! # .pave.env
+//! API_TOKEN=abc123
+//! export DB_PASSWORD="hunter2"
+//! ```
+//!
+//! Values are never stored in results; `pave verify` redacts them from
+//! captured output and reports (see [`crate::redact`]).
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// File of `KEY=VALUE` secrets, relative to the config directory.
+pub const SECRETS_FILE: &str = ".pave.env";
+
+/// Secret values from the environment and the secrets file.
+#[derive(Debug, Default, Clone)]
+pub struct Secrets {
+    file: HashMap<String, String>,
+}
+
+impl Secrets {
+    /// Load the secrets file in `config_dir`. A missing file has no secrets.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(SECRETS_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Parse `KEY=VALUE` lines, skipping blank lines and `#` comments. An
+    /// `export ` prefix and matching quotes around the value are dropped.
+    pub fn parse(content: &str) -> Self {
+        let file = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                let (key, value) = line.split_once('=')?;
+                let key = key.trim();
+                (!key.is_empty()).then(|| (key.to_string(), unquote(value.trim()).to_string()))
+            })
+            .collect();
+        Self { file }
+    }
+
+    /// The value of `name`, preferring the environment over the file.
+    pub fn get(&self, name: &str) -> Option<String> {
+        env::var(name).ok().or_else(|| self.file.get(name).cloned())
+    }
+
+    /// The values of the named secrets that are set.
+    pub fn values<'a>(&self, names: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+        names
+            .into_iter()
+            .filter_map(|name| self.get(name))
+            .collect()
+    }
+}
+
+/// Strip one pair of matching single or double quotes.
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_env_file_lines() {
+        let secrets = Secrets::parse(
+            "# tokens\n\nPAVE_TEST_TOKEN=abc123\nexport PAVE_TEST_PASSWORD=\"hunter 2\"\nnot a pair\n",
+        );
+        assert_eq!(secrets.get("PAVE_TEST_TOKEN").as_deref(), Some("abc123"));
+        assert_eq!(
+            secrets.get("PAVE_TEST_PASSWORD").as_deref(),
+            Some("hunter 2")
+        );
+        assert_eq!(secrets.get("PAVE_TEST_MISSING"), None);
+    }
+
+    #[test]
+    fn values_skip_unset_secrets() {
+        let secrets = Secrets::parse("PAVE_TEST_SHORT=abc\nPAVE_TEST_LONG=abcdef\n");
+        let names = [
+            "PAVE_TEST_SHORT".to_string(),
+            "PAVE_TEST_UNSET".to_string(),
+            "PAVE_TEST_LONG".to_string(),
+        ];
+        assert_eq!(secrets.values(&names), ["abc", "abcdef"]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Service dependencies started around a document's verification commands.
+//!
+//! Runbooks that need a database or cache can list them in `pave.services`:
+//!
+//! ```yaml
+//! pave:
+//!   services:
+//!     - image: postgres:16
+//!       name: db
+//!       port: 5432
+//!       env: { POSTGRES_PASSWORD: pave }
+//!       export: { DATABASE_URL: "postgres://postgres:pave@{host}:{port}/postgres" }
+//!     - compose: services/docker-compose.yml
+//!       wait_for: ["tcp://localhost:6379"]
+//! ```
+//!
+//! An image service runs detached with `docker run` (or `[verify]
+//! container_cmd`), its `port` published on a free localhost port. Once the
+//! container's healthcheck reports healthy, or the port accepts connections
+//! if the image has no healthcheck, the document's commands get
+//! `<NAME>_HOST` and `<NAME>_PORT`, plus any `export` variables with `{host}`
+//! and `{port}` filled in. A compose service runs `up -d` on its file and
+//! waits for its containers' healthchecks. Both also wait for their
+//! `wait_for` endpoints. Services start before setup commands and stop after
+//! teardown; `pave verify --no-services` leaves them to the caller.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use crate::compose::ComposeBootstrap;
+use crate::verify_export::quote;
+
+/// Host that published service ports are bound to.
+pub const SERVICE_HOST: &str = "127.0.0.1";
+
+/// How long to wait for a service to accept connections by default, in
+/// seconds.
+const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 60;
+
+/// A service from `pave.services` in frontmatter.
+#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq)]
+pub struct Service {
+    /// Name used for the container and the `<NAME>_HOST`/`<NAME>_PORT`
+    /// variables. Defaults to the image name without registry or tag.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Image to run, such as `postgres:16`.
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Compose file to bring up instead of an image, relative to the
+    /// document's working directory.
+    #[serde(default)]
+    pub compose: Option<String>,
+    /// Container port of an image service to publish and wait for.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Environment for the service container.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Variables passed to the document's commands. `{host}` and `{port}`
+    /// are replaced with the published address of an image service.
+    #[serde(default)]
+    pub export: BTreeMap<String, String>,
+    /// Extra endpoints that must accept connections, such as
+    /// `tcp://localhost:6379`.
+    #[serde(default)]
+    pub wait_for: Vec<String>,
+    /// Seconds to wait for the service's endpoints.
+    #[serde(default = "default_wait_timeout")]
+    pub wait_timeout: u64,
+}
+
+fn default_wait_timeout() -> u64 {
+    DEFAULT_WAIT_TIMEOUT_SECS
+}
+
+/// How to start, wait for, and stop one service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServicePlan {
+    /// Command that starts the service in the background.
+    pub start: String,
+    /// Command that stops and removes the service.
+    pub stop: String,
+    /// Command that prints the health of the service's containers, one per
+    /// line; see [`parse_health`].
+    pub health: String,
+    /// Endpoint of the published port, waited for only when the service has
+    /// no healthcheck.
+    pub published: Option<String>,
+    /// Endpoints that must accept connections before commands run.
+    pub wait_for: Vec<String>,
+    /// Time allowed for the `wait_for` endpoints.
+    pub wait_timeout: Duration,
+    /// Variables passed to the document's commands.
+    pub env: Vec<(String, String)>,
+}
+
+impl Service {
+    /// Name of the service, from `name` or the image.
+    pub fn name(&self) -> Option<String> {
+        self.name.clone().or_else(|| {
+            let image = self.image.as_deref()?;
+            let repo = image.rsplit('/').next().unwrap_or(image);
+            let repo = repo.split(['@', ':']).next().unwrap_or(repo);
+            (!repo.is_empty()).then(|| repo.to_string())
+        })
+    }
+
+    /// Plan the commands for this service, the `index`th of the document.
+    ///
+    /// Image services get a free localhost port each time they're planned.
+    pub fn plan(
+        &self,
+        index: usize,
+        container_cmd: &str,
+        compose_cmd: &str,
+    ) -> Result<ServicePlan, String> {
+        match (&self.image, &self.compose) {
+            (Some(image), None) => self.plan_image(image, index, container_cmd),
+            (None, Some(file)) => Ok(self.plan_compose(file, compose_cmd)),
+            (Some(_), Some(_)) => Err("service sets both image and compose".to_string()),
+            (None, None) => Err("service needs an image or a compose file".to_string()),
+        }
+    }
+
+    fn plan_image(
+        &self,
+        image: &str,
+        index: usize,
+        container_cmd: &str,
+    ) -> Result<ServicePlan, String> {
+        let name = self
+            .name()
+            .ok_or_else(|| format!("cannot name service for image '{}'", image))?;
+        let container = format!(
+            "pave-{}-{}-{}",
+            sanitize(&name, '-').to_lowercase(),
+            std::process::id(),
+            index
+        );
+
+        let mut start = format!("{} run -d --rm --name {}", container_cmd, container);
+        let mut published = None;
+        let mut env = Vec::new();
+        let host_port = match self.port {
+            Some(port) => {
+                let host_port =
+                    free_port().map_err(|e| format!("no free port for {}: {}", name, e))?;
+                start.push_str(&format!(" -p {}:{}:{}", SERVICE_HOST, host_port, port));
+                published = Some(format!("tcp://{}:{}", SERVICE_HOST, host_port));
+                let prefix = sanitize(&name, '_').to_uppercase();
+                env.push((format!("{}_HOST", prefix), SERVICE_HOST.to_string()));
+                env.push((format!("{}_PORT", prefix), host_port.to_string()));
+                Some(host_port)
+            }
+            None => None,
+        };
+        for (key, value) in &self.env {
+            start.push_str(&format!(" -e {}", quote(&format!("{}={}", key, value))));
+        }
+        start.push(' ');
+        start.push_str(&quote(image));
+
+        env.extend(self.export.iter().map(|(key, value)| {
+            let mut value = value.replace("{host}", SERVICE_HOST);
+            if let Some(port) = host_port {
+                value = value.replace("{port}", &port.to_string());
+            }
+            (key.clone(), value)
+        }));
+
+        Ok(ServicePlan {
+            start,
+            stop: format!("{} rm -f {}", container_cmd, container),
+            health: format!(
+                "{} inspect --format {} {}",
+                container_cmd,
+                quote("{{if .State.Health}}{{.State.Health.Status}}{{end}}"),
+                container
+            ),
+            published,
+            wait_for: self.wait_for.clone(),
+            wait_timeout: Duration::from_secs(self.wait_timeout),
+            env,
+        })
+    }
+
+    fn plan_compose(&self, file: &str, compose_cmd: &str) -> ServicePlan {
+        let bootstrap = ComposeBootstrap {
+            file: Some(file.to_string()),
+            up: Vec::new(),
+            wait_for: self.wait_for.clone(),
+            wait_timeout: self.wait_timeout,
+        };
+        ServicePlan {
+            start: bootstrap.up_command(compose_cmd, file),
+            stop: bootstrap.down_command(compose_cmd, file),
+            health: format!(
+                "{} -f {} ps --format {}",
+                compose_cmd,
+                quote(file),
+                quote("{{.Health}}")
+            ),
+            published: None,
+            wait_for: bootstrap.wait_for.clone(),
+            wait_timeout: bootstrap.wait_timeout(),
+            env: self
+                .export
+                .iter()
+                .map(|(key, value)| (key.clone(), value.replace("{host}", SERVICE_HOST)))
+                .collect(),
+        }
+    }
+}
+
+/// Health reported by a service's containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    /// Some healthcheck hasn't passed yet.
+    Starting,
+    /// Every healthcheck passes.
+    Healthy,
+    /// Some healthcheck has failed.
+    Unhealthy,
+}
+
+/// Parse the output of a [`ServicePlan::health`] command.
+///
+/// Containers without a healthcheck print an empty line and are ignored, so
+/// `None` means the service has no healthcheck to wait for.
+pub fn parse_health(output: &str) -> Option<Health> {
+    let statuses: Vec<Health> = output
+        .lines()
+        .filter_map(|line| match line.trim() {
+            "starting" => Some(Health::Starting),
+            "healthy" => Some(Health::Healthy),
+            "unhealthy" => Some(Health::Unhealthy),
+            _ => None,
+        })
+        .collect();
+    if statuses.contains(&Health::Unhealthy) {
+        Some(Health::Unhealthy)
+    } else if statuses.contains(&Health::Starting) {
+        Some(Health::Starting)
+    } else {
+        statuses.first().copied()
+    }
+}
+
+/// `name` with every character that isn't ASCII alphanumeric replaced by
+/// `sep`.
+fn sanitize(name: &str, sep: char) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { sep })
+        .collect()
+}
+
+/// A localhost port that is free right now.
+fn free_port() -> std::io::Result<u16> {
+    Ok(TcpListener::bind((SERVICE_HOST, 0))?.local_addr()?.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(yaml: &str) -> Service {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn names_default_to_image_repository() {
+        assert_eq!(
+            service("image: ghcr.io/acme/redis-stack:7@sha256:abc").name(),
+            Some("redis-stack".to_string())
+        );
+        assert_eq!(
+            service("image: postgres:16\nname: db").name(),
+            Some("db".to_string())
+        );
+        assert_eq!(service("compose: compose.yaml").name(), None);
+    }
+
+    #[test]
+    fn image_services_publish_a_port_and_export_its_address() {
+        let plan = service(
+            "image: postgres:16\nname: main-db\nport: 5432\n\
+             env: { POSTGRES_PASSWORD: pave }\n\
+             export: { DATABASE_URL: 'postgres://{host}:{port}/app' }\n",
+        )
+        .plan(0, "podman", "docker compose")
+        .unwrap();
+
+        let port = plan.env[1].1.clone();
+        let container = format!("pave-main-db-{}-0", std::process::id());
+        assert_eq!(
+            plan.start,
+            format!(
+                "podman run -d --rm --name {} -p 127.0.0.1:{}:5432 -e POSTGRES_PASSWORD=pave postgres:16",
+                container, port
+            )
+        );
+        assert_eq!(plan.stop, format!("podman rm -f {}", container));
+        assert_eq!(
+            plan.health,
+            format!(
+                "podman inspect --format '{{{{if .State.Health}}}}{{{{.State.Health.Status}}}}{{{{end}}}}' {}",
+                container
+            )
+        );
+        assert_eq!(plan.published, Some(format!("tcp://127.0.0.1:{}", port)));
+        assert!(plan.wait_for.is_empty());
+        assert_eq!(
+            plan.env,
+            [
+                ("MAIN_DB_HOST".to_string(), "127.0.0.1".to_string()),
+                ("MAIN_DB_PORT".to_string(), port.clone()),
+                (
+                    "DATABASE_URL".to_string(),
+                    format!("postgres://127.0.0.1:{}/app", port)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn compose_services_use_the_compose_file() {
+        let plan = service(
+            "compose: services/compose.yaml\nwait_for: ['tcp://localhost:6379']\n\
+             export: { REDIS_URL: 'redis://{host}:6379' }\nwait_timeout: 5\n",
+        )
+        .plan(1, "docker", "docker compose")
+        .unwrap();
+        assert_eq!(plan.start, "docker compose -f services/compose.yaml up -d");
+        assert_eq!(plan.stop, "docker compose -f services/compose.yaml down");
+        assert_eq!(
+            plan.health,
+            "docker compose -f services/compose.yaml ps --format '{{.Health}}'"
+        );
+        assert_eq!(plan.published, None);
+        assert_eq!(plan.wait_for, ["tcp://localhost:6379"]);
+        assert_eq!(plan.wait_timeout, Duration::from_secs(5));
+        assert_eq!(
+            plan.env,
+            [(
+                "REDIS_URL".to_string(),
+                "redis://127.0.0.1:6379".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn health_waits_for_every_container_with_a_healthcheck() {
+        assert_eq!(parse_health(""), None);
+        assert_eq!(parse_health("\n\n"), None);
+        assert_eq!(parse_health("healthy\n\n"), Some(Health::Healthy));
+        assert_eq!(parse_health("healthy\nstarting\n"), Some(Health::Starting));
+        assert_eq!(
+            parse_health("starting\nunhealthy\n"),
+            Some(Health::Unhealthy)
+        );
+    }
+
+    #[test]
+    fn services_need_exactly_one_source() {
+        let err = service("image: redis\ncompose: compose.yaml")
+            .plan(0, "docker", "docker compose")
+            .unwrap_err();
+        assert!(err.contains("both image and compose"));
+        let err = service("name: cache")
+            .plan(0, "docker", "docker compose")
+            .unwrap_err();
+        assert!(err.contains("needs an image or a compose file"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Spellchecking for the `spelling` lint rule.
+//!
+//! Words are checked against the word lists in `lint.spelling_dictionaries`,
+//! such as `/usr/share/dict/words`, plus the project dictionary,
+//! `.pave-dictionary.txt` at the project root. Both take one word per line
+//! with `#` comments, and lookups ignore case.
+//!
+//! Only plain words are checked. Tokens with digits, underscores, slashes, or
+//! inner dots, as well as acronyms and camelCase words, are taken to be names
+//! rather than prose. Inline code, link targets, URLs, and HTML tags are
+//! skipped; callers skip code blocks.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::terminology::NON_PROSE;
+
+/// Name of the project dictionary, read from the project root.
+pub const DICTIONARY_FILENAME: &str = ".pave-dictionary.txt";
+
+/// Most suggestions offered for an unknown word.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Most edits between an unknown word and a suggestion.
+const MAX_EDIT_DISTANCE: usize = 2;
+
+/// Known words, for finding unknown ones in prose.
+#[derive(Debug)]
+pub struct Dictionary {
+    /// Words by their lowercase form, as written in the word list.
+    words: HashMap<String, String>,
+    /// Matches spans that aren't prose.
+    skipped: Regex,
+    /// Suggestions already computed, by lowercase word. Shared by the
+    /// threads linting documents in parallel.
+    suggestions: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self {
+            words: HashMap::new(),
+            skipped: Regex::new(&format!("{}|<[^>]*>", NON_PROSE)).unwrap(),
+            suggestions: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Dictionary {
+    /// Load the word lists, relative to `project_root`, and the project
+    /// dictionary if there is one.
+    ///
+    /// Returns `None` if no word lists are configured, since the project
+    /// dictionary alone can't tell misspellings from ordinary words.
+    pub fn load(project_root: &Path, word_lists: &[PathBuf]) -> Result<Option<Self>> {
+        if word_lists.is_empty() {
+            return Ok(None);
+        }
+
+        let mut dictionary = Self::default();
+        for path in word_lists {
+            let path = project_root.join(path);
+            let content = std::fs::read_to_string(&path).with_context(|| {
+                format!("Failed to read spelling dictionary: {}", path.display())
+            })?;
+            dictionary.add_words(&content);
+        }
+
+        let project = project_root.join(DICTIONARY_FILENAME);
+        if project.is_file() {
+            let content = std::fs::read_to_string(&project)
+                .with_context(|| format!("Failed to read {}", project.display()))?;
+            dictionary.add_words(&content);
+        }
+        Ok(Some(dictionary))
+    }
+
+    /// Add the words in a word list.
+    pub fn add_words(&mut self, content: &str) {
+        for line in content.lines() {
+            // Some word lists start with a count or follow words with flags
+            let word = line.split('/').next().unwrap_or_default().trim();
+            if word.is_empty() || word.starts_with('#') || word.chars().all(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+            self.words.insert(word.to_lowercase(), word.to_string());
+        }
+    }
+
+    /// Whether a word, or the word without a possessive `'s`, is known.
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.replace('’', "'").to_lowercase();
+        self.words.contains_key(&word)
+            || word
+                .strip_suffix("'s")
+                .is_some_and(|stem| self.words.contains_key(stem))
+    }
+
+    /// Unknown words in a line of prose, in order.
+    pub fn unknown_words(&self, line: &str) -> Vec<String> {
+        let prose = self.skipped.replace_all(line, " ");
+        let mut unknown = Vec::new();
+        for token in prose.split_whitespace() {
+            let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+            if !token
+                .chars()
+                .all(|c| c.is_alphabetic() || matches!(c, '\'' | '’' | '-'))
+            {
+                continue;
+            }
+            for word in token.split('-') {
+                let is_plain = word.chars().skip(1).all(|c| !c.is_uppercase());
+                if word.chars().count() > 1 && is_plain && !self.contains(word) {
+                    unknown.push(word.to_string());
+                }
+            }
+        }
+        unknown
+    }
+
+    /// Known words within a couple of edits of `word`, closest first.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let word = word.to_lowercase();
+        if let Some(cached) = self.suggestions.lock().unwrap().get(&word) {
+            return cached.clone();
+        }
+
+        let len = word.chars().count();
+        let mut candidates: Vec<(usize, &String)> = self
+            .words
+            .iter()
+            .filter(|(key, _)| key.chars().count().abs_diff(len) <= MAX_EDIT_DISTANCE)
+            .map(|(key, written)| (strsim::damerau_levenshtein(&word, key), written))
+            .filter(|(distance, _)| *distance <= MAX_EDIT_DISTANCE)
+            .collect();
+        candidates.sort();
+        let suggestions: Vec<String> = candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, written)| written.clone())
+            .collect();
+
+        self.suggestions
+            .lock()
+            .unwrap()
+            .insert(word, suggestions.clone());
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn dictionary(words: &str) -> Dictionary {
+        let mut dictionary = Dictionary::default();
+        dictionary.add_words(words);
+        dictionary
+    }
+
+    #[test]
+    fn finds_unknown_prose_words() {
+        let dictionary = dictionary("the\nteam\nreviews\ndocs\nweekly\nto\nsee\nit's\n");
+
+        assert_eq!(
+            dictionary.unknown_words(
+                "The teem reveiws docs weekly: see `pave_lint`, [the docs](./teh.md), \
+                 <https://example.com/teh>, src/main.rs, v2, API, camelCase, and it's the team's."
+            ),
+            vec!["teem", "reveiws", "and"]
+        );
+        assert!(
+            dictionary
+                .unknown_words("<!-- pave-lint-disable spelling -->")
+                .is_empty()
+        );
+        assert_eq!(dictionary.unknown_words("weekly-reveiws"), vec!["reveiws"]);
+    }
+
+    #[test]
+    fn suggests_close_words() {
+        let dictionary = dictionary("4\nthe/S\nten\nthen\nGitHub\n# comment\n");
+
+        assert_eq!(dictionary.suggest("teh"), vec!["ten", "the", "then"]);
+        assert_eq!(dictionary.suggest("Githb"), vec!["GitHub"]);
+        assert!(dictionary.suggest("xylophone").is_empty());
+        assert!(dictionary.contains("github"));
+        assert!(!dictionary.contains("4"));
+    }
+
+    #[test]
+    fn loads_project_dictionary_with_word_lists() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("words.txt"), "docs\n").unwrap();
+        fs::write(
+            root.join(DICTIONARY_FILENAME),
+            "# Project terms\nfrontmatter\n",
+        )
+        .unwrap();
+
+        assert!(Dictionary::load(root, &[]).unwrap().is_none());
+        let dictionary = Dictionary::load(root, &[PathBuf::from("words.txt")])
+            .unwrap()
+            .unwrap();
+        assert!(dictionary.unknown_words("Docs frontmatter").is_empty());
+        assert!(Dictionary::load(root, &[PathBuf::from("missing.txt")]).is_err());
+    }
+}
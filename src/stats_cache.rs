@@ -0,0 +1,326 @@
+//! Per-document summary cache for `pave status` and `pave doctor`.
+//!
+//! Both commands need the same facts about every document: its type and
+//! state, which sections it has, how long it is, and how many rule errors
+//! and warnings it produces. Computing them means parsing and validating
+//! the whole tree, so summaries are stored in `.pave/stats-cache.json`
+//! keyed by a hash of each document's contents. Only documents that changed
+//! since the last run are re-analyzed.
+//!
+//! Changing `.pave.toml` or upgrading pave discards the whole cache. Rules
+//! that look outside the document, such as `warn_empty_paths`, are not
+//! tracked; pass `--refresh` to recompute everything.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::PaveConfig;
+use crate::parser::{DocState, ParsedDoc};
+use crate::rules::{
+    DocType, RulesEngine, check_owner, detect_doc_type, validate_frontmatter, validate_metadata,
+    validate_state,
+};
+use crate::verification::extract_verification_spec;
+use crate::verify_cache::hash_bytes;
+
+/// Cache file location, relative to the config directory.
+pub const STATS_CACHE_PATH: &str = ".pave/stats-cache.json";
+
+/// Bump when [`DocSummary`] changes so old caches are ignored.
+const CACHE_VERSION: u32 = 2;
+
+/// What `status` and `doctor` need to know about a document.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocSummary {
+    /// Hash of the document's contents when it was summarized.
+    pub hash: String,
+    /// Detected document type.
+    pub doc_type: DocType,
+    /// Lifecycle state from frontmatter.
+    pub state: DocState,
+    /// Number of lines in the document.
+    pub line_count: usize,
+    /// Names of the document's H2 sections.
+    pub sections: Vec<String>,
+    /// Rule errors, after drafts are downgraded to warnings.
+    pub error_count: usize,
+    /// Rule warnings, including an over-long document.
+    pub warning_count: usize,
+    /// Commands in the Verification section.
+    pub verification_commands: Vec<String>,
+    /// Raw content of the Paths section, if there is one.
+    pub paths_section: Option<String>,
+    /// Owner from frontmatter.
+    pub owner: Option<String>,
+    /// Tags from frontmatter.
+    pub tags: Vec<String>,
+    /// Date the document is next due for review (YYYY-MM-DD). Overdue reviews
+    /// depend on the day, so they aren't counted in `warning_count`.
+    pub review_due: Option<String>,
+}
+
+impl DocSummary {
+    /// Whether the document has a section, ignoring case like [`ParsedDoc::has_section`].
+    pub fn has_section(&self, name: &str) -> bool {
+        self.sections.iter().any(|s| s.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the document has no rule errors.
+    pub fn is_compliant(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    /// pave version and config hash the summaries were computed with.
+    fingerprint: String,
+    entries: BTreeMap<String, DocSummary>,
+}
+
+/// Summary cache loaded from disk.
+pub struct StatsCache {
+    path: PathBuf,
+    config_dir: PathBuf,
+    fingerprint: String,
+    entries: BTreeMap<String, DocSummary>,
+    /// Whether any entry was added or replaced in this run.
+    changed: bool,
+    /// Number of summaries served from the cache in this run.
+    hits: usize,
+}
+
+impl StatsCache {
+    /// Load the cache for the project whose config file is `config_path`.
+    ///
+    /// With `refresh`, or when the config or pave version changed, the cache
+    /// starts empty. A missing or corrupt cache file is not an error.
+    pub fn load(config_path: &Path, refresh: bool) -> Self {
+        let config_dir = absolute(config_path.parent().unwrap_or_else(|| Path::new(".")));
+        let config = std::fs::read(config_path).unwrap_or_default();
+        let fingerprint = format!("{}:{}", env!("CARGO_PKG_VERSION"), hash_bytes(&config));
+        let path = config_dir.join(STATS_CACHE_PATH);
+
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .filter(|_| !refresh)
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == CACHE_VERSION && file.fingerprint == fingerprint)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            config_dir,
+            fingerprint,
+            entries,
+            changed: refresh,
+            hits: 0,
+        }
+    }
+
+    /// Summary of a document, recomputed only if its contents changed.
+    pub fn summary(&mut self, file: &Path, config: &PaveConfig) -> Result<DocSummary> {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let hash = hash_bytes(content.as_bytes());
+        let key = self.key(file);
+
+        if let Some(summary) = self.entries.get(&key)
+            && summary.hash == hash
+        {
+            self.hits += 1;
+            return Ok(summary.clone());
+        }
+
+        let summary = summarize(file, &content, hash, config, &self.config_dir)?;
+        self.entries.insert(key, summary.clone());
+        self.changed = true;
+        Ok(summary)
+    }
+
+    /// Number of summaries served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Write the cache if anything changed, dropping deleted documents.
+    pub fn save(&mut self) -> Result<()> {
+        let config_dir = &self.config_dir;
+        let before = self.entries.len();
+        self.entries.retain(|key, _| config_dir.join(key).is_file());
+        if !self.changed && self.entries.len() == before {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            fingerprint: self.fingerprint.clone(),
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file).context("Failed to serialize cache")?;
+        self.changed = false;
+        std::fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write cache: {}", self.path.display()))
+    }
+
+    /// Cache key: the document's path relative to the config directory.
+    fn key(&self, file: &Path) -> String {
+        let file = absolute(file);
+        file.strip_prefix(&self.config_dir)
+            .unwrap_or(&file)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// Resolve a relative path against the current directory.
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Parse and validate a document.
+fn summarize(
+    file: &Path,
+    content: &str,
+    hash: String,
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<DocSummary> {
+    let doc = ParsedDoc::parse_content(file.to_path_buf(), content)?;
+    let doc_type = detect_doc_type(file, content);
+
+    // Build rules engine with project root for path validation
+    let engine = RulesEngine::from_config_with_root(&config.rules, config_dir);
+
+    // Validate with type-specific rules and the document's lifecycle state
+    let mut result = engine.validate_with_type(&doc, doc_type, &config.rules);
+    validate_state(&doc, &mut result);
+    validate_metadata(&doc, &mut result);
+    validate_frontmatter(content, &mut result);
+    check_owner(&doc, &config.rules, &mut result);
+
+    // Drafts only get warnings
+    let state = doc.state();
+    if state == DocState::Draft {
+        result.downgrade_errors();
+    }
+
+    let mut warning_count = result.warnings.len();
+    if doc.line_count > config.rules.max_lines as usize {
+        warning_count += 1;
+    }
+
+    let frontmatter = doc.frontmatter.clone().unwrap_or_default();
+    let verification_commands = extract_verification_spec(&doc)
+        .map(|spec| spec.items.into_iter().map(|item| item.command).collect())
+        .unwrap_or_default();
+
+    Ok(DocSummary {
+        hash,
+        doc_type,
+        state,
+        line_count: doc.line_count,
+        sections: doc.sections.iter().map(|s| s.name.clone()).collect(),
+        error_count: result.errors.len(),
+        warning_count,
+        verification_commands,
+        paths_section: doc.get_section("Paths").map(|s| s.content.clone()),
+        owner: frontmatter.owner.filter(|owner| !owner.trim().is_empty()),
+        tags: frontmatter.tags,
+        review_due: doc
+            .review_due(config.rules.review_interval_days)
+            .map(|due| due.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf, PaveConfig) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".pave.toml");
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        )
+        .unwrap();
+        fs::create_dir(temp_dir.path().join("docs")).unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+        (temp_dir, config_path, config)
+    }
+
+    #[test]
+    fn reuses_summaries_until_document_changes() {
+        let (temp_dir, config_path, config) = setup();
+        let doc = temp_dir.path().join("docs/auth.md");
+        fs::write(&doc, "# Auth\n\n## Purpose\nLogin.\n").unwrap();
+
+        let mut cache = StatsCache::load(&config_path, false);
+        let summary = cache.summary(&doc, &config).unwrap();
+        assert!(summary.has_section("purpose"));
+        assert!(!summary.is_compliant());
+        assert_eq!(cache.hits(), 0);
+        cache.save().unwrap();
+
+        let mut cache = StatsCache::load(&config_path, false);
+        assert_eq!(cache.summary(&doc, &config).unwrap(), summary);
+        assert_eq!(cache.hits(), 1);
+
+        fs::write(
+            &doc,
+            "# Auth\n\n## Verification\n```bash\ntrue\n```\n\n## Examples\nx\n",
+        )
+        .unwrap();
+        let updated = cache.summary(&doc, &config).unwrap();
+        assert_eq!(cache.hits(), 1);
+        assert!(updated.has_section("Examples"));
+        assert_eq!(updated.verification_commands, vec!["true"]);
+        cache.save().unwrap();
+
+        // --refresh ignores the stored summaries
+        let mut cache = StatsCache::load(&config_path, true);
+        cache.summary(&doc, &config).unwrap();
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn config_change_invalidates_cache() {
+        let (temp_dir, config_path, config) = setup();
+        let doc = temp_dir.path().join("docs/auth.md");
+        fs::write(&doc, "# Auth\n").unwrap();
+
+        let mut cache = StatsCache::load(&config_path, false);
+        cache.summary(&doc, &config).unwrap();
+        cache.save().unwrap();
+
+        let mut content = fs::read_to_string(&config_path).unwrap();
+        content.push_str("\n[rules]\nrequire_examples = false\n");
+        fs::write(&config_path, content).unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+
+        let mut cache = StatsCache::load(&config_path, false);
+        cache.summary(&doc, &config).unwrap();
+        assert_eq!(cache.hits(), 0);
+
+        fs::remove_file(&doc).unwrap();
+        cache.save().unwrap();
+        let saved = fs::read_to_string(temp_dir.path().join(STATS_CACHE_PATH)).unwrap();
+        assert!(!saved.contains("auth.md"));
+    }
+}
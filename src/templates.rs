@@ -1,6 +1,14 @@
-//! PAVED document templates for component, runbook, and ADR documentation.
+//! PAVED document templates for component, runbook, ADR, postmortem, and API
+//! reference documentation.
 //!
 //! These templates follow the PAVED structure optimized for AI agents to author and consume.
+//! A project can override them, and add document types of its own, with
+//! markdown files in its templates directory (`[templates] dir`).
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+use crate::config::PaveConfig;
 
 /// The types of PAVED document templates available.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +19,10 @@ pub enum TemplateType {
     Runbook,
     /// Architecture Decision Record.
     Adr,
+    /// Incident postmortem.
+    Postmortem,
+    /// API reference.
+    Api,
 }
 
 impl TemplateType {
@@ -20,15 +32,35 @@ impl TemplateType {
             TemplateType::Component,
             TemplateType::Runbook,
             TemplateType::Adr,
+            TemplateType::Postmortem,
+            TemplateType::Api,
         ]
     }
 
+    /// Returns the name used for this type on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateType::Component => "component",
+            TemplateType::Runbook => "runbook",
+            TemplateType::Adr => "adr",
+            TemplateType::Postmortem => "postmortem",
+            TemplateType::Api => "api",
+        }
+    }
+
+    /// Returns the built-in type with the given command-line name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|t| t.name() == name)
+    }
+
     /// Returns the default filename for this template type.
     pub fn default_filename(&self) -> &'static str {
         match self {
             TemplateType::Component => "component.md",
             TemplateType::Runbook => "runbook.md",
             TemplateType::Adr => "adr.md",
+            TemplateType::Postmortem => "postmortem.md",
+            TemplateType::Api => "api.md",
         }
     }
 }
@@ -39,12 +71,205 @@ pub fn get_template(template_type: TemplateType) -> &'static str {
         TemplateType::Component => include_str!("../templates/component.md"),
         TemplateType::Runbook => include_str!("../templates/runbook.md"),
         TemplateType::Adr => include_str!("../templates/adr.md"),
+        TemplateType::Postmortem => include_str!("../templates/postmortem.md"),
+        TemplateType::Api => include_str!("../templates/api.md"),
+    }
+}
+
+/// A document template, built in or from the project's templates directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocTemplate {
+    /// Document type name: a built-in type, or a project template's file
+    /// stem such as `rfc`.
+    pub name: String,
+    /// The built-in type this template is used for, if any.
+    pub builtin_type: Option<TemplateType>,
+    /// Template content.
+    pub content: String,
+    /// Path relative to the config directory, or `None` for a built-in
+    /// template.
+    pub source: Option<PathBuf>,
+}
+
+impl DocTemplate {
+    /// The built-in template for a type.
+    pub fn builtin(template_type: TemplateType) -> Self {
+        Self {
+            name: template_type.name().to_string(),
+            builtin_type: Some(template_type),
+            content: get_template(template_type).to_string(),
+            source: None,
+        }
+    }
+}
+
+/// Directory holding project templates: `[templates] dir`, or the older
+/// `[docs] templates`.
+pub fn templates_dir(config: &PaveConfig) -> Option<&Path> {
+    config
+        .templates
+        .dir
+        .as_deref()
+        .or(config.docs.templates.as_deref())
+}
+
+/// Template filename for a built-in type, honoring `[templates]` overrides.
+pub fn template_filename(config: &PaveConfig, template_type: TemplateType) -> String {
+    let configured = match template_type {
+        TemplateType::Component => &config.templates.component,
+        TemplateType::Runbook => &config.templates.runbook,
+        TemplateType::Adr => &config.templates.adr,
+        TemplateType::Postmortem => &config.templates.postmortem,
+        TemplateType::Api => &config.templates.api,
+    };
+    configured
+        .clone()
+        .unwrap_or_else(|| template_type.default_filename().to_string())
+}
+
+/// Load the template for a document type.
+///
+/// Built-in types use the project's template when the templates directory
+/// has one and the built-in template otherwise. Any other name must match a
+/// markdown file in the templates directory.
+pub fn load_template(config: &PaveConfig, config_dir: &Path, name: &str) -> Result<DocTemplate> {
+    let dir = templates_dir(config);
+    if let Some(template_type) = TemplateType::from_name(name) {
+        if let Some(dir) = dir {
+            let relative = dir.join(template_filename(config, template_type));
+            if config_dir.join(&relative).is_file() {
+                return read_template(config_dir, relative, name, Some(template_type));
+            }
+        }
+        return Ok(DocTemplate::builtin(template_type));
+    }
+
+    let available: Vec<String> = list_templates(config, config_dir)?
+        .into_iter()
+        .map(|template| template.name)
+        .collect();
+    if let Some(dir) = dir {
+        let relative = dir.join(format!("{}.md", name));
+        if available.iter().any(|n| n == name) {
+            return read_template(config_dir, relative, name, None);
+        }
     }
+    bail!(
+        "Unknown document type `{}`: expected one of {}",
+        name,
+        available.join(", ")
+    );
+}
+
+/// Every available template: the built-in types, with project overrides
+/// applied, then the project's extra types by name.
+pub fn list_templates(config: &PaveConfig, config_dir: &Path) -> Result<Vec<DocTemplate>> {
+    let mut templates = Vec::new();
+    for &template_type in TemplateType::all() {
+        templates.push(load_template(config, config_dir, template_type.name())?);
+    }
+
+    let Some(dir) = templates_dir(config) else {
+        return Ok(templates);
+    };
+    let Ok(entries) = std::fs::read_dir(config_dir.join(dir)) else {
+        return Ok(templates);
+    };
+    let overrides: Vec<String> = TemplateType::all()
+        .iter()
+        .map(|&t| template_filename(config, t))
+        .collect();
+    let mut extra = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(stem) = file_name.strip_suffix(".md") else {
+            continue;
+        };
+        if !path.is_file()
+            || overrides.iter().any(|o| o == file_name)
+            || TemplateType::from_name(stem).is_some()
+        {
+            continue;
+        }
+        extra.push(read_template(config_dir, dir.join(file_name), stem, None)?);
+    }
+    extra.sort_by(|a, b| a.name.cmp(&b.name));
+    templates.extend(extra);
+    Ok(templates)
+}
+
+fn read_template(
+    config_dir: &Path,
+    relative: PathBuf,
+    name: &str,
+    builtin_type: Option<TemplateType>,
+) -> Result<DocTemplate> {
+    let path = config_dir.join(&relative);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read template: {}", path.display()))?;
+    Ok(DocTemplate {
+        name: name.to_string(),
+        builtin_type,
+        content,
+        source: Some(relative),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn project_templates_override_builtins_and_add_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("tmpl");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("runbook.md"), "# Runbook: {Task Name}\n").unwrap();
+        fs::write(dir.join("rfc.md"), "# RFC: {{title}}\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not a template").unwrap();
+
+        let mut config = PaveConfig::default();
+        config.templates.dir = Some(PathBuf::from("tmpl"));
+
+        let names: Vec<(String, Option<PathBuf>)> = list_templates(&config, temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.name, t.source))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("component".to_string(), None),
+                (
+                    "runbook".to_string(),
+                    Some(PathBuf::from("tmpl/runbook.md"))
+                ),
+                ("adr".to_string(), None),
+                ("postmortem".to_string(), None),
+                ("api".to_string(), None),
+                ("rfc".to_string(), Some(PathBuf::from("tmpl/rfc.md"))),
+            ]
+        );
+
+        let rfc = load_template(&config, temp_dir.path(), "rfc").unwrap();
+        assert_eq!(rfc.builtin_type, None);
+        assert_eq!(rfc.content, "# RFC: {{title}}\n");
+        let runbook = load_template(&config, temp_dir.path(), "runbook").unwrap();
+        assert_eq!(runbook.builtin_type, Some(TemplateType::Runbook));
+        assert_eq!(runbook.content, "# Runbook: {Task Name}\n");
+
+        let error = load_template(&config, temp_dir.path(), "memo").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown document type `memo`: expected one of component, runbook, adr, postmortem, \
+             api, rfc"
+        );
+    }
 
     #[test]
     fn component_template_has_required_sections() {
@@ -79,10 +304,30 @@ mod tests {
         assert!(template.contains("## Alternatives Considered"));
     }
 
+    #[test]
+    fn postmortem_template_has_required_sections() {
+        let template = get_template(TemplateType::Postmortem);
+        assert!(template.contains("## Impact"));
+        assert!(template.contains("## Timeline"));
+        assert!(template.contains("## Root Cause"));
+        assert!(template.contains("## Action Items"));
+        assert!(template.contains("## Verification"));
+    }
+
+    #[test]
+    fn api_template_has_required_sections() {
+        let template = get_template(TemplateType::Api);
+        assert!(template.contains("## Authentication"));
+        assert!(template.contains("## Endpoints"));
+        assert!(template.contains("## Errors"));
+        assert!(template.contains("## Verification"));
+        assert!(template.contains("## Examples"));
+    }
+
     #[test]
     fn all_templates_returns_all_types() {
         let all = TemplateType::all();
-        assert_eq!(all.len(), 3);
+        assert_eq!(all.len(), 5);
         assert!(all.contains(&TemplateType::Component));
         assert!(all.contains(&TemplateType::Runbook));
         assert!(all.contains(&TemplateType::Adr));
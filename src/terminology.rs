@@ -0,0 +1,184 @@
+//! Preferred terminology, from `[lint.terminology]`.
+//!
+//! Each entry maps a term to avoid to the term to use instead:
+//!
+//! ```toml
+//! [lint.terminology]
+//! "front-end" = "frontend"
+//! "K8s" = "Kubernetes"
+//! "Github" = "GitHub"
+//! ```
+//!
+//! Terms match as whole words, ignoring case, so `Front-end` is flagged too.
+//! A match already spelled exactly like the preferred term is left alone,
+//! which lets an entry fix casing alone. Inline code, link targets, and
+//! URLs are skipped; callers skip code blocks.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Spans of a line that aren't prose: inline code, link targets, and URLs.
+pub(crate) const NON_PROSE: &str = r"`[^`]*`|\]\([^)]*\)|[a-zA-Z][a-zA-Z0-9+.-]*://[^\s>)]*";
+
+/// A term to avoid and its replacement.
+#[derive(Debug, Clone)]
+struct Term {
+    pattern: Regex,
+    preferred: String,
+}
+
+/// An occurrence of a term to avoid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermMatch {
+    /// Byte range of the match in the line.
+    pub range: Range<usize>,
+    /// The text as written.
+    pub found: String,
+    /// What to write instead, matching the found text's initial capital.
+    pub replacement: String,
+}
+
+/// Compiled terminology rules.
+#[derive(Debug, Clone)]
+pub struct Terminology {
+    terms: Vec<Term>,
+    /// Matches [`NON_PROSE`] spans.
+    skipped: Regex,
+}
+
+impl Terminology {
+    /// Compile the entries of a `[lint.terminology]` table.
+    pub fn new(entries: &BTreeMap<String, String>) -> Result<Self> {
+        let mut terms = Vec::new();
+        for (avoid, preferred) in entries {
+            let avoid = avoid.trim();
+            if avoid.is_empty() {
+                continue;
+            }
+            // \b only works next to word characters, so look around instead
+            let pattern = Regex::new(&format!(
+                r"(?i)(?:^|[^\w-])({})(?:$|[^\w-])",
+                regex::escape(avoid)
+            ))
+            .with_context(|| format!("Invalid terminology entry '{}'", avoid))?;
+            terms.push(Term {
+                pattern,
+                preferred: preferred.clone(),
+            });
+        }
+        Ok(Self {
+            terms,
+            skipped: Regex::new(NON_PROSE).unwrap(),
+        })
+    }
+
+    /// Whether there are no terms to check.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Terms to avoid in a line of prose, in order.
+    pub fn find(&self, line: &str) -> Vec<TermMatch> {
+        let skipped: Vec<Range<usize>> = self.skipped.find_iter(line).map(|m| m.range()).collect();
+
+        let mut matches: Vec<TermMatch> = Vec::new();
+        for term in &self.terms {
+            let mut start = 0;
+            // Matches consume their surrounding characters, so step past
+            // each term rather than each match to find adjacent ones
+            while let Some(caps) = term.pattern.captures_at(line, start) {
+                let found = caps.get(1).unwrap();
+                start = found.end();
+                let range = found.range();
+                if found.as_str() == term.preferred
+                    || skipped
+                        .iter()
+                        .any(|s| s.start < range.end && range.start < s.end)
+                    || matches
+                        .iter()
+                        .any(|m| m.range.start < range.end && range.start < m.range.end)
+                {
+                    continue;
+                }
+                matches.push(TermMatch {
+                    range,
+                    found: found.as_str().to_string(),
+                    replacement: match_capital(found.as_str(), &term.preferred),
+                });
+            }
+        }
+        matches.sort_by_key(|m| m.range.start);
+        matches
+    }
+
+    /// The line with every term to avoid replaced.
+    pub fn replace(&self, line: &str) -> String {
+        let mut fixed = line.to_string();
+        for term in self.find(line).into_iter().rev() {
+            fixed.replace_range(term.range, &term.replacement);
+        }
+        fixed
+    }
+}
+
+/// Capitalize `preferred` if `found` starts with a capital and `preferred`
+/// doesn't, as in `Front-end` to `Frontend`.
+fn match_capital(found: &str, preferred: &str) -> String {
+    let found_upper = found.chars().next().is_some_and(char::is_uppercase);
+    let mut chars = preferred.chars();
+    match chars.next() {
+        Some(first) if found_upper && first.is_lowercase() => {
+            first.to_uppercase().chain(chars).collect()
+        }
+        _ => preferred.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminology(entries: &[(&str, &str)]) -> Terminology {
+        let entries = entries
+            .iter()
+            .map(|(avoid, preferred)| (avoid.to_string(), preferred.to_string()))
+            .collect();
+        Terminology::new(&entries).unwrap()
+    }
+
+    #[test]
+    fn finds_whole_words_ignoring_case() {
+        let terms = terminology(&[("front-end", "frontend"), ("K8s", "Kubernetes")]);
+
+        let found: Vec<(String, String)> = terms
+            .find("Front-end talks to k8s, not the front-ends or k8s-operator.")
+            .into_iter()
+            .map(|m| (m.found, m.replacement))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Front-end".to_string(), "Frontend".to_string()),
+                ("k8s".to_string(), "Kubernetes".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn fixes_casing_and_skips_code_and_links() {
+        let terms = terminology(&[("Github", "GitHub")]);
+
+        assert_eq!(
+            terms.replace("github and GitHub: see [github](https://github.com/x) or `github`"),
+            "GitHub and GitHub: see [GitHub](https://github.com/x) or `github`"
+        );
+        assert!(
+            terms
+                .find("<https://github.com> and https://github.com/x")
+                .is_empty()
+        );
+        assert_eq!(terms.replace("github github"), "GitHub GitHub");
+    }
+}
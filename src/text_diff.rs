@@ -0,0 +1,187 @@
+//! Line-based diffs for showing expected and actual output side by side.
+//!
+//! `pave verify` uses these to explain `pave:expect` mismatches: a unified
+//! diff with a few lines of context is much easier to read than two dumped
+//! strings when only one line of a long output differs.
+
+/// One line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// Present in both texts.
+    Same(&'a str),
+    /// Only in the expected text.
+    Removed(&'a str),
+    /// Only in the actual text.
+    Added(&'a str),
+}
+
+/// Largest expected × actual line product diffed line by line. Bigger inputs
+/// are shown as a whole removal followed by a whole addition.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Diff two texts line by line, keeping the longest common subsequence.
+pub fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .into_iter()
+            .map(DiffLine::Removed)
+            .chain(new.into_iter().map(DiffLine::Added))
+            .collect();
+    }
+
+    // lcs[i][j] is the common subsequence length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    diff.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+    diff
+}
+
+/// Render a diff in unified format with `context` unchanged lines around
+/// each change, e.g. `@@ -2,3 +2,3 @@` followed by ` `, `-` and `+` lines.
+///
+/// With `color`, removals are red, additions green, and hunk headers cyan.
+pub fn render_unified(diff: &[DiffLine], context: usize, color: bool) -> Vec<String> {
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+
+    let changes: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // Group changes whose context windows touch into hunks
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for idx in changes {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = Vec::new();
+    for (start, end) in hunks {
+        // Line numbers (1-indexed) of the hunk's first line in each text
+        let old_start = 1 + diff[..start]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_start = 1 + diff[..start]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let hunk = &diff[start..end];
+        let old_len = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+
+        out.push(paint(
+            "36",
+            format!(
+                "@@ -{},{} +{},{} @@",
+                old_start, old_len, new_start, new_len
+            ),
+        ));
+        for line in hunk {
+            out.push(match line {
+                DiffLine::Same(text) => format!(" {}", text),
+                DiffLine::Removed(text) => paint("31", format!("-{}", text)),
+                DiffLine::Added(text) => paint("32", format!("+{}", text)),
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_keeps_common_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nB\nc\nd\n");
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("B"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unified_output_limits_context_and_splits_hunks() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let actual = "1\nTWO\n3\n4\n5\n6\n7\n8\nNINE\n";
+
+        let lines = render_unified(&diff_lines(expected, actual), 1, false);
+
+        assert_eq!(
+            lines,
+            vec![
+                "@@ -1,3 +1,3 @@",
+                " 1",
+                "-2",
+                "+TWO",
+                " 3",
+                "@@ -8,2 +8,2 @@",
+                " 8",
+                "-9",
+                "+NINE",
+            ]
+        );
+    }
+
+    #[test]
+    fn unified_output_colors_changes() {
+        let lines = render_unified(&diff_lines("a\n", "b\n"), 3, true);
+
+        assert_eq!(lines[1], "\x1b[31m-a\x1b[0m");
+        assert_eq!(lines[2], "\x1b[32m+b\x1b[0m");
+    }
+}
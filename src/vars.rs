@@ -0,0 +1,171 @@
+//! Project variables substituted into document content.
+//!
+//! Values come from the `[vars]` table in `.pave.toml`:
+//!
+//! ```toml
+//! [vars]
+//! service_url = "https://api.example.com"
+//! ```
+//!
+//! and are referenced as `{{ vars.service_url }}` anywhere in a document,
+//! code blocks included, so verification commands can use them too. Inline
+//! code spans are left alone, so prose can show the placeholder syntax.
+//! `pave build` renders them, `pave verify` substitutes them into commands
+//! before running them, and `pave check` reports references to variables
+//! that aren't defined.
+
+use pulldown_cmark::{Event, Options, Parser};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// A variable reference; the first group is the name.
+static VAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*vars\.([A-Za-z0-9_-]+)\s*\}\}").unwrap());
+
+/// A variable reference in a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarRef {
+    /// 1-indexed line of the reference.
+    pub line: usize,
+    /// Variable name, without the `vars.` prefix.
+    pub name: String,
+}
+
+/// Variable references in document `content`, in order, outside inline code.
+pub fn find_vars(content: &str) -> Vec<VarRef> {
+    let inline_code = inline_code_ranges(content);
+    VAR.captures_iter(content)
+        .filter(|caps| !in_ranges(&inline_code, caps.get(0).unwrap().start()))
+        .map(|caps| VarRef {
+            line: content[..caps.get(0).unwrap().start()]
+                .matches('\n')
+                .count()
+                + 1,
+            name: caps[1].to_string(),
+        })
+        .collect()
+}
+
+/// Replace the references in document `content` to variables defined in
+/// `vars` with their values. References to undefined variables and those in
+/// inline code are left as written.
+pub fn render(content: &str, vars: &BTreeMap<String, String>) -> String {
+    if vars.is_empty() {
+        return content.to_string();
+    }
+    let inline_code = inline_code_ranges(content);
+    VAR.replace_all(content, |caps: &regex::Captures| {
+        let whole = caps.get(0).unwrap();
+        match vars.get(&caps[1]) {
+            Some(value) if !in_ranges(&inline_code, whole.start()) => value.clone(),
+            _ => whole.as_str().to_string(),
+        }
+    })
+    .into_owned()
+}
+
+/// Like [`render`], for a command rather than a document: every reference
+/// is replaced, backticks included.
+pub fn render_command(command: &str, vars: &BTreeMap<String, String>) -> String {
+    VAR.replace_all(command, |caps: &regex::Captures| match vars.get(&caps[1]) {
+        Some(value) => value.clone(),
+        None => caps[0].to_string(),
+    })
+    .into_owned()
+}
+
+/// References in document `content` to variables not defined in `vars`.
+pub fn check(content: &str, vars: &BTreeMap<String, String>) -> Vec<VarRef> {
+    find_vars(content)
+        .into_iter()
+        .filter(|var| !vars.contains_key(&var.name))
+        .collect()
+}
+
+/// Byte ranges of the inline code spans in `content`.
+fn inline_code_ranges(content: &str) -> Vec<Range<usize>> {
+    Parser::new_ext(content, Options::ENABLE_TABLES)
+        .into_offset_iter()
+        .filter(|(event, _)| matches!(event, Event::Code(_)))
+        .map(|(_, range)| range)
+        .collect()
+}
+
+fn in_ranges(ranges: &[Range<usize>], offset: usize) -> bool {
+    ranges.iter().any(|range| range.contains(&offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> BTreeMap<String, String> {
+        BTreeMap::from([(
+            "service_url".to_string(),
+            "https://api.example.com".to_string(),
+        )])
+    }
+
+    #[test]
+    fn find_vars_reports_each_reference_with_its_line() {
+        let content = "# Deploy\n\ncurl {{ vars.service_url }}/health {{vars.token}}\n";
+        assert_eq!(
+            find_vars(content),
+            vec![
+                VarRef {
+                    line: 3,
+                    name: "service_url".to_string()
+                },
+                VarRef {
+                    line: 3,
+                    name: "token".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_substitutes_defined_vars_and_keeps_undefined_ones() {
+        let content = "```bash\ncurl {{ vars.service_url }}/health\n```\n{{ vars.missing }}";
+        assert_eq!(
+            render(content, &vars()),
+            "```bash\ncurl https://api.example.com/health\n```\n{{ vars.missing }}"
+        );
+    }
+
+    #[test]
+    fn inline_code_keeps_placeholders() {
+        let content = "Write `{{ vars.service_url }}` to get {{ vars.service_url }}.\n\
+                       ```bash\ncurl `{{ vars.region }}`\n```\n";
+        assert_eq!(
+            render(content, &vars()),
+            "Write `{{ vars.service_url }}` to get https://api.example.com.\n\
+             ```bash\ncurl `{{ vars.region }}`\n```\n"
+        );
+        assert_eq!(
+            check(content, &vars()),
+            vec![VarRef {
+                line: 3,
+                name: "region".to_string()
+            }]
+        );
+        assert_eq!(
+            render_command("echo `{{ vars.service_url }}`", &vars()),
+            "echo `https://api.example.com`"
+        );
+    }
+
+    #[test]
+    fn check_flags_undefined_vars() {
+        let content = "{{ vars.service_url }}\n\n{{ vars.region }}\n";
+        assert_eq!(
+            check(content, &vars()),
+            vec![VarRef {
+                line: 3,
+                name: "region".to_string()
+            }]
+        );
+    }
+}
@@ -5,13 +5,20 @@
 //! - Execute verification commands with timeout and output capture
 //! - Report results including pass/fail status, timing, and error details
 
+use std::borrow::Cow;
+use std::io::Write;
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use crate::parser::{CodeBlock, ExpectMatchStrategy, ParsedDoc};
+use crate::compose::ComposeBootstrap;
+use crate::config::{Shell, VerifySection};
+use crate::http::{self, HTTP_LANGUAGE, Request};
+use crate::parser::{BlockPhase, CodeBlock, ExpectMatchStrategy, ExpectedOutput, ParsedDoc};
+use crate::services::Service;
+use crate::sql::{self, SQL_LANGUAGE};
 
 /// Default timeout for command execution in seconds.
 pub const DEFAULT_TIMEOUT_SECS: u32 = 30;
@@ -25,6 +32,8 @@ pub enum OutputMatcher {
     Regex(String),
     /// Match if stdout matches exactly (after trimming whitespace).
     Exact(String),
+    /// Match if stdout does not contain the given substring.
+    NotContains(String),
     /// Only check the exit code, ignore output.
     ExitCodeOnly,
 }
@@ -40,10 +49,120 @@ pub struct VerificationItem {
     pub expected_exit_code: Option<i32>,
     /// How to validate command output.
     pub expected_output: Option<OutputMatcher>,
-    /// Timeout in seconds (default: 30).
+    /// Timeout in seconds (from a `pave:timeout` marker); `None` uses the
+    /// run's default (30 seconds unless `pave verify --timeout` says otherwise).
     pub timeout_secs: Option<u32>,
+    /// How many more times to run the command if it fails (from a
+    /// `pave:retries` marker).
+    pub retries: u32,
     /// Environment variables to set for this command.
     pub env_vars: Vec<(String, String)>,
+    /// Secrets passed through to this command by name; their values are
+    /// looked up at run time and never stored.
+    pub secrets: Vec<String>,
+    /// Container image to run the command in (from a `pave:container`
+    /// marker), overriding `[verify] container`.
+    pub container: Option<String>,
+    /// Host from `[verify.hosts]` to run the command on over SSH (from a
+    /// `pave:host` marker).
+    pub host: Option<String>,
+    /// Database from `[verify.databases]` a `sql` block queries (from a
+    /// `pave:database` marker).
+    pub database: Option<String>,
+    /// Whether to compile `script` instead of running it (from a
+    /// `pave:compile` marker on an example).
+    pub compile: bool,
+    /// Shell override (from a `pave:shell` marker or the fence language).
+    pub shell: Option<String>,
+    /// Fence language of a non-shell block, used to pick an interpreter.
+    pub language: Option<String>,
+    /// Raw block content, piped to the interpreter when one is configured.
+    pub script: Option<String>,
+    /// Start line of the block this item is a prompt of. The prompts of a
+    /// block run one after another in a single shell, so `cd` and `export`
+    /// carry over from one to the next.
+    pub session: Option<usize>,
+}
+
+/// How a verification item is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Invocation {
+    /// Run the command string with a shell.
+    Shell(Shell),
+    /// Pipe the script to an interpreter command line.
+    Interpreter(Vec<String>),
+    /// Pipe the `sql` block's query to its database client.
+    Database(sql::Client),
+    /// Send the `http` block's request with `curl`.
+    Http,
+}
+
+impl VerificationItem {
+    /// Decide how to run this item.
+    ///
+    /// Examples marked `pave:compile` go to the compiler for their language.
+    /// Otherwise a `pave:shell` marker wins, then an `http` request, a `sql`
+    /// query's database client, or an interpreter for the block's language,
+    /// then the configured shell. Returns an error message if the item names
+    /// an unknown shell or database, is a malformed request, or is an
+    /// example with no compiler.
+    pub fn invocation(&self, verify: &VerifySection) -> Result<Invocation, String> {
+        if self.compile {
+            let language = self.language.as_deref().unwrap_or_default();
+            return verify
+                .compiler(language)
+                .map(Invocation::Interpreter)
+                .ok_or_else(|| {
+                    format!(
+                        "no compiler for '{}' examples; add one to [verify.compilers]",
+                        language
+                    )
+                });
+        }
+        if let Some(url) = self.database_url(verify) {
+            return sql::client(&sql::expand(url?)?).map(Invocation::Database);
+        }
+        if self.shell.is_none()
+            && let Some(language) = &self.language
+        {
+            if language == HTTP_LANGUAGE {
+                return self.request().map(|_| Invocation::Http);
+            }
+            if let Some(argv) = verify.interpreter(language) {
+                return Ok(Invocation::Interpreter(argv));
+            }
+        }
+        self.resolve_shell(verify.shell()).map(Invocation::Shell)
+    }
+
+    /// The configured connection string of a `sql` block, before `${NAME}`
+    /// references are expanded. `None` for other blocks.
+    pub fn database_url<'a>(&self, verify: &'a VerifySection) -> Option<Result<&'a str, String>> {
+        (self.shell.is_none() && self.language.as_deref() == Some(SQL_LANGUAGE))
+            .then(|| sql::select(&verify.databases, self.database.as_deref()))
+    }
+
+    /// The request of an `http` block.
+    pub fn request(&self) -> Result<Request, String> {
+        Request::parse(self.script.as_deref().unwrap_or(&self.command))
+    }
+
+    /// Resolve the shell to run this item with.
+    ///
+    /// Returns an error message if the item names an unknown shell.
+    pub fn resolve_shell(&self, default: Shell) -> Result<Shell, String> {
+        match &self.shell {
+            None => Ok(default),
+            Some(name) => Shell::from_name(name).ok_or_else(|| {
+                let known: Vec<&str> = Shell::ALL.iter().map(|shell| shell.name()).collect();
+                format!(
+                    "unknown shell '{}' (expected one of: {})",
+                    name,
+                    known.join(", ")
+                )
+            }),
+        }
+    }
 }
 
 impl Default for VerificationItem {
@@ -53,8 +172,18 @@ impl Default for VerificationItem {
             working_dir: None,
             expected_exit_code: Some(0),
             expected_output: None,
-            timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+            timeout_secs: None,
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         }
     }
 }
@@ -68,6 +197,23 @@ pub struct VerificationSpec {
     pub section_line: usize,
     /// List of verification items to execute.
     pub items: Vec<VerificationItem>,
+    /// Commands that prepare a shared fixture before the items run.
+    pub setup: Vec<VerificationItem>,
+    /// Commands that clean up the fixture after the items run.
+    pub teardown: Vec<VerificationItem>,
+    /// Compose services started before setup and stopped after teardown.
+    pub compose: Option<ComposeFixture>,
+    /// Services from `pave.services`, started before the compose bootstrap.
+    pub services: Vec<Service>,
+}
+
+/// Compose services a document brings up around its commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeFixture {
+    /// The document's `pave.verify.compose` settings.
+    pub bootstrap: ComposeBootstrap,
+    /// Working directory from frontmatter, where the compose file is found.
+    pub working_dir: Option<PathBuf>,
 }
 
 /// Result of executing a single verification item.
@@ -92,70 +238,210 @@ pub struct VerificationResult {
 /// Extract a verification specification from a parsed document.
 ///
 /// Looks for a "Verification" section and extracts executable code blocks
-/// as verification items.
+/// as verification items, followed by "Examples" blocks marked
+/// `pave:compile`, which are compiled rather than run.
 ///
 /// # Arguments
 /// * `doc` - The parsed markdown document
 ///
 /// # Returns
-/// `Some(VerificationSpec)` if a Verification section with commands exists,
+/// `Some(VerificationSpec)` if there are commands or examples to verify,
 /// `None` otherwise.
 pub fn extract_verification_spec(doc: &ParsedDoc) -> Option<VerificationSpec> {
-    let section = doc.get_section("Verification")?;
+    let section = doc.get_section("Verification");
+    let examples = doc.get_section("Examples");
 
-    let executable_blocks: Vec<&CodeBlock> = section.executable_commands();
-
-    if executable_blocks.is_empty() {
-        return None;
-    }
+    let executable_blocks: Vec<&CodeBlock> = section
+        .map(|section| section.executable_commands())
+        .unwrap_or_default();
+    let compile_blocks: Vec<&CodeBlock> = examples
+        .map(|examples| examples.code_blocks.iter().filter(|b| b.compile).collect())
+        .unwrap_or_default();
 
     // Get default working_dir from frontmatter
-    let default_working_dir = doc
-        .frontmatter
-        .as_ref()
+    let frontmatter = doc.frontmatter.as_ref();
+    let default_working_dir = frontmatter
         .and_then(|fm| fm.working_dir.as_ref())
         .map(PathBuf::from);
 
-    let items: Vec<VerificationItem> = executable_blocks
+    let to_item = |block: &CodeBlock| {
+        let command = extract_command_from_block(&block.content);
+        let expected_output = convert_expected_output(block);
+        // Per-block working_dir overrides frontmatter default
+        let working_dir = block
+            .working_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(|| default_working_dir.clone());
+        let shell = block_shell(block);
+        let language = interpreter_language(block, shell.as_deref());
+        let script = language.as_ref().map(|_| block.content.clone());
+        let item = VerificationItem {
+            command,
+            working_dir,
+            expected_exit_code: Some(block.exit_code.unwrap_or(0)),
+            expected_output,
+            timeout_secs: block.timeout_secs,
+            retries: block.retries,
+            env_vars: block.env_vars.clone(),
+            secrets: block.secrets.clone(),
+            container: block.container.clone(),
+            host: block.host.clone(),
+            database: block.database.clone(),
+            compile: false,
+            shell,
+            language,
+            script,
+            session: None,
+        };
+        if !runs_per_prompt(block) {
+            return vec![item];
+        }
+        // Earlier prompts must succeed for the rest to run, so only the last
+        // one takes the block's `pave:exit-code`
+        let last = block.prompts.len() - 1;
+        block
+            .prompts
+            .iter()
+            .enumerate()
+            .map(|(index, prompt)| VerificationItem {
+                command: prompt.command.clone(),
+                expected_output: prompt.expected_output.as_ref().map(to_matcher),
+                expected_exit_code: if index == last {
+                    item.expected_exit_code
+                } else {
+                    Some(0)
+                },
+                session: Some(block.start_line),
+                ..item.clone()
+            })
+            .collect()
+    };
+    let compile_item = |block: &CodeBlock| VerificationItem {
+        command: block.content.clone(),
+        working_dir: block
+            .working_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(|| default_working_dir.clone()),
+        expected_exit_code: Some(block.exit_code.unwrap_or(0)),
+        timeout_secs: block.timeout_secs,
+        retries: block.retries,
+        env_vars: block.env_vars.clone(),
+        container: block.container.clone(),
+        compile: true,
+        language: block.language.as_deref().map(str::to_lowercase),
+        script: Some(block.content.clone()),
+        ..VerificationItem::default()
+    };
+    let frontmatter_item = |command: &String| VerificationItem {
+        command: command.clone(),
+        working_dir: default_working_dir.clone(),
+        ..VerificationItem::default()
+    };
+    let blocks_in = |phase: BlockPhase| {
+        executable_blocks
+            .iter()
+            .filter(move |block| block.phase == phase)
+            .flat_map(|block| to_item(block))
+    };
+
+    let items: Vec<VerificationItem> = blocks_in(BlockPhase::Verify)
+        .chain(compile_blocks.iter().map(|block| compile_item(block)))
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+
+    // Frontmatter setup runs first and frontmatter teardown runs last, so
+    // the document's own fixture nests inside it
+    let no_commands = Vec::new();
+    let (fm_setup, fm_teardown) = frontmatter
+        .map(|fm| (&fm.setup, &fm.teardown))
+        .unwrap_or((&no_commands, &no_commands));
+    let setup = fm_setup
+        .iter()
+        .map(frontmatter_item)
+        .chain(blocks_in(BlockPhase::Setup))
+        .collect();
+    let teardown = blocks_in(BlockPhase::Teardown)
+        .chain(fm_teardown.iter().map(frontmatter_item))
+        .collect();
+    let compose = frontmatter
+        .and_then(|fm| fm.verify.as_ref())
+        .and_then(|verify| verify.compose.clone())
+        .map(|bootstrap| ComposeFixture {
+            bootstrap,
+            working_dir: default_working_dir.clone(),
+        });
+    // Compose files are relative to the document's working directory, like
+    // the compose bootstrap's
+    let services = frontmatter
+        .map(|fm| fm.services.clone())
+        .unwrap_or_default()
         .into_iter()
-        .map(|block| {
-            let command = extract_command_from_block(&block.content);
-            let expected_output = convert_expected_output(block);
-            // Per-block working_dir overrides frontmatter default
-            let working_dir = block
-                .working_dir
-                .as_ref()
-                .map(PathBuf::from)
-                .or_else(|| default_working_dir.clone());
-            VerificationItem {
-                command,
-                working_dir,
-                expected_exit_code: Some(0),
-                expected_output,
-                timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
-                env_vars: block.env_vars.clone(),
+        .map(|mut service| {
+            if let (Some(file), Some(dir)) = (&mut service.compose, &default_working_dir) {
+                *file = dir.join(&*file).to_string_lossy().into_owned();
             }
+            service
         })
         .collect();
 
     Some(VerificationSpec {
         source_file: doc.path.clone(),
-        section_line: section.start_line,
+        section_line: section.or(examples).map_or(0, |section| section.start_line),
         items,
+        setup,
+        teardown,
+        compose,
+        services,
+    })
+}
+
+/// Whether a block runs as one item per prompt.
+///
+/// Output written between prompts splits a shell block so each command is
+/// checked against its own output. Blocks piped to an interpreter run whole.
+pub fn runs_per_prompt(block: &CodeBlock) -> bool {
+    !block.prompts.is_empty()
+        && interpreter_language(block, block_shell(block).as_deref()).is_none()
+}
+
+/// Shell a block asks for, from a `pave:shell` marker or its fence language.
+fn block_shell(block: &CodeBlock) -> Option<String> {
+    // Explicit marker wins over a shell implied by the fence language
+    block.shell.clone().or_else(|| {
+        block
+            .language
+            .as_deref()
+            .and_then(Shell::for_language)
+            .map(|shell| shell.name().to_string())
     })
 }
 
+/// Language of a non-shell block, which may run with an interpreter instead.
+fn interpreter_language(block: &CodeBlock, shell: Option<&str>) -> Option<String> {
+    block
+        .language
+        .as_deref()
+        .filter(|lang| shell.is_none() && !is_shell_language(lang))
+        .map(str::to_lowercase)
+}
+
 /// Convert parsed expected output to an OutputMatcher.
 fn convert_expected_output(block: &CodeBlock) -> Option<OutputMatcher> {
-    let expected = block.expected_output.as_ref()?;
+    block.expected_output.as_ref().map(to_matcher)
+}
 
-    let matcher = match expected.strategy {
+/// Convert one expected output to the matcher for its strategy.
+fn to_matcher(expected: &ExpectedOutput) -> OutputMatcher {
+    match expected.strategy {
         ExpectMatchStrategy::Contains => OutputMatcher::Contains(expected.content.clone()),
         ExpectMatchStrategy::Regex => OutputMatcher::Regex(expected.content.clone()),
         ExpectMatchStrategy::Exact => OutputMatcher::Exact(expected.content.clone()),
-    };
-
-    Some(matcher)
+        ExpectMatchStrategy::NotContains => OutputMatcher::NotContains(expected.content.clone()),
+    }
 }
 
 /// Extract the command string from a code block's content.
@@ -193,6 +479,232 @@ fn extract_command_from_block(content: &str) -> String {
     commands.join(" && ")
 }
 
+/// Check if a fence language denotes a POSIX shell block.
+fn is_shell_language(language: &str) -> bool {
+    matches!(
+        language.to_lowercase().as_str(),
+        "bash" | "sh" | "shell" | "zsh" | "console"
+    )
+}
+
+/// Build a process that runs `command` with the given shell.
+pub fn shell_command(shell: Shell, command: &str) -> Command {
+    let mut cmd = Command::new(shell.name());
+    cmd.args(shell.command_args()).arg(command);
+    cmd
+}
+
+/// Build the process for an item along with the input to feed its stdin.
+pub fn build_command<'a>(
+    item: &'a VerificationItem,
+    invocation: &Invocation,
+    secrets: &[(String, String)],
+) -> (Command, Option<Cow<'a, str>>) {
+    match invocation {
+        Invocation::Shell(shell) => (shell_command(*shell, &item.command), None),
+        Invocation::Interpreter(argv) => {
+            let mut cmd = Command::new(&argv[0]);
+            cmd.args(&argv[1..]);
+            let script = item.script.as_deref().unwrap_or(&item.command);
+            (cmd, Some(Cow::Borrowed(script)))
+        }
+        Invocation::Database(client) => {
+            let mut cmd = Command::new(&client.argv[0]);
+            cmd.args(&client.argv[1..]).envs(client.env.iter().cloned());
+            let script = item.script.as_deref().unwrap_or(&item.command);
+            (cmd, Some(Cow::Borrowed(script)))
+        }
+        Invocation::Http => {
+            let request = item
+                .request()
+                .expect("invocation rejects malformed requests");
+            let request = request.substitute(|name| {
+                item.env_vars
+                    .iter()
+                    .chain(secrets)
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+                    .or_else(|| std::env::var(name).ok())
+            });
+            (request.command(), Some(Cow::Owned(request.curl_config())))
+        }
+    }
+}
+
+/// The exit code and output to report for a finished command.
+///
+/// An `http` request that got a response reports its status and body
+/// instead of curl's raw output.
+pub fn command_outcome(
+    invocation: &Invocation,
+    exit_code: Option<i32>,
+    stdout: String,
+) -> (Option<i32>, String) {
+    if matches!(invocation, Invocation::Http)
+        && exit_code == Some(0)
+        && let Some((output, code)) = http::response(&stdout)
+    {
+        return (Some(code), output);
+    }
+    (exit_code, stdout)
+}
+
+/// Spawn a process with captured output, writing `input` to its stdin.
+///
+/// The input is written from a separate thread so a process that fills its
+/// output pipes before reading stdin cannot deadlock.
+pub fn spawn_with_input(cmd: &mut Command, input: Option<&str>) -> std::io::Result<Child> {
+    cmd.stdin(if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    });
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd.spawn()?;
+    if let Some(input) = input
+        && let Some(mut stdin) = child.stdin.take()
+    {
+        let input = input.to_string();
+        thread::spawn(move || {
+            // A process that exits without reading stdin closes the pipe
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    Ok(child)
+}
+
+/// Check a command for shell syntax errors before it is executed.
+///
+/// Detects unbalanced quotes with a position hint, then defers to the
+/// shell's `-n` mode for everything else. Returns a human-readable message
+/// describing the first problem found, or `None` if the command parses
+/// cleanly. Non-POSIX shells are not checked.
+pub fn check_shell_syntax(command: &str, shell: Shell) -> Option<String> {
+    if !shell.is_posix() {
+        return None;
+    }
+
+    if let Some(msg) = find_unbalanced_quote(command) {
+        return Some(msg);
+    }
+
+    let output = Command::new(shell.name())
+        .arg("-n")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        return None;
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = stderr
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("the shell rejected the command");
+    Some(format!("shell syntax error: {}", detail))
+}
+
+/// Look for constructs that are legal shell but risky in documentation.
+///
+/// Flags legacy backtick substitution and command substitutions that fetch
+/// remote content (`$(curl ...)`, `$(wget ...)`). Text inside single quotes
+/// is ignored since the shell never expands it.
+pub fn find_suspicious_construct(command: &str) -> Option<String> {
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut escaped = false;
+    let mut token_start = true;
+
+    for (idx, c) in command.char_indices() {
+        if escaped {
+            escaped = false;
+            token_start = false;
+            continue;
+        }
+        let quoted = in_single || in_double;
+        if !quoted && token_start && c == '#' {
+            break;
+        }
+        token_start = !quoted && ends_token(c);
+        match c {
+            '\\' if !in_single => escaped = true,
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '`' if !in_single => {
+                return Some(format!(
+                    "backtick command substitution at column {}; use $(...) instead",
+                    idx + 1
+                ));
+            }
+            '$' if !in_single => {
+                let rest = command[idx + 1..].strip_prefix('(').map(str::trim_start);
+                if let Some(rest) = rest {
+                    for fetcher in ["curl", "wget"] {
+                        if rest
+                            .strip_prefix(fetcher)
+                            .is_some_and(|after| after.is_empty() || after.starts_with(' '))
+                        {
+                            return Some(format!(
+                                "command substitution fetches remote content at column {}: $({} ...)",
+                                idx + 1,
+                                fetcher
+                            ));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Whether an unquoted `c` ends a shell token, so that a `#` after it
+/// starts a comment.
+fn ends_token(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')' | '<' | '>')
+}
+
+/// Find the first unterminated single or double quote in a command.
+fn find_unbalanced_quote(command: &str) -> Option<String> {
+    let mut open: Option<(char, usize)> = None;
+    let mut escaped = false;
+    let mut token_start = true;
+
+    for (idx, c) in command.char_indices() {
+        if escaped {
+            escaped = false;
+            token_start = false;
+            continue;
+        }
+        if open.is_none() && token_start && c == '#' {
+            break;
+        }
+        token_start = open.is_none() && ends_token(c);
+        match (open, c) {
+            (Some(('\'', _)), '\'') => open = None,
+            (Some(('\'', _)), _) => {}
+            (_, '\\') => escaped = true,
+            (Some(('"', _)), '"') => open = None,
+            (None, '\'' | '"') => open = Some((c, idx)),
+            _ => {}
+        }
+    }
+
+    open.map(|(quote, idx)| {
+        let kind = if quote == '\'' { "single" } else { "double" };
+        format!("unbalanced {} quote starting at column {}", kind, idx + 1)
+    })
+}
+
 /// Execute all verification items in a specification.
 ///
 /// Runs each command and collects results including:
@@ -205,9 +717,24 @@ fn extract_command_from_block(content: &str) -> String {
 /// * `spec` - The verification specification to execute
 ///
 /// # Returns
-/// A vector of `VerificationResult` for each item in the spec.
+/// A vector of `VerificationResult` for each setup command, item, and
+/// teardown command in the spec. If a setup command fails, the items are not
+/// run but teardown still is.
 pub fn run_verification(spec: &VerificationSpec) -> Vec<VerificationResult> {
-    spec.items.iter().map(run_single_verification).collect()
+    let mut results = Vec::new();
+    for item in &spec.setup {
+        let result = run_single_verification(item);
+        let passed = result.passed;
+        results.push(result);
+        if !passed {
+            break;
+        }
+    }
+    if results.iter().all(|result| result.passed) {
+        results.extend(spec.items.iter().map(run_single_verification));
+    }
+    results.extend(spec.teardown.iter().map(run_single_verification));
+    results
 }
 
 /// Execute a single verification item.
@@ -219,8 +746,21 @@ fn run_single_verification(item: &VerificationItem) -> VerificationResult {
     let item_clone = item.clone();
 
     // Spawn the command
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c").arg(&item.command);
+    let invocation = match item.invocation(&VerifySection::default()) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            return VerificationResult {
+                item: item_clone,
+                passed: false,
+                exit_code: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                duration_ms: 0,
+                error: Some(message),
+            };
+        }
+    };
+    let (mut cmd, input) = build_command(item, &invocation, &[]);
 
     if let Some(ref working_dir) = item.working_dir {
         cmd.current_dir(working_dir);
@@ -231,10 +771,7 @@ fn run_single_verification(item: &VerificationItem) -> VerificationResult {
         cmd.env(key, value);
     }
 
-    cmd.stdout(Stdio::piped());
-    cmd.stderr(Stdio::piped());
-
-    let child = match cmd.spawn() {
+    let child = match spawn_with_input(&mut cmd, input.as_deref()) {
         Ok(child) => child,
         Err(e) => {
             return VerificationResult {
@@ -261,9 +798,9 @@ fn run_single_verification(item: &VerificationItem) -> VerificationResult {
         Ok(Ok(output)) => {
             let _ = handle.join();
             let duration_ms = start.elapsed().as_millis() as u64;
-            let exit_code = output.status.code();
             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            let (exit_code, stdout) = command_outcome(&invocation, output.status.code(), stdout);
 
             let expected_code = item.expected_exit_code.unwrap_or(0);
             let code_matches = exit_code == Some(expected_code);
@@ -276,6 +813,7 @@ fn run_single_verification(item: &VerificationItem) -> VerificationResult {
                     .map(|re| re.is_match(&stdout))
                     .unwrap_or(false),
                 Some(OutputMatcher::Exact(expected)) => stdout.trim() == expected.trim(),
+                Some(OutputMatcher::NotContains(substring)) => !stdout.contains(substring),
             };
 
             let passed = code_matches && output_matches;
@@ -340,7 +878,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -359,7 +907,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -376,7 +934,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(1),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -393,7 +961,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -410,7 +988,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -427,7 +1015,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -445,7 +1043,17 @@ mod tests {
             expected_exit_code: Some(42),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -462,7 +1070,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("world".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -478,7 +1096,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("foo".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -494,7 +1122,17 @@ mod tests {
             expected_exit_code: Some(0),
             expected_output: None,
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -558,6 +1196,57 @@ echo "test"
         assert_eq!(spec.items[0].command, "echo \"test\"");
     }
 
+    #[test]
+    fn test_extract_verification_spec_compiles_marked_examples() {
+        let content = r#"# Test Doc
+
+## Examples
+<!-- pave:compile -->
+```rust
+pub fn answer() -> u32 { 42 }
+```
+
+```python
+print("not compiled")
+```
+
+## Verification
+```bash
+echo "test"
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 2);
+        let example = &spec.items[1];
+        assert!(example.compile);
+        assert_eq!(example.language.as_deref(), Some("rust"));
+        assert_eq!(
+            example.script.as_deref(),
+            Some("pub fn answer() -> u32 { 42 }")
+        );
+        let argv = match example.invocation(&VerifySection::default()) {
+            Ok(Invocation::Interpreter(argv)) => argv,
+            other => panic!("unexpected invocation: {:?}", other),
+        };
+        assert_eq!(argv[0], "rustc");
+
+        // Examples alone are enough for a spec
+        let doc = ParsedDoc::parse_content(
+            PathBuf::from("test.md"),
+            "# Doc\n\n## Examples\n<!-- pave:compile -->\n```go\npackage main\n```\n",
+        )
+        .unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        assert_eq!(spec.section_line, 3);
+        assert_eq!(
+            spec.items[0].invocation(&VerifySection::default()),
+            Err("no compiler for 'go' examples; add one to [verify.compilers]".to_string())
+        );
+    }
+
     #[test]
     fn test_extract_verification_spec_no_verification_section() {
         let content = r#"# Test Doc
@@ -619,7 +1308,17 @@ echo "second"
                     expected_exit_code: Some(0),
                     expected_output: None,
                     timeout_secs: Some(5),
+                    retries: 0,
                     env_vars: Vec::new(),
+                    secrets: Vec::new(),
+                    container: None,
+                    host: None,
+                    database: None,
+                    compile: false,
+                    shell: None,
+                    language: None,
+                    script: None,
+                    session: None,
                 },
                 VerificationItem {
                     command: "echo 'second'".to_string(),
@@ -627,9 +1326,23 @@ echo "second"
                     expected_exit_code: Some(0),
                     expected_output: None,
                     timeout_secs: Some(5),
+                    retries: 0,
                     env_vars: Vec::new(),
+                    secrets: Vec::new(),
+                    container: None,
+                    host: None,
+                    database: None,
+                    compile: false,
+                    shell: None,
+                    language: None,
+                    script: None,
+                    session: None,
                 },
             ],
+            setup: vec![],
+            teardown: vec![],
+            compose: None,
+            services: Vec::new(),
         };
 
         let results = run_verification(&spec);
@@ -649,7 +1362,17 @@ echo "second"
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("Hello, World!".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -669,7 +1392,17 @@ echo "second"
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Regex(r"test \d+ passed".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -685,7 +1418,17 @@ echo "second"
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Regex(r"test \d+ passed".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -701,7 +1444,17 @@ echo "second"
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Exact("hello".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -717,7 +1470,17 @@ echo "second"
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Exact("hello".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: Vec::new(),
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -752,6 +1515,66 @@ hello
         }
     }
 
+    #[test]
+    fn test_extract_verification_spec_output_between_prompts() {
+        let content = r#"# Test Doc
+
+## Verification
+<!-- pave:working_dir sub -->
+```bash
+$ echo one
+one
+$ echo two
+two
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 2);
+        assert_eq!(spec.items[0].command, "echo one");
+        assert_eq!(
+            spec.items[0].expected_output,
+            Some(OutputMatcher::Contains("one".to_string()))
+        );
+        assert_eq!(spec.items[1].command, "echo two");
+        assert_eq!(
+            spec.items[1].expected_output,
+            Some(OutputMatcher::Contains("two".to_string()))
+        );
+        assert!(
+            spec.items
+                .iter()
+                .all(|item| item.working_dir == Some(PathBuf::from("sub"))
+                    && item.session == Some(5))
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_exit_code_applies_to_last_prompt() {
+        let content = r#"# Test Doc
+
+## Verification
+<!-- pave:exit-code 2 -->
+```bash
+$ echo one
+one
+$ grep -q missing file.txt
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        let exit_codes: Vec<Option<i32>> = spec
+            .items
+            .iter()
+            .map(|item| item.expected_exit_code)
+            .collect();
+        assert_eq!(exit_codes, [Some(0), Some(2)]);
+    }
+
     #[test]
     fn test_extract_verification_spec_with_explicit_output_block() {
         let content = r#"# Test Doc
@@ -828,6 +1651,234 @@ cargo test
         assert_eq!(item.working_dir, Some(PathBuf::from("src/tests")));
     }
 
+    #[test]
+    fn test_extract_verification_spec_shell_from_marker_and_language() {
+        let content = r#"# Windows Setup
+
+## Verification
+```powershell
+Get-Service
+```
+<!-- pave:shell powershell -->
+```pwsh
+Get-Process
+```
+```bash
+cargo test
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 3);
+        assert_eq!(spec.items[0].shell, Some("pwsh".to_string()));
+        assert_eq!(spec.items[1].shell, Some("powershell".to_string()));
+        assert_eq!(spec.items[2].shell, None);
+    }
+
+    #[test]
+    fn test_extract_verification_spec_setup_and_teardown() {
+        let content = r#"---
+pave:
+  setup:
+    - docker compose up -d
+  teardown:
+    - docker compose down
+---
+# Service
+
+## Verification
+<!-- pave:setup -->
+```bash
+./seed-db.sh
+```
+```bash
+curl localhost:8080/health
+```
+<!-- pave:teardown -->
+```bash
+./drop-db.sh
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        let commands = |items: &[VerificationItem]| -> Vec<String> {
+            items.iter().map(|item| item.command.clone()).collect()
+        };
+        assert_eq!(commands(&spec.items), vec!["curl localhost:8080/health"]);
+        assert_eq!(
+            commands(&spec.setup),
+            vec!["docker compose up -d", "./seed-db.sh"]
+        );
+        assert_eq!(
+            commands(&spec.teardown),
+            vec!["./drop-db.sh", "docker compose down"]
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_compose_bootstrap() {
+        let content = r#"---
+pave:
+  working_dir: services/api
+  verify:
+    compose:
+      up: [db, redis]
+      wait_for: ["tcp://localhost:5432"]
+---
+# Service
+
+## Verification
+```bash
+curl localhost:8080/health
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        let compose = spec.compose.unwrap();
+        assert_eq!(compose.working_dir, Some(PathBuf::from("services/api")));
+        assert_eq!(compose.bootstrap.file, None);
+        assert_eq!(compose.bootstrap.up, vec!["db", "redis"]);
+        assert_eq!(compose.bootstrap.wait_for, vec!["tcp://localhost:5432"]);
+        assert_eq!(compose.bootstrap.wait_timeout, 60);
+        assert!(spec.setup.is_empty());
+    }
+
+    #[test]
+    fn test_extract_verification_spec_services() {
+        let content = r#"---
+pave:
+  working_dir: services/api
+  services:
+    - image: postgres:16
+      port: 5432
+    - compose: compose.yaml
+---
+# Service
+
+## Verification
+```bash
+psql -c 'select 1'
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.services.len(), 2);
+        assert_eq!(spec.services[0].image.as_deref(), Some("postgres:16"));
+        assert_eq!(spec.services[0].port, Some(5432));
+        assert_eq!(
+            spec.services[1].compose.as_deref(),
+            Some("services/api/compose.yaml")
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_setup_only_is_none() {
+        let content = r#"# Service
+
+## Verification
+<!-- pave:setup -->
+```bash
+./seed-db.sh
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert!(extract_verification_spec(&doc).is_none());
+    }
+
+    #[test]
+    fn test_resolve_shell() {
+        let item = VerificationItem {
+            shell: Some("fish".to_string()),
+            ..VerificationItem::default()
+        };
+        let err = item.resolve_shell(Shell::Sh).unwrap_err();
+        assert!(err.contains("unknown shell 'fish'"));
+
+        assert_eq!(
+            VerificationItem::default().resolve_shell(Shell::Bash),
+            Ok(Shell::Bash)
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_interpreter_blocks() {
+        let content = r#"# Scripts
+
+## Verification
+<!-- pave:run -->
+```python
+import sys
+print(sys.version)
+```
+```bash
+cargo test
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let verify = VerifySection::default();
+
+        assert_eq!(spec.items.len(), 2);
+        let python = &spec.items[0];
+        assert_eq!(python.language, Some("python".to_string()));
+        assert_eq!(
+            python.script.as_deref(),
+            Some("import sys\nprint(sys.version)")
+        );
+        assert_eq!(
+            python.invocation(&verify),
+            Ok(Invocation::Interpreter(vec![
+                "python3".to_string(),
+                "-".to_string()
+            ]))
+        );
+        assert_eq!(spec.items[1].language, None);
+        assert_eq!(
+            spec.items[1].invocation(&verify),
+            Ok(Invocation::Shell(verify.shell()))
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_exit_code_marker() {
+        let content = r#"# Failure Paths
+
+## Verification
+<!-- pave:exit-code 2 -->
+```bash
+exit 2
+```
+```bash
+true
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items[0].expected_exit_code, Some(2));
+        assert_eq!(spec.items[1].expected_exit_code, Some(0));
+
+        let result = run_single_verification(&spec.items[0]);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_check_shell_syntax_skips_non_posix_shells() {
+        assert!(check_shell_syntax("echo 'unterminated", Shell::Sh).is_some());
+        assert_eq!(check_shell_syntax("echo 'unterminated", Shell::Pwsh), None);
+    }
+
     #[test]
     fn test_extract_verification_spec_inline_overrides_frontmatter() {
         let content = r#"---
@@ -894,7 +1945,17 @@ cargo test
             expected_exit_code: Some(0),
             expected_output: Some(OutputMatcher::Contains("hello_from_env".to_string())),
             timeout_secs: Some(5),
+            retries: 0,
             env_vars: vec![("MY_VAR".to_string(), "hello_from_env".to_string())],
+            secrets: Vec::new(),
+            container: None,
+            host: None,
+            database: None,
+            compile: false,
+            shell: None,
+            language: None,
+            script: None,
+            session: None,
         };
 
         let result = run_single_verification(&item);
@@ -937,4 +1998,55 @@ echo second
             Some(PathBuf::from("packages/shared"))
         );
     }
+
+    #[test]
+    fn shell_syntax_accepts_valid_commands() {
+        assert_eq!(
+            check_shell_syntax("echo 'hello world' && ls", Shell::Sh),
+            None
+        );
+        assert_eq!(
+            check_shell_syntax("echo \"it's fine\" # don't care", Shell::Sh),
+            None
+        );
+    }
+
+    #[test]
+    fn shell_syntax_reports_unbalanced_quotes() {
+        let msg = check_shell_syntax("echo \"hello", Shell::Sh).unwrap();
+        assert_eq!(msg, "unbalanced double quote starting at column 6");
+    }
+
+    #[test]
+    fn shell_syntax_reports_parse_errors() {
+        let msg = check_shell_syntax("if true; then echo hi", Shell::Sh).unwrap();
+        assert!(msg.starts_with("shell syntax error:"), "got: {}", msg);
+    }
+
+    #[test]
+    fn suspicious_construct_flags_backticks() {
+        let msg = find_suspicious_construct("echo `whoami`").unwrap();
+        assert!(msg.contains("backtick"));
+        assert!(msg.contains("column 6"));
+        assert_eq!(find_suspicious_construct("echo '`literal`'"), None);
+    }
+
+    #[test]
+    fn suspicious_construct_flags_remote_fetch_substitution() {
+        let msg = find_suspicious_construct("sh -c \"$(curl -fsSL https://x.sh)\"").unwrap();
+        assert!(msg.contains("$(curl ...)"));
+        assert!(find_suspicious_construct("echo $( wget -qO- x)").is_some());
+        assert_eq!(find_suspicious_construct("echo $(date) $(curly)"), None);
+    }
+
+    #[test]
+    fn comments_start_only_at_the_start_of_an_unquoted_token() {
+        assert_eq!(find_suspicious_construct("true&&# `whoami`"), None);
+        assert_eq!(find_suspicious_construct("true;# `whoami`"), None);
+        assert!(find_suspicious_construct("echo a#`whoami`").is_some());
+        assert!(find_suspicious_construct("echo \"#\" `whoami`").is_some());
+        assert!(find_suspicious_construct("echo \\# `whoami`").is_some());
+        assert_eq!(find_unbalanced_quote("echo ok|# don't"), None);
+        assert!(find_unbalanced_quote("echo a#'b").is_some());
+    }
 }
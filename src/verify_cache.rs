@@ -0,0 +1,415 @@
+//! Cache of passing verification commands.
+//!
+//! Each entry is keyed on a hash of everything that can change a command's
+//! outcome: the command itself, its expectations, environment, working
+//! directory, secret values, and the contents of the files the document
+//! covers through `pave.paths`, matched the way `pave coverage` and
+//! `pave which` match them. A document whose commands all have keys
+//! unchanged since they last passed does not need to run again. Documents
+//! that cover no files are never cached.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::commands::coverage::matches_any_pattern;
+use crate::config::VerifySection;
+use crate::secrets::Secrets;
+use crate::verification::VerificationItem;
+use crate::walk::SKIPPED_DIRS;
+
+/// Cache file location, relative to the config directory.
+pub const CACHE_PATH: &str = ".pave/verify-cache.json";
+
+/// Bump when the key derivation changes so old entries are ignored.
+const CACHE_VERSION: u32 = 1;
+
+/// A cached passing result.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheEntry {
+    /// Document the command belongs to (relative to the config directory).
+    pub doc: String,
+    /// The command that passed.
+    pub command: String,
+    /// Exit code of the passing run.
+    pub exit_code: Option<i32>,
+    /// When the command last passed (RFC 3339).
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+/// Verification cache loaded from disk.
+#[derive(Debug, Default)]
+pub struct VerifyCache {
+    entries: BTreeMap<String, CacheEntry>,
+    /// Documents verified in this run.
+    docs: HashSet<String>,
+    /// Keys looked up or recorded in this run.
+    used: HashSet<String>,
+}
+
+impl VerifyCache {
+    /// Load the cache, starting empty if the file is missing or unreadable.
+    ///
+    /// A corrupt cache only costs a re-run, so it never fails verification.
+    pub fn load(path: &Path) -> Self {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == CACHE_VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+        Self {
+            entries,
+            ..Self::default()
+        }
+    }
+
+    /// Write the cache, dropping stale entries for documents verified in this run.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        let (docs, used) = (&self.docs, &self.used);
+        self.entries
+            .retain(|key, entry| !docs.contains(&entry.doc) || used.contains(key));
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file).context("Failed to serialize cache")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write cache: {}", path.display()))
+    }
+
+    /// Note that a document is being verified in this run.
+    pub fn mark_doc(&mut self, doc: &str) {
+        self.docs.insert(doc.to_string());
+    }
+
+    /// Look up a cached passing result.
+    pub fn lookup(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.get(key).cloned();
+        if entry.is_some() {
+            self.used.insert(key.to_string());
+        }
+        entry
+    }
+
+    /// Record a passing result.
+    pub fn record(&mut self, key: String, entry: CacheEntry) {
+        self.docs.insert(entry.doc.clone());
+        self.used.insert(key.clone());
+        self.entries.insert(key, entry);
+    }
+}
+
+/// Compute the cache key for a verification item.
+///
+/// `verify` decides which shell or interpreter runs the item,
+/// `inputs_hash` is the document's [`hash_path_inputs`] value, and
+/// `secrets` holds the values of the item's secrets.
+pub fn cache_key(
+    item: &VerificationItem,
+    verify: &VerifySection,
+    inputs_hash: &str,
+    secrets: &Secrets,
+) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write_str(&item.command);
+    hasher.write_str(&format!("{:?}", item.invocation(verify)));
+    hasher.write_str(&format!("{:?}", item.script));
+    hasher.write_str(&format!("{:?}", item.working_dir));
+    hasher.write_str(&format!("{:?}", item.expected_exit_code));
+    hasher.write_str(&format!("{:?}", item.expected_output));
+    for (key, value) in &item.env_vars {
+        hasher.write_str(key);
+        hasher.write_str(value);
+    }
+    // Secret values change without the doc changing; the key only holds a
+    // digest of each, so the cache file never reveals them
+    for name in &item.secrets {
+        hasher.write_str(name);
+        let value = secrets.get(name).unwrap_or_default();
+        hasher.write_str(&hash_bytes(value.as_bytes()));
+    }
+    if let Some(image) = &item.container {
+        hasher.write_str(image);
+    }
+    if let Some(host) = &item.host {
+        hasher.write_str(host);
+    }
+    if let Some(database) = &item.database {
+        hasher.write_str(database);
+    }
+    hasher.write_str(inputs_hash);
+    hasher.finish_hex()
+}
+
+/// Files under `root` that `pave.paths` patterns can match, relative to it
+/// and sorted. Hidden files and [`SKIPPED_DIRS`] are left out.
+pub fn project_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files);
+    let mut files: Vec<PathBuf> = files
+        .into_iter()
+        .filter_map(|file| file.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Hash the paths and contents of the `files` matched by `patterns`.
+///
+/// `files` are the [`project_files`] of `root`, matched with the same
+/// patterns as coverage. Returns `None` when no files match, since nothing
+/// could then invalidate a cached pass.
+pub fn hash_path_inputs(patterns: &[String], root: &Path, files: &[PathBuf]) -> Option<String> {
+    let mut matched = files
+        .iter()
+        .filter(|file| matches_any_pattern(file, patterns))
+        .peekable();
+    matched.peek()?;
+
+    let mut hasher = Fnv1a::new();
+    for file in matched {
+        hasher.write_str(&file.to_string_lossy());
+        match std::fs::read(root.join(file)) {
+            Ok(content) => hasher.write(&content),
+            Err(_) => hasher.write_str("<unreadable>"),
+        }
+    }
+    Some(hasher.finish_hex())
+}
+
+/// Hash arbitrary bytes, e.g. a file's contents, with the cache's hasher.
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(bytes);
+    hasher.finish_hex()
+}
+
+/// Collect a file, or every file beneath a directory.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let child = entry.path();
+        let skipped = child.file_name().is_some_and(|name| {
+            let name = name.to_string_lossy();
+            name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref())
+        });
+        if !skipped {
+            collect_files(&child, files);
+        }
+    }
+}
+
+/// 64-bit FNV-1a, used because its output is stable across Rust releases.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Write a string followed by a separator so adjacent fields can't blur.
+    fn write_str(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    fn finish_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Shell;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn entry(doc: &str) -> CacheEntry {
+        CacheEntry {
+            doc: doc.to_string(),
+            command: "true".to_string(),
+            exit_code: Some(0),
+            recorded_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn cache_key_changes_with_inputs() {
+        let verify = VerifySection::default();
+        let item = VerificationItem {
+            command: "cargo test".to_string(),
+            ..VerificationItem::default()
+        };
+        let base = cache_key(&item, &verify, "aaaa", &Secrets::default());
+
+        assert_eq!(base, cache_key(&item, &verify, "aaaa", &Secrets::default()));
+        assert_ne!(base, cache_key(&item, &verify, "bbbb", &Secrets::default()));
+
+        let with_env = VerificationItem {
+            env_vars: vec![("RUST_LOG".to_string(), "debug".to_string())],
+            ..item.clone()
+        };
+        assert_ne!(
+            base,
+            cache_key(&with_env, &verify, "aaaa", &Secrets::default())
+        );
+
+        let bash = VerifySection {
+            shell: Some(Shell::Bash),
+            ..VerifySection::default()
+        };
+        assert_ne!(base, cache_key(&item, &bash, "aaaa", &Secrets::default()));
+
+        let with_dir = VerificationItem {
+            working_dir: Some(PathBuf::from("sub")),
+            ..item
+        };
+        assert_ne!(
+            base,
+            cache_key(&with_dir, &verify, "aaaa", &Secrets::default())
+        );
+    }
+
+    #[test]
+    fn cache_key_tracks_interpreter() {
+        let item = VerificationItem {
+            command: "print(1)".to_string(),
+            language: Some("python".to_string()),
+            script: Some("print(1)\n".to_string()),
+            ..VerificationItem::default()
+        };
+        let verify = VerifySection::default();
+        let pypy = VerifySection {
+            interpreters: [("python".to_string(), "pypy3 -".to_string())].into(),
+            ..VerifySection::default()
+        };
+
+        assert_ne!(
+            cache_key(&item, &verify, "aaaa", &Secrets::default()),
+            cache_key(&item, &pypy, "aaaa", &Secrets::default())
+        );
+    }
+
+    #[test]
+    fn cache_key_tracks_secret_values() {
+        let verify = VerifySection::default();
+        let item = VerificationItem {
+            command: "deploy".to_string(),
+            secrets: vec!["PAVE_TEST_CACHE_TOKEN".to_string()],
+            ..VerificationItem::default()
+        };
+        let key = |secrets: &str| cache_key(&item, &verify, "aaaa", &Secrets::parse(secrets));
+
+        assert_eq!(
+            key("PAVE_TEST_CACHE_TOKEN=one"),
+            key("PAVE_TEST_CACHE_TOKEN=one")
+        );
+        assert_ne!(
+            key("PAVE_TEST_CACHE_TOKEN=one"),
+            key("PAVE_TEST_CACHE_TOKEN=two")
+        );
+        assert!(!key("PAVE_TEST_CACHE_TOKEN=one").contains("one"));
+    }
+
+    #[test]
+    fn hash_path_inputs_tracks_file_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join("src/lib.rs"), "fn a() {}").unwrap();
+        let patterns = vec!["src/".to_string()];
+        let hash = |patterns: &[String]| hash_path_inputs(patterns, root, &project_files(root));
+
+        let before = hash(&patterns);
+        assert_eq!(before, hash(&patterns));
+
+        fs::write(root.join("src/lib.rs"), "fn b() {}").unwrap();
+        assert_ne!(before, hash(&patterns));
+
+        let glob = vec!["src/*.rs".to_string()];
+        assert_eq!(hash(&glob), hash(&patterns));
+
+        // Recursive globs cover files the way coverage does
+        fs::write(root.join("src/nested/mod.rs"), "fn c() {}").unwrap();
+        let recursive = vec!["src/**".to_string()];
+        assert!(hash(&recursive).is_some());
+        assert_eq!(hash(&recursive), hash(&patterns));
+
+        // Docs that cover no files are never cached
+        assert_eq!(hash(&[]), None);
+        assert_eq!(hash(&["lib/".to_string()]), None);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CACHE_PATH);
+
+        let mut cache = VerifyCache::load(&path);
+        assert!(cache.lookup("k1").is_none());
+        cache.record("k1".to_string(), entry("docs/a.md"));
+        cache.save(&path).unwrap();
+
+        let mut cache = VerifyCache::load(&path);
+        assert_eq!(cache.lookup("k1"), Some(entry("docs/a.md")));
+    }
+
+    #[test]
+    fn save_prunes_stale_entries_of_verified_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json");
+
+        let mut cache = VerifyCache::default();
+        cache.record("old-a".to_string(), entry("docs/a.md"));
+        cache.record("old-b".to_string(), entry("docs/b.md"));
+        cache.save(&path).unwrap();
+
+        // Only docs/a.md is verified again, and its command changed
+        let mut cache = VerifyCache::load(&path);
+        cache.mark_doc("docs/a.md");
+        cache.record("new-a".to_string(), entry("docs/a.md"));
+        cache.save(&path).unwrap();
+
+        let mut cache = VerifyCache::load(&path);
+        assert!(cache.lookup("old-a").is_none());
+        assert!(cache.lookup("new-a").is_some());
+        assert!(cache.lookup("old-b").is_some());
+    }
+
+    #[test]
+    fn corrupt_cache_loads_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json");
+        fs::write(&path, "{not json").unwrap();
+
+        let mut cache = VerifyCache::load(&path);
+        assert!(cache.lookup("anything").is_none());
+    }
+}
@@ -0,0 +1,529 @@
+//! Export planned verification commands as a shell script or Makefile.
+//!
+//! `pave verify --export-script` and `--export-make` write the commands
+//! `pave verify` would run, in the same order and with the same shells,
+//! interpreters, environment variables, and working directories, so that
+//! environments without pave can still run the documented verification.
+
+use anyhow::{Result, bail};
+use std::path::Path;
+
+use crate::config::VerifySection;
+use crate::sql;
+use crate::verification::{Invocation, VerificationItem, VerificationSpec};
+
+/// A document's commands, ready to export.
+pub struct ExportDoc<'a> {
+    /// Document path as shown in comments and target names.
+    pub file: &'a Path,
+    /// The document's verification spec.
+    pub spec: &'a VerificationSpec,
+}
+
+/// Render a POSIX shell script that runs every command.
+///
+/// Like `pave verify --keep-going`, the script runs all documents and exits
+/// non-zero if any command's exit code differs from the expected one. A
+/// failed setup command skips the rest of its document except teardown.
+///
+/// `root` is the project root relative to the directory the script is
+/// written to.
+pub fn render_script(docs: &[ExportDoc], verify: &VerifySection, root: &Path) -> Result<String> {
+    let mut out = String::new();
+    out.push_str("#!/bin/sh\n");
+    out.push_str("# Verification commands exported by `pave verify --export-script`.\n");
+    out.push_str("# Commands run from the project root, as they do under pave.\n\n");
+    out.push_str(&format!(
+        "cd \"$(dirname \"$0\")\"/{} || exit 1\n\n",
+        quote(&root.to_string_lossy())
+    ));
+    out.push_str("failed=0\n\n");
+    out.push_str("# Record a failure if a command's exit code isn't the expected one.\n");
+    out.push_str("check() {\n");
+    out.push_str("    if [ \"$1\" -ne \"$2\" ]; then\n");
+    out.push_str("        echo \"FAIL (exit $1, expected $2): $3\" >&2\n");
+    out.push_str("        failed=$((failed + 1))\n");
+    out.push_str("        return 1\n");
+    out.push_str("    fi\n");
+    out.push_str("    echo \"PASS: $3\"\n");
+    out.push_str("}\n");
+
+    for doc in docs {
+        let spec = doc.spec;
+        out.push_str(&format!(
+            "\n# {}:{}\n",
+            doc.file.display(),
+            spec.section_line
+        ));
+
+        let has_setup = !spec.setup.is_empty();
+        if has_setup {
+            out.push_str("setup_ok=1\n");
+            for item in &spec.setup {
+                out.push_str("if [ \"$setup_ok\" -eq 1 ]; then\n");
+                push_script_item(&mut out, item, verify, "    ", " || setup_ok=0")?;
+                out.push_str("fi\n");
+            }
+            out.push_str("if [ \"$setup_ok\" -eq 1 ]; then\n");
+        }
+
+        let indent = if has_setup { "    " } else { "" };
+        for item in &spec.items {
+            push_script_item(&mut out, item, verify, indent, "")?;
+        }
+
+        if has_setup {
+            out.push_str("fi\n");
+        }
+        for item in &spec.teardown {
+            push_script_item(&mut out, item, verify, "", "")?;
+        }
+    }
+
+    out.push_str("\nexit $((failed > 0))\n");
+    Ok(out)
+}
+
+/// Append one command and its exit code check to a script.
+fn push_script_item(
+    out: &mut String,
+    item: &VerificationItem,
+    verify: &VerifySection,
+    indent: &str,
+    on_failure: &str,
+) -> Result<()> {
+    let expected = item.expected_exit_code.unwrap_or(0);
+    if expected != 0 {
+        out.push_str(&format!("{}# expects exit code {}\n", indent, expected));
+    }
+    let line = command_line(item, verify)?;
+    let line = match &item.working_dir {
+        Some(dir) if item.host.is_none() => {
+            format!("(cd {} && {})", quote(&dir.to_string_lossy()), line)
+        }
+        _ => line,
+    };
+    out.push_str(&format!("{}{}\n", indent, line));
+    out.push_str(&format!(
+        "{}check $? {} {}{}\n",
+        indent,
+        expected,
+        quote(label(item)),
+        on_failure
+    ));
+    Ok(())
+}
+
+/// Render a Makefile with a `verify` target and one target per document.
+///
+/// Make stops at the first failing command, so a failure skips the rest of
+/// that document, including its teardown. Use `make -k` to still run the
+/// other documents.
+///
+/// `root` is the project root relative to the directory the Makefile is
+/// written to.
+pub fn render_makefile(docs: &[ExportDoc], verify: &VerifySection, root: &Path) -> Result<String> {
+    let targets: Vec<String> = docs.iter().map(|doc| target_name(doc.file)).collect();
+
+    let mut out = String::new();
+    out.push_str("# Verification commands exported by `pave verify --export-make`.\n");
+    out.push_str("# Make stops a document at its first failure, skipping its teardown;\n");
+    out.push_str("# run with `make -k` to continue with the other documents.\n\n");
+    out.push_str(&format!(
+        "PAVE_ROOT := $(dir $(lastword $(MAKEFILE_LIST))){}\n\n",
+        root.display()
+    ));
+    out.push_str(&format!(".PHONY: verify {}\n", targets.join(" ")));
+    out.push_str(&format!("verify: {}\n", targets.join(" ")));
+
+    for (doc, target) in docs.iter().zip(&targets) {
+        let spec = doc.spec;
+        out.push_str(&format!(
+            "\n# {}:{}\n{}:\n",
+            doc.file.display(),
+            spec.section_line,
+            target
+        ));
+        let items = spec.setup.iter().chain(&spec.items).chain(&spec.teardown);
+        for item in items {
+            let line = command_line(item, verify)?;
+            if line.contains('\n') {
+                bail!(
+                    "Cannot export the multi-line command at {}:{} to a Makefile; use --export-script",
+                    doc.file.display(),
+                    spec.section_line
+                );
+            }
+
+            let dir = match &item.working_dir {
+                _ if item.host.is_some() => "$(PAVE_ROOT)".to_string(),
+                Some(dir) if dir.is_absolute() => dir.display().to_string(),
+                Some(dir) => format!("$(PAVE_ROOT)/{}", dir.display()),
+                None => "$(PAVE_ROOT)".to_string(),
+            };
+            let mut recipe = format!("cd {} && {}", quote(&dir), line.replace('$', "$$"));
+            let expected = item.expected_exit_code.unwrap_or(0);
+            if expected != 0 {
+                recipe.push_str(&format!("; test $$? -eq {}", expected));
+            }
+            out.push_str(&format!("\t{}\n", recipe));
+        }
+    }
+
+    Ok(out)
+}
+
+/// The shell command line that runs an item the way `pave verify` would.
+fn command_line(item: &VerificationItem, verify: &VerifySection) -> Result<String> {
+    // Connection strings keep their `${NAME}` references for the shell to
+    // expand, so credentials stay out of the script
+    let invocation = match item.database_url(verify) {
+        Some(url) => url.and_then(sql::client).map(Invocation::Database),
+        None => item.invocation(verify),
+    }
+    .map_err(|e| anyhow::anyhow!("Cannot export '{}': {}", label(item), e))?;
+
+    // A database client's credentials keep their `${NAME}` references
+    let client_env: Vec<(&String, String)> = match &invocation {
+        Invocation::Database(client) => client
+            .env
+            .iter()
+            .map(|(key, value)| (key, quote_expanding(value)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let env: String = match &item.container {
+        // The current directory is mounted, since exports can't know where
+        // the project root ends up
+        Some(image) => {
+            let vars: String = item
+                .env_vars
+                .iter()
+                .map(|(key, value)| (key, quote(value)))
+                .chain(client_env)
+                .map(|(key, value)| format!("-e {}={} ", key, value))
+                .chain(item.secrets.iter().map(|name| format!("-e {} ", name)))
+                .collect();
+            format!(
+                "{} run --rm -i -v \"$PWD\":\"$PWD\" -w \"$PWD\" {}{} ",
+                verify.container_cmd(),
+                vars,
+                quote(image)
+            )
+        }
+        None => item
+            .env_vars
+            .iter()
+            .map(|(key, value)| (key, quote(value)))
+            .chain(client_env)
+            .map(|(key, value)| format!("{}={} ", key, value))
+            .collect(),
+    };
+
+    let line = match invocation {
+        Invocation::Shell(shell) => format!(
+            "{}{} {} {}",
+            env,
+            shell.name(),
+            shell.command_args().join(" "),
+            quote(&item.command)
+        ),
+        Invocation::Interpreter(argv) => pipe_script(item, &env, &argv),
+        Invocation::Database(client) => pipe_script(item, &env, &client.argv),
+        Invocation::Http => {
+            // `--fail-with-body` stands in for pave failing on error statuses
+            let request = item
+                .request()
+                .map_err(|e| anyhow::anyhow!("Cannot export '{}': {}", label(item), e))?
+                .substitute(|name| {
+                    item.env_vars
+                        .iter()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.clone())
+                });
+            let args: Vec<String> = request.curl_args().iter().map(|arg| quote(arg)).collect();
+            let curl = format!("{}curl -sS --fail-with-body {}", env, args.join(" "));
+            match &request.body {
+                Some(body) => format!("printf '%s' {} | {}", quote(body), curl),
+                None => curl,
+            }
+        }
+    };
+
+    // Remote items run the whole line on their host, in their working dir
+    let Some(host) = &item.host else {
+        return Ok(line);
+    };
+    let destination = verify.hosts.get(host).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot export '{}': host '{}' is not in [verify.hosts]",
+            label(item),
+            host
+        )
+    })?;
+    let line = match &item.working_dir {
+        Some(dir) => format!("cd {} && {}", quote(&dir.to_string_lossy()), line),
+        None => line,
+    };
+    Ok(format!(
+        "{} -T {} {}",
+        verify.ssh_cmd(),
+        quote(destination),
+        quote(&line)
+    ))
+}
+
+/// Short description of an item for messages.
+fn label(item: &VerificationItem) -> &str {
+    item.command.lines().next().unwrap_or_default()
+}
+
+/// Quote a string for POSIX shells, leaving simple words unquoted.
+pub(crate) fn quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c));
+    if is_plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// A line that pipes the item's script into `argv`, line by line as pave
+/// writes it to stdin, with `env` assignments in front.
+fn pipe_script(item: &VerificationItem, env: &str, argv: &[String]) -> String {
+    let script = item.script.as_deref().unwrap_or(&item.command);
+    let lines: Vec<String> = script.lines().map(quote).collect();
+    let argv: Vec<String> = argv
+        .iter()
+        .map(|arg| {
+            if arg.contains("${") {
+                quote_expanding(arg)
+            } else {
+                quote(arg)
+            }
+        })
+        .collect();
+    format!(
+        "printf '%s\\n' {} | {}{}",
+        lines.join(" "),
+        env,
+        argv.join(" ")
+    )
+}
+
+/// Double-quote a value for the shell, leaving its `${NAME}` references to
+/// be expanded.
+fn quote_expanding(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('`', "\\`");
+    format!("\"{}\"", escaped)
+}
+
+/// Make target name for a document, e.g. `verify-docs-api-md`.
+fn target_name(file: &Path) -> String {
+    let slug: String = file
+        .to_string_lossy()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("verify-{}", slug.trim_matches('-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParsedDoc;
+    use crate::verification::extract_verification_spec;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn spec(content: &str) -> VerificationSpec {
+        let doc = ParsedDoc::parse_content(PathBuf::from("docs/api.md"), content).unwrap();
+        extract_verification_spec(&doc).unwrap()
+    }
+
+    const DOC: &str = r#"# API
+
+## Verification
+<!-- pave:setup -->
+```bash
+echo seeded > seed.txt
+```
+<!-- pave:env GREETING=it's -->
+```bash
+echo "$GREETING here"
+```
+<!-- pave:exit-code 3 -->
+```bash
+exit 3
+```
+<!-- pave:teardown -->
+```bash
+rm seed.txt
+```
+"#;
+
+    #[test]
+    fn script_preserves_order_env_and_exit_codes() {
+        let spec = spec(DOC);
+        let docs = [ExportDoc {
+            file: Path::new("docs/api.md"),
+            spec: &spec,
+        }];
+
+        let script = render_script(&docs, &VerifySection::default(), Path::new(".")).unwrap();
+
+        let setup = script.find("echo seeded").unwrap();
+        let greeting = script.find("GREETING='it'\\''s' sh -c").unwrap();
+        let exit = script.find("# expects exit code 3").unwrap();
+        let teardown = script.find("rm seed.txt").unwrap();
+        assert!(setup < greeting && greeting < exit && exit < teardown);
+        assert!(script.contains("check $? 3 'exit 3'"));
+        assert!(script.contains(" || setup_ok=0\n"));
+    }
+
+    #[test]
+    fn exported_script_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = spec(DOC);
+        let docs = [ExportDoc {
+            file: Path::new("docs/api.md"),
+            spec: &spec,
+        }];
+        let script = render_script(&docs, &VerifySection::default(), Path::new(".")).unwrap();
+        let path = temp_dir.path().join("verify.sh");
+        std::fs::write(&path, script).unwrap();
+
+        let output = Command::new("sh").arg(&path).output().unwrap();
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}", stdout);
+        assert!(stdout.contains("PASS: exit 3"));
+        assert!(!temp_dir.path().join("seed.txt").exists());
+    }
+
+    #[test]
+    fn makefile_escapes_dollars_and_checks_exit_codes() {
+        let spec = spec(DOC);
+        let docs = [ExportDoc {
+            file: Path::new("docs/api.md"),
+            spec: &spec,
+        }];
+
+        let makefile = render_makefile(&docs, &VerifySection::default(), Path::new("..")).unwrap();
+
+        assert!(makefile.contains("PAVE_ROOT := $(dir $(lastword $(MAKEFILE_LIST))).."));
+        assert!(makefile.contains("verify: verify-docs-api-md\n"));
+        assert!(makefile.contains("\tcd '$(PAVE_ROOT)' && sh -c 'echo seeded > seed.txt'\n"));
+        assert!(makefile.contains("sh -c 'echo \"$$GREETING here\"'"));
+        assert!(makefile.contains("sh -c 'exit 3'; test $$? -eq 3\n"));
+    }
+
+    #[test]
+    fn interpreter_blocks_are_piped_to_the_interpreter() {
+        let spec = spec("# S\n\n## Verification\n<!-- pave:run -->\n```python\nprint('hi')\n```\n");
+
+        let line = command_line(&spec.items[0], &VerifySection::default()).unwrap();
+
+        assert_eq!(line, "printf '%s\\n' 'print('\\''hi'\\'')' | python3 -");
+    }
+
+    #[test]
+    fn container_blocks_run_in_their_image() {
+        let spec = spec(
+            "# S\n\n## Verification\n<!-- pave:container node:20 -->\n<!-- pave:env CI=1 -->\n\
+             <!-- pave:env-from NPM_TOKEN -->\n```bash\nnpm test\n```\n",
+        );
+        let verify = VerifySection {
+            container_cmd: Some("podman".to_string()),
+            ..VerifySection::default()
+        };
+
+        let line = command_line(&spec.items[0], &verify).unwrap();
+
+        assert_eq!(
+            line,
+            "podman run --rm -i -v \"$PWD\":\"$PWD\" -w \"$PWD\" -e CI=1 -e NPM_TOKEN node:20 sh -c 'npm test'"
+        );
+    }
+
+    #[test]
+    fn http_blocks_run_with_curl() {
+        let spec = spec(
+            "# S\n\n## Verification\n<!-- pave:env HOST=localhost:8080 -->\n\
+             ```http\nPOST http://{{HOST}}/items\nContent-Type: application/json\n\n{\"a\": 1}\n```\n",
+        );
+
+        let line = command_line(&spec.items[0], &VerifySection::default()).unwrap();
+
+        assert_eq!(
+            line,
+            "printf '%s' '{\"a\": 1}' | HOST=localhost:8080 curl -sS --fail-with-body -X POST \
+             -H 'Content-Type: application/json' --data-binary @- --url http://localhost:8080/items"
+        );
+    }
+
+    #[test]
+    fn sql_blocks_keep_database_urls_from_the_environment() {
+        let spec = spec(
+            "# S\n\n## Verification\n<!-- pave:run -->\n<!-- pave:database warehouse -->\n\
+             ```sql\nselect count(*) from orders;\n```\n",
+        );
+        let mut verify = VerifySection::default();
+        verify.databases.insert(
+            "warehouse".to_string(),
+            "postgres://report:${PAVE_TEST_UNSET_PASSWORD}@db/wh".to_string(),
+        );
+
+        let line = command_line(&spec.items[0], &verify).unwrap();
+
+        assert!(line.starts_with(
+            "printf '%s\\n' 'select count(*) from orders;' | \
+             PGPASSWORD=\"${PAVE_TEST_UNSET_PASSWORD}\" psql "
+        ));
+        assert!(line.contains(" -d postgres://report@db/wh -f -"));
+    }
+
+    #[test]
+    fn host_blocks_run_over_ssh() {
+        let spec = spec(
+            "# S\n\n## Verification\n<!-- pave:host staging -->\n<!-- pave:working_dir /srv/api -->\n\
+             ```bash\nsystemctl is-active api\n```\n",
+        );
+        let mut verify = VerifySection::default();
+
+        let err = command_line(&spec.items[0], &verify).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("host 'staging' is not in [verify.hosts]")
+        );
+
+        verify
+            .hosts
+            .insert("staging".to_string(), "deploy@bastion".to_string());
+        let line = command_line(&spec.items[0], &verify).unwrap();
+        assert_eq!(
+            line,
+            "ssh -T deploy@bastion 'cd /srv/api && sh -c '\\''systemctl is-active api'\\'''"
+        );
+    }
+
+    #[test]
+    fn quote_leaves_plain_words_alone() {
+        assert_eq!(quote("src/lib.rs"), "src/lib.rs");
+        assert_eq!(quote("two words"), "'two words'");
+        assert_eq!(quote(""), "''");
+    }
+}
@@ -0,0 +1,333 @@
+//! Rewrite expected outputs in documents from real command output.
+//!
+//! `pave verify --update-expected` uses this when behavior changes on
+//! purpose. Each stale expectation, whether a `pave:expect` block or output
+//! written inline after a `$ ` prompt, is replaced with what the command
+//! actually printed, keeping the block's match strategy.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::parser::{Annotation, BlockPhase, CodeBlock, ExpectMatchStrategy, ParsedDoc};
+use crate::verification::runs_per_prompt;
+
+/// An expectation that was left as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedUpdate {
+    /// Line of the command's code block (1-indexed).
+    pub line: usize,
+    /// Why the expectation couldn't be rewritten.
+    pub reason: String,
+}
+
+/// Result of rewriting a document's expected outputs.
+#[derive(Debug, Clone)]
+pub struct UpdateOutcome {
+    /// The document content with expectations replaced.
+    pub content: String,
+    /// Number of expectations rewritten.
+    pub updated: usize,
+    /// Expectations that couldn't be rewritten.
+    pub skipped: Vec<SkippedUpdate>,
+}
+
+/// Replace the expected output of a document's verification commands.
+///
+/// `actual` holds the output to record for each verification command, in
+/// the order `pave verify` runs them, or `None` to leave a command's
+/// expectation alone. `contains` and `exact` expectations are rewritten;
+/// `regex` and `not` expectations can't be derived from output and are
+/// reported as skipped.
+pub fn update_expected(
+    path: &Path,
+    content: &str,
+    actual: &[Option<&str>],
+) -> Result<UpdateOutcome> {
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), content)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
+    let mut skipped = Vec::new();
+
+    let blocks = doc
+        .get_section("Verification")
+        .map(|section| section.executable_commands())
+        .unwrap_or_default();
+    // One target per verification command, in run order: a whole block, or
+    // one of its prompts when output is written between prompts
+    let mut targets: Vec<(&CodeBlock, Option<usize>)> = Vec::new();
+    for &block in blocks
+        .iter()
+        .filter(|block| block.phase == BlockPhase::Verify)
+    {
+        if runs_per_prompt(block) {
+            targets.extend((0..block.prompts.len()).map(|idx| (block, Some(idx))));
+        } else {
+            targets.push((block, None));
+        }
+    }
+
+    for ((block, prompt), actual) in targets.into_iter().zip(actual) {
+        let expected = match prompt {
+            Some(idx) => &block.prompts[idx].expected_output,
+            None => &block.expected_output,
+        };
+        let (Some(actual), Some(expected)) = (actual, expected) else {
+            continue;
+        };
+        let skip = |reason: &str| SkippedUpdate {
+            line: block.start_line,
+            reason: reason.to_string(),
+        };
+
+        match expected.strategy {
+            ExpectMatchStrategy::Contains | ExpectMatchStrategy::Exact => {}
+            ExpectMatchStrategy::Regex => {
+                skipped.push(skip("regex patterns must be updated by hand"));
+                continue;
+            }
+            ExpectMatchStrategy::NotContains => {
+                skipped.push(skip("`not` expectations must be updated by hand"));
+                continue;
+            }
+        }
+
+        let new_lines: Vec<String> = actual
+            .trim_end()
+            .trim_start_matches(['\n', '\r'])
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        match locate_expected(&lines, block.start_line - 1, prompt) {
+            Ok(Region::Inline { start, end }) => {
+                if new_lines.iter().any(|line| is_prompt(line)) {
+                    skipped.push(skip("output has lines that look like shell prompts"));
+                } else {
+                    replacements.push((start, end, new_lines));
+                }
+            }
+            Ok(Region::Block {
+                start,
+                end,
+                fence_len,
+            }) => {
+                if new_lines
+                    .iter()
+                    .any(|line| ParsedDoc::is_closing_fence(line.trim(), fence_len))
+                {
+                    skipped.push(skip("output contains a closing code fence"));
+                } else {
+                    replacements.push((start, end, new_lines));
+                }
+            }
+            Err(reason) => skipped.push(skip(reason)),
+        }
+    }
+
+    let updated = replacements.len();
+    let mut out: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    replacements.sort_by_key(|(start, _, _)| *start);
+    for (start, end, new_lines) in replacements.into_iter().rev() {
+        out.splice(start..end, new_lines);
+    }
+
+    let mut new_content = out.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+
+    Ok(UpdateOutcome {
+        content: new_content,
+        updated,
+        skipped,
+    })
+}
+
+/// Where a command's expected output is written, as 0-indexed line ranges.
+enum Region {
+    /// Output lines after a `$ ` prompt inside the command's block.
+    Inline { start: usize, end: usize },
+    /// Body of the `pave:expect` block that follows the command's block.
+    Block {
+        start: usize,
+        end: usize,
+        fence_len: usize,
+    },
+}
+
+/// Find the expected output of the code block whose opening fence is `open`.
+///
+/// With `prompt`, finds the output written after that prompt (0-indexed)
+/// instead of the block's.
+fn locate_expected(
+    lines: &[&str],
+    open: usize,
+    prompt: Option<usize>,
+) -> Result<Region, &'static str> {
+    let (fence_len, _) = ParsedDoc::parse_opening_fence(lines[open].trim())
+        .ok_or("couldn't find the command's code block")?;
+    let close = find_closing_fence(lines, open + 1, fence_len)
+        .ok_or("the command's code block is not closed")?;
+    let body = &lines[open + 1..close];
+
+    // Output between this prompt and the next, without the blank and
+    // comment lines around it. The last prompt's output may instead be in a
+    // pave:expect block after the code block.
+    if let Some(idx) = prompt {
+        let prompts: Vec<usize> = (0..body.len()).filter(|&i| is_prompt(body[i])).collect();
+        let from = prompts
+            .get(idx)
+            .ok_or("couldn't find the command's prompt")?
+            + 1;
+        let to = prompts.get(idx + 1).copied().unwrap_or(body.len());
+        let is_output = |line: &&str| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        };
+        let output = &body[from..to];
+        match output.iter().position(is_output) {
+            Some(start) => {
+                let end = output.iter().rposition(is_output).unwrap_or(start) + 1;
+                return Ok(Region::Inline {
+                    start: open + 1 + from + start,
+                    end: open + 1 + from + end,
+                });
+            }
+            None if idx + 1 < prompts.len() => {
+                return Err("couldn't find the command's expected output");
+            }
+            None => {}
+        }
+    } else if let Some(first_prompt) = body.iter().position(|line| is_prompt(line)) {
+        // Inline output: everything after the last prompt
+        let last_prompt = body
+            .iter()
+            .rposition(|line| is_prompt(line))
+            .unwrap_or(first_prompt);
+        let has_output = body[first_prompt..].iter().any(|line| {
+            let trimmed = line.trim();
+            !is_prompt(line) && !trimmed.is_empty() && !trimmed.starts_with('#')
+        });
+        if has_output {
+            return Ok(Region::Inline {
+                start: open + 2 + last_prompt,
+                end: close,
+            });
+        }
+    }
+
+    // Otherwise the next pave:expect block holds it
+    let mut idx = close + 1;
+    let mut seen_marker = false;
+    while idx < lines.len() {
+        let trimmed = lines[idx].trim();
+        if let Some(Ok(Annotation::Expect(_))) = Annotation::parse(trimmed) {
+            seen_marker = true;
+        } else if let Some((expect_fence_len, _)) = ParsedDoc::parse_opening_fence(trimmed) {
+            if !seen_marker {
+                break;
+            }
+            let end = find_closing_fence(lines, idx + 1, expect_fence_len)
+                .ok_or("the pave:expect block is not closed")?;
+            return Ok(Region::Block {
+                start: idx + 1,
+                end,
+                fence_len: expect_fence_len,
+            });
+        } else if trimmed.starts_with('#') {
+            break;
+        }
+        idx += 1;
+    }
+    Err("couldn't find the command's expected output")
+}
+
+/// Index of the first closing fence at or after `from`.
+fn find_closing_fence(lines: &[&str], from: usize, fence_len: usize) -> Option<usize> {
+    (from..lines.len()).find(|&idx| ParsedDoc::is_closing_fence(lines[idx].trim(), fence_len))
+}
+
+/// Whether a line is a shell prompt, which the parser reads as a command.
+fn is_prompt(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("$ ") || trimmed.starts_with("> ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(content: &str, actual: &[Option<&str>]) -> UpdateOutcome {
+        update_expected(Path::new("doc.md"), content, actual).unwrap()
+    }
+
+    #[test]
+    fn rewrites_expect_blocks_and_keeps_strategy() {
+        let content = "# Doc\n\n## Verification\n\n```bash\necho one\n```\n\n<!-- pave:expect:exact -->\n```\nold\n```\n\n```bash\necho two\n```\n";
+
+        let outcome = update(content, &[Some("one\nline two\n"), None]);
+
+        assert_eq!(outcome.updated, 1);
+        assert!(outcome.skipped.is_empty());
+        assert_eq!(
+            outcome.content,
+            "# Doc\n\n## Verification\n\n```bash\necho one\n```\n\n<!-- pave:expect:exact -->\n```\none\nline two\n```\n\n```bash\necho two\n```\n"
+        );
+    }
+
+    #[test]
+    fn rewrites_inline_output_after_prompt() {
+        let content = "## Verification\n\n```bash\n$ pave check\nChecked 3 documents\n```\n";
+
+        let outcome = update(content, &[Some("Checked 4 documents\n")]);
+
+        assert_eq!(outcome.updated, 1);
+        assert_eq!(
+            outcome.content,
+            "## Verification\n\n```bash\n$ pave check\nChecked 4 documents\n```\n"
+        );
+    }
+
+    #[test]
+    fn skips_regex_expectations() {
+        let content = "## Verification\n\n```bash\necho 1\n```\n\n<!-- pave:expect:regex -->\n```\n\\d+\n```\n";
+
+        let outcome = update(content, &[Some("x")]);
+
+        assert_eq!(outcome.updated, 0);
+        assert_eq!(outcome.content, content);
+        let lines: Vec<usize> = outcome.skipped.iter().map(|s| s.line).collect();
+        assert_eq!(lines, vec![3]);
+        assert!(outcome.skipped[0].reason.contains("regex"));
+    }
+
+    #[test]
+    fn rewrites_output_between_prompts_per_command() {
+        let content = "## Verification\n\n```bash\n$ echo a\na\n\n# next\n$ echo b\nb\n```\n";
+
+        let outcome = update(content, &[Some("a2\n"), None]);
+
+        assert_eq!(outcome.updated, 1);
+        assert_eq!(
+            outcome.content,
+            "## Verification\n\n```bash\n$ echo a\na2\n\n# next\n$ echo b\nb\n```\n"
+        );
+
+        let outcome = update(content, &[None, Some("b2\nb3")]);
+        assert_eq!(
+            outcome.content,
+            "## Verification\n\n```bash\n$ echo a\na\n\n# next\n$ echo b\nb2\nb3\n```\n"
+        );
+
+        // The last prompt's output can be in a pave:expect block instead
+        let content = "## Verification\n\n```bash\n$ echo a\na\n$ echo b\n```\n\
+                       <!-- pave:expect -->\n```\nb\n```\n";
+        let outcome = update(content, &[None, Some("b2")]);
+        assert_eq!(outcome.updated, 1);
+        assert!(
+            outcome
+                .content
+                .ends_with("<!-- pave:expect -->\n```\nb2\n```\n")
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Markdown file discovery shared by every command.
+//!
+//! Commands find documents by walking the paths they're given. The walk
+//! never descends into `.git`, `node_modules`, or `target`, and skips
+//! anything matched by, in increasing precedence, the `[docs] exclude`
+//! patterns in `.pave.toml`, the project's `.gitignore`, and its
+//! `.paveignore`:
+//!
+//! ```toml
+//! [docs]
+//! root = "docs"
+//! exclude = ["docs/archive/", "*.draft.md"]
+//! ```
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, trace};
+
+use crate::config::PaveConfig;
+use crate::ignore::PaveIgnore;
+use crate::include::INCLUDES_DIR;
+
+/// Directories no command looks inside.
+pub const SKIPPED_DIRS: [&str; 3] = [".git", "node_modules", "target"];
+
+/// Directories without hand-written docs, which `adopt` and `migrate` also
+/// skip since they scan trees pave doesn't manage yet.
+pub const NON_DOC_DIRS: [&str; 6] = [".github", "templates", "_site", ".pave", "vendor", "build"];
+
+/// Where `[docs] exclude` rules are reported as coming from.
+const EXCLUDE_SOURCE: &str = "[docs] exclude";
+
+/// Load the ignore rules for the project whose config is in `config_dir`.
+pub fn load_ignore(config_dir: &Path, config: &PaveConfig) -> Result<PaveIgnore> {
+    PaveIgnore::load(config_dir)?.with_excludes(&config.docs.exclude, EXCLUDE_SOURCE)
+}
+
+/// Find all markdown files in the given paths, sorted.
+///
+/// Files named directly are included unless ignored. A path that doesn't
+/// exist is an error; callers that tolerate missing paths filter first.
+pub fn find_markdown_files(paths: &[PathBuf], ignore: &PaveIgnore) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_file() {
+            if is_markdown(path) && !is_ignored(path, ignore) {
+                files.push(path.clone());
+            }
+        } else if path.is_dir() {
+            collect_markdown_files(path, ignore, &mut files)?;
+        } else {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+    }
+
+    // Sort for consistent output
+    files.sort();
+    Ok(files)
+}
+
+/// Recursively collect markdown files from a directory, in directory order.
+pub fn collect_markdown_files(
+    dir: &Path,
+    ignore: &PaveIgnore,
+    files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+    trace!("Walking {}", dir.display());
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if is_ignored(&path, ignore) {
+            continue;
+        }
+
+        if path.is_dir() {
+            if is_skipped_dir(&path) {
+                debug!("Skipping {}: never searched for docs", path.display());
+            } else {
+                collect_markdown_files(&path, ignore, files)?;
+            }
+        } else if is_markdown(&path) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Documents under `docs_root`, sorted, leaving out `index.md` navigation
+/// pages, templates, and include snippets. A missing docs root has none.
+pub fn find_docs(docs_root: &Path, ignore: &PaveIgnore) -> Result<Vec<PathBuf>> {
+    if !docs_root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    collect_markdown_files(docs_root, ignore, &mut files)?;
+    files.retain(|path| {
+        if path.file_name().is_some_and(|name| name == "index.md") {
+            info!("Skipping {}: index.md is a navigation page", path.display());
+            false
+        } else if under_dir_named(path, docs_root, &["templates"]) {
+            info!("Skipping {}: templates are not docs", path.display());
+            false
+        } else if under_dir_named(path, docs_root, &[INCLUDES_DIR]) {
+            info!("Skipping {}: include snippets are not docs", path.display());
+            false
+        } else {
+            true
+        }
+    });
+    files.sort();
+    Ok(files)
+}
+
+/// Whether `path` is inside a directory named in `names`, below `root`.
+pub fn under_dir_named(path: &Path, root: &Path, names: &[&str]) -> bool {
+    let Some(parent) = path.strip_prefix(root).ok().and_then(Path::parent) else {
+        return false;
+    };
+    parent
+        .components()
+        .any(|c| names.iter().any(|name| c.as_os_str() == *name))
+}
+
+/// Whether `ignore` skips `path`, logging the rule that does.
+fn is_ignored(path: &Path, ignore: &PaveIgnore) -> bool {
+    if !ignore.is_ignored(path) {
+        return false;
+    }
+    if let Some(rule) = ignore.matching_rule(path) {
+        info!(
+            "Skipping {}: ignored by `{}` ({}:{})",
+            path.display(),
+            rule.pattern,
+            rule.source,
+            rule.line
+        );
+    }
+    true
+}
+
+fn is_skipped_dir(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| SKIPPED_DIRS.iter().any(|skipped| name == *skipped))
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "# Doc\n").unwrap();
+    }
+
+    fn relative(root: &Path, files: &[PathBuf]) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| {
+                f.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skips_vendor_dirs_and_gitignored_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for path in [
+            "docs/a.md",
+            "docs/notes.txt",
+            "docs/generated/api.md",
+            "docs/node_modules/pkg/README.md",
+            "docs/target/doc.md",
+            ".git/info.md",
+            "README.md",
+        ] {
+            write(root, path);
+        }
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        let ignore = PaveIgnore::load(root).unwrap();
+
+        let files = find_markdown_files(&[root.to_path_buf()], &ignore).unwrap();
+        assert_eq!(relative(root, &files), vec!["README.md", "docs/a.md"]);
+
+        let err = find_markdown_files(&[root.join("missing")], &ignore).unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn docs_exclude_applies_before_ignore_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for path in ["docs/a.md", "docs/archive/old.md", "docs/archive/keep.md"] {
+            write(root, path);
+        }
+        write(root, "docs/b.draft.md");
+        fs::write(root.join(".paveignore"), "!keep.md\n").unwrap();
+
+        let mut config = PaveConfig::default();
+        config.docs.exclude = vec!["docs/archive/*".to_string(), "*.draft.md".to_string()];
+        let ignore = load_ignore(root, &config).unwrap();
+
+        let files = find_markdown_files(&[root.join("docs")], &ignore).unwrap();
+        assert_eq!(
+            relative(root, &files),
+            vec!["docs/a.md", "docs/archive/keep.md"]
+        );
+        let rule = ignore.matching_rule(&root.join("docs/b.draft.md")).unwrap();
+        assert_eq!((rule.source.as_str(), rule.line), ("[docs] exclude", 2));
+    }
+
+    #[test]
+    fn find_docs_leaves_out_index_and_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        for path in ["index.md", "b.md", "a/index.md", "a/c.md", "templates/t.md"] {
+            write(&docs, path);
+        }
+
+        let files = find_docs(&docs, &PaveIgnore::default()).unwrap();
+        assert_eq!(relative(&docs, &files), vec!["a/c.md", "b.md"]);
+        assert!(
+            find_docs(&docs.join("missing"), &PaveIgnore::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn under_dir_named_only_looks_below_root() {
+        let root = Path::new("/build/docs");
+        let names = ["templates", "build"];
+        assert!(under_dir_named(
+            Path::new("/build/docs/templates/a.md"),
+            root,
+            &names
+        ));
+        assert!(!under_dir_named(
+            Path::new("/build/docs/a.md"),
+            root,
+            &names
+        ));
+        assert!(!under_dir_named(Path::new("/build/a.md"), root, &names));
+    }
+}
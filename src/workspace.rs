@@ -0,0 +1,657 @@
+//! Config discovery and monorepo workspaces.
+//!
+//! Commands find their `.pave.toml` by walking up from the current
+//! directory, so the nearest config wins: inside a package with its own
+//! config, that config is used rather than the repository's.
+//!
+//! A root config can list member packages, each with its own docs root and
+//! rules:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["packages/*"]
+//! ```
+//!
+//! Run from the root, `check`, `lint`, `verify`, `status` and `coverage` run
+//! once per member, as if from the member's directory and with its config,
+//! and fail if any member fails. The root config runs too when its docs root
+//! exists. Paths named on the command line are resolved against the
+//! invocation directory and go to the member containing them; a path
+//! containing whole members runs those members as if none were named. JSON
+//! output is one document for the whole workspace, with paths prefixed by
+//! the member's directory. `--package` limits the run to some members and
+//! works from anywhere inside the workspace. Members' own `[workspace]`
+//! tables are not expanded.
+
+use anyhow::{Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::exit::{Violations, is_violations};
+use crate::paths::absolutize;
+
+/// A member package of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// Directory relative to the workspace root, with `/` separators.
+    pub name: String,
+    /// The package directory.
+    pub dir: PathBuf,
+    /// The package's config file.
+    pub config_path: PathBuf,
+}
+
+/// Where a command runs: the directory it treats as the current one, and
+/// the config file given with `--config`, if any.
+///
+/// Commands resolve relative paths against `dir` rather than the process's
+/// current directory, so `--root` and workspace members need no `chdir`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workdir {
+    /// Absolute directory relative paths are resolved against.
+    pub dir: PathBuf,
+    /// Config file to use instead of searching for one.
+    pub config: Option<PathBuf>,
+}
+
+impl Workdir {
+    /// Run in `dir`, resolved against the current directory, searching for
+    /// the config from there.
+    pub fn new(dir: &Path) -> Result<Self> {
+        let cwd = env::current_dir().context("Failed to get current directory")?;
+        Ok(Self {
+            dir: absolutize(dir, &cwd),
+            config: None,
+        })
+    }
+
+    /// Run in the current directory.
+    pub fn current() -> Result<Self> {
+        Self::new(Path::new("."))
+    }
+
+    /// Use `config` instead of searching for one.
+    pub fn with_config(self, config: PathBuf) -> Self {
+        Self {
+            config: Some(config),
+            ..self
+        }
+    }
+
+    /// `path` made absolute against [`Workdir::dir`].
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        absolutize(path, &self.dir)
+    }
+
+    /// Find the config file: the `--config` file, or the nearest
+    /// `.pave.toml` in the directory or its parents.
+    pub fn find_config(&self) -> Result<PathBuf> {
+        self.locate_config()?.with_context(|| {
+            format!(
+                "No {} found in {} or any parent directory",
+                CONFIG_FILENAME,
+                self.dir.display()
+            )
+        })
+    }
+
+    /// Like [`Workdir::find_config`], but `None` when there is no config.
+    pub fn locate_config(&self) -> Result<Option<PathBuf>> {
+        if let Some(path) = &self.config {
+            info!("Using config {} (--config)", path.display());
+            return Ok(Some(path.clone()));
+        }
+        let found = find_config_from(&self.dir);
+        match &found {
+            Some(path) => info!("Using config {}", path.display()),
+            None => info!(
+                "No {} in {} or its parents",
+                CONFIG_FILENAME,
+                self.dir.display()
+            ),
+        }
+        Ok(found)
+    }
+}
+
+/// Nearest `.pave.toml` in `start` or its parents.
+pub fn find_config_from(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILENAME))
+        .find(|path| path.is_file())
+}
+
+/// Member packages listed by the config at `config_path`, sorted by name.
+///
+/// Directories matching a `members` pattern without a `.pave.toml` are not
+/// members. A pattern that matches no member is an error.
+pub fn members(config_path: &Path) -> Result<Vec<Package>> {
+    let config = PaveConfig::load(config_path)?;
+    let root = config_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut packages = Vec::new();
+    for pattern in &config.workspace.members {
+        let full = root.join(pattern);
+        let matches = glob::glob(&full.to_string_lossy())
+            .with_context(|| format!("Invalid workspace member pattern: {}", pattern))?;
+        let before = packages.len();
+        for dir in matches.filter_map(Result::ok) {
+            let member_config = dir.join(CONFIG_FILENAME);
+            if !member_config.is_file() {
+                continue;
+            }
+            let name = package_name(root, &dir);
+            if packages.iter().any(|p: &Package| p.name == name) {
+                continue;
+            }
+            packages.push(Package {
+                name,
+                dir,
+                config_path: member_config,
+            });
+        }
+        if packages.len() == before {
+            anyhow::bail!(
+                "Workspace member pattern '{}' matches no directory with a {}",
+                pattern,
+                CONFIG_FILENAME
+            );
+        }
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+/// Name of a package directory relative to the workspace root.
+fn package_name(root: &Path, dir: &Path) -> String {
+    let relative = dir.strip_prefix(root).unwrap_or(dir);
+    let name = relative
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if name.is_empty() {
+        ".".to_string()
+    } else {
+        name
+    }
+}
+
+/// Pick the packages named by `--package`.
+///
+/// A package matches by its full name or its directory name.
+pub fn select_packages(packages: Vec<Package>, names: &[String]) -> Result<Vec<Package>> {
+    if names.is_empty() {
+        return Ok(packages);
+    }
+    for name in names {
+        if !packages.iter().any(|p| package_matches(p, name)) {
+            let known: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+            anyhow::bail!(
+                "Unknown package '{}' (workspace members: {})",
+                name,
+                known.join(", ")
+            );
+        }
+    }
+    Ok(packages
+        .into_iter()
+        .filter(|p| names.iter().any(|name| package_matches(p, name)))
+        .collect())
+}
+
+fn package_matches(package: &Package, name: &str) -> bool {
+    let name = name.trim_end_matches('/');
+    package.name == name || package.name.rsplit('/').next() == Some(name)
+}
+
+/// Nearest config in `start` or its parents that lists workspace members.
+fn find_workspace_root(start: &Path) -> Result<Option<PathBuf>> {
+    for dir in start.ancestors() {
+        let config_path = dir.join(CONFIG_FILENAME);
+        if config_path.is_file() && !PaveConfig::load(&config_path)?.workspace.is_empty() {
+            return Ok(Some(config_path));
+        }
+    }
+    Ok(None)
+}
+
+/// Results that can be combined across workspace members into one report.
+pub trait Merge {
+    /// Prefix the relative paths in the results, which are relative to a
+    /// member, with the member's `package` directory.
+    fn prefix_paths(&mut self, package: &Path);
+
+    /// Add another member's results.
+    fn merge(&mut self, other: Self);
+}
+
+/// Results of running a command once, and whether the command passed.
+pub struct Outcome<T> {
+    /// What the command found.
+    pub results: T,
+    /// `Err` with [`Violations`] when the results are a failure.
+    pub status: Result<()>,
+}
+
+/// A command that runs once per workspace member.
+pub trait PackageCommand {
+    /// Results of one run.
+    type Results: Merge;
+
+    /// Whether the output is a JSON document, which is printed once for the
+    /// whole workspace instead of once per member.
+    fn is_json(&self) -> bool;
+
+    /// Paths named on the command line, relative to the invocation
+    /// directory. Empty runs on the config's defaults.
+    fn paths(&self) -> &[PathBuf];
+
+    /// The same command run on `paths` instead.
+    fn with_paths(&self, paths: Vec<PathBuf>) -> Self;
+
+    /// Run in `workdir`. `None` when there was nothing to report.
+    fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<Self::Results>>>;
+
+    /// Print the results.
+    fn output(&self, results: Self::Results) -> Result<()>;
+}
+
+/// Run `command` once in `workdir` and print its results.
+pub fn run_once<C: PackageCommand>(command: &C, workdir: &Workdir) -> Result<()> {
+    let Some(outcome) = command.run(workdir)? else {
+        return Ok(());
+    };
+    command.output(outcome.results)?;
+    outcome.status
+}
+
+/// Run a command once per selected workspace member, in the member's
+/// directory and with its config.
+///
+/// Without `--package` names, the command fans out only when the config in
+/// use is a workspace root; otherwise it runs once in `workdir`. With names,
+/// the nearest workspace root is used even from inside a member. The root
+/// config also runs, without names, when its docs root exists or it owns a
+/// named path. Named paths are split between the runs by [`route_paths`].
+/// JSON results are merged, with paths prefixed by the member's directory,
+/// and printed as one document.
+pub fn for_each_package<C: PackageCommand>(
+    workdir: &Workdir,
+    names: &[String],
+    command: &C,
+) -> Result<()> {
+    let root = if names.is_empty() {
+        // Not `locate_config`, which would log the config a second time.
+        workdir
+            .config
+            .clone()
+            .or_else(|| find_config_from(&workdir.dir))
+            .filter(|path| PaveConfig::load(path).is_ok_and(|c| !c.workspace.is_empty()))
+    } else {
+        let root = match &workdir.config {
+            Some(path) => Some(path.clone()),
+            None => find_workspace_root(&workdir.dir)?,
+        };
+        Some(root.with_context(|| {
+            format!(
+                "--package needs a {} with [workspace] members",
+                CONFIG_FILENAME
+            )
+        })?)
+    };
+    let Some(root) = root else {
+        return run_once(command, workdir);
+    };
+
+    let root = workdir.resolve(&root);
+    let mut packages = select_packages(members(&root)?, names)?;
+    let root_dir = root.parent().unwrap_or_else(|| Path::new("/"));
+    let root_package = Package {
+        name: ".".to_string(),
+        dir: root_dir.to_path_buf(),
+        config_path: root.clone(),
+    };
+    if names.is_empty() {
+        packages.insert(0, root_package);
+    }
+    let paths: Vec<PathBuf> = command
+        .paths()
+        .iter()
+        .map(|path| workdir.resolve(path))
+        .collect();
+    let routed = route_paths(&packages, &paths);
+
+    let mut runs = Vec::new();
+    for (package, selection) in packages.iter().zip(routed) {
+        let selection = match selection {
+            Some(selection) => selection,
+            None if paths.is_empty() => Vec::new(),
+            None => continue,
+        };
+        // The root usually only lists members; run its own docs if it has any
+        if package.name == "." && selection.is_empty() {
+            let config = PaveConfig::load(&package.config_path)?;
+            if !package.dir.join(&config.docs.root).is_dir() {
+                continue;
+            }
+        }
+        runs.push((package, command.with_paths(selection)));
+    }
+
+    let mut merged: Option<C::Results> = None;
+    let mut failed = Vec::new();
+    let mut tool_error = false;
+    for (package, run) in &runs {
+        eprintln!("==> {}", package.name);
+        let member = Workdir {
+            dir: package.dir.clone(),
+            config: Some(package.config_path.clone()),
+        };
+        let result = if command.is_json() {
+            run.run(&member).and_then(|outcome| {
+                let Some(mut outcome) = outcome else {
+                    return Ok(());
+                };
+                if package.name != "." {
+                    outcome.results.prefix_paths(Path::new(&package.name));
+                }
+                match &mut merged {
+                    Some(merged) => merged.merge(outcome.results),
+                    None => merged = Some(outcome.results),
+                }
+                outcome.status
+            })
+        } else {
+            run_once(run, &member)
+        };
+        if let Err(err) = result {
+            eprintln!("error: {:#}", err);
+            tool_error |= !is_violations(&err);
+            failed.push(package.name.as_str());
+        }
+    }
+    if let Some(merged) = merged {
+        command.output(merged)?;
+    }
+
+    if !failed.is_empty() {
+        let message = format!(
+            "{} of {} packages failed: {}",
+            failed.len(),
+            runs.len(),
+            failed.join(", ")
+        );
+        // Problems in every failed package still mean "violations found"
+        if tool_error {
+            anyhow::bail!(message);
+        }
+        anyhow::bail!(Violations(message));
+    }
+    Ok(())
+}
+
+/// Split absolute `paths` between `packages`, in order.
+///
+/// A path containing a package's directory runs that package on its
+/// defaults, `Some` with no paths. Any other path goes to the package with
+/// the deepest directory containing it. `None` for packages no path selects.
+fn route_paths(packages: &[Package], paths: &[PathBuf]) -> Vec<Option<Vec<PathBuf>>> {
+    let mut routed: Vec<Option<Vec<PathBuf>>> = vec![None; packages.len()];
+    let mut whole = vec![false; packages.len()];
+    for path in paths {
+        let contained: Vec<usize> = (0..packages.len())
+            .filter(|&i| packages[i].dir.starts_with(path))
+            .collect();
+        if !contained.is_empty() {
+            for i in contained {
+                whole[i] = true;
+            }
+            continue;
+        }
+        let owner = (0..packages.len())
+            .filter(|&i| path.starts_with(&packages[i].dir))
+            .max_by_key(|&i| packages[i].dir.components().count());
+        if let Some(i) = owner {
+            routed[i].get_or_insert_with(Vec::new).push(path.clone());
+        }
+    }
+    for (selection, whole) in routed.iter_mut().zip(whole) {
+        if whole {
+            *selection = Some(Vec::new());
+        }
+    }
+    routed
+}
+
+/// `path`, relative to a member, made relative to the workspace root by
+/// prefixing the member's `package` directory. Absolute paths are kept.
+pub fn prefixed(package: &Path, path: &Path) -> PathBuf {
+    if path.is_relative() {
+        package.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n";
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join(CONFIG_FILENAME),
+            format!(
+                "{}\n[workspace]\nmembers = [\"packages/*\", \"tools/cli\"]\n",
+                CONFIG
+            ),
+        )
+        .unwrap();
+        for dir in [
+            "packages/api",
+            "packages/web",
+            "packages/scratch",
+            "tools/cli",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for dir in ["packages/api", "packages/web", "tools/cli"] {
+            fs::write(root.join(dir).join(CONFIG_FILENAME), CONFIG).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn expands_members_with_their_own_config() {
+        let temp_dir = setup();
+        let packages = members(&temp_dir.path().join(CONFIG_FILENAME)).unwrap();
+
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["packages/api", "packages/web", "tools/cli"]);
+        assert_eq!(
+            packages[0].config_path,
+            temp_dir.path().join("packages/api").join(CONFIG_FILENAME)
+        );
+
+        let selected = select_packages(
+            packages.clone(),
+            &["cli".to_string(), "packages/api".to_string()],
+        )
+        .unwrap();
+        let names: Vec<&str> = selected.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["packages/api", "tools/cli"]);
+
+        let err = select_packages(packages, &["nope".to_string()]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("packages/api, packages/web, tools/cli")
+        );
+    }
+
+    #[test]
+    fn nearest_config_wins_and_workspace_root_is_found_from_members() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        let nested = root.join("packages/api/src");
+        fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(
+            find_config_from(&nested),
+            Some(root.join("packages/api").join(CONFIG_FILENAME))
+        );
+        assert_eq!(
+            find_workspace_root(&nested).unwrap(),
+            Some(root.join(CONFIG_FILENAME))
+        );
+        assert_eq!(
+            find_config_from(&root.join("packages/scratch")),
+            Some(root.join(CONFIG_FILENAME))
+        );
+    }
+
+    #[test]
+    fn pattern_without_members_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &config_path,
+            format!("{}\n[workspace]\nmembers = [\"libs/*\"]\n", CONFIG),
+        )
+        .unwrap();
+
+        let err = members(&config_path).unwrap_err();
+        assert!(err.to_string().contains("libs/*"));
+    }
+
+    /// Reports the member's directory name as a file, or the paths it was
+    /// given relative to the member, failing in `web`.
+    #[derive(Clone, Default)]
+    struct ListDir {
+        paths: Vec<PathBuf>,
+        printed: std::cell::RefCell<Vec<Vec<PathBuf>>>,
+    }
+
+    impl Merge for Vec<PathBuf> {
+        fn prefix_paths(&mut self, package: &Path) {
+            for path in self.iter_mut() {
+                *path = prefixed(package, path);
+            }
+        }
+
+        fn merge(&mut self, other: Self) {
+            self.extend(other);
+        }
+    }
+
+    impl PackageCommand for ListDir {
+        type Results = Vec<PathBuf>;
+
+        fn is_json(&self) -> bool {
+            true
+        }
+
+        fn paths(&self) -> &[PathBuf] {
+            &self.paths
+        }
+
+        fn with_paths(&self, paths: Vec<PathBuf>) -> Self {
+            Self {
+                paths,
+                ..self.clone()
+            }
+        }
+
+        fn run(&self, workdir: &Workdir) -> Result<Option<Outcome<Vec<PathBuf>>>> {
+            let name = workdir.dir.file_name().unwrap();
+            let status = if name == "web" {
+                Err(Violations("web failed".to_string()).into())
+            } else {
+                Ok(())
+            };
+            let results = if self.paths.is_empty() {
+                vec![PathBuf::from(name).join("doc.md")]
+            } else {
+                self.paths
+                    .iter()
+                    .map(|path| workdir.resolve(path))
+                    .map(|path| path.strip_prefix(&workdir.dir).unwrap().to_path_buf())
+                    .collect()
+            };
+            Ok(Some(Outcome { results, status }))
+        }
+
+        fn output(&self, results: Vec<PathBuf>) -> Result<()> {
+            self.printed.borrow_mut().push(results);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_output_is_merged_across_members() {
+        let temp_dir = setup();
+        let command = ListDir::default();
+
+        let err =
+            for_each_package(&Workdir::new(temp_dir.path()).unwrap(), &[], &command).unwrap_err();
+        assert!(is_violations(&err));
+        assert_eq!(err.to_string(), "1 of 3 packages failed: packages/web");
+        assert_eq!(
+            command.printed.into_inner(),
+            [vec![
+                PathBuf::from("packages/api/api/doc.md"),
+                PathBuf::from("packages/web/web/doc.md"),
+                PathBuf::from("tools/cli/cli/doc.md"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn named_paths_go_to_the_member_containing_them() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        let command = ListDir {
+            paths: vec![
+                PathBuf::from("packages/api/docs/d.md"),
+                PathBuf::from("docs/root.md"),
+                PathBuf::from("tools"),
+            ],
+            ..Default::default()
+        };
+
+        for_each_package(&Workdir::new(root).unwrap(), &[], &command).unwrap();
+        assert_eq!(
+            command.printed.into_inner(),
+            [vec![
+                PathBuf::from("docs/root.md"),
+                PathBuf::from("packages/api/docs/d.md"),
+                PathBuf::from("tools/cli/cli/doc.md"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn root_docs_run_alongside_members() {
+        let temp_dir = setup();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        let command = ListDir::default();
+
+        let err = for_each_package(&Workdir::new(root).unwrap(), &[], &command).unwrap_err();
+        assert_eq!(err.to_string(), "1 of 4 packages failed: packages/web");
+        let printed = command.printed.into_inner();
+        let root_name = root.file_name().unwrap();
+        assert_eq!(printed[0][0], PathBuf::from(root_name).join("doc.md"));
+        assert_eq!(printed[0].len(), 4);
+    }
+}